@@ -0,0 +1,35 @@
+-- Upcoming episode air-time notifications
+-- This migration adds support for:
+-- 1. Per-show lead-time overrides ("notify me N hours before this show airs")
+-- 2. A sent ledger so each airing is only announced once per user
+--
+-- The global lead time and digest threshold live in app_settings under the
+-- "notifications" category (notifications.episode_air_lead_hours,
+-- notifications.episode_air_digest_threshold).
+
+-- ============================================================================
+-- Per-show Lead-time Overrides
+-- ============================================================================
+-- lead_hours = 0 disables air notifications for the show; no row = inherit global.
+
+CREATE TABLE IF NOT EXISTS episode_air_notification_overrides (
+    tv_show_id TEXT PRIMARY KEY REFERENCES tv_shows(id) ON DELETE CASCADE,
+    lead_hours INTEGER NOT NULL CHECK (lead_hours >= 0),
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- ============================================================================
+-- Sent Ledger
+-- ============================================================================
+
+CREATE TABLE IF NOT EXISTS episode_air_notifications_sent (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    tvmaze_episode_id INTEGER NOT NULL,
+    air_stamp TEXT,
+    sent_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(user_id, tvmaze_episode_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_air_notifications_sent_at ON episode_air_notifications_sent(sent_at);
@@ -0,0 +1,7 @@
+-- Artwork blurhash placeholders
+-- Stores a compact blurhash string for each cached artwork image so the
+-- frontend can render instant placeholders in poster grids before the
+-- image itself loads. Computed when artwork is cached; existing rows are
+-- backfilled by the artwork blurhash job.
+
+ALTER TABLE artwork_cache ADD COLUMN blurhash TEXT;
@@ -0,0 +1,46 @@
+-- Google Cast speaker groups
+-- This migration adds support for:
+-- 1. Speaker groups, which are advertised on the address of whichever member
+--    currently leads the group, using their own port. A group therefore shares
+--    an address with one of its speakers, so devices become unique per
+--    (address, port) instead of per address.
+--
+-- SQLite cannot drop an inline UNIQUE constraint, so cast_devices is rebuilt.
+-- Dropping the old table nulls cast_sessions.device_id (ON DELETE SET NULL),
+-- so session links are saved first and restored afterwards.
+
+CREATE TEMP TABLE cast_session_devices AS
+SELECT id, device_id FROM cast_sessions WHERE device_id IS NOT NULL;
+
+CREATE TABLE cast_devices_new (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    address TEXT NOT NULL,
+    port INTEGER NOT NULL DEFAULT 8009,
+    model TEXT,
+    device_type TEXT NOT NULL DEFAULT 'chromecast',
+    is_favorite INTEGER NOT NULL DEFAULT 0,
+    is_manual INTEGER NOT NULL DEFAULT 0,
+    last_seen_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    UNIQUE(address, port)
+);
+
+INSERT INTO cast_devices_new (id, name, address, port, model, device_type, is_favorite,
+                              is_manual, last_seen_at, created_at, updated_at)
+SELECT id, name, address, port, model, device_type, is_favorite,
+       is_manual, last_seen_at, created_at, updated_at
+FROM cast_devices;
+
+DROP TABLE cast_devices;
+ALTER TABLE cast_devices_new RENAME TO cast_devices;
+
+CREATE INDEX idx_cast_devices_address ON cast_devices(address);
+CREATE INDEX idx_cast_devices_group_name ON cast_devices(name) WHERE device_type = 'speaker_group';
+
+UPDATE cast_sessions
+SET device_id = (SELECT device_id FROM cast_session_devices WHERE cast_session_devices.id = cast_sessions.id)
+WHERE id IN (SELECT id FROM cast_session_devices);
+
+DROP TABLE cast_session_devices;
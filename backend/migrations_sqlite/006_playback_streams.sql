@@ -0,0 +1,27 @@
+-- Playback stream leases
+-- A playback session holds a user's resumable player state (one per user).
+-- Each client actually streaming gets a lease here, kept alive by heartbeats:
+-- 1. Leases whose client stops heartbeating (crashed tab, lost network) are
+--    expired by the playback stream job, so they stop counting as active
+-- 2. Active leases are counted against the per-user concurrent stream limit
+--    (app_settings: playback_max_concurrent_streams, 0 = unlimited)
+-- 3. Admins can list active leases and stop one; the client learns about it on
+--    its next heartbeat
+
+CREATE TABLE IF NOT EXISTS playback_streams (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL,
+    playback_session_id TEXT REFERENCES playback_sessions(id) ON DELETE SET NULL,
+    media_file_id TEXT REFERENCES media_files(id) ON DELETE SET NULL,
+    content_type TEXT,
+    client_name TEXT,
+    started_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_heartbeat_at TEXT NOT NULL DEFAULT (datetime('now')),
+    ended_at TEXT,
+    -- 'stopped' (client), 'expired' (missed heartbeats), 'terminated' (admin)
+    end_reason TEXT
+);
+
+CREATE INDEX idx_playback_streams_user_active ON playback_streams(user_id) WHERE ended_at IS NULL;
+CREATE INDEX idx_playback_streams_heartbeat ON playback_streams(last_heartbeat_at) WHERE ended_at IS NULL;
+CREATE INDEX idx_playback_streams_ended ON playback_streams(ended_at);
@@ -0,0 +1,7 @@
+-- Library scanner exclusion rules
+-- JSON array of glob patterns matched against paths relative to the library
+-- root (e.g. "**/Extras/**", "*.sample.*"). A bare name such as "@eaDir"
+-- excludes any file or folder with that name. NAS system folders and
+-- dot-folders are always skipped and need no pattern.
+
+ALTER TABLE libraries ADD COLUMN exclude_patterns TEXT NOT NULL DEFAULT '[]';
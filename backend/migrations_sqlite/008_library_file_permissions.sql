@@ -0,0 +1,8 @@
+-- Per-library ownership and permissions for imported files
+-- Owner uid/gid and octal file mode (e.g. "664") applied to files copied into
+-- the library and the folders created for them. NULL inherits the global
+-- import.file_uid / import.file_gid / import.file_mode settings.
+
+ALTER TABLE libraries ADD COLUMN file_uid INTEGER;
+ALTER TABLE libraries ADD COLUMN file_gid INTEGER;
+ALTER TABLE libraries ADD COLUMN file_mode TEXT;
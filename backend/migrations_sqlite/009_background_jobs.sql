@@ -0,0 +1,33 @@
+-- Background jobs
+-- Heavy mutations (library consolidation, bulk renames...) would otherwise run
+-- inline and outlive the client's request timeout. Instead they enqueue a job
+-- here and return its ID straight away:
+-- 1. The job runs in the background, updating status/progress as it goes
+-- 2. Its result (JSON) or error is stored when it finishes, so clients can
+--    fetch it later through the job API or follow it by subscription
+-- 3. Jobs still queued or running when the server stops are marked
+--    'interrupted' on the next startup
+
+CREATE TABLE IF NOT EXISTS background_jobs (
+    id TEXT PRIMARY KEY,
+    -- What the job does, e.g. 'consolidate_library'
+    kind TEXT NOT NULL,
+    -- 'queued', 'running', 'completed', 'failed', 'interrupted'
+    status TEXT NOT NULL DEFAULT 'queued',
+    -- 0.0 - 1.0, when the job reports it
+    progress REAL,
+    message TEXT,
+    -- Input the job was started with (JSON)
+    payload TEXT,
+    -- Output of a completed job (JSON)
+    result TEXT,
+    error TEXT,
+    user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    started_at TEXT,
+    finished_at TEXT
+);
+
+CREATE INDEX idx_background_jobs_user ON background_jobs(user_id, created_at);
+CREATE INDEX idx_background_jobs_status ON background_jobs(status);
+CREATE INDEX idx_background_jobs_finished ON background_jobs(finished_at);
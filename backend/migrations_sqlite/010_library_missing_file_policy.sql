@@ -0,0 +1,9 @@
+-- What a library scan does when files disappear from disk
+--   'flag'      keep the records and notify (items are not re-downloaded)
+--   'unmonitor' remove the records and stop monitoring the movie or show
+--               (monitoring is per show, so this covers the whole show)
+--   'rewant'    remove the records so monitored items become wanted again
+--               and auto-hunt can re-download them
+
+ALTER TABLE libraries ADD COLUMN missing_file_policy TEXT NOT NULL DEFAULT 'flag'
+    CHECK (missing_file_policy IN ('flag', 'unmonitor', 'rewant'));
@@ -0,0 +1,17 @@
+-- Tracker statistics
+-- Private trackers enforce a minimum share ratio per account. librqbit's
+-- upload counters only cover the current session, so the torrent sync loop
+-- adds what each torrent transferred since its last sync to the indexer the
+-- torrent was grabbed from (torrents.source_indexer_id). The totals here
+-- therefore only cover traffic seen by Librarian, not the whole account.
+
+CREATE TABLE IF NOT EXISTS indexer_tracker_stats (
+    indexer_id TEXT PRIMARY KEY REFERENCES indexer_configs(id) ON DELETE CASCADE,
+    uploaded_bytes INTEGER NOT NULL DEFAULT 0,
+    downloaded_bytes INTEGER NOT NULL DEFAULT 0,
+    -- Ratio the tracker requires; NULL means no warnings
+    min_ratio REAL,
+    -- Set once a low-ratio warning has been sent, cleared when the ratio recovers
+    ratio_warned INTEGER NOT NULL DEFAULT 0,
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
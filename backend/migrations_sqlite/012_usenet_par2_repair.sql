@@ -0,0 +1,15 @@
+-- PAR2 verification results for usenet downloads
+-- Recorded after all articles are assembled and before the download is
+-- marked completed:
+--   'verified'  files were intact
+--   'repaired'  damaged or missing blocks were rebuilt from recovery volumes
+--   'failed'    too few recovery blocks; the download is marked failed
+--   'no_parity' the post had no PAR2 files
+--   'skipped'   par2 isn't installed
+
+ALTER TABLE usenet_downloads ADD COLUMN repair_status TEXT;
+-- Data blocks that were damaged or missing
+ALTER TABLE usenet_downloads ADD COLUMN repair_blocks_needed INTEGER;
+-- Recovery blocks that were available to repair them
+ALTER TABLE usenet_downloads ADD COLUMN repair_recovery_blocks INTEGER;
+ALTER TABLE usenet_downloads ADD COLUMN repair_duration_ms INTEGER;
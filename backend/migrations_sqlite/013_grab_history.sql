@@ -0,0 +1,32 @@
+-- Grab ledger
+-- Every release handed to a download client is recorded here, keyed by info
+-- hash, indexer GUID (or NZB URL) and normalized release name, so the same
+-- release isn't grabbed twice by different pipelines (e.g. auto-hunt racing
+-- a manual grab of the same search result). A grab is claimed before the
+-- download starts, in a single INSERT ... WHERE NOT EXISTS, and the claim is
+-- dropped again if the download client rejects it. Manual grabs can set
+-- `forced` to grab a release again regardless.
+
+CREATE TABLE IF NOT EXISTS grab_history (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- 'auto_hunt' or 'manual'
+    source TEXT NOT NULL,
+    -- 'torrent' or 'usenet'
+    download_type TEXT NOT NULL DEFAULT 'torrent',
+    release_title TEXT,
+    -- Lowercased alphanumeric tokens of the title, joined with '.'
+    normalized_title TEXT,
+    -- Lowercase hex; set after the grab when only a .torrent URL was known
+    info_hash TEXT,
+    -- Indexer GUID, or the NZB URL for manual usenet grabs
+    release_guid TEXT,
+    indexer_id TEXT REFERENCES indexer_configs(id) ON DELETE SET NULL,
+    forced INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_grab_history_user ON grab_history(user_id, created_at);
+CREATE INDEX idx_grab_history_info_hash ON grab_history(user_id, info_hash);
+CREATE INDEX idx_grab_history_guid ON grab_history(user_id, release_guid);
+CREATE INDEX idx_grab_history_title ON grab_history(user_id, normalized_title);
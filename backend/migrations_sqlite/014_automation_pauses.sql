@@ -0,0 +1,18 @@
+-- Automation pauses ("vacation mode")
+-- While a pause is active, auto-hunt (scheduled, post-scan and on add) and
+-- RSS polling skip the affected libraries; scans, metadata refresh and
+-- manual hunts keep running. A row with no library_id pauses all of the
+-- user's libraries. A pause with resume_at ends on its own once that time
+-- has passed; without one it lasts until it is removed.
+
+CREATE TABLE IF NOT EXISTS automation_pauses (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    library_id TEXT REFERENCES libraries(id) ON DELETE CASCADE,
+    resume_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+-- One pause per library, and one global pause, per user
+CREATE UNIQUE INDEX idx_automation_pauses_scope
+    ON automation_pauses(user_id, COALESCE(library_id, ''));
@@ -0,0 +1,33 @@
+-- Language profiles
+-- A profile lists the audio languages a user prefers (in order), the audio
+-- languages a release must have, and the subtitle languages every imported
+-- file should end up with. Profiles are assigned to libraries, and a show's
+-- assignment overrides its library's. Auto-hunt and RSS rank and reject
+-- releases with the audio rules; media analysis checks imported files
+-- against both and queues subtitle downloads for missing languages.
+-- Languages are stored as ISO 639-1 codes in JSON arrays.
+
+CREATE TABLE IF NOT EXISTS language_profiles (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    preferred_audio_languages TEXT NOT NULL DEFAULT '[]',
+    required_audio_languages TEXT NOT NULL DEFAULT '[]',
+    required_subtitle_languages TEXT NOT NULL DEFAULT '[]',
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE UNIQUE INDEX idx_language_profiles_user_name ON language_profiles(user_id, name);
+
+CREATE TRIGGER IF NOT EXISTS update_language_profiles_updated_at
+AFTER UPDATE ON language_profiles
+FOR EACH ROW
+BEGIN
+    UPDATE language_profiles SET updated_at = datetime('now') WHERE id = NEW.id;
+END;
+
+ALTER TABLE libraries ADD COLUMN language_profile_id TEXT
+    REFERENCES language_profiles(id) ON DELETE SET NULL;
+ALTER TABLE tv_shows ADD COLUMN language_profile_id TEXT
+    REFERENCES language_profiles(id) ON DELETE SET NULL;
@@ -0,0 +1,7 @@
+-- Tracker passkey errors
+-- Private trackers embed a per-user passkey in announce and download URLs.
+-- When the tracker rotates it, every torrent grabbed with the old passkey
+-- starts failing. The first failure that looks like a rejected passkey is
+-- recorded here so the user is prompted once; updating the passkey clears it.
+
+ALTER TABLE indexer_tracker_stats ADD COLUMN passkey_error TEXT;
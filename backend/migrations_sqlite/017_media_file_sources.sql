@@ -0,0 +1,9 @@
+-- Media file download sources
+-- Records which pending match (and so which torrent or usenet download)
+-- produced an imported media file. Torrent files were already linked via
+-- torrent_files.media_file_id; this covers usenet and every other source,
+-- so the UI can warn before deleting a file that is still being seeded.
+
+ALTER TABLE pending_file_matches ADD COLUMN media_file_id TEXT REFERENCES media_files(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_pending_file_matches_media_file ON pending_file_matches(media_file_id);
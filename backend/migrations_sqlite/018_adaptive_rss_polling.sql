@@ -0,0 +1,13 @@
+-- Adaptive RSS polling
+-- Feeds used to be polled by a fixed 15-minute job. The poller now checks
+-- every minute and each feed's interval adapts to how often new items
+-- arrive: busy feeds are polled faster and quiet ones back off, between
+-- the feed's min and max. poll_interval_minutes stays the starting point;
+-- current_poll_interval_minutes is the adapted value (NULL until the first
+-- successful poll). ttl_minutes is the feed's own <ttl>, which the interval
+-- never goes below.
+
+ALTER TABLE rss_feeds ADD COLUMN min_poll_interval_minutes INTEGER NOT NULL DEFAULT 5;
+ALTER TABLE rss_feeds ADD COLUMN max_poll_interval_minutes INTEGER NOT NULL DEFAULT 120;
+ALTER TABLE rss_feeds ADD COLUMN current_poll_interval_minutes INTEGER;
+ALTER TABLE rss_feeds ADD COLUMN ttl_minutes INTEGER;
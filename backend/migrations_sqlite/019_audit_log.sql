@@ -0,0 +1,21 @@
+-- Audit log
+-- Who changed what and when: create, update and delete mutations record an
+-- entry here through the AuditService. `changes` is a JSON object of the
+-- fields that changed, each with its "from" and/or "to" value (creates only
+-- have "to", deletes only "from"). Secrets are stored as "[redacted]".
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id TEXT PRIMARY KEY,
+    -- Who made the change (kept when the user is deleted, as NULL)
+    user_id TEXT REFERENCES users(id) ON DELETE SET NULL,
+    -- e.g. 'library', 'rss_feed'
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    -- 'create', 'update' or 'delete'
+    action TEXT NOT NULL,
+    changes TEXT NOT NULL DEFAULT '{}',
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_audit_log_entity ON audit_log(entity_type, entity_id, created_at);
+CREATE INDEX idx_audit_log_user ON audit_log(user_id, created_at);
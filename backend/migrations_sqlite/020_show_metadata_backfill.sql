@@ -0,0 +1,15 @@
+-- Show metadata backfill
+-- Adding a show no longer fails when TMDB or TheTVDB time out, or when the
+-- episode list or artwork can't be fetched. The show is created with what
+-- was fetched and the missing parts are listed in missing_metadata (a JSON
+-- array of "tmdb", "tvdb", "episodes", "artwork"). The metadata backfill
+-- job retries them at metadata_retry_at, backing off on each attempt;
+-- metadata_retry_at is NULL once it gives up or nothing is missing.
+
+ALTER TABLE tv_shows ADD COLUMN missing_metadata TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE tv_shows ADD COLUMN metadata_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tv_shows ADD COLUMN metadata_retry_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tv_shows_metadata_retry
+    ON tv_shows(metadata_retry_at)
+    WHERE metadata_retry_at IS NOT NULL;
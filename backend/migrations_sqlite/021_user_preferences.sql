@@ -0,0 +1,18 @@
+-- Per-user UI preferences
+-- Stored server-side so they follow the user across devices. A user without
+-- a row gets the defaults below; the row is created on the first update.
+
+CREATE TABLE IF NOT EXISTS user_preferences (
+    user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    theme TEXT NOT NULL DEFAULT 'system' CHECK (theme IN ('light', 'dark', 'system')),
+    -- Library opened by default (cleared when the library is deleted)
+    default_library_id TEXT REFERENCES libraries(id) ON DELETE SET NULL,
+    grid_density TEXT NOT NULL DEFAULT 'comfortable'
+        CHECK (grid_density IN ('compact', 'comfortable', 'spacious')),
+    -- BCP 47 language tag for the UI
+    language TEXT NOT NULL DEFAULT 'en',
+    notifications_enabled INTEGER NOT NULL DEFAULT 1,
+    -- JSON array of notification categories the user doesn't want toasts for
+    muted_notification_categories TEXT NOT NULL DEFAULT '[]',
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
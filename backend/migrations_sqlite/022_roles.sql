@@ -0,0 +1,33 @@
+-- Roles and permissions
+-- A role is a named set of permissions. The built-in roles match the values
+-- users.role has always allowed and can't be edited or deleted; admins can
+-- add custom roles with any subset of the permissions.
+--
+-- users.role is constrained to the built-in names and SQLite can't drop an
+-- inline CHECK without rebuilding users (which would cascade into every
+-- table that references it), so a custom role is assigned through
+-- users.custom_role instead. A user's effective role is custom_role when
+-- set, otherwise role; deleting a custom role drops its users back to their
+-- built-in role.
+
+CREATE TABLE IF NOT EXISTS roles (
+    name TEXT PRIMARY KEY COLLATE NOCASE,
+    description TEXT,
+    -- JSON array of permissions: 'manage_libraries', 'manage_downloads',
+    -- 'manage_users', 'manage_server'. An empty array is view-only.
+    permissions TEXT NOT NULL DEFAULT '[]',
+    is_builtin INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+INSERT OR IGNORE INTO roles (name, description, permissions, is_builtin) VALUES
+    ('admin', 'Full access',
+     '["manage_libraries","manage_downloads","manage_users","manage_server"]', 1),
+    ('member', 'Manage libraries and downloads',
+     '["manage_libraries","manage_downloads"]', 1),
+    ('guest', 'View-only', '[]', 1);
+
+ALTER TABLE users ADD COLUMN custom_role TEXT REFERENCES roles(name) ON DELETE SET NULL;
+
+CREATE INDEX idx_users_custom_role ON users(custom_role) WHERE custom_role IS NOT NULL;
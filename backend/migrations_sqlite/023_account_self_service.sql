@@ -0,0 +1,55 @@
+-- Account self-service
+-- This migration adds support for:
+-- 1. Changing your own email address. The new address has to be confirmed
+--    with a single-use token mailed to it before users.email changes. Only
+--    a SHA-256 hash of the token is stored, like refresh tokens.
+-- 2. Uploaded avatars, stored in artwork_cache as entity 'user' with
+--    artwork type 'avatars'.
+--
+-- SQLite cannot change an inline CHECK, so artwork_cache is rebuilt. Nothing
+-- references it, so the rebuild is a plain copy.
+
+CREATE TABLE IF NOT EXISTS email_change_tokens (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    new_email TEXT NOT NULL COLLATE NOCASE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_email_change_tokens_user ON email_change_tokens(user_id);
+
+CREATE TABLE artwork_cache_new (
+    id TEXT PRIMARY KEY,
+    -- Entity identification
+    entity_type TEXT NOT NULL CHECK (entity_type IN ('show', 'movie', 'episode', 'album', 'artist', 'audiobook', 'user')),
+    entity_id TEXT NOT NULL,
+    artwork_type TEXT NOT NULL CHECK (artwork_type IN ('poster', 'posters', 'backdrop', 'backdrops', 'thumbnail', 'thumbnails', 'banner', 'banners', 'cover', 'avatars')),
+    -- Image data
+    content_hash TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    data BLOB NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    -- Original source
+    source_url TEXT,
+    -- Metadata
+    width INTEGER,
+    height INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    blurhash TEXT,
+    -- Unique per entity/type
+    UNIQUE(entity_type, entity_id, artwork_type)
+);
+
+INSERT INTO artwork_cache_new (id, entity_type, entity_id, artwork_type, content_hash, mime_type,
+                               data, size_bytes, source_url, width, height, created_at, blurhash)
+SELECT id, entity_type, entity_id, artwork_type, content_hash, mime_type,
+       data, size_bytes, source_url, width, height, created_at, blurhash
+FROM artwork_cache;
+
+DROP TABLE artwork_cache;
+ALTER TABLE artwork_cache_new RENAME TO artwork_cache;
+
+CREATE INDEX idx_artwork_cache_lookup ON artwork_cache(entity_type, entity_id);
+CREATE INDEX idx_artwork_cache_hash ON artwork_cache(content_hash);
@@ -0,0 +1,14 @@
+-- Password reset tokens
+-- A user who forgot their password asks for a reset link by email; the
+-- single-use token in it lets them set a new password. Only a SHA-256 hash
+-- of the token is stored, like refresh and email change tokens.
+
+CREATE TABLE IF NOT EXISTS password_reset_tokens (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_password_reset_tokens_user ON password_reset_tokens(user_id);
@@ -0,0 +1,23 @@
+-- Language mismatches
+-- Media analysis compares an imported file's audio tracks with its language
+-- profile. A file missing a required audio language gets a row here, usually
+-- because the release's MULTi or language tag was wrong. With the profile's
+-- regrab_missing_audio set, regrab_pending asks auto-hunt to search for the
+-- item again; the release that produced the file is in the grab ledger, so
+-- it isn't picked a second time. The row goes away with the file (e.g. when
+-- it is upgraded) or when a later analysis finds the languages after all.
+-- Languages are stored as ISO 639-1 codes in a JSON array.
+
+CREATE TABLE IF NOT EXISTS language_mismatches (
+    media_file_id TEXT PRIMARY KEY REFERENCES media_files(id) ON DELETE CASCADE,
+    library_id TEXT NOT NULL REFERENCES libraries(id) ON DELETE CASCADE,
+    missing_audio_languages TEXT NOT NULL DEFAULT '[]',
+    -- Torrent or NZB name the file was imported from, when known
+    release_title TEXT,
+    regrab_pending INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE INDEX idx_language_mismatches_library ON language_mismatches(library_id);
+
+ALTER TABLE language_profiles ADD COLUMN regrab_missing_audio INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Audio loudness
+-- With the media_analysis.loudness setting on, media analysis measures the
+-- EBU R128 loudness of every audio stream with ffmpeg's ebur128 filter. The
+-- streaming endpoints turn it into a ReplayGain-style gain for files without
+-- ReplayGain tags, so players can level tracks against each other.
+
+-- Integrated loudness in LUFS
+ALTER TABLE audio_streams ADD COLUMN integrated_loudness REAL;
+-- Loudness range in LU
+ALTER TABLE audio_streams ADD COLUMN loudness_range REAL;
+-- True peak in dBTP
+ALTER TABLE audio_streams ADD COLUMN true_peak REAL;
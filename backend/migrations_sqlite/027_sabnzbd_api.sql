@@ -0,0 +1,17 @@
+-- SABnzbd-compatible API
+-- Sonarr, Radarr and other apps can use Librarian as a SABnzbd download
+-- client. Each user has at most one API key for it; only a SHA-256 hash of
+-- the key is stored, like refresh tokens. The category an app sent with an
+-- NZB is kept so it can find its own downloads in the queue and history.
+
+CREATE TABLE IF NOT EXISTS sabnzbd_api_keys (
+    user_id TEXT PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    key_hash TEXT NOT NULL UNIQUE,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    last_used_at TEXT
+);
+
+CREATE TABLE IF NOT EXISTS sabnzbd_downloads (
+    usenet_download_id TEXT PRIMARY KEY REFERENCES usenet_downloads(id) ON DELETE CASCADE,
+    category TEXT
+);
@@ -0,0 +1,17 @@
+-- qBittorrent-compatible API
+-- Sonarr, Radarr and other apps can use the embedded torrent client as a
+-- qBittorrent download client, logging in with the same API key as the
+-- SABnzbd API. Apps create categories for their downloads and only look at
+-- torrents in their own category.
+
+CREATE TABLE IF NOT EXISTS qbittorrent_categories (
+    user_id TEXT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (user_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS qbittorrent_torrents (
+    torrent_id TEXT PRIMARY KEY REFERENCES torrents(id) ON DELETE CASCADE,
+    category TEXT
+);
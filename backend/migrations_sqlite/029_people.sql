@@ -0,0 +1,31 @@
+-- People credited on movies and shows
+-- Filled from TMDB credits when a movie or show is added or refreshed. A
+-- person is shared by every user's library (they're keyed by TMDB ID);
+-- credits link them to one user's movie or show, so each user only sees
+-- their own filmography.
+
+CREATE TABLE IF NOT EXISTS people (
+    id TEXT PRIMARY KEY,
+    tmdb_id INTEGER NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    profile_url TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS credits (
+    person_id TEXT NOT NULL REFERENCES people(id) ON DELETE CASCADE,
+    movie_id TEXT REFERENCES movies(id) ON DELETE CASCADE,
+    tv_show_id TEXT REFERENCES tv_shows(id) ON DELETE CASCADE,
+    -- 'cast' or 'director'
+    role TEXT NOT NULL,
+    -- Character played, for cast
+    character TEXT,
+    -- Billing position, for cast
+    billing_order INTEGER,
+    CHECK ((movie_id IS NULL) <> (tv_show_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_credits_person ON credits(person_id);
+CREATE INDEX IF NOT EXISTS idx_credits_movie ON credits(movie_id);
+CREATE INDEX IF NOT EXISTS idx_credits_tv_show ON credits(tv_show_id);
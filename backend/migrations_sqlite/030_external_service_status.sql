@@ -0,0 +1,20 @@
+-- Health of configured external services
+-- Written by the service health prober, which checks TMDB, TheTVDB,
+-- OpenSubtitles, indexers, usenet servers, SMTP and manually added cast
+-- devices every few minutes. One row per service; rows for services that
+-- are no longer configured are removed on the next probe.
+
+CREATE TABLE IF NOT EXISTS external_service_status (
+    -- e.g. 'tmdb', 'indexer:<id>', 'usenet:<id>', 'cast:<id>'
+    id TEXT PRIMARY KEY,
+    -- 'tmdb', 'tvdb', 'opensubtitles', 'indexer', 'usenet', 'smtp' or 'cast'
+    kind TEXT NOT NULL,
+    name TEXT NOT NULL,
+    -- 'up', 'degraded' (slow to answer) or 'down'
+    status TEXT NOT NULL,
+    latency_ms INTEGER,
+    last_error TEXT,
+    last_checked_at TEXT NOT NULL,
+    -- When the status last changed
+    last_changed_at TEXT NOT NULL
+);
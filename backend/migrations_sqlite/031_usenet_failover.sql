@@ -0,0 +1,20 @@
+-- Usenet failover: block accounts and per-server usage
+-- Downloads try each article on the user's primary servers in priority
+-- order, then on their backup servers. Backups are usually block accounts
+-- (paid per GB), so they're only used for articles the primaries don't
+-- have. Usage is counted per server per day for the usenetServerUsage query.
+
+ALTER TABLE usenet_servers ADD COLUMN is_backup INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS usenet_server_usage (
+    server_id TEXT NOT NULL REFERENCES usenet_servers(id) ON DELETE CASCADE,
+    -- UTC date, YYYY-MM-DD
+    day TEXT NOT NULL,
+    bytes_downloaded INTEGER NOT NULL DEFAULT 0,
+    articles_downloaded INTEGER NOT NULL DEFAULT 0,
+    -- 430 responses: the server doesn't have the article
+    articles_missing INTEGER NOT NULL DEFAULT 0,
+    -- Connection errors and other failures
+    articles_failed INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (server_id, day)
+);
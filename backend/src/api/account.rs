@@ -0,0 +1,158 @@
+//! Account avatar upload endpoint
+//!
+//! Like .torrent files, avatars are uploaded as multipart form data, so this
+//! is REST. Removing an avatar and the rest of account self-service are
+//! GraphQL mutations.
+
+use axum::{
+    Json, Router,
+    extract::{DefaultBodyLimit, Multipart, State},
+    http::{HeaderMap, StatusCode, header::AUTHORIZATION},
+    routing::post,
+};
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::AppState;
+use crate::db::UpdateUser;
+use crate::graphql::verify_token;
+use crate::services::ArtworkService;
+use crate::services::artwork::ArtworkType;
+
+/// Largest avatar accepted, in bytes
+const MAX_AVATAR_BYTES: usize = 2 * 1024 * 1024;
+
+/// Image formats accepted as avatars
+const AVATAR_MIME_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp", "image/gif"];
+
+#[derive(Debug, Serialize)]
+pub struct AvatarResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn avatar_error(
+    status: StatusCode,
+    error: impl Into<String>,
+) -> (StatusCode, Json<AvatarResponse>) {
+    (
+        status,
+        Json(AvatarResponse {
+            success: false,
+            avatar_url: None,
+            error: Some(error.into()),
+        }),
+    )
+}
+
+/// Upload the current user's avatar
+///
+/// POST /api/account/avatar with the image in a field named `file` or
+/// `avatar`. The format is sniffed from the bytes, not taken from the
+/// upload's content type.
+async fn upload_avatar(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<AvatarResponse>) {
+    let user = match headers
+        .get(AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .map(|h| verify_token(h.trim_start_matches("Bearer ").trim()))
+    {
+        Some(Ok(user)) => user,
+        _ => return avatar_error(StatusCode::UNAUTHORIZED, "Authentication required"),
+    };
+
+    let mut file_data: Option<Vec<u8>> = None;
+    while let Ok(Some(field)) = multipart.next_field().await {
+        let field_name = field.name().unwrap_or("").to_string();
+        if field_name == "file" || field_name == "avatar" {
+            match field.bytes().await {
+                Ok(bytes) => {
+                    file_data = Some(bytes.to_vec());
+                    break;
+                }
+                Err(e) => {
+                    return avatar_error(
+                        StatusCode::BAD_REQUEST,
+                        format!("Failed to read file: {}", e),
+                    );
+                }
+            }
+        }
+    }
+
+    let Some(data) = file_data else {
+        return avatar_error(
+            StatusCode::BAD_REQUEST,
+            "No image provided. Use field name 'file' or 'avatar'.",
+        );
+    };
+    if data.len() > MAX_AVATAR_BYTES {
+        return avatar_error(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            "Avatar must be 2 MB or smaller",
+        );
+    }
+    let Some(mime_type) = infer::get(&data)
+        .map(|kind| kind.mime_type())
+        .filter(|mime| AVATAR_MIME_TYPES.contains(mime))
+    else {
+        return avatar_error(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Avatar must be a PNG, JPEG, WebP or GIF image",
+        );
+    };
+
+    let artwork = ArtworkService::with_env(state.db.clone());
+    let url = match artwork
+        .store_image(
+            data,
+            mime_type.to_string(),
+            ArtworkType::Avatar,
+            "user",
+            &user.user_id,
+            None,
+        )
+        .await
+    {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::error!(user_id = %user.user_id, error = %e, "Failed to store avatar");
+            return avatar_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to store avatar");
+        }
+    };
+
+    // Artwork is served with a day-long cache, so give each upload its own URL
+    let avatar_url = format!("{}?v={}", url, Utc::now().timestamp());
+    let update = UpdateUser {
+        avatar_url: Some(Some(avatar_url.clone())),
+        ..Default::default()
+    };
+    if let Err(e) = state.db.users().update(&user.user_id, update).await {
+        tracing::error!(user_id = %user.user_id, error = %e, "Failed to set avatar URL");
+        return avatar_error(StatusCode::INTERNAL_SERVER_ERROR, "Failed to store avatar");
+    }
+
+    tracing::info!(user_id = %user.user_id, "Avatar updated");
+    (
+        StatusCode::OK,
+        Json(AvatarResponse {
+            success: true,
+            avatar_url: Some(avatar_url),
+            error: None,
+        }),
+    )
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route(
+        "/account/avatar",
+        // Leave room for the multipart framing around a maximum-size image
+        post(upload_avatar).layer(DefaultBodyLimit::max(MAX_AVATAR_BYTES + 64 * 1024)),
+    )
+}
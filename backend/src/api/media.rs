@@ -1,10 +1,12 @@
 //! Media streaming API endpoints
 //!
 //! Provides HTTP endpoints for streaming media files to cast devices
-//! and browser-based playback with Range header support.
+//! and browser-based playback with Range header support, and HLS transcodes
+//! for clients that can't play a file directly.
 
 use std::io::SeekFrom;
 use std::path::Path;
+use std::sync::Arc;
 
 use axum::Router;
 use axum::body::Body;
@@ -12,9 +14,10 @@ use axum::extract::{Path as AxumPath, Query, State};
 use axum::http::header::{
     ACCEPT_RANGES, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, RANGE,
 };
+use axum::http::response::Builder;
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::{IntoResponse, Response};
-use axum::routing::get;
+use axum::routing::{delete, get};
 use serde::Deserialize;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncSeekExt};
@@ -22,12 +25,18 @@ use tokio_util::io::ReaderStream;
 use tracing::{debug, error, warn};
 use uuid::Uuid;
 
-use crate::db::Database;
+use crate::db::{Database, MediaFileRecord};
+use crate::services::ffmpeg::Loudness;
+use crate::services::transcode::{
+    DEFAULT_HARDWARE_ACCELERATION, HARDWARE_ACCELERATION_KEY, TranscodeQuality, TranscodeService,
+    is_chromecast_compatible, parse_segment_name,
+};
 
 /// App state for media routes
 #[derive(Clone)]
 pub struct MediaState {
     pub db: Database,
+    pub transcode: Arc<TranscodeService>,
 }
 
 /// Create media routes
@@ -35,6 +44,12 @@ pub fn media_routes() -> Router<MediaState> {
     Router::new()
         .route("/media/{file_id}/stream", get(stream_media))
         .route("/media/{file_id}/info", get(media_info))
+        .route("/media/{file_id}/hls.m3u8", get(hls_playlist))
+        .route("/media/{file_id}/hls/{session_id}", delete(stop_hls))
+        .route(
+            "/media/{file_id}/hls/{session_id}/{segment}",
+            get(hls_segment),
+        )
 }
 
 /// Query params for stream endpoint
@@ -91,6 +106,7 @@ async fn stream_media(
 
     // Determine content type
     let content_type = get_content_type(&media_file.path);
+    let replay_gain = ReplayGain::for_file(&media_file);
 
     // Parse Range header
     let range = headers
@@ -121,7 +137,8 @@ async fn stream_media(
 
             let content_range = format!("bytes {}-{}/{}", start, end, file_size);
 
-            Ok(Response::builder()
+            Ok(replay_gain
+                .headers(Response::builder())
                 .status(StatusCode::PARTIAL_CONTENT)
                 .header(CONTENT_TYPE, content_type)
                 .header(CONTENT_LENGTH, length.to_string())
@@ -138,7 +155,8 @@ async fn stream_media(
             let stream = ReaderStream::new(file);
             let body = Body::from_stream(stream);
 
-            Ok(Response::builder()
+            Ok(replay_gain
+                .headers(Response::builder())
                 .status(StatusCode::OK)
                 .header(CONTENT_TYPE, content_type)
                 .header(CONTENT_LENGTH, file_size.to_string())
@@ -150,6 +168,117 @@ async fn stream_media(
     }
 }
 
+/// Start an HLS transcode of a file and return its playlist
+///
+/// Every request opens a new session; its ID is in the segment URIs and the
+/// `x-transcode-session` header. The playlist covers the whole file, and
+/// segments are encoded as they are requested.
+async fn hls_playlist(
+    State(state): State<MediaState>,
+    AxumPath(file_id): AxumPath<Uuid>,
+    Query(params): Query<StreamParams>,
+) -> Result<Response, StatusCode> {
+    let media_file = state
+        .db
+        .media_files()
+        .get_by_id(file_id)
+        .await
+        .map_err(|e| {
+            error!("Database error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let path = Path::new(&media_file.path);
+    if !path.exists() {
+        warn!("Media file path does not exist: {}", media_file.path);
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let quality = params
+        .quality
+        .as_deref()
+        .and_then(TranscodeQuality::from_param)
+        .unwrap_or(TranscodeQuality::P1080);
+    let hardware_allowed = state
+        .db
+        .settings()
+        .get_or_default(HARDWARE_ACCELERATION_KEY, DEFAULT_HARDWARE_ACCELERATION)
+        .await
+        .unwrap_or(DEFAULT_HARDWARE_ACCELERATION);
+
+    let session = state
+        .transcode
+        .start_session(file_id, path, quality, hardware_allowed)
+        .await
+        .map_err(|e| {
+            error!("Failed to start transcode for {}: {}", file_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let playlist = state
+        .transcode
+        .playlist(&session, &format!("hls/{}/", session.id));
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "application/vnd.apple.mpegurl")
+        .header(CACHE_CONTROL, "no-cache")
+        .header("x-transcode-session", session.id.as_str())
+        .body(Body::from(playlist))
+        .unwrap())
+}
+
+/// Serve one segment of an HLS transcode, encoding it first if needed
+async fn hls_segment(
+    State(state): State<MediaState>,
+    AxumPath((file_id, session_id, segment)): AxumPath<(Uuid, String, String)>,
+) -> Result<Response, StatusCode> {
+    let n = parse_segment_name(&segment).ok_or(StatusCode::NOT_FOUND)?;
+    let session = state
+        .transcode
+        .session(&session_id)
+        .filter(|s| s.media_file_id == file_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let path = state
+        .transcode
+        .segment(&session, n)
+        .await
+        .map_err(|e| {
+            error!("Transcode segment {} of {} failed: {}", n, session_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let file = File::open(&path).await.map_err(|e| {
+        error!("Failed to open transcode segment: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(CONTENT_TYPE, "video/mp2t")
+        .header(CACHE_CONTROL, "no-cache")
+        .body(Body::from_stream(ReaderStream::new(file)))
+        .unwrap())
+}
+
+/// End an HLS transcode session early, stopping its encoder
+async fn stop_hls(
+    State(state): State<MediaState>,
+    AxumPath((file_id, session_id)): AxumPath<(Uuid, String)>,
+) -> StatusCode {
+    let owned = state
+        .transcode
+        .session(&session_id)
+        .is_some_and(|s| s.media_file_id == file_id);
+    if owned && state.transcode.stop_session(&session_id) {
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
 /// Get media file information
 async fn media_info(
     State(state): State<MediaState>,
@@ -176,6 +305,7 @@ async fn media_info(
         media_file.video_codec.as_deref(),
         media_file.audio_codec.as_deref(),
     );
+    let replay_gain = ReplayGain::for_file(&media_file);
 
     let info = serde_json::json!({
         "id": media_file.id.to_string(),
@@ -193,11 +323,83 @@ async fn media_info(
         "is_hdr": media_file.is_hdr,
         "hdr_type": media_file.hdr_type,
         "chromecast_compatible": chromecast_compatible,
+        "replaygain_track_gain": replay_gain.track_gain,
+        "replaygain_track_peak": replay_gain.track_peak,
+        "replaygain_album_gain": replay_gain.album_gain,
+        "replaygain_album_peak": replay_gain.album_peak,
     });
 
     Ok(axum::Json(info))
 }
 
+/// ReplayGain values served with a file, so players can level tracks
+/// against each other
+#[derive(Debug, Default)]
+struct ReplayGain {
+    /// Gain in dB
+    track_gain: Option<f64>,
+    /// Peak as a linear sample value
+    track_peak: Option<f64>,
+    album_gain: Option<f64>,
+    album_peak: Option<f64>,
+}
+
+impl ReplayGain {
+    /// The file's ReplayGain tags, or for untagged files track values derived
+    /// from the loudness measured during analysis
+    ///
+    /// Album values only come from tags; they need the loudness of the whole
+    /// album measured as one.
+    fn for_file(media_file: &MediaFileRecord) -> Self {
+        let album_gain = media_file.replaygain_album_gain;
+        let album_peak = media_file.replaygain_album_peak;
+
+        if let Some(track_gain) = media_file.replaygain_track_gain {
+            return Self {
+                track_gain: Some(track_gain),
+                track_peak: media_file.replaygain_track_peak,
+                album_gain,
+                album_peak,
+            };
+        }
+
+        let measured = media_file
+            .r128_integrated_loudness
+            .map(|integrated| Loudness {
+                integrated,
+                range: None,
+                true_peak: media_file.r128_true_peak,
+            });
+        Self {
+            track_gain: measured.and_then(|l| l.replay_gain()),
+            track_peak: measured.and_then(|l| l.peak_amplitude()),
+            album_gain,
+            album_peak,
+        }
+    }
+
+    /// Add `X-ReplayGain-*` headers for the values that are known
+    fn headers(&self, mut builder: Builder) -> Builder {
+        let values = [
+            ("x-replaygain-track-gain", self.track_gain, true),
+            ("x-replaygain-track-peak", self.track_peak, false),
+            ("x-replaygain-album-gain", self.album_gain, true),
+            ("x-replaygain-album-peak", self.album_peak, false),
+        ];
+        for (name, value, is_gain) in values {
+            if let Some(value) = value {
+                let value = if is_gain {
+                    format!("{:.2} dB", value)
+                } else {
+                    format!("{:.6}", value)
+                };
+                builder = builder.header(name, value);
+            }
+        }
+        builder
+    }
+}
+
 /// Parse HTTP Range header
 fn parse_range_header(header: &str, file_size: u64) -> Option<(u64, u64)> {
     // Format: "bytes=start-end" or "bytes=start-"
@@ -238,7 +440,8 @@ fn get_content_type(path: &str) -> &'static str {
         "avi" => "video/x-msvideo",
         "mov" => "video/quicktime",
         "wmv" => "video/x-ms-wmv",
-        "ts" => "video/mp2t",
+        "ts" | "m2ts" => "video/mp2t",
+        "vob" => "video/mpeg",
         "m3u8" => "application/vnd.apple.mpegurl",
         "mp3" => "audio/mpeg",
         "flac" => "audio/flac",
@@ -253,44 +456,3 @@ fn get_content_type(path: &str) -> &'static str {
         _ => "application/octet-stream",
     }
 }
-
-/// Check if media file is Chromecast compatible
-/// Chromecast supports:
-/// - Containers: MP4, WebM
-/// - Video: H.264 (up to level 4.2), VP8, VP9
-/// - Audio: AAC, MP3, Opus, Vorbis, FLAC
-fn is_chromecast_compatible(
-    container: Option<&str>,
-    video_codec: Option<&str>,
-    audio_codec: Option<&str>,
-) -> bool {
-    let container_ok = match container {
-        Some(c) => {
-            let c = c.to_lowercase();
-            c.contains("mp4") || c.contains("m4v") || c.contains("webm")
-        }
-        None => false,
-    };
-
-    let video_ok = match video_codec {
-        Some(v) => {
-            let v = v.to_lowercase();
-            v.contains("h264") || v.contains("avc") || v.contains("vp8") || v.contains("vp9")
-        }
-        None => true, // No video is OK (audio only)
-    };
-
-    let audio_ok = match audio_codec {
-        Some(a) => {
-            let a = a.to_lowercase();
-            a.contains("aac")
-                || a.contains("mp3")
-                || a.contains("opus")
-                || a.contains("vorbis")
-                || a.contains("flac")
-        }
-        None => true, // No audio is OK (video only, though uncommon)
-    };
-
-    container_ok && video_ok && audio_ok
-}
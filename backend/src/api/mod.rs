@@ -2,16 +2,21 @@
 //!
 //! The primary API is GraphQL at /graphql.
 //! REST endpoints are provided only for operations that don't work well with GraphQL:
-//! - File uploads (multipart form data)
+//! - File uploads (multipart form data): .torrent files and avatars
 //! - Filesystem browsing (simple REST is cleaner)
 //! - Health checks
 //! - Torznab API for external app compatibility (Sonarr, Radarr)
+//! - SABnzbd API so the same apps can use the usenet downloader
+//! - qBittorrent API so they can use the torrent client
 //! - Media streaming for cast devices and browser playback
 //! - Artwork serving (SQLite mode only - images are stored as BLOBs)
 
+pub mod account;
 pub mod artwork;
 pub mod filesystem;
 pub mod health;
 pub mod media;
+pub mod qbittorrent;
+pub mod sabnzbd;
 pub mod torrents;
 pub mod torznab;
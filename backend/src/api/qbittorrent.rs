@@ -0,0 +1,826 @@
+//! qBittorrent-compatible WebUI API
+//!
+//! Lets Sonarr, Radarr and other apps use the embedded torrent client as a
+//! qBittorrent download client. Apps point at host `/api/qbittorrent` and
+//! log in with any username and the user's SABnzbd API key as the password;
+//! only the endpoints those apps rely on are emulated. Torrents added here
+//! are left for the app to import, not Librarian.
+//!
+//! # Endpoints
+//!
+//! All under `/api/qbittorrent/api/v2`:
+//!
+//! - `POST auth/login` - Sets the `SID` cookie the other endpoints need
+//! - `POST auth/logout`
+//! - `GET app/version`, `GET app/webapiVersion`, `GET app/preferences`
+//! - `GET torrents/info?category=...&hashes=...` - The user's torrents
+//! - `POST torrents/add` - Add magnets or URLs (`urls`) or .torrent files (`torrents`)
+//! - `POST torrents/delete` - Remove torrents (`hashes`, `deleteFiles`)
+//! - `GET torrents/categories`, `POST torrents/createCategory`, `POST torrents/setCategory`
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use axum::{
+    Form, Json, Router,
+    extract::{FromRequest, Multipart, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use parking_lot::RwLock;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use super::sabnzbd::{EXTERNAL_POST_PROCESS_STATUS, authenticate};
+use crate::AppState;
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource};
+use crate::services::release_precheck;
+use crate::services::{TorrentInfo, TorrentState};
+
+/// qBittorrent version reported to apps
+const QBITTORRENT_VERSION: &str = "v4.6.7";
+
+/// WebUI API version of that qBittorrent release
+const WEBAPI_VERSION: &str = "2.9.3";
+
+/// Name of the session cookie
+const SESSION_COOKIE: &str = "SID";
+
+/// How long a session lasts before the app has to log in again
+const SESSION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// qBittorrent's ETA for torrents that aren't making progress
+const ETA_INFINITY: u64 = 8_640_000;
+
+/// Sessions handed out by `auth/login`, by session ID
+///
+/// The cookie only carries a random ID; the API key stays server-side and is
+/// re-checked on every request, so revoking it still logs the app out.
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: RwLock<HashMap<String, Session>>,
+}
+
+struct Session {
+    user_id: Uuid,
+    api_key: String,
+    expires_at: Instant,
+}
+
+impl SessionStore {
+    /// Start a session for a user's API key, returning its ID
+    pub fn create(&self, user_id: Uuid, api_key: String) -> String {
+        let id = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let now = Instant::now();
+
+        let mut sessions = self.sessions.write();
+        sessions.retain(|_, session| session.expires_at > now);
+        sessions.insert(
+            id.clone(),
+            Session {
+                user_id,
+                api_key,
+                expires_at: now + SESSION_TTL,
+            },
+        );
+        id
+    }
+
+    /// The user and API key of an unexpired session
+    pub fn get(&self, id: &str) -> Option<(Uuid, String)> {
+        self.sessions
+            .read()
+            .get(id)
+            .filter(|session| session.expires_at > Instant::now())
+            .map(|session| (session.user_id, session.api_key.clone()))
+    }
+
+    /// End a session
+    pub fn remove(&self, id: &str) {
+        self.sessions.write().remove(id);
+    }
+}
+
+/// `auth/login` form
+#[derive(Debug, Deserialize)]
+pub struct LoginForm {
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: String,
+}
+
+/// `torrents/info` query parameters
+#[derive(Debug, Deserialize, Default)]
+pub struct InfoParams {
+    pub category: Option<String>,
+    /// `|`-separated info hashes
+    pub hashes: Option<String>,
+}
+
+/// `torrents/delete` form
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteForm {
+    pub hashes: String,
+    #[serde(default)]
+    pub delete_files: String,
+}
+
+/// `torrents/createCategory` form
+#[derive(Debug, Deserialize)]
+pub struct CreateCategoryForm {
+    pub category: String,
+}
+
+/// `torrents/setCategory` form
+#[derive(Debug, Deserialize)]
+pub struct SetCategoryForm {
+    pub hashes: String,
+    #[serde(default)]
+    pub category: String,
+}
+
+/// Create the qBittorrent router
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/qbittorrent/api/v2/auth/login", post(login))
+        .route("/qbittorrent/api/v2/auth/logout", post(logout))
+        .route("/qbittorrent/api/v2/app/version", get(version))
+        .route("/qbittorrent/api/v2/app/webapiVersion", get(webapi_version))
+        .route("/qbittorrent/api/v2/app/preferences", get(preferences))
+        .route("/qbittorrent/api/v2/torrents/info", get(torrents_info))
+        .route("/qbittorrent/api/v2/torrents/add", post(add_torrents))
+        .route("/qbittorrent/api/v2/torrents/delete", post(delete_torrents))
+        .route("/qbittorrent/api/v2/torrents/categories", get(categories))
+        .route(
+            "/qbittorrent/api/v2/torrents/createCategory",
+            post(create_category),
+        )
+        .route(
+            "/qbittorrent/api/v2/torrents/setCategory",
+            post(set_category),
+        )
+}
+
+/// `auth/login`: check the API key and start a session for it
+async fn login(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<LoginForm>,
+) -> Response {
+    let user_id = match authenticate(&state.db, Some(form.password.as_str())).await {
+        Ok(id) => id,
+        Err(_) => return "Fails.".into_response(),
+    };
+
+    // A username is optional, but must be the key owner's if given
+    if !form.username.is_empty() {
+        match state.db.users().get_by_id(&user_id.to_string()).await {
+            Ok(Some(user)) if user.username.eq_ignore_ascii_case(&form.username) => {}
+            _ => return "Fails.".into_response(),
+        }
+    }
+
+    let id = state.qbittorrent_sessions.create(user_id, form.password);
+    // Apps often reach the API over plain HTTP on the LAN, where a Secure
+    // cookie would never be sent back
+    let secure = if is_https(&headers) { "; Secure" } else { "" };
+
+    (
+        [(
+            header::SET_COOKIE,
+            format!(
+                "{}={}; HttpOnly; SameSite=Strict; path=/; Max-Age={}{}",
+                SESSION_COOKIE,
+                id,
+                SESSION_TTL.as_secs(),
+                secure
+            ),
+        )],
+        "Ok.",
+    )
+        .into_response()
+}
+
+async fn logout(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Some(id) = cookie_session_id(&headers) {
+        state.qbittorrent_sessions.remove(id);
+    }
+
+    (
+        [(
+            header::SET_COOKIE,
+            format!("{}=; HttpOnly; path=/; Max-Age=0", SESSION_COOKIE),
+        )],
+        "",
+    )
+        .into_response()
+}
+
+async fn version() -> &'static str {
+    QBITTORRENT_VERSION
+}
+
+async fn webapi_version() -> &'static str {
+    WEBAPI_VERSION
+}
+
+/// `app/preferences`: the settings apps validate against
+async fn preferences(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    if let Err(response) = session_user(&state, &headers).await {
+        return response;
+    }
+
+    Json(json!({
+        "save_path": state.torrent_service.download_dir().to_string_lossy(),
+        "temp_path_enabled": false,
+        "auto_tmm_enabled": false,
+        "max_ratio_enabled": false,
+        "max_ratio": -1,
+        "max_seeding_time_enabled": false,
+        "max_seeding_time": -1,
+        "max_ratio_act": 0,
+        "queueing_enabled": false,
+        "dht": true,
+    }))
+    .into_response()
+}
+
+/// `torrents/info`: the user's torrents, optionally filtered
+async fn torrents_info(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<InfoParams>,
+) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let result = async {
+        let records = state.db.torrents().list_by_user(user_id).await?;
+        let categories: HashMap<String, Option<String>> = state
+            .db
+            .qbittorrent()
+            .list_torrent_categories(user_id)
+            .await?
+            .into_iter()
+            .collect();
+        anyhow::Ok((records, categories))
+    }
+    .await;
+    let (records, categories) = match result {
+        Ok(result) => result,
+        Err(e) => return internal_error(e),
+    };
+
+    let hashes = params.hashes.as_deref().and_then(parse_hashes);
+    let mut torrents = Vec::new();
+    for record in records {
+        if hashes
+            .as_ref()
+            .is_some_and(|h| !h.contains(&record.info_hash))
+        {
+            continue;
+        }
+        let category = categories
+            .get(&record.info_hash)
+            .cloned()
+            .flatten()
+            .unwrap_or_default();
+        if params.category.as_ref().is_some_and(|c| *c != category) {
+            continue;
+        }
+        let Some(info) = state
+            .torrent_service
+            .get_torrent_info_by_hash(&record.info_hash)
+            .await
+        else {
+            continue;
+        };
+
+        torrents.push(torrent_json(
+            &info,
+            &category,
+            record.added_at.timestamp(),
+            record.completed_at.map(|at| at.timestamp()),
+        ));
+    }
+
+    Json(torrents).into_response()
+}
+
+/// `torrents/add`: add magnets, URLs or .torrent files
+///
+/// Apps send a multipart form, but a plain form works for `urls`.
+async fn add_torrents(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    request: Request,
+) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let form = match read_add_form(request).await {
+        Ok(form) => form,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    let category = form.category.as_deref().filter(|c| !c.is_empty());
+
+    let mut added = 0;
+    let mut last_error = None;
+    for url in form.urls.lines().map(str::trim).filter(|u| !u.is_empty()) {
+        match add_one(&state, user_id, AddSource::Url(url), category, form.paused).await {
+            Ok(()) => added += 1,
+            Err(e) => last_error = Some(e),
+        }
+    }
+    for bytes in &form.torrents {
+        match add_one(
+            &state,
+            user_id,
+            AddSource::File(bytes),
+            category,
+            form.paused,
+        )
+        .await
+        {
+            Ok(()) => added += 1,
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    match last_error {
+        Some(e) if added == 0 => {
+            tracing::warn!(error = %e, "qBittorrent API failed to add torrent");
+            (StatusCode::UNSUPPORTED_MEDIA_TYPE, "Fails.").into_response()
+        }
+        _ => "Ok.".into_response(),
+    }
+}
+
+/// Where a torrent added through the API comes from
+#[derive(Clone, Copy)]
+enum AddSource<'a> {
+    Url(&'a str),
+    File(&'a [u8]),
+}
+
+/// Add one torrent, leaving it to the app to import
+async fn add_one(
+    state: &AppState,
+    user_id: Uuid,
+    source: AddSource<'_>,
+    category: Option<&str>,
+    paused: bool,
+) -> anyhow::Result<()> {
+    let info_hash = match source {
+        AddSource::Url(url) => grab_ledger::info_hash_from_magnet(url),
+        AddSource::File(_) => None,
+    };
+
+    // Skip releases already grabbed by another pipeline
+    let request = GrabRequest {
+        user_id,
+        source: GrabSource::Manual,
+        download_type: "torrent",
+        title: None,
+        info_hash,
+        guid: None,
+        indexer_id: None,
+        force: false,
+    };
+    let claim_id = match grab_ledger::claim(&state.db, request).await? {
+        Claim::Claimed(id) => id,
+        Claim::Duplicate(earlier) => {
+            anyhow::bail!(grab_ledger::duplicate_message(earlier.as_ref()));
+        }
+    };
+
+    // Torrents Librarian is already downloading keep their own post-processing
+    let existing: Vec<String> = state
+        .db
+        .torrents()
+        .list_by_user(user_id)
+        .await?
+        .into_iter()
+        .map(|t| t.info_hash)
+        .collect();
+
+    let service = &state.torrent_service;
+    let result = match source {
+        AddSource::Url(url) if url.starts_with("magnet:") => {
+            service.add_magnet(url, Some(user_id)).await
+        }
+        AddSource::Url(url) => service.add_torrent_url(url, Some(user_id)).await,
+        AddSource::File(bytes) => {
+            match release_precheck::check_torrent(&state.db, "qBittorrent API upload", bytes, None)
+                .await
+            {
+                Ok(()) => service.add_torrent_bytes(bytes, Some(user_id)).await,
+                Err(e) => Err(e),
+            }
+        }
+    };
+    let info = match result {
+        Ok(info) => info,
+        Err(e) => {
+            if let Err(e) = grab_ledger::abandon(&state.db, claim_id).await {
+                tracing::warn!(error = %e, "Failed to drop grab claim");
+            }
+            return Err(e);
+        }
+    };
+    grab_ledger::confirm(&state.db, claim_id, &info.info_hash).await?;
+
+    if existing.contains(&info.info_hash) {
+        tracing::info!(
+            info_hash = %info.info_hash,
+            "Torrent added through the qBittorrent API is already downloading"
+        );
+        return Ok(());
+    }
+
+    let torrents = state.db.torrents();
+    if let Some(record) = torrents.get_by_info_hash(&info.info_hash).await?
+        && record.user_id == user_id
+    {
+        torrents
+            .update_post_process_status(&info.info_hash, EXTERNAL_POST_PROCESS_STATUS)
+            .await?;
+        state
+            .db
+            .qbittorrent()
+            .set_category(record.id, category)
+            .await?;
+    }
+    if paused {
+        service.pause(info.id).await?;
+    }
+
+    Ok(())
+}
+
+/// Fields of a `torrents/add` request
+#[derive(Debug, Default)]
+struct AddForm {
+    /// Newline-separated magnets and .torrent URLs
+    urls: String,
+    torrents: Vec<Vec<u8>>,
+    category: Option<String>,
+    paused: bool,
+}
+
+async fn read_add_form(request: Request) -> Result<AddForm, String> {
+    let is_multipart = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    if !is_multipart {
+        let Form(fields) = Form::<HashMap<String, String>>::from_request(request, &())
+            .await
+            .map_err(|e| e.body_text())?;
+        return Ok(AddForm {
+            urls: fields.get("urls").cloned().unwrap_or_default(),
+            torrents: Vec::new(),
+            category: fields.get("category").cloned(),
+            paused: fields.get("paused").is_some_and(|p| p == "true"),
+        });
+    }
+
+    let mut multipart = Multipart::from_request(request, &())
+        .await
+        .map_err(|e| e.body_text())?;
+    let mut form = AddForm::default();
+    while let Some(field) = multipart.next_field().await.map_err(|e| e.body_text())? {
+        let name = field.name().unwrap_or_default().to_string();
+        match name.as_str() {
+            "torrents" => {
+                let bytes = field.bytes().await.map_err(|e| e.body_text())?;
+                form.torrents.push(bytes.to_vec());
+            }
+            "urls" => form.urls = field.text().await.map_err(|e| e.body_text())?,
+            "category" => form.category = Some(field.text().await.map_err(|e| e.body_text())?),
+            "paused" => form.paused = field.text().await.map_err(|e| e.body_text())? == "true",
+            _ => {}
+        }
+    }
+
+    Ok(form)
+}
+
+/// `torrents/delete`: remove the user's torrents
+async fn delete_torrents(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<DeleteForm>,
+) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let records = match state.db.torrents().list_by_user(user_id).await {
+        Ok(records) => records,
+        Err(e) => return internal_error(e),
+    };
+    let hashes = parse_hashes(&form.hashes);
+    let delete_files = form.delete_files == "true";
+
+    for record in records {
+        if hashes
+            .as_ref()
+            .is_some_and(|h| !h.contains(&record.info_hash))
+        {
+            continue;
+        }
+        let Some(info) = state
+            .torrent_service
+            .get_torrent_info_by_hash(&record.info_hash)
+            .await
+        else {
+            continue;
+        };
+        if let Err(e) = state.torrent_service.remove(info.id, delete_files).await {
+            return internal_error(e);
+        }
+    }
+
+    StatusCode::OK.into_response()
+}
+
+/// `torrents/categories`: the user's categories by name
+async fn categories(State(state): State<AppState>, headers: HeaderMap) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let names = match state.db.qbittorrent().list_categories(user_id).await {
+        Ok(names) => names,
+        Err(e) => return internal_error(e),
+    };
+    let save_path = state.torrent_service.download_dir().to_string_lossy();
+    let categories: serde_json::Map<String, Value> = names
+        .into_iter()
+        .map(|name| {
+            let category = json!({ "name": name, "savePath": save_path });
+            (name, category)
+        })
+        .collect();
+
+    Json(categories).into_response()
+}
+
+/// `torrents/createCategory`: apps create their category when first set up
+async fn create_category(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<CreateCategoryForm>,
+) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let name = form.category.trim();
+    if name.is_empty() {
+        return (StatusCode::BAD_REQUEST, "Invalid category name").into_response();
+    }
+
+    match state.db.qbittorrent().create_category(user_id, name).await {
+        Ok(()) => StatusCode::OK.into_response(),
+        Err(e) => internal_error(e),
+    }
+}
+
+/// `torrents/setCategory`: move the user's torrents to a category
+async fn set_category(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Form(form): Form<SetCategoryForm>,
+) -> Response {
+    let user_id = match session_user(&state, &headers).await {
+        Ok(id) => id,
+        Err(response) => return response,
+    };
+
+    let records = match state.db.torrents().list_by_user(user_id).await {
+        Ok(records) => records,
+        Err(e) => return internal_error(e),
+    };
+    let hashes = parse_hashes(&form.hashes);
+    let category = Some(form.category.as_str()).filter(|c| !c.is_empty());
+
+    for record in records {
+        if hashes
+            .as_ref()
+            .is_some_and(|h| !h.contains(&record.info_hash))
+        {
+            continue;
+        }
+        if let Err(e) = state
+            .db
+            .qbittorrent()
+            .set_category(record.id, category)
+            .await
+        {
+            return internal_error(e);
+        }
+    }
+
+    StatusCode::OK.into_response()
+}
+
+/// The user of the session in the cookie, or a 403 like
+/// qBittorrent sends when logged out
+async fn session_user(state: &AppState, headers: &HeaderMap) -> Result<Uuid, Response> {
+    let forbidden = || (StatusCode::FORBIDDEN, "Forbidden").into_response();
+
+    let id = cookie_session_id(headers).ok_or_else(forbidden)?;
+    let (user_id, api_key) = state.qbittorrent_sessions.get(id).ok_or_else(forbidden)?;
+
+    // The key may have been revoked, or its owner lost the permission
+    match authenticate(&state.db, Some(api_key.as_str())).await {
+        Ok(key_user_id) if key_user_id == user_id => Ok(user_id),
+        _ => {
+            state.qbittorrent_sessions.remove(id);
+            Err(forbidden())
+        }
+    }
+}
+
+fn cookie_session_id(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .find_map(session_id)
+}
+
+/// Whether the request reached the proxy in front of us over HTTPS
+fn is_https(headers: &HeaderMap) -> bool {
+    headers
+        .get("x-forwarded-proto")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|proto| proto.eq_ignore_ascii_case("https"))
+}
+
+fn torrent_json(
+    info: &TorrentInfo,
+    category: &str,
+    added_on: i64,
+    completion_on: Option<i64>,
+) -> Value {
+    let amount_left = info.size.saturating_sub(info.downloaded);
+    let ratio = if info.downloaded > 0 {
+        info.uploaded as f64 / info.downloaded as f64
+    } else {
+        0.0
+    };
+    json!({
+        "hash": info.info_hash,
+        "name": info.name,
+        "size": info.size,
+        "total_size": info.size,
+        "progress": info.progress,
+        "dlspeed": info.download_speed,
+        "upspeed": info.upload_speed,
+        "eta": eta(amount_left, info.download_speed),
+        "state": torrent_state(info.state, info.progress),
+        "category": category,
+        "tags": "",
+        "save_path": info.save_path,
+        "content_path": std::path::Path::new(&info.save_path).join(&info.name),
+        "downloaded": info.downloaded,
+        "uploaded": info.uploaded,
+        "amount_left": amount_left,
+        "ratio": ratio,
+        "ratio_limit": -2,
+        "seeding_time_limit": -2,
+        "num_seeds": info.seeds,
+        "num_leechs": info.peers.saturating_sub(info.seeds),
+        "added_on": added_on,
+        "completion_on": completion_on.unwrap_or(-1),
+        "priority": 0,
+        "seq_dl": false,
+        "auto_tmm": false,
+    })
+}
+
+fn internal_error(e: anyhow::Error) -> Response {
+    tracing::error!(error = %e, "qBittorrent API request failed");
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+}
+
+/// The session ID in a `Cookie` header
+pub fn session_id(cookies: &str) -> Option<&str> {
+    cookies
+        .split(';')
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == SESSION_COOKIE)
+        .map(|(_, value)| value)
+        .filter(|value| !value.is_empty())
+}
+
+/// qBittorrent's name for a torrent state
+pub fn torrent_state(state: TorrentState, progress: f64) -> &'static str {
+    match state {
+        TorrentState::Queued => "queuedDL",
+        TorrentState::Checking => "checkingDL",
+        TorrentState::Downloading => "downloading",
+        TorrentState::Seeding => "uploading",
+        TorrentState::Paused if progress >= 1.0 => "pausedUP",
+        TorrentState::Paused => "pausedDL",
+        TorrentState::Error => "error",
+    }
+}
+
+/// Seconds until a download finishes, qBittorrent's infinity when stalled
+pub fn eta(amount_left: u64, download_speed: u64) -> u64 {
+    match (amount_left, download_speed) {
+        (0, _) => 0,
+        (_, 0) => ETA_INFINITY,
+        (left, speed) => left.div_ceil(speed).min(ETA_INFINITY),
+    }
+}
+
+/// Info hashes from a `|`-separated list, `None` for `all`
+pub fn parse_hashes(hashes: &str) -> Option<Vec<String>> {
+    if hashes.trim() == "all" {
+        return None;
+    }
+    Some(
+        hashes
+            .split('|')
+            .filter_map(grab_ledger::normalize_info_hash)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_id() {
+        assert_eq!(session_id("SID=abc123"), Some("abc123"));
+        assert_eq!(
+            session_id("theme=dark; SID=abc123; lang=en"),
+            Some("abc123")
+        );
+        assert_eq!(session_id("SID="), None);
+        assert_eq!(session_id("SIDE=abc"), None);
+    }
+
+    #[test]
+    fn test_session_store() {
+        let store = SessionStore::default();
+        let user_id = Uuid::new_v4();
+
+        let id = store.create(user_id, "key".to_string());
+        assert_ne!(id, "key");
+        assert_eq!(store.get(&id), Some((user_id, "key".to_string())));
+        assert_eq!(store.get("unknown"), None);
+
+        store.remove(&id);
+        assert_eq!(store.get(&id), None);
+    }
+
+    #[test]
+    fn test_torrent_state() {
+        assert_eq!(torrent_state(TorrentState::Downloading, 0.5), "downloading");
+        assert_eq!(torrent_state(TorrentState::Seeding, 1.0), "uploading");
+        assert_eq!(torrent_state(TorrentState::Paused, 0.5), "pausedDL");
+        assert_eq!(torrent_state(TorrentState::Paused, 1.0), "pausedUP");
+    }
+
+    #[test]
+    fn test_eta() {
+        assert_eq!(eta(0, 0), 0);
+        assert_eq!(eta(1000, 0), ETA_INFINITY);
+        assert_eq!(eta(1000, 300), 4);
+        assert_eq!(eta(u64::MAX, 1), ETA_INFINITY);
+    }
+
+    #[test]
+    fn test_parse_hashes() {
+        let hash = "a".repeat(40);
+        assert_eq!(parse_hashes("all"), None);
+        assert_eq!(
+            parse_hashes(&format!(
+                "{}|not-a-hash|{}",
+                hash.to_uppercase(),
+                "b".repeat(40)
+            )),
+            Some(vec![hash, "b".repeat(40)])
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! SABnzbd-compatible REST API
+//!
+//! Lets Sonarr, Radarr and other apps use Librarian's usenet downloader as a
+//! SABnzbd download client. Apps point at host `/api/sabnzbd` and call its
+//! `api` endpoint; only the JSON modes those apps rely on are emulated.
+//! Downloads added here are left for the app to import, not Librarian.
+//!
+//! # Endpoints
+//!
+//! - `GET /api/sabnzbd/api?mode=version` - SABnzbd version (no key needed)
+//! - `GET /api/sabnzbd/api?apikey=...&mode=get_config` - Categories and completed folder
+//! - `GET /api/sabnzbd/api?apikey=...&mode=addurl&name=<url>&nzbname=...&cat=...` - Add an NZB
+//! - `GET /api/sabnzbd/api?apikey=...&mode=queue` - Downloads in progress
+//! - `GET /api/sabnzbd/api?apikey=...&mode=history` - Completed and failed downloads
+//! - `GET /api/sabnzbd/api?apikey=...&mode=queue&name=delete&value=<ids>` - Remove downloads
+//!   (same for `mode=history`, add `del_files=1` to delete the files too)
+
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::AppState;
+use crate::db::{Database, UsenetDownloadRecord, roles::Permission};
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource};
+use crate::services::usenet::UsenetService;
+
+/// SABnzbd version reported to apps, recent enough for all of them
+const SABNZBD_VERSION: &str = "4.3.3";
+
+/// Categories offered to apps besides the ones they already used
+const DEFAULT_CATEGORIES: [&str; 5] = ["*", "tv", "movies", "music", "audiobooks"];
+
+/// Post-processing status of downloads the adding app imports itself
+pub const EXTERNAL_POST_PROCESS_STATUS: &str = "external";
+
+/// SABnzbd API query parameters
+#[derive(Debug, Deserialize, Default)]
+pub struct SabnzbdParams {
+    pub mode: Option<String>,
+    pub apikey: Option<String>,
+    /// NZB URL for `addurl`, sub-command for `queue` and `history`
+    pub name: Option<String>,
+    /// Comma-separated download IDs for `name=delete`
+    pub value: Option<String>,
+    pub nzbname: Option<String>,
+    pub cat: Option<String>,
+    pub start: Option<String>,
+    pub limit: Option<String>,
+    pub del_files: Option<String>,
+}
+
+/// Create the SABnzbd router
+pub fn router() -> Router<AppState> {
+    Router::new().route("/sabnzbd/api", get(sabnzbd_handler).post(sabnzbd_handler))
+}
+
+/// Hash an API key for storage (using SHA-256)
+pub fn hash_api_key(key: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(key.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Main SABnzbd endpoint handler
+async fn sabnzbd_handler(
+    State(state): State<AppState>,
+    Query(params): Query<SabnzbdParams>,
+) -> Response {
+    let mode = params.mode.as_deref().unwrap_or_default();
+
+    // Apps check the version before asking for a key
+    if mode == "version" {
+        return Json(json!({ "version": SABNZBD_VERSION })).into_response();
+    }
+
+    let user_id = match authenticate(&state.db, params.apikey.as_deref()).await {
+        Ok(id) => id,
+        Err(message) => return error_response(&message),
+    };
+
+    let result = match (mode, params.name.as_deref()) {
+        ("get_config", _) => get_config(&state.db, &state.usenet_service, user_id).await,
+        ("addurl", _) => add_url(&state.db, &state.usenet_service, user_id, &params).await,
+        ("queue" | "history", Some("delete")) => {
+            delete(&state.db, &state.usenet_service, user_id, &params).await
+        }
+        ("queue", _) => queue(&state.db, user_id, &params).await,
+        ("history", _) => history(&state.db, user_id, &params).await,
+        _ => return error_response("not implemented"),
+    };
+
+    match result {
+        Ok(body) => Json(body).into_response(),
+        Err(e) => error_response(&e.to_string()),
+    }
+}
+
+/// Find the user an API key belongs to
+///
+/// Keys outlive role changes, so the permission is checked on every call.
+/// The qBittorrent API accepts the same keys as passwords.
+pub async fn authenticate(db: &Database, apikey: Option<&str>) -> Result<Uuid, String> {
+    let Some(apikey) = apikey.filter(|key| !key.is_empty()) else {
+        return Err("API Key Required".to_string());
+    };
+
+    let key = db
+        .sabnzbd()
+        .get_key_by_hash(&hash_api_key(apikey))
+        .await
+        .map_err(|e| format!("Database error: {}", e))?
+        .ok_or_else(|| "API Key Incorrect".to_string())?;
+
+    let role = db
+        .roles()
+        .get_for_user(key.user_id)
+        .await
+        .map_err(|e| format!("Database error: {}", e))?;
+    if !role.is_some_and(|role| role.has(Permission::ManageDownloads)) {
+        return Err(format!(
+            "Permission '{}' required",
+            Permission::ManageDownloads.as_str()
+        ));
+    }
+
+    if let Err(e) = db.sabnzbd().touch_key(key.user_id).await {
+        tracing::warn!(error = %e, "Failed to record SABnzbd API key use");
+    }
+
+    Ok(key.user_id)
+}
+
+/// `get_config`: the categories and completed folder apps validate against
+async fn get_config(
+    db: &Database,
+    usenet_service: &UsenetService,
+    user_id: Uuid,
+) -> anyhow::Result<Value> {
+    let mut names: Vec<String> = DEFAULT_CATEGORIES.iter().map(|c| c.to_string()).collect();
+    for (_, category) in db.sabnzbd().list_categories(user_id).await? {
+        if let Some(category) = category
+            && !names.contains(&category)
+        {
+            names.push(category);
+        }
+    }
+
+    let categories: Vec<Value> = names
+        .iter()
+        .enumerate()
+        .map(|(order, name)| {
+            json!({
+                "name": name,
+                "order": order,
+                "pp": "3",
+                "script": "None",
+                "dir": "",
+                "priority": 0,
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "config": {
+            "misc": {
+                "complete_dir": usenet_service.downloads_path(),
+                "pre_check": false,
+                "history_retention": "",
+                "enable_tv_sorting": false,
+                "enable_movie_sorting": false,
+                "enable_date_sorting": false,
+            },
+            "categories": categories,
+            "sorters": [],
+        }
+    }))
+}
+
+/// `addurl`: download an NZB by URL
+async fn add_url(
+    db: &Database,
+    usenet_service: &UsenetService,
+    user_id: Uuid,
+    params: &SabnzbdParams,
+) -> anyhow::Result<Value> {
+    let url = params
+        .name
+        .as_deref()
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("expects one parameter"))?;
+    let nzb_name = params.nzbname.as_deref().filter(|name| !name.is_empty());
+    let category = params
+        .cat
+        .as_deref()
+        .filter(|cat| !cat.is_empty() && *cat != "*");
+
+    // Skip releases already grabbed by another pipeline
+    let request = GrabRequest {
+        user_id,
+        source: GrabSource::Manual,
+        download_type: "usenet",
+        title: nzb_name.map(str::to_string),
+        info_hash: None,
+        guid: None,
+        indexer_id: None,
+        force: false,
+    };
+    let claim_id = match grab_ledger::claim(db, request).await? {
+        Claim::Claimed(id) => id,
+        Claim::Duplicate(earlier) => {
+            anyhow::bail!(grab_ledger::duplicate_message(earlier.as_ref()));
+        }
+    };
+
+    let result = usenet_service
+        .add_nzb_url(url, nzb_name, user_id, None, None, None, None, None, None)
+        .await;
+    if result.is_err()
+        && let Err(e) = grab_ledger::abandon(db, claim_id).await
+    {
+        tracing::warn!(error = %e, "Failed to drop grab claim");
+    }
+    let info = result?;
+
+    db.usenet_downloads()
+        .set_post_process_status(info.id, EXTERNAL_POST_PROCESS_STATUS)
+        .await?;
+    db.sabnzbd().set_category(info.id, category).await?;
+
+    Ok(json!({ "status": true, "nzo_ids": [info.id.to_string()] }))
+}
+
+/// `queue`: downloads that haven't finished, oldest first
+async fn queue(db: &Database, user_id: Uuid, params: &SabnzbdParams) -> anyhow::Result<Value> {
+    let (records, categories) = user_downloads(db, user_id).await?;
+    let active: Vec<&UsenetDownloadRecord> = records
+        .iter()
+        .rev()
+        .filter(|r| !is_history_state(&r.state))
+        .collect();
+
+    let speed: i64 = active
+        .iter()
+        .filter(|r| r.state == "downloading")
+        .filter_map(|r| r.download_speed)
+        .sum();
+
+    let slots: Vec<Value> = active
+        .iter()
+        .enumerate()
+        .skip(parse_number(params.start.as_deref()).unwrap_or(0))
+        .take(parse_number(params.limit.as_deref()).unwrap_or(usize::MAX))
+        .map(|(index, r)| {
+            let size = r.size_bytes.unwrap_or(0);
+            let left = (size - r.downloaded_bytes.unwrap_or(0)).max(0);
+            json!({
+                "index": index,
+                "nzo_id": r.id.to_string(),
+                "filename": r.nzb_name,
+                "cat": category_of(&categories, r.id),
+                "status": slot_status(&r.state),
+                "mb": format!("{:.2}", megabytes(size)),
+                "mbleft": format!("{:.2}", megabytes(left)),
+                "percentage": progress_percent(r).to_string(),
+                "timeleft": format_timeleft(r.eta_seconds),
+                "priority": "Normal",
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "queue": {
+            "status": if speed > 0 { "Downloading" } else { "Idle" },
+            "paused": false,
+            "kbpersec": format!("{:.2}", speed as f64 / 1024.0),
+            "speed": format!("{:.1} K", speed as f64 / 1024.0),
+            "noofslots": active.len(),
+            "noofslots_total": active.len(),
+            "slots": slots,
+        }
+    }))
+}
+
+/// `history`: completed and failed downloads, newest first
+async fn history(db: &Database, user_id: Uuid, params: &SabnzbdParams) -> anyhow::Result<Value> {
+    let (records, categories) = user_downloads(db, user_id).await?;
+    let finished: Vec<&UsenetDownloadRecord> = records
+        .iter()
+        .filter(|r| is_history_state(&r.state))
+        .collect();
+
+    let slots: Vec<Value> = finished
+        .iter()
+        .skip(parse_number(params.start.as_deref()).unwrap_or(0))
+        .take(parse_number(params.limit.as_deref()).unwrap_or(usize::MAX))
+        .map(|r| {
+            let completed = r.completed_at.unwrap_or(r.updated_at);
+            json!({
+                "nzo_id": r.id.to_string(),
+                "name": r.nzb_name,
+                "nzb_name": format!("{}.nzb", r.nzb_name),
+                "category": category_of(&categories, r.id),
+                "status": slot_status(&r.state),
+                "fail_message": r.error_message.clone().unwrap_or_default(),
+                "bytes": r.size_bytes.or(r.downloaded_bytes).unwrap_or(0),
+                "storage": r.download_path,
+                "download_time": (completed - r.created_at).num_seconds().max(0),
+                "completed": completed.timestamp(),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "history": {
+            "noofslots": finished.len(),
+            "slots": slots,
+        }
+    }))
+}
+
+/// `name=delete`: remove downloads from the queue or history
+async fn delete(
+    db: &Database,
+    usenet_service: &UsenetService,
+    user_id: Uuid,
+    params: &SabnzbdParams,
+) -> anyhow::Result<Value> {
+    let delete_files = params.del_files.as_deref() == Some("1");
+    let mut removed = Vec::new();
+
+    for id in parse_ids(params.value.as_deref().unwrap_or_default()) {
+        let Some(record) = db.usenet_downloads().get(id).await? else {
+            continue;
+        };
+        if record.user_id != user_id {
+            continue;
+        }
+
+        usenet_service.remove(id, delete_files).await?;
+        removed.push(id.to_string());
+    }
+
+    Ok(json!({ "status": true, "nzo_ids": removed }))
+}
+
+/// A user's downloads, newest first, with the categories apps gave them
+async fn user_downloads(
+    db: &Database,
+    user_id: Uuid,
+) -> anyhow::Result<(Vec<UsenetDownloadRecord>, HashMap<Uuid, Option<String>>)> {
+    let records = db.usenet_downloads().list_by_user(user_id).await?;
+    let categories = db
+        .sabnzbd()
+        .list_categories(user_id)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok((records, categories))
+}
+
+fn category_of(categories: &HashMap<Uuid, Option<String>>, id: Uuid) -> String {
+    categories
+        .get(&id)
+        .cloned()
+        .flatten()
+        .unwrap_or_else(|| "*".to_string())
+}
+
+fn progress_percent(record: &UsenetDownloadRecord) -> u32 {
+    record
+        .progress
+        .and_then(|p| p.to_string().parse::<f64>().ok())
+        .unwrap_or(0.0)
+        .clamp(0.0, 100.0)
+        .round() as u32
+}
+
+fn megabytes(bytes: i64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
+/// Whether a download state belongs in the history rather than the queue
+pub fn is_history_state(state: &str) -> bool {
+    matches!(state, "completed" | "failed" | "repair_failed")
+}
+
+/// SABnzbd's name for a download state
+pub fn slot_status(state: &str) -> &'static str {
+    match state {
+        "downloading" => "Downloading",
+        "paused" => "Paused",
+        "repairing" => "Repairing",
+        "completed" => "Completed",
+        "failed" | "repair_failed" => "Failed",
+        _ => "Queued",
+    }
+}
+
+/// Time left as SABnzbd formats it, `h:mm:ss`
+pub fn format_timeleft(eta_seconds: Option<i32>) -> String {
+    let seconds = eta_seconds.unwrap_or(0).max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Download IDs from a comma-separated `value`, skipping anything else
+pub fn parse_ids(value: &str) -> Vec<Uuid> {
+    value
+        .split(',')
+        .filter_map(|id| Uuid::parse_str(id.trim()).ok())
+        .collect()
+}
+
+fn parse_number(value: Option<&str>) -> Option<usize> {
+    value.and_then(|v| v.trim().parse().ok())
+}
+
+fn error_response(message: &str) -> Response {
+    // SABnzbd returns 200 even for errors
+    Json(json!({ "status": false, "error": message })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_status() {
+        assert_eq!(slot_status("queued"), "Queued");
+        assert_eq!(slot_status("downloading"), "Downloading");
+        assert_eq!(slot_status("paused"), "Paused");
+        assert_eq!(slot_status("completed"), "Completed");
+        assert_eq!(slot_status("failed"), "Failed");
+        assert_eq!(slot_status("repair_failed"), "Failed");
+    }
+
+    #[test]
+    fn test_history_states() {
+        assert!(is_history_state("completed"));
+        assert!(is_history_state("failed"));
+        assert!(is_history_state("repair_failed"));
+        assert!(!is_history_state("downloading"));
+        assert!(!is_history_state("paused"));
+    }
+
+    #[test]
+    fn test_format_timeleft() {
+        assert_eq!(format_timeleft(None), "0:00:00");
+        assert_eq!(format_timeleft(Some(59)), "0:00:59");
+        assert_eq!(format_timeleft(Some(3725)), "1:02:05");
+        assert_eq!(format_timeleft(Some(-5)), "0:00:00");
+    }
+
+    #[test]
+    fn test_parse_ids() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        assert_eq!(parse_ids(&format!("{}, {}", a, b)), vec![a, b]);
+        assert_eq!(parse_ids(&format!("SABnzbd_nzo_x,{}", a)), vec![a]);
+        assert!(parse_ids("").is_empty());
+    }
+}
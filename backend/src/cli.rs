@@ -1,12 +1,19 @@
-//! Minimal CLI parsing for run mode overrides.
+//! Minimal CLI parsing for run mode overrides and offline tooling flags.
 
 use std::env;
+use std::path::PathBuf;
 
 use crate::app_mode::RunMode;
 
 #[derive(Debug, Default)]
 pub struct CliOptions {
     pub run_mode_override: Option<RunMode>,
+    /// Write TypeScript types for the GraphQL schema to this path and exit
+    pub emit_ts: Option<PathBuf>,
+    /// Populate the database with a fake demo library on startup
+    pub seed_demo: bool,
+    /// Print the version and exit
+    pub version: bool,
 }
 
 impl CliOptions {
@@ -18,11 +25,23 @@ impl CliOptions {
                 "--tray" => options.run_mode_override = Some(RunMode::Tray),
                 "--service" => options.run_mode_override = Some(RunMode::Service),
                 "--server" => options.run_mode_override = Some(RunMode::Server),
+                "--seed-demo" => options.seed_demo = true,
+                "--version" | "-V" => options.version = true,
                 "--run-mode" => {
                     if let Some(value) = args.next() {
                         options.run_mode_override = RunMode::from_arg(&value);
                     }
                 }
+                "--emit-ts" => {
+                    if let Some(value) = args.next() {
+                        options.emit_ts = Some(PathBuf::from(value));
+                    }
+                }
+                _ if arg.starts_with("--emit-ts=") => {
+                    if let Some(value) = arg.split_once('=').map(|(_, v)| v) {
+                        options.emit_ts = Some(PathBuf::from(value));
+                    }
+                }
                 _ if arg.starts_with("--run-mode=") => {
                     if let Some(value) = arg.split_once('=').map(|(_, v)| v) {
                         options.run_mode_override = RunMode::from_arg(value);
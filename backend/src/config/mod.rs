@@ -1,6 +1,7 @@
 //! Application configuration management
 
 use std::env;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 
@@ -54,6 +55,46 @@ pub struct Config {
 
     /// Auto-start tray on login (Windows)
     pub tray_autostart: bool,
+
+    /// Allow GraphQL introspection and the GraphiQL playground
+    /// (disable when the server is exposed publicly)
+    pub graphql_introspection: bool,
+
+    /// Time limits for GraphQL operations
+    pub graphql_timeouts: GraphqlTimeouts,
+}
+
+/// How long a GraphQL operation may run before it's cancelled, by class
+///
+/// None means no limit (the variable was set to 0).
+#[derive(Debug, Clone, Copy)]
+pub struct GraphqlTimeouts {
+    /// Everything not in another class
+    pub default: Option<Duration>,
+    /// Interactive indexer searches
+    pub search: Option<Duration>,
+    /// ffprobe analysis of a file
+    pub analysis: Option<Duration>,
+}
+
+impl GraphqlTimeouts {
+    /// Read GRAPHQL_TIMEOUT_SECS, GRAPHQL_SEARCH_TIMEOUT_SECS and
+    /// GRAPHQL_ANALYSIS_TIMEOUT_SECS
+    pub fn from_env() -> Self {
+        Self {
+            default: timeout_from_env("GRAPHQL_TIMEOUT_SECS", 60),
+            search: timeout_from_env("GRAPHQL_SEARCH_TIMEOUT_SECS", 120),
+            analysis: timeout_from_env("GRAPHQL_ANALYSIS_TIMEOUT_SECS", 300),
+        }
+    }
+}
+
+fn timeout_from_env(key: &str, default_secs: u64) -> Option<Duration> {
+    let secs = env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default_secs);
+    (secs > 0).then(|| Duration::from_secs(secs))
 }
 
 impl Config {
@@ -122,6 +163,12 @@ impl Config {
             tray_autostart: env::var("TRAY_AUTOSTART")
                 .map(|v| v == "true" || v == "1")
                 .unwrap_or(false),
+
+            graphql_introspection: env::var("GRAPHQL_INTROSPECTION")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(true),
+
+            graphql_timeouts: GraphqlTimeouts::from_env(),
         })
     }
 }
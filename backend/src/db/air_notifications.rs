@@ -0,0 +1,201 @@
+//! Episode air-time notification database operations
+//!
+//! Stores per-show lead-time overrides and a ledger of airings that have
+//! already been announced, so the air notification job never repeats itself.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_uuid, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A show that is eligible for air-time notifications
+#[derive(Debug, Clone)]
+pub struct AirNotificationShow {
+    pub library_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub tvmaze_id: i32,
+    /// Per-show lead time (None = inherit the global setting, 0 = disabled)
+    pub lead_hours_override: Option<i32>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for AirNotificationShow {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let library_id_str: String = row.try_get("library_id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+
+        Ok(Self {
+            library_id: str_to_uuid(&library_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            name: row.try_get("name")?,
+            tvmaze_id: row.try_get("tvmaze_id")?,
+            lead_hours_override: row.try_get("lead_hours")?,
+        })
+    }
+}
+
+/// A per-show lead-time override
+#[derive(Debug, Clone)]
+pub struct AirNotificationOverrideRecord {
+    pub tv_show_id: Uuid,
+    pub show_name: String,
+    pub lead_hours: i32,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for AirNotificationOverrideRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("tv_show_id")?;
+
+        Ok(Self {
+            tv_show_id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            show_name: row.try_get("show_name")?,
+            lead_hours: row.try_get("lead_hours")?,
+        })
+    }
+}
+
+/// Air notification repository for database operations
+pub struct AirNotificationRepository {
+    pool: DbPool,
+}
+
+impl AirNotificationRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List monitored shows with a TVMaze ID, joined with their override (if any)
+    #[cfg(feature = "sqlite")]
+    pub async fn list_candidate_shows(&self) -> Result<Vec<AirNotificationShow>> {
+        let records = sqlx::query_as::<_, AirNotificationShow>(
+            r#"
+            SELECT s.library_id, s.user_id, s.name, s.tvmaze_id, o.lead_hours
+            FROM tv_shows s
+            LEFT JOIN episode_air_notification_overrides o ON o.tv_show_id = s.id
+            WHERE s.monitored = 1 AND s.tvmaze_id IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// List all per-show overrides
+    #[cfg(feature = "sqlite")]
+    pub async fn list_overrides(&self) -> Result<Vec<AirNotificationOverrideRecord>> {
+        let records = sqlx::query_as::<_, AirNotificationOverrideRecord>(
+            r#"
+            SELECT o.tv_show_id, s.name AS show_name, o.lead_hours
+            FROM episode_air_notification_overrides o
+            JOIN tv_shows s ON s.id = o.tv_show_id
+            ORDER BY s.name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Set the lead time for a show (0 disables notifications for the show)
+    #[cfg(feature = "sqlite")]
+    pub async fn set_override(&self, tv_show_id: Uuid, lead_hours: i32) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO episode_air_notification_overrides (tv_show_id, lead_hours, created_at, updated_at)
+            VALUES (?1, ?2, datetime('now'), datetime('now'))
+            ON CONFLICT (tv_show_id) DO UPDATE SET
+                lead_hours = ?2,
+                updated_at = datetime('now')
+            "#,
+        )
+        .bind(uuid_to_str(tv_show_id))
+        .bind(lead_hours)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove a show's override so it inherits the global lead time again
+    #[cfg(feature = "sqlite")]
+    pub async fn clear_override(&self, tv_show_id: Uuid) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM episode_air_notification_overrides WHERE tv_show_id = ?1")
+                .bind(uuid_to_str(tv_show_id))
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that an airing was announced to a user
+    ///
+    /// Returns false if it had already been recorded (another run got there first).
+    #[cfg(feature = "sqlite")]
+    pub async fn mark_sent(
+        &self,
+        user_id: Uuid,
+        tvmaze_episode_id: i32,
+        air_stamp: Option<&str>,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO episode_air_notifications_sent (id, user_id, tvmaze_episode_id, air_stamp, sent_at)
+            VALUES (?1, ?2, ?3, ?4, datetime('now'))
+            ON CONFLICT (user_id, tvmaze_episode_id) DO NOTHING
+            "#,
+        )
+        .bind(uuid_to_str(Uuid::new_v4()))
+        .bind(uuid_to_str(user_id))
+        .bind(tvmaze_episode_id)
+        .bind(air_stamp)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Check whether an airing was already announced to a user
+    #[cfg(feature = "sqlite")]
+    pub async fn was_sent(&self, user_id: Uuid, tvmaze_episode_id: i32) -> Result<bool> {
+        let count = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT COUNT(*) FROM episode_air_notifications_sent
+            WHERE user_id = ?1 AND tvmaze_episode_id = ?2
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(tvmaze_episode_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count > 0)
+    }
+
+    /// Prune ledger entries older than the cutoff
+    #[cfg(feature = "sqlite")]
+    pub async fn delete_sent_before(&self, before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM episode_air_notifications_sent WHERE sent_at < ?1")
+            .bind(before.format("%Y-%m-%d %H:%M:%S").to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
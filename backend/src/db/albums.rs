@@ -251,6 +251,20 @@ impl AlbumRepository {
         Ok(())
     }
 
+    /// Replace an album's cover URL
+    #[cfg(feature = "sqlite")]
+    pub async fn set_cover_url(&self, id: Uuid, cover_url: Option<&str>) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query("UPDATE albums SET cover_url = ?2, updated_at = datetime('now') WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .bind(cover_url)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Get album by MusicBrainz ID within a library
     #[cfg(feature = "sqlite")]
     pub async fn get_by_musicbrainz_id(
@@ -432,7 +446,7 @@ impl AlbumRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        name_filter: Option<&str>,
+        name_filter: &crate::db::sqlite_helpers::TextFilter,
         year_filter: Option<i32>,
         has_files_filter: Option<bool>,
         sort_column: &str,
@@ -443,10 +457,8 @@ impl AlbumRepository {
         let mut conditions = vec!["library_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if name_filter.is_some() {
-            conditions.push(format!("LOWER(name) LIKE ?{}", param_idx));
-            param_idx += 1;
-        }
+        let (name_conditions, name_binds) = name_filter.to_sql("name", &mut param_idx);
+        conditions.extend(name_conditions);
         if year_filter.is_some() {
             conditions.push(format!("year = ?{}", param_idx));
             param_idx += 1;
@@ -498,8 +510,8 @@ impl AlbumRepository {
         let library_id_str = uuid_to_str(library_id);
 
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            count_builder = count_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            count_builder = count_builder.bind(value);
         }
         if let Some(year) = year_filter {
             count_builder = count_builder.bind(year);
@@ -512,8 +524,8 @@ impl AlbumRepository {
 
         let mut data_builder =
             sqlx::query_as::<_, AlbumRecord>(&data_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            data_builder = data_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            data_builder = data_builder.bind(value);
         }
         if let Some(year) = year_filter {
             data_builder = data_builder.bind(year);
@@ -555,7 +567,7 @@ impl AlbumRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        name_filter: Option<&str>,
+        name_filter: &crate::db::sqlite_helpers::TextFilter,
         sort_column: &str,
         sort_asc: bool,
     ) -> Result<(Vec<ArtistRecord>, i64)> {
@@ -563,9 +575,9 @@ impl AlbumRepository {
 
         let mut conditions = vec!["library_id = ?1".to_string()];
 
-        if name_filter.is_some() {
-            conditions.push("LOWER(name) LIKE ?2".to_string());
-        }
+        let mut param_idx = 2;
+        let (name_conditions, name_binds) = name_filter.to_sql("name", &mut param_idx);
+        conditions.extend(name_conditions);
 
         let where_clause = conditions.join(" AND ");
 
@@ -597,16 +609,16 @@ impl AlbumRepository {
         let library_id_str = uuid_to_str(library_id);
 
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            count_builder = count_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            count_builder = count_builder.bind(value);
         }
 
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
 
         let mut data_builder =
             sqlx::query_as::<_, ArtistRecord>(&data_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            data_builder = data_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            data_builder = data_builder.bind(value);
         }
 
         let records = data_builder.fetch_all(&self.pool).await?;
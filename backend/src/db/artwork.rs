@@ -28,6 +28,8 @@ pub struct ArtworkRecord {
     pub source_url: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    /// Blurhash placeholder (None until computed)
+    pub blurhash: Option<String>,
     pub created_at: String,
 }
 
@@ -48,6 +50,7 @@ pub struct UpsertArtwork {
     pub source_url: Option<String>,
     pub width: Option<i32>,
     pub height: Option<i32>,
+    pub blurhash: Option<String>,
 }
 
 // ============================================================================
@@ -73,9 +76,9 @@ impl ArtworkRepository {
             r#"
             INSERT INTO artwork_cache (
                 id, entity_type, entity_id, artwork_type, content_hash, 
-                mime_type, data, size_bytes, source_url, width, height, created_at
+                mime_type, data, size_bytes, source_url, width, height, blurhash, created_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(entity_type, entity_id, artwork_type) DO UPDATE SET
                 content_hash = excluded.content_hash,
                 mime_type = excluded.mime_type,
@@ -83,7 +86,8 @@ impl ArtworkRepository {
                 size_bytes = excluded.size_bytes,
                 source_url = excluded.source_url,
                 width = excluded.width,
-                height = excluded.height
+                height = excluded.height,
+                blurhash = excluded.blurhash
             "#,
         )
         .bind(&id)
@@ -97,6 +101,7 @@ impl ArtworkRepository {
         .bind(&artwork.source_url)
         .bind(artwork.width)
         .bind(artwork.height)
+        .bind(&artwork.blurhash)
         .bind(&now)
         .execute(&self.pool)
         .await?;
@@ -113,10 +118,10 @@ impl ArtworkRepository {
         entity_id: &str,
         artwork_type: &str,
     ) -> Result<Option<ArtworkRecord>> {
-        let row = sqlx::query_as::<_, (String, String, String, String, String, String, i64, Option<String>, Option<i32>, Option<i32>, String)>(
+        let row = sqlx::query_as::<_, (String, String, String, String, String, String, i64, Option<String>, Option<i32>, Option<i32>, Option<String>, String)>(
             r#"
             SELECT id, entity_type, entity_id, artwork_type, content_hash, 
-                   mime_type, size_bytes, source_url, width, height, created_at
+                   mime_type, size_bytes, source_url, width, height, blurhash, created_at
             FROM artwork_cache 
             WHERE entity_type = ? AND entity_id = ? AND artwork_type = ?
             "#,
@@ -138,7 +143,8 @@ impl ArtworkRepository {
             source_url: r.7,
             width: r.8,
             height: r.9,
-            created_at: r.10,
+            blurhash: r.10,
+            created_at: r.11,
         }))
     }
 
@@ -149,10 +155,10 @@ impl ArtworkRepository {
         entity_id: &str,
         artwork_type: &str,
     ) -> Result<Option<ArtworkWithData>> {
-        let row = sqlx::query_as::<_, (String, String, String, String, String, String, Vec<u8>, i64, Option<String>, Option<i32>, Option<i32>, String)>(
+        let row = sqlx::query_as::<_, (String, String, String, String, String, String, Vec<u8>, i64, Option<String>, Option<i32>, Option<i32>, Option<String>, String)>(
             r#"
             SELECT id, entity_type, entity_id, artwork_type, content_hash, 
-                   mime_type, data, size_bytes, source_url, width, height, created_at
+                   mime_type, data, size_bytes, source_url, width, height, blurhash, created_at
             FROM artwork_cache 
             WHERE entity_type = ? AND entity_id = ? AND artwork_type = ?
             "#,
@@ -175,7 +181,8 @@ impl ArtworkRepository {
                 source_url: r.8,
                 width: r.9,
                 height: r.10,
-                created_at: r.11,
+                blurhash: r.11,
+                created_at: r.12,
             },
             data: r.6,
         }))
@@ -199,10 +206,10 @@ impl ArtworkRepository {
         entity_type: &str,
         entity_id: &str,
     ) -> Result<Vec<ArtworkRecord>> {
-        let rows = sqlx::query_as::<_, (String, String, String, String, String, String, i64, Option<String>, Option<i32>, Option<i32>, String)>(
+        let rows = sqlx::query_as::<_, (String, String, String, String, String, String, i64, Option<String>, Option<i32>, Option<i32>, Option<String>, String)>(
             r#"
             SELECT id, entity_type, entity_id, artwork_type, content_hash, 
-                   mime_type, size_bytes, source_url, width, height, created_at
+                   mime_type, size_bytes, source_url, width, height, blurhash, created_at
             FROM artwork_cache 
             WHERE entity_type = ? AND entity_id = ?
             "#,
@@ -223,7 +230,8 @@ impl ArtworkRepository {
             source_url: r.7,
             width: r.8,
             height: r.9,
-            created_at: r.10,
+            blurhash: r.10,
+            created_at: r.11,
         }).collect())
     }
 
@@ -259,6 +267,53 @@ impl ArtworkRepository {
         Ok(result.rows_affected() > 0)
     }
 
+    /// Get just the blurhash for an artwork item
+    pub async fn get_blurhash(
+        &self,
+        entity_type: &str,
+        entity_id: &str,
+        artwork_type: &str,
+    ) -> Result<Option<String>> {
+        let row = sqlx::query_as::<_, (Option<String>,)>(
+            r#"
+            SELECT blurhash FROM artwork_cache
+            WHERE entity_type = ? AND entity_id = ? AND artwork_type = ?
+            "#,
+        )
+        .bind(entity_type)
+        .bind(entity_id)
+        .bind(artwork_type)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.and_then(|r| r.0).filter(|h| !h.is_empty()))
+    }
+
+    /// List artwork that has no blurhash yet, with image data (for backfilling)
+    pub async fn list_missing_blurhash(&self, limit: i64) -> Result<Vec<(String, Vec<u8>)>> {
+        let rows = sqlx::query_as::<_, (String, Vec<u8>)>(
+            "SELECT id, data FROM artwork_cache WHERE blurhash IS NULL LIMIT ?"
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
+    /// Set the blurhash for an artwork item
+    ///
+    /// An empty string marks images that could not be decoded so they are not retried.
+    pub async fn set_blurhash(&self, id: &str, blurhash: &str) -> Result<()> {
+        sqlx::query("UPDATE artwork_cache SET blurhash = ? WHERE id = ?")
+            .bind(blurhash)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Get total storage used by artwork
     pub async fn total_storage_bytes(&self) -> Result<i64> {
         let row = sqlx::query_as::<_, (Option<i64>,)>(
@@ -482,7 +482,7 @@ impl AudiobookRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        title_filter: Option<&str>,
+        title_filter: &crate::db::sqlite_helpers::TextFilter,
         has_files_filter: Option<bool>,
         sort_column: &str,
         sort_asc: bool,
@@ -492,10 +492,8 @@ impl AudiobookRepository {
         let mut conditions = vec!["library_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if title_filter.is_some() {
-            conditions.push(format!("LOWER(title) LIKE ?{}", param_idx));
-            param_idx += 1;
-        }
+        let (title_conditions, title_binds) = title_filter.to_sql("title", &mut param_idx);
+        conditions.extend(title_conditions);
         if has_files_filter.is_some() {
             conditions.push(format!("has_files = ?{}", param_idx));
         }
@@ -543,8 +541,8 @@ impl AudiobookRepository {
         let library_id_str = uuid_to_str(library_id);
         
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            count_builder = count_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            count_builder = count_builder.bind(value);
         }
         if let Some(has_files) = has_files_filter {
             count_builder = count_builder.bind(bool_to_int(has_files));
@@ -553,8 +551,8 @@ impl AudiobookRepository {
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
 
         let mut data_builder = sqlx::query_as::<_, AudiobookRecord>(&data_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            data_builder = data_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            data_builder = data_builder.bind(value);
         }
         if let Some(has_files) = has_files_filter {
             data_builder = data_builder.bind(bool_to_int(has_files));
@@ -597,7 +595,7 @@ impl AudiobookRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        name_filter: Option<&str>,
+        name_filter: &crate::db::sqlite_helpers::TextFilter,
         sort_column: &str,
         sort_asc: bool,
     ) -> Result<(Vec<AudiobookAuthorRecord>, i64)> {
@@ -605,9 +603,9 @@ impl AudiobookRepository {
         
         let mut conditions = vec!["library_id = ?1".to_string()];
 
-        if name_filter.is_some() {
-            conditions.push("LOWER(name) LIKE ?2".to_string());
-        }
+        let mut param_idx = 2;
+        let (name_conditions, name_binds) = name_filter.to_sql("name", &mut param_idx);
+        conditions.extend(name_conditions);
 
         let where_clause = conditions.join(" AND ");
 
@@ -639,16 +637,16 @@ impl AudiobookRepository {
         let library_id_str = uuid_to_str(library_id);
         
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            count_builder = count_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            count_builder = count_builder.bind(value);
         }
 
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
 
         let mut data_builder =
             sqlx::query_as::<_, AudiobookAuthorRecord>(&data_query).bind(&library_id_str);
-        if let Some(name) = name_filter {
-            data_builder = data_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            data_builder = data_builder.bind(value);
         }
 
         let records = data_builder.fetch_all(&self.pool).await?;
@@ -0,0 +1,129 @@
+//! Audit log database operations
+//!
+//! One row per create, update or delete made through a mutation, with the
+//! user who made it and the fields that changed. Rows are only ever
+//! inserted; history is browsed per entity or per user.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_datetime, str_to_uuid, str_to_uuid_opt, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Audit log entry record
+#[derive(Debug, Clone)]
+pub struct AuditLogRecord {
+    pub id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    /// 'create', 'update' or 'delete'
+    pub action: String,
+    /// Changed fields, each `{ "from": ..., "to": ... }`
+    pub changes: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for AuditLogRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: Option<String> = row.try_get("user_id")?;
+        let entity_id_str: String = row.try_get("entity_id")?;
+        let changes: String = row.try_get("changes")?;
+        let created_str: String = row.try_get("created_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid_opt(user_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            entity_type: row.try_get("entity_type")?,
+            entity_id: str_to_uuid(&entity_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            action: row.try_get("action")?,
+            changes: serde_json::from_str(&changes).unwrap_or_default(),
+            created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Input for recording an audit log entry
+#[derive(Debug)]
+pub struct CreateAuditLogEntry {
+    pub user_id: Option<Uuid>,
+    pub entity_type: String,
+    pub entity_id: Uuid,
+    pub action: String,
+    pub changes: serde_json::Value,
+}
+
+/// Audit log repository for database operations
+pub struct AuditLogRepository {
+    pool: DbPool,
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an entry
+    #[cfg(feature = "sqlite")]
+    pub async fn create(&self, input: CreateAuditLogEntry) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_log (id, user_id, entity_type, entity_id, action, changes, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'))
+            "#,
+        )
+        .bind(uuid_to_str(Uuid::new_v4()))
+        .bind(input.user_id.map(uuid_to_str))
+        .bind(&input.entity_type)
+        .bind(uuid_to_str(input.entity_id))
+        .bind(&input.action)
+        .bind(input.changes.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// List entries, newest first
+    ///
+    /// Every filter is optional: by user, by entity type, and by entity.
+    #[cfg(feature = "sqlite")]
+    pub async fn list(
+        &self,
+        user_id: Option<Uuid>,
+        entity_type: Option<&str>,
+        entity_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<AuditLogRecord>> {
+        let records = sqlx::query_as::<_, AuditLogRecord>(
+            r#"
+            SELECT * FROM audit_log
+            WHERE (?1 IS NULL OR user_id = ?1)
+              AND (?2 IS NULL OR entity_type = ?2)
+              AND (?3 IS NULL OR entity_id = ?3)
+            ORDER BY created_at DESC, rowid DESC
+            LIMIT ?4
+            "#,
+        )
+        .bind(user_id.map(uuid_to_str))
+        .bind(entity_type)
+        .bind(entity_id.map(uuid_to_str))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
@@ -0,0 +1,140 @@
+//! Automation pause database operations
+//!
+//! A pause stops auto-hunt and RSS polling for one library, or for all of a
+//! user's libraries when `library_id` is None, until it is removed or its
+//! `resume_at` passes.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{
+    datetime_to_str, str_to_datetime, str_to_datetime_opt, str_to_uuid, str_to_uuid_opt,
+    uuid_to_str,
+};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// An automation pause
+#[derive(Debug, Clone)]
+pub struct AutomationPauseRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// None pauses all of the user's libraries
+    pub library_id: Option<Uuid>,
+    /// When the pause ends on its own, if set
+    pub resume_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for AutomationPauseRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+        let library_id_str: Option<String> = row.try_get("library_id")?;
+        let resume_str: Option<String> = row.try_get("resume_at")?;
+        let created_str: String = row.try_get("created_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            library_id: str_to_uuid_opt(library_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            resume_at: str_to_datetime_opt(resume_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Automation pause repository
+pub struct AutomationPauseRepository {
+    pool: DbPool,
+}
+
+impl AutomationPauseRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// All of a user's pauses, including ones whose resume time has passed
+    #[cfg(feature = "sqlite")]
+    pub async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<AutomationPauseRecord>> {
+        let records = sqlx::query_as::<_, AutomationPauseRecord>(
+            r#"
+            SELECT * FROM automation_pauses
+            WHERE user_id = ?1
+            ORDER BY library_id IS NOT NULL, created_at
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Pause a library (or all libraries), replacing any existing pause for
+    /// the same scope
+    #[cfg(feature = "sqlite")]
+    pub async fn set(
+        &self,
+        user_id: Uuid,
+        library_id: Option<Uuid>,
+        resume_at: Option<DateTime<Utc>>,
+    ) -> Result<AutomationPauseRecord> {
+        let id = Uuid::new_v4();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query("DELETE FROM automation_pauses WHERE user_id = ?1 AND library_id IS ?2")
+            .bind(uuid_to_str(user_id))
+            .bind(library_id.map(uuid_to_str))
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO automation_pauses (id, user_id, library_id, resume_at, created_at)
+            VALUES (?1, ?2, ?3, ?4, datetime('now'))
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(user_id))
+        .bind(library_id.map(uuid_to_str))
+        .bind(resume_at.map(datetime_to_str))
+        .execute(&mut *tx)
+        .await?;
+
+        let record = sqlx::query_as::<_, AutomationPauseRecord>(
+            "SELECT * FROM automation_pauses WHERE id = ?1",
+        )
+        .bind(uuid_to_str(id))
+        .fetch_one(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(record)
+    }
+
+    /// Remove the pause for a library (or the global one), returning whether
+    /// there was one
+    #[cfg(feature = "sqlite")]
+    pub async fn clear(&self, user_id: Uuid, library_id: Option<Uuid>) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM automation_pauses WHERE user_id = ?1 AND library_id IS ?2")
+                .bind(uuid_to_str(user_id))
+                .bind(library_id.map(uuid_to_str))
+                .execute(&self.pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
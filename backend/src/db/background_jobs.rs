@@ -0,0 +1,254 @@
+//! Background job database operations
+//!
+//! Heavy mutations enqueue a job here and return its ID instead of running
+//! inline. The job's status, progress and final result are persisted so that
+//! clients can pick the outcome up later, even after reconnecting.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{
+    str_to_datetime, str_to_datetime_opt, str_to_uuid, str_to_uuid_opt, uuid_to_str,
+};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Background job record
+#[derive(Debug, Clone)]
+pub struct BackgroundJobRecord {
+    pub id: Uuid,
+    pub kind: String,
+    /// 'queued', 'running', 'completed', 'failed' or 'interrupted'
+    pub status: String,
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+    pub payload: Option<serde_json::Value>,
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl BackgroundJobRecord {
+    /// Whether the job has stopped (successfully or not)
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "completed" | "failed" | "interrupted")
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for BackgroundJobRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: Option<String> = row.try_get("user_id")?;
+        let payload: Option<String> = row.try_get("payload")?;
+        let result: Option<String> = row.try_get("result")?;
+        let created_str: String = row.try_get("created_at")?;
+        let started_str: Option<String> = row.try_get("started_at")?;
+        let finished_str: Option<String> = row.try_get("finished_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            kind: row.try_get("kind")?,
+            status: row.try_get("status")?,
+            progress: row.try_get("progress")?,
+            message: row.try_get("message")?,
+            payload: payload.and_then(|p| serde_json::from_str(&p).ok()),
+            result: result.and_then(|r| serde_json::from_str(&r).ok()),
+            error: row.try_get("error")?,
+            user_id: str_to_uuid_opt(user_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            started_at: str_to_datetime_opt(started_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            finished_at: str_to_datetime_opt(finished_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Input for enqueuing a job
+#[derive(Debug)]
+pub struct CreateBackgroundJob {
+    pub kind: String,
+    pub payload: Option<serde_json::Value>,
+    pub user_id: Option<Uuid>,
+}
+
+/// Background job repository for database operations
+pub struct BackgroundJobRepository {
+    pool: DbPool,
+}
+
+impl BackgroundJobRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a job by ID
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, id: Uuid) -> Result<Option<BackgroundJobRecord>> {
+        let record =
+            sqlx::query_as::<_, BackgroundJobRecord>("SELECT * FROM background_jobs WHERE id = ?1")
+                .bind(uuid_to_str(id))
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(record)
+    }
+
+    /// List recent jobs, newest first, optionally limited to one user
+    #[cfg(feature = "sqlite")]
+    pub async fn list_recent(
+        &self,
+        user_id: Option<Uuid>,
+        limit: i64,
+    ) -> Result<Vec<BackgroundJobRecord>> {
+        let records = sqlx::query_as::<_, BackgroundJobRecord>(
+            r#"
+            SELECT * FROM background_jobs
+            WHERE ?1 IS NULL OR user_id = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(user_id.map(uuid_to_str))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Enqueue a new job
+    #[cfg(feature = "sqlite")]
+    pub async fn create(&self, input: CreateBackgroundJob) -> Result<BackgroundJobRecord> {
+        let id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO background_jobs (id, kind, status, payload, user_id, created_at)
+            VALUES (?1, ?2, 'queued', ?3, ?4, datetime('now'))
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(&input.kind)
+        .bind(input.payload.as_ref().map(|p| p.to_string()))
+        .bind(input.user_id.map(uuid_to_str))
+        .execute(&self.pool)
+        .await?;
+
+        self.get(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve job after insert"))
+    }
+
+    /// Mark a job as running
+    #[cfg(feature = "sqlite")]
+    pub async fn mark_running(&self, id: Uuid) -> Result<()> {
+        sqlx::query(
+            "UPDATE background_jobs SET status = 'running', started_at = datetime('now') WHERE id = ?1",
+        )
+        .bind(uuid_to_str(id))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record progress (0.0 - 1.0) and an optional status message
+    #[cfg(feature = "sqlite")]
+    pub async fn update_progress(
+        &self,
+        id: Uuid,
+        progress: Option<f64>,
+        message: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE background_jobs SET progress = ?2, message = ?3 WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .bind(progress)
+            .bind(message)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Store the result of a job that completed
+    #[cfg(feature = "sqlite")]
+    pub async fn complete(&self, id: Uuid, result: &serde_json::Value) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE background_jobs SET
+                status = 'completed', progress = 1.0, result = ?2, finished_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(result.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Store the error of a job that failed
+    #[cfg(feature = "sqlite")]
+    pub async fn fail(&self, id: Uuid, error: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE background_jobs SET status = 'failed', error = ?2, finished_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark jobs left queued or running by a previous process as interrupted
+    ///
+    /// Jobs run in-process, so nothing is still working on them after a restart.
+    #[cfg(feature = "sqlite")]
+    pub async fn mark_interrupted(&self) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE background_jobs SET
+                status = 'interrupted',
+                error = 'Server restarted before the job finished',
+                finished_at = datetime('now')
+            WHERE status IN ('queued', 'running')
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Delete finished jobs older than the cutoff
+    #[cfg(feature = "sqlite")]
+    pub async fn delete_finished_before(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM background_jobs WHERE finished_at IS NOT NULL AND finished_at < ?1",
+        )
+        .bind(cutoff.format("%Y-%m-%d %H:%M:%S").to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -264,19 +264,42 @@ impl CastRepository {
         Ok(record)
     }
 
-    /// Get a cast device by address
+    /// Get a cast device by address and port
 
     #[cfg(feature = "sqlite")]
-    pub async fn get_device_by_address(&self, address: &str) -> Result<Option<CastDeviceRecord>> {
+    pub async fn get_device_by_endpoint(
+        &self,
+        address: &str,
+        port: i32,
+    ) -> Result<Option<CastDeviceRecord>> {
         let record = sqlx::query_as::<_, CastDeviceRecord>(
             r#"
             SELECT id, name, address, port, model, device_type, is_favorite, is_manual,
                    last_seen_at, created_at, updated_at
             FROM cast_devices
-            WHERE address = ?1
+            WHERE address = ?1 AND port = ?2
             "#,
         )
         .bind(address)
+        .bind(port)
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(record)
+    }
+
+    /// Get a speaker group by its name
+
+    #[cfg(feature = "sqlite")]
+    pub async fn get_group_by_name(&self, name: &str) -> Result<Option<CastDeviceRecord>> {
+        let record = sqlx::query_as::<_, CastDeviceRecord>(
+            r#"
+            SELECT id, name, address, port, model, device_type, is_favorite, is_manual,
+                   last_seen_at, created_at, updated_at
+            FROM cast_devices
+            WHERE name = ?1 AND device_type = 'speaker_group'
+            "#,
+        )
+        .bind(name)
         .fetch_optional(&self.pool)
         .await?;
         Ok(record)
@@ -310,7 +333,7 @@ impl CastRepository {
             .ok_or_else(|| anyhow::anyhow!("Failed to retrieve device after insert"))
     }
 
-    /// Upsert a cast device (update if exists by address, create if not)
+    /// Upsert a cast device (update if exists by address and port, create if not)
 
     #[cfg(feature = "sqlite")]
     pub async fn upsert_device(&self, input: CreateCastDevice) -> Result<CastDeviceRecord> {
@@ -321,9 +344,8 @@ impl CastRepository {
             r#"
             INSERT INTO cast_devices (id, name, address, port, model, device_type, is_manual, is_favorite, last_seen_at, created_at, updated_at)
             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, datetime('now'), datetime('now'), datetime('now'))
-            ON CONFLICT (address) DO UPDATE SET
+            ON CONFLICT (address, port) DO UPDATE SET
                 name = excluded.name,
-                port = excluded.port,
                 model = excluded.model,
                 device_type = excluded.device_type,
                 last_seen_at = datetime('now'),
@@ -340,11 +362,53 @@ impl CastRepository {
         .execute(&self.pool)
         .await?;
 
-        self.get_device_by_address(&input.address)
+        self.get_device_by_endpoint(&input.address, input.port)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Failed to retrieve device after upsert"))
     }
 
+    /// Upsert a speaker group (update if exists by name, create if not)
+    ///
+    /// Groups move to another address/port whenever a new member takes over as
+    /// leader, so they are matched by name rather than by endpoint.
+
+    #[cfg(feature = "sqlite")]
+    pub async fn upsert_group(&self, input: CreateCastDevice) -> Result<CastDeviceRecord> {
+        let Some(existing) = self.get_group_by_name(&input.name).await? else {
+            return self.upsert_device(input).await;
+        };
+
+        // Another device may still be registered at the new leader's group port
+        sqlx::query("DELETE FROM cast_devices WHERE address = ?1 AND port = ?2 AND id != ?3")
+            .bind(&input.address)
+            .bind(input.port)
+            .bind(uuid_to_str(existing.id))
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            UPDATE cast_devices SET
+                address = ?2,
+                port = ?3,
+                model = ?4,
+                last_seen_at = datetime('now'),
+                updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(existing.id))
+        .bind(&input.address)
+        .bind(input.port)
+        .bind(&input.model)
+        .execute(&self.pool)
+        .await?;
+
+        self.get_device(existing.id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve group after upsert"))
+    }
+
     /// Update a cast device
 
     #[cfg(feature = "sqlite")]
@@ -508,14 +508,15 @@ impl EpisodeRepository {
 
     /// Get upcoming episodes for a user across all libraries
     ///
-    /// Returns episodes with air_date between today and today + days,
-    /// ordered by air date ascending.
-
+    /// Returns episodes with air_date between `from` and `to` (inclusive),
+    /// ordered by air date ascending. The caller picks the dates so "today"
+    /// follows the user's timezone rather than the server's.
     #[cfg(feature = "sqlite")]
     pub async fn list_upcoming_by_user(
         &self,
         user_id: Uuid,
-        days: i32,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
     ) -> Result<Vec<UpcomingEpisodeRecord>> {
         use crate::db::sqlite_helpers::uuid_to_str;
 
@@ -541,14 +542,15 @@ impl EpisodeRepository {
             JOIN libraries l ON l.id = ts.library_id
             WHERE l.user_id = ?1 
               AND ts.monitored = 1
-              AND e.air_date >= date('now')
-              AND e.air_date <= date('now', '+' || ?2 || ' days')
+              AND e.air_date >= ?2
+              AND e.air_date <= ?3
             ORDER BY e.air_date ASC, ts.name ASC, e.season ASC, e.episode ASC
             LIMIT 50
             "#,
         )
         .bind(uuid_to_str(user_id))
-        .bind(days)
+        .bind(from.to_string())
+        .bind(to.to_string())
         .fetch_all(&self.pool)
         .await?;
 
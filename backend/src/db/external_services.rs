@@ -0,0 +1,144 @@
+//! External service status database operations
+//!
+//! The last probe result for each configured external service, written by
+//! the service health prober.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{datetime_to_str, str_to_datetime};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Last known status of an external service
+#[derive(Debug, Clone)]
+pub struct ExternalServiceStatusRecord {
+    /// e.g. "tmdb", "indexer:<id>"
+    pub id: String,
+    /// "tmdb", "tvdb", "opensubtitles", "indexer", "usenet", "smtp" or "cast"
+    pub kind: String,
+    pub name: String,
+    /// "up", "degraded" or "down"
+    pub status: String,
+    pub latency_ms: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_checked_at: DateTime<Utc>,
+    /// When the status last changed
+    pub last_changed_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for ExternalServiceStatusRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let checked_str: String = row.try_get("last_checked_at")?;
+        let changed_str: String = row.try_get("last_changed_at")?;
+
+        Ok(Self {
+            id: row.try_get("id")?,
+            kind: row.try_get("kind")?,
+            name: row.try_get("name")?,
+            status: row.try_get("status")?,
+            latency_ms: row.try_get("latency_ms")?,
+            last_error: row.try_get("last_error")?,
+            last_checked_at: str_to_datetime(&checked_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            last_changed_at: str_to_datetime(&changed_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// A probe result to store
+#[derive(Debug, Clone)]
+pub struct UpsertExternalServiceStatus {
+    pub id: String,
+    pub kind: String,
+    pub name: String,
+    pub status: String,
+    pub latency_ms: Option<i64>,
+    pub last_error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// External service status repository
+pub struct ExternalServicesRepository {
+    pool: DbPool,
+}
+
+impl ExternalServicesRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Every service's last status, by kind then name
+    #[cfg(feature = "sqlite")]
+    pub async fn list(&self) -> Result<Vec<ExternalServiceStatusRecord>> {
+        let records = sqlx::query_as::<_, ExternalServiceStatusRecord>(
+            r#"
+            SELECT id, kind, name, status, latency_ms, last_error,
+                   last_checked_at, last_changed_at
+            FROM external_service_status
+            ORDER BY kind, name
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Store a probe result, keeping the change time if the status is the same
+    #[cfg(feature = "sqlite")]
+    pub async fn upsert(&self, data: UpsertExternalServiceStatus) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO external_service_status
+                (id, kind, name, status, latency_ms, last_error, last_checked_at, last_changed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+            ON CONFLICT(id) DO UPDATE SET
+                kind = excluded.kind,
+                name = excluded.name,
+                last_changed_at = CASE
+                    WHEN external_service_status.status = excluded.status
+                    THEN external_service_status.last_changed_at
+                    ELSE excluded.last_changed_at
+                END,
+                status = excluded.status,
+                latency_ms = excluded.latency_ms,
+                last_error = excluded.last_error,
+                last_checked_at = excluded.last_checked_at
+            "#,
+        )
+        .bind(&data.id)
+        .bind(&data.kind)
+        .bind(&data.name)
+        .bind(&data.status)
+        .bind(data.latency_ms)
+        .bind(&data.last_error)
+        .bind(datetime_to_str(data.checked_at))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove services that aren't in `ids`, i.e. are no longer configured
+    #[cfg(feature = "sqlite")]
+    pub async fn retain(&self, ids: &[String]) -> Result<u64> {
+        let result = sqlx::query(
+            "DELETE FROM external_service_status WHERE id NOT IN (SELECT value FROM json_each(?1))",
+        )
+        .bind(serde_json::to_string(ids)?)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,204 @@
+//! Grab ledger database operations
+//!
+//! One row per release handed to a download client. Pipelines claim a grab
+//! here before starting it, so a release already grabbed by another pipeline
+//! (or the user) is skipped instead of downloaded twice.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_datetime, str_to_uuid, str_to_uuid_opt, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A recorded grab
+#[derive(Debug, Clone)]
+pub struct GrabHistoryRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// 'auto_hunt' or 'manual'
+    pub source: String,
+    /// 'torrent' or 'usenet'
+    pub download_type: String,
+    pub release_title: Option<String>,
+    pub normalized_title: Option<String>,
+    pub info_hash: Option<String>,
+    pub release_guid: Option<String>,
+    pub indexer_id: Option<Uuid>,
+    pub forced: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for GrabHistoryRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+        let indexer_id_str: Option<String> = row.try_get("indexer_id")?;
+        let forced: i32 = row.try_get("forced")?;
+        let created_str: String = row.try_get("created_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            source: row.try_get("source")?,
+            download_type: row.try_get("download_type")?,
+            release_title: row.try_get("release_title")?,
+            normalized_title: row.try_get("normalized_title")?,
+            info_hash: row.try_get("info_hash")?,
+            release_guid: row.try_get("release_guid")?,
+            indexer_id: str_to_uuid_opt(indexer_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            forced: forced != 0,
+            created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Input for claiming a grab
+#[derive(Debug, Clone)]
+pub struct CreateGrab {
+    pub user_id: Uuid,
+    pub source: String,
+    pub download_type: String,
+    pub release_title: Option<String>,
+    pub normalized_title: Option<String>,
+    pub info_hash: Option<String>,
+    pub release_guid: Option<String>,
+    pub indexer_id: Option<Uuid>,
+    /// Record the grab even if it matches an earlier one
+    pub forced: bool,
+}
+
+/// Grab ledger repository
+pub struct GrabHistoryRepository {
+    pool: DbPool,
+}
+
+impl GrabHistoryRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record a grab unless the user already grabbed a release with the same
+    /// info hash, GUID or normalized title
+    ///
+    /// The check and insert are one statement, so two pipelines claiming the
+    /// same release at once can't both succeed. Returns the new row's ID, or
+    /// None if it was a duplicate.
+    #[cfg(feature = "sqlite")]
+    pub async fn claim(&self, input: CreateGrab) -> Result<Option<Uuid>> {
+        let id = Uuid::new_v4();
+
+        let result = sqlx::query(
+            r#"
+            INSERT INTO grab_history (
+                id, user_id, source, download_type, release_title, normalized_title,
+                info_hash, release_guid, indexer_id, forced
+            )
+            SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10
+            WHERE ?10 = 1 OR NOT EXISTS (
+                SELECT 1 FROM grab_history
+                WHERE user_id = ?2
+                  AND ((?7 IS NOT NULL AND info_hash = ?7)
+                    OR (?8 IS NOT NULL AND release_guid = ?8)
+                    OR (?6 IS NOT NULL AND normalized_title = ?6))
+            )
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(input.user_id))
+        .bind(&input.source)
+        .bind(&input.download_type)
+        .bind(&input.release_title)
+        .bind(&input.normalized_title)
+        .bind(&input.info_hash)
+        .bind(&input.release_guid)
+        .bind(input.indexer_id.map(uuid_to_str))
+        .bind(input.forced as i32)
+        .execute(&self.pool)
+        .await?;
+
+        Ok((result.rows_affected() > 0).then_some(id))
+    }
+
+    /// Most recent earlier grab matching any of the keys
+    #[cfg(feature = "sqlite")]
+    pub async fn find_duplicate(
+        &self,
+        user_id: Uuid,
+        info_hash: Option<&str>,
+        release_guid: Option<&str>,
+        normalized_title: Option<&str>,
+    ) -> Result<Option<GrabHistoryRecord>> {
+        let record = sqlx::query_as::<_, GrabHistoryRecord>(
+            r#"
+            SELECT * FROM grab_history
+            WHERE user_id = ?1
+              AND ((?2 IS NOT NULL AND info_hash = ?2)
+                OR (?3 IS NOT NULL AND release_guid = ?3)
+                OR (?4 IS NOT NULL AND normalized_title = ?4))
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(info_hash)
+        .bind(release_guid)
+        .bind(normalized_title)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Fill in the info hash once the download client reports it
+    #[cfg(feature = "sqlite")]
+    pub async fn set_info_hash(&self, id: Uuid, info_hash: &str) -> Result<()> {
+        sqlx::query("UPDATE grab_history SET info_hash = ?2 WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .bind(info_hash.to_lowercase())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Drop a claim whose download never started
+    #[cfg(feature = "sqlite")]
+    pub async fn delete(&self, id: Uuid) -> Result<()> {
+        sqlx::query("DELETE FROM grab_history WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// A user's grabs, newest first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_by_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<GrabHistoryRecord>> {
+        let records = sqlx::query_as::<_, GrabHistoryRecord>(
+            r#"
+            SELECT * FROM grab_history
+            WHERE user_id = ?1
+            ORDER BY created_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
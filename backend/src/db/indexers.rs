@@ -180,6 +180,54 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for IndexerSettingRecord {
     }
 }
 
+/// Transfer totals for the torrents grabbed from an indexer, joined with the
+/// indexer it belongs to
+#[derive(Debug, Clone)]
+pub struct TrackerStatsRecord {
+    pub indexer_id: Uuid,
+    pub user_id: Uuid,
+    pub indexer_name: String,
+    pub indexer_type: String,
+    pub uploaded_bytes: i64,
+    pub downloaded_bytes: i64,
+    pub min_ratio: Option<f64>,
+    pub ratio_warned: bool,
+    /// Tracker error that looked like a rejected passkey, until the passkey
+    /// is updated
+    pub passkey_error: Option<String>,
+    /// When traffic was last recorded (None if never)
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for TrackerStatsRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let indexer_id_str: String = row.try_get("indexer_id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+        let ratio_warned_int: i32 = row.try_get("ratio_warned")?;
+        let updated_at_str: Option<String> = row.try_get("updated_at")?;
+
+        Ok(Self {
+            indexer_id: str_to_uuid(&indexer_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            indexer_name: row.try_get("indexer_name")?,
+            indexer_type: row.try_get("indexer_type")?,
+            uploaded_bytes: row.try_get("uploaded_bytes")?,
+            downloaded_bytes: row.try_get("downloaded_bytes")?,
+            min_ratio: row.try_get("min_ratio")?,
+            ratio_warned: int_to_bool(ratio_warned_int),
+            passkey_error: row.try_get("passkey_error")?,
+            updated_at: updated_at_str
+                .map(|s| str_to_datetime(&s))
+                .transpose()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
 /// Data for creating a new indexer configuration
 #[derive(Debug, Clone)]
 pub struct CreateIndexerConfig {
@@ -720,6 +768,155 @@ impl IndexerRepository {
         Ok(())
     }
 
+    // ========== Tracker Stats ==========
+
+    /// Tracker stats for every indexer a user has configured (indexers with no
+    /// recorded traffic have zero totals)
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_tracker_stats(&self, user_id: Uuid) -> Result<Vec<TrackerStatsRecord>> {
+        let records = sqlx::query_as::<_, TrackerStatsRecord>(
+            r#"
+            SELECT
+                c.id AS indexer_id, c.user_id, c.name AS indexer_name, c.indexer_type,
+                COALESCE(s.uploaded_bytes, 0) AS uploaded_bytes,
+                COALESCE(s.downloaded_bytes, 0) AS downloaded_bytes,
+                s.min_ratio, COALESCE(s.ratio_warned, 0) AS ratio_warned, s.passkey_error,
+                s.updated_at
+            FROM indexer_configs c
+            LEFT JOIN indexer_tracker_stats s ON s.indexer_id = c.id
+            WHERE c.user_id = ?1
+            ORDER BY c.priority DESC, c.name
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Tracker stats for one indexer
+
+    #[cfg(feature = "sqlite")]
+    pub async fn get_tracker_stats(&self, indexer_id: Uuid) -> Result<Option<TrackerStatsRecord>> {
+        let record = sqlx::query_as::<_, TrackerStatsRecord>(
+            r#"
+            SELECT
+                c.id AS indexer_id, c.user_id, c.name AS indexer_name, c.indexer_type,
+                COALESCE(s.uploaded_bytes, 0) AS uploaded_bytes,
+                COALESCE(s.downloaded_bytes, 0) AS downloaded_bytes,
+                s.min_ratio, COALESCE(s.ratio_warned, 0) AS ratio_warned, s.passkey_error,
+                s.updated_at
+            FROM indexer_configs c
+            LEFT JOIN indexer_tracker_stats s ON s.indexer_id = c.id
+            WHERE c.id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(indexer_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Add traffic to an indexer's running totals
+
+    #[cfg(feature = "sqlite")]
+    pub async fn add_tracker_transfer(
+        &self,
+        indexer_id: Uuid,
+        uploaded_bytes: i64,
+        downloaded_bytes: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO indexer_tracker_stats (indexer_id, uploaded_bytes, downloaded_bytes, updated_at)
+            VALUES (?1, ?2, ?3, datetime('now'))
+            ON CONFLICT(indexer_id) DO UPDATE SET
+                uploaded_bytes = uploaded_bytes + excluded.uploaded_bytes,
+                downloaded_bytes = downloaded_bytes + excluded.downloaded_bytes,
+                updated_at = datetime('now')
+            "#,
+        )
+        .bind(uuid_to_str(indexer_id))
+        .bind(uploaded_bytes)
+        .bind(downloaded_bytes)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set (or clear) the minimum ratio a tracker requires
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_min_ratio(&self, indexer_id: Uuid, min_ratio: Option<f64>) -> Result<()> {
+        // A new threshold gets a fresh warning if the ratio is already low
+        sqlx::query(
+            r#"
+            INSERT INTO indexer_tracker_stats (indexer_id, min_ratio)
+            VALUES (?1, ?2)
+            ON CONFLICT(indexer_id) DO UPDATE SET
+                min_ratio = excluded.min_ratio,
+                ratio_warned = 0
+            "#,
+        )
+        .bind(uuid_to_str(indexer_id))
+        .bind(min_ratio)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Record whether a low-ratio warning is outstanding for an indexer
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_ratio_warned(&self, indexer_id: Uuid, warned: bool) -> Result<()> {
+        sqlx::query("UPDATE indexer_tracker_stats SET ratio_warned = ?2 WHERE indexer_id = ?1")
+            .bind(uuid_to_str(indexer_id))
+            .bind(bool_to_int(warned))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Record a passkey error for an indexer, returning false if one was
+    /// already outstanding
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_passkey_error(&self, indexer_id: Uuid, error: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO indexer_tracker_stats (indexer_id, passkey_error)
+            VALUES (?1, ?2)
+            ON CONFLICT(indexer_id) DO UPDATE SET
+                passkey_error = excluded.passkey_error
+            WHERE passkey_error IS NULL
+            "#,
+        )
+        .bind(uuid_to_str(indexer_id))
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Clear an indexer's passkey error once its passkey has been updated
+
+    #[cfg(feature = "sqlite")]
+    pub async fn clear_passkey_error(&self, indexer_id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE indexer_tracker_stats SET passkey_error = NULL WHERE indexer_id = ?1")
+            .bind(uuid_to_str(indexer_id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     // ========== Cache ==========
 
     /// Clean up expired cache entries
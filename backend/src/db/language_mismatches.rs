@@ -0,0 +1,159 @@
+//! Language mismatch database operations
+//!
+//! One row per imported file that is missing a required audio language of
+//! its language profile. Auto-hunt searches again for the items of files
+//! whose row has `regrab_pending` set.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{from_json, str_to_datetime, str_to_uuid, to_json, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A file missing required audio languages
+#[derive(Debug, Clone)]
+pub struct LanguageMismatchRecord {
+    pub media_file_id: Uuid,
+    pub library_id: Uuid,
+    /// ISO 639-1 codes
+    pub missing_audio_languages: Vec<String>,
+    /// Torrent or NZB name the file was imported from
+    pub release_title: Option<String>,
+    /// Auto-hunt should look for a replacement
+    pub regrab_pending: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for LanguageMismatchRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let media_file_id_str: String = row.try_get("media_file_id")?;
+        let library_id_str: String = row.try_get("library_id")?;
+        let missing_json: String = row.try_get("missing_audio_languages")?;
+        let regrab_pending: i32 = row.try_get("regrab_pending")?;
+        let created_at_str: String = row.try_get("created_at")?;
+
+        Ok(Self {
+            media_file_id: str_to_uuid(&media_file_id_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            library_id: str_to_uuid(&library_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            missing_audio_languages: from_json(&missing_json)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            release_title: row.try_get("release_title")?,
+            regrab_pending: regrab_pending != 0,
+            created_at: str_to_datetime(&created_at_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Data for recording a mismatch
+#[derive(Debug, Clone)]
+pub struct UpsertLanguageMismatch {
+    pub media_file_id: Uuid,
+    pub library_id: Uuid,
+    pub missing_audio_languages: Vec<String>,
+    pub release_title: Option<String>,
+    pub regrab_pending: bool,
+}
+
+/// Language mismatch repository
+pub struct LanguageMismatchRepository {
+    pool: DbPool,
+}
+
+impl LanguageMismatchRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the mismatch recorded for a file
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, media_file_id: Uuid) -> Result<Option<LanguageMismatchRecord>> {
+        let record = sqlx::query_as::<_, LanguageMismatchRecord>(
+            "SELECT * FROM language_mismatches WHERE media_file_id = ?1",
+        )
+        .bind(uuid_to_str(media_file_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Record a mismatch, replacing an earlier one for the same file
+    ///
+    /// A re-grab that was already started isn't requested again when the
+    /// file is re-analyzed.
+    #[cfg(feature = "sqlite")]
+    pub async fn upsert(&self, input: UpsertLanguageMismatch) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO language_mismatches (
+                media_file_id, library_id, missing_audio_languages, release_title,
+                regrab_pending
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(media_file_id) DO UPDATE SET
+                missing_audio_languages = excluded.missing_audio_languages,
+                release_title = COALESCE(excluded.release_title, release_title)
+            "#,
+        )
+        .bind(uuid_to_str(input.media_file_id))
+        .bind(uuid_to_str(input.library_id))
+        .bind(to_json(&input.missing_audio_languages))
+        .bind(&input.release_title)
+        .bind(input.regrab_pending as i32)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark a file's re-grab as started
+    #[cfg(feature = "sqlite")]
+    pub async fn clear_regrab(&self, media_file_id: Uuid) -> Result<()> {
+        sqlx::query("UPDATE language_mismatches SET regrab_pending = 0 WHERE media_file_id = ?1")
+            .bind(uuid_to_str(media_file_id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Forget a file's mismatch, returning whether there was one
+    #[cfg(feature = "sqlite")]
+    pub async fn delete(&self, media_file_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM language_mismatches WHERE media_file_id = ?1")
+            .bind(uuid_to_str(media_file_id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// A library's mismatches, newest first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_by_library(&self, library_id: Uuid) -> Result<Vec<LanguageMismatchRecord>> {
+        let records = sqlx::query_as::<_, LanguageMismatchRecord>(
+            r#"
+            SELECT * FROM language_mismatches
+            WHERE library_id = ?1
+            ORDER BY created_at DESC
+            "#,
+        )
+        .bind(uuid_to_str(library_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+}
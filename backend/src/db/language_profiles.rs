@@ -0,0 +1,286 @@
+//! Language profile database operations
+//!
+//! A language profile holds the audio and subtitle language rules for the
+//! libraries and shows it is assigned to. A show's own assignment takes
+//! precedence over its library's; see [`LanguageProfileRepository::get_effective`].
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{from_json, str_to_datetime, str_to_uuid, to_json, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A language profile
+#[derive(Debug, Clone)]
+pub struct LanguageProfileRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Audio languages in order of preference (ISO 639-1)
+    pub preferred_audio_languages: Vec<String>,
+    /// Releases without all of these audio languages are rejected
+    pub required_audio_languages: Vec<String>,
+    /// Subtitle languages every imported file should have
+    pub required_subtitle_languages: Vec<String>,
+    /// Search again when an imported file turns out to lack a required
+    /// audio language
+    pub regrab_missing_audio: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for LanguageProfileRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+        let preferred_audio_json: String = row.try_get("preferred_audio_languages")?;
+        let required_audio_json: String = row.try_get("required_audio_languages")?;
+        let required_subtitle_json: String = row.try_get("required_subtitle_languages")?;
+        let regrab_missing_audio: i32 = row.try_get("regrab_missing_audio")?;
+        let created_at_str: String = row.try_get("created_at")?;
+        let updated_at_str: String = row.try_get("updated_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            name: row.try_get("name")?,
+            preferred_audio_languages: from_json(&preferred_audio_json)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            required_audio_languages: from_json(&required_audio_json)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            required_subtitle_languages: from_json(&required_subtitle_json)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            regrab_missing_audio: regrab_missing_audio != 0,
+            created_at: str_to_datetime(&created_at_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            updated_at: str_to_datetime(&updated_at_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Data for creating a language profile
+#[derive(Debug, Clone)]
+pub struct CreateLanguageProfile {
+    pub user_id: Uuid,
+    pub name: String,
+    pub preferred_audio_languages: Vec<String>,
+    pub required_audio_languages: Vec<String>,
+    pub required_subtitle_languages: Vec<String>,
+    pub regrab_missing_audio: bool,
+}
+
+/// Data for updating a language profile
+#[derive(Debug, Clone, Default)]
+pub struct UpdateLanguageProfile {
+    pub name: Option<String>,
+    pub preferred_audio_languages: Option<Vec<String>>,
+    pub required_audio_languages: Option<Vec<String>>,
+    pub required_subtitle_languages: Option<Vec<String>>,
+    pub regrab_missing_audio: Option<bool>,
+}
+
+/// Language profile repository
+pub struct LanguageProfileRepository {
+    pool: DbPool,
+}
+
+impl LanguageProfileRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// All of a user's profiles, by name
+    #[cfg(feature = "sqlite")]
+    pub async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<LanguageProfileRecord>> {
+        let records = sqlx::query_as::<_, LanguageProfileRecord>(
+            "SELECT * FROM language_profiles WHERE user_id = ?1 ORDER BY name COLLATE NOCASE",
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Get a profile by ID
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, id: Uuid) -> Result<Option<LanguageProfileRecord>> {
+        let record = sqlx::query_as::<_, LanguageProfileRecord>(
+            "SELECT * FROM language_profiles WHERE id = ?1",
+        )
+        .bind(uuid_to_str(id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Create a profile
+    #[cfg(feature = "sqlite")]
+    pub async fn create(&self, input: CreateLanguageProfile) -> Result<LanguageProfileRecord> {
+        let id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO language_profiles (
+                id, user_id, name, preferred_audio_languages,
+                required_audio_languages, required_subtitle_languages, regrab_missing_audio
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(input.user_id))
+        .bind(&input.name)
+        .bind(to_json(&input.preferred_audio_languages))
+        .bind(to_json(&input.required_audio_languages))
+        .bind(to_json(&input.required_subtitle_languages))
+        .bind(input.regrab_missing_audio as i32)
+        .execute(&self.pool)
+        .await?;
+
+        self.get(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve created language profile"))
+    }
+
+    /// Update a profile, returning None if it doesn't exist
+    #[cfg(feature = "sqlite")]
+    pub async fn update(
+        &self,
+        id: Uuid,
+        input: UpdateLanguageProfile,
+    ) -> Result<Option<LanguageProfileRecord>> {
+        sqlx::query(
+            r#"
+            UPDATE language_profiles SET
+                name = COALESCE(?2, name),
+                preferred_audio_languages = COALESCE(?3, preferred_audio_languages),
+                required_audio_languages = COALESCE(?4, required_audio_languages),
+                required_subtitle_languages = COALESCE(?5, required_subtitle_languages),
+                regrab_missing_audio = COALESCE(?6, regrab_missing_audio)
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(&input.name)
+        .bind(input.preferred_audio_languages.as_ref().map(to_json))
+        .bind(input.required_audio_languages.as_ref().map(to_json))
+        .bind(input.required_subtitle_languages.as_ref().map(to_json))
+        .bind(input.regrab_missing_audio.map(|r| r as i32))
+        .execute(&self.pool)
+        .await?;
+
+        self.get(id).await
+    }
+
+    /// Delete a profile; libraries and shows using it fall back to none
+    #[cfg(feature = "sqlite")]
+    pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM language_profiles WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Assign a profile to a library, or clear its assignment
+    #[cfg(feature = "sqlite")]
+    pub async fn assign_to_library(
+        &self,
+        library_id: Uuid,
+        profile_id: Option<Uuid>,
+    ) -> Result<()> {
+        sqlx::query("UPDATE libraries SET language_profile_id = ?2 WHERE id = ?1")
+            .bind(uuid_to_str(library_id))
+            .bind(profile_id.map(uuid_to_str))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Assign a profile to a show, or clear it so the library's applies
+    #[cfg(feature = "sqlite")]
+    pub async fn assign_to_show(&self, show_id: Uuid, profile_id: Option<Uuid>) -> Result<()> {
+        sqlx::query("UPDATE tv_shows SET language_profile_id = ?2 WHERE id = ?1")
+            .bind(uuid_to_str(show_id))
+            .bind(profile_id.map(uuid_to_str))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// The profile assigned directly to a library
+    #[cfg(feature = "sqlite")]
+    pub async fn get_for_library(&self, library_id: Uuid) -> Result<Option<LanguageProfileRecord>> {
+        let record = sqlx::query_as::<_, LanguageProfileRecord>(
+            r#"
+            SELECT lp.* FROM language_profiles lp
+            JOIN libraries l ON l.language_profile_id = lp.id
+            WHERE l.id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(library_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// The profile assigned directly to a show (not inherited)
+    #[cfg(feature = "sqlite")]
+    pub async fn get_for_show(&self, show_id: Uuid) -> Result<Option<LanguageProfileRecord>> {
+        let record = sqlx::query_as::<_, LanguageProfileRecord>(
+            r#"
+            SELECT lp.* FROM language_profiles lp
+            JOIN tv_shows s ON s.language_profile_id = lp.id
+            WHERE s.id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(show_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// The profile that applies to a library, or to a show in it
+    ///
+    /// The show's own profile wins; otherwise the library's is used.
+    #[cfg(feature = "sqlite")]
+    pub async fn get_effective(
+        &self,
+        library_id: Uuid,
+        show_id: Option<Uuid>,
+    ) -> Result<Option<LanguageProfileRecord>> {
+        let record = sqlx::query_as::<_, LanguageProfileRecord>(
+            r#"
+            SELECT * FROM language_profiles
+            WHERE id = COALESCE(
+                (SELECT language_profile_id FROM tv_shows WHERE id = ?2),
+                (SELECT language_profile_id FROM libraries WHERE id = ?1)
+            )
+            "#,
+        )
+        .bind(uuid_to_str(library_id))
+        .bind(show_id.map(uuid_to_str))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+}
@@ -10,7 +10,7 @@ use sqlx::SqlitePool;
 type DbPool = SqlitePool;
 
 /// Library record from database
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct LibraryRecord {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -45,6 +45,14 @@ pub struct LibraryRecord {
     // Subtitle settings
     pub auto_download_subtitles: Option<bool>,
     pub preferred_subtitle_languages: Option<Vec<String>>,
+    // Scanner exclusion globs (relative to the library root)
+    pub exclude_patterns: Vec<String>,
+    // Imported file ownership/permissions (None = inherit global settings)
+    pub file_uid: Option<u32>,
+    pub file_gid: Option<u32>,
+    pub file_mode: Option<String>,
+    // What scans do with files deleted from disk: 'flag', 'unmonitor' or 'rewant'
+    pub missing_file_policy: String,
 }
 
 
@@ -69,6 +77,7 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for LibraryRecord {
         let release_group_blacklist_json: String = row.try_get("release_group_blacklist")?;
         let release_group_whitelist_json: String = row.try_get("release_group_whitelist")?;
         let preferred_subtitle_languages_json: Option<String> = row.try_get("preferred_subtitle_languages")?;
+        let exclude_patterns_json: String = row.try_get("exclude_patterns")?;
         
         // Booleans stored as INTEGER
         let auto_scan: i32 = row.try_get("auto_scan")?;
@@ -116,6 +125,11 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for LibraryRecord {
             release_group_whitelist: json_to_vec(&release_group_whitelist_json),
             auto_download_subtitles: auto_download_subtitles.map(int_to_bool),
             preferred_subtitle_languages: preferred_subtitle_languages_json.map(|s| json_to_vec(&s)),
+            exclude_patterns: json_to_vec(&exclude_patterns_json),
+            file_uid: row.try_get("file_uid")?,
+            file_gid: row.try_get("file_gid")?,
+            file_mode: row.try_get("file_mode")?,
+            missing_file_policy: row.try_get("missing_file_policy")?,
         })
     }
 }
@@ -148,6 +162,11 @@ pub struct CreateLibrary {
     pub allowed_sources: Vec<String>,
     pub release_group_blacklist: Vec<String>,
     pub release_group_whitelist: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+    pub file_uid: Option<u32>,
+    pub file_gid: Option<u32>,
+    pub file_mode: Option<String>,
+    pub missing_file_policy: String,
 }
 
 /// Input for updating a library
@@ -176,6 +195,12 @@ pub struct UpdateLibrary {
     pub allowed_sources: Option<Vec<String>>,
     pub release_group_blacklist: Option<Vec<String>>,
     pub release_group_whitelist: Option<Vec<String>>,
+    pub exclude_patterns: Option<Vec<String>>,
+    // Some(None) clears an override so the global setting applies again
+    pub file_uid: Option<Option<u32>>,
+    pub file_gid: Option<Option<u32>>,
+    pub file_mode: Option<Option<String>>,
+    pub missing_file_policy: Option<String>,
 }
 
 /// Library statistics
@@ -212,7 +237,9 @@ impl LibraryRepository {
                    allowed_resolutions, allowed_video_codecs, allowed_audio_formats,
                    require_hdr, allowed_hdr_types, allowed_sources,
                    release_group_blacklist, release_group_whitelist,
-                   auto_download_subtitles, preferred_subtitle_languages
+                   auto_download_subtitles, preferred_subtitle_languages,
+                   exclude_patterns, file_uid, file_gid, file_mode,
+                   missing_file_policy
             FROM libraries
             WHERE user_id = ?1
             ORDER BY name
@@ -225,6 +252,17 @@ impl LibraryRepository {
         Ok(records)
     }
 
+    /// Name and path of every library, across all users
+    #[cfg(feature = "sqlite")]
+    pub async fn list_paths(&self) -> Result<Vec<(String, String)>> {
+        let rows =
+            sqlx::query_as::<_, (String, String)>("SELECT name, path FROM libraries ORDER BY name")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(rows)
+    }
+
     /// Get a library by ID
 
     #[cfg(feature = "sqlite")]
@@ -241,7 +279,9 @@ impl LibraryRepository {
                    allowed_resolutions, allowed_video_codecs, allowed_audio_formats,
                    require_hdr, allowed_hdr_types, allowed_sources,
                    release_group_blacklist, release_group_whitelist,
-                   auto_download_subtitles, preferred_subtitle_languages
+                   auto_download_subtitles, preferred_subtitle_languages,
+                   exclude_patterns, file_uid, file_gid, file_mode,
+                   missing_file_policy
             FROM libraries
             WHERE id = ?1
             "#,
@@ -273,7 +313,9 @@ impl LibraryRepository {
                    allowed_resolutions, allowed_video_codecs, allowed_audio_formats,
                    require_hdr, allowed_hdr_types, allowed_sources,
                    release_group_blacklist, release_group_whitelist,
-                   auto_download_subtitles, preferred_subtitle_languages
+                   auto_download_subtitles, preferred_subtitle_languages,
+                   exclude_patterns, file_uid, file_gid, file_mode,
+                   missing_file_policy
             FROM libraries
             WHERE id = ?1 AND user_id = ?2
             "#,
@@ -304,11 +346,13 @@ impl LibraryRepository {
                 auto_add_discovered, auto_download, auto_hunt,
                 allowed_resolutions, allowed_video_codecs, allowed_audio_formats,
                 require_hdr, allowed_hdr_types, allowed_sources,
-                release_group_blacklist, release_group_whitelist,
+                release_group_blacklist, release_group_whitelist, exclude_patterns,
+                file_uid, file_gid, file_mode, missing_file_policy,
                 scanning, created_at, updated_at
             )
             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
-                    ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, 0, datetime('now'), datetime('now'))
+                    ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28, ?29, ?30,
+                    0, datetime('now'), datetime('now'))
             "#,
         )
         .bind(&id_str)
@@ -336,6 +380,11 @@ impl LibraryRepository {
         .bind(vec_to_json(&input.allowed_sources))
         .bind(vec_to_json(&input.release_group_blacklist))
         .bind(vec_to_json(&input.release_group_whitelist))
+        .bind(vec_to_json(&input.exclude_patterns))
+        .bind(input.file_uid)
+        .bind(input.file_gid)
+        .bind(&input.file_mode)
+        .bind(&input.missing_file_policy)
         .execute(&self.pool)
         .await?;
 
@@ -383,6 +432,11 @@ impl LibraryRepository {
                 allowed_sources = ?21,
                 release_group_blacklist = ?22,
                 release_group_whitelist = ?23,
+                exclude_patterns = ?24,
+                file_uid = ?25,
+                file_gid = ?26,
+                file_mode = ?27,
+                missing_file_policy = ?28,
                 updated_at = datetime('now')
             WHERE id = ?1
             "#,
@@ -410,6 +464,11 @@ impl LibraryRepository {
         .bind(vec_to_json(&input.allowed_sources.unwrap_or(current.allowed_sources)))
         .bind(vec_to_json(&input.release_group_blacklist.unwrap_or(current.release_group_blacklist)))
         .bind(vec_to_json(&input.release_group_whitelist.unwrap_or(current.release_group_whitelist)))
+        .bind(vec_to_json(&input.exclude_patterns.unwrap_or(current.exclude_patterns)))
+        .bind(input.file_uid.unwrap_or(current.file_uid))
+        .bind(input.file_gid.unwrap_or(current.file_gid))
+        .bind(input.file_mode.unwrap_or(current.file_mode))
+        .bind(input.missing_file_policy.unwrap_or(current.missing_file_policy))
         .execute(&self.pool)
         .await?;
 
@@ -463,7 +522,7 @@ impl LibraryRepository {
 
     #[cfg(feature = "sqlite")]
     pub async fn get_stats(&self, id: Uuid) -> Result<LibraryStats> {
-        use crate::db::sqlite_helpers::uuid_to_str;
+        use crate::db::sqlite_helpers::{path_prefix_sql, uuid_to_str};
         
         let id_str = uuid_to_str(id);
         
@@ -474,27 +533,31 @@ impl LibraryRepository {
                 .fetch_optional(&self.pool)
                 .await?;
 
-        let path_pattern = library_path
-            .map(|p| format!("{}%", p))
-            .unwrap_or_else(|| "%".to_string());
+        // An empty prefix matches every path
+        let library_path = library_path.unwrap_or_default();
+        let in_library = path_prefix_sql("path", "?2");
 
         // Count only files that are within the library path
-        let file_count: i64 = sqlx::query_scalar(
-            "SELECT COUNT(*) FROM media_files WHERE library_id = ?1 AND path LIKE ?2",
-        )
-        .bind(&id_str)
-        .bind(&path_pattern)
-        .fetch_one(&self.pool)
-        .await?;
+        let count_query = format!(
+            "SELECT COUNT(*) FROM media_files WHERE library_id = ?1 AND {}",
+            in_library
+        );
+        let file_count: i64 = sqlx::query_scalar(&count_query)
+            .bind(&id_str)
+            .bind(&library_path)
+            .fetch_one(&self.pool)
+            .await?;
 
         // Sum size only for files within the library path (no ::BIGINT cast in SQLite)
-        let total_size: i64 = sqlx::query_scalar(
-            "SELECT COALESCE(SUM(size), 0) FROM media_files WHERE library_id = ?1 AND path LIKE ?2",
-        )
-        .bind(&id_str)
-        .bind(&path_pattern)
-        .fetch_one(&self.pool)
-        .await?;
+        let size_query = format!(
+            "SELECT COALESCE(SUM(size), 0) FROM media_files WHERE library_id = ?1 AND {}",
+            in_library
+        );
+        let total_size: i64 = sqlx::query_scalar(&size_query)
+            .bind(&id_str)
+            .bind(&library_path)
+            .fetch_one(&self.pool)
+            .await?;
 
         let show_count: i64 =
             sqlx::query_scalar("SELECT COUNT(*) FROM tv_shows WHERE library_id = ?1")
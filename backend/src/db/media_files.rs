@@ -11,7 +11,7 @@ type DbPool = SqlitePool;
 
 #[cfg(feature = "sqlite")]
 use crate::db::sqlite_helpers::{
-    bool_to_int, int_to_bool, str_to_datetime, str_to_uuid, uuid_to_str,
+    bool_to_int, int_to_bool, path_prefix_sql, str_to_datetime, str_to_uuid, uuid_to_str,
 };
 
 /// Media file record from database
@@ -433,6 +433,43 @@ pub struct CreateMediaFile {
     pub hdr_type: Option<String>,
 }
 
+/// A property media files can be grouped by for a library breakdown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakdownDimension {
+    Resolution,
+    VideoCodec,
+    HdrType,
+    AudioLanguage,
+    Container,
+}
+
+impl BreakdownDimension {
+    /// SQL expression for the group key; NULL groups files where it's unknown
+    fn group_expr(&self) -> &'static str {
+        match self {
+            Self::Resolution => "NULLIF(TRIM(resolution), '')",
+            Self::VideoCodec => "LOWER(NULLIF(TRIM(video_codec), ''))",
+            // Only video files have an HDR type; HDR without a known format is 'HDR'
+            Self::HdrType => {
+                "CASE WHEN video_codec IS NULL THEN NULL \
+                 WHEN NULLIF(TRIM(hdr_type), '') IS NOT NULL THEN TRIM(hdr_type) \
+                 WHEN is_hdr = 1 THEN 'HDR' ELSE 'SDR' END"
+            }
+            Self::AudioLanguage => "LOWER(NULLIF(TRIM(audio_language), ''))",
+            Self::Container => "LOWER(NULLIF(TRIM(container), ''))",
+        }
+    }
+}
+
+/// Files sharing one value of a [`BreakdownDimension`]
+#[derive(Debug, Clone)]
+pub struct BreakdownBucket {
+    /// None for files where the value is unknown
+    pub value: Option<String>,
+    pub file_count: i64,
+    pub total_size: i64,
+}
+
 pub struct MediaFileRepository {
     pool: DbPool,
 }
@@ -498,13 +535,15 @@ impl MediaFileRepository {
         Ok(result > 0)
     }
 
-    /// Get a media file by path
+    /// Get a media file by path (case-insensitive on Windows)
 
     #[cfg(feature = "sqlite")]
     pub async fn get_by_path(&self, path: &str) -> Result<Option<MediaFileRecord>> {
+        // Windows filesystems are case-insensitive: "D:\TV\show" and "D:\TV\Show" are one file
+        let collation = if cfg!(windows) { " COLLATE NOCASE" } else { "" };
         let query = format!(
-            "SELECT {} FROM media_files WHERE path = ?1",
-            MEDIA_FILE_COLUMNS
+            "SELECT {} FROM media_files WHERE path = ?1{}",
+            MEDIA_FILE_COLUMNS, collation
         );
         let record = sqlx::query_as::<_, MediaFileRecord>(&query)
             .bind(path)
@@ -760,6 +799,41 @@ impl MediaFileRepository {
         Ok(count as i64)
     }
 
+    /// File count and total size of a library's files, grouped by one
+    /// property, largest groups first
+
+    #[cfg(feature = "sqlite")]
+    pub async fn breakdown_by(
+        &self,
+        library_id: Uuid,
+        dimension: BreakdownDimension,
+    ) -> Result<Vec<BreakdownBucket>> {
+        let query = format!(
+            r#"
+            SELECT {} AS value, COUNT(*) AS file_count, COALESCE(SUM(size), 0) AS total_size
+            FROM media_files
+            WHERE library_id = ?1
+            GROUP BY value
+            ORDER BY file_count DESC, value
+            "#,
+            dimension.group_expr()
+        );
+
+        let rows = sqlx::query_as::<_, (Option<String>, i64, i64)>(&query)
+            .bind(uuid_to_str(library_id))
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(value, file_count, total_size)| BreakdownBucket {
+                value,
+                file_count,
+                total_size,
+            })
+            .collect())
+    }
+
     /// Link a media file to an episode
 
     #[cfg(feature = "sqlite")]
@@ -1037,6 +1111,45 @@ impl MediaFileRepository {
         Ok(records)
     }
 
+    /// Matched video files of a library with no subtitle in a language
+    ///
+    /// `languages` are the lowercase spellings the language may be stored
+    /// under (e.g. "en", "eng", "english").
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_missing_subtitle_language(
+        &self,
+        library_id: Uuid,
+        languages: &[String],
+    ) -> Result<Vec<MediaFileRecord>> {
+        let placeholders: Vec<String> = (0..languages.len())
+            .map(|i| format!("?{}", i + 2))
+            .collect();
+        let query = format!(
+            r#"
+            SELECT {} FROM media_files
+            WHERE library_id = ?1
+              AND (movie_id IS NOT NULL OR episode_id IS NOT NULL)
+              AND NOT EXISTS (
+                  SELECT 1 FROM subtitles s
+                  WHERE s.media_file_id = media_files.id
+                    AND LOWER(s.language) IN ({})
+              )
+            ORDER BY path
+            "#,
+            MEDIA_FILE_COLUMNS,
+            placeholders.join(", ")
+        );
+
+        let mut query_builder =
+            sqlx::query_as::<_, MediaFileRecord>(&query).bind(uuid_to_str(library_id));
+        for language in languages {
+            query_builder = query_builder.bind(language);
+        }
+
+        Ok(query_builder.fetch_all(&self.pool).await?)
+    }
+
     /// Update only the path of a media file
 
     #[cfg(feature = "sqlite")]
@@ -1076,7 +1189,7 @@ impl MediaFileRepository {
         &self,
         library_id: Uuid,
     ) -> Result<Vec<MediaFileRecord>> {
-        let records = sqlx::query_as::<_, MediaFileRecord>(
+        let query = format!(
             r#"
             SELECT mf.id, mf.library_id, mf.path, mf.size as size_bytes, 
                    mf.container, mf.video_codec, mf.audio_codec, mf.width, mf.height,
@@ -1095,13 +1208,15 @@ impl MediaFileRepository {
             WHERE mf.library_id = ?1 
               AND mf.episode_id IS NULL 
               AND mf.movie_id IS NULL
-              AND mf.path LIKE l.path || '%'
+              AND {}
             ORDER BY mf.path
             "#,
-        )
-        .bind(uuid_to_str(library_id))
-        .fetch_all(&self.pool)
-        .await?;
+            path_prefix_sql("mf.path", "l.path")
+        );
+        let records = sqlx::query_as::<_, MediaFileRecord>(&query)
+            .bind(uuid_to_str(library_id))
+            .fetch_all(&self.pool)
+            .await?;
 
         Ok(records)
     }
@@ -1112,7 +1227,7 @@ impl MediaFileRepository {
 
     #[cfg(feature = "sqlite")]
     pub async fn count_unmatched_by_library(&self, library_id: Uuid) -> Result<i64> {
-        let count = sqlx::query_scalar::<_, i32>(
+        let query = format!(
             r#"
             SELECT COUNT(*) 
             FROM media_files mf
@@ -1120,12 +1235,14 @@ impl MediaFileRepository {
             WHERE mf.library_id = ?1 
               AND mf.episode_id IS NULL 
               AND mf.movie_id IS NULL
-              AND mf.path LIKE l.path || '%'
+              AND {}
             "#,
-        )
-        .bind(uuid_to_str(library_id))
-        .fetch_one(&self.pool)
-        .await?;
+            path_prefix_sql("mf.path", "l.path")
+        );
+        let count = sqlx::query_scalar::<_, i32>(&query)
+            .bind(uuid_to_str(library_id))
+            .fetch_one(&self.pool)
+            .await?;
 
         Ok(count as i64)
     }
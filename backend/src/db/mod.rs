@@ -6,12 +6,20 @@
 
 #![allow(unused_imports)]
 
+pub mod air_notifications;
 pub mod albums;
 pub mod artwork;
 pub mod audiobooks;
+pub mod audit_log;
+pub mod automation_pauses;
+pub mod background_jobs;
 pub mod cast;
 pub mod episodes;
+pub mod external_services;
+pub mod grab_history;
 pub mod indexers;
+pub mod language_mismatches;
+pub mod language_profiles;
 pub mod libraries;
 pub mod logs;
 pub mod media_files;
@@ -19,10 +27,16 @@ pub mod notifications;
 pub mod movies;
 pub mod naming_patterns;
 pub mod pending_file_matches;
+pub mod people;
 pub mod playback;
+pub mod playback_streams;
 pub mod priority_rules;
+pub mod qbittorrent;
+pub mod roles;
 pub mod rss_feeds;
+pub mod sabnzbd;
 pub mod schedule;
+pub mod search;
 pub mod settings;
 pub mod sqlite_helpers;
 pub mod subtitles;
@@ -32,6 +46,7 @@ pub mod tracks;
 pub mod tv_shows;
 pub mod usenet_downloads;
 pub mod usenet_servers;
+pub mod user_preferences;
 pub mod users;
 pub mod watch_progress;
 
@@ -44,18 +59,37 @@ use sqlx::sqlite::SqlitePoolOptions as DbPoolOptions;
 // Re-export the pool type for external use
 pub use DbPool as Pool;
 
+pub use air_notifications::{
+    AirNotificationOverrideRecord, AirNotificationRepository, AirNotificationShow,
+};
 pub use albums::{AlbumRecord, AlbumRepository, ArtistRecord};
 pub use artwork::{ArtworkRecord, ArtworkRepository, ArtworkWithData, UpsertArtwork};
 pub use audiobooks::{
     AudiobookAuthorRecord, AudiobookChapterRecord, AudiobookChapterRepository, AudiobookRecord,
     AudiobookRepository, CreateAudiobook, CreateAudiobookChapter,
 };
+pub use audit_log::{AuditLogRecord, AuditLogRepository, CreateAuditLogEntry};
+pub use automation_pauses::{AutomationPauseRecord, AutomationPauseRepository};
+pub use background_jobs::{BackgroundJobRecord, BackgroundJobRepository, CreateBackgroundJob};
 pub use cast::{
     CastDeviceRecord, CastRepository, CastSessionRecord, CastSettingsRecord, CreateCastDevice,
     CreateCastSession, UpdateCastDevice, UpdateCastSession, UpdateCastSettings,
 };
 pub use episodes::{CreateEpisode, EpisodeRecord, EpisodeRepository};
-pub use indexers::{CreateIndexerConfig, IndexerRepository, UpdateIndexerConfig, UpsertCredential};
+pub use external_services::{
+    ExternalServiceStatusRecord, ExternalServicesRepository, UpsertExternalServiceStatus,
+};
+pub use grab_history::{CreateGrab, GrabHistoryRecord, GrabHistoryRepository};
+pub use indexers::{
+    CreateIndexerConfig, IndexerRepository, TrackerStatsRecord, UpdateIndexerConfig,
+    UpsertCredential,
+};
+pub use language_mismatches::{
+    LanguageMismatchRecord, LanguageMismatchRepository, UpsertLanguageMismatch,
+};
+pub use language_profiles::{
+    CreateLanguageProfile, LanguageProfileRecord, LanguageProfileRepository, UpdateLanguageProfile,
+};
 pub use libraries::{CreateLibrary, LibraryRecord, LibraryRepository, LibraryStats, UpdateLibrary};
 pub use logs::{CreateLog, LogFilter, LogsRepository};
 pub use notifications::{
@@ -63,18 +97,29 @@ pub use notifications::{
     NotificationRecord, NotificationRepository, NotificationType, PaginatedNotifications,
     Resolution,
 };
-pub use media_files::{CreateMediaFile, EmbeddedMetadata, MediaFileRecord, MediaFileRepository};
+pub use media_files::{
+    BreakdownBucket, BreakdownDimension, CreateMediaFile, EmbeddedMetadata, MediaFileRecord,
+    MediaFileRepository,
+};
 pub use movies::{CreateMovie, MovieCollectionRecord, MovieRecord, MovieRepository, UpdateMovie};
 pub use naming_patterns::{CreateNamingPattern, NamingPatternRecord, NamingPatternRepository, UpdateNamingPattern};
+pub use people::{CreateCredit, CreditRecord, PeopleRepository, PersonRecord};
 pub use playback::{
     PlaybackRepository, PlaybackSessionRecord, UpdatePlaybackPosition, UpsertPlaybackSession,
 };
+pub use playback_streams::{
+    CreatePlaybackStream, PlaybackStreamRecord, PlaybackStreamRepository,
+};
+pub use qbittorrent::QbittorrentRepository;
+pub use roles::{CreateRole, RoleRecord, RolesRepository, UpdateRole};
 pub use rss_feeds::{
     CreateRssFeed, CreateRssFeedItem, RssFeedRecord, RssFeedRepository, UpdateRssFeed,
 };
+pub use sabnzbd::{SabnzbdApiKeyRecord, SabnzbdRepository};
 pub use schedule::{
     ScheduleCacheRecord, ScheduleRepository, ScheduleSyncStateRecord, UpsertScheduleEntry,
 };
+pub use search::{PersonCandidate, SearchCandidate, SearchRepository};
 pub use settings::SettingsRepository;
 pub use subtitles::{
     AudioStreamRecord, ChapterRecord, CreateDownloadedSubtitle, CreateEmbeddedSubtitle,
@@ -87,21 +132,29 @@ pub use pending_file_matches::{
 pub use torrent_files::{TorrentFileRecord, TorrentFileRepository, UpsertTorrentFile};
 pub use torrents::{CreateTorrent, TorrentRecord, TorrentRepository};
 pub use tracks::{CreateTrack, TrackRecord, TrackRepository, TrackWithStatus, UpdateTrack};
-pub use tv_shows::{CreateTvShow, TvShowRecord, TvShowRepository, UpdateTvShow};
+pub use tv_shows::{
+    CreateTvShow, FillTvShowMetadata, MissingMetadataRecord, TvShowRecord, TvShowRepository,
+    UpdateTvShow,
+};
 pub use watch_progress::{UpsertWatchProgress, WatchProgressRecord, WatchProgressRepository};
 pub use priority_rules::{
     CreatePriorityRule, PriorityRuleRecord, PriorityRulesRepository, SourceRef, SourceType,
     UpdatePriorityRule,
 };
 pub use usenet_servers::{
-    CreateUsenetServer, UpdateUsenetServer, UsenetServerRecord, UsenetServersRepository,
+    CreateUsenetServer, UpdateUsenetServer, UsenetServerRecord, UsenetServerUsageRecord,
+    UsenetServersRepository, UsenetUsageCounts,
 };
 pub use usenet_downloads::{
     CreateUsenetDownload, UpdateUsenetDownload, UsenetDownloadRecord, UsenetDownloadsRepository,
 };
+pub use user_preferences::{
+    UpdateUserPreferences, UserPreferencesRecord, UserPreferencesRepository,
+};
 pub use users::{
-    CreateUser, InviteTokenRecord, RefreshTokenRecord, UpdateUser, UserLibraryAccessRecord,
-    UserRecord, UserRestrictionRecord, UsersRepository,
+    CreateUser, EmailChangeTokenRecord, InviteTokenRecord, PasswordResetTokenRecord,
+    RefreshTokenRecord, UpdateUser, UserLibraryAccessRecord, UserRecord, UserRestrictionRecord,
+    UsersRepository,
 };
 
 /// Database wrapper providing connection pool access
@@ -247,6 +300,21 @@ impl Database {
         PlaybackRepository::new(self.pool.clone())
     }
 
+    /// Get a playback stream lease repository
+    pub fn playback_streams(&self) -> PlaybackStreamRepository {
+        PlaybackStreamRepository::new(self.pool.clone())
+    }
+
+    /// Get a background job repository
+    pub fn background_jobs(&self) -> BackgroundJobRepository {
+        BackgroundJobRepository::new(self.pool.clone())
+    }
+
+    /// Get an audit log repository
+    pub fn audit_log(&self) -> AuditLogRepository {
+        AuditLogRepository::new(self.pool.clone())
+    }
+
     /// Get a schedule cache repository
     pub fn schedule(&self) -> ScheduleRepository {
         ScheduleRepository::new(self.pool.clone())
@@ -317,6 +385,16 @@ impl Database {
         NotificationRepository::new(self.pool.clone())
     }
 
+    /// Get a roles repository
+    pub fn roles(&self) -> RolesRepository {
+        RolesRepository::new(self.pool.clone())
+    }
+
+    /// Get a user preferences repository
+    pub fn user_preferences(&self) -> UserPreferencesRepository {
+        UserPreferencesRepository::new(self.pool.clone())
+    }
+
     /// Get a users repository
     pub fn users(&self) -> UsersRepository {
         UsersRepository::new(self.pool.clone())
@@ -327,6 +405,56 @@ impl Database {
         ArtworkRepository::new(self.pool.clone())
     }
 
+    /// Get an episode air notification repository
+    pub fn air_notifications(&self) -> AirNotificationRepository {
+        AirNotificationRepository::new(self.pool.clone())
+    }
+
+    /// Get a grab ledger repository
+    pub fn grab_history(&self) -> GrabHistoryRepository {
+        GrabHistoryRepository::new(self.pool.clone())
+    }
+
+    /// Get an automation pause repository
+    pub fn automation_pauses(&self) -> AutomationPauseRepository {
+        AutomationPauseRepository::new(self.pool.clone())
+    }
+
+    /// Get a language profile repository
+    pub fn language_profiles(&self) -> LanguageProfileRepository {
+        LanguageProfileRepository::new(self.pool.clone())
+    }
+
+    /// Get a language mismatch repository
+    pub fn language_mismatches(&self) -> LanguageMismatchRepository {
+        LanguageMismatchRepository::new(self.pool.clone())
+    }
+
+    /// Get a SABnzbd API repository
+    pub fn sabnzbd(&self) -> SabnzbdRepository {
+        SabnzbdRepository::new(self.pool.clone())
+    }
+
+    /// Get a global search repository
+    pub fn search(&self) -> SearchRepository {
+        SearchRepository::new(self.pool.clone())
+    }
+
+    /// Get a qBittorrent API repository
+    pub fn qbittorrent(&self) -> QbittorrentRepository {
+        QbittorrentRepository::new(self.pool.clone())
+    }
+
+    /// Get a people repository
+    pub fn people(&self) -> PeopleRepository {
+        PeopleRepository::new(self.pool.clone())
+    }
+
+    /// Get an external service status repository
+    pub fn external_services(&self) -> ExternalServicesRepository {
+        ExternalServicesRepository::new(self.pool.clone())
+    }
+
     /// Run database migrations (SQLite)
     ///
     /// For a self-contained application, we handle checksum mismatches gracefully:
@@ -275,10 +275,14 @@ impl MovieRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        title_filter: Option<&str>,
+        title_filter: &crate::db::sqlite_helpers::TextFilter,
         year_filter: Option<i32>,
         monitored_filter: Option<bool>,
         has_file_filter: Option<bool>,
+        rating_min: Option<f64>,
+        rating_max: Option<f64>,
+        genres_filter: &crate::db::sqlite_helpers::JsonArrayFilter,
+        cast_filter: &crate::db::sqlite_helpers::JsonArrayFilter,
         sort_column: &str,
         sort_asc: bool,
     ) -> Result<(Vec<MovieRecord>, i64)> {
@@ -288,10 +292,8 @@ impl MovieRepository {
         let mut conditions = vec!["library_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if title_filter.is_some() {
-            conditions.push(format!("LOWER(title) LIKE ?{}", param_idx));
-            param_idx += 1;
-        }
+        let (title_conditions, title_binds) = title_filter.to_sql("title", &mut param_idx);
+        conditions.extend(title_conditions);
         if year_filter.is_some() {
             conditions.push(format!("year = ?{}", param_idx));
             param_idx += 1;
@@ -307,7 +309,19 @@ impl MovieRepository {
                 conditions.push("media_file_id IS NULL".to_string());
             }
         }
-        let _ = param_idx;
+        if rating_min.is_some() {
+            conditions.push(format!("tmdb_rating >= ?{}", param_idx));
+            param_idx += 1;
+        }
+        if rating_max.is_some() {
+            conditions.push(format!("tmdb_rating <= ?{}", param_idx));
+            param_idx += 1;
+        }
+        let (genre_conditions, genre_binds) = genres_filter.to_sql("genres", &mut param_idx);
+        conditions.extend(genre_conditions);
+        let (cast_conditions, cast_binds) = cast_filter.to_sql("cast_names", &mut param_idx);
+        conditions.extend(cast_conditions);
+        let list_binds: Vec<String> = genre_binds.into_iter().chain(cast_binds).collect();
 
         let where_clause = conditions.join(" AND ");
 
@@ -350,8 +364,8 @@ impl MovieRepository {
         
         // Build and execute count query with bindings
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            count_builder = count_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            count_builder = count_builder.bind(value);
         }
         if let Some(year) = year_filter {
             count_builder = count_builder.bind(year);
@@ -359,13 +373,22 @@ impl MovieRepository {
         if let Some(monitored) = monitored_filter {
             count_builder = count_builder.bind(bool_to_int(monitored));
         }
+        if let Some(min) = rating_min {
+            count_builder = count_builder.bind(min);
+        }
+        if let Some(max) = rating_max {
+            count_builder = count_builder.bind(max);
+        }
+        for value in &list_binds {
+            count_builder = count_builder.bind(value);
+        }
 
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
 
         // Build and execute data query with bindings
         let mut data_builder = sqlx::query_as::<_, MovieRecord>(&data_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            data_builder = data_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            data_builder = data_builder.bind(value);
         }
         if let Some(year) = year_filter {
             data_builder = data_builder.bind(year);
@@ -373,6 +396,15 @@ impl MovieRepository {
         if let Some(monitored) = monitored_filter {
             data_builder = data_builder.bind(bool_to_int(monitored));
         }
+        if let Some(min) = rating_min {
+            data_builder = data_builder.bind(min);
+        }
+        if let Some(max) = rating_max {
+            data_builder = data_builder.bind(max);
+        }
+        for value in &list_binds {
+            data_builder = data_builder.bind(value);
+        }
 
         let records = data_builder.fetch_all(&self.pool).await?;
 
@@ -406,6 +438,72 @@ impl MovieRepository {
         Ok(records)
     }
 
+    /// List movies a user has recently watched (at least halfway), most recent first
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_recently_watched(&self, user_id: Uuid, limit: i64) -> Result<Vec<MovieRecord>> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        let records = sqlx::query_as::<_, MovieRecord>(
+            r#"
+            SELECT m.id, m.library_id, m.user_id, m.title, m.sort_title, m.original_title, m.year,
+                   m.tmdb_id, m.imdb_id, m.overview, m.tagline, m.runtime, m.genres,
+                   m.production_countries, m.spoken_languages, m.director, m.cast_names,
+                   m.tmdb_rating, m.tmdb_vote_count, m.poster_url, m.backdrop_url,
+                   m.collection_id, m.collection_name, m.collection_poster_url,
+                   m.release_date, m.certification, m.status, m.monitored,
+                   m.media_file_id, m.created_at, m.updated_at
+            FROM movies m
+            JOIN watch_progress wp ON wp.movie_id = m.id
+            WHERE wp.user_id = ?1
+              AND (wp.is_watched = 1 OR wp.progress_percent >= 0.5)
+            ORDER BY wp.last_watched_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// List downloaded movies the user hasn't started watching, newest first
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_unwatched_with_files(&self, user_id: Uuid, limit: i64) -> Result<Vec<MovieRecord>> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        let records = sqlx::query_as::<_, MovieRecord>(
+            r#"
+            SELECT m.id, m.library_id, m.user_id, m.title, m.sort_title, m.original_title, m.year,
+                   m.tmdb_id, m.imdb_id, m.overview, m.tagline, m.runtime, m.genres,
+                   m.production_countries, m.spoken_languages, m.director, m.cast_names,
+                   m.tmdb_rating, m.tmdb_vote_count, m.poster_url, m.backdrop_url,
+                   m.collection_id, m.collection_name, m.collection_poster_url,
+                   m.release_date, m.certification, m.status, m.monitored,
+                   m.media_file_id, m.created_at, m.updated_at
+            FROM movies m
+            WHERE m.user_id = ?1
+              AND m.media_file_id IS NOT NULL
+              AND NOT EXISTS (
+                  SELECT 1 FROM watch_progress wp
+                  WHERE wp.movie_id = m.id AND wp.user_id = ?1
+                    AND (wp.is_watched = 1 OR wp.progress_percent > 0.01)
+              )
+            ORDER BY m.created_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
     /// Get a movie by ID
 
     #[cfg(feature = "sqlite")]
@@ -615,6 +713,41 @@ impl MovieRepository {
         Ok(())
     }
 
+    /// Set whether a movie is monitored
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_monitored(&self, movie_id: Uuid, monitored: bool) -> Result<()> {
+        use crate::db::sqlite_helpers::{bool_to_int, uuid_to_str};
+
+        sqlx::query("UPDATE movies SET monitored = ?2, updated_at = datetime('now') WHERE id = ?1")
+            .bind(uuid_to_str(movie_id))
+            .bind(bool_to_int(monitored))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace a movie's poster and backdrop URLs
+    #[cfg(feature = "sqlite")]
+    pub async fn set_artwork_urls(
+        &self,
+        movie_id: Uuid,
+        poster_url: Option<&str>,
+        backdrop_url: Option<&str>,
+    ) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query(
+            "UPDATE movies SET poster_url = ?2, backdrop_url = ?3, updated_at = datetime('now') WHERE id = ?1",
+        )
+        .bind(uuid_to_str(movie_id))
+        .bind(poster_url)
+        .bind(backdrop_url)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
     /// Get movie count for a library
 
     #[cfg(feature = "sqlite")]
@@ -157,6 +157,9 @@ pub enum NotificationCategory {
     Storage,
     Extraction,
     Configuration,
+    Schedule,
+    Update,
+    Services,
 }
 
 impl NotificationCategory {
@@ -168,6 +171,9 @@ impl NotificationCategory {
             NotificationCategory::Storage => "storage",
             NotificationCategory::Extraction => "extraction",
             NotificationCategory::Configuration => "configuration",
+            NotificationCategory::Schedule => "schedule",
+            NotificationCategory::Update => "update",
+            NotificationCategory::Services => "services",
         }
     }
 
@@ -179,6 +185,9 @@ impl NotificationCategory {
             "storage" => Some(NotificationCategory::Storage),
             "extraction" => Some(NotificationCategory::Extraction),
             "configuration" => Some(NotificationCategory::Configuration),
+            "schedule" => Some(NotificationCategory::Schedule),
+            "update" => Some(NotificationCategory::Update),
+            "services" => Some(NotificationCategory::Services),
             _ => None,
         }
     }
@@ -527,6 +527,47 @@ impl PendingFileMatchRepository {
             .ok_or_else(|| anyhow::anyhow!("Failed to retrieve pending file match after update"))
     }
 
+    /// Link a copied match to the media file it produced
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_media_file(&self, id: Uuid, media_file_id: Uuid) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE pending_file_matches
+            SET media_file_id = ?2, updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(media_file_id))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the most recent match that produced a media file
+
+    #[cfg(feature = "sqlite")]
+    pub async fn get_by_media_file(
+        &self,
+        media_file_id: Uuid,
+    ) -> Result<Option<PendingFileMatchRecord>> {
+        let record = sqlx::query_as::<_, PendingFileMatchRecord>(
+            r#"
+            SELECT * FROM pending_file_matches
+            WHERE media_file_id = ?1
+            ORDER BY copied_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(uuid_to_str(media_file_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
     /// Mark a match as failed
 
     #[cfg(feature = "sqlite")]
@@ -0,0 +1,237 @@
+//! People database operations
+//!
+//! Actors and directors from TMDB credits, and the credits linking them to
+//! movies and shows. People are shared between users; credits belong to the
+//! library item they're on, so queries through credits are per user.
+
+use anyhow::Result;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_uuid, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A person from TMDB
+#[derive(Debug, Clone)]
+pub struct PersonRecord {
+    pub id: Uuid,
+    pub tmdb_id: i32,
+    pub name: String,
+    pub profile_url: Option<String>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for PersonRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            tmdb_id: row.try_get("tmdb_id")?,
+            name: row.try_get("name")?,
+            profile_url: row.try_get("profile_url")?,
+        })
+    }
+}
+
+/// A person's credit on a movie or show
+#[derive(Debug, Clone)]
+pub struct CreditRecord {
+    pub person: PersonRecord,
+    /// Set for movie credits
+    pub movie_id: Option<Uuid>,
+    /// Set for show credits
+    pub tv_show_id: Option<Uuid>,
+    /// 'cast' or 'director'
+    pub role: String,
+    pub character: Option<String>,
+    pub billing_order: Option<i32>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for CreditRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let movie_id: Option<String> = row.try_get("movie_id")?;
+        let tv_show_id: Option<String> = row.try_get("tv_show_id")?;
+
+        Ok(Self {
+            person: PersonRecord::from_row(row)?,
+            movie_id: movie_id
+                .map(|id| str_to_uuid(&id))
+                .transpose()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            tv_show_id: tv_show_id
+                .map(|id| str_to_uuid(&id))
+                .transpose()
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            role: row.try_get("role")?,
+            character: row.try_get("character")?,
+            billing_order: row.try_get("billing_order")?,
+        })
+    }
+}
+
+/// A credit to store for a movie or show
+#[derive(Debug, Clone)]
+pub struct CreateCredit {
+    pub tmdb_id: i32,
+    pub name: String,
+    pub profile_url: Option<String>,
+    /// 'cast' or 'director'
+    pub role: String,
+    pub character: Option<String>,
+    pub billing_order: Option<i32>,
+}
+
+/// People repository
+pub struct PeopleRepository {
+    pool: DbPool,
+}
+
+impl PeopleRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a person by ID
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, id: Uuid) -> Result<Option<PersonRecord>> {
+        let record = sqlx::query_as::<_, PersonRecord>(
+            "SELECT id, tmdb_id, name, profile_url FROM people WHERE id = ?1",
+        )
+        .bind(uuid_to_str(id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Replace a movie's credits
+    #[cfg(feature = "sqlite")]
+    pub async fn set_movie_credits(&self, movie_id: Uuid, credits: &[CreateCredit]) -> Result<()> {
+        self.set_credits("movie_id", movie_id, credits).await
+    }
+
+    /// Replace a show's credits
+    #[cfg(feature = "sqlite")]
+    pub async fn set_show_credits(&self, tv_show_id: Uuid, credits: &[CreateCredit]) -> Result<()> {
+        self.set_credits("tv_show_id", tv_show_id, credits).await
+    }
+
+    /// Credits on a movie, directors first, then cast in billing order
+    #[cfg(feature = "sqlite")]
+    pub async fn list_for_movie(&self, movie_id: Uuid) -> Result<Vec<CreditRecord>> {
+        self.list_for_item("movie_id", movie_id).await
+    }
+
+    /// Credits on a show, directors first, then cast in billing order
+    #[cfg(feature = "sqlite")]
+    pub async fn list_for_show(&self, tv_show_id: Uuid) -> Result<Vec<CreditRecord>> {
+        self.list_for_item("tv_show_id", tv_show_id).await
+    }
+
+    /// A person's credits on a user's movies and shows, newest first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_for_person(
+        &self,
+        person_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<CreditRecord>> {
+        let records = sqlx::query_as::<_, CreditRecord>(
+            r#"
+            SELECT p.id, p.tmdb_id, p.name, p.profile_url,
+                   c.movie_id, c.tv_show_id, c.role, c.character, c.billing_order
+            FROM credits c
+            JOIN people p ON p.id = c.person_id
+            LEFT JOIN movies m ON m.id = c.movie_id
+            LEFT JOIN tv_shows s ON s.id = c.tv_show_id
+            WHERE c.person_id = ?1 AND COALESCE(m.user_id, s.user_id) = ?2
+            ORDER BY COALESCE(m.year, s.year) DESC, COALESCE(m.title, s.name)
+            "#,
+        )
+        .bind(uuid_to_str(person_id))
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn list_for_item(&self, column: &str, id: Uuid) -> Result<Vec<CreditRecord>> {
+        let records = sqlx::query_as::<_, CreditRecord>(&format!(
+            r#"
+            SELECT p.id, p.tmdb_id, p.name, p.profile_url,
+                   c.movie_id, c.tv_show_id, c.role, c.character, c.billing_order
+            FROM credits c
+            JOIN people p ON p.id = c.person_id
+            WHERE c.{} = ?1
+            ORDER BY c.role = 'cast', c.billing_order, p.name
+            "#,
+            column
+        ))
+        .bind(uuid_to_str(id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Upsert the people and swap the item's credits in one transaction
+    #[cfg(feature = "sqlite")]
+    async fn set_credits(&self, column: &str, id: Uuid, credits: &[CreateCredit]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(&format!("DELETE FROM credits WHERE {} = ?1", column))
+            .bind(uuid_to_str(id))
+            .execute(&mut *tx)
+            .await?;
+
+        for credit in credits {
+            let person_id: String = sqlx::query_scalar(
+                r#"
+                INSERT INTO people (id, tmdb_id, name, profile_url)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT(tmdb_id) DO UPDATE SET
+                    name = excluded.name,
+                    profile_url = COALESCE(excluded.profile_url, people.profile_url),
+                    updated_at = datetime('now')
+                RETURNING id
+                "#,
+            )
+            .bind(uuid_to_str(Uuid::new_v4()))
+            .bind(credit.tmdb_id)
+            .bind(&credit.name)
+            .bind(&credit.profile_url)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            sqlx::query(&format!(
+                r#"
+                INSERT INTO credits (person_id, {}, role, character, billing_order)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                "#,
+                column
+            ))
+            .bind(person_id)
+            .bind(uuid_to_str(id))
+            .bind(&credit.role)
+            .bind(&credit.character)
+            .bind(credit.billing_order)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,288 @@
+//! Playback stream lease database operations
+//!
+//! A lease is held by each client that is actively streaming. Clients keep it
+//! alive with heartbeats; leases that stop heartbeating are expired so they no
+//! longer count against the per-user concurrent stream limit.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{
+    int_to_bool, str_to_datetime, str_to_datetime_opt, str_to_uuid, str_to_uuid_opt, uuid_to_str,
+};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Playback stream lease record, joined with its owner and playback state
+#[derive(Debug, Clone)]
+pub struct PlaybackStreamRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub username: Option<String>,
+    pub playback_session_id: Option<Uuid>,
+    pub media_file_id: Option<Uuid>,
+    pub content_type: Option<String>,
+    pub client_name: Option<String>,
+    pub current_position: Option<f64>,
+    pub duration: Option<f64>,
+    pub is_playing: bool,
+    pub started_at: DateTime<Utc>,
+    pub last_heartbeat_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub end_reason: Option<String>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for PlaybackStreamRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let id_str: String = row.try_get("id")?;
+        let user_id_str: String = row.try_get("user_id")?;
+        let session_id_str: Option<String> = row.try_get("playback_session_id")?;
+        let media_file_id_str: Option<String> = row.try_get("media_file_id")?;
+        let is_playing: Option<i32> = row.try_get("is_playing")?;
+        let started_str: String = row.try_get("started_at")?;
+        let heartbeat_str: String = row.try_get("last_heartbeat_at")?;
+        let ended_str: Option<String> = row.try_get("ended_at")?;
+
+        Ok(Self {
+            id: str_to_uuid(&id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            username: row.try_get("username")?,
+            playback_session_id: str_to_uuid_opt(session_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            media_file_id: str_to_uuid_opt(media_file_id_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            content_type: row.try_get("content_type")?,
+            client_name: row.try_get("client_name")?,
+            current_position: row.try_get("current_position")?,
+            duration: row.try_get("duration")?,
+            is_playing: is_playing.map(int_to_bool).unwrap_or(false),
+            started_at: str_to_datetime(&started_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            last_heartbeat_at: str_to_datetime(&heartbeat_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            ended_at: str_to_datetime_opt(ended_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            end_reason: row.try_get("end_reason")?,
+        })
+    }
+}
+
+/// Input for opening a stream lease
+#[derive(Debug)]
+pub struct CreatePlaybackStream {
+    pub user_id: Uuid,
+    pub playback_session_id: Option<Uuid>,
+    pub media_file_id: Option<Uuid>,
+    pub content_type: Option<String>,
+    pub client_name: Option<String>,
+}
+
+/// Columns selected for a stream, joined with the user and playback session
+#[cfg(feature = "sqlite")]
+const STREAM_SELECT: &str = r#"
+    SELECT s.id, s.user_id, u.username, s.playback_session_id, s.media_file_id,
+           s.content_type, s.client_name, p.current_position, p.duration, p.is_playing,
+           s.started_at, s.last_heartbeat_at, s.ended_at, s.end_reason
+    FROM playback_streams s
+    LEFT JOIN users u ON u.id = s.user_id
+    LEFT JOIN playback_sessions p ON p.id = s.playback_session_id
+"#;
+
+/// Playback stream repository for database operations
+pub struct PlaybackStreamRepository {
+    pool: DbPool,
+}
+
+impl PlaybackStreamRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a stream by ID (active or ended)
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, id: Uuid) -> Result<Option<PlaybackStreamRecord>> {
+        let record = sqlx::query_as::<_, PlaybackStreamRecord>(&format!(
+            "{} WHERE s.id = ?1",
+            STREAM_SELECT
+        ))
+        .bind(uuid_to_str(id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// List all active streams, most recently started first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_active(&self) -> Result<Vec<PlaybackStreamRecord>> {
+        let records = sqlx::query_as::<_, PlaybackStreamRecord>(&format!(
+            "{} WHERE s.ended_at IS NULL ORDER BY s.started_at DESC",
+            STREAM_SELECT
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Count a user's active streams
+    #[cfg(feature = "sqlite")]
+    pub async fn count_active_for_user(&self, user_id: Uuid) -> Result<i64> {
+        let count = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM playback_streams WHERE user_id = ?1 AND ended_at IS NULL",
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    /// Open a new stream lease
+    #[cfg(feature = "sqlite")]
+    pub async fn create(&self, input: CreatePlaybackStream) -> Result<PlaybackStreamRecord> {
+        let id = Uuid::new_v4();
+
+        sqlx::query(
+            r#"
+            INSERT INTO playback_streams (id, user_id, playback_session_id, media_file_id,
+                                          content_type, client_name, started_at, last_heartbeat_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, datetime('now'), datetime('now'))
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(input.user_id))
+        .bind(input.playback_session_id.map(uuid_to_str))
+        .bind(input.media_file_id.map(uuid_to_str))
+        .bind(&input.content_type)
+        .bind(&input.client_name)
+        .execute(&self.pool)
+        .await?;
+
+        self.get(id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to retrieve stream after insert"))
+    }
+
+    /// Point an active lease at new content (same client, next item)
+    ///
+    /// Returns false if the lease doesn't belong to the user or has ended.
+    #[cfg(feature = "sqlite")]
+    pub async fn renew(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        input: &CreatePlaybackStream,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE playback_streams SET
+                playback_session_id = ?3,
+                media_file_id = ?4,
+                content_type = ?5,
+                last_heartbeat_at = datetime('now')
+            WHERE id = ?1 AND user_id = ?2 AND ended_at IS NULL
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(user_id))
+        .bind(input.playback_session_id.map(uuid_to_str))
+        .bind(input.media_file_id.map(uuid_to_str))
+        .bind(&input.content_type)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record a heartbeat for a user's stream
+    ///
+    /// Returns the stream as it stands afterwards; an ended stream is returned
+    /// unchanged so the client can learn that it was stopped.
+    #[cfg(feature = "sqlite")]
+    pub async fn heartbeat(&self, id: Uuid, user_id: Uuid) -> Result<Option<PlaybackStreamRecord>> {
+        sqlx::query(
+            r#"
+            UPDATE playback_streams SET last_heartbeat_at = datetime('now')
+            WHERE id = ?1 AND user_id = ?2 AND ended_at IS NULL
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(uuid_to_str(user_id))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(self.get(id).await?.filter(|s| s.user_id == user_id))
+    }
+
+    /// End a stream (no-op if already ended)
+    #[cfg(feature = "sqlite")]
+    pub async fn end(&self, id: Uuid, reason: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE playback_streams SET ended_at = datetime('now'), end_reason = ?2
+            WHERE id = ?1 AND ended_at IS NULL
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(reason)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Expire streams whose last heartbeat is older than the cutoff
+    ///
+    /// The playback sessions behind them are marked as no longer playing.
+    #[cfg(feature = "sqlite")]
+    pub async fn expire_stale(&self, cutoff: DateTime<Utc>) -> Result<u64> {
+        let cutoff = cutoff.format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"
+            UPDATE playback_sessions SET is_playing = 0, updated_at = datetime('now')
+            WHERE id IN (
+                SELECT playback_session_id FROM playback_streams
+                WHERE ended_at IS NULL AND last_heartbeat_at < ?1
+            )
+            "#,
+        )
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE playback_streams SET ended_at = datetime('now'), end_reason = 'expired'
+            WHERE ended_at IS NULL AND last_heartbeat_at < ?1
+            "#,
+        )
+        .bind(&cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Delete streams that ended before the cutoff
+    #[cfg(feature = "sqlite")]
+    pub async fn delete_ended_before(&self, before: DateTime<Utc>) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM playback_streams WHERE ended_at < ?1")
+            .bind(before.format("%Y-%m-%d %H:%M:%S").to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,98 @@
+//! qBittorrent API database operations
+//!
+//! Categories apps created through the qBittorrent-compatible endpoint and
+//! the category of each torrent they added.
+
+use anyhow::Result;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::uuid_to_str;
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// qBittorrent API repository
+pub struct QbittorrentRepository {
+    pool: DbPool,
+}
+
+impl QbittorrentRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Names of a user's categories
+    #[cfg(feature = "sqlite")]
+    pub async fn list_categories(&self, user_id: Uuid) -> Result<Vec<String>> {
+        let names = sqlx::query_scalar(
+            "SELECT name FROM qbittorrent_categories WHERE user_id = ?1 ORDER BY name",
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(names)
+    }
+
+    /// Create a category, doing nothing if it exists
+    #[cfg(feature = "sqlite")]
+    pub async fn create_category(&self, user_id: Uuid, name: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO qbittorrent_categories (user_id, name)
+            VALUES (?1, ?2)
+            ON CONFLICT(user_id, name) DO NOTHING
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(name)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Set the category of a torrent
+    #[cfg(feature = "sqlite")]
+    pub async fn set_category(&self, torrent_id: Uuid, category: Option<&str>) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO qbittorrent_torrents (torrent_id, category)
+            VALUES (?1, ?2)
+            ON CONFLICT(torrent_id) DO UPDATE SET category = excluded.category
+            "#,
+        )
+        .bind(uuid_to_str(torrent_id))
+        .bind(category)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Categories of a user's torrents that were added through the API,
+    /// keyed by info hash
+    #[cfg(feature = "sqlite")]
+    pub async fn list_torrent_categories(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(String, Option<String>)>> {
+        let rows = sqlx::query_as(
+            r#"
+            SELECT t.info_hash, q.category
+            FROM qbittorrent_torrents q
+            JOIN torrents t ON t.id = q.torrent_id
+            WHERE t.user_id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+}
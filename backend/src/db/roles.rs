@@ -0,0 +1,362 @@
+//! Roles database operations
+//!
+//! A role is a named set of permissions. "admin", "member" and "guest" are
+//! built in and fixed; admins can add custom roles. A user's effective role
+//! is `users.custom_role` when set, otherwise the built-in `users.role`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{int_to_bool, str_to_datetime, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Roles every install has; they can't be edited or deleted
+pub const BUILTIN_ROLES: &[&str] = &["admin", "member", "guest"];
+
+/// Something a role allows. Everyone signed in can view; a role with no
+/// permissions is view-only.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Create, edit, scan and delete libraries and their contents
+    ManageLibraries,
+    /// Add and control torrents and usenet downloads
+    ManageDownloads,
+    /// Create roles, assign them, and see other users' activity
+    ManageUsers,
+    /// Server-wide settings, caches and other users' streams and jobs
+    ManageServer,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 4] = [
+        Self::ManageLibraries,
+        Self::ManageDownloads,
+        Self::ManageUsers,
+        Self::ManageServer,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ManageLibraries => "manage_libraries",
+            Self::ManageDownloads => "manage_downloads",
+            Self::ManageUsers => "manage_users",
+            Self::ManageServer => "manage_server",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "manage_libraries" => Some(Self::ManageLibraries),
+            "manage_downloads" => Some(Self::ManageDownloads),
+            "manage_users" => Some(Self::ManageUsers),
+            "manage_server" => Some(Self::ManageServer),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a stored permissions array, skipping names this build doesn't know
+pub fn permissions_from_json(json: &str) -> Vec<Permission> {
+    serde_json::from_str::<Vec<String>>(json)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|p| Permission::parse(p))
+        .collect()
+}
+
+/// Serialize permissions in a stable order without duplicates
+pub fn permissions_to_json(permissions: &[Permission]) -> String {
+    let names: Vec<&str> = Permission::ALL
+        .iter()
+        .filter(|p| permissions.contains(p))
+        .map(|p| p.as_str())
+        .collect();
+    serde_json::to_string(&names).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Check a new role's name: lowercase letters, digits, '-' and '_', and not
+/// the name of a built-in role
+pub fn validate_role_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 32 {
+        anyhow::bail!("Role name must be 1 to 32 characters");
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        anyhow::bail!("Role name may only contain lowercase letters, digits, '-' and '_'");
+    }
+    if BUILTIN_ROLES.contains(&name) {
+        anyhow::bail!("'{}' is a built-in role", name);
+    }
+    Ok(())
+}
+
+/// Role record
+#[derive(Debug, Clone)]
+pub struct RoleRecord {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+    pub is_builtin: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl RoleRecord {
+    pub fn has(&self, permission: Permission) -> bool {
+        self.permissions.contains(&permission)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for RoleRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let permissions: String = row.try_get("permissions")?;
+        let is_builtin: i32 = row.try_get("is_builtin")?;
+        let created_str: String = row.try_get("created_at")?;
+        let updated_str: String = row.try_get("updated_at")?;
+
+        Ok(Self {
+            name: row.try_get("name")?,
+            description: row.try_get("description")?,
+            permissions: permissions_from_json(&permissions),
+            is_builtin: int_to_bool(is_builtin),
+            created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            updated_at: str_to_datetime(&updated_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Input for creating a custom role
+#[derive(Debug, Clone)]
+pub struct CreateRole {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+}
+
+/// Input for updating a custom role (None leaves a field unchanged)
+#[derive(Debug, Clone, Default)]
+pub struct UpdateRole {
+    pub description: Option<Option<String>>,
+    pub permissions: Option<Vec<Permission>>,
+}
+
+/// Roles repository for database operations
+pub struct RolesRepository {
+    pool: DbPool,
+}
+
+impl RolesRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List all roles, built-in first
+    #[cfg(feature = "sqlite")]
+    pub async fn list(&self) -> Result<Vec<RoleRecord>> {
+        let records =
+            sqlx::query_as::<_, RoleRecord>("SELECT * FROM roles ORDER BY is_builtin DESC, name")
+                .fetch_all(&self.pool)
+                .await?;
+
+        Ok(records)
+    }
+
+    /// Get a role by name (case-insensitive)
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, name: &str) -> Result<Option<RoleRecord>> {
+        let record = sqlx::query_as::<_, RoleRecord>("SELECT * FROM roles WHERE name = ?1")
+            .bind(name)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(record)
+    }
+
+    /// Create a custom role
+    #[cfg(feature = "sqlite")]
+    pub async fn create(&self, input: CreateRole) -> Result<RoleRecord> {
+        validate_role_name(&input.name)?;
+        if self.get(&input.name).await?.is_some() {
+            anyhow::bail!("A role named '{}' already exists", input.name);
+        }
+
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO roles (name, description, permissions, is_builtin, created_at, updated_at)
+            VALUES (?1, ?2, ?3, 0, ?4, ?4)
+            "#,
+        )
+        .bind(&input.name)
+        .bind(&input.description)
+        .bind(permissions_to_json(&input.permissions))
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+
+        self.get(&input.name)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Failed to create role"))
+    }
+
+    /// Update a custom role; built-in roles are left alone and return None
+    #[cfg(feature = "sqlite")]
+    pub async fn update(&self, name: &str, input: UpdateRole) -> Result<Option<RoleRecord>> {
+        let Some(mut role) = self.get(name).await? else {
+            return Ok(None);
+        };
+        if role.is_builtin {
+            return Ok(None);
+        }
+
+        if let Some(description) = input.description {
+            role.description = description;
+        }
+        if let Some(permissions) = input.permissions {
+            role.permissions = permissions;
+        }
+
+        sqlx::query(
+            "UPDATE roles SET description = ?2, permissions = ?3, updated_at = ?4 WHERE name = ?1",
+        )
+        .bind(&role.name)
+        .bind(&role.description)
+        .bind(permissions_to_json(&role.permissions))
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        self.get(name).await
+    }
+
+    /// Delete a custom role. Its users fall back to their built-in role.
+    #[cfg(feature = "sqlite")]
+    pub async fn delete(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM roles WHERE name = ?1 AND is_builtin = 0")
+            .bind(name)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// A user's effective role, or None if the user doesn't exist or is
+    /// disabled
+    #[cfg(feature = "sqlite")]
+    pub async fn get_for_user(&self, user_id: Uuid) -> Result<Option<RoleRecord>> {
+        let record = sqlx::query_as::<_, RoleRecord>(
+            r#"
+            SELECT r.* FROM users u
+            JOIN roles r ON r.name = COALESCE(u.custom_role, u.role)
+            WHERE u.id = ?1 AND u.is_active = 1
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Give a user a role. Built-in roles go in `users.role`; a custom role
+    /// goes in `users.custom_role` on top of the member role.
+    #[cfg(feature = "sqlite")]
+    pub async fn assign(&self, user_id: Uuid, role: &RoleRecord) -> Result<bool> {
+        let (base_role, custom_role) = if role.is_builtin {
+            (role.name.as_str(), None)
+        } else {
+            ("member", Some(role.name.as_str()))
+        };
+
+        let result = sqlx::query(
+            "UPDATE users SET role = ?2, custom_role = ?3, updated_at = ?4 WHERE id = ?1",
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(base_role)
+        .bind(custom_role)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Count active users who can manage users, leaving out one user and/or
+    /// everyone holding one role
+    ///
+    /// Used to refuse changes that would leave nobody able to manage users.
+    #[cfg(feature = "sqlite")]
+    pub async fn count_user_managers_except(
+        &self,
+        user_id: Option<Uuid>,
+        role: Option<&str>,
+    ) -> Result<i64> {
+        let row = sqlx::query_as::<_, (i64,)>(
+            r#"
+            SELECT COUNT(*) FROM users u
+            JOIN roles r ON r.name = COALESCE(u.custom_role, u.role)
+            WHERE u.is_active = 1
+              AND EXISTS (SELECT 1 FROM json_each(r.permissions) WHERE value = 'manage_users')
+              AND (?1 IS NULL OR u.id != ?1)
+              AND (?2 IS NULL OR r.name != ?2)
+            "#,
+        )
+        .bind(user_id.map(uuid_to_str))
+        .bind(role)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permission_round_trip() {
+        for permission in Permission::ALL {
+            assert_eq!(Permission::parse(permission.as_str()), Some(permission));
+        }
+        assert_eq!(Permission::parse("view"), None);
+    }
+
+    #[test]
+    fn test_permissions_json() {
+        let json = permissions_to_json(&[
+            Permission::ManageUsers,
+            Permission::ManageLibraries,
+            Permission::ManageUsers,
+        ]);
+        assert_eq!(json, r#"["manage_libraries","manage_users"]"#);
+        assert_eq!(
+            permissions_from_json(r#"["manage_users","delete_everything"]"#),
+            vec![Permission::ManageUsers]
+        );
+        assert!(permissions_from_json("not json").is_empty());
+    }
+
+    #[test]
+    fn test_validate_role_name() {
+        assert!(validate_role_name("uploader").is_ok());
+        assert!(validate_role_name("family_tv-2").is_ok());
+        assert!(validate_role_name("").is_err());
+        assert!(validate_role_name("Uploader").is_err());
+        assert!(validate_role_name("has space").is_err());
+        assert!(validate_role_name("admin").is_err());
+        assert!(validate_role_name(&"x".repeat(33)).is_err());
+    }
+}
@@ -16,7 +16,7 @@ use crate::db::sqlite_helpers::{
 type DbPool = SqlitePool;
 
 /// RSS Feed record from database
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize)]
 pub struct RssFeedRecord {
     pub id: Uuid,
     pub user_id: Uuid,
@@ -25,6 +25,13 @@ pub struct RssFeedRecord {
     pub url: String,
     pub enabled: bool,
     pub poll_interval_minutes: i32,
+    /// Bounds for the adapted poll interval
+    pub min_poll_interval_minutes: i32,
+    pub max_poll_interval_minutes: i32,
+    /// Poll interval adapted to how often items arrive (None until the first poll)
+    pub current_poll_interval_minutes: Option<i32>,
+    /// The feed's own <ttl>, if it has one
+    pub ttl_minutes: Option<i32>,
     /// Post-download action override (copy-only today; future source rules) - NULL uses library setting
     pub post_download_action: Option<String>,
     pub last_polled_at: Option<chrono::DateTime<chrono::Utc>>,
@@ -59,6 +66,10 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for RssFeedRecord {
             url: row.try_get("url")?,
             enabled: int_to_bool(enabled_int),
             poll_interval_minutes: row.try_get("poll_interval_minutes")?,
+            min_poll_interval_minutes: row.try_get("min_poll_interval_minutes")?,
+            max_poll_interval_minutes: row.try_get("max_poll_interval_minutes")?,
+            current_poll_interval_minutes: row.try_get("current_poll_interval_minutes")?,
+            ttl_minutes: row.try_get("ttl_minutes")?,
             post_download_action: row.try_get("post_download_action")?,
             last_polled_at: str_to_datetime_opt(last_polled_str.as_deref())
                 .map_err(|e| sqlx::Error::Decode(e.into()))?,
@@ -152,6 +163,8 @@ pub struct CreateRssFeed {
     pub url: String,
     pub enabled: bool,
     pub poll_interval_minutes: i32,
+    pub min_poll_interval_minutes: i32,
+    pub max_poll_interval_minutes: i32,
 }
 
 /// Input for updating an RSS feed
@@ -162,6 +175,8 @@ pub struct UpdateRssFeed {
     pub library_id: Option<Uuid>,
     pub enabled: Option<bool>,
     pub poll_interval_minutes: Option<i32>,
+    pub min_poll_interval_minutes: Option<i32>,
+    pub max_poll_interval_minutes: Option<i32>,
 }
 
 /// Input for creating an RSS feed item
@@ -201,7 +216,9 @@ impl RssFeedRepository {
         let records = sqlx::query_as::<_, RssFeedRecord>(
             r#"
             SELECT id, user_id, library_id, name, url, enabled,
-                   poll_interval_minutes, post_download_action, last_polled_at, last_successful_at,
+                   poll_interval_minutes, min_poll_interval_minutes, max_poll_interval_minutes,
+                   current_poll_interval_minutes, ttl_minutes,
+                   post_download_action, last_polled_at, last_successful_at,
                    last_error, consecutive_failures, created_at, updated_at
             FROM rss_feeds
             WHERE user_id = ?1
@@ -222,7 +239,9 @@ impl RssFeedRepository {
         let records = sqlx::query_as::<_, RssFeedRecord>(
             r#"
             SELECT id, user_id, library_id, name, url, enabled,
-                   poll_interval_minutes, post_download_action, last_polled_at, last_successful_at,
+                   poll_interval_minutes, min_poll_interval_minutes, max_poll_interval_minutes,
+                   current_poll_interval_minutes, ttl_minutes,
+                   post_download_action, last_polled_at, last_successful_at,
                    last_error, consecutive_failures, created_at, updated_at
             FROM rss_feeds
             WHERE library_id = ?1 OR library_id IS NULL
@@ -243,12 +262,17 @@ impl RssFeedRepository {
         let records = sqlx::query_as::<_, RssFeedRecord>(
             r#"
             SELECT id, user_id, library_id, name, url, enabled,
-                   poll_interval_minutes, post_download_action, last_polled_at, last_successful_at,
+                   poll_interval_minutes, min_poll_interval_minutes, max_poll_interval_minutes,
+                   current_poll_interval_minutes, ttl_minutes,
+                   post_download_action, last_polled_at, last_successful_at,
                    last_error, consecutive_failures, created_at, updated_at
             FROM rss_feeds
             WHERE enabled = 1
               AND (last_polled_at IS NULL 
-                   OR datetime(last_polled_at, '+' || poll_interval_minutes || ' minutes') < datetime('now'))
+                   OR datetime(
+                          last_polled_at,
+                          '+' || COALESCE(current_poll_interval_minutes, poll_interval_minutes) || ' minutes'
+                      ) <= datetime('now'))
             ORDER BY last_polled_at IS NOT NULL, last_polled_at
             "#,
         )
@@ -265,7 +289,9 @@ impl RssFeedRepository {
         let record = sqlx::query_as::<_, RssFeedRecord>(
             r#"
             SELECT id, user_id, library_id, name, url, enabled,
-                   poll_interval_minutes, post_download_action, last_polled_at, last_successful_at,
+                   poll_interval_minutes, min_poll_interval_minutes, max_poll_interval_minutes,
+                   current_poll_interval_minutes, ttl_minutes,
+                   post_download_action, last_polled_at, last_successful_at,
                    last_error, consecutive_failures, created_at, updated_at
             FROM rss_feeds
             WHERE id = ?1
@@ -289,9 +315,9 @@ impl RssFeedRepository {
             r#"
             INSERT INTO rss_feeds (
                 id, user_id, library_id, name, url, enabled, poll_interval_minutes,
-                created_at, updated_at
+                min_poll_interval_minutes, max_poll_interval_minutes, created_at, updated_at
             )
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, datetime('now'), datetime('now'))
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, datetime('now'), datetime('now'))
             "#,
         )
         .bind(&id_str)
@@ -301,6 +327,8 @@ impl RssFeedRepository {
         .bind(&input.url)
         .bind(bool_to_int(input.enabled))
         .bind(input.poll_interval_minutes)
+        .bind(input.min_poll_interval_minutes)
+        .bind(input.max_poll_interval_minutes)
         .execute(&self.pool)
         .await?;
 
@@ -323,6 +351,15 @@ impl RssFeedRepository {
                 library_id = COALESCE(?4, library_id),
                 enabled = COALESCE(?5, enabled),
                 poll_interval_minutes = COALESCE(?6, poll_interval_minutes),
+                min_poll_interval_minutes = COALESCE(?7, min_poll_interval_minutes),
+                max_poll_interval_minutes = COALESCE(?8, max_poll_interval_minutes),
+                -- Changing the starting interval or bounds restarts the adaptation
+                current_poll_interval_minutes = CASE
+                    WHEN COALESCE(?6, poll_interval_minutes) = poll_interval_minutes
+                     AND COALESCE(?7, min_poll_interval_minutes) = min_poll_interval_minutes
+                     AND COALESCE(?8, max_poll_interval_minutes) = max_poll_interval_minutes
+                    THEN current_poll_interval_minutes
+                END,
                 updated_at = datetime('now')
             WHERE id = ?1
             "#,
@@ -333,6 +370,8 @@ impl RssFeedRepository {
         .bind(input.library_id.map(uuid_to_str))
         .bind(input.enabled.map(bool_to_int))
         .bind(input.poll_interval_minutes)
+        .bind(input.min_poll_interval_minutes)
+        .bind(input.max_poll_interval_minutes)
         .execute(&self.pool)
         .await?;
 
@@ -351,10 +390,16 @@ impl RssFeedRepository {
         Ok(result.rows_affected() > 0)
     }
 
-    /// Update poll status (success)
+    /// Update poll status (success), with the adapted interval for the next
+    /// poll and the feed's <ttl>
 
     #[cfg(feature = "sqlite")]
-    pub async fn mark_poll_success(&self, id: Uuid) -> Result<()> {
+    pub async fn mark_poll_success(
+        &self,
+        id: Uuid,
+        next_interval_minutes: i32,
+        ttl_minutes: Option<i32>,
+    ) -> Result<()> {
         sqlx::query(
             r#"
             UPDATE rss_feeds SET
@@ -362,11 +407,15 @@ impl RssFeedRepository {
                 last_successful_at = datetime('now'),
                 last_error = NULL,
                 consecutive_failures = 0,
+                current_poll_interval_minutes = ?2,
+                ttl_minutes = ?3,
                 updated_at = datetime('now')
             WHERE id = ?1
             "#,
         )
         .bind(uuid_to_str(id))
+        .bind(next_interval_minutes)
+        .bind(ttl_minutes)
         .execute(&self.pool)
         .await?;
 
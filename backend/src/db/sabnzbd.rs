@@ -0,0 +1,175 @@
+//! SABnzbd API database operations
+//!
+//! API keys for the SABnzbd-compatible endpoint (one per user, stored as a
+//! SHA-256 hash) and the category each app sent with the NZBs it added.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_datetime, str_to_datetime_opt, str_to_uuid, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A user's SABnzbd API key
+#[derive(Debug, Clone)]
+pub struct SabnzbdApiKeyRecord {
+    pub user_id: Uuid,
+    pub key_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for SabnzbdApiKeyRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let user_id_str: String = row.try_get("user_id")?;
+        let created_at_str: String = row.try_get("created_at")?;
+        let last_used_at_str: Option<String> = row.try_get("last_used_at")?;
+
+        Ok(Self {
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            key_hash: row.try_get("key_hash")?,
+            created_at: str_to_datetime(&created_at_str)
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            last_used_at: str_to_datetime_opt(last_used_at_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// SABnzbd API repository
+pub struct SabnzbdRepository {
+    pool: DbPool,
+}
+
+impl SabnzbdRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a user's API key
+    #[cfg(feature = "sqlite")]
+    pub async fn get_key(&self, user_id: Uuid) -> Result<Option<SabnzbdApiKeyRecord>> {
+        let record = sqlx::query_as::<_, SabnzbdApiKeyRecord>(
+            "SELECT * FROM sabnzbd_api_keys WHERE user_id = ?1",
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Find the key with a hash
+    #[cfg(feature = "sqlite")]
+    pub async fn get_key_by_hash(&self, key_hash: &str) -> Result<Option<SabnzbdApiKeyRecord>> {
+        let record = sqlx::query_as::<_, SabnzbdApiKeyRecord>(
+            "SELECT * FROM sabnzbd_api_keys WHERE key_hash = ?1",
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record)
+    }
+
+    /// Set a user's API key, replacing the old one
+    #[cfg(feature = "sqlite")]
+    pub async fn replace_key(&self, user_id: Uuid, key_hash: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sabnzbd_api_keys (user_id, key_hash)
+            VALUES (?1, ?2)
+            ON CONFLICT(user_id) DO UPDATE SET
+                key_hash = excluded.key_hash,
+                created_at = datetime('now'),
+                last_used_at = NULL
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(key_hash)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Revoke a user's API key, returning whether there was one
+    #[cfg(feature = "sqlite")]
+    pub async fn delete_key(&self, user_id: Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM sabnzbd_api_keys WHERE user_id = ?1")
+            .bind(uuid_to_str(user_id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Record that a key was used
+    #[cfg(feature = "sqlite")]
+    pub async fn touch_key(&self, user_id: Uuid) -> Result<()> {
+        sqlx::query(
+            "UPDATE sabnzbd_api_keys SET last_used_at = datetime('now') WHERE user_id = ?1",
+        )
+        .bind(uuid_to_str(user_id))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remember the category a download was added with
+    #[cfg(feature = "sqlite")]
+    pub async fn set_category(
+        &self,
+        usenet_download_id: Uuid,
+        category: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO sabnzbd_downloads (usenet_download_id, category)
+            VALUES (?1, ?2)
+            ON CONFLICT(usenet_download_id) DO UPDATE SET category = excluded.category
+            "#,
+        )
+        .bind(uuid_to_str(usenet_download_id))
+        .bind(category)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Categories of a user's downloads that were added through the API
+    #[cfg(feature = "sqlite")]
+    pub async fn list_categories(&self, user_id: Uuid) -> Result<Vec<(Uuid, Option<String>)>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT s.usenet_download_id, s.category
+            FROM sabnzbd_downloads s
+            JOIN usenet_downloads d ON d.id = s.usenet_download_id
+            WHERE d.user_id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("usenet_download_id")?;
+                Ok((str_to_uuid(&id)?, row.try_get("category")?))
+            })
+            .collect()
+    }
+}
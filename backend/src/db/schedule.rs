@@ -387,6 +387,46 @@ impl ScheduleRepository {
         Ok(records)
     }
 
+    /// Get schedule entries for the given shows airing between two dates (inclusive), across all countries
+    #[cfg(feature = "sqlite")]
+    pub async fn get_for_shows_between(
+        &self,
+        tvmaze_show_ids: &[i32],
+        start_date: time::Date,
+        end_date: time::Date,
+    ) -> Result<Vec<ScheduleCacheRecord>> {
+        if tvmaze_show_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let date_format = time::macros::format_description!("[year]-[month]-[day]");
+        let start_str = start_date.format(date_format).unwrap_or_default();
+        let end_str = end_date.format(date_format).unwrap_or_default();
+
+        let placeholders: Vec<String> = (0..tvmaze_show_ids.len())
+            .map(|i| format!("?{}", i + 3))
+            .collect();
+        let query = format!(
+            r#"
+            SELECT * FROM schedule_cache
+            WHERE air_date >= ?1
+              AND air_date <= ?2
+              AND tvmaze_show_id IN ({})
+            ORDER BY air_date, air_time
+            "#,
+            placeholders.join(", ")
+        );
+
+        let mut q = sqlx::query_as::<_, ScheduleCacheRecord>(&query)
+            .bind(&start_str)
+            .bind(&end_str);
+        for id in tvmaze_show_ids {
+            q = q.bind(id);
+        }
+
+        Ok(q.fetch_all(&self.pool).await?)
+    }
+
     /// Get schedule entries for a specific date
 
     #[cfg(feature = "sqlite")]
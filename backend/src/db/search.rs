@@ -0,0 +1,227 @@
+//! Global search database operations
+//!
+//! Cheap `LIKE` lookups of names across a user's library, one entity type at
+//! a time. Results are candidates only; `services::global_search` scores and
+//! ranks them.
+
+use anyhow::Result;
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{str_to_uuid, uuid_to_str};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// A library item whose name matched
+#[derive(Debug, Clone)]
+pub struct SearchCandidate {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// A person credited on a movie or audiobook whose name matched
+#[derive(Debug, Clone)]
+pub struct PersonCandidate {
+    pub name: String,
+    /// 'cast', 'director', 'author' or 'narrator'
+    pub role: String,
+}
+
+/// Global search repository
+pub struct SearchRepository {
+    pool: DbPool,
+}
+
+impl SearchRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Movies whose title or original title matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn movies(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            r#"
+            SELECT id, title AS name FROM movies
+            WHERE user_id = ?1 AND (LOWER(title) LIKE ?2 OR LOWER(original_title) LIKE ?2)
+            LIMIT ?3
+            "#,
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// TV shows whose name matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn shows(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            "SELECT id, name FROM tv_shows WHERE user_id = ?1 AND LOWER(name) LIKE ?2 LIMIT ?3",
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// Episodes whose title matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn episodes(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            r#"
+            SELECT e.id, e.title AS name FROM episodes e
+            JOIN tv_shows s ON s.id = e.tv_show_id
+            WHERE s.user_id = ?1 AND LOWER(e.title) LIKE ?2
+            LIMIT ?3
+            "#,
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// Albums whose name matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn albums(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            "SELECT id, name FROM albums WHERE user_id = ?1 AND LOWER(name) LIKE ?2 LIMIT ?3",
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// Artists whose name matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn artists(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            "SELECT id, name FROM artists WHERE user_id = ?1 AND LOWER(name) LIKE ?2 LIMIT ?3",
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// Audiobooks whose title matches a `LIKE` pattern
+    #[cfg(feature = "sqlite")]
+    pub async fn audiobooks(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        self.candidates(
+            "SELECT id, title AS name FROM audiobooks WHERE user_id = ?1 AND LOWER(title) LIKE ?2 LIMIT ?3",
+            user_id,
+            pattern,
+            limit,
+        )
+        .await
+    }
+
+    /// Cast, directors, authors and narrators whose name matches a `LIKE`
+    /// pattern, one row per name and role
+    #[cfg(feature = "sqlite")]
+    pub async fn people(
+        &self,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<PersonCandidate>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT j.value AS name, 'cast' AS role
+            FROM movies m, json_each(m.cast_names) j
+            WHERE m.user_id = ?1 AND json_valid(m.cast_names) AND LOWER(j.value) LIKE ?2
+            UNION
+            SELECT director, 'director' FROM movies
+            WHERE user_id = ?1 AND LOWER(director) LIKE ?2
+            UNION
+            SELECT name, 'author' FROM authors
+            WHERE user_id = ?1 AND LOWER(name) LIKE ?2
+            UNION
+            SELECT j.value, 'narrator'
+            FROM audiobooks a, json_each(a.narrators) j
+            WHERE a.user_id = ?1 AND json_valid(a.narrators) AND LOWER(j.value) LIKE ?2
+            LIMIT ?3
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(pattern)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                Ok(PersonCandidate {
+                    name: row.try_get("name")?,
+                    role: row.try_get("role")?,
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn candidates(
+        &self,
+        sql: &str,
+        user_id: Uuid,
+        pattern: &str,
+        limit: i64,
+    ) -> Result<Vec<SearchCandidate>> {
+        use sqlx::Row;
+
+        let rows = sqlx::query(sql)
+            .bind(uuid_to_str(user_id))
+            .bind(pattern)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let id: String = row.try_get("id")?;
+                Ok(SearchCandidate {
+                    id: str_to_uuid(&id)?,
+                    name: row.try_get("name")?,
+                })
+            })
+            .collect()
+    }
+}
@@ -177,6 +177,219 @@ pub fn json_array_overlaps_sql(column: &str, placeholder_count: usize) -> String
     )
 }
 
+/// Membership conditions on a JSON array column (genres, cast names, ...)
+///
+/// Values match case-insensitively. Rows whose column isn't valid JSON are
+/// treated as an empty list.
+#[derive(Debug, Clone, Default)]
+pub struct JsonArrayFilter {
+    /// At least one of these values is present
+    pub has_any: Vec<String>,
+    /// Every one of these values is present
+    pub has_all: Vec<String>,
+    /// None of these values are present
+    pub has_none: Vec<String>,
+}
+
+impl JsonArrayFilter {
+    pub fn is_empty(&self) -> bool {
+        self.has_any.is_empty() && self.has_all.is_empty() && self.has_none.is_empty()
+    }
+
+    /// SQL conditions for `column`, with placeholders numbered from
+    /// `param_idx` (which is advanced past them)
+    ///
+    /// Returns the conditions to AND together and the values to bind, in
+    /// placeholder order.
+    pub fn to_sql(&self, column: &str, param_idx: &mut usize) -> (Vec<String>, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut binds = Vec::new();
+        let mut exists = |values: &[String], param_idx: &mut usize| {
+            let placeholders: Vec<String> = values
+                .iter()
+                .map(|value| {
+                    binds.push(value.to_lowercase());
+                    *param_idx += 1;
+                    format!("?{}", *param_idx - 1)
+                })
+                .collect();
+            format!(
+                "EXISTS (SELECT 1 FROM json_each(CASE WHEN json_valid({col}) THEN {col} ELSE '[]' END) \
+                 WHERE LOWER(value) IN ({}))",
+                placeholders.join(", "),
+                col = column
+            )
+        };
+
+        if !self.has_any.is_empty() {
+            conditions.push(exists(&self.has_any, param_idx));
+        }
+        for value in &self.has_all {
+            conditions.push(exists(std::slice::from_ref(value), param_idx));
+        }
+        if !self.has_none.is_empty() {
+            conditions.push(format!("NOT {}", exists(&self.has_none, param_idx)));
+        }
+
+        (conditions, binds)
+    }
+}
+
+/// Conditions on a text column (titles, names, statuses)
+///
+/// Values match case-insensitively unless `case_sensitive` is set. Like
+/// SQLite's `NOCASE` and `LIKE`, that only folds ASCII letters.
+#[derive(Debug, Clone, Default)]
+pub struct TextFilter {
+    pub eq: Option<String>,
+    pub ne: Option<String>,
+    pub contains: Option<String>,
+    pub starts_with: Option<String>,
+    pub ends_with: Option<String>,
+    pub in_list: Vec<String>,
+    pub not_in: Vec<String>,
+    /// Compare exactly instead of ignoring case
+    pub case_sensitive: bool,
+}
+
+impl TextFilter {
+    pub fn is_empty(&self) -> bool {
+        self.eq.is_none()
+            && self.ne.is_none()
+            && self.contains.is_none()
+            && self.starts_with.is_none()
+            && self.ends_with.is_none()
+            && self.in_list.is_empty()
+            && self.not_in.is_empty()
+    }
+
+    /// SQL conditions for `column`, with placeholders numbered from
+    /// `param_idx` (which is advanced past them)
+    ///
+    /// Returns the conditions to AND together and the values to bind, in
+    /// placeholder order. Case-insensitive conditions compare with
+    /// `COLLATE NOCASE` and `LIKE`, case-sensitive ones with plain `=` and
+    /// `GLOB`; wildcards in the values are escaped either way.
+    pub fn to_sql(&self, column: &str, param_idx: &mut usize) -> (Vec<String>, Vec<String>) {
+        let mut conditions = Vec::new();
+        let mut binds = Vec::new();
+        let mut placeholder = |value: String| {
+            binds.push(value);
+            *param_idx += 1;
+            format!("?{}", *param_idx - 1)
+        };
+        let collate = if self.case_sensitive {
+            ""
+        } else {
+            " COLLATE NOCASE"
+        };
+
+        if let Some(value) = &self.eq {
+            conditions.push(format!(
+                "{}{} = {}",
+                column,
+                collate,
+                placeholder(value.clone())
+            ));
+        }
+        if let Some(value) = &self.ne {
+            conditions.push(format!(
+                "{}{} <> {}",
+                column,
+                collate,
+                placeholder(value.clone())
+            ));
+        }
+
+        let patterns = [
+            (&self.contains, true, true),
+            (&self.starts_with, false, true),
+            (&self.ends_with, true, false),
+        ];
+        for (value, any_before, any_after) in patterns {
+            let Some(value) = value else {
+                continue;
+            };
+            let (wildcard, escaped) = if self.case_sensitive {
+                ("*", escape_glob(value))
+            } else {
+                ("%", escape_like(value))
+            };
+            let pattern = format!(
+                "{}{}{}",
+                if any_before { wildcard } else { "" },
+                escaped,
+                if any_after { wildcard } else { "" }
+            );
+            let condition = if self.case_sensitive {
+                format!("{} GLOB {}", column, placeholder(pattern))
+            } else {
+                format!("{} LIKE {} ESCAPE '\\'", column, placeholder(pattern))
+            };
+            conditions.push(condition);
+        }
+
+        for (values, operator) in [(&self.in_list, "IN"), (&self.not_in, "NOT IN")] {
+            if values.is_empty() {
+                continue;
+            }
+            let placeholders: Vec<String> = values
+                .iter()
+                .map(|value| placeholder(value.clone()))
+                .collect();
+            conditions.push(format!(
+                "{}{} {} ({})",
+                column,
+                collate,
+                operator,
+                placeholders.join(", ")
+            ));
+        }
+
+        (conditions, binds)
+    }
+}
+
+/// Escape `LIKE` wildcards for use with `ESCAPE '\'`
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Escape `GLOB` wildcards by wrapping each in a character class
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '*' | '?' | '[' => {
+                escaped.push('[');
+                escaped.push(c);
+                escaped.push(']');
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Condition that `column` is `prefix` or a path under it
+///
+/// Compares exactly rather than with `LIKE`, which would ignore case and
+/// treat `_` and `%` in folder names as wildcards. Like
+/// [`MediaFileRepository::get_by_path`](crate::db::MediaFileRepository::get_by_path),
+/// paths ignore case on Windows.
+pub fn path_prefix_sql(column: &str, prefix: &str) -> String {
+    let collation = if cfg!(windows) { " COLLATE NOCASE" } else { "" };
+    format!(
+        "substr({col}, 1, length({prefix})) = {prefix}{collation}",
+        col = column,
+        prefix = prefix,
+        collation = collation
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,6 +447,75 @@ mod tests {
         assert!(!int_to_bool(0));
     }
 
+    #[test]
+    fn test_json_array_filter_sql() {
+        let filter = JsonArrayFilter {
+            has_any: vec![],
+            has_all: vec!["Horror".to_string(), "Thriller".to_string()],
+            has_none: vec!["Comedy".to_string()],
+        };
+        let mut param_idx = 3;
+        let (conditions, binds) = filter.to_sql("genres", &mut param_idx);
+        assert_eq!(conditions.len(), 3);
+        assert!(conditions[0].ends_with("WHERE LOWER(value) IN (?3))"));
+        assert!(conditions[1].ends_with("IN (?4))"));
+        assert!(conditions[2].starts_with("NOT EXISTS"));
+        assert!(conditions[2].ends_with("IN (?5))"));
+        assert_eq!(binds, vec!["horror", "thriller", "comedy"]);
+        assert_eq!(param_idx, 6);
+        assert!(JsonArrayFilter::default().is_empty());
+    }
+
+    #[test]
+    fn test_text_filter_sql() {
+        let filter = TextFilter {
+            eq: Some("Alien".to_string()),
+            contains: Some("50%_off".to_string()),
+            not_in: vec!["Aliens".to_string(), "Prometheus".to_string()],
+            ..Default::default()
+        };
+        let mut param_idx = 2;
+        let (conditions, binds) = filter.to_sql("title", &mut param_idx);
+        assert_eq!(
+            conditions,
+            vec![
+                "title COLLATE NOCASE = ?2",
+                "title LIKE ?3 ESCAPE '\\'",
+                "title COLLATE NOCASE NOT IN (?4, ?5)",
+            ]
+        );
+        assert_eq!(
+            binds,
+            vec!["Alien", "%50\\%\\_off%", "Aliens", "Prometheus"]
+        );
+        assert_eq!(param_idx, 6);
+        assert!(TextFilter::default().is_empty());
+    }
+
+    #[test]
+    fn test_text_filter_case_sensitive_sql() {
+        let filter = TextFilter {
+            ne: Some("Alien".to_string()),
+            starts_with: Some("What?".to_string()),
+            ends_with: Some("[2019]".to_string()),
+            case_sensitive: true,
+            ..Default::default()
+        };
+        let mut param_idx = 2;
+        let (conditions, binds) = filter.to_sql("name", &mut param_idx);
+        assert_eq!(
+            conditions,
+            vec!["name <> ?2", "name GLOB ?3", "name GLOB ?4"]
+        );
+        assert_eq!(binds, vec!["Alien", "What[?]*", "*[[]2019]"]);
+    }
+
+    #[test]
+    fn test_path_prefix_sql() {
+        let sql = path_prefix_sql("mf.path", "l.path");
+        assert!(sql.starts_with("substr(mf.path, 1, length(l.path)) = l.path"));
+    }
+
     #[test]
     fn test_json_array_contains_sql() {
         let sql = json_array_contains_sql("genres");
@@ -248,6 +248,12 @@ pub struct AudioStreamRecord {
     pub is_default: bool,
     pub is_commentary: bool,
     pub metadata: Option<serde_json::Value>,
+    /// EBU R128 integrated loudness in LUFS, if measured
+    pub integrated_loudness: Option<f64>,
+    /// Loudness range in LU
+    pub loudness_range: Option<f64>,
+    /// True peak in dBTP
+    pub true_peak: Option<f64>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -284,11 +290,25 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for AudioStreamRecord {
                 .map(|s| serde_json::from_str(&s))
                 .transpose()
                 .map_err(|e: serde_json::Error| sqlx::Error::Decode(Box::new(e)))?,
+            integrated_loudness: row.try_get("integrated_loudness")?,
+            loudness_range: row.try_get("loudness_range")?,
+            true_peak: row.try_get("true_peak")?,
             created_at: str_to_datetime(&created_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
         })
     }
 }
 
+impl AudioStreamRecord {
+    /// The measured loudness, if loudness analysis has run on the file
+    pub fn loudness(&self) -> Option<crate::services::ffmpeg::Loudness> {
+        Some(crate::services::ffmpeg::Loudness {
+            integrated: self.integrated_loudness?,
+            range: self.loudness_range,
+            true_peak: self.true_peak,
+        })
+    }
+}
+
 /// Chapter record from database
 #[derive(Debug, Clone)]
 pub struct ChapterRecord {
@@ -709,7 +729,8 @@ impl StreamRepository {
             r#"
             SELECT id, media_file_id, stream_index, codec, codec_long_name,
                    channels, channel_layout, sample_rate, bitrate, bit_depth,
-                   language, title, is_default, is_commentary, metadata, created_at
+                   language, title, is_default, is_commentary, metadata,
+                   integrated_loudness, loudness_range, true_peak, created_at
             FROM audio_streams
             WHERE media_file_id = ?1
             ORDER BY stream_index
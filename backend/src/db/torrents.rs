@@ -284,6 +284,50 @@ impl TorrentRepository {
         Ok(())
     }
 
+    /// Record the indexer a torrent was grabbed from
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_source_indexer(&self, info_hash: &str, indexer_id: Uuid) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query("UPDATE torrents SET source_indexer_id = ?2 WHERE info_hash = ?1")
+            .bind(info_hash)
+            .bind(uuid_to_str(indexer_id))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Get all torrents grabbed from an indexer
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_by_source_indexer(&self, indexer_id: Uuid) -> Result<Vec<TorrentRecord>> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        let records = sqlx::query_as::<_, TorrentRecord>(
+            "SELECT * FROM torrents WHERE source_indexer_id = ?1 ORDER BY added_at ASC",
+        )
+        .bind(uuid_to_str(indexer_id))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
+    /// Replace the magnet (or .torrent URL) a torrent is resumed from
+
+    #[cfg(feature = "sqlite")]
+    pub async fn set_magnet_uri(&self, info_hash: &str, magnet_uri: &str) -> Result<()> {
+        sqlx::query("UPDATE torrents SET magnet_uri = ?2 WHERE info_hash = ?1")
+            .bind(info_hash)
+            .bind(magnet_uri)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
     /// Mark torrent as completed
 
     #[cfg(feature = "sqlite")]
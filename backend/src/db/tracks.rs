@@ -641,7 +641,7 @@ impl TrackRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        title_filter: Option<&str>,
+        title_filter: &crate::db::sqlite_helpers::TextFilter,
         has_file_filter: Option<bool>,
         sort_column: &str,
         sort_asc: bool,
@@ -651,11 +651,8 @@ impl TrackRepository {
         let mut conditions = vec!["library_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if title_filter.is_some() {
-            conditions.push(format!("LOWER(title) LIKE ?{}", param_idx));
-            param_idx += 1;
-        }
-        let _ = param_idx; // Suppress unused variable warning
+        let (title_conditions, title_binds) = title_filter.to_sql("title", &mut param_idx);
+        conditions.extend(title_conditions);
         if has_file_filter.is_some() {
             conditions.push(format!(
                 "media_file_id IS {} NULL",
@@ -707,16 +704,16 @@ impl TrackRepository {
         let library_id_str = uuid_to_str(library_id);
 
         let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            count_builder = count_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            count_builder = count_builder.bind(value);
         }
 
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
 
         let mut data_builder =
             sqlx::query_as::<_, TrackRecord>(&data_query).bind(&library_id_str);
-        if let Some(title) = title_filter {
-            data_builder = data_builder.bind(format!("%{}%", title.to_lowercase()));
+        for value in &title_binds {
+            data_builder = data_builder.bind(value);
         }
 
         let records: Vec<TrackRecord> = data_builder.fetch_all(&self.pool).await?;
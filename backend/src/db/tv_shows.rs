@@ -219,6 +219,31 @@ pub struct UpdateTvShow {
     pub release_group_whitelist_override: Option<Option<Vec<String>>>,
 }
 
+/// Metadata to fill into a TV show where its fields are still empty
+#[derive(Debug, Default)]
+pub struct FillTvShowMetadata {
+    pub year: Option<i32>,
+    pub overview: Option<String>,
+    pub network: Option<String>,
+    pub runtime: Option<i32>,
+    /// Only used when the show has no genres
+    pub genres: Vec<String>,
+    /// Cached artwork; also replaces a provider URL left by a failed cache
+    pub poster_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    pub tmdb_id: Option<i32>,
+    pub tvdb_id: Option<i32>,
+    pub imdb_id: Option<String>,
+}
+
+/// A show whose missing metadata is due for another attempt
+#[derive(Debug, Clone)]
+pub struct MissingMetadataRecord {
+    pub show_id: Uuid,
+    pub missing: Vec<String>,
+    pub attempts: i32,
+}
+
 pub struct TvShowRepository {
     pool: DbPool,
 }
@@ -269,10 +294,10 @@ impl TvShowRepository {
         library_id: Uuid,
         offset: i64,
         limit: i64,
-        name_filter: Option<&str>,
+        name_filter: &crate::db::sqlite_helpers::TextFilter,
         year_filter: Option<i32>,
         monitored_filter: Option<bool>,
-        status_filter: Option<&str>,
+        status_filter: &crate::db::sqlite_helpers::TextFilter,
         sort_column: &str,
         sort_asc: bool,
     ) -> Result<(Vec<TvShowRecord>, i64)> {
@@ -280,10 +305,8 @@ impl TvShowRepository {
         let mut conditions = vec!["library_id = ?1".to_string()];
         let mut param_idx = 2;
 
-        if name_filter.is_some() {
-            conditions.push(format!("LOWER(name) LIKE ?{}", param_idx));
-            param_idx += 1;
-        }
+        let (name_conditions, name_binds) = name_filter.to_sql("name", &mut param_idx);
+        conditions.extend(name_conditions);
         if year_filter.is_some() {
             conditions.push(format!("year = ?{}", param_idx));
             param_idx += 1;
@@ -292,9 +315,8 @@ impl TvShowRepository {
             conditions.push(format!("monitored = ?{}", param_idx));
             param_idx += 1;
         }
-        if status_filter.is_some() {
-            conditions.push(format!("LOWER(status) = ?{}", param_idx));
-        }
+        let (status_conditions, status_binds) = status_filter.to_sql("status", &mut param_idx);
+        conditions.extend(status_conditions);
 
         let where_clause = conditions.join(" AND ");
 
@@ -342,8 +364,8 @@ impl TvShowRepository {
         // Execute count query
         let mut count_builder =
             sqlx::query_scalar::<_, i64>(&count_query).bind(library_id_str.clone());
-        if let Some(name) = name_filter {
-            count_builder = count_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            count_builder = count_builder.bind(value);
         }
         if let Some(year) = year_filter {
             count_builder = count_builder.bind(year);
@@ -351,8 +373,8 @@ impl TvShowRepository {
         if let Some(monitored) = monitored_filter {
             count_builder = count_builder.bind(bool_to_int(monitored));
         }
-        if let Some(status) = status_filter {
-            count_builder = count_builder.bind(status.to_lowercase());
+        for value in &status_binds {
+            count_builder = count_builder.bind(value);
         }
 
         let total: i64 = count_builder.fetch_one(&self.pool).await?;
@@ -360,8 +382,8 @@ impl TvShowRepository {
         // Execute data query
         let mut data_builder =
             sqlx::query_as::<_, TvShowRecord>(&data_query).bind(library_id_str);
-        if let Some(name) = name_filter {
-            data_builder = data_builder.bind(format!("%{}%", name.to_lowercase()));
+        for value in &name_binds {
+            data_builder = data_builder.bind(value);
         }
         if let Some(year) = year_filter {
             data_builder = data_builder.bind(year);
@@ -369,8 +391,8 @@ impl TvShowRepository {
         if let Some(monitored) = monitored_filter {
             data_builder = data_builder.bind(bool_to_int(monitored));
         }
-        if let Some(status) = status_filter {
-            data_builder = data_builder.bind(status.to_lowercase());
+        for value in &status_binds {
+            data_builder = data_builder.bind(value);
         }
 
         let records = data_builder.fetch_all(&self.pool).await?;
@@ -436,6 +458,43 @@ impl TvShowRepository {
         Ok(records)
     }
 
+    /// List shows with downloaded episodes that the user hasn't started, newest first
+
+    #[cfg(feature = "sqlite")]
+    pub async fn list_unstarted_with_files(&self, user_id: Uuid, limit: i64) -> Result<Vec<TvShowRecord>> {
+        let records = sqlx::query_as::<_, TvShowRecord>(
+            r#"
+            SELECT id, library_id, user_id, name, sort_name, year, status,
+                   tvmaze_id, tmdb_id, tvdb_id, imdb_id, overview, network,
+                   runtime, genres, poster_url, backdrop_url, monitored,
+                   monitor_type, path,
+                   auto_download_override, backfill_existing,
+                   organize_files_override, rename_style_override, auto_hunt_override,
+                   episode_count, episode_file_count, size_bytes, created_at, updated_at,
+                   allowed_resolutions_override, allowed_video_codecs_override,
+                   allowed_audio_formats_override, require_hdr_override,
+                   allowed_hdr_types_override, allowed_sources_override,
+                   release_group_blacklist_override, release_group_whitelist_override
+            FROM tv_shows s
+            WHERE s.user_id = ?1
+              AND s.episode_file_count > 0
+              AND NOT EXISTS (
+                  SELECT 1 FROM watch_progress wp
+                  JOIN episodes e ON e.id = wp.episode_id
+                  WHERE e.tv_show_id = s.id AND wp.user_id = ?1
+              )
+            ORDER BY s.created_at DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
     /// Get a TV show by ID
 
     #[cfg(feature = "sqlite")]
@@ -880,4 +939,155 @@ impl TvShowRepository {
 
         Ok(())
     }
+
+    /// Set whether a TV show is monitored
+    #[cfg(feature = "sqlite")]
+    pub async fn set_monitored(&self, id: Uuid, monitored: bool) -> Result<()> {
+        use crate::db::sqlite_helpers::bool_to_int;
+
+        sqlx::query("UPDATE tv_shows SET monitored = ?2, updated_at = datetime('now') WHERE id = ?1")
+            .bind(uuid_to_str(id))
+            .bind(bool_to_int(monitored))
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Replace a show's poster and backdrop URLs
+    #[cfg(feature = "sqlite")]
+    pub async fn set_artwork_urls(
+        &self,
+        id: Uuid,
+        poster_url: Option<&str>,
+        backdrop_url: Option<&str>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE tv_shows SET poster_url = ?2, backdrop_url = ?3, updated_at = datetime('now') WHERE id = ?1",
+        )
+        .bind(uuid_to_str(id))
+        .bind(poster_url)
+        .bind(backdrop_url)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+    /// Record the metadata a show is missing and when to retry it
+    ///
+    /// An empty list (or no retry time) takes the show off the backfill
+    /// job's list.
+    #[cfg(feature = "sqlite")]
+    pub async fn set_missing_metadata(
+        &self,
+        id: Uuid,
+        missing: &[String],
+        attempts: i32,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> Result<()> {
+        use crate::db::sqlite_helpers::datetime_to_str;
+
+        let retry_at = retry_at.filter(|_| !missing.is_empty());
+        sqlx::query(
+            r#"
+            UPDATE tv_shows SET
+                missing_metadata = ?2,
+                metadata_attempts = ?3,
+                metadata_retry_at = ?4
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(vec_to_json(missing))
+        .bind(attempts)
+        .bind(retry_at.map(datetime_to_str))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get the metadata a show is missing
+    #[cfg(feature = "sqlite")]
+    pub async fn get_missing_metadata(&self, id: Uuid) -> Result<Vec<String>> {
+        let missing: Option<String> =
+            sqlx::query_scalar("SELECT missing_metadata FROM tv_shows WHERE id = ?1")
+                .bind(uuid_to_str(id))
+                .fetch_optional(&self.pool)
+                .await?;
+
+        Ok(missing.map(|m| json_to_vec(&m)).unwrap_or_default())
+    }
+
+    /// Shows whose missing metadata is due for a retry, oldest first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_missing_metadata_due(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<MissingMetadataRecord>> {
+        use crate::db::sqlite_helpers::datetime_to_str;
+
+        let rows: Vec<(String, String, i32)> = sqlx::query_as(
+            r#"
+            SELECT id, missing_metadata, metadata_attempts
+            FROM tv_shows
+            WHERE metadata_retry_at IS NOT NULL AND metadata_retry_at <= ?1
+            ORDER BY metadata_retry_at
+            LIMIT ?2
+            "#,
+        )
+        .bind(datetime_to_str(chrono::Utc::now()))
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|(id, missing, attempts)| {
+                Ok(MissingMetadataRecord {
+                    show_id: str_to_uuid(&id)?,
+                    missing: json_to_vec(&missing),
+                    attempts,
+                })
+            })
+            .collect()
+    }
+
+    /// Fill a show's empty metadata fields, leaving ones that are set alone
+    #[cfg(feature = "sqlite")]
+    pub async fn fill_missing_metadata(&self, id: Uuid, input: FillTvShowMetadata) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tv_shows SET
+                year = COALESCE(year, ?2),
+                overview = COALESCE(NULLIF(overview, ''), ?3),
+                network = COALESCE(network, ?4),
+                runtime = COALESCE(runtime, ?5),
+                genres = CASE WHEN genres IS NULL OR genres = '[]' THEN ?6 ELSE genres END,
+                poster_url = CASE WHEN ?7 LIKE '%/api/artwork/%' THEN ?7
+                                  ELSE COALESCE(poster_url, ?7) END,
+                backdrop_url = CASE WHEN ?8 LIKE '%/api/artwork/%' THEN ?8
+                                    ELSE COALESCE(backdrop_url, ?8) END,
+                tmdb_id = COALESCE(tmdb_id, ?9),
+                tvdb_id = COALESCE(tvdb_id, ?10),
+                imdb_id = COALESCE(imdb_id, ?11),
+                updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(input.year)
+        .bind(input.overview)
+        .bind(input.network)
+        .bind(input.runtime)
+        .bind(vec_to_json(&input.genres))
+        .bind(input.poster_url)
+        .bind(input.backdrop_url)
+        .bind(input.tmdb_id)
+        .bind(input.tvdb_id)
+        .bind(input.imdb_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -18,8 +18,12 @@ pub enum UsenetDownloadState {
     Queued,
     Downloading,
     Paused,
+    /// PAR2 verification or repair is running
+    Repairing,
     Completed,
     Failed,
+    /// Articles were missing and PAR2 couldn't repair them
+    RepairFailed,
     Removed,
 }
 
@@ -29,8 +33,10 @@ impl std::fmt::Display for UsenetDownloadState {
             UsenetDownloadState::Queued => write!(f, "queued"),
             UsenetDownloadState::Downloading => write!(f, "downloading"),
             UsenetDownloadState::Paused => write!(f, "paused"),
+            UsenetDownloadState::Repairing => write!(f, "repairing"),
             UsenetDownloadState::Completed => write!(f, "completed"),
             UsenetDownloadState::Failed => write!(f, "failed"),
+            UsenetDownloadState::RepairFailed => write!(f, "repair_failed"),
             UsenetDownloadState::Removed => write!(f, "removed"),
         }
     }
@@ -44,8 +50,10 @@ impl std::str::FromStr for UsenetDownloadState {
             "queued" => Ok(UsenetDownloadState::Queued),
             "downloading" => Ok(UsenetDownloadState::Downloading),
             "paused" => Ok(UsenetDownloadState::Paused),
+            "repairing" => Ok(UsenetDownloadState::Repairing),
             "completed" => Ok(UsenetDownloadState::Completed),
             "failed" => Ok(UsenetDownloadState::Failed),
+            "repair_failed" => Ok(UsenetDownloadState::RepairFailed),
             "removed" => Ok(UsenetDownloadState::Removed),
             _ => Err(anyhow::anyhow!("Unknown usenet download state: {}", s)),
         }
@@ -75,6 +83,11 @@ pub struct UsenetDownloadRecord {
     pub audiobook_id: Option<Uuid>,
     pub indexer_id: Option<Uuid>,
     pub post_process_status: Option<String>,
+    /// PAR2 result (see `services::par2::Par2Status`)
+    pub repair_status: Option<String>,
+    pub repair_blocks_needed: Option<i64>,
+    pub repair_recovery_blocks: Option<i64>,
+    pub repair_duration_ms: Option<i64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
@@ -143,6 +156,10 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for UsenetDownloadRecord {
                 .transpose()
                 .map_err(|e| sqlx::Error::Decode(e.into()))?,
             post_process_status: row.try_get("post_process_status")?,
+            repair_status: row.try_get("repair_status")?,
+            repair_blocks_needed: row.try_get("repair_blocks_needed")?,
+            repair_recovery_blocks: row.try_get("repair_recovery_blocks")?,
+            repair_duration_ms: row.try_get("repair_duration_ms")?,
             created_at: str_to_datetime(&created_at_str)
                 .map_err(|e| sqlx::Error::Decode(e.into()))?,
             updated_at: str_to_datetime(&updated_at_str)
@@ -207,7 +224,8 @@ impl UsenetDownloadsRepository {
                    size_bytes, downloaded_bytes, download_speed, eta_seconds,
                    error_message, retry_count, download_path,
                    library_id, episode_id, movie_id, album_id, audiobook_id,
-                   indexer_id, post_process_status, created_at, updated_at, completed_at
+                   indexer_id, post_process_status, repair_status, repair_blocks_needed,
+                   repair_recovery_blocks, repair_duration_ms, created_at, updated_at, completed_at
             FROM usenet_downloads
             WHERE id = ?1
             "#,
@@ -229,7 +247,8 @@ impl UsenetDownloadsRepository {
                    size_bytes, downloaded_bytes, download_speed, eta_seconds,
                    error_message, retry_count, download_path,
                    library_id, episode_id, movie_id, album_id, audiobook_id,
-                   indexer_id, post_process_status, created_at, updated_at, completed_at
+                   indexer_id, post_process_status, repair_status, repair_blocks_needed,
+                   repair_recovery_blocks, repair_duration_ms, created_at, updated_at, completed_at
             FROM usenet_downloads
             WHERE nzb_hash = ?1
             "#,
@@ -253,7 +272,8 @@ impl UsenetDownloadsRepository {
                    size_bytes, downloaded_bytes, download_speed, eta_seconds,
                    error_message, retry_count, download_path,
                    library_id, episode_id, movie_id, album_id, audiobook_id,
-                   indexer_id, post_process_status, created_at, updated_at, completed_at
+                   indexer_id, post_process_status, repair_status, repair_blocks_needed,
+                   repair_recovery_blocks, repair_duration_ms, created_at, updated_at, completed_at
             FROM usenet_downloads
             WHERE user_id = ?1 AND state != 'removed'
             ORDER BY created_at DESC
@@ -266,7 +286,7 @@ impl UsenetDownloadsRepository {
         Ok(records)
     }
 
-    /// Get active usenet downloads (downloading or queued)
+    /// Get active usenet downloads (queued, downloading or repairing)
 
     #[cfg(feature = "sqlite")]
     pub async fn list_active(&self, user_id: Uuid) -> Result<Vec<UsenetDownloadRecord>> {
@@ -278,9 +298,10 @@ impl UsenetDownloadsRepository {
                    size_bytes, downloaded_bytes, download_speed, eta_seconds,
                    error_message, retry_count, download_path,
                    library_id, episode_id, movie_id, album_id, audiobook_id,
-                   indexer_id, post_process_status, created_at, updated_at, completed_at
+                   indexer_id, post_process_status, repair_status, repair_blocks_needed,
+                   repair_recovery_blocks, repair_duration_ms, created_at, updated_at, completed_at
             FROM usenet_downloads
-            WHERE user_id = ?1 AND state IN ('queued', 'downloading')
+            WHERE user_id = ?1 AND state IN ('queued', 'downloading', 'repairing')
             ORDER BY created_at ASC
             "#,
         )
@@ -299,7 +320,8 @@ impl UsenetDownloadsRepository {
                    size_bytes, downloaded_bytes, download_speed, eta_seconds,
                    error_message, retry_count, download_path,
                    library_id, episode_id, movie_id, album_id, audiobook_id,
-                   indexer_id, post_process_status, created_at, updated_at, completed_at
+                   indexer_id, post_process_status, repair_status, repair_blocks_needed,
+                   repair_recovery_blocks, repair_duration_ms, created_at, updated_at, completed_at
             FROM usenet_downloads
             WHERE state = 'completed'
               AND (post_process_status IS NULL OR post_process_status = 'pending')
@@ -436,6 +458,53 @@ impl UsenetDownloadsRepository {
         Ok(())
     }
 
+    /// Mark download as failed because PAR2 couldn't repair it
+
+    #[cfg(feature = "sqlite")]
+    pub async fn mark_repair_failed(&self, id: Uuid, error: &str) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query(
+            r#"
+            UPDATE usenet_downloads
+            SET state = 'repair_failed',
+                error_message = ?2,
+                retry_count = retry_count + 1,
+                updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Mark download as repairing once all articles are assembled
+
+    #[cfg(feature = "sqlite")]
+    pub async fn mark_repairing(&self, id: Uuid) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query(
+            r#"
+            UPDATE usenet_downloads
+            SET state = 'repairing',
+                download_speed = 0,
+                eta_seconds = NULL,
+                updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Pause a download
 
     #[cfg(feature = "sqlite")]
@@ -531,6 +600,41 @@ impl UsenetDownloadsRepository {
         Ok(())
     }
 
+    /// Record the result of PAR2 verification
+
+    #[cfg(feature = "sqlite")]
+    pub async fn record_repair(
+        &self,
+        id: Uuid,
+        status: &str,
+        blocks_needed: Option<i64>,
+        recovery_blocks: Option<i64>,
+        duration_ms: i64,
+    ) -> Result<()> {
+        use crate::db::sqlite_helpers::uuid_to_str;
+
+        sqlx::query(
+            r#"
+            UPDATE usenet_downloads
+            SET repair_status = ?2,
+                repair_blocks_needed = ?3,
+                repair_recovery_blocks = ?4,
+                repair_duration_ms = ?5,
+                updated_at = datetime('now')
+            WHERE id = ?1
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(status)
+        .bind(blocks_needed)
+        .bind(recovery_blocks)
+        .bind(duration_ms)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
     /// Link download to library item
 
     #[cfg(feature = "sqlite")]
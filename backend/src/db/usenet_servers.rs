@@ -1,6 +1,7 @@
 //! Usenet servers database repository
 //!
-//! Handles CRUD operations for Usenet NNTP server configurations.
+//! Handles CRUD operations for Usenet NNTP server configurations, and the
+//! per-server daily usage counted by downloads.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -33,6 +34,9 @@ pub struct UsenetServerRecord {
     pub connections: i32,
     pub priority: i32,
     pub enabled: bool,
+    /// Only used for articles the primary servers don't have (block
+    /// accounts)
+    pub is_backup: bool,
     pub retention_days: Option<i32>,
     pub last_success_at: Option<DateTime<Utc>>,
     pub last_error: Option<String>,
@@ -51,6 +55,7 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for UsenetServerRecord {
         let user_id_str: String = row.try_get("user_id")?;
         let use_ssl: i32 = row.try_get("use_ssl")?;
         let enabled: i32 = row.try_get("enabled")?;
+        let is_backup: i32 = row.try_get("is_backup")?;
         let last_success_str: Option<String> = row.try_get("last_success_at")?;
         let created_str: String = row.try_get("created_at")?;
         let updated_str: String = row.try_get("updated_at")?;
@@ -68,6 +73,7 @@ impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for UsenetServerRecord {
             connections: row.try_get("connections")?,
             priority: row.try_get("priority")?,
             enabled: int_to_bool(enabled),
+            is_backup: int_to_bool(is_backup),
             retention_days: row.try_get("retention_days")?,
             last_success_at: str_to_datetime_opt(last_success_str.as_deref())
                 .map_err(|e| sqlx::Error::Decode(e.into()))?,
@@ -94,6 +100,7 @@ pub struct CreateUsenetServer {
     pub password_nonce: Option<String>,
     pub connections: i32,
     pub priority: i32,
+    pub is_backup: bool,
     pub retention_days: Option<i32>,
 }
 
@@ -110,9 +117,59 @@ pub struct UpdateUsenetServer {
     pub connections: Option<i32>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
+    pub is_backup: Option<bool>,
     pub retention_days: Option<i32>,
 }
 
+/// Article counts for a server
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsenetUsageCounts {
+    pub bytes_downloaded: i64,
+    pub articles_downloaded: i64,
+    /// Articles the server didn't have (430)
+    pub articles_missing: i64,
+    /// Articles that failed for other reasons
+    pub articles_failed: i64,
+}
+
+impl UsenetUsageCounts {
+    pub fn add(&mut self, other: &UsenetUsageCounts) {
+        self.bytes_downloaded += other.bytes_downloaded;
+        self.articles_downloaded += other.articles_downloaded;
+        self.articles_missing += other.articles_missing;
+        self.articles_failed += other.articles_failed;
+    }
+}
+
+/// A server's usage on one day
+#[derive(Debug, Clone)]
+pub struct UsenetServerUsageRecord {
+    pub server_id: Uuid,
+    /// UTC date, YYYY-MM-DD
+    pub day: String,
+    pub counts: UsenetUsageCounts,
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for UsenetServerUsageRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let server_id_str: String = row.try_get("server_id")?;
+
+        Ok(Self {
+            server_id: str_to_uuid(&server_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            day: row.try_get("day")?,
+            counts: UsenetUsageCounts {
+                bytes_downloaded: row.try_get("bytes_downloaded")?,
+                articles_downloaded: row.try_get("articles_downloaded")?,
+                articles_missing: row.try_get("articles_missing")?,
+                articles_failed: row.try_get("articles_failed")?,
+            },
+        })
+    }
+}
+
 /// Usenet servers database repository
 pub struct UsenetServersRepository {
     pool: DbPool,
@@ -132,8 +189,8 @@ impl UsenetServersRepository {
             r#"
             SELECT id, user_id, name, host, port, use_ssl, username,
                    encrypted_password, password_nonce, connections, priority,
-                   enabled, retention_days, last_success_at, last_error,
-                   error_count, created_at, updated_at
+                   enabled, is_backup, retention_days, last_success_at,
+                   last_error, error_count, created_at, updated_at
             FROM usenet_servers
             WHERE id = ?1
             "#,
@@ -153,8 +210,8 @@ impl UsenetServersRepository {
             r#"
             SELECT id, user_id, name, host, port, use_ssl, username,
                    encrypted_password, password_nonce, connections, priority,
-                   enabled, retention_days, last_success_at, last_error,
-                   error_count, created_at, updated_at
+                   enabled, is_backup, retention_days, last_success_at,
+                   last_error, error_count, created_at, updated_at
             FROM usenet_servers
             WHERE user_id = ?1
             ORDER BY priority ASC, name ASC
@@ -167,7 +224,8 @@ impl UsenetServersRepository {
         Ok(records)
     }
 
-    /// Get enabled usenet servers for a user, ordered by priority
+    /// Get enabled usenet servers for a user in the order downloads try
+    /// them: primary servers by priority, then backup servers by priority
 
     #[cfg(feature = "sqlite")]
     pub async fn list_enabled_by_user(&self, user_id: Uuid) -> Result<Vec<UsenetServerRecord>> {
@@ -175,11 +233,11 @@ impl UsenetServersRepository {
             r#"
             SELECT id, user_id, name, host, port, use_ssl, username,
                    encrypted_password, password_nonce, connections, priority,
-                   enabled, retention_days, last_success_at, last_error,
-                   error_count, created_at, updated_at
+                   enabled, is_backup, retention_days, last_success_at,
+                   last_error, error_count, created_at, updated_at
             FROM usenet_servers
             WHERE user_id = ?1 AND enabled = 1
-            ORDER BY priority ASC, name ASC
+            ORDER BY is_backup ASC, priority ASC, name ASC
             "#,
         )
         .bind(uuid_to_str(user_id))
@@ -189,6 +247,26 @@ impl UsenetServersRepository {
         Ok(records)
     }
 
+    /// Get every user's enabled usenet servers
+    #[cfg(feature = "sqlite")]
+    pub async fn list_enabled(&self) -> Result<Vec<UsenetServerRecord>> {
+        let records = sqlx::query_as::<_, UsenetServerRecord>(
+            r#"
+            SELECT id, user_id, name, host, port, use_ssl, username,
+                   encrypted_password, password_nonce, connections, priority,
+                   enabled, is_backup, retention_days, last_success_at,
+                   last_error, error_count, created_at, updated_at
+            FROM usenet_servers
+            WHERE enabled = 1
+            ORDER BY name ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
+
     /// Create a new usenet server
 
     #[cfg(feature = "sqlite")]
@@ -201,9 +279,9 @@ impl UsenetServersRepository {
             INSERT INTO usenet_servers (
                 id, user_id, name, host, port, use_ssl, username,
                 encrypted_password, password_nonce, connections, priority, retention_days,
-                enabled, error_count, created_at, updated_at
+                is_backup, enabled, error_count, created_at, updated_at
             )
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, 1, 0, datetime('now'), datetime('now'))
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, 1, 0, datetime('now'), datetime('now'))
             "#,
         )
         .bind(&id_str)
@@ -218,6 +296,7 @@ impl UsenetServersRepository {
         .bind(data.connections)
         .bind(data.priority)
         .bind(data.retention_days)
+        .bind(bool_to_int(data.is_backup))
         .execute(&self.pool)
         .await?;
 
@@ -276,6 +355,10 @@ impl UsenetServersRepository {
             updates.push(format!("enabled = ?{}", param_idx));
             param_idx += 1;
         }
+        if data.is_backup.is_some() {
+            updates.push(format!("is_backup = ?{}", param_idx));
+            param_idx += 1;
+        }
         if data.retention_days.is_some() {
             updates.push(format!("retention_days = ?{}", param_idx));
         }
@@ -317,6 +400,9 @@ impl UsenetServersRepository {
         if let Some(enabled) = data.enabled {
             q = q.bind(bool_to_int(enabled));
         }
+        if let Some(is_backup) = data.is_backup {
+            q = q.bind(bool_to_int(is_backup));
+        }
         if let Some(retention_days) = data.retention_days {
             q = q.bind(retention_days);
         }
@@ -403,4 +489,58 @@ impl UsenetServersRepository {
 
         Ok(())
     }
+
+    /// Add to a server's usage for today
+    #[cfg(feature = "sqlite")]
+    pub async fn record_usage(&self, id: Uuid, counts: &UsenetUsageCounts) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO usenet_server_usage (
+                server_id, day, bytes_downloaded, articles_downloaded,
+                articles_missing, articles_failed
+            )
+            VALUES (?1, date('now'), ?2, ?3, ?4, ?5)
+            ON CONFLICT(server_id, day) DO UPDATE SET
+                bytes_downloaded = bytes_downloaded + excluded.bytes_downloaded,
+                articles_downloaded = articles_downloaded + excluded.articles_downloaded,
+                articles_missing = articles_missing + excluded.articles_missing,
+                articles_failed = articles_failed + excluded.articles_failed
+            "#,
+        )
+        .bind(uuid_to_str(id))
+        .bind(counts.bytes_downloaded)
+        .bind(counts.articles_downloaded)
+        .bind(counts.articles_missing)
+        .bind(counts.articles_failed)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Daily usage of a user's servers over the last `days` days, oldest
+    /// first
+    #[cfg(feature = "sqlite")]
+    pub async fn list_usage(
+        &self,
+        user_id: Uuid,
+        days: i64,
+    ) -> Result<Vec<UsenetServerUsageRecord>> {
+        let records = sqlx::query_as::<_, UsenetServerUsageRecord>(
+            r#"
+            SELECT u.server_id, u.day, u.bytes_downloaded, u.articles_downloaded,
+                   u.articles_missing, u.articles_failed
+            FROM usenet_server_usage u
+            JOIN usenet_servers s ON s.id = u.server_id
+            WHERE s.user_id = ?1 AND u.day > date('now', '-' || ?2 || ' days')
+            ORDER BY u.day ASC
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(days)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records)
+    }
 }
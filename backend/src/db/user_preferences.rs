@@ -0,0 +1,266 @@
+//! User preferences database operations
+//!
+//! One row per user holding UI preferences (theme, default library, grid
+//! density, language, notification toggles). Users without a row get the
+//! defaults; the row is written on the first update.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg(feature = "sqlite")]
+use sqlx::SqlitePool;
+
+#[cfg(feature = "sqlite")]
+use crate::db::sqlite_helpers::{
+    bool_to_int, int_to_bool, json_to_vec, str_to_datetime, str_to_uuid, str_to_uuid_opt,
+    uuid_to_str, vec_to_json,
+};
+
+#[cfg(feature = "sqlite")]
+type DbPool = SqlitePool;
+
+/// Themes the UI supports ("system" follows the OS)
+pub const THEMES: &[&str] = &["light", "dark", "system"];
+
+/// Poster grid densities
+pub const GRID_DENSITIES: &[&str] = &["compact", "comfortable", "spacious"];
+
+/// User preferences record
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserPreferencesRecord {
+    pub user_id: Uuid,
+    pub theme: String,
+    pub default_library_id: Option<Uuid>,
+    pub grid_density: String,
+    /// BCP 47 language tag, e.g. "en" or "pt-BR"
+    pub language: String,
+    pub notifications_enabled: bool,
+    /// Notification categories the user doesn't want toasts for
+    pub muted_notification_categories: Vec<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserPreferencesRecord {
+    /// Preferences for a user who hasn't changed any
+    pub fn defaults(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            theme: "system".to_string(),
+            default_library_id: None,
+            grid_density: "comfortable".to_string(),
+            language: "en".to_string(),
+            notifications_enabled: true,
+            muted_notification_categories: Vec::new(),
+            updated_at: DateTime::<Utc>::UNIX_EPOCH,
+        }
+    }
+
+    /// Apply an update; fields left as None are unchanged
+    pub fn apply(&mut self, update: UpdateUserPreferences) {
+        if let Some(theme) = update.theme {
+            self.theme = theme;
+        }
+        if let Some(default_library_id) = update.default_library_id {
+            self.default_library_id = default_library_id;
+        }
+        if let Some(grid_density) = update.grid_density {
+            self.grid_density = grid_density;
+        }
+        if let Some(language) = update.language {
+            self.language = language;
+        }
+        if let Some(enabled) = update.notifications_enabled {
+            self.notifications_enabled = enabled;
+        }
+        if let Some(categories) = update.muted_notification_categories {
+            self.muted_notification_categories = categories;
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl sqlx::FromRow<'_, sqlx::sqlite::SqliteRow> for UserPreferencesRecord {
+    fn from_row(row: &sqlx::sqlite::SqliteRow) -> sqlx::Result<Self> {
+        use sqlx::Row;
+
+        let user_id_str: String = row.try_get("user_id")?;
+        let default_library_str: Option<String> = row.try_get("default_library_id")?;
+        let notifications_enabled: i32 = row.try_get("notifications_enabled")?;
+        let muted: String = row.try_get("muted_notification_categories")?;
+        let updated_str: String = row.try_get("updated_at")?;
+
+        Ok(Self {
+            user_id: str_to_uuid(&user_id_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+            theme: row.try_get("theme")?,
+            default_library_id: str_to_uuid_opt(default_library_str.as_deref())
+                .map_err(|e| sqlx::Error::Decode(e.into()))?,
+            grid_density: row.try_get("grid_density")?,
+            language: row.try_get("language")?,
+            notifications_enabled: int_to_bool(notifications_enabled),
+            muted_notification_categories: json_to_vec(&muted),
+            updated_at: str_to_datetime(&updated_str).map_err(|e| sqlx::Error::Decode(e.into()))?,
+        })
+    }
+}
+
+/// Input for updating preferences (None leaves a field unchanged)
+#[derive(Debug, Clone, Default)]
+pub struct UpdateUserPreferences {
+    pub theme: Option<String>,
+    /// Some(None) clears the default library
+    pub default_library_id: Option<Option<Uuid>>,
+    pub grid_density: Option<String>,
+    pub language: Option<String>,
+    pub notifications_enabled: Option<bool>,
+    pub muted_notification_categories: Option<Vec<String>>,
+}
+
+impl UpdateUserPreferences {
+    /// Check the values the schema constrains, so a bad value is reported
+    /// by name rather than as a failed CHECK
+    pub fn validate(&self) -> Result<()> {
+        if let Some(ref theme) = self.theme
+            && !THEMES.contains(&theme.as_str())
+        {
+            anyhow::bail!("Theme must be one of: {}", THEMES.join(", "));
+        }
+        if let Some(ref density) = self.grid_density
+            && !GRID_DENSITIES.contains(&density.as_str())
+        {
+            anyhow::bail!("Grid density must be one of: {}", GRID_DENSITIES.join(", "));
+        }
+        if let Some(ref language) = self.language {
+            let valid = !language.is_empty()
+                && language.len() <= 35
+                && language.split('-').all(|part| {
+                    !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric())
+                });
+            if !valid {
+                anyhow::bail!("Language must be a language tag such as \"en\" or \"pt-BR\"");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// User preferences repository for database operations
+pub struct UserPreferencesRepository {
+    pool: DbPool,
+}
+
+impl UserPreferencesRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get a user's preferences, or the defaults if they haven't set any
+    #[cfg(feature = "sqlite")]
+    pub async fn get(&self, user_id: Uuid) -> Result<UserPreferencesRecord> {
+        let record = sqlx::query_as::<_, UserPreferencesRecord>(
+            "SELECT * FROM user_preferences WHERE user_id = ?1",
+        )
+        .bind(uuid_to_str(user_id))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.unwrap_or_else(|| UserPreferencesRecord::defaults(user_id)))
+    }
+
+    /// Update a user's preferences, creating their row if needed
+    #[cfg(feature = "sqlite")]
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        update: UpdateUserPreferences,
+    ) -> Result<UserPreferencesRecord> {
+        let mut prefs = self.get(user_id).await?;
+        prefs.apply(update);
+        prefs.updated_at = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_preferences (
+                user_id, theme, default_library_id, grid_density, language,
+                notifications_enabled, muted_notification_categories, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT(user_id) DO UPDATE SET
+                theme = excluded.theme,
+                default_library_id = excluded.default_library_id,
+                grid_density = excluded.grid_density,
+                language = excluded.language,
+                notifications_enabled = excluded.notifications_enabled,
+                muted_notification_categories = excluded.muted_notification_categories,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(uuid_to_str(user_id))
+        .bind(&prefs.theme)
+        .bind(prefs.default_library_id.map(uuid_to_str))
+        .bind(&prefs.grid_density)
+        .bind(&prefs.language)
+        .bind(bool_to_int(prefs.notifications_enabled))
+        .bind(vec_to_json(&prefs.muted_notification_categories))
+        .bind(prefs.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(prefs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_only_changes_given_fields() {
+        let library_id = Uuid::new_v4();
+        let mut prefs = UserPreferencesRecord::defaults(Uuid::new_v4());
+        prefs.default_library_id = Some(library_id);
+
+        prefs.apply(UpdateUserPreferences {
+            theme: Some("dark".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(prefs.theme, "dark");
+        assert_eq!(prefs.default_library_id, Some(library_id));
+        assert_eq!(prefs.grid_density, "comfortable");
+
+        prefs.apply(UpdateUserPreferences {
+            default_library_id: Some(None),
+            ..Default::default()
+        });
+        assert_eq!(prefs.default_library_id, None);
+        assert_eq!(prefs.theme, "dark");
+    }
+
+    #[test]
+    fn test_validate() {
+        let ok = UpdateUserPreferences {
+            theme: Some("light".to_string()),
+            grid_density: Some("compact".to_string()),
+            language: Some("pt-BR".to_string()),
+            ..Default::default()
+        };
+        assert!(ok.validate().is_ok());
+
+        for bad in [
+            UpdateUserPreferences {
+                theme: Some("blue".to_string()),
+                ..Default::default()
+            },
+            UpdateUserPreferences {
+                grid_density: Some("dense".to_string()),
+                ..Default::default()
+            },
+            UpdateUserPreferences {
+                language: Some("en_US".to_string()),
+                ..Default::default()
+            },
+        ] {
+            assert!(bad.validate().is_err());
+        }
+    }
+}
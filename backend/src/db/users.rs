@@ -119,6 +119,33 @@ pub struct RefreshTokenRecord {
     pub last_used_at: Option<String>,
 }
 
+// ============================================================================
+// Email Change Token Records
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailChangeTokenRecord {
+    pub id: String,
+    pub user_id: String,
+    pub new_email: String,
+    pub token_hash: String,
+    pub expires_at: String,
+    pub created_at: String,
+}
+
+// ============================================================================
+// Password Reset Token Records
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetTokenRecord {
+    pub id: String,
+    pub user_id: String,
+    pub token_hash: String,
+    pub expires_at: String,
+    pub created_at: String,
+}
+
 // ============================================================================
 // Repository
 // ============================================================================
@@ -616,6 +643,219 @@ impl UsersRepository {
         Ok(result.rows_affected())
     }
 
+    // ========================================================================
+    // Email Change Tokens
+    // ========================================================================
+
+    /// Create an email change token, replacing any the user already has
+    pub async fn create_email_change_token(
+        &self,
+        user_id: &str,
+        new_email: &str,
+        token_hash: &str,
+        expires_at: &str,
+    ) -> Result<EmailChangeTokenRecord> {
+        let id = Uuid::new_v4().to_string();
+        let now = now_iso8601();
+
+        sqlx::query("DELETE FROM email_change_tokens WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_change_tokens (id, user_id, new_email, token_hash, expires_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(new_email)
+        .bind(token_hash)
+        .bind(expires_at)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(EmailChangeTokenRecord {
+            id,
+            user_id: user_id.to_string(),
+            new_email: new_email.to_string(),
+            token_hash: token_hash.to_string(),
+            expires_at: expires_at.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// Remove an email change token by hash and return it (tokens are single-use)
+    ///
+    /// Returns None if there is no such token, or another request took it first.
+    pub async fn take_email_change_token(&self, token_hash: &str) -> Result<Option<EmailChangeTokenRecord>> {
+        let row = sqlx::query_as::<_, (String, String, String, String, String, String)>(
+            "SELECT id, user_id, new_email, token_hash, expires_at, created_at FROM email_change_tokens WHERE token_hash = ?"
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(r) = row else {
+            return Ok(None);
+        };
+        if !self.delete_email_change_token(&r.0).await? {
+            return Ok(None);
+        }
+
+        Ok(Some(EmailChangeTokenRecord {
+            id: r.0,
+            user_id: r.1,
+            new_email: r.2,
+            token_hash: r.3,
+            expires_at: r.4,
+            created_at: r.5,
+        }))
+    }
+
+    /// Delete an email change token
+    pub async fn delete_email_change_token(&self, id: &str) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM email_change_tokens WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Set a user's email; a username that was the old email follows it
+    pub async fn change_email(&self, id: &str, new_email: &str) -> Result<()> {
+        let now = now_iso8601();
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET username = CASE WHEN username = email THEN ? ELSE username END,
+                email = ?,
+                updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(new_email)
+        .bind(new_email)
+        .bind(&now)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Clean up expired email change tokens
+    pub async fn cleanup_expired_email_change_tokens(&self) -> Result<u64> {
+        let now = now_iso8601();
+        let result = sqlx::query("DELETE FROM email_change_tokens WHERE expires_at < ?")
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    // ========================================================================
+    // Password Reset Tokens
+    // ========================================================================
+
+    /// Create a password reset token, replacing any the user already has
+    pub async fn create_password_reset_token(
+        &self,
+        user_id: &str,
+        token_hash: &str,
+        expires_at: &str,
+    ) -> Result<PasswordResetTokenRecord> {
+        let id = Uuid::new_v4().to_string();
+        let now = now_iso8601();
+
+        sqlx::query("DELETE FROM password_reset_tokens WHERE user_id = ?")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO password_reset_tokens (id, user_id, token_hash, expires_at, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .bind(&now)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(PasswordResetTokenRecord {
+            id,
+            user_id: user_id.to_string(),
+            token_hash: token_hash.to_string(),
+            expires_at: expires_at.to_string(),
+            created_at: now,
+        })
+    }
+
+    /// When the user's pending password reset was requested, if they have one
+    pub async fn last_password_reset_requested_at(&self, user_id: &str) -> Result<Option<String>> {
+        let created_at: Option<String> = sqlx::query_scalar(
+            "SELECT MAX(created_at) FROM password_reset_tokens WHERE user_id = ?",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(created_at)
+    }
+
+    /// Get a password reset token by hash
+    pub async fn get_password_reset_token(&self, token_hash: &str) -> Result<Option<PasswordResetTokenRecord>> {
+        let row = sqlx::query_as::<_, (String, String, String, String, String)>(
+            "SELECT id, user_id, token_hash, expires_at, created_at FROM password_reset_tokens WHERE token_hash = ?"
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|r| PasswordResetTokenRecord {
+            id: r.0,
+            user_id: r.1,
+            token_hash: r.2,
+            expires_at: r.3,
+            created_at: r.4,
+        }))
+    }
+
+    /// Remove a password reset token by hash and return it (tokens are single-use)
+    ///
+    /// Returns None if there is no such token, or another request took it first.
+    pub async fn take_password_reset_token(&self, token_hash: &str) -> Result<Option<PasswordResetTokenRecord>> {
+        let Some(record) = self.get_password_reset_token(token_hash).await? else {
+            return Ok(None);
+        };
+
+        let result = sqlx::query("DELETE FROM password_reset_tokens WHERE id = ?")
+            .bind(&record.id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(record))
+    }
+
+    /// Clean up expired password reset tokens
+    pub async fn cleanup_expired_password_reset_tokens(&self) -> Result<u64> {
+        let now = now_iso8601();
+        let result = sqlx::query("DELETE FROM password_reset_tokens WHERE expires_at < ?")
+            .bind(&now)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
     // ========================================================================
     // User Restrictions
     // ========================================================================
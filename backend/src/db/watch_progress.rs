@@ -730,12 +730,35 @@ impl WatchProgressRepository {
             id_column
         );
 
-        sqlx::query(&query)
+        let result = sqlx::query(&query)
             .bind(uuid_to_str(user_id))
             .bind(uuid_to_str(content_id))
             .execute(&self.pool)
             .await?;
 
+        // Content marked watched without being played has no row yet
+        if result.rows_affected() == 0 {
+            let insert = format!(
+                r#"
+                INSERT INTO watch_progress (
+                    id, user_id, content_type, {}, current_position, progress_percent,
+                    is_watched, watched_at, last_watched_at, created_at, updated_at
+                )
+                VALUES (?1, ?2, ?3, ?4, 0, 1.0, 1, datetime('now'), datetime('now'),
+                        datetime('now'), datetime('now'))
+                "#,
+                id_column
+            );
+
+            sqlx::query(&insert)
+                .bind(uuid_to_str(Uuid::new_v4()))
+                .bind(uuid_to_str(user_id))
+                .bind(content_type.as_str())
+                .bind(uuid_to_str(content_id))
+                .execute(&self.pool)
+                .await?;
+        }
+
         self.get_progress(user_id, content_type, content_id).await
     }
 
@@ -852,9 +875,18 @@ impl WatchProgressRepository {
     pub async fn get_continue_watching(
         &self,
         user_id: Uuid,
+        content_types: &[ContentType],
         limit: i64,
     ) -> Result<Vec<WatchProgressRecord>> {
-        let records = sqlx::query_as::<_, WatchProgressRecord>(
+        if content_types.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let placeholders: Vec<String> = (0..content_types.len())
+            .map(|i| format!("?{}", i + 3))
+            .collect();
+
+        let query = format!(
             r#"
             SELECT id, user_id, episode_id, movie_id, track_id, audiobook_id,
                    content_type, media_file_id, current_position, duration, 
@@ -865,16 +897,45 @@ impl WatchProgressRepository {
               AND is_watched = 0 
               AND progress_percent > 0.01
               AND progress_percent < 0.95
+              AND content_type IN ({})
             ORDER BY last_watched_at DESC
             LIMIT ?2
             "#,
+            placeholders.join(", ")
+        );
+
+        let mut query_builder = sqlx::query_as::<_, WatchProgressRecord>(&query)
+            .bind(uuid_to_str(user_id))
+            .bind(limit);
+        for content_type in content_types {
+            query_builder = query_builder.bind(content_type.as_str());
+        }
+
+        let records = query_builder.fetch_all(&self.pool).await?;
+
+        Ok(records)
+    }
+
+    /// Shows the user has watched episodes of, most recently watched first
+    #[cfg(feature = "sqlite")]
+    pub async fn get_recent_shows(&self, user_id: Uuid, limit: i64) -> Result<Vec<Uuid>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            r#"
+            SELECT e.tv_show_id
+            FROM watch_progress wp
+            JOIN episodes e ON e.id = wp.episode_id
+            WHERE wp.user_id = ?1 AND wp.content_type = 'episode'
+            GROUP BY e.tv_show_id
+            ORDER BY MAX(wp.last_watched_at) DESC
+            LIMIT ?2
+            "#,
         )
         .bind(uuid_to_str(user_id))
         .bind(limit)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(records)
+        rows.into_iter().map(|(id,)| str_to_uuid(&id)).collect()
     }
 
     /// Get watched episodes for a TV show
@@ -11,16 +11,27 @@
 //! async fn protected_query(&self, ctx: &Context<'_>) -> Result<String> { ... }
 //! ```
 //!
-//! Use `RoleGuard` to require a specific role:
+//! Use `PermissionGuard` to require a permission from the user's role:
 //!
 //! ```ignore
-//! #[graphql(guard = "RoleGuard::new(\"admin\")")]
+//! #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
 //! async fn admin_only(&self, ctx: &Context<'_>) -> Result<String> { ... }
 //! ```
+//!
+//! ## Permissions
+//!
+//! Permissions come from the user's role in the database rather than the
+//! role in their token, so a role change applies to the next request.
+//! Resolvers that only widen what they return (e.g. everyone's jobs instead
+//! of your own) call [`has_permission`] instead of using the guard.
 
 use async_graphql::{Context, ErrorExtensions, Result};
 use jsonwebtoken::{Algorithm, DecodingKey, Validation, decode};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::Database;
+pub use crate::db::roles::Permission;
 
 /// User context extracted from JWT, available in GraphQL resolvers
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -135,28 +146,57 @@ impl async_graphql::Guard for AuthGuard {
     }
 }
 
-/// Guard that requires a specific role for GraphQL operations.
+/// Whether the authenticated user's role grants a permission
+///
+/// Errors if nobody is signed in; a disabled or deleted user has no
+/// permissions.
+pub async fn has_permission(ctx: &Context<'_>, permission: Permission) -> Result<bool> {
+    let user = ctx.auth_user()?;
+    let user_id = Uuid::parse_str(&user.user_id)
+        .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+    let role = ctx
+        .data_unchecked::<Database>()
+        .roles()
+        .get_for_user(user_id)
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+    Ok(role.is_some_and(|role| role.has(permission)))
+}
+
+/// Require a permission, returning the user or a FORBIDDEN error
+pub async fn require_permission<'a>(
+    ctx: &'a Context<'_>,
+    permission: Permission,
+) -> Result<&'a AuthUser> {
+    if !has_permission(ctx, permission).await? {
+        return Err(async_graphql::Error::new(format!(
+            "Permission '{}' required",
+            permission.as_str()
+        ))
+        .extend_with(|_, e| e.set("code", "FORBIDDEN")));
+    }
+    ctx.auth_user()
+}
+
+/// Guard that requires a permission for GraphQL operations.
 ///
-/// Use with `#[graphql(guard = "RoleGuard::new(\"admin\")")]` on queries, mutations, or subscriptions.
-pub struct RoleGuard {
-    pub role: String,
+/// Use with `#[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]`
+/// on queries, mutations, or subscriptions.
+pub struct PermissionGuard {
+    pub permission: Permission,
 }
 
-impl RoleGuard {
-    pub fn new(role: impl Into<String>) -> Self {
-        Self { role: role.into() }
+impl PermissionGuard {
+    pub fn new(permission: Permission) -> Self {
+        Self { permission }
     }
 }
 
-impl async_graphql::Guard for RoleGuard {
+impl async_graphql::Guard for PermissionGuard {
     fn check(&self, ctx: &Context<'_>) -> impl std::future::Future<Output = Result<()>> + Send {
-        let result = ctx.auth_user().and_then(|user| match &user.role {
-            Some(r) if r == &self.role => Ok(()),
-            _ => Err(
-                async_graphql::Error::new(format!("Role '{}' required", self.role))
-                    .extend_with(|_, e| e.set("code", "FORBIDDEN")),
-            ),
-        });
-        async move { result }
+        let permission = self.permission;
+        async move { require_permission(ctx, permission).await.map(|_| ()) }
     }
 }
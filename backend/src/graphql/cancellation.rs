@@ -0,0 +1,211 @@
+//! Per-operation time limits and cancellation
+//!
+//! Every HTTP GraphQL request gets a `CancellationToken`. It is cancelled
+//! when the operation runs past the time limit for its class, or when the
+//! client goes away and the handler future is dropped. Resolvers that wait
+//! on slow remote servers or spawn tasks take it from the context with
+//! `ctx.cancellation()` and wind down: searches return what they found so
+//! far, spawned tasks stop.
+//!
+//! After cancelling, the operation gets a short grace period to return a
+//! partial result before it is dropped and a `TIMEOUT` error is returned.
+
+use std::time::Duration;
+
+use async_graphql::parser::parse_query;
+use async_graphql::parser::types::{DocumentOperations, Selection};
+use async_graphql::{Context, Request, Response, ServerError};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::GraphqlTimeouts;
+use crate::graphql::LibrarianSchema;
+
+/// How long a cancelled operation gets to return a partial result
+const GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// Root fields that search indexers interactively
+const SEARCH_FIELDS: &[&str] = &["searchIndexers"];
+
+/// Root fields that run ffprobe
+const ANALYSIS_FIELDS: &[&str] = &["analyzeMediaFile"];
+
+/// Kind of operation, for picking its time limit
+///
+/// Ordered by how long operations of the class may take, so an operation
+/// with several root fields gets the most generous limit among them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OperationClass {
+    #[default]
+    Default,
+    Search,
+    Analysis,
+}
+
+impl OperationClass {
+    fn for_field(name: &str) -> Self {
+        if ANALYSIS_FIELDS.contains(&name) {
+            OperationClass::Analysis
+        } else if SEARCH_FIELDS.contains(&name) {
+            OperationClass::Search
+        } else {
+            OperationClass::Default
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationClass::Default => "default",
+            OperationClass::Search => "search",
+            OperationClass::Analysis => "analysis",
+        }
+    }
+
+    /// Time limit for the class, or None for no limit
+    pub fn limit(&self, timeouts: &GraphqlTimeouts) -> Option<Duration> {
+        match self {
+            OperationClass::Default => timeouts.default,
+            OperationClass::Search => timeouts.search,
+            OperationClass::Analysis => timeouts.analysis,
+        }
+    }
+}
+
+/// Class of the operation a request runs, from its root fields
+///
+/// Requests that don't parse are classed as default; execution reports the
+/// syntax error.
+pub fn classify(query: &str, operation_name: Option<&str>) -> OperationClass {
+    let Ok(document) = parse_query(query) else {
+        return OperationClass::Default;
+    };
+    let operation = match &document.operations {
+        DocumentOperations::Single(operation) => Some(operation),
+        DocumentOperations::Multiple(operations) => match operation_name {
+            Some(name) => operations
+                .iter()
+                .find(|(n, _)| n.as_str() == name)
+                .map(|(_, operation)| operation),
+            None => operations.values().next(),
+        },
+    };
+    let Some(operation) = operation else {
+        return OperationClass::Default;
+    };
+
+    operation
+        .node
+        .selection_set
+        .node
+        .items
+        .iter()
+        .filter_map(|selection| match &selection.node {
+            Selection::Field(field) => {
+                Some(OperationClass::for_field(field.node.name.node.as_str()))
+            }
+            _ => None,
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+/// The request's cancellation token, stored in the request data
+#[derive(Clone)]
+pub struct RequestCancellation(pub CancellationToken);
+
+/// Extension trait to get the request's cancellation token
+pub trait CancellationExt {
+    /// Token cancelled when the operation times out or the client goes away
+    ///
+    /// Operations without one (WebSocket subscriptions) get a token that is
+    /// never cancelled.
+    fn cancellation(&self) -> CancellationToken;
+}
+
+impl<'a> CancellationExt for Context<'a> {
+    fn cancellation(&self) -> CancellationToken {
+        self.data_opt::<RequestCancellation>()
+            .map(|c| c.0.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Execute a request under its class's time limit
+///
+/// The token is also cancelled if this future is dropped, e.g. because the
+/// client disconnected.
+pub async fn execute_with_deadline(
+    schema: &LibrarianSchema,
+    request: Request,
+    timeouts: &GraphqlTimeouts,
+) -> Response {
+    let class = classify(&request.query, request.operation_name.as_deref());
+    let cancel = CancellationToken::new();
+    let _guard = cancel.clone().drop_guard();
+
+    let execute = schema.execute(request.data(RequestCancellation(cancel.clone())));
+    let Some(limit) = class.limit(timeouts) else {
+        return execute.await;
+    };
+    tokio::pin!(execute);
+
+    if let Ok(response) = tokio::time::timeout(limit, &mut execute).await {
+        return response;
+    }
+
+    // Give resolvers watching the token a moment to return what they have
+    cancel.cancel();
+    if let Ok(response) = tokio::time::timeout(GRACE_PERIOD, &mut execute).await {
+        return response;
+    }
+
+    tracing::warn!(
+        class = class.as_str(),
+        limit_secs = limit.as_secs(),
+        "GraphQL operation timed out"
+    );
+    let mut error = ServerError::new(
+        format!("Operation timed out after {} seconds", limit.as_secs()),
+        None,
+    );
+    let mut extensions = async_graphql::ErrorExtensionValues::default();
+    extensions.set("code", "TIMEOUT");
+    error.extensions = Some(extensions);
+    Response::from_errors(vec![error])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            classify("{ libraries { id } }", None),
+            OperationClass::Default
+        );
+        assert_eq!(
+            classify(
+                "query Search($input: IndexerSearchInput!) { searchIndexers(input: $input) { totalReleases } }",
+                Some("Search")
+            ),
+            OperationClass::Search
+        );
+        assert_eq!(
+            classify(
+                "mutation { me: analyzeMediaFile(mediaFileId: \"1\") { success } }",
+                None
+            ),
+            OperationClass::Analysis
+        );
+        assert_eq!(classify("{ not valid", None), OperationClass::Default);
+    }
+
+    #[test]
+    fn test_classify_picks_slowest_field_of_named_operation() {
+        let query = "query A { libraries { id } } \
+                     query B { libraries { id } searchIndexers(input: {query: \"x\"}) { totalReleases } }";
+        assert_eq!(classify(query, Some("A")), OperationClass::Default);
+        assert_eq!(classify(query, Some("B")), OperationClass::Search);
+        assert_eq!(classify(query, Some("C")), OperationClass::Default);
+    }
+}
@@ -5,18 +5,25 @@
 //! - lt, lte, gt, gte (comparisons)
 //! - contains, startsWith, endsWith (string matching)
 //! - in, notIn (list membership)
-//! - between (range queries)
+//! - hasAny, hasAll, hasNone (for list fields like genres)
+//! - between (range queries, with an epsilon for floats)
+//! - relative, olderThan, newerThan (dates relative to now, in the user's
+//!   timezone)
 
+use anyhow::{Result, anyhow, bail};
 use async_graphql::InputObject;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, TimeZone, Utc};
 
 /// Filter for string fields
+///
+/// Comparisons ignore case unless `mode` is `CASE_SENSITIVE`.
 #[derive(InputObject, Default, Clone, Debug)]
 pub struct StringFilter {
     /// Equals
     pub eq: Option<String>,
     /// Not equals
     pub ne: Option<String>,
-    /// Contains substring (case-insensitive)
+    /// Contains substring
     pub contains: Option<String>,
     /// Starts with
     pub starts_with: Option<String>,
@@ -27,6 +34,18 @@ pub struct StringFilter {
     pub in_list: Option<Vec<String>>,
     /// Not in list
     pub not_in: Option<Vec<String>>,
+    /// How values are compared (default case-insensitive)
+    pub mode: Option<StringFilterMode>,
+}
+
+/// How a string filter compares values
+#[derive(async_graphql::Enum, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum StringFilterMode {
+    /// Ignore case (ASCII letters only)
+    #[default]
+    CaseInsensitive,
+    /// Compare exactly
+    CaseSensitive,
 }
 
 /// Filter for integer fields
@@ -51,6 +70,48 @@ pub struct IntFilter {
     pub not_in: Option<Vec<i32>>,
 }
 
+/// Filter for list fields such as genres or cast (case-insensitive)
+#[derive(InputObject, Default, Clone, Debug)]
+pub struct ListFilter {
+    /// Contains at least one of these values
+    pub has_any: Option<Vec<String>>,
+    /// Contains all of these values
+    pub has_all: Option<Vec<String>>,
+    /// Contains none of these values
+    pub has_none: Option<Vec<String>>,
+}
+
+/// Range for float between queries
+#[derive(InputObject, Default, Clone, Debug)]
+pub struct FloatRange {
+    /// Lower bound (inclusive)
+    pub start: Option<f64>,
+    /// Upper bound (inclusive)
+    pub end: Option<f64>,
+}
+
+/// Filter for floating point fields (ratings, scores)
+///
+/// Stored floats rarely compare exactly (7.3 may be stored as
+/// 7.2999999), so values within `epsilon` of a bound count as equal to it.
+#[derive(InputObject, Default, Clone, Debug)]
+pub struct FloatFilter {
+    /// Equals (within epsilon)
+    pub eq: Option<f64>,
+    /// Less than
+    pub lt: Option<f64>,
+    /// Less than or equal
+    pub lte: Option<f64>,
+    /// Greater than
+    pub gt: Option<f64>,
+    /// Greater than or equal
+    pub gte: Option<f64>,
+    /// Between two values (inclusive)
+    pub between: Option<FloatRange>,
+    /// Tolerance for comparisons (default 0.000001)
+    pub epsilon: Option<f64>,
+}
+
 /// Filter for boolean fields
 #[derive(InputObject, Default, Clone, Debug)]
 pub struct BoolFilter {
@@ -86,6 +147,35 @@ pub struct DateFilter {
     pub gte: Option<String>,
     /// Between two dates (inclusive)
     pub between: Option<DateRange>,
+    /// Calendar period relative to today, e.g. TODAY or THIS_WEEK
+    pub relative: Option<RelativeDate>,
+    /// Older than an ISO 8601 duration ago, e.g. "P30D" or "PT12H"
+    pub older_than: Option<String>,
+    /// Newer than an ISO 8601 duration ago
+    pub newer_than: Option<String>,
+    /// The user's offset from UTC in minutes (e.g. -300 for UTC-5), used to
+    /// decide where "today" starts. Defaults to UTC.
+    pub utc_offset_minutes: Option<i32>,
+}
+
+/// Calendar periods for relative date filters
+#[derive(async_graphql::Enum, Copy, Clone, Debug, Eq, PartialEq)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum RelativeDate {
+    Today,
+    Yesterday,
+    Tomorrow,
+    /// Monday through Sunday of the current week
+    ThisWeek,
+    LastWeek,
+    ThisMonth,
+}
+
+/// A span of time resolved from a date filter: `start <= t < end`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TimeWindow {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
 }
 
 /// Order direction for sorting
@@ -123,6 +213,20 @@ impl StringFilter {
             && self.in_list.as_ref().map_or(true, |v| v.is_empty())
             && self.not_in.as_ref().map_or(true, |v| v.is_empty())
     }
+
+    /// Conditions for a text column
+    pub fn to_text_filter(&self) -> crate::db::sqlite_helpers::TextFilter {
+        crate::db::sqlite_helpers::TextFilter {
+            eq: self.eq.clone(),
+            ne: self.ne.clone(),
+            contains: self.contains.clone(),
+            starts_with: self.starts_with.clone(),
+            ends_with: self.ends_with.clone(),
+            in_list: self.in_list.clone().unwrap_or_default(),
+            not_in: self.not_in.clone().unwrap_or_default(),
+            case_sensitive: self.mode == Some(StringFilterMode::CaseSensitive),
+        }
+    }
 }
 
 impl IntFilter {
@@ -139,6 +243,76 @@ impl IntFilter {
     }
 }
 
+impl ListFilter {
+    /// Check if filter has any conditions
+    pub fn is_empty(&self) -> bool {
+        self.has_any.as_ref().is_none_or(|v| v.is_empty())
+            && self.has_all.as_ref().is_none_or(|v| v.is_empty())
+            && self.has_none.as_ref().is_none_or(|v| v.is_empty())
+    }
+
+    /// Conditions for a JSON array column
+    pub fn to_json_array_filter(&self) -> crate::db::sqlite_helpers::JsonArrayFilter {
+        crate::db::sqlite_helpers::JsonArrayFilter {
+            has_any: self.has_any.clone().unwrap_or_default(),
+            has_all: self.has_all.clone().unwrap_or_default(),
+            has_none: self.has_none.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl FloatFilter {
+    /// Tolerance used when `epsilon` isn't given
+    pub const DEFAULT_EPSILON: f64 = 1e-6;
+
+    /// Check if filter has any conditions
+    pub fn is_empty(&self) -> bool {
+        self.eq.is_none()
+            && self.lt.is_none()
+            && self.lte.is_none()
+            && self.gt.is_none()
+            && self.gte.is_none()
+            && self.between.is_none()
+    }
+
+    /// Combine the conditions into one inclusive `(min, max)` range for
+    /// `column >= min AND column <= max`
+    ///
+    /// Strict bounds are pulled in by epsilon, so `lt: 7.3` excludes a
+    /// stored 7.2999999 the same as an exact 7.3.
+    pub fn bounds(&self) -> (Option<f64>, Option<f64>) {
+        let epsilon = self.epsilon.unwrap_or(Self::DEFAULT_EPSILON).abs();
+        let mut mins = Vec::new();
+        let mut maxes = Vec::new();
+
+        if let Some(eq) = self.eq {
+            mins.push(eq - epsilon);
+            maxes.push(eq + epsilon);
+        }
+        if let Some(gt) = self.gt {
+            mins.push(gt + epsilon);
+        }
+        if let Some(gte) = self.gte {
+            mins.push(gte - epsilon);
+        }
+        if let Some(lt) = self.lt {
+            maxes.push(lt - epsilon);
+        }
+        if let Some(lte) = self.lte {
+            maxes.push(lte + epsilon);
+        }
+        if let Some(range) = &self.between {
+            mins.extend(range.start.map(|start| start - epsilon));
+            maxes.extend(range.end.map(|end| end + epsilon));
+        }
+
+        (
+            mins.into_iter().reduce(f64::max),
+            maxes.into_iter().reduce(f64::min),
+        )
+    }
+}
+
 impl BoolFilter {
     /// Check if filter has any conditions
     pub fn is_empty(&self) -> bool {
@@ -156,5 +330,312 @@ impl DateFilter {
             && self.gt.is_none()
             && self.gte.is_none()
             && self.between.is_none()
+            && self.relative.is_none()
+            && self.older_than.is_none()
+            && self.newer_than.is_none()
+    }
+
+    /// Resolve `relative`, `olderThan` and `newerThan` to UTC bounds
+    ///
+    /// Calendar periods start at midnight in the user's timezone, so TODAY
+    /// for a user at UTC-5 runs from 05:00 to 05:00 UTC. Compare the bounds
+    /// with timestamp columns directly, or use [`TimeWindow::local_dates`]
+    /// for date-only columns like `air_date`.
+    pub fn relative_window(&self, now: DateTime<Utc>) -> Result<TimeWindow> {
+        let offset = utc_offset(self.utc_offset_minutes)?;
+        let mut window = match self.relative {
+            Some(period) => period_window(period, now, offset),
+            None => TimeWindow::default(),
+        };
+
+        if let Some(older_than) = &self.older_than {
+            let end = before(now, older_than)?;
+            window.end = Some(window.end.map_or(end, |e| e.min(end)));
+        }
+        if let Some(newer_than) = &self.newer_than {
+            let start = before(now, newer_than)?;
+            window.start = Some(window.start.map_or(start, |s| s.max(start)));
+        }
+
+        Ok(window)
+    }
+}
+
+impl TimeWindow {
+    /// Whether a timestamp falls inside the window
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start.is_none_or(|start| at >= start) && self.end.is_none_or(|end| at < end)
+    }
+
+    /// First and last calendar dates (inclusive) the window covers in the
+    /// given timezone
+    pub fn local_dates(&self, offset: FixedOffset) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        let start = self.start.map(|s| s.with_timezone(&offset).date_naive());
+        let end = self.end.map(|e| {
+            (e - Duration::nanoseconds(1))
+                .with_timezone(&offset)
+                .date_naive()
+        });
+        (start, end)
+    }
+}
+
+/// Timezone for a UTC offset in minutes (None = UTC)
+pub fn utc_offset(minutes: Option<i32>) -> Result<FixedOffset> {
+    let minutes = minutes.unwrap_or(0);
+    if minutes.abs() > 14 * 60 {
+        bail!(
+            "UTC offset must be within ±14 hours, got {} minutes",
+            minutes
+        );
+    }
+    FixedOffset::east_opt(minutes * 60).ok_or_else(|| anyhow!("Invalid UTC offset"))
+}
+
+/// Today's date in the given timezone
+pub fn local_today(now: DateTime<Utc>, offset: FixedOffset) -> NaiveDate {
+    now.with_timezone(&offset).date_naive()
+}
+
+fn period_window(period: RelativeDate, now: DateTime<Utc>, offset: FixedOffset) -> TimeWindow {
+    let today = local_today(now, offset);
+    let (first, last) = match period {
+        RelativeDate::Today => (today, today),
+        RelativeDate::Yesterday => (today - Duration::days(1), today - Duration::days(1)),
+        RelativeDate::Tomorrow => (today + Duration::days(1), today + Duration::days(1)),
+        RelativeDate::ThisWeek => {
+            let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+            (monday, monday + Duration::days(6))
+        }
+        RelativeDate::LastWeek => {
+            let monday = today
+                - Duration::days(today.weekday().num_days_from_monday() as i64)
+                - Duration::days(7);
+            (monday, monday + Duration::days(6))
+        }
+        RelativeDate::ThisMonth => {
+            let first = today.with_day(1).unwrap_or(today);
+            let next = first
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap_or(first);
+            (first, next - Duration::days(1))
+        }
+    };
+
+    TimeWindow {
+        start: Some(local_midnight(first, offset)),
+        end: Some(local_midnight(last + Duration::days(1), offset)),
+    }
+}
+
+fn local_midnight(date: NaiveDate, offset: FixedOffset) -> DateTime<Utc> {
+    offset
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .single()
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+}
+
+/// The time an ISO 8601 duration before `now`
+fn before(now: DateTime<Utc>, duration: &str) -> Result<DateTime<Utc>> {
+    now.checked_sub_signed(parse_iso_duration(duration)?)
+        .ok_or_else(|| anyhow!("Duration '{}' is too long", duration))
+}
+
+/// Parse an ISO 8601 duration of weeks, days, hours, minutes and seconds
+/// ("P2W", "P30D", "P1DT12H", "PT90M")
+///
+/// Years and months are rejected since their length varies.
+pub fn parse_iso_duration(input: &str) -> Result<Duration> {
+    let invalid = || anyhow!("Invalid duration '{}', expected e.g. P30D or PT12H", input);
+    let too_long = || anyhow!("Duration '{}' is too long", input);
+    let rest = input.trim().strip_prefix(['P', 'p']).ok_or_else(invalid)?;
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total = Duration::zero();
+    let mut in_time = false;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c.to_ascii_uppercase() {
+            'T' if !in_time && number.is_empty() => in_time = true,
+            d if d.is_ascii_digit() => number.push(d),
+            unit => {
+                if number.is_empty() {
+                    return Err(invalid());
+                }
+                // All digits, so a parse failure is an overflow
+                let value: i64 = number.parse().map_err(|_| too_long())?;
+                number.clear();
+                let part = match (unit, in_time) {
+                    ('W', false) => Duration::try_weeks(value),
+                    ('D', false) => Duration::try_days(value),
+                    ('H', true) => Duration::try_hours(value),
+                    ('M', true) => Duration::try_minutes(value),
+                    ('S', true) => Duration::try_seconds(value),
+                    ('Y' | 'M', false) => {
+                        bail!("Years and months aren't supported in '{}'; use days", input)
+                    }
+                    _ => return Err(invalid()),
+                };
+                total = part
+                    .and_then(|part| total.checked_add(&part))
+                    .ok_or_else(too_long)?;
+            }
+        }
+    }
+    if !number.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_float_filter_bounds() {
+        let filter = FloatFilter {
+            eq: Some(7.3),
+            ..Default::default()
+        };
+        let (min, max) = filter.bounds();
+        let stored = 7.299_999_9;
+        assert!(min.unwrap() <= stored && stored <= max.unwrap());
+
+        let filter = FloatFilter {
+            gte: Some(6.0),
+            lt: Some(8.0),
+            between: Some(FloatRange {
+                start: Some(5.0),
+                end: Some(7.5),
+            }),
+            ..Default::default()
+        };
+        let (min, max) = filter.bounds();
+        assert!((min.unwrap() - 6.0).abs() < 1e-5);
+        assert!((max.unwrap() - 7.5).abs() < 1e-5);
+
+        let filter = FloatFilter {
+            lt: Some(7.3),
+            epsilon: Some(0.01),
+            ..Default::default()
+        };
+        assert!(filter.bounds().1.unwrap() < 7.295);
+        assert_eq!(FloatFilter::default().bounds(), (None, None));
+    }
+
+    #[test]
+    fn test_parse_iso_duration() {
+        assert_eq!(parse_iso_duration("P30D").unwrap(), Duration::days(30));
+        assert_eq!(parse_iso_duration("P2W").unwrap(), Duration::weeks(2));
+        assert_eq!(
+            parse_iso_duration("P1DT12H30M").unwrap(),
+            Duration::hours(36) + Duration::minutes(30)
+        );
+        assert_eq!(parse_iso_duration("pt90s").unwrap(), Duration::seconds(90));
+        assert!(parse_iso_duration("P1M").is_err());
+        assert!(parse_iso_duration("30D").is_err());
+        assert!(parse_iso_duration("P").is_err());
+        assert!(parse_iso_duration("PD").is_err());
+        assert!(parse_iso_duration("PT5").is_err());
+        // Overflow is an error, not a panic
+        assert!(parse_iso_duration("P99999999999W").is_err());
+        assert!(parse_iso_duration("P99999999999999999999D").is_err());
+        assert!(parse_iso_duration("P15000000WT9223372036854775S").is_err());
+    }
+
+    #[test]
+    fn test_today_follows_user_timezone() {
+        // 02:00 UTC on the 15th is still the evening of the 14th at UTC-5
+        let now = at("2025-03-15T02:00:00Z");
+        let filter = DateFilter {
+            relative: Some(RelativeDate::Today),
+            utc_offset_minutes: Some(-300),
+            ..Default::default()
+        };
+        let window = filter.relative_window(now).unwrap();
+        assert_eq!(window.start, Some(at("2025-03-14T05:00:00Z")));
+        assert_eq!(window.end, Some(at("2025-03-15T05:00:00Z")));
+        let offset = utc_offset(Some(-300)).unwrap();
+        let day = NaiveDate::from_ymd_opt(2025, 3, 14);
+        assert_eq!(window.local_dates(offset), (day, day));
+
+        let utc = DateFilter {
+            relative: Some(RelativeDate::Today),
+            ..Default::default()
+        };
+        let day = NaiveDate::from_ymd_opt(2025, 3, 15);
+        assert_eq!(
+            utc.relative_window(now)
+                .unwrap()
+                .local_dates(utc_offset(None).unwrap()),
+            (day, day)
+        );
+    }
+
+    #[test]
+    fn test_periods_and_durations() {
+        // A Wednesday
+        let now = at("2025-03-12T12:00:00Z");
+        let offset = utc_offset(None).unwrap();
+        let date = |d| NaiveDate::from_ymd_opt(2025, 3, d);
+
+        let week = period_window(RelativeDate::ThisWeek, now, offset);
+        assert_eq!(week.local_dates(offset), (date(10), date(16)));
+        let last_week = period_window(RelativeDate::LastWeek, now, offset);
+        assert_eq!(last_week.local_dates(offset), (date(3), date(9)));
+        let month = period_window(RelativeDate::ThisMonth, now, offset);
+        assert_eq!(month.local_dates(offset), (date(1), date(31)));
+
+        let filter = DateFilter {
+            older_than: Some("P30D".to_string()),
+            ..Default::default()
+        };
+        let window = filter.relative_window(now).unwrap();
+        assert_eq!(window.start, None);
+        assert!(window.contains(at("2025-02-01T00:00:00Z")));
+        assert!(!window.contains(at("2025-03-01T00:00:00Z")));
+
+        // A valid duration reaching past the earliest representable date
+        let filter = DateFilter {
+            newer_than: Some("P1000000000D".to_string()),
+            ..Default::default()
+        };
+        assert!(filter.relative_window(now).is_err());
+
+        assert!(utc_offset(Some(15 * 60)).is_err());
+    }
+
+    #[test]
+    fn test_string_filter_mode() {
+        let filter = StringFilter {
+            contains: Some("alien".to_string()),
+            ..Default::default()
+        };
+        assert!(!filter.to_text_filter().case_sensitive);
+
+        let filter = StringFilter {
+            mode: Some(StringFilterMode::CaseSensitive),
+            ..filter
+        };
+        let text = filter.to_text_filter();
+        assert!(text.case_sensitive);
+        assert_eq!(text.contains.as_deref(), Some("alien"));
+        // The mode alone isn't a condition
+        assert!(
+            StringFilter {
+                mode: Some(StringFilterMode::CaseSensitive),
+                ..Default::default()
+            }
+            .is_empty()
+        );
     }
 }
@@ -32,6 +32,7 @@
 //! ```
 
 pub mod auth;
+pub mod cancellation;
 pub mod filters;
 pub mod helpers;
 pub mod mutations;
@@ -40,7 +41,8 @@ pub mod queries;
 mod schema;
 mod subscriptions;
 pub mod types;
+pub mod typescript;
 
 pub use auth::{AuthUser, verify_token};
-pub use schema::{LibrarianSchema, build_schema};
+pub use schema::{LibrarianSchema, build_schema, schema_sdl, schema_version};
 pub use types::{Library, LibraryChangeType, LibraryChangedEvent, MediaFileUpdatedEvent, ContentDownloadProgressEvent};
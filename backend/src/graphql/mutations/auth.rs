@@ -1,14 +1,16 @@
 //! GraphQL authentication mutations
 //!
-//! Provides mutations for user registration, login, token management, and logout.
-//! Most auth mutations do not require authentication (register, login, refreshToken, logout),
-//! while some operations like logoutAll require the user to be authenticated.
+//! Provides mutations for user registration, login, token management, logout, password
+//! reset, and account self-service (password, email, avatar).
+//! Most auth mutations do not require authentication (register, login, refreshToken, logout,
+//! requestPasswordReset, resetPassword, confirmEmailChange), while some operations like
+//! logoutAll require the user to be authenticated.
 
 use async_graphql::{Context, InputObject, Object, Result, SimpleObject};
 
-use crate::db::Database;
+use crate::db::{Database, UpdateUser};
 use crate::graphql::auth::AuthExt;
-use crate::services::{AuthService, RegisterInput};
+use crate::services::{ArtworkService, AuthService, Mailer, RegisterInput};
 
 // ============================================================================
 // Input Types
@@ -139,6 +141,30 @@ pub struct AuthMutationResult {
     pub error: Option<String>,
 }
 
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// The SMTP mailer, or the error to show when email isn't set up
+fn configured_mailer() -> std::result::Result<Mailer, String> {
+    match Mailer::from_env() {
+        Ok(Some(mailer)) => Ok(mailer),
+        Ok(None) => Err("Email is not configured on this server".to_string()),
+        Err(e) => {
+            tracing::error!(error = %e, "Invalid SMTP configuration");
+            Err("Email is not configured correctly on this server".to_string())
+        }
+    }
+}
+
+/// Base URL for links in account mail
+fn public_url() -> String {
+    std::env::var("PUBLIC_URL")
+        .unwrap_or_else(|_| "http://localhost:3001".to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
 // ============================================================================
 // Mutations
 // ============================================================================
@@ -320,8 +346,9 @@ impl AuthMutations {
 
     /// Change the current user's password
     ///
-    /// Requires authentication. All refresh tokens will be invalidated.
-    async fn change_password(
+    /// Requires authentication. The new password must meet the strength rules.
+    /// All refresh tokens will be invalidated.
+    async fn change_my_password(
         &self,
         ctx: &Context<'_>,
         current_password: String,
@@ -358,4 +385,244 @@ impl AuthMutations {
             }
         }
     }
+
+    /// Email a password reset link to the account with this address
+    ///
+    /// No authentication required. Always answers the same way, whether or
+    /// not an account has the address, and sends the mail in the background
+    /// so response times don't tell either. An account gets at most one mail
+    /// every few minutes.
+    async fn request_password_reset(
+        &self,
+        ctx: &Context<'_>,
+        email: String,
+    ) -> Result<AuthMutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let auth_service = AuthService::with_env(db.clone());
+        let email = email.trim().to_string();
+
+        let mailer = match configured_mailer() {
+            Ok(mailer) => mailer,
+            Err(error) => {
+                return Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(error),
+                });
+            }
+        };
+
+        let token = match auth_service.request_password_reset(&email).await {
+            Ok(Some(token)) => Some(token),
+            Ok(None) => {
+                tracing::debug!("Password reset not sent: unknown email or on cooldown");
+                None
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Password reset request failed");
+                None
+            }
+        };
+
+        if let Some(token) = token {
+            let body = format!(
+                "Someone asked to reset the password of the Librarian account for this \
+                 address.\n\nTo choose a new password, open this link within an hour:\n\n\
+                 {}/auth/reset-password?token={}\n\n\
+                 If this wasn't you, ignore this message and your password won't change.\n",
+                public_url(),
+                token
+            );
+
+            tokio::spawn(async move {
+                if let Err(e) = mailer.send(&email, "Reset your password", &body).await {
+                    tracing::warn!(error = %e, "Failed to send password reset email");
+                    if let Err(e) = auth_service.cancel_password_reset(&token).await {
+                        tracing::warn!(error = %e, "Failed to discard password reset token");
+                    }
+                    return;
+                }
+                tracing::info!("Password reset email sent");
+            });
+        }
+
+        Ok(AuthMutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Set a new password with the token from a password reset email
+    ///
+    /// No authentication required (the token authorizes it). Tokens are
+    /// single-use, and all of the user's sessions are signed out.
+    async fn reset_password(
+        &self,
+        ctx: &Context<'_>,
+        token: String,
+        new_password: String,
+    ) -> Result<AuthMutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let auth_service = AuthService::with_env(db.clone());
+
+        match auth_service.reset_password(&token, &new_password).await {
+            Ok(user_id) => {
+                tracing::info!(user_id = %user_id, "Password reset");
+                Ok(AuthMutationResult {
+                    success: true,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Password reset failed");
+                Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                })
+            }
+        }
+    }
+
+    /// Start changing the current user's email address
+    ///
+    /// Requires authentication and the current password. A confirmation link is
+    /// mailed to the new address; the email only changes once it's followed.
+    async fn change_my_email(
+        &self,
+        ctx: &Context<'_>,
+        new_email: String,
+        current_password: String,
+    ) -> Result<AuthMutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let auth_service = AuthService::with_env(db.clone());
+        let new_email = new_email.trim();
+
+        let mailer = match configured_mailer() {
+            Ok(mailer) => mailer,
+            Err(error) => {
+                return Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(error),
+                });
+            }
+        };
+
+        let token = match auth_service
+            .request_email_change(&user.user_id, &current_password, new_email)
+            .await
+        {
+            Ok(token) => token,
+            Err(e) => {
+                return Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
+        let body = format!(
+            "Someone asked to change the email address of a Librarian account to this \
+             address.\n\nTo confirm, open this link within 24 hours:\n\n\
+             {}/auth/confirm-email?token={}\n\n\
+             If this wasn't you, ignore this message and nothing will change.\n",
+            public_url(),
+            token
+        );
+
+        match mailer
+            .send(new_email, "Confirm your new email address", &body)
+            .await
+        {
+            Ok(()) => {
+                tracing::info!(user_id = %user.user_id, "Email change confirmation sent");
+                Ok(AuthMutationResult {
+                    success: true,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                tracing::warn!(
+                    user_id = %user.user_id,
+                    error = %e,
+                    "Failed to send email change confirmation"
+                );
+                // A link that never arrived shouldn't stay valid
+                if let Err(e) = auth_service.cancel_email_change(&token).await {
+                    tracing::warn!(error = %e, "Failed to discard email change token");
+                }
+                Ok(AuthMutationResult {
+                    success: false,
+                    error: Some("Couldn't send the confirmation email".to_string()),
+                })
+            }
+        }
+    }
+
+    /// Confirm an email change with the token from the confirmation link
+    ///
+    /// No authentication required (the token authorizes it). Tokens are single-use.
+    async fn confirm_email_change(
+        &self,
+        ctx: &Context<'_>,
+        token: String,
+    ) -> Result<AuthMutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let auth_service = AuthService::with_env(db.clone());
+
+        match auth_service.confirm_email_change(&token).await {
+            Ok(user_id) => {
+                tracing::info!(user_id = %user_id, "Email address changed");
+                Ok(AuthMutationResult {
+                    success: true,
+                    error: None,
+                })
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Email change confirmation failed");
+                Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                })
+            }
+        }
+    }
+
+    /// Remove the current user's avatar
+    ///
+    /// Requires authentication. Avatars are uploaded with `POST /api/account/avatar`.
+    async fn remove_my_avatar(&self, ctx: &Context<'_>) -> Result<AuthMutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let result = async {
+            ArtworkService::with_env(db.clone())
+                .delete_entity_artwork("user", &user.user_id)
+                .await?;
+            db.users()
+                .update(
+                    &user.user_id,
+                    UpdateUser {
+                        avatar_url: Some(None),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            anyhow::Ok(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => Ok(AuthMutationResult {
+                success: true,
+                error: None,
+            }),
+            Err(e) => {
+                tracing::warn!(user_id = %user.user_id, error = %e, "Failed to remove avatar");
+                Ok(AuthMutationResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                })
+            }
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use super::prelude::*;
+use crate::graphql::auth::{Permission, PermissionGuard};
 
 #[derive(Default)]
 pub struct IndexerMutations;
@@ -6,6 +7,7 @@ pub struct IndexerMutations;
 #[Object]
 impl IndexerMutations {
     /// Create a new indexer
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn create_indexer(
         &self,
         ctx: &Context<'_>,
@@ -144,6 +146,7 @@ impl IndexerMutations {
     }
 
     /// Update an existing indexer
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn update_indexer(
         &self,
         ctx: &Context<'_>,
@@ -278,7 +281,187 @@ impl IndexerMutations {
         })
     }
 
+    /// Set the minimum ratio a tracker requires (null to stop warning)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn set_tracker_min_ratio(
+        &self,
+        ctx: &Context<'_>,
+        indexer_id: String,
+        min_ratio: Option<f64>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let config_id = Uuid::parse_str(&indexer_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid indexer ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)?;
+
+        if min_ratio.is_some_and(|r| !r.is_finite() || r < 0.0) {
+            return Ok(MutationResult {
+                success: false,
+                error: Some("Minimum ratio must be zero or more".to_string()),
+            });
+        }
+
+        // Verify ownership
+        let existing = db
+            .indexers()
+            .get(config_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        match existing {
+            Some(r) if r.user_id == user_id => {}
+            _ => {
+                return Ok(MutationResult {
+                    success: false,
+                    error: Some("Indexer not found".to_string()),
+                });
+            }
+        }
+
+        db.indexers()
+            .set_min_ratio(config_id, min_ratio)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Replace a tracker's passkey after the tracker rotated it
+    ///
+    /// Stores the new passkey for future grabs, rewrites RSS feed URLs that
+    /// contain the old one, and re-adds the indexer's torrents with updated
+    /// announce URLs.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn update_tracker_passkey(
+        &self,
+        ctx: &Context<'_>,
+        indexer_id: String,
+        new_passkey: String,
+    ) -> Result<TrackerPasskeyResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let torrent_service = ctx.data_unchecked::<Arc<TorrentService>>();
+        let config_id = Uuid::parse_str(&indexer_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid indexer ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)?;
+
+        let new_passkey = new_passkey.trim();
+        if new_passkey.is_empty() || !new_passkey.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Ok(TrackerPasskeyResult {
+                success: false,
+                error: Some("Passkey must be letters and digits only".to_string()),
+                torrents_updated: 0,
+            });
+        }
+
+        // Verify ownership
+        let existing = db
+            .indexers()
+            .get(config_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        match existing {
+            Some(r) if r.user_id == user_id => {}
+            _ => {
+                return Ok(TrackerPasskeyResult {
+                    success: false,
+                    error: Some("Indexer not found".to_string()),
+                    torrents_updated: 0,
+                });
+            }
+        }
+
+        let encryption_key = db
+            .settings()
+            .get_or_create_indexer_encryption_key()
+            .await
+            .map_err(|e| {
+                async_graphql::Error::new(format!("Failed to get encryption key: {}", e))
+            })?;
+        let encryption =
+            crate::indexer::encryption::CredentialEncryption::from_base64_key(&encryption_key)
+                .map_err(|e| async_graphql::Error::new(format!("Encryption error: {}", e)))?;
+
+        // The stored passkey, if any, pins down exactly what to replace;
+        // without it the passkey is located in each URL
+        let credentials = db
+            .indexers()
+            .get_credentials(config_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let old_passkey = credentials
+            .iter()
+            .find(|c| c.credential_type == "passkey")
+            .and_then(|c| encryption.decrypt(&c.encrypted_value, &c.nonce).ok());
+
+        let (encrypted_value, nonce) = encryption
+            .encrypt(new_passkey)
+            .map_err(|e| async_graphql::Error::new(format!("Encryption error: {}", e)))?;
+        db.indexers()
+            .upsert_credential(
+                config_id,
+                crate::db::UpsertCredential {
+                    credential_type: "passkey".to_string(),
+                    encrypted_value,
+                    nonce,
+                },
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        if let Some(old) = old_passkey.as_deref().filter(|old| !old.is_empty()) {
+            let feeds = db
+                .rss_feeds()
+                .list_by_user(user_id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            for feed in feeds.into_iter().filter(|f| f.url.contains(old)) {
+                let update = crate::db::UpdateRssFeed {
+                    url: Some(feed.url.replace(old, new_passkey)),
+                    ..Default::default()
+                };
+                if let Err(e) = db.rss_feeds().update(feed.id, update).await {
+                    tracing::warn!(feed_id = %feed.id, error = %e, "Failed to update RSS feed passkey");
+                }
+            }
+        }
+
+        let result = torrent_service
+            .update_announce_passkey(config_id, old_passkey.as_deref(), new_passkey)
+            .await;
+
+        db.indexers()
+            .clear_passkey_error(config_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(match result {
+            Ok(updated) => {
+                tracing::info!(
+                    user_id = %user.user_id,
+                    indexer_id = %config_id,
+                    torrents_updated = updated,
+                    "Tracker passkey updated"
+                );
+                TrackerPasskeyResult {
+                    success: true,
+                    error: None,
+                    torrents_updated: updated as i32,
+                }
+            }
+            Err(e) => TrackerPasskeyResult {
+                success: false,
+                error: Some(e.to_string()),
+                torrents_updated: 0,
+            },
+        })
+    }
+
     /// Delete an indexer
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn delete_indexer(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -320,6 +503,7 @@ impl IndexerMutations {
     }
 
     /// Test an indexer connection
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn test_indexer(&self, ctx: &Context<'_>, id: String) -> Result<IndexerTestResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -0,0 +1,279 @@
+use super::prelude::*;
+use crate::services::subtitle_download::iso639_1;
+
+#[derive(Default)]
+pub struct LanguageProfileMutations;
+
+#[Object]
+impl LanguageProfileMutations {
+    /// Create a language profile
+    async fn create_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        input: CreateLanguageProfileInput,
+    ) -> Result<LanguageProfileResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let name = input.name.trim().to_string();
+        if name.is_empty() {
+            return Ok(profile_error("Name is required"));
+        }
+        let existing = db
+            .language_profiles()
+            .list_by_user(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if existing.iter().any(|p| p.name.eq_ignore_ascii_case(&name)) {
+            return Ok(profile_error(
+                "A language profile with that name already exists",
+            ));
+        }
+        let languages = (
+            normalize_languages(input.preferred_audio_languages.unwrap_or_default()),
+            normalize_languages(input.required_audio_languages.unwrap_or_default()),
+            normalize_languages(input.required_subtitle_languages.unwrap_or_default()),
+        );
+        let (preferred_audio, required_audio, required_subtitles) = match languages {
+            (Ok(p), Ok(a), Ok(s)) => (p, a, s),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return Ok(profile_error(&e)),
+        };
+
+        let record = db
+            .language_profiles()
+            .create(CreateLanguageProfile {
+                user_id,
+                name,
+                preferred_audio_languages: preferred_audio,
+                required_audio_languages: required_audio,
+                required_subtitle_languages: required_subtitles,
+                regrab_missing_audio: input.regrab_missing_audio.unwrap_or(false),
+            })
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(LanguageProfileResult {
+            success: true,
+            error: None,
+            profile: Some(LanguageProfile::from(record)),
+        })
+    }
+
+    /// Update a language profile
+    async fn update_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+        input: UpdateLanguageProfileInput,
+    ) -> Result<LanguageProfileResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let profile_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid profile ID: {}", e)))?;
+
+        if !owns_profile(db, profile_id, user_id).await? {
+            return Ok(profile_error("Language profile not found"));
+        }
+
+        let name = input.name.map(|n| n.trim().to_string());
+        if name.as_deref() == Some("") {
+            return Ok(profile_error("Name is required"));
+        }
+        let languages = (
+            input
+                .preferred_audio_languages
+                .map(normalize_languages)
+                .transpose(),
+            input
+                .required_audio_languages
+                .map(normalize_languages)
+                .transpose(),
+            input
+                .required_subtitle_languages
+                .map(normalize_languages)
+                .transpose(),
+        );
+        let (preferred_audio, required_audio, required_subtitles) = match languages {
+            (Ok(p), Ok(a), Ok(s)) => (p, a, s),
+            (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => return Ok(profile_error(&e)),
+        };
+
+        let record = db
+            .language_profiles()
+            .update(
+                profile_id,
+                UpdateLanguageProfile {
+                    name,
+                    preferred_audio_languages: preferred_audio,
+                    required_audio_languages: required_audio,
+                    required_subtitle_languages: required_subtitles,
+                    regrab_missing_audio: input.regrab_missing_audio,
+                },
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(LanguageProfileResult {
+            success: record.is_some(),
+            error: record
+                .is_none()
+                .then(|| "Language profile not found".to_string()),
+            profile: record.map(LanguageProfile::from),
+        })
+    }
+
+    /// Delete a language profile
+    ///
+    /// Libraries and shows that used it are left without a profile.
+    async fn delete_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let profile_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid profile ID: {}", e)))?;
+
+        if !owns_profile(db, profile_id, user_id).await? {
+            return Ok(MutationResult {
+                success: false,
+                error: Some("Language profile not found".to_string()),
+            });
+        }
+
+        let deleted = db
+            .language_profiles()
+            .delete(profile_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: deleted,
+            error: (!deleted).then(|| "Language profile not found".to_string()),
+        })
+    }
+
+    /// Assign a language profile to a library, or clear it with null
+    async fn set_library_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        library_id: String,
+        profile_id: Option<String>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let profile_id = parse_owned_profile(db, profile_id, user_id).await?;
+
+        db.language_profiles()
+            .assign_to_library(lib_id, profile_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Assign a language profile to a show, or clear it with null so the
+    /// library's profile applies
+    async fn set_show_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        show_id: String,
+        profile_id: Option<String>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let show_id = Uuid::parse_str(&show_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid show ID: {}", e)))?;
+
+        db.tv_shows()
+            .get_by_id(show_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .filter(|show| show.user_id == user_id)
+            .ok_or_else(|| async_graphql::Error::new("Show not found"))?;
+
+        let profile_id = parse_owned_profile(db, profile_id, user_id).await?;
+
+        db.language_profiles()
+            .assign_to_show(show_id, profile_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+}
+
+fn profile_error(message: &str) -> LanguageProfileResult {
+    LanguageProfileResult {
+        success: false,
+        error: Some(message.to_string()),
+        profile: None,
+    }
+}
+
+/// Convert languages to ISO 639-1 codes, dropping duplicates but keeping
+/// the order (it is the preference order for audio)
+fn normalize_languages(languages: Vec<String>) -> std::result::Result<Vec<String>, String> {
+    let mut codes: Vec<String> = Vec::new();
+    for language in languages {
+        let code = iso639_1(&language).ok_or_else(|| format!("Unknown language: {}", language))?;
+        if !codes.iter().any(|c| c == code) {
+            codes.push(code.to_string());
+        }
+    }
+    Ok(codes)
+}
+
+async fn owns_profile(db: &Database, profile_id: Uuid, user_id: Uuid) -> Result<bool> {
+    let profile = db
+        .language_profiles()
+        .get(profile_id)
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+    Ok(profile.is_some_and(|p| p.user_id == user_id))
+}
+
+/// Parse an optional profile ID for assignment, checking it belongs to the
+/// user
+async fn parse_owned_profile(
+    db: &Database,
+    profile_id: Option<String>,
+    user_id: Uuid,
+) -> Result<Option<Uuid>> {
+    let Some(profile_id) = profile_id else {
+        return Ok(None);
+    };
+    let profile_id = Uuid::parse_str(&profile_id)
+        .map_err(|e| async_graphql::Error::new(format!("Invalid profile ID: {}", e)))?;
+
+    if !owns_profile(db, profile_id, user_id).await? {
+        return Err(async_graphql::Error::new("Language profile not found"));
+    }
+    Ok(Some(profile_id))
+}
@@ -1,11 +1,25 @@
 use super::prelude::*;
 
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::services::file_permissions::parse_mode;
+use crate::services::scan_exclusions::ScanExclusions;
+use crate::services::{ArtworkAuditService, AuditService, BackgroundJobService};
+
+/// Validate an octal file mode and store it in canonical form (blank = none)
+fn normalize_file_mode(mode: Option<String>) -> anyhow::Result<Option<String>> {
+    match mode.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+        Some(mode) => Ok(Some(format!("{:o}", parse_mode(mode)?))),
+        None => Ok(None),
+    }
+}
+
 #[derive(Default)]
 pub struct LibraryMutations;
 
 #[Object]
 impl LibraryMutations {
     /// Create a new library
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
     async fn create_library(
         &self,
         ctx: &Context<'_>,
@@ -16,6 +30,27 @@ impl LibraryMutations {
         let user_id = Uuid::parse_str(&user.user_id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
 
+        if let Some(patterns) = &input.exclude_patterns
+            && let Err(e) = ScanExclusions::new(patterns)
+        {
+            return Ok(LibraryResult {
+                success: false,
+                library: None,
+                error: Some(e.to_string()),
+            });
+        }
+
+        let file_mode = match normalize_file_mode(input.file_mode) {
+            Ok(mode) => mode,
+            Err(e) => {
+                return Ok(LibraryResult {
+                    success: false,
+                    library: None,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
         let library_type = match input.library_type {
             LibraryType::Movies => "movies",
             LibraryType::Tv => "tv",
@@ -60,10 +95,23 @@ impl LibraryMutations {
                 allowed_sources: input.allowed_sources.unwrap_or_default(),
                 release_group_blacklist: input.release_group_blacklist.unwrap_or_default(),
                 release_group_whitelist: input.release_group_whitelist.unwrap_or_default(),
+                exclude_patterns: input.exclude_patterns.unwrap_or_default(),
+                file_uid: input.file_uid,
+                file_gid: input.file_gid,
+                file_mode,
+                missing_file_policy: input
+                    .missing_file_policy
+                    .unwrap_or(MissingFilePolicy::Flag)
+                    .as_str()
+                    .to_string(),
             })
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
+        ctx.data_unchecked::<Arc<AuditService>>()
+            .record_create(user_id, "library", record.id, &record)
+            .await;
+
         tracing::info!(
             user_id = %user.user_id,
             library_id = %record.id,
@@ -104,14 +152,12 @@ impl LibraryMutations {
         };
 
         // Emit library created event
-        if let Ok(library_tx) = ctx.data::<tokio::sync::broadcast::Sender<LibraryChangedEvent>>() {
-            let _ = library_tx.send(LibraryChangedEvent {
-                change_type: LibraryChangeType::Created,
-                library_id: library.id.clone(),
-                library_name: Some(library.name.clone()),
-                library: Some(library.clone()),
-            });
-        }
+        ctx.data_unchecked::<Arc<EventBus>>().publish(LibraryChangedEvent {
+            change_type: LibraryChangeType::Created,
+            library_id: library.id.clone(),
+            library_name: Some(library.name.clone()),
+            library: Some(library.clone()),
+        });
 
         // Check if this is a movie library and TMDB isn't configured
         if input.library_type == LibraryType::Movies {
@@ -157,17 +203,41 @@ impl LibraryMutations {
     }
 
     /// Update an existing library
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
     async fn update_library(
         &self,
         ctx: &Context<'_>,
         id: String,
         input: UpdateLibraryInput,
     ) -> Result<LibraryResult> {
-        let _user = ctx.auth_user()?;
+        let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
         let lib_id = Uuid::parse_str(&id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
 
+        if let Some(patterns) = &input.exclude_patterns
+            && let Err(e) = ScanExclusions::new(patterns)
+        {
+            return Ok(LibraryResult {
+                success: false,
+                library: None,
+                error: Some(e.to_string()),
+            });
+        }
+
+        let file_mode = match input.file_mode.map(normalize_file_mode).transpose() {
+            Ok(mode) => mode,
+            Err(e) => {
+                return Ok(LibraryResult {
+                    success: false,
+                    library: None,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
         let post_download_action = input.post_download_action.map(|a| {
             match a {
                 PostDownloadAction::Move => "move",
@@ -177,6 +247,12 @@ impl LibraryMutations {
             .to_string()
         });
 
+        let before = db
+            .libraries()
+            .get_by_id(lib_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
         let result = db
             .libraries()
             .update(
@@ -205,12 +281,25 @@ impl LibraryMutations {
                     allowed_sources: input.allowed_sources,
                     release_group_blacklist: input.release_group_blacklist,
                     release_group_whitelist: input.release_group_whitelist,
+                    exclude_patterns: input.exclude_patterns,
+                    file_uid: input.file_uid,
+                    file_gid: input.file_gid,
+                    file_mode,
+                    missing_file_policy: input
+                        .missing_file_policy
+                        .map(|p| p.as_str().to_string()),
                 },
             )
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
         if let Some(record) = result {
+            if let Some(before) = &before {
+                ctx.data_unchecked::<Arc<AuditService>>()
+                    .record_update(user_id, "library", lib_id, before, &record)
+                    .await;
+            }
+
             let library = Library {
                 id: record.id.to_string(),
                 name: record.name,
@@ -233,16 +322,12 @@ impl LibraryMutations {
             };
 
             // Emit library updated event
-            if let Ok(library_tx) =
-                ctx.data::<tokio::sync::broadcast::Sender<LibraryChangedEvent>>()
-            {
-                let _ = library_tx.send(LibraryChangedEvent {
-                    change_type: LibraryChangeType::Updated,
-                    library_id: library.id.clone(),
-                    library_name: Some(library.name.clone()),
-                    library: Some(library.clone()),
-                });
-            }
+            ctx.data_unchecked::<Arc<EventBus>>().publish(LibraryChangedEvent {
+                change_type: LibraryChangeType::Updated,
+                library_id: library.id.clone(),
+                library_name: Some(library.name.clone()),
+                library: Some(library.clone()),
+            });
 
             Ok(LibraryResult {
                 success: true,
@@ -259,20 +344,18 @@ impl LibraryMutations {
     }
 
     /// Delete a library
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
     async fn delete_library(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
-        let _user = ctx.auth_user()?;
+        let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
         let lib_id = Uuid::parse_str(&id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
 
-        // Get library name before deleting for the event
-        let library_name = db
-            .libraries()
-            .get_by_id(lib_id)
-            .await
-            .ok()
-            .flatten()
-            .map(|lib| lib.name);
+        // Get the library before deleting, for the event and the audit log
+        let existing = db.libraries().get_by_id(lib_id).await.ok().flatten();
+        let library_name = existing.as_ref().map(|lib| lib.name.clone());
 
         let deleted = db
             .libraries()
@@ -280,18 +363,20 @@ impl LibraryMutations {
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
+        if deleted && let Some(existing) = &existing {
+            ctx.data_unchecked::<Arc<AuditService>>()
+                .record_delete(user_id, "library", lib_id, existing)
+                .await;
+        }
+
         // Emit library deleted event
         if deleted {
-            if let Ok(library_tx) =
-                ctx.data::<tokio::sync::broadcast::Sender<LibraryChangedEvent>>()
-            {
-                let _ = library_tx.send(LibraryChangedEvent {
-                    change_type: LibraryChangeType::Deleted,
-                    library_id: id.clone(),
-                    library_name,
-                    library: None,
-                });
-            }
+            ctx.data_unchecked::<Arc<EventBus>>().publish(LibraryChangedEvent {
+                change_type: LibraryChangeType::Deleted,
+                library_id: id.clone(),
+                library_name,
+                library: None,
+            });
         }
 
         Ok(MutationResult {
@@ -305,6 +390,7 @@ impl LibraryMutations {
     }
 
     /// Trigger a library scan
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
     async fn scan_library(&self, ctx: &Context<'_>, id: String) -> Result<ScanStatus> {
         let _user = ctx.auth_user()?;
         let scanner = ctx.data_unchecked::<Arc<ScannerService>>();
@@ -347,25 +433,33 @@ impl LibraryMutations {
 
     /// Consolidate library folders - merge duplicate show folders, update paths
     /// This is useful after changing naming conventions to clean up old folder structures
+    ///
+    /// Runs as a background job: the job's result is a ConsolidateLibraryResult.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
     async fn consolidate_library(
         &self,
         ctx: &Context<'_>,
         id: String,
-    ) -> Result<ConsolidateLibraryResult> {
-        let _user = ctx.auth_user()?;
+    ) -> Result<ConsolidateLibraryJobResult> {
+        let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
+        let jobs = ctx.data_unchecked::<Arc<BackgroundJobService>>();
 
         let library_id = Uuid::parse_str(&id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id).ok();
 
         tracing::debug!("Consolidation requested for library {}", id);
 
         let organizer = crate::services::OrganizerService::new(db.clone());
+        let payload = serde_json::json!({ "library_id": id });
 
-        match organizer.consolidate_library(library_id).await {
-            Ok(result) => {
+        let job = jobs
+            .enqueue("consolidate_library", user_id, Some(payload), move |job| async move {
+                job.progress(0.0, Some("Consolidating library folders")).await;
+                let result = organizer.consolidate_library(library_id).await?;
                 tracing::info!(
-                    library_id = %id,
+                    library_id = %library_id,
                     folders_removed = result.folders_removed,
                     files_moved = result.files_moved,
                     "Library consolidation complete"
@@ -376,16 +470,233 @@ impl LibraryMutations {
                     files_moved: result.files_moved,
                     messages: result.messages,
                 })
+            })
+            .await;
+
+        match job {
+            Ok(job) => Ok(ConsolidateLibraryJobResult::queued(job.id)),
+            Err(e) => {
+                tracing::error!(library_id = %id, error = %e, "Failed to queue library consolidation");
+                Ok(ConsolidateLibraryJobResult::error(format!(
+                    "Failed to start consolidation: {}",
+                    e
+                )))
             }
+        }
+    }
+
+    /// Check the artwork of every show, movie and album in a library (or in
+    /// all of the user's libraries)
+    ///
+    /// Cached images whose cache entry is gone and provider URLs left by
+    /// failed caching are downloaded again; items with no poster get one
+    /// from their provider. Runs as a background job reporting progress.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
+    async fn audit_artwork(
+        &self,
+        ctx: &Context<'_>,
+        library_id: Option<String>,
+    ) -> Result<ArtworkAuditJobResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let jobs = ctx.data_unchecked::<Arc<BackgroundJobService>>();
+        let metadata = ctx.data_unchecked::<Arc<MetadataService>>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        if metadata.artwork_service().is_none() {
+            return Ok(ArtworkAuditJobResult::error(
+                "Artwork caching is not enabled",
+            ));
+        }
+
+        let library_ids = match owned_library_id(db, user_id, library_id.as_deref()).await? {
+            Some(library_id) => vec![library_id],
+            None => db
+                .libraries()
+                .list_by_user(user_id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                .into_iter()
+                .map(|library| library.id)
+                .collect(),
+        };
+
+        let audit = ArtworkAuditService::new(db.clone(), metadata.clone());
+        let payload = serde_json::json!({ "library_id": library_id });
+
+        let job = jobs
+            .enqueue(
+                "artwork_audit",
+                Some(user_id),
+                Some(payload),
+                move |job| async move { audit.audit_libraries(&library_ids, &job).await },
+            )
+            .await;
+
+        match job {
+            Ok(job) => Ok(ArtworkAuditJobResult::queued(job.id)),
             Err(e) => {
-                tracing::error!(library_id = %id, error = %e, "Library consolidation failed");
-                Ok(ConsolidateLibraryResult {
-                    success: false,
-                    folders_removed: 0,
-                    files_moved: 0,
-                    messages: vec![format!("Consolidation failed: {}", e)],
-                })
+                tracing::error!(error = %e, "Failed to queue artwork audit");
+                Ok(ArtworkAuditJobResult::error(format!(
+                    "Failed to start artwork audit: {}",
+                    e
+                )))
             }
         }
     }
+
+    /// Fetch a show's, movie's or album's artwork from its provider and cache
+    /// it again
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
+    async fn refresh_artwork(
+        &self,
+        ctx: &Context<'_>,
+        entity_type: ArtworkEntityType,
+        id: String,
+    ) -> Result<RefreshArtworkResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let metadata = ctx.data_unchecked::<Arc<MetadataService>>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let entity_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid item ID: {}", e)))?;
+
+        let audit = ArtworkAuditService::new(db.clone(), metadata.clone());
+        let failed = |error: String| RefreshArtworkResult {
+            success: false,
+            error: Some(error),
+            poster_url: None,
+            backdrop_url: None,
+            images_updated: 0,
+            images_failed: 0,
+        };
+
+        match audit.refresh(entity_type.into(), entity_id, user_id).await {
+            Ok(Some(refresh)) => Ok(RefreshArtworkResult {
+                success: refresh.report.images_failed == 0,
+                error: (refresh.report.images_failed > 0)
+                    .then(|| "Some artwork couldn't be downloaded".to_string()),
+                poster_url: refresh.poster_url,
+                backdrop_url: refresh.backdrop_url,
+                images_updated: refresh.report.images_recached + refresh.report.images_fetched,
+                images_failed: refresh.report.images_failed,
+            }),
+            Ok(None) => Ok(failed("Item not found".to_string())),
+            Err(e) => Ok(failed(e.to_string())),
+        }
+    }
+
+    /// Pause auto-hunt and RSS polling for a library, or for every library
+    /// when `library_id` is omitted
+    ///
+    /// Scans and metadata refresh keep running. With `resume_at` (RFC 3339)
+    /// the pause lifts itself at that time.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
+    async fn pause_automation(
+        &self,
+        ctx: &Context<'_>,
+        library_id: Option<String>,
+        resume_at: Option<String>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let library_id = owned_library_id(db, user_id, library_id.as_deref()).await?;
+
+        let resume_at = match resume_at.as_deref().map(str::trim) {
+            None | Some("") => None,
+            Some(s) => match chrono::DateTime::parse_from_rfc3339(s) {
+                Ok(dt) if dt.to_utc() > chrono::Utc::now() => Some(dt.to_utc()),
+                Ok(_) => {
+                    return Ok(MutationResult {
+                        success: false,
+                        error: Some("Resume time must be in the future".to_string()),
+                    });
+                }
+                Err(e) => {
+                    return Ok(MutationResult {
+                        success: false,
+                        error: Some(format!("Invalid resume time: {}", e)),
+                    });
+                }
+            },
+        };
+
+        db.automation_pauses()
+            .set(user_id, library_id, resume_at)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        tracing::info!(
+            user_id = %user_id,
+            library_id = ?library_id,
+            resume_at = ?resume_at,
+            "Automation paused"
+        );
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Resume automation for a library, or lift the global pause when
+    /// `library_id` is omitted
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageLibraries)")]
+    async fn resume_automation(
+        &self,
+        ctx: &Context<'_>,
+        library_id: Option<String>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let library_id = owned_library_id(db, user_id, library_id.as_deref()).await?;
+
+        let cleared = db
+            .automation_pauses()
+            .clear(user_id, library_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        if cleared {
+            tracing::info!(user_id = %user_id, library_id = ?library_id, "Automation resumed");
+        }
+
+        Ok(MutationResult {
+            success: cleared,
+            error: if cleared {
+                None
+            } else {
+                Some("Automation is not paused".to_string())
+            },
+        })
+    }
+}
+
+/// Parse an optional library ID, checking the user owns the library
+async fn owned_library_id(
+    db: &Database,
+    user_id: Uuid,
+    library_id: Option<&str>,
+) -> Result<Option<Uuid>> {
+    let Some(library_id) = library_id else {
+        return Ok(None);
+    };
+    let lib_id = Uuid::parse_str(library_id)
+        .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+
+    db.libraries()
+        .get_by_id_and_user(lib_id, user_id)
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+    Ok(Some(lib_id))
 }
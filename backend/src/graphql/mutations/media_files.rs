@@ -155,9 +155,28 @@ impl MediaFileMutations {
             "Running FFprobe analysis on media file"
         );
 
-        // Run FFmpeg analysis
+        // Run FFmpeg analysis, killing ffprobe if the request is cancelled
         let ffmpeg = crate::services::FfmpegService::new();
-        let analysis = match ffmpeg.analyze(std::path::Path::new(&file.path)).await {
+        let cancel = ctx.cancellation();
+        let analyzed = tokio::select! {
+            analyzed = ffmpeg.analyze(std::path::Path::new(&file.path)) => analyzed,
+            _ = cancel.cancelled() => {
+                tracing::warn!(
+                    media_file_id = %media_file_id,
+                    path = %file.path,
+                    "FFprobe analysis cancelled"
+                );
+                return Ok(AnalyzeMediaFileResult {
+                    success: false,
+                    error: Some("FFmpeg analysis timed out".to_string()),
+                    video_stream_count: None,
+                    audio_stream_count: None,
+                    subtitle_stream_count: None,
+                    chapter_count: None,
+                });
+            }
+        };
+        let analysis = match analyzed {
             Ok(a) => a,
             Err(e) => {
                 tracing::warn!(
@@ -295,6 +314,92 @@ impl MediaFileMutations {
         })
     }
 
+    /// Queue subtitle downloads for every file missing a language
+    ///
+    /// Files are searched one at a time on the subtitle download queue, which
+    /// paces requests and stops for the day once the OpenSubtitles download
+    /// quota is used up. Requires OpenSubtitles credentials in settings.
+    async fn search_subtitles_bulk(
+        &self,
+        ctx: &Context<'_>,
+        r#where: SubtitleSearchWhereInput,
+    ) -> Result<SearchSubtitlesBulkResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let lib_id = Uuid::parse_str(&r#where.library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let Some(language) = crate::services::subtitle_download::iso639_1(&r#where.language) else {
+            return Ok(SearchSubtitlesBulkResult {
+                success: false,
+                queued_count: 0,
+                error: Some(format!("Unsupported language: {}", r#where.language)),
+            });
+        };
+
+        let Some(queue) = ctx.data_opt::<Arc<crate::services::SubtitleDownloadQueue>>() else {
+            return Ok(SearchSubtitlesBulkResult {
+                success: false,
+                queued_count: 0,
+                error: Some("Subtitle download queue not available".to_string()),
+            });
+        };
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let variants = crate::services::subtitle_download::language_variants(language);
+        let mut files = db
+            .media_files()
+            .list_missing_subtitle_language(lib_id, &variants)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if let Some(ids) = &r#where.media_file_ids {
+            files.retain(|f| ids.contains(&f.id.to_string()));
+        }
+
+        let count = files.len();
+        tracing::info!(
+            library_id = %lib_id,
+            language,
+            file_count = count,
+            "Queueing subtitle searches"
+        );
+
+        // The queue holds a limited number of jobs, so submitting a large
+        // library waits for room; do that in the background
+        let queue = queue.clone();
+        let language = language.to_string();
+        tokio::spawn(async move {
+            for file in files {
+                let job = crate::services::SubtitleDownloadJob {
+                    media_file_id: file.id,
+                    episode_id: file.episode_id,
+                    languages: vec![language.clone()],
+                    imdb_id: None,
+                    show_name: None,
+                    season: None,
+                    episode: None,
+                };
+                if queue.submit(job).await.is_err() {
+                    tracing::warn!("Subtitle download queue closed");
+                    break;
+                }
+            }
+        });
+
+        Ok(SearchSubtitlesBulkResult {
+            success: true,
+            queued_count: count as i32,
+            error: None,
+        })
+    }
+
     /// Delete a subtitle (external or downloaded only)
     async fn delete_subtitle(
         &self,
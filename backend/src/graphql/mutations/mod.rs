@@ -2,6 +2,7 @@ pub mod auth;
 pub mod audiobooks;
 pub mod filesystem;
 pub mod indexers;
+pub mod language_profiles;
 pub mod libraries;
 pub mod logs;
 pub mod media_files;
@@ -10,17 +11,21 @@ pub mod music;
 pub mod notifications;
 pub mod playback;
 pub mod priority_rules;
+pub mod roles;
 pub mod rss_feeds;
 pub mod settings;
+pub mod system;
 pub mod torrents;
 pub mod tv_shows;
 pub mod usenet;
 pub mod user;
+pub mod watch_state;
 
 pub use auth::AuthMutations;
 pub use audiobooks::AudiobookMutations;
 pub use filesystem::FilesystemMutations;
 pub use indexers::IndexerMutations;
+pub use language_profiles::LanguageProfileMutations;
 pub use libraries::LibraryMutations;
 pub use logs::LogMutations;
 pub use media_files::MediaFileMutations;
@@ -29,12 +34,15 @@ pub use music::MusicMutations;
 pub use notifications::NotificationMutations;
 pub use playback::PlaybackMutations;
 pub use priority_rules::PriorityRuleMutations;
+pub use roles::RoleMutations;
 pub use rss_feeds::RssFeedMutations;
 pub use settings::SettingsMutations;
+pub use system::SystemMutations;
 pub use torrents::TorrentMutations;
 pub use tv_shows::TvShowMutations;
 pub use usenet::UsenetMutations;
 pub use user::UserMutations;
+pub use watch_state::WatchStateMutations;
 
 pub(crate) mod prelude {
     pub(crate) use std::sync::Arc;
@@ -44,9 +52,10 @@ pub(crate) mod prelude {
 
     pub(crate) use crate::db::*;
     pub(crate) use crate::graphql::auth::AuthExt;
+    pub(crate) use crate::graphql::cancellation::CancellationExt;
     pub(crate) use crate::graphql::helpers::*;
     pub(crate) use crate::graphql::types::*;
     pub(crate) use crate::services::{
-        CastService, FilesystemService, MetadataService, ScannerService, TorrentService,
+        CastService, EventBus, FilesystemService, MetadataService, ScannerService, TorrentService,
     };
 }
@@ -1,21 +1,18 @@
 use super::prelude::*;
-use tokio::sync::broadcast;
 
 #[derive(Default)]
 pub struct MusicMutations;
 
 /// Helper to broadcast library change events
 async fn broadcast_library_changed(ctx: &Context<'_>, library_id: Uuid) {
-    if let Ok(tx) = ctx.data::<broadcast::Sender<LibraryChangedEvent>>() {
-        let db = ctx.data_unchecked::<Database>();
-        if let Ok(Some(lib)) = db.libraries().get_by_id(library_id).await {
-            let _ = tx.send(LibraryChangedEvent {
-                change_type: LibraryChangeType::Updated,
-                library_id: library_id.to_string(),
-                library_name: Some(lib.name.clone()),
-                library: Some(Library::from_db(lib)),
-            });
-        }
+    let db = ctx.data_unchecked::<Database>();
+    if let Ok(Some(lib)) = db.libraries().get_by_id(library_id).await {
+        ctx.data_unchecked::<Arc<EventBus>>().publish(LibraryChangedEvent {
+            change_type: LibraryChangeType::Updated,
+            library_id: library_id.to_string(),
+            library_name: Some(lib.name.clone()),
+            library: Some(Library::from_db(lib)),
+        });
     }
 }
 
@@ -1,4 +1,6 @@
 use super::prelude::*;
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::jobs::playback_streams as streams;
 
 #[derive(Default)]
 pub struct PlaybackMutations;
@@ -489,6 +491,47 @@ impl PlaybackMutations {
             PlaybackContentType::Audiobook => (None, None, None, Some(content_id), None, None),
         };
 
+        let stream_id = input
+            .stream_id
+            .as_deref()
+            .map(Uuid::parse_str)
+            .transpose()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid stream ID: {}", e)))?;
+
+        // A client continuing on its own lease doesn't take another slot
+        let renewing = match stream_id {
+            Some(id) => db
+                .playback_streams()
+                .get(id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                .is_some_and(|s| s.user_id == user_id && s.ended_at.is_none()),
+            None => false,
+        };
+        if !renewing {
+            let max_streams = db
+                .settings()
+                .get_or_default::<i32>(streams::MAX_STREAMS_KEY, streams::DEFAULT_MAX_STREAMS)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            let active = db
+                .playback_streams()
+                .count_active_for_user(user_id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            if streams::stream_limit_reached(active, max_streams) {
+                return Ok(PlaybackResult {
+                    success: false,
+                    session: None,
+                    stream_id: None,
+                    error: Some(format!(
+                        "Concurrent stream limit reached ({} of {}); stop playback on another device first",
+                        active, max_streams
+                    )),
+                });
+            }
+        }
+
         let db_input = crate::db::UpsertPlaybackSession {
             user_id,
             content_type: input.content_type.as_str().to_string(),
@@ -507,14 +550,35 @@ impl PlaybackMutations {
         };
 
         match db.playback().upsert_session(db_input).await {
-            Ok(session) => Ok(PlaybackResult {
-                success: true,
-                session: Some(PlaybackSession::from_record(session)),
-                error: None,
-            }),
+            Ok(session) => {
+                let lease = crate::db::CreatePlaybackStream {
+                    user_id,
+                    playback_session_id: Some(session.id),
+                    media_file_id: Some(media_file_id),
+                    content_type: Some(input.content_type.as_str().to_string()),
+                    client_name: input.client_name,
+                };
+                let streams_repo = db.playback_streams();
+                let stream_id = match stream_id {
+                    Some(id) if renewing => streams_repo
+                        .renew(id, user_id, &lease)
+                        .await
+                        .map(|_| id),
+                    _ => streams_repo.create(lease).await.map(|s| s.id),
+                }
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+                Ok(PlaybackResult {
+                    success: true,
+                    session: Some(PlaybackSession::from_record(session)),
+                    stream_id: Some(stream_id.to_string()),
+                    error: None,
+                })
+            }
             Err(e) => Ok(PlaybackResult {
                 success: false,
                 session: None,
+                stream_id: None,
                 error: Some(e.to_string()),
             }),
         }
@@ -522,6 +586,7 @@ impl PlaybackMutations {
 
     /// Update playback position/state
     /// Also persists watch progress to the watch_progress table for resume functionality
+    /// and publishes it as `watchStateChanged`
     async fn update_playback(
         &self,
         ctx: &Context<'_>,
@@ -582,12 +647,17 @@ impl PlaybackMutations {
                         };
 
                         match db.watch_progress().upsert_progress(wp_input).await {
-                            Ok(wp) => tracing::debug!(
-                                "Watch progress saved: content={}, progress={:.1}%, is_watched={}",
-                                content_id,
-                                wp.progress_percent * 100.0,
-                                wp.is_watched
-                            ),
+                            Ok(wp) => {
+                                tracing::debug!(
+                                    "Watch progress saved: content={}, progress={:.1}%, is_watched={}",
+                                    content_id,
+                                    wp.progress_percent * 100.0,
+                                    wp.is_watched
+                                );
+                                if let Some(state) = WatchState::from_record(wp) {
+                                    ctx.data_unchecked::<Arc<EventBus>>().publish(state);
+                                }
+                            }
                             Err(e) => tracing::warn!("Failed to persist watch progress: {}", e),
                         }
                     } else {
@@ -598,48 +668,167 @@ impl PlaybackMutations {
                 Ok(PlaybackResult {
                     success: true,
                     session: Some(PlaybackSession::from_record(session)),
+                    stream_id: None,
                     error: None,
                 })
             }
             Ok(None) => Ok(PlaybackResult {
                 success: false,
                 session: None,
+                stream_id: None,
                 error: Some("No active playback session".to_string()),
             }),
             Err(e) => Ok(PlaybackResult {
                 success: false,
                 session: None,
+                stream_id: None,
                 error: Some(e.to_string()),
             }),
         }
     }
 
-    /// Stop playback (mark session as completed)
-    async fn stop_playback(&self, ctx: &Context<'_>) -> Result<PlaybackResult> {
+    /// Stop playback (mark session as completed and release the stream lease)
+    async fn stop_playback(
+        &self,
+        ctx: &Context<'_>,
+        stream_id: Option<String>,
+    ) -> Result<PlaybackResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
         let user_id = Uuid::parse_str(&user.user_id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
 
+        if let Some(stream_id) = stream_id {
+            let id = Uuid::parse_str(&stream_id)
+                .map_err(|e| async_graphql::Error::new(format!("Invalid stream ID: {}", e)))?;
+            let owned = db
+                .playback_streams()
+                .get(id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                .is_some_and(|s| s.user_id == user_id);
+            if owned {
+                db.playback_streams()
+                    .end(id, "stopped")
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            }
+        }
+
         match db.playback().complete_session(user_id).await {
             Ok(Some(session)) => Ok(PlaybackResult {
                 success: true,
                 session: Some(PlaybackSession::from_record(session)),
+                stream_id: None,
                 error: None,
             }),
             Ok(None) => Ok(PlaybackResult {
                 success: true,
                 session: None,
+                stream_id: None,
                 error: None, // No active session is not an error
             }),
             Err(e) => Ok(PlaybackResult {
                 success: false,
                 session: None,
+                stream_id: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Keep a stream lease alive while playing
+    ///
+    /// Clients should call this more often than the heartbeat timeout. Once the
+    /// stream has ended (expired or stopped by an admin) `active` is false and
+    /// the client should stop playing.
+    async fn playback_heartbeat(
+        &self,
+        ctx: &Context<'_>,
+        stream_id: String,
+    ) -> Result<PlaybackHeartbeatResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let id = Uuid::parse_str(&stream_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid stream ID: {}", e)))?;
+
+        match db.playback_streams().heartbeat(id, user_id).await {
+            Ok(Some(stream)) => Ok(PlaybackHeartbeatResult {
+                success: true,
+                active: stream.ended_at.is_none(),
+                end_reason: stream.end_reason,
+                error: None,
+            }),
+            Ok(None) => Ok(PlaybackHeartbeatResult {
+                success: false,
+                active: false,
+                end_reason: None,
+                error: Some("Stream not found".to_string()),
+            }),
+            Err(e) => Ok(PlaybackHeartbeatResult {
+                success: false,
+                active: false,
+                end_reason: None,
                 error: Some(e.to_string()),
             }),
         }
     }
 
+    /// Stop another client's stream (admin only)
+    ///
+    /// The client is told to stop on its next heartbeat.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn stop_session(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let stream_id =
+            Uuid::parse_str(&id).map_err(|_| async_graphql::Error::new("Invalid stream ID"))?;
+
+        let stream = match db.playback_streams().get(stream_id).await {
+            Ok(Some(stream)) if stream.ended_at.is_none() => stream,
+            Ok(_) => {
+                return Ok(MutationResult {
+                    success: false,
+                    error: Some("Stream not found or already ended".to_string()),
+                });
+            }
+            Err(e) => {
+                return Ok(MutationResult {
+                    success: false,
+                    error: Some(e.to_string()),
+                });
+            }
+        };
+
+        if let Err(e) = db.playback_streams().end(stream_id, "terminated").await {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(e.to_string()),
+            });
+        }
+
+        if stream.playback_session_id.is_some() {
+            let paused = crate::db::UpdatePlaybackPosition {
+                is_playing: Some(false),
+                ..Default::default()
+            };
+            if let Err(e) = db.playback().update_position(stream.user_id, paused).await {
+                tracing::warn!("Failed to pause playback session for stopped stream: {}", e);
+            }
+        }
+
+        tracing::info!(
+            stream_id = %stream_id,
+            user = stream.username.as_deref().unwrap_or("unknown"),
+            "Admin stopped playback stream"
+        );
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
     /// Update playback settings
     async fn update_playback_settings(
         &self,
@@ -670,8 +859,49 @@ impl PlaybackMutations {
                 .map_err(|e| async_graphql::Error::new(e.to_string()))?;
         }
 
+        let mut max_streams = db
+            .settings()
+            .get_or_default::<i32>(streams::MAX_STREAMS_KEY, streams::DEFAULT_MAX_STREAMS)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if let Some(new_max) = input.max_concurrent_streams {
+            max_streams = new_max.clamp(0, 20);
+            db.settings()
+                .set_with_category(
+                    streams::MAX_STREAMS_KEY,
+                    max_streams,
+                    "playback",
+                    Some("Most streams a user may play at once (0 = unlimited)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+
+        let mut heartbeat_timeout = db
+            .settings()
+            .get_or_default::<i32>(
+                streams::HEARTBEAT_TIMEOUT_KEY,
+                streams::DEFAULT_HEARTBEAT_TIMEOUT_SECS,
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if let Some(new_timeout) = input.heartbeat_timeout_seconds {
+            heartbeat_timeout = new_timeout.clamp(streams::MIN_HEARTBEAT_TIMEOUT_SECS, 600);
+            db.settings()
+                .set_with_category(
+                    streams::HEARTBEAT_TIMEOUT_KEY,
+                    heartbeat_timeout,
+                    "playback",
+                    Some("Seconds without a heartbeat before a stream is considered abandoned"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+
         Ok(PlaybackSettings {
             sync_interval_seconds: sync_interval,
+            max_concurrent_streams: max_streams,
+            heartbeat_timeout_seconds: heartbeat_timeout,
         })
     }
 }
@@ -0,0 +1,228 @@
+use super::prelude::*;
+
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::services::AuditService;
+
+const LAST_USER_MANAGER: &str = "At least one active user must be able to manage users";
+
+#[derive(Default)]
+pub struct RoleMutations;
+
+#[Object]
+impl RoleMutations {
+    /// Create a custom role
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageUsers)")]
+    async fn create_role(&self, ctx: &Context<'_>, input: CreateRoleInput) -> Result<RoleResult> {
+        let db = ctx.data_unchecked::<Database>();
+
+        match db
+            .roles()
+            .create(CreateRole {
+                name: input.name.trim().to_string(),
+                description: input.description,
+                permissions: input.permissions.into_iter().map(Into::into).collect(),
+            })
+            .await
+        {
+            Ok(record) => Ok(RoleResult {
+                success: true,
+                role: Some(Role::from_record(record)),
+                error: None,
+            }),
+            Err(e) => Ok(RoleResult {
+                success: false,
+                role: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Update a custom role's description or permissions
+    ///
+    /// Users holding the role get the new permissions on their next request.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageUsers)")]
+    async fn update_role(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        input: UpdateRoleInput,
+    ) -> Result<RoleResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let roles = db.roles();
+
+        let Some(existing) = roles
+            .get(&name)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .filter(|role| !role.is_builtin)
+        else {
+            return Ok(RoleResult {
+                success: false,
+                role: None,
+                error: Some("Role not found or built in".to_string()),
+            });
+        };
+
+        let permissions: Option<Vec<Permission>> = input
+            .permissions
+            .map(|permissions| permissions.into_iter().map(Into::into).collect());
+        if let Some(ref permissions) = permissions
+            && existing.has(Permission::ManageUsers)
+            && !permissions.contains(&Permission::ManageUsers)
+            && roles
+                .count_user_managers_except(None, Some(&name))
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                == 0
+        {
+            return Ok(RoleResult {
+                success: false,
+                role: None,
+                error: Some(LAST_USER_MANAGER.to_string()),
+            });
+        }
+
+        let update = UpdateRole {
+            description: input.description.map(|d| Some(d).filter(|d| !d.is_empty())),
+            permissions,
+        };
+        match roles.update(&name, update).await {
+            Ok(Some(record)) => Ok(RoleResult {
+                success: true,
+                role: Some(Role::from_record(record)),
+                error: None,
+            }),
+            Ok(None) => Ok(RoleResult {
+                success: false,
+                role: None,
+                error: Some("Role not found or built in".to_string()),
+            }),
+            Err(e) => Ok(RoleResult {
+                success: false,
+                role: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Delete a custom role; its users go back to their built-in role
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageUsers)")]
+    async fn delete_role(&self, ctx: &Context<'_>, name: String) -> Result<MutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let roles = db.roles();
+
+        let Some(existing) = roles
+            .get(&name)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .filter(|role| !role.is_builtin)
+        else {
+            return Ok(MutationResult {
+                success: false,
+                error: Some("Role not found or built in".to_string()),
+            });
+        };
+
+        if existing.has(Permission::ManageUsers)
+            && roles
+                .count_user_managers_except(None, Some(&name))
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                == 0
+        {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(LAST_USER_MANAGER.to_string()),
+            });
+        }
+
+        match roles.delete(&name).await {
+            Ok(true) => Ok(MutationResult {
+                success: true,
+                error: None,
+            }),
+            Ok(false) => Ok(MutationResult {
+                success: false,
+                error: Some("Role not found or built in".to_string()),
+            }),
+            Err(e) => Ok(MutationResult {
+                success: false,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Assign a role to a user
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageUsers)")]
+    async fn assign_role(
+        &self,
+        ctx: &Context<'_>,
+        user_id: String,
+        role: String,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let roles = db.roles();
+        let actor_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let target_id = Uuid::parse_str(&user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let Some(role) = roles
+            .get(&role)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        else {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(format!("Role '{}' not found", role)),
+            });
+        };
+
+        let previous = roles
+            .get_for_user(target_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if previous
+            .as_ref()
+            .is_some_and(|previous| previous.has(Permission::ManageUsers))
+            && !role.has(Permission::ManageUsers)
+            && roles
+                .count_user_managers_except(Some(target_id), None)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                == 0
+        {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(LAST_USER_MANAGER.to_string()),
+            });
+        }
+
+        let assigned = roles
+            .assign(target_id, &role)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if !assigned {
+            return Ok(MutationResult {
+                success: false,
+                error: Some("User not found".to_string()),
+            });
+        }
+
+        ctx.data_unchecked::<Arc<AuditService>>()
+            .record_update(
+                actor_id,
+                "user",
+                target_id,
+                &serde_json::json!({ "role": previous.map(|r| r.name) }),
+                &serde_json::json!({ "role": role.name }),
+            )
+            .await;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+}
@@ -1,11 +1,15 @@
 use super::prelude::*;
 
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::services::AuditService;
+
 #[derive(Default)]
 pub struct RssFeedMutations;
 
 #[Object]
 impl RssFeedMutations {
     /// Create an RSS feed
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn create_rss_feed(
         &self,
         ctx: &Context<'_>,
@@ -16,6 +20,16 @@ impl RssFeedMutations {
         let user_id = Uuid::parse_str(&user.user_id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
 
+        let min_interval = input.min_poll_interval_minutes.unwrap_or(5);
+        let max_interval = input.max_poll_interval_minutes.unwrap_or(120);
+        if let Some(error) = poll_bounds_error(min_interval, max_interval) {
+            return Ok(RssFeedResult {
+                success: false,
+                rss_feed: None,
+                error: Some(error.to_string()),
+            });
+        }
+
         let record = db
             .rss_feeds()
             .create(CreateRssFeed {
@@ -25,10 +39,16 @@ impl RssFeedMutations {
                 url: input.url,
                 enabled: input.enabled.unwrap_or(true),
                 poll_interval_minutes: input.poll_interval_minutes.unwrap_or(15),
+                min_poll_interval_minutes: min_interval,
+                max_poll_interval_minutes: max_interval,
             })
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
+        ctx.data_unchecked::<Arc<AuditService>>()
+            .record_create(user_id, "rss_feed", record.id, &record)
+            .await;
+
         Ok(RssFeedResult {
             success: true,
             rss_feed: Some(RssFeed {
@@ -38,6 +58,12 @@ impl RssFeedMutations {
                 url: record.url,
                 enabled: record.enabled,
                 poll_interval_minutes: record.poll_interval_minutes,
+                min_poll_interval_minutes: record.min_poll_interval_minutes,
+                max_poll_interval_minutes: record.max_poll_interval_minutes,
+                current_poll_interval_minutes: record
+                    .current_poll_interval_minutes
+                    .unwrap_or(record.poll_interval_minutes),
+                ttl_minutes: record.ttl_minutes,
                 last_polled_at: record.last_polled_at.map(|t| t.to_rfc3339()),
                 last_successful_at: record.last_successful_at.map(|t| t.to_rfc3339()),
                 last_error: record.last_error,
@@ -48,17 +74,43 @@ impl RssFeedMutations {
     }
 
     /// Update an RSS feed
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn update_rss_feed(
         &self,
         ctx: &Context<'_>,
         id: String,
         input: UpdateRssFeedInput,
     ) -> Result<RssFeedResult> {
-        let _user = ctx.auth_user()?;
+        let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
         let feed_id = Uuid::parse_str(&id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid feed ID: {}", e)))?;
 
+        let existing = db
+            .rss_feeds()
+            .get_by_id(feed_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("RSS feed not found"))?;
+
+        if input.min_poll_interval_minutes.is_some() || input.max_poll_interval_minutes.is_some() {
+            let min_interval = input
+                .min_poll_interval_minutes
+                .unwrap_or(existing.min_poll_interval_minutes);
+            let max_interval = input
+                .max_poll_interval_minutes
+                .unwrap_or(existing.max_poll_interval_minutes);
+            if let Some(error) = poll_bounds_error(min_interval, max_interval) {
+                return Ok(RssFeedResult {
+                    success: false,
+                    rss_feed: None,
+                    error: Some(error.to_string()),
+                });
+            }
+        }
+
         let result = db
             .rss_feeds()
             .update(
@@ -69,12 +121,18 @@ impl RssFeedMutations {
                     url: input.url,
                     enabled: input.enabled,
                     poll_interval_minutes: input.poll_interval_minutes,
+                    min_poll_interval_minutes: input.min_poll_interval_minutes,
+                    max_poll_interval_minutes: input.max_poll_interval_minutes,
                 },
             )
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
         if let Some(record) = result {
+            ctx.data_unchecked::<Arc<AuditService>>()
+                .record_update(user_id, "rss_feed", feed_id, &existing, &record)
+                .await;
+
             Ok(RssFeedResult {
                 success: true,
                 rss_feed: Some(RssFeed {
@@ -84,6 +142,12 @@ impl RssFeedMutations {
                     url: record.url,
                     enabled: record.enabled,
                     poll_interval_minutes: record.poll_interval_minutes,
+                    min_poll_interval_minutes: record.min_poll_interval_minutes,
+                    max_poll_interval_minutes: record.max_poll_interval_minutes,
+                    current_poll_interval_minutes: record
+                        .current_poll_interval_minutes
+                        .unwrap_or(record.poll_interval_minutes),
+                    ttl_minutes: record.ttl_minutes,
                     last_polled_at: record.last_polled_at.map(|t| t.to_rfc3339()),
                     last_successful_at: record.last_successful_at.map(|t| t.to_rfc3339()),
                     last_error: record.last_error,
@@ -101,18 +165,33 @@ impl RssFeedMutations {
     }
 
     /// Delete an RSS feed
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn delete_rss_feed(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
-        let _user = ctx.auth_user()?;
+        let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
         let feed_id = Uuid::parse_str(&id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid feed ID: {}", e)))?;
 
+        let existing = db
+            .rss_feeds()
+            .get_by_id(feed_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
         let deleted = db
             .rss_feeds()
             .delete(feed_id)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
+        if deleted && let Some(existing) = &existing {
+            ctx.data_unchecked::<Arc<AuditService>>()
+                .record_delete(user_id, "rss_feed", feed_id, existing)
+                .await;
+        }
+
         Ok(MutationResult {
             success: deleted,
             error: if deleted {
@@ -124,13 +203,15 @@ impl RssFeedMutations {
     }
 
     /// Test an RSS feed by fetching and parsing its items (without storing)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn test_rss_feed(&self, ctx: &Context<'_>, url: String) -> Result<RssFeedTestResult> {
         let _user = ctx.auth_user()?;
 
         let rss_service = crate::services::RssService::new();
         match rss_service.fetch_feed(&url).await {
-            Ok(items) => {
-                let sample_items: Vec<RssItem> = items
+            Ok(feed) => {
+                let sample_items: Vec<RssItem> = feed
+                    .items
                     .into_iter()
                     .take(10)
                     .map(|item| RssItem {
@@ -163,6 +244,7 @@ impl RssFeedMutations {
     }
 
     /// Manually poll an RSS feed
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn poll_rss_feed(&self, ctx: &Context<'_>, id: String) -> Result<RssFeedResult> {
         let _user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -198,6 +280,12 @@ impl RssFeedMutations {
                         url: updated_feed.url,
                         enabled: updated_feed.enabled,
                         poll_interval_minutes: updated_feed.poll_interval_minutes,
+                        min_poll_interval_minutes: updated_feed.min_poll_interval_minutes,
+                        max_poll_interval_minutes: updated_feed.max_poll_interval_minutes,
+                        current_poll_interval_minutes: updated_feed
+                            .current_poll_interval_minutes
+                            .unwrap_or(updated_feed.poll_interval_minutes),
+                        ttl_minutes: updated_feed.ttl_minutes,
                         last_polled_at: updated_feed.last_polled_at.map(|t| t.to_rfc3339()),
                         last_successful_at: updated_feed.last_successful_at.map(|t| t.to_rfc3339()),
                         last_error: updated_feed.last_error,
@@ -222,3 +310,14 @@ impl RssFeedMutations {
         }
     }
 }
+
+/// Why a feed's poll interval bounds are invalid, if they are
+fn poll_bounds_error(min_minutes: i32, max_minutes: i32) -> Option<&'static str> {
+    if min_minutes < 1 {
+        Some("Minimum poll interval must be at least 1 minute")
+    } else if max_minutes < min_minutes {
+        Some("Maximum poll interval must not be below the minimum")
+    } else {
+        None
+    }
+}
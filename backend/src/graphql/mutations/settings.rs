@@ -1,5 +1,7 @@
 use super::prelude::*;
 
+use crate::graphql::auth::{Permission, PermissionGuard};
+
 #[derive(Default)]
 pub struct SettingsMutations;
 
@@ -492,4 +494,219 @@ impl SettingsMutations {
             error: result.error,
         })
     }
+
+    /// Update upcoming episode air notification settings
+    async fn update_episode_air_notification_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateEpisodeAirNotificationSettingsInput,
+    ) -> Result<SettingsResult> {
+        use crate::jobs::air_notifications::{DIGEST_THRESHOLD_KEY, LEAD_HOURS_KEY, MAX_LEAD_HOURS};
+
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let settings = db.settings();
+
+        if let Some(v) = input.lead_hours {
+            if !(0..=MAX_LEAD_HOURS).contains(&v) {
+                return Ok(SettingsResult {
+                    success: false,
+                    error: Some(format!("Lead time must be between 0 and {} hours", MAX_LEAD_HOURS)),
+                });
+            }
+            settings
+                .set_with_category(
+                    LEAD_HOURS_KEY,
+                    v,
+                    "notifications",
+                    Some("Hours before an episode airs to send a notification (0 = disabled)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+        if let Some(v) = input.digest_threshold {
+            settings
+                .set_with_category(
+                    DIGEST_THRESHOLD_KEY,
+                    v.max(0),
+                    "notifications",
+                    Some("Same-night episode count that is coalesced into a digest (0 = never)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+
+        Ok(SettingsResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Set the download speed limits and the time windows that override them
+    ///
+    /// Takes effect immediately; the scheduler re-applies the limits as
+    /// windows start and end.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn set_download_schedule(
+        &self,
+        ctx: &Context<'_>,
+        input: SetDownloadScheduleInput,
+    ) -> Result<SettingsResult> {
+        use crate::services::bandwidth::{
+            BandwidthManager, BandwidthWindow, SCHEDULE_KEY, TORRENT_DOWNLOAD_LIMIT_KEY,
+            TORRENT_UPLOAD_LIMIT_KEY, USENET_DOWNLOAD_LIMIT_KEY, parse_time,
+        };
+
+        let db = ctx.data_unchecked::<Database>();
+        let torrent_service = ctx.data_unchecked::<Arc<TorrentService>>();
+        let settings = db.settings();
+
+        let limits = [
+            input.torrent_download_limit,
+            input.torrent_upload_limit,
+            input.usenet_download_limit,
+        ];
+        let window_limits = input.windows.iter().flat_map(|w| {
+            [
+                w.torrent_download_limit,
+                w.torrent_upload_limit,
+                w.usenet_download_limit,
+            ]
+        });
+        if limits
+            .into_iter()
+            .chain(window_limits)
+            .flatten()
+            .any(|v| v < 0)
+        {
+            return Ok(SettingsResult {
+                success: false,
+                error: Some("Speed limits can't be negative".to_string()),
+            });
+        }
+
+        let mut windows = Vec::with_capacity(input.windows.len());
+        for window in input.windows {
+            let (start, end) = match (parse_time(&window.start), parse_time(&window.end)) {
+                (Ok(start), Ok(end)) => (start, end),
+                (Err(e), _) | (_, Err(e)) => {
+                    return Ok(SettingsResult {
+                        success: false,
+                        error: Some(e.to_string()),
+                    });
+                }
+            };
+            windows.push(BandwidthWindow {
+                days: window.days.into_iter().map(Into::into).collect(),
+                start,
+                end,
+                torrent_download: window.torrent_download_limit.map(|v| v as u64),
+                torrent_upload: window.torrent_upload_limit.map(|v| v as u64),
+                usenet_download: window.usenet_download_limit.map(|v| v as u64),
+            });
+        }
+
+        let default_keys = [
+            (TORRENT_DOWNLOAD_LIMIT_KEY, "torrent"),
+            (TORRENT_UPLOAD_LIMIT_KEY, "torrent"),
+            (USENET_DOWNLOAD_LIMIT_KEY, "usenet"),
+        ];
+        for ((key, category), value) in default_keys.into_iter().zip(limits) {
+            if let Some(v) = value {
+                settings
+                    .set_with_category(key, v, category, None)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            }
+        }
+        settings
+            .set_with_category(
+                SCHEDULE_KEY,
+                windows,
+                "downloads",
+                Some("Time windows that override the download speed limits"),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        BandwidthManager::global()
+            .apply(db, torrent_service)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(SettingsResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Update the ownership and mode applied to imported files (admin only)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn update_import_permission_settings(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateImportPermissionSettingsInput,
+    ) -> Result<SettingsResult> {
+        use crate::services::file_permissions::{
+            FILE_GID_KEY, FILE_MODE_KEY, FILE_UID_KEY, parse_mode,
+        };
+
+        let db = ctx.data_unchecked::<Database>();
+        let settings = db.settings();
+
+        let file_mode = match input.file_mode {
+            Some(mode) => match mode.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+                Some(mode) => match parse_mode(mode) {
+                    Ok(mode) => Some(Some(format!("{:o}", mode))),
+                    Err(e) => {
+                        return Ok(SettingsResult {
+                            success: false,
+                            error: Some(e.to_string()),
+                        });
+                    }
+                },
+                None => Some(None),
+            },
+            None => None,
+        };
+
+        if let Some(v) = input.file_uid {
+            settings
+                .set_with_category(
+                    FILE_UID_KEY,
+                    v,
+                    "import",
+                    Some("Owner uid for imported files (null = PUID or unchanged)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+        if let Some(v) = input.file_gid {
+            settings
+                .set_with_category(
+                    FILE_GID_KEY,
+                    v,
+                    "import",
+                    Some("Group gid for imported files (null = PGID or unchanged)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+        if let Some(v) = file_mode {
+            settings
+                .set_with_category(
+                    FILE_MODE_KEY,
+                    v,
+                    "import",
+                    Some("Octal mode for imported files (null = from UMASK or unchanged)"),
+                )
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        }
+
+        Ok(SettingsResult {
+            success: true,
+            error: None,
+        })
+    }
 }
@@ -0,0 +1,158 @@
+use super::prelude::*;
+
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::jobs::update_check;
+use crate::services::NotificationService;
+use crate::services::self_update;
+use crate::services::transcode_cache::{MAX_SIZE_GB_KEY, TranscodeCache, max_bytes_from_gb};
+
+#[derive(Default)]
+pub struct SystemMutations;
+
+#[Object]
+impl SystemMutations {
+    /// Remove all transcoded segments from the cache (admin only)
+    ///
+    /// Sessions that are currently being played are kept.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn clear_transcode_cache(&self, ctx: &Context<'_>) -> Result<ClearTranscodeCacheResult> {
+        let cache = ctx.data_unchecked::<Arc<TranscodeCache>>();
+
+        match cache.clear().await {
+            Ok(result) => Ok(ClearTranscodeCacheResult {
+                success: true,
+                sessions_removed: result.sessions_removed as i32,
+                bytes_freed: result.bytes_freed as i64,
+                sessions_skipped: result.sessions_skipped as i32,
+                error: None,
+            }),
+            Err(e) => Ok(ClearTranscodeCacheResult {
+                success: false,
+                sessions_removed: 0,
+                bytes_freed: 0,
+                sessions_skipped: 0,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Set the transcode cache size cap in GB (0 = unlimited, admin only)
+    ///
+    /// The cache is trimmed to the new cap right away.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn update_transcode_cache_settings(
+        &self,
+        ctx: &Context<'_>,
+        max_size_gb: f64,
+    ) -> Result<MutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let cache = ctx.data_unchecked::<Arc<TranscodeCache>>();
+
+        if !max_size_gb.is_finite() || max_size_gb < 0.0 {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(
+                    "Cache size must be zero (unlimited) or a positive number of GB".to_string(),
+                ),
+            });
+        }
+
+        db.settings()
+            .set_with_category(
+                MAX_SIZE_GB_KEY,
+                max_size_gb,
+                "transcode",
+                Some("Transcode cache size cap in GB (0 = unlimited)"),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        if let Err(e) = cache.evict(max_bytes_from_gb(max_size_gb)).await {
+            tracing::warn!(error = %e, "Failed to trim transcode cache after settings change");
+        }
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Turn the scheduled update check on or off (admin only)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn update_update_check_settings(
+        &self,
+        ctx: &Context<'_>,
+        enabled: bool,
+    ) -> Result<MutationResult> {
+        let db = ctx.data_unchecked::<Database>();
+
+        db.settings()
+            .set_with_category(
+                update_check::CHECK_ENABLED_KEY,
+                enabled,
+                "updates",
+                Some("Check GitHub for new Librarian releases every six hours"),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
+    /// Check GitHub for a newer release now (admin only)
+    ///
+    /// Works even when the scheduled check is off. A new release is announced
+    /// the same way as from the scheduled check.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn check_for_updates(&self, ctx: &Context<'_>) -> Result<CheckForUpdatesResult> {
+        let db = ctx.data_unchecked::<Database>();
+        let notifications = ctx.data_unchecked::<Arc<NotificationService>>();
+
+        match update_check::check_for_updates(db, notifications).await {
+            Ok(release) => Ok(CheckForUpdatesResult {
+                success: true,
+                update: release.map(UpdateInfo::from),
+                error: None,
+            }),
+            Err(e) => Ok(CheckForUpdatesResult {
+                success: false,
+                update: None,
+                error: Some(e.to_string()),
+            }),
+        }
+    }
+
+    /// Download and install the latest release, then restart (admin only)
+    ///
+    /// For bare-metal installs; Docker users pull a new image instead. The
+    /// download is checked against the release's SHA256SUMS and the database
+    /// is snapshotted first. If the new version doesn't report ready after
+    /// restarting, the old binary and database are put back.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn apply_update(&self, ctx: &Context<'_>) -> Result<ApplyUpdateResult> {
+        let db = ctx.data_unchecked::<Database>();
+
+        match self_update::apply_update(db).await {
+            Ok(release) => {
+                tokio::spawn(async {
+                    // Give the response time to reach the client first
+                    tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+                    self_update::restart();
+                });
+                Ok(ApplyUpdateResult {
+                    success: true,
+                    version: Some(release.version),
+                    error: None,
+                })
+            }
+            Err(e) => Ok(ApplyUpdateResult {
+                success: false,
+                version: None,
+                error: Some(format!("{:#}", e)),
+            }),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use super::prelude::*;
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource, info_hash_from_magnet};
 
 #[derive(Default)]
 pub struct TorrentMutations;
@@ -6,6 +8,7 @@ pub struct TorrentMutations;
 #[Object]
 impl TorrentMutations {
     /// Add a new torrent from magnet link or URL to a .torrent file
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn add_torrent(
         &self,
         ctx: &Context<'_>,
@@ -36,6 +39,40 @@ impl TorrentMutations {
             .as_ref()
             .and_then(|id| Uuid::parse_str(id).ok());
 
+        // Skip releases already grabbed by auto-hunt or an earlier manual grab
+        let claim_id = match user_id {
+            Some(user_id) => {
+                let request = GrabRequest {
+                    user_id,
+                    source: GrabSource::Manual,
+                    download_type: "torrent",
+                    title: input.name.clone(),
+                    info_hash: input.magnet.as_deref().and_then(info_hash_from_magnet),
+                    guid: None,
+                    indexer_id: input
+                        .indexer_id
+                        .as_deref()
+                        .and_then(|id| Uuid::parse_str(id).ok()),
+                    force: input.force.unwrap_or(false),
+                };
+                match grab_ledger::claim(db, request).await {
+                    Ok(Claim::Claimed(id)) => Some(id),
+                    Ok(Claim::Duplicate(earlier)) => {
+                        return Ok(AddTorrentResult {
+                            success: false,
+                            torrent: None,
+                            error: Some(grab_ledger::duplicate_message(earlier.as_ref())),
+                        });
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to check grab ledger");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
         let result = if let Some(magnet) = input.magnet {
             // Magnet links go through add_magnet
             service.add_magnet(&magnet, user_id).await
@@ -89,6 +126,10 @@ impl TorrentMutations {
                                                         torrent_name = %info.name,
                                                         "User added torrent from authenticated download"
                                                     );
+                                                    service
+                                                        .set_source_indexer(&info.info_hash, indexer_id_str)
+                                                        .await;
+                                                    finish_manual_grab(db, claim_id, Some(&info.info_hash)).await;
 
                                                     // Create file-level matches if a target item is specified
                                                     create_file_matches_for_target(
@@ -113,6 +154,7 @@ impl TorrentMutations {
                                                         bytes_len = bytes.len(),
                                                         "Failed to add torrent from downloaded bytes"
                                                     );
+                                                    finish_manual_grab(db, claim_id, None).await;
                                                     return Ok(AddTorrentResult {
                                                         success: false,
                                                         torrent: None,
@@ -140,6 +182,7 @@ impl TorrentMutations {
             // Fall back to unauthenticated download
             service.add_torrent_url(&url, user_id).await
         } else {
+            finish_manual_grab(db, claim_id, None).await;
             return Ok(AddTorrentResult {
                 success: false,
                 torrent: None,
@@ -158,6 +201,11 @@ impl TorrentMutations {
                     info.name
                 );
 
+                if let Some(ref indexer_id) = input.indexer_id {
+                    service.set_source_indexer(&info.info_hash, indexer_id).await;
+                }
+                finish_manual_grab(db, claim_id, Some(&info.info_hash)).await;
+
                 // Create file-level matches if a target item is specified
                 create_file_matches_for_target(db, &info, album_id, movie_id, episode_id).await;
 
@@ -173,6 +221,7 @@ impl TorrentMutations {
                     error = %e,
                     "User failed to add torrent"
                 );
+                finish_manual_grab(db, claim_id, None).await;
                 Ok(AddTorrentResult {
                     success: false,
                     torrent: None,
@@ -183,6 +232,7 @@ impl TorrentMutations {
     }
 
     /// Pause a torrent
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn pause_torrent(&self, ctx: &Context<'_>, id: i32) -> Result<TorrentActionResult> {
         let _user = ctx.auth_user()?;
         let service = ctx.data_unchecked::<Arc<TorrentService>>();
@@ -200,6 +250,7 @@ impl TorrentMutations {
     }
 
     /// Resume a paused torrent
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn resume_torrent(&self, ctx: &Context<'_>, id: i32) -> Result<TorrentActionResult> {
         let _user = ctx.auth_user()?;
         let service = ctx.data_unchecked::<Arc<TorrentService>>();
@@ -217,6 +268,7 @@ impl TorrentMutations {
     }
 
     /// Remove a torrent
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn remove_torrent(
         &self,
         ctx: &Context<'_>,
@@ -251,6 +303,7 @@ impl TorrentMutations {
     /// If library_id is provided, the torrent will be linked to that library first.
     /// If album_id is provided for music, files will be matched to that album's tracks.
     /// Process pending file matches for a torrent (copy files to library)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn organize_torrent(
         &self,
         ctx: &Context<'_>,
@@ -363,6 +416,7 @@ impl TorrentMutations {
     }
 
     /// Update torrent client settings
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn update_torrent_settings(
         &self,
         ctx: &Context<'_>,
@@ -416,6 +470,13 @@ impl TorrentMutations {
                 .map_err(|e| async_graphql::Error::new(e.to_string()))?;
         }
 
+        // Speed limits take effect straight away
+        let torrent_service = ctx.data_unchecked::<Arc<TorrentService>>();
+        crate::services::bandwidth::BandwidthManager::global()
+            .apply(db, torrent_service)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
         Ok(SettingsResult {
             success: true,
             error: None,
@@ -433,6 +494,7 @@ impl TorrentMutations {
     /// The source_id can be either:
     /// - A UUID (database ID)
     /// - An info_hash (for torrents) - will be looked up to get the database ID
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn rematch_source(
         &self,
         ctx: &Context<'_>,
@@ -528,6 +590,7 @@ impl TorrentMutations {
     /// The source_id can be either:
     /// - A UUID (database ID)
     /// - An info_hash (for torrents) - will be looked up to get the database ID
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn process_source(
         &self,
         ctx: &Context<'_>,
@@ -585,6 +648,7 @@ impl TorrentMutations {
     ///
     /// Use this when you have a completed torrent and want to add its content
     /// as a new item in your library (vs linking to an existing item).
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn import_to_library(
         &self,
         ctx: &Context<'_>,
@@ -752,6 +816,7 @@ impl TorrentMutations {
     }
 
     /// Manually set a match target for a pending file match
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn set_match(
         &self,
         ctx: &Context<'_>,
@@ -789,6 +854,7 @@ impl TorrentMutations {
     }
 
     /// Remove a pending file match
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn remove_match(
         &self,
         ctx: &Context<'_>,
@@ -873,6 +939,22 @@ async fn copy_dir_recursive(
     Ok(count)
 }
 
+/// Record the info hash of a started manual grab, or drop its claim if the
+/// torrent wasn't added
+async fn finish_manual_grab(db: &Database, claim_id: Option<Uuid>, info_hash: Option<&str>) {
+    let Some(claim_id) = claim_id else {
+        return;
+    };
+
+    let recorded = match info_hash {
+        Some(info_hash) => grab_ledger::confirm(db, claim_id, info_hash).await,
+        None => grab_ledger::abandon(db, claim_id).await,
+    };
+    if let Err(e) = recorded {
+        tracing::warn!(error = %e, "Failed to update grab ledger");
+    }
+}
+
 /// Create file-level matches for a torrent if a target item (album, movie, or episode) is specified
 /// Create file-level matches for explicit target selection (album, movie, episode)
 ///
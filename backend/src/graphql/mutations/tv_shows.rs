@@ -1,21 +1,18 @@
 use super::prelude::*;
-use tokio::sync::broadcast;
 
 #[derive(Default)]
 pub struct TvShowMutations;
 
 /// Helper to broadcast library change events
 async fn broadcast_library_changed(ctx: &Context<'_>, library_id: Uuid) {
-    if let Ok(tx) = ctx.data::<broadcast::Sender<LibraryChangedEvent>>() {
-        let db = ctx.data_unchecked::<Database>();
-        if let Ok(Some(lib)) = db.libraries().get_by_id(library_id).await {
-            let _ = tx.send(LibraryChangedEvent {
-                change_type: LibraryChangeType::Updated,
-                library_id: library_id.to_string(),
-                library_name: Some(lib.name.clone()),
-                library: Some(Library::from_db(lib)),
-            });
-        }
+    let db = ctx.data_unchecked::<Database>();
+    if let Ok(Some(lib)) = db.libraries().get_by_id(library_id).await {
+        ctx.data_unchecked::<Arc<EventBus>>().publish(LibraryChangedEvent {
+            change_type: LibraryChangeType::Updated,
+            library_id: library_id.to_string(),
+            library_name: Some(lib.name.clone()),
+            library: Some(Library::from_db(lib)),
+        });
     }
 }
 
@@ -129,6 +126,14 @@ impl TvShowMutations {
             });
         }
 
+        // Providers that failed are retried by the metadata backfill job
+        let missing_metadata = ctx
+            .data_unchecked::<Database>()
+            .tv_shows()
+            .get_missing_metadata(record.id)
+            .await
+            .unwrap_or_default();
+
         Ok(TvShowResult {
             success: true,
             tv_show: Some(TvShow {
@@ -178,6 +183,7 @@ impl TvShowMutations {
                 release_group_whitelist_override: record.release_group_whitelist_override,
             }),
             error: None,
+            missing_metadata,
         })
     }
 
@@ -280,12 +286,14 @@ impl TvShowMutations {
                     release_group_whitelist_override: record.release_group_whitelist_override,
                 }),
                 error: None,
+                missing_metadata: Vec::new(),
             })
         } else {
             Ok(TvShowResult {
                 success: false,
                 tv_show: None,
                 error: Some("Show not found".to_string()),
+                missing_metadata: Vec::new(),
             })
         }
     }
@@ -323,6 +331,64 @@ impl TvShowMutations {
         })
     }
 
+    /// Set the episode air notification lead time for a TV show
+    ///
+    /// Pass null to clear the override and inherit the global setting; 0 disables
+    /// air notifications for the show.
+    async fn set_show_air_notification_lead_time(
+        &self,
+        ctx: &Context<'_>,
+        show_id: String,
+        lead_hours: Option<i32>,
+    ) -> Result<MutationResult> {
+        use crate::jobs::air_notifications::MAX_LEAD_HOURS;
+
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let show_id = Uuid::parse_str(&show_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid show ID: {}", e)))?;
+
+        if db
+            .tv_shows()
+            .get_by_id(show_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .is_none()
+        {
+            return Ok(MutationResult {
+                success: false,
+                error: Some("Show not found".to_string()),
+            });
+        }
+
+        let repo = db.air_notifications();
+        match lead_hours {
+            Some(hours) if !(0..=MAX_LEAD_HOURS).contains(&hours) => {
+                return Ok(MutationResult {
+                    success: false,
+                    error: Some(format!(
+                        "Lead time must be between 0 and {} hours",
+                        MAX_LEAD_HOURS
+                    )),
+                });
+            }
+            Some(hours) => repo
+                .set_override(show_id, hours)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?,
+            None => {
+                repo.clear_override(show_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            }
+        }
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
     /// Refresh metadata for a TV show
     async fn refresh_tv_show(&self, ctx: &Context<'_>, id: String) -> Result<TvShowResult> {
         let _user = ctx.auth_user()?;
@@ -351,12 +417,14 @@ impl TvShowMutations {
                 success: false,
                 tv_show: None,
                 error: Some("No provider ID found for show".to_string()),
+                missing_metadata: Vec::new(),
             });
         };
 
-        // Fetch fresh show details (including updated artwork)
-        let show_details = metadata
-            .get_show(provider, provider_id)
+        // Fetch fresh show details (including updated artwork) from the
+        // provider chain
+        let (show_details, _) = metadata
+            .get_show_chained(provider, provider_id)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
@@ -416,6 +484,12 @@ impl TvShowMutations {
             )
             .await;
 
+        if let Some(tmdb_id) = show_details.tmdb_id.or(show.tmdb_id.map(|id| id as u32))
+            && let Err(e) = metadata.sync_show_credits(show_id, tmdb_id).await
+        {
+            tracing::warn!(show_id = %show_id, error = %e, "Failed to save show credits");
+        }
+
         // Fetch fresh episodes
         let episodes = metadata
             .get_episodes(provider, provider_id)
@@ -453,6 +527,12 @@ impl TvShowMutations {
         // Get updated show
         let updated_show = db.tv_shows().get_by_id(show_id).await.ok().flatten();
 
+        let missing_metadata = db
+            .tv_shows()
+            .get_missing_metadata(show_id)
+            .await
+            .unwrap_or_default();
+
         Ok(TvShowResult {
             success: true,
             tv_show: updated_show.map(|record| TvShow {
@@ -502,6 +582,7 @@ impl TvShowMutations {
                 release_group_whitelist_override: record.release_group_whitelist_override,
             }),
             error: None,
+            missing_metadata,
         })
     }
 }
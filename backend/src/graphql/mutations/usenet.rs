@@ -1,7 +1,12 @@
 use super::prelude::*;
+use crate::api::sabnzbd::hash_api_key;
 use crate::db::{CreateUsenetServer, UpdateUsenetServer};
+use crate::graphql::auth::{Permission, PermissionGuard};
 use crate::indexer::encryption::CredentialEncryption;
-use crate::services::usenet::{UsenetService, UsenetServiceConfig};
+use crate::jobs::nzb_watch_folder::{WATCH_FOLDER_KEY, WatchFolder};
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource};
+use crate::services::usenet::UsenetService;
+use crate::usenet::nzb::NzbFile;
 
 #[derive(Default)]
 pub struct UsenetMutations;
@@ -9,6 +14,7 @@ pub struct UsenetMutations;
 #[Object]
 impl UsenetMutations {
     /// Create a new usenet server
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn create_usenet_server(
         &self,
         ctx: &Context<'_>,
@@ -48,6 +54,7 @@ impl UsenetMutations {
                 password_nonce,
                 connections: input.connections.unwrap_or(10),
                 priority: input.priority.unwrap_or(0),
+                is_backup: input.is_backup.unwrap_or(false),
                 retention_days: input.retention_days,
             })
             .await
@@ -61,6 +68,7 @@ impl UsenetMutations {
     }
 
     /// Update a usenet server
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn update_usenet_server(
         &self,
         ctx: &Context<'_>,
@@ -116,6 +124,7 @@ impl UsenetMutations {
                     connections: input.connections,
                     priority: input.priority,
                     enabled: input.enabled,
+                    is_backup: input.is_backup,
                     retention_days: input.retention_days,
                 },
             )
@@ -130,6 +139,7 @@ impl UsenetMutations {
     }
 
     /// Delete a usenet server
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn delete_usenet_server(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -162,6 +172,7 @@ impl UsenetMutations {
     }
 
     /// Reorder usenet servers
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn reorder_usenet_servers(
         &self,
         ctx: &Context<'_>,
@@ -189,6 +200,7 @@ impl UsenetMutations {
     }
 
     /// Add a usenet download from URL
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn add_usenet_download(
         &self,
         ctx: &Context<'_>,
@@ -199,12 +211,7 @@ impl UsenetMutations {
         let user_id = Uuid::parse_str(&user.user_id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
 
-        // TODO: Get UsenetService from context
-        // For now, create a temporary one
-        let usenet_service = UsenetService::new(
-            db.clone(),
-            UsenetServiceConfig::default(),
-        );
+        let usenet_service = ctx.data_unchecked::<Arc<UsenetService>>();
 
         let library_id = input.library_id.as_ref().and_then(|id| Uuid::parse_str(id).ok());
         let episode_id = input.episode_id.as_ref().and_then(|id| Uuid::parse_str(id).ok());
@@ -213,9 +220,35 @@ impl UsenetMutations {
         let audiobook_id = input.audiobook_id.as_ref().and_then(|id| Uuid::parse_str(id).ok());
         let indexer_id = input.indexer_id.as_ref().and_then(|id| Uuid::parse_str(id).ok());
 
-        let info = usenet_service
+        // Skip releases already grabbed by another pipeline
+        let request = GrabRequest {
+            user_id,
+            source: GrabSource::Manual,
+            download_type: "usenet",
+            title: input.title.clone(),
+            info_hash: None,
+            guid: input.guid.clone(),
+            indexer_id,
+            force: input.force.unwrap_or(false),
+        };
+        let claim_id = match grab_ledger::claim(db, request)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        {
+            Claim::Claimed(id) => id,
+            Claim::Duplicate(earlier) => {
+                return Ok(UsenetDownloadResult {
+                    success: false,
+                    error: Some(grab_ledger::duplicate_message(earlier.as_ref())),
+                    download: None,
+                });
+            }
+        };
+
+        let result = usenet_service
             .add_nzb_url(
                 &input.nzb_url,
+                None,
                 user_id,
                 library_id,
                 episode_id,
@@ -224,8 +257,13 @@ impl UsenetMutations {
                 audiobook_id,
                 indexer_id,
             )
-            .await
-            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            .await;
+        if result.is_err()
+            && let Err(e) = grab_ledger::abandon(db, claim_id).await
+        {
+            tracing::warn!(error = %e, "Failed to drop grab claim");
+        }
+        let info = result.map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
         Ok(UsenetDownloadResult {
             success: true,
@@ -245,11 +283,185 @@ impl UsenetMutations {
                 movie_id: info.movie_id.map(|id| id.to_string()),
                 album_id: info.album_id.map(|id| id.to_string()),
                 audiobook_id: info.audiobook_id.map(|id| id.to_string()),
+                repair_status: None,
+                repair_blocks_needed: None,
+                repair_recovery_blocks: None,
             }),
         })
     }
 
+    /// Add a usenet download from an NZB document or URL
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn add_nzb(&self, ctx: &Context<'_>, input: AddNzbInput) -> Result<UsenetDownloadResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let (nzb, url) = match (input.nzb.as_deref(), input.url.as_deref()) {
+            (Some(nzb), None) => (Some(nzb), None),
+            (None, Some(url)) => (None, Some(url)),
+            _ => return Err(async_graphql::Error::new("Provide either nzb or url")),
+        };
+        let parsed = nzb
+            .map(NzbFile::parse_str)
+            .transpose()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid NZB: {}", e)))?;
+        let name = input
+            .name
+            .clone()
+            .or_else(|| parsed.and_then(|nzb| nzb.metadata.title))
+            .filter(|name| !name.trim().is_empty());
+
+        let library_id = input
+            .library_id
+            .as_ref()
+            .and_then(|id| Uuid::parse_str(id).ok());
+        let episode_id = input
+            .episode_id
+            .as_ref()
+            .and_then(|id| Uuid::parse_str(id).ok());
+        let movie_id = input
+            .movie_id
+            .as_ref()
+            .and_then(|id| Uuid::parse_str(id).ok());
+        let album_id = input
+            .album_id
+            .as_ref()
+            .and_then(|id| Uuid::parse_str(id).ok());
+        let audiobook_id = input
+            .audiobook_id
+            .as_ref()
+            .and_then(|id| Uuid::parse_str(id).ok());
+
+        // Skip releases already grabbed by another pipeline
+        let request = GrabRequest {
+            user_id,
+            source: GrabSource::Manual,
+            download_type: "usenet",
+            title: name.clone(),
+            info_hash: None,
+            guid: None,
+            indexer_id: None,
+            force: input.force.unwrap_or(false),
+        };
+        let claim_id = match grab_ledger::claim(db, request)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        {
+            Claim::Claimed(id) => id,
+            Claim::Duplicate(earlier) => {
+                return Ok(UsenetDownloadResult {
+                    success: false,
+                    error: Some(grab_ledger::duplicate_message(earlier.as_ref())),
+                    download: None,
+                });
+            }
+        };
+
+        let usenet_service = ctx.data_unchecked::<Arc<UsenetService>>();
+        let result = match (nzb, url) {
+            (Some(nzb), _) => {
+                usenet_service
+                    .add_nzb(
+                        nzb.as_bytes(),
+                        name.unwrap_or_else(|| "Uploaded NZB".to_string()),
+                        user_id,
+                        library_id,
+                        episode_id,
+                        movie_id,
+                        album_id,
+                        audiobook_id,
+                        None,
+                    )
+                    .await
+            }
+            (_, url) => {
+                usenet_service
+                    .add_nzb_url(
+                        url.unwrap_or_default(),
+                        name.as_deref(),
+                        user_id,
+                        library_id,
+                        episode_id,
+                        movie_id,
+                        album_id,
+                        audiobook_id,
+                        None,
+                    )
+                    .await
+            }
+        };
+        if result.is_err()
+            && let Err(e) = grab_ledger::abandon(db, claim_id).await
+        {
+            tracing::warn!(error = %e, "Failed to drop grab claim");
+        }
+        let info = result.map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let record = db
+            .usenet_downloads()
+            .get(info.id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(UsenetDownloadResult {
+            success: true,
+            error: None,
+            download: record.map(UsenetDownload::from),
+        })
+    }
+
+    /// Set the folder polled for NZB files, or turn it off with null
+    ///
+    /// Downloads from the folder belong to the user who set it.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn set_nzb_watch_folder(
+        &self,
+        ctx: &Context<'_>,
+        path: Option<String>,
+    ) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let Some(path) = path.map(|p| p.trim().to_string()).filter(|p| !p.is_empty()) else {
+            db.settings()
+                .delete(WATCH_FOLDER_KEY)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+            return Ok(MutationResult {
+                success: true,
+                error: None,
+            });
+        };
+
+        if !std::path::Path::new(&path).is_dir() {
+            return Ok(MutationResult {
+                success: false,
+                error: Some(format!("'{}' is not a folder", path)),
+            });
+        }
+
+        db.settings()
+            .set_with_category(
+                WATCH_FOLDER_KEY,
+                WatchFolder { path, user_id },
+                "usenet",
+                Some("Folder polled for NZB files to download"),
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: true,
+            error: None,
+        })
+    }
+
     /// Pause a usenet download
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn pause_usenet_download(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -269,7 +481,7 @@ impl UsenetMutations {
             return Err(async_graphql::Error::new("Not authorized"));
         }
 
-        db.usenet_downloads()
+        ctx.data_unchecked::<Arc<UsenetService>>()
             .pause(download_id)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
@@ -281,6 +493,7 @@ impl UsenetMutations {
     }
 
     /// Resume a paused usenet download
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn resume_usenet_download(&self, ctx: &Context<'_>, id: String) -> Result<MutationResult> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
@@ -300,7 +513,7 @@ impl UsenetMutations {
             return Err(async_graphql::Error::new("Not authorized"));
         }
 
-        db.usenet_downloads()
+        ctx.data_unchecked::<Arc<UsenetService>>()
             .resume(download_id)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
@@ -312,6 +525,7 @@ impl UsenetMutations {
     }
 
     /// Remove a usenet download
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
     async fn remove_usenet_download(
         &self,
         ctx: &Context<'_>,
@@ -336,22 +550,65 @@ impl UsenetMutations {
             return Err(async_graphql::Error::new("Not authorized"));
         }
 
-        // Remove from database (soft delete)
-        db.usenet_downloads()
-            .remove(download_id)
+        // Cancels it if still downloading, then soft deletes it
+        ctx.data_unchecked::<Arc<UsenetService>>()
+            .remove(download_id, delete_files.unwrap_or(false))
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
-        // Delete files if requested
-        if delete_files.unwrap_or(false) {
-            if let Some(path) = existing.download_path {
-                let _ = std::fs::remove_dir_all(&path);
-            }
-        }
-
         Ok(MutationResult {
             success: true,
             error: None,
         })
     }
+
+    /// Generate an API key for the SABnzbd-compatible API
+    ///
+    /// Replaces the user's previous key. The key is only returned here.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn generate_sabnzbd_api_key(&self, ctx: &Context<'_>) -> Result<SabnzbdApiKey> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        // 32 hex characters, like SABnzbd's own keys
+        let key = Uuid::new_v4().simple().to_string();
+        db.sabnzbd()
+            .replace_key(user_id, &hash_api_key(&key))
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let record = db
+            .sabnzbd()
+            .get_key(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Failed to generate API key"))?;
+
+        Ok(SabnzbdApiKey {
+            key: Some(key),
+            ..SabnzbdApiKey::from(record)
+        })
+    }
+
+    /// Revoke the current user's SABnzbd API key
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageDownloads)")]
+    async fn revoke_sabnzbd_api_key(&self, ctx: &Context<'_>) -> Result<MutationResult> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let revoked = db
+            .sabnzbd()
+            .delete_key(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(MutationResult {
+            success: revoked,
+            error: (!revoked).then(|| "No API key to revoke".to_string()),
+        })
+    }
 }
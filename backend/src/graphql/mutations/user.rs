@@ -6,16 +6,54 @@ pub struct UserMutations;
 #[Object]
 impl UserMutations {
     /// Update user preferences
+    ///
+    /// Other sessions of the same user get the new preferences through
+    /// `myPreferencesChanged`.
     async fn update_preferences(
         &self,
         ctx: &Context<'_>,
         input: UpdatePreferencesInput,
     ) -> Result<UserPreferences> {
-        let _user = ctx.auth_user()?;
-        // TODO: Update in database
-        Ok(UserPreferences {
-            theme: input.theme.unwrap_or_else(|| "system".to_string()),
-            notifications_enabled: input.notifications_enabled.unwrap_or(true),
-        })
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let default_library_id = match input.default_library_id.as_deref().map(str::trim) {
+            None => None,
+            Some("") => Some(None),
+            Some(id) => {
+                let library_id = Uuid::parse_str(id)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+                db.libraries()
+                    .get_by_id_and_user(library_id, user_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+                Some(Some(library_id))
+            }
+        };
+
+        let update = UpdateUserPreferences {
+            theme: input.theme,
+            default_library_id,
+            grid_density: input.grid_density,
+            language: input.language.map(|l| l.trim().to_string()),
+            notifications_enabled: input.notifications_enabled,
+            muted_notification_categories: input.muted_notification_categories,
+        };
+        update
+            .validate()
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let prefs = db
+            .user_preferences()
+            .update(user_id, update)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let prefs = UserPreferences::from_record(prefs);
+        ctx.data_unchecked::<Arc<EventBus>>().publish(prefs.clone());
+        Ok(prefs)
     }
 }
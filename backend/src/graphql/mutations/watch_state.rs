@@ -0,0 +1,77 @@
+use super::prelude::*;
+use crate::db::watch_progress::ContentType;
+
+#[derive(Default)]
+pub struct WatchStateMutations;
+
+#[Object]
+impl WatchStateMutations {
+    /// Mark an episode, movie, track or audiobook as watched
+    ///
+    /// Other sessions of the same user get the new state through
+    /// `watchStateChanged`.
+    async fn mark_watched(
+        &self,
+        ctx: &Context<'_>,
+        content_type: PlaybackContentType,
+        content_id: String,
+    ) -> Result<WatchState> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let id = Uuid::parse_str(&content_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid content ID: {}", e)))?;
+
+        let state = db
+            .watch_progress()
+            .mark_watched(user_id, progress_content_type(content_type), id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .and_then(WatchState::from_record)
+            .ok_or_else(|| async_graphql::Error::new("Failed to mark as watched"))?;
+
+        ctx.data_unchecked::<Arc<EventBus>>().publish(state.clone());
+
+        Ok(state)
+    }
+
+    /// Clear the watched mark and resume position of an item
+    ///
+    /// Other sessions of the same user get the new state through
+    /// `watchStateChanged`.
+    async fn mark_unwatched(
+        &self,
+        ctx: &Context<'_>,
+        content_type: PlaybackContentType,
+        content_id: String,
+    ) -> Result<WatchState> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let id = Uuid::parse_str(&content_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid content ID: {}", e)))?;
+
+        let state = db
+            .watch_progress()
+            .mark_unwatched(user_id, progress_content_type(content_type), id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .and_then(WatchState::from_record)
+            .unwrap_or_else(|| WatchState::unwatched(&user.user_id, content_type, &content_id));
+
+        ctx.data_unchecked::<Arc<EventBus>>().publish(state.clone());
+
+        Ok(state)
+    }
+}
+
+fn progress_content_type(content_type: PlaybackContentType) -> ContentType {
+    match content_type {
+        PlaybackContentType::Episode => ContentType::Episode,
+        PlaybackContentType::Movie => ContentType::Movie,
+        PlaybackContentType::Track => ContentType::Track,
+        PlaybackContentType::Audiobook => ContentType::Audiobook,
+    }
+}
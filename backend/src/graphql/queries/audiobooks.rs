@@ -62,7 +62,9 @@ impl AudiobookQueries {
 
         let title_filter = r#where
             .as_ref()
-            .and_then(|w| w.title.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.title.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
         let has_files_filter = r#where
             .as_ref()
             .and_then(|w| w.has_files.as_ref().and_then(|f| f.eq));
@@ -82,7 +84,7 @@ impl AudiobookQueries {
                 lib_id,
                 offset,
                 limit,
-                title_filter.as_deref(),
+                &title_filter,
                 has_files_filter,
                 &audiobook_sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
@@ -217,7 +219,9 @@ impl AudiobookQueries {
 
         let name_filter = r#where
             .as_ref()
-            .and_then(|w| w.name.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.name.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
 
         let sort_field = order_by
             .as_ref()
@@ -234,7 +238,7 @@ impl AudiobookQueries {
                 lib_id,
                 offset,
                 limit,
-                name_filter.as_deref(),
+                &name_filter,
                 &audiobook_author_sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
             )
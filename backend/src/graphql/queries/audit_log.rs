@@ -0,0 +1,53 @@
+use super::prelude::*;
+use crate::graphql::auth::{Permission, has_permission};
+
+#[derive(Default)]
+pub struct AuditLogQueries;
+
+#[Object]
+impl AuditLogQueries {
+    /// Audit log entries, newest first
+    ///
+    /// Filter by entity type, or by a single entity to see its history.
+    /// Users who can manage users see everyone's changes; everyone else sees
+    /// their own.
+    async fn audit_log(
+        &self,
+        ctx: &Context<'_>,
+        entity_type: Option<String>,
+        entity_id: Option<String>,
+        #[graphql(default = 100)] limit: i32,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let user_filter = if has_permission(ctx, Permission::ManageUsers).await? {
+            None
+        } else {
+            Some(
+                Uuid::parse_str(&user.user_id)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?,
+            )
+        };
+        let entity_id = entity_id
+            .map(|id| Uuid::parse_str(&id))
+            .transpose()
+            .map_err(|e| async_graphql::Error::new(format!("Invalid entity ID: {}", e)))?;
+
+        let entries = db
+            .audit_log()
+            .list(
+                user_filter,
+                entity_type.as_deref(),
+                entity_id,
+                limit.clamp(1, 500) as i64,
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(entries
+            .into_iter()
+            .map(AuditLogEntry::from_record)
+            .collect())
+    }
+}
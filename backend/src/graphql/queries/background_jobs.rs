@@ -0,0 +1,61 @@
+use super::prelude::*;
+use crate::graphql::auth::{Permission, has_permission};
+
+#[derive(Default)]
+pub struct BackgroundJobQueries;
+
+#[Object]
+impl BackgroundJobQueries {
+    /// Get a background job by ID
+    ///
+    /// Your own jobs, or any job if you can manage the server.
+    async fn background_job(&self, ctx: &Context<'_>, id: String) -> Result<Option<BackgroundJob>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let job_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid job ID: {}", e)))?;
+
+        let job = db
+            .background_jobs()
+            .get(job_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let see_all = has_permission(ctx, Permission::ManageServer).await?;
+        let user_id = Uuid::parse_str(&user.user_id).ok();
+        Ok(job
+            .filter(|job| see_all || (job.user_id.is_some() && job.user_id == user_id))
+            .map(BackgroundJob::from_record))
+    }
+
+    /// Recent background jobs, newest first
+    ///
+    /// Users who can manage the server see every user's jobs; everyone else
+    /// sees their own.
+    async fn background_jobs(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<BackgroundJob>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let user_filter = if has_permission(ctx, Permission::ManageServer).await? {
+            None
+        } else {
+            Some(
+                Uuid::parse_str(&user.user_id)
+                    .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?,
+            )
+        };
+
+        let jobs = db
+            .background_jobs()
+            .list_recent(user_filter, limit.clamp(1, 500) as i64)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(jobs.into_iter().map(BackgroundJob::from_record).collect())
+    }
+}
@@ -92,6 +92,48 @@ impl IndexerQueries {
         }))
     }
 
+    /// Upload/download totals and ratio per torrent indexer
+    async fn tracker_stats(&self, ctx: &Context<'_>) -> Result<Vec<TrackerStats>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let records = db
+            .indexers()
+            .list_tracker_stats(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        // Usenet indexers have no share ratio
+        Ok(records
+            .into_iter()
+            .filter(|r| r.indexer_type != "newznab")
+            .map(TrackerStats::from)
+            .collect())
+    }
+
+    /// Releases grabbed by auto-hunt and manual grabs, newest first
+    async fn grab_history(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> Result<Vec<GrabHistoryEntry>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let limit = limit.unwrap_or(100).clamp(1, 500);
+        let records = db
+            .grab_history()
+            .list_by_user(user_id, limit as i64)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(records.into_iter().map(GrabHistoryEntry::from).collect())
+    }
+
     /// Get available indexer types (for creating new indexers)
     async fn available_indexer_types(&self, ctx: &Context<'_>) -> Result<Vec<IndexerTypeInfo>> {
         let _user = ctx.auth_user()?;
@@ -231,9 +273,16 @@ impl IndexerQueries {
 
         let mut results: Vec<IndexerSearchResultItem> = Vec::new();
         let mut total_releases = 0;
+        let mut stopped_early = false;
+        let cancel = ctx.cancellation();
 
-        // Search each indexer
+        // Search each indexer, returning what was found so far if the
+        // request is cancelled
         for config in configs {
+            if cancel.is_cancelled() {
+                stopped_early = true;
+                break;
+            }
             let indexer_start = std::time::Instant::now();
 
             // Get and decrypt credentials
@@ -305,7 +354,14 @@ impl IndexerQueries {
                         }
                     };
 
-                    match indexer.search(&query).await {
+                    let searched = tokio::select! {
+                        searched = indexer.search(&query) => searched,
+                        _ = cancel.cancelled() => {
+                            stopped_early = true;
+                            break;
+                        }
+                    };
+                    match searched {
                         Ok(releases) => {
                             let _ = db.indexers().record_success(config.id).await;
 
@@ -384,7 +440,7 @@ impl IndexerQueries {
             indexers: results,
             total_releases,
             total_elapsed_ms: start.elapsed().as_millis() as i64,
-            stopped_early: false,
+            stopped_early,
             sources_searched,
             priority_rule_used,
         })
@@ -0,0 +1,135 @@
+use super::prelude::*;
+
+#[derive(Default)]
+pub struct LanguageProfileQueries;
+
+#[Object]
+impl LanguageProfileQueries {
+    /// Get all language profiles for the current user
+    async fn language_profiles(&self, ctx: &Context<'_>) -> Result<Vec<LanguageProfile>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let records = db
+            .language_profiles()
+            .list_by_user(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(records.into_iter().map(LanguageProfile::from).collect())
+    }
+
+    /// Get the language profile assigned to a library
+    async fn library_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        library_id: String,
+    ) -> Result<Option<LanguageProfile>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let record = db
+            .language_profiles()
+            .get_for_library(lib_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(record.map(LanguageProfile::from))
+    }
+
+    /// Get the language profile assigned to a show itself
+    ///
+    /// Returns null when the show uses its library's profile.
+    async fn show_language_profile(
+        &self,
+        ctx: &Context<'_>,
+        show_id: String,
+    ) -> Result<Option<LanguageProfile>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let show_id = Uuid::parse_str(&show_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid show ID: {}", e)))?;
+
+        db.tv_shows()
+            .get_by_id(show_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .filter(|show| show.user_id == user_id)
+            .ok_or_else(|| async_graphql::Error::new("Show not found"))?;
+
+        let record = db
+            .language_profiles()
+            .get_for_show(show_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(record.map(LanguageProfile::from))
+    }
+
+    /// Files in a library missing audio languages their profile requires
+    async fn language_mismatches(
+        &self,
+        ctx: &Context<'_>,
+        library_id: String,
+    ) -> Result<Vec<LanguageMismatch>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let records = db
+            .language_mismatches()
+            .list_by_library(lib_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let mut mismatches = Vec::with_capacity(records.len());
+        for record in records {
+            let Some(file) = db
+                .media_files()
+                .get_by_id(record.media_file_id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            else {
+                continue;
+            };
+            let file_name = std::path::Path::new(&file.path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or(file.path);
+
+            mismatches.push(LanguageMismatch {
+                media_file_id: record.media_file_id.to_string(),
+                file_name,
+                missing_audio_languages: record.missing_audio_languages,
+                release_title: record.release_title,
+                regrab_pending: record.regrab_pending,
+                created_at: record.created_at.to_rfc3339(),
+            });
+        }
+
+        Ok(mismatches)
+    }
+}
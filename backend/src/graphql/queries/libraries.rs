@@ -56,4 +56,92 @@ impl LibraryQueries {
             Ok(None)
         }
     }
+
+    /// Counts and total size of a library's files grouped by resolution,
+    /// video codec, HDR type, audio language and container
+    async fn library_breakdown(
+        &self,
+        ctx: &Context<'_>,
+        library_id: String,
+    ) -> Result<LibraryBreakdown> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let repo = db.media_files();
+        Ok(LibraryBreakdown {
+            library_id: lib_id.to_string(),
+            resolutions: breakdown(&repo, lib_id, BreakdownDimension::Resolution).await?,
+            video_codecs: breakdown(&repo, lib_id, BreakdownDimension::VideoCodec).await?,
+            hdr_types: breakdown(&repo, lib_id, BreakdownDimension::HdrType).await?,
+            audio_languages: breakdown(&repo, lib_id, BreakdownDimension::AudioLanguage).await?,
+            containers: breakdown(&repo, lib_id, BreakdownDimension::Container).await?,
+        })
+    }
+
+    /// Automation pauses of the current user that are still in effect
+    async fn automation_pauses(&self, ctx: &Context<'_>) -> Result<Vec<AutomationPause>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let pauses = crate::services::automation_pause::active_pauses(db, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(pauses.into_iter().map(AutomationPause::from).collect())
+    }
+
+    /// Preview what scanning a library would do, without changing anything
+    ///
+    /// Reports new and missing files, and the shows/movies new files would be
+    /// matched to (with confidence) or created as.
+    async fn preview_scan(&self, ctx: &Context<'_>, library_id: String) -> Result<ScanPreview> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let scanner = ctx.data_unchecked::<Arc<crate::services::ScannerService>>();
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let preview = scanner
+            .preview_scan(lib_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(ScanPreview::from_preview(preview))
+    }
+}
+
+/// One dimension of a library breakdown
+async fn breakdown(
+    repo: &MediaFileRepository,
+    library_id: Uuid,
+    dimension: BreakdownDimension,
+) -> Result<Vec<LibraryBreakdownEntry>> {
+    let buckets = repo
+        .breakdown_by(library_id, dimension)
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+    Ok(buckets
+        .into_iter()
+        .map(LibraryBreakdownEntry::from)
+        .collect())
 }
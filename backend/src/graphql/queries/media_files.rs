@@ -208,6 +208,39 @@ impl MediaFileQueries {
         Ok(file.map(MediaFile::from_record))
     }
 
+    /// Matched video files of a library without a subtitle in a language
+    ///
+    /// Embedded, external and downloaded subtitles all count, whether tagged
+    /// with an ISO 639-1 code, an ISO 639-2 code or the language's name.
+    async fn missing_subtitles(
+        &self,
+        ctx: &Context<'_>,
+        library_id: String,
+        #[graphql(desc = "Wanted language (\"en\", \"eng\" or \"English\")")] language: String,
+    ) -> Result<Vec<MediaFile>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let lib_id = Uuid::parse_str(&library_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid library ID: {}", e)))?;
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        db.libraries()
+            .get_by_id_and_user(lib_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Library not found"))?;
+
+        let variants = crate::services::subtitle_download::language_variants(&language);
+        let records = db
+            .media_files()
+            .list_missing_subtitle_language(lib_id, &variants)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(records.into_iter().map(MediaFile::from_record).collect())
+    }
+
     /// Get subtitle settings for a library
     async fn library_subtitle_settings(
         &self,
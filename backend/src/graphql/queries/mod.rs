@@ -1,7 +1,10 @@
 pub mod audiobooks;
+pub mod audit_log;
+pub mod background_jobs;
 pub mod episodes;
 pub mod filesystem;
 pub mod indexers;
+pub mod language_profiles;
 pub mod libraries;
 pub mod logs;
 pub mod media;
@@ -9,9 +12,13 @@ pub mod media_files;
 pub mod movies;
 pub mod music;
 pub mod notifications;
+pub mod people;
 pub mod playback;
 pub mod priority_rules;
+pub mod recommendations;
+pub mod roles;
 pub mod rss_feeds;
+pub mod search;
 pub mod settings;
 pub mod system;
 pub mod torrents;
@@ -19,11 +26,15 @@ pub mod tv_shows;
 pub mod upcoming;
 pub mod usenet;
 pub mod user;
+pub mod watch_state;
 
 pub use audiobooks::AudiobookQueries;
+pub use audit_log::AuditLogQueries;
+pub use background_jobs::BackgroundJobQueries;
 pub use episodes::EpisodeQueries;
 pub use filesystem::FilesystemQueries;
 pub use indexers::IndexerQueries;
+pub use language_profiles::LanguageProfileQueries;
 pub use libraries::LibraryQueries;
 pub use logs::LogQueries;
 pub use media::MediaQueries;
@@ -31,9 +42,13 @@ pub use media_files::MediaFileQueries;
 pub use movies::MovieQueries;
 pub use music::MusicQueries;
 pub use notifications::NotificationQueries;
+pub use people::PeopleQueries;
 pub use playback::PlaybackQueries;
 pub use priority_rules::PriorityRuleQueries;
+pub use recommendations::RecommendationQueries;
+pub use roles::RoleQueries;
 pub use rss_feeds::RssFeedQueries;
+pub use search::SearchQueries;
 pub use settings::SettingsQueries;
 pub use system::SystemQueries;
 pub use torrents::TorrentQueries;
@@ -41,6 +56,7 @@ pub use tv_shows::TvShowQueries;
 pub use upcoming::UpcomingQueries;
 pub use usenet::UsenetQueries;
 pub use user::UserQueries;
+pub use watch_state::WatchStateQueries;
 
 pub(crate) mod prelude {
     pub(crate) use std::collections::HashMap;
@@ -51,6 +67,7 @@ pub(crate) mod prelude {
 
     pub(crate) use crate::db::*;
     pub(crate) use crate::graphql::auth::AuthExt;
+    pub(crate) use crate::graphql::cancellation::CancellationExt;
     pub(crate) use crate::graphql::filters::OrderDirection;
     pub(crate) use crate::graphql::helpers::*;
     pub(crate) use crate::graphql::pagination::{Connection, parse_pagination_args};
@@ -80,7 +80,9 @@ impl MovieQueries {
         // Build filter conditions
         let title_filter = r#where
             .as_ref()
-            .and_then(|w| w.title.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.title.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
         let year_filter = r#where
             .as_ref()
             .and_then(|w| w.year.as_ref().and_then(|f| f.eq));
@@ -90,6 +92,21 @@ impl MovieQueries {
         let has_file_filter = r#where
             .as_ref()
             .and_then(|w| w.has_file.as_ref().and_then(|f| f.eq));
+        let (rating_min, rating_max) = r#where
+            .as_ref()
+            .and_then(|w| w.tmdb_rating.as_ref())
+            .map(|f| f.bounds())
+            .unwrap_or_default();
+        let genres_filter = r#where
+            .as_ref()
+            .and_then(|w| w.genres.as_ref())
+            .map(|f| f.to_json_array_filter())
+            .unwrap_or_default();
+        let cast_filter = r#where
+            .as_ref()
+            .and_then(|w| w.cast_names.as_ref())
+            .map(|f| f.to_json_array_filter())
+            .unwrap_or_default();
 
         // Determine sort field and direction
         let sort_field = order_by
@@ -109,10 +126,14 @@ impl MovieQueries {
                 lib_id,
                 offset,
                 limit,
-                title_filter.as_deref(),
+                &title_filter,
                 year_filter,
                 monitored_filter,
                 has_file_filter,
+                rating_min,
+                rating_max,
+                &genres_filter,
+                &cast_filter,
                 &sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
             )
@@ -178,21 +199,6 @@ impl MovieQueries {
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
-        Ok(results
-            .into_iter()
-            .map(|m| MovieSearchResult {
-                provider: "tmdb".to_string(),
-                provider_id: m.provider_id as i32,
-                title: m.title,
-                original_title: m.original_title,
-                year: m.year,
-                overview: m.overview,
-                poster_url: m.poster_url,
-                backdrop_url: m.backdrop_url,
-                imdb_id: m.imdb_id,
-                vote_average: m.vote_average,
-                popularity: m.popularity,
-            })
-            .collect())
+        Ok(results.into_iter().map(MovieSearchResult::from).collect())
     }
 }
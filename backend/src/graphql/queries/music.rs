@@ -41,7 +41,9 @@ impl MusicQueries {
 
         let name_filter = r#where
             .as_ref()
-            .and_then(|w| w.name.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.name.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
         let year_filter = r#where
             .as_ref()
             .and_then(|w| w.year.as_ref().and_then(|f| f.eq));
@@ -64,7 +66,7 @@ impl MusicQueries {
                 lib_id,
                 offset,
                 limit,
-                name_filter.as_deref(),
+                &name_filter,
                 year_filter,
                 has_files_filter,
                 &album_sort_field_to_column(sort_field),
@@ -131,7 +133,9 @@ impl MusicQueries {
 
         let name_filter = r#where
             .as_ref()
-            .and_then(|w| w.name.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.name.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
 
         let sort_field = order_by
             .as_ref()
@@ -148,7 +152,7 @@ impl MusicQueries {
                 lib_id,
                 offset,
                 limit,
-                name_filter.as_deref(),
+                &name_filter,
                 &artist_sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
             )
@@ -286,7 +290,9 @@ impl MusicQueries {
 
         let title_filter = r#where
             .as_ref()
-            .and_then(|w| w.title.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.title.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
         let has_file_filter = r#where
             .as_ref()
             .and_then(|w| w.has_file.as_ref().and_then(|f| f.eq));
@@ -306,7 +312,7 @@ impl MusicQueries {
                 lib_id,
                 offset,
                 limit,
-                title_filter.as_deref(),
+                &title_filter,
                 has_file_filter,
                 &track_sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
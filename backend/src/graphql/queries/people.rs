@@ -0,0 +1,122 @@
+use std::collections::HashSet;
+
+use super::prelude::*;
+
+/// Default number of director suggestions
+const DEFAULT_SUGGESTIONS: i32 = 10;
+
+#[derive(Default)]
+pub struct PeopleQueries;
+
+#[Object]
+impl PeopleQueries {
+    /// Get an actor or director with their credits in your libraries
+    ///
+    /// Null if they aren't credited on any of your movies or shows.
+    async fn person(&self, ctx: &Context<'_>, id: String) -> Result<Option<Person>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let person_id = Uuid::parse_str(&id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid person ID: {}", e)))?;
+
+        let Some(person) = db
+            .people()
+            .get(person_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let credits = db
+            .people()
+            .list_for_person(person_id, user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        if credits.is_empty() {
+            return Ok(None);
+        }
+
+        let mut filmography = Vec::with_capacity(credits.len());
+        for credit in credits {
+            let movie = match credit.movie_id {
+                Some(movie_id) => db
+                    .movies()
+                    .get_by_id(movie_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(movie_record_to_graphql),
+                None => None,
+            };
+            let tv_show = match credit.tv_show_id {
+                Some(show_id) => db
+                    .tv_shows()
+                    .get_by_id(show_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(TvShow::from_record),
+                None => None,
+            };
+            filmography.push(FilmographyEntry {
+                role: credit.role,
+                character: credit.character,
+                movie,
+                tv_show,
+            });
+        }
+
+        Ok(Some(Person {
+            id: person.id.to_string(),
+            tmdb_id: person.tmdb_id,
+            name: person.name,
+            profile_url: person.profile_url,
+            filmography,
+        }))
+    }
+
+    /// Other movies a person directed that aren't in your libraries, from
+    /// TMDB, most popular first
+    async fn director_suggestions(
+        &self,
+        ctx: &Context<'_>,
+        person_id: String,
+        limit: Option<i32>,
+    ) -> Result<Vec<MovieSearchResult>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let metadata = ctx.data_unchecked::<Arc<MetadataService>>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let person_id = Uuid::parse_str(&person_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid person ID: {}", e)))?;
+
+        let person = db
+            .people()
+            .get(person_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .ok_or_else(|| async_graphql::Error::new("Person not found"))?;
+
+        let directed = metadata
+            .get_directed_movies(person.tmdb_id as u32)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let owned: HashSet<i32> = db
+            .movies()
+            .list_by_user(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            .into_iter()
+            .filter_map(|movie| movie.tmdb_id)
+            .collect();
+
+        Ok(directed
+            .into_iter()
+            .filter(|movie| !owned.contains(&(movie.provider_id as i32)))
+            .take(limit.unwrap_or(DEFAULT_SUGGESTIONS).clamp(1, 50) as usize)
+            .map(MovieSearchResult::from)
+            .collect())
+    }
+}
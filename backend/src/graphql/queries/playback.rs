@@ -1,4 +1,6 @@
 use super::prelude::*;
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::jobs::playback_streams as streams;
 
 #[derive(Default)]
 pub struct PlaybackQueries;
@@ -136,9 +138,41 @@ impl PlaybackQueries {
             .get_or_default::<i32>("playback_sync_interval", 15)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let max_streams = db
+            .settings()
+            .get_or_default::<i32>(streams::MAX_STREAMS_KEY, streams::DEFAULT_MAX_STREAMS)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let heartbeat_timeout = db
+            .settings()
+            .get_or_default::<i32>(
+                streams::HEARTBEAT_TIMEOUT_KEY,
+                streams::DEFAULT_HEARTBEAT_TIMEOUT_SECS,
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
         Ok(PlaybackSettings {
             sync_interval_seconds: sync_interval,
+            max_concurrent_streams: max_streams,
+            heartbeat_timeout_seconds: heartbeat_timeout,
         })
     }
+
+    /// List streams currently being played on any client (admin only)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn active_sessions(&self, ctx: &Context<'_>) -> Result<Vec<ActivePlaybackStream>> {
+        let db = ctx.data_unchecked::<Database>();
+
+        let streams = db
+            .playback_streams()
+            .list_active()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(streams
+            .into_iter()
+            .map(ActivePlaybackStream::from_record)
+            .collect())
+    }
 }
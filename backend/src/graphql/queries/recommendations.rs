@@ -0,0 +1,29 @@
+use super::prelude::*;
+
+#[derive(Default)]
+pub struct RecommendationQueries;
+
+#[Object]
+impl RecommendationQueries {
+    /// Get watch-next recommendations for the current user
+    ///
+    /// Mixes unwatched items already in the user's libraries with TMDB
+    /// suggestions based on recently watched movies. Cached per user daily.
+    async fn recommendations(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default = 20, desc = "Maximum number of recommendations")] limit: i32,
+    ) -> Result<Vec<Recommendation>> {
+        let user = ctx.auth_user()?;
+        let service = ctx.data_unchecked::<Arc<crate::services::RecommendationService>>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let recommendations = service
+            .get_for_user(user_id, limit.clamp(1, 50) as usize)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(recommendations.into_iter().map(Recommendation::from).collect())
+    }
+}
@@ -0,0 +1,39 @@
+use super::prelude::*;
+
+use crate::graphql::auth::{Permission, PermissionGuard};
+
+#[derive(Default)]
+pub struct RoleQueries;
+
+#[Object]
+impl RoleQueries {
+    /// All roles, built-in first
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageUsers)")]
+    async fn roles(&self, ctx: &Context<'_>) -> Result<Vec<Role>> {
+        let db = ctx.data_unchecked::<Database>();
+
+        let roles = db
+            .roles()
+            .list()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(roles.into_iter().map(Role::from_record).collect())
+    }
+
+    /// The current user's role and what it allows
+    async fn my_role(&self, ctx: &Context<'_>) -> Result<Option<Role>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let role = db
+            .roles()
+            .get_for_user(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(role.map(Role::from_record))
+    }
+}
@@ -34,6 +34,12 @@ impl RssFeedQueries {
                 url: r.url,
                 enabled: r.enabled,
                 poll_interval_minutes: r.poll_interval_minutes,
+                min_poll_interval_minutes: r.min_poll_interval_minutes,
+                max_poll_interval_minutes: r.max_poll_interval_minutes,
+                current_poll_interval_minutes: r
+                    .current_poll_interval_minutes
+                    .unwrap_or(r.poll_interval_minutes),
+                ttl_minutes: r.ttl_minutes,
                 last_polled_at: r.last_polled_at.map(|t| t.to_rfc3339()),
                 last_successful_at: r.last_successful_at.map(|t| t.to_rfc3339()),
                 last_error: r.last_error,
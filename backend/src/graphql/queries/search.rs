@@ -0,0 +1,134 @@
+use super::prelude::*;
+use crate::services::global_search::{self, SearchKind};
+
+/// Default number of global search hits
+const DEFAULT_LIMIT: i32 = 20;
+
+#[derive(Default)]
+pub struct SearchQueries;
+
+#[Object]
+impl SearchQueries {
+    /// Search the current user's movies, shows, episodes, albums, artists,
+    /// audiobooks and people at once, best matches first
+    async fn global_search(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<i32>,
+    ) -> Result<Vec<GlobalSearchHit>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let hits = global_search::search(
+            db,
+            user_id,
+            &query,
+            limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 100) as usize,
+        )
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let mut results = Vec::with_capacity(hits.len());
+        for hit in hits {
+            let score = hit.score;
+            let Some(id) = hit.id else {
+                results.push(GlobalSearchHit::Person(PersonSearchHit {
+                    score,
+                    name: hit.name,
+                    roles: hit.roles,
+                }));
+                continue;
+            };
+
+            // Items can disappear between the search and the lookup
+            let result = match hit.kind {
+                SearchKind::Movie => db
+                    .movies()
+                    .get_by_id(id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(|movie| {
+                        GlobalSearchHit::Movie(MovieSearchHit {
+                            score,
+                            movie: movie_record_to_graphql(movie),
+                        })
+                    }),
+                SearchKind::Show => db
+                    .tv_shows()
+                    .get_by_id(id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(|show| {
+                        GlobalSearchHit::Show(ShowSearchHit {
+                            score,
+                            show: TvShow::from_record(show),
+                        })
+                    }),
+                SearchKind::Episode => {
+                    let Some(episode) = db
+                        .episodes()
+                        .get_by_id(id)
+                        .await
+                        .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    else {
+                        continue;
+                    };
+                    let show = db
+                        .tv_shows()
+                        .get_by_id(episode.tv_show_id)
+                        .await
+                        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+                    let media_file = db.media_files().get_by_episode_id(id).await.ok().flatten();
+                    show.map(|show| {
+                        GlobalSearchHit::Episode(EpisodeSearchHit {
+                            score,
+                            episode: Episode::from_record(episode, media_file),
+                            show: TvShow::from_record(show),
+                        })
+                    })
+                }
+                SearchKind::Album => db
+                    .albums()
+                    .get_by_id(id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(|album| {
+                        GlobalSearchHit::Album(AlbumSearchHit {
+                            score,
+                            album: Album::from(album),
+                        })
+                    }),
+                SearchKind::Artist => db
+                    .albums()
+                    .get_artist_by_id(id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(|artist| {
+                        GlobalSearchHit::Artist(ArtistSearchHit {
+                            score,
+                            artist: Artist::from(artist),
+                        })
+                    }),
+                SearchKind::Audiobook => db
+                    .audiobooks()
+                    .get_by_id(id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(|audiobook| {
+                        GlobalSearchHit::Audiobook(AudiobookSearchHit {
+                            score,
+                            audiobook: Audiobook::from(audiobook),
+                        })
+                    }),
+                SearchKind::Person => None,
+            };
+
+            results.extend(result);
+        }
+
+        Ok(results)
+    }
+}
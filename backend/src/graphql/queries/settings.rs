@@ -43,6 +43,30 @@ impl SettingsQueries {
         })
     }
 
+    /// Get the download speed limits and their schedule
+    async fn download_schedule(&self, ctx: &Context<'_>) -> Result<DownloadSchedule> {
+        use crate::services::bandwidth::{limits_at, load_defaults, load_windows};
+
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let defaults = load_defaults(db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let windows = load_windows(db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let (active, active_window) =
+            limits_at(defaults, &windows, chrono::Local::now().naive_local());
+
+        Ok(DownloadSchedule {
+            defaults: defaults.into(),
+            windows: windows.into_iter().map(Into::into).collect(),
+            active: active.into(),
+            active_window: active_window.map(|i| i as i32),
+        })
+    }
+
     /// Get all settings in a category
     async fn settings_by_category(
         &self,
@@ -237,4 +261,76 @@ Filename: {filename}
             error: result.error,
         })
     }
+
+    /// Get upcoming episode air notification settings
+    async fn episode_air_notification_settings(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<EpisodeAirNotificationSettings> {
+        use crate::jobs::air_notifications::{
+            DEFAULT_DIGEST_THRESHOLD, DEFAULT_LEAD_HOURS, DIGEST_THRESHOLD_KEY, LEAD_HOURS_KEY,
+        };
+
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let settings = db.settings();
+
+        let overrides = db
+            .air_notifications()
+            .list_overrides()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(EpisodeAirNotificationSettings {
+            lead_hours: settings
+                .get_or_default(LEAD_HOURS_KEY, DEFAULT_LEAD_HOURS)
+                .await
+                .unwrap_or(DEFAULT_LEAD_HOURS),
+            digest_threshold: settings
+                .get_or_default(DIGEST_THRESHOLD_KEY, DEFAULT_DIGEST_THRESHOLD)
+                .await
+                .unwrap_or(DEFAULT_DIGEST_THRESHOLD),
+            show_overrides: overrides
+                .into_iter()
+                .map(|o| ShowAirNotificationOverride {
+                    tv_show_id: o.tv_show_id.to_string(),
+                    show_name: o.show_name,
+                    lead_hours: o.lead_hours,
+                })
+                .collect(),
+        })
+    }
+
+    /// Get the ownership and mode applied to imported files
+    async fn import_permission_settings(
+        &self,
+        ctx: &Context<'_>,
+    ) -> Result<ImportPermissionSettings> {
+        use crate::services::file_permissions::{
+            FILE_GID_KEY, FILE_MODE_KEY, FILE_UID_KEY, ImportPermissions,
+        };
+
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let settings = db.settings();
+        let env = ImportPermissions::from_env();
+
+        Ok(ImportPermissionSettings {
+            file_uid: settings
+                .get_or_default::<Option<u32>>(FILE_UID_KEY, None)
+                .await
+                .unwrap_or_default(),
+            file_gid: settings
+                .get_or_default::<Option<u32>>(FILE_GID_KEY, None)
+                .await
+                .unwrap_or_default(),
+            file_mode: settings
+                .get_or_default::<Option<String>>(FILE_MODE_KEY, None)
+                .await
+                .unwrap_or_default(),
+            env_uid: env.uid,
+            env_gid: env.gid,
+            env_file_mode: env.file_mode.map(|m| format!("{:o}", m)),
+        })
+    }
 }
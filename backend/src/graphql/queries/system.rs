@@ -1,6 +1,10 @@
 use super::prelude::*;
 
-use crate::services::AuthService;
+use crate::graphql::auth::{Permission, PermissionGuard};
+use crate::jobs::update_check;
+use crate::services::self_update;
+use crate::services::transcode_cache::{DEFAULT_MAX_SIZE_GB, MAX_SIZE_GB_KEY, TranscodeCache};
+use crate::services::{AuthService, DiagnosticsService};
 
 #[derive(Default)]
 pub struct SystemQueries;
@@ -30,4 +34,95 @@ impl SystemQueries {
             .await
             .map_err(|e| async_graphql::Error::new(format!("Failed to check setup status: {}", e)))
     }
+
+    /// Transcode cache usage (admin only)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn transcode_cache_stats(&self, ctx: &Context<'_>) -> Result<TranscodeCacheStats> {
+        let db = ctx.data_unchecked::<Database>();
+        let cache = ctx.data_unchecked::<Arc<TranscodeCache>>();
+
+        let max_size_gb = db
+            .settings()
+            .get_or_default::<f64>(MAX_SIZE_GB_KEY, DEFAULT_MAX_SIZE_GB)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let stats = cache
+            .stats()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(TranscodeCacheStats::from_stats(stats, max_size_gb))
+    }
+
+    /// Check the server's environment (admin only)
+    ///
+    /// Runs the same checks as the startup self-check: writable paths,
+    /// ffmpeg/ffprobe, database journal mode, free space, torrent port
+    /// reachability and clock skew, plus the external services as last
+    /// probed. Takes a few seconds because the port and clock checks contact
+    /// remote servers.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn diagnostics(&self, ctx: &Context<'_>) -> Result<Vec<DiagnosticCheck>> {
+        let db = ctx.data_unchecked::<Database>();
+        let torrent_service = ctx.data_unchecked::<Arc<TorrentService>>();
+        let cache = ctx.data_unchecked::<Arc<TranscodeCache>>();
+
+        let service = DiagnosticsService::new(
+            db.clone(),
+            torrent_service.clone(),
+            cache.root().to_path_buf(),
+        );
+        Ok(service.run().await.into_iter().map(Into::into).collect())
+    }
+
+    /// Health of the configured external services (admin only)
+    ///
+    /// TMDB, TheTVDB, OpenSubtitles, indexers, usenet servers, SMTP and
+    /// manually added cast devices, as of the last probe (every 5 minutes).
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn external_services_status(&self, ctx: &Context<'_>) -> Result<ExternalServicesStatus> {
+        let db = ctx.data_unchecked::<Database>();
+
+        let services = db
+            .external_services()
+            .list()
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(services.into())
+    }
+
+    ///
+    /// Reads the result of the last update check; null when up to date or
+    /// before the first check has run.
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn update_available(&self, ctx: &Context<'_>) -> Result<Option<UpdateInfo>> {
+        let db = ctx.data_unchecked::<Database>();
+
+        let release = update_check::cached_release(db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(release.filter(|r| r.is_update()).map(UpdateInfo::from))
+    }
+
+    /// Update check settings and when it last ran (admin only)
+    #[graphql(guard = "PermissionGuard::new(Permission::ManageServer)")]
+    async fn update_check_settings(&self, ctx: &Context<'_>) -> Result<UpdateCheckSettings> {
+        let db = ctx.data_unchecked::<Database>();
+
+        let enabled = update_check::is_enabled(db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let release = update_check::cached_release(db)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(UpdateCheckSettings {
+            enabled,
+            latest_version: release.as_ref().map(|r| r.version.clone()),
+            last_checked_at: release.map(|r| r.checked_at),
+            self_update_unavailable_reason: self_update::unsupported_reason(),
+        })
+    }
 }
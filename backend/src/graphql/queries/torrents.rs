@@ -20,8 +20,8 @@ impl TorrentQueries {
         if let Ok(records) = db.torrents().list_by_user(user_uuid).await {
             // Create a map of info_hash -> added_at
             let added_at_map: HashMap<String, String> = records
-                .into_iter()
-                .map(|r| (r.info_hash, r.added_at.to_rfc3339()))
+                .iter()
+                .map(|r| (r.info_hash.clone(), r.added_at.to_rfc3339()))
                 .collect();
 
             // Merge added_at into the result
@@ -30,6 +30,25 @@ impl TorrentQueries {
                     torrent.added_at = Some(added_at.clone());
                 }
             }
+
+            // Demo torrents are records only, never loaded in the session;
+            // list them from their saved state (with placeholder IDs)
+            let demo_seeded = db
+                .settings()
+                .get_value::<bool>(crate::services::demo::DEMO_SEEDED_KEY)
+                .await
+                .ok()
+                .flatten()
+                .unwrap_or(false);
+            if demo_seeded {
+                let saved: Vec<_> = records
+                    .into_iter()
+                    .filter(|r| !result.iter().any(|t| t.info_hash == r.info_hash))
+                    .collect();
+                for (i, record) in saved.into_iter().enumerate() {
+                    result.push(Torrent::from_saved(-(i as i32) - 1, record));
+                }
+            }
         }
 
         Ok(result)
@@ -55,6 +74,23 @@ impl TorrentQueries {
         }
     }
 
+    /// Recent transfer rates for sparkline graphs
+    ///
+    /// With a torrent ID, that torrent's rates; otherwise the totals across
+    /// all torrents. History is kept in memory for the last hour.
+    async fn transfer_rate_history(
+        &self,
+        ctx: &Context<'_>,
+        torrent_id: Option<i32>,
+        #[graphql(default = 10, desc = "How many minutes back (at most 60)")] minutes: i32,
+    ) -> Result<TransferRateHistory> {
+        let _user = ctx.auth_user()?;
+        let service = ctx.data_unchecked::<Arc<TorrentService>>();
+        let minutes = minutes.clamp(1, crate::services::speed_history::RETENTION_MINUTES as i32);
+        let samples = service.speed_history(torrent_id.map(|id| id as usize), minutes as u32);
+        Ok(samples.into())
+    }
+
     /// Get pending file matches for a source (torrent, usenet, etc.)
     ///
     /// Returns the list of files and what library items they match to.
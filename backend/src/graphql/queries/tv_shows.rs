@@ -58,7 +58,9 @@ impl TvShowQueries {
         // Build filter conditions
         let name_filter = r#where
             .as_ref()
-            .and_then(|w| w.name.as_ref().and_then(|f| f.contains.clone()));
+            .and_then(|w| w.name.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
         let year_filter = r#where
             .as_ref()
             .and_then(|w| w.year.as_ref().and_then(|f| f.eq));
@@ -67,7 +69,9 @@ impl TvShowQueries {
             .and_then(|w| w.monitored.as_ref().and_then(|f| f.eq));
         let status_filter = r#where
             .as_ref()
-            .and_then(|w| w.status.as_ref().and_then(|f| f.eq.clone()));
+            .and_then(|w| w.status.as_ref())
+            .map(|f| f.to_text_filter())
+            .unwrap_or_default();
 
         let sort_field = order_by
             .as_ref()
@@ -84,10 +88,10 @@ impl TvShowQueries {
                 lib_id,
                 offset,
                 limit,
-                name_filter.as_deref(),
+                &name_filter,
                 year_filter,
                 monitored_filter,
-                status_filter.as_deref(),
+                &status_filter,
                 &tv_sort_field_to_column(sort_field),
                 sort_dir == OrderDirection::Asc,
             )
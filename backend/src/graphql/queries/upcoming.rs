@@ -70,15 +70,22 @@ impl UpcomingQueries {
         &self,
         ctx: &Context<'_>,
         #[graphql(default = 7, desc = "Number of days to look ahead")] days: i32,
+        #[graphql(desc = "The user's offset from UTC in minutes, so 'today' is their local date")]
+        utc_offset_minutes: Option<i32>,
     ) -> Result<Vec<LibraryUpcomingEpisode>> {
         let user = ctx.auth_user()?;
         let db = ctx.data_unchecked::<Database>();
         let user_id = Uuid::parse_str(&user.user_id)
             .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
 
+        let offset = crate::graphql::filters::utc_offset(utc_offset_minutes)
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        let today = crate::graphql::filters::local_today(chrono::Utc::now(), offset);
+        let last_day = today + chrono::Duration::days(days.max(0) as i64);
+
         let records = db
             .episodes()
-            .list_upcoming_by_user(user_id, days)
+            .list_upcoming_by_user(user_id, today, last_day)
             .await
             .map_err(|e| async_graphql::Error::new(e.to_string()))?;
 
@@ -1,4 +1,8 @@
 use super::prelude::*;
+use crate::jobs::nzb_watch_folder::{WATCH_FOLDER_KEY, WatchFolder};
+
+/// Default period for usenet server usage, in days
+const DEFAULT_USAGE_DAYS: i32 = 30;
 
 #[derive(Default)]
 pub struct UsenetQueries;
@@ -45,6 +49,67 @@ impl UsenetQueries {
         Ok(record.map(UsenetServer::from))
     }
 
+    /// How much each of the current user's usenet servers was used over the
+    /// last `days` days (default 30), in the order downloads try them
+    async fn usenet_server_usage(
+        &self,
+        ctx: &Context<'_>,
+        days: Option<i32>,
+    ) -> Result<Vec<UsenetServerUsage>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+        let days = days.unwrap_or(DEFAULT_USAGE_DAYS).clamp(1, 365);
+
+        let mut servers = db
+            .usenet_servers()
+            .list_by_user(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        servers.sort_by_key(|server| (server.is_backup, server.priority));
+
+        let mut daily: HashMap<Uuid, Vec<UsenetServerUsageRecord>> = HashMap::new();
+        for record in db
+            .usenet_servers()
+            .list_usage(user_id, days as i64)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?
+        {
+            daily.entry(record.server_id).or_default().push(record);
+        }
+
+        Ok(servers
+            .into_iter()
+            .map(|server| {
+                let days = daily.remove(&server.id).unwrap_or_default();
+                let mut total = UsenetUsageCounts::default();
+                for day in &days {
+                    total.add(&day.counts);
+                }
+                UsenetServerUsage {
+                    server_id: server.id.to_string(),
+                    server_name: server.name,
+                    is_backup: server.is_backup,
+                    bytes_downloaded: total.bytes_downloaded,
+                    articles_downloaded: total.articles_downloaded,
+                    articles_missing: total.articles_missing,
+                    articles_failed: total.articles_failed,
+                    daily: days
+                        .into_iter()
+                        .map(|day| UsenetUsageDay {
+                            day: day.day,
+                            bytes_downloaded: day.counts.bytes_downloaded,
+                            articles_downloaded: day.counts.articles_downloaded,
+                            articles_missing: day.counts.articles_missing,
+                            articles_failed: day.counts.articles_failed,
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
     /// Get all usenet downloads for the current user
     async fn usenet_downloads(&self, ctx: &Context<'_>) -> Result<Vec<UsenetDownload>> {
         let user = ctx.auth_user()?;
@@ -84,4 +149,34 @@ impl UsenetQueries {
 
         Ok(record.map(UsenetDownload::from))
     }
+
+    /// The folder polled for NZB files, if one is set
+    async fn nzb_watch_folder(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let _user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+
+        let folder = db
+            .settings()
+            .get_value::<WatchFolder>(WATCH_FOLDER_KEY)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(folder.map(|folder| folder.path))
+    }
+
+    /// The current user's SABnzbd API key, without the key itself
+    async fn sabnzbd_api_key(&self, ctx: &Context<'_>) -> Result<Option<SabnzbdApiKey>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let record = db
+            .sabnzbd()
+            .get_key(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(record.map(SabnzbdApiKey::from))
+    }
 }
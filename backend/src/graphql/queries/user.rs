@@ -8,20 +8,47 @@ impl UserQueries {
     /// Get the current authenticated user
     async fn me(&self, ctx: &Context<'_>) -> Result<User> {
         let user = ctx.auth_user()?;
-        Ok(User {
-            id: user.user_id.clone(),
-            email: user.email.clone(),
-            role: user.role.clone(),
+        let db = ctx.data_unchecked::<Database>();
+
+        // The token's claims can be stale (e.g. after an email change), so
+        // prefer the stored record
+        let record = db
+            .users()
+            .get_by_id(&user.user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(match record {
+            Some(record) => User {
+                id: record.id,
+                email: record.email,
+                role: Some(record.role),
+                display_name: record.display_name,
+                avatar_url: record.avatar_url,
+            },
+            None => User {
+                id: user.user_id.clone(),
+                email: user.email.clone(),
+                role: user.role.clone(),
+                display_name: None,
+                avatar_url: None,
+            },
         })
     }
 
     /// Get user preferences
     async fn my_preferences(&self, ctx: &Context<'_>) -> Result<UserPreferences> {
-        let _user = ctx.auth_user()?;
-        // TODO: Fetch from database
-        Ok(UserPreferences {
-            theme: "system".to_string(),
-            notifications_enabled: true,
-        })
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let prefs = db
+            .user_preferences()
+            .get(user_id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        Ok(UserPreferences::from_record(prefs))
     }
 }
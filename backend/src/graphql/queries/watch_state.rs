@@ -0,0 +1,137 @@
+use super::prelude::*;
+use crate::db::watch_progress::ContentType;
+
+/// Default number of items in continue watching and next up
+const DEFAULT_LIMIT: i32 = 20;
+
+#[derive(Default)]
+pub struct WatchStateQueries;
+
+#[Object]
+impl WatchStateQueries {
+    /// Episodes and movies the current user started but didn't finish,
+    /// most recently watched first
+    async fn continue_watching(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<i32>,
+    ) -> Result<Vec<ContinueWatchingItem>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let records = db
+            .watch_progress()
+            .get_continue_watching(
+                user_id,
+                &[ContentType::Episode, ContentType::Movie],
+                limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 100) as i64,
+            )
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(records.len());
+        for record in records {
+            let (episode_id, movie_id) = (record.episode_id, record.movie_id);
+            let Some(state) = WatchState::from_record(record.clone()) else {
+                continue;
+            };
+
+            let mut item = ContinueWatchingItem {
+                state,
+                episode: None,
+                show: None,
+                movie: None,
+            };
+
+            if let Some(episode_id) = episode_id {
+                let Some(episode) = db
+                    .episodes()
+                    .get_by_id(episode_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                else {
+                    continue;
+                };
+                item.show = db
+                    .tv_shows()
+                    .get_by_id(episode.tv_show_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(TvShow::from_record);
+                let media_file = db
+                    .media_files()
+                    .get_by_episode_id(episode_id)
+                    .await
+                    .ok()
+                    .flatten();
+                item.episode = Some(Episode::from_record_with_progress(
+                    episode,
+                    media_file,
+                    Some(record),
+                ));
+            } else if let Some(movie_id) = movie_id {
+                item.movie = db
+                    .movies()
+                    .get_by_id(movie_id)
+                    .await
+                    .map_err(|e| async_graphql::Error::new(e.to_string()))?
+                    .map(movie_record_to_graphql);
+                if item.movie.is_none() {
+                    continue;
+                }
+            }
+
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
+    /// The next episode of each show the current user is watching, shows
+    /// watched most recently first
+    async fn next_up(&self, ctx: &Context<'_>, limit: Option<i32>) -> Result<Vec<NextUpItem>> {
+        let user = ctx.auth_user()?;
+        let db = ctx.data_unchecked::<Database>();
+        let user_id = Uuid::parse_str(&user.user_id)
+            .map_err(|e| async_graphql::Error::new(format!("Invalid user ID: {}", e)))?;
+
+        let next_up = crate::services::watch_state::next_up(
+            db,
+            user_id,
+            limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 100) as i64,
+        )
+        .await
+        .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(next_up.len());
+        for next in next_up {
+            let Some(show) = db
+                .tv_shows()
+                .get_by_id(next.episode.tv_show_id)
+                .await
+                .map_err(|e| async_graphql::Error::new(e.to_string()))?
+            else {
+                continue;
+            };
+            let media_file = db
+                .media_files()
+                .get_by_episode_id(next.episode.id)
+                .await
+                .ok()
+                .flatten();
+
+            items.push(NextUpItem {
+                show: TvShow::from_record(show),
+                episode: Episode::from_record_with_progress(
+                    next.episode,
+                    media_file,
+                    next.progress,
+                ),
+            });
+        }
+
+        Ok(items)
+    }
+}
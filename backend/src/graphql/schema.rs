@@ -10,11 +10,12 @@ use async_graphql::{MergedObject, Schema};
 use crate::db::Database;
 use crate::graphql::mutations;
 use crate::graphql::queries;
-use crate::graphql::types::{ContentDownloadProgressEvent, LibraryChangedEvent, MediaFileUpdatedEvent};
 use crate::services::{
-    AuthService, CastService, FilesystemService, LogEvent, MetadataService, NotificationService,
-    ScannerService, TorrentService,
+    AuditService, AuthService, BackgroundJobService, CastService, EventBus, FilesystemService,
+    MetadataService, NotificationService, RecommendationService, ScannerService, TorrentService,
+    UsenetService,
 };
+use crate::services::transcode_cache::TranscodeCache;
 
 use super::subscriptions::SubscriptionRoot;
 
@@ -24,60 +25,77 @@ pub type LibrarianSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
 /// Build the GraphQL schema with all resolvers
 pub fn build_schema(
     torrent_service: Arc<TorrentService>,
+    usenet_service: Arc<UsenetService>,
     metadata_service: Arc<MetadataService>,
     scanner_service: Arc<ScannerService>,
     cast_service: Arc<CastService>,
     filesystem_service: Arc<FilesystemService>,
     notification_service: Arc<NotificationService>,
     auth_service: Arc<AuthService>,
+    recommendation_service: Arc<RecommendationService>,
+    transcode_cache: Arc<TranscodeCache>,
+    background_jobs: Arc<BackgroundJobService>,
+    audit: Arc<AuditService>,
     db: Database,
     analysis_queue: Arc<crate::services::MediaAnalysisQueue>,
-    log_broadcast: Option<tokio::sync::broadcast::Sender<LogEvent>>,
-    library_broadcast: Option<tokio::sync::broadcast::Sender<LibraryChangedEvent>>,
-    media_file_broadcast: Option<tokio::sync::broadcast::Sender<MediaFileUpdatedEvent>>,
-    content_progress_broadcast: Option<tokio::sync::broadcast::Sender<ContentDownloadProgressEvent>>,
+    subtitle_queue: Arc<crate::services::SubtitleDownloadQueue>,
+    events: Arc<EventBus>,
+    introspection: bool,
 ) -> LibrarianSchema {
-    // Create library events broadcast channel (use provided or create new)
-    let library_tx = library_broadcast
-        .unwrap_or_else(|| tokio::sync::broadcast::channel::<LibraryChangedEvent>(100).0);
-
-    // Create media file updated broadcast channel (use provided or create new)
-    let media_file_tx = media_file_broadcast
-        .unwrap_or_else(|| tokio::sync::broadcast::channel::<MediaFileUpdatedEvent>(100).0);
-
-    // Create content download progress broadcast channel (use provided or create new)
-    let content_progress_tx = content_progress_broadcast
-        .unwrap_or_else(|| tokio::sync::broadcast::channel::<ContentDownloadProgressEvent>(100).0);
-
     let mut schema = Schema::build(
         QueryRoot::default(),
         MutationRoot::default(),
         SubscriptionRoot,
     )
     .data(torrent_service)
+    .data(usenet_service)
     .data(metadata_service)
     .data(scanner_service)
     .data(cast_service)
     .data(filesystem_service)
     .data(notification_service)
     .data(auth_service)
+    .data(recommendation_service)
+    .data(transcode_cache)
+    .data(background_jobs)
+    .data(audit)
     .data(db)
     .data(analysis_queue)
-    .data(library_tx)
-    .data(media_file_tx)
-    .data(content_progress_tx);
+    .data(subtitle_queue)
+    .data(events);
 
-    // Add log broadcast sender if provided
-    if let Some(sender) = log_broadcast {
-        schema = schema.data(sender);
+    if !introspection {
+        schema = schema.disable_introspection();
     }
 
     schema.finish()
 }
 
+/// Render the schema as SDL without any services attached
+///
+/// Used by `--emit-ts` to generate frontend types offline; resolvers are never
+/// executed so no context data is required.
+pub fn schema_sdl() -> String {
+    Schema::build(QueryRoot::default(), MutationRoot::default(), SubscriptionRoot)
+        .finish()
+        .sdl()
+}
+
+/// Short hash of the schema SDL
+///
+/// Sent with every GraphQL response so clients can tell when the server was
+/// upgraded to a schema their generated types don't match.
+pub fn schema_version() -> String {
+    use sha2::{Digest, Sha256};
+
+    let hash = format!("{:x}", Sha256::digest(schema_sdl().as_bytes()));
+    hash[..12].to_string()
+}
+
 #[derive(MergedObject, Default)]
 pub struct QueryRoot(
     queries::UserQueries,
+    queries::RoleQueries,
     queries::LibraryQueries,
     queries::TvShowQueries,
     queries::MediaFileQueries,
@@ -96,14 +114,22 @@ pub struct QueryRoot(
     queries::FilesystemQueries,
     queries::SystemQueries,
     queries::PriorityRuleQueries,
+    queries::LanguageProfileQueries,
     queries::UsenetQueries,
     queries::NotificationQueries,
+    queries::RecommendationQueries,
+    queries::BackgroundJobQueries,
+    queries::AuditLogQueries,
+    queries::WatchStateQueries,
+    queries::SearchQueries,
+    queries::PeopleQueries,
 );
 
 #[derive(MergedObject, Default)]
 pub struct MutationRoot(
     mutations::AuthMutations,
     mutations::UserMutations,
+    mutations::RoleMutations,
     mutations::LibraryMutations,
     mutations::MediaFileMutations,
     mutations::TvShowMutations,
@@ -118,6 +144,9 @@ pub struct MutationRoot(
     mutations::IndexerMutations,
     mutations::FilesystemMutations,
     mutations::PriorityRuleMutations,
+    mutations::LanguageProfileMutations,
     mutations::UsenetMutations,
     mutations::NotificationMutations,
+    mutations::SystemMutations,
+    mutations::WatchStateMutations,
 );
@@ -7,24 +7,24 @@ use std::sync::Arc;
 
 use async_graphql::{Context, Subscription};
 use futures::Stream;
-use tokio::sync::broadcast;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::BroadcastStream;
 
 use crate::services::{
-    CastDevicesEvent, CastService, CastSessionEvent,
-    DirectoryChangeEvent as ServiceDirectoryChangeEvent, FilesystemService, LogEvent,
+    BackgroundJobService, CastDevicesEvent, CastService, CastSessionEvent,
+    DirectoryChangeEvent as ServiceDirectoryChangeEvent, EventBus, FilesystemService, LogEvent,
     NotificationCountEvent, NotificationEvent as ServiceNotificationEvent, NotificationService,
     TorrentEvent, TorrentService,
 };
 
-use super::auth::{AuthGuard, AuthUser};
+use super::auth::{AuthGuard, AuthUser, Permission, has_permission};
 use super::types::{
-    ActiveDownloadCount, CastDevice, CastPlayerState, CastSession, ContentDownloadProgressEvent,
-    DirectoryChangeEvent, LibraryChangedEvent, LogEventSubscription, LogLevel,
-    MediaFileUpdatedEvent, Notification, NotificationCounts, NotificationEvent,
-    NotificationEventType, TorrentAddedEvent, TorrentCompletedEvent, TorrentProgress,
-    TorrentRemovedEvent, TorrentState,
+    ActiveDownloadCount, BackgroundJob, CastDevice, CastPlayerState, CastSession,
+    ContentDownloadProgressEvent, DirectoryChangeEvent, LibraryChangedEvent,
+    LogEventSubscription, LogLevel, MediaFileUpdatedEvent, Notification, NotificationCounts,
+    NotificationEvent, NotificationEventType, TorrentAddedEvent, TorrentCompletedEvent,
+    TorrentFile, TorrentFileProgressEvent, TorrentProgress, TorrentRemovedEvent, TorrentState,
+    UserPreferences, WatchState,
 };
 
 pub struct SubscriptionRoot;
@@ -200,6 +200,7 @@ impl SubscriptionRoot {
                         // Error state is not "active"
                         states_guard.insert(*id, crate::services::TorrentState::Error);
                     }
+                    TorrentEvent::FileProgress { .. } => {}
                 }
 
                 // Calculate current active count
@@ -257,6 +258,35 @@ impl SubscriptionRoot {
         })
     }
 
+    /// Subscribe to per-file progress of a torrent
+    ///
+    /// Each event lists only the files that downloaded more since the last
+    /// one, so a season pack shows which episodes finish first.
+    #[graphql(guard = "AuthGuard")]
+    async fn torrent_file_progress<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        torrent_id: i32,
+    ) -> impl Stream<Item = TorrentFileProgressEvent> + 'ctx {
+        let torrent_service = ctx.data_unchecked::<Arc<TorrentService>>();
+        let receiver = torrent_service.subscribe();
+
+        BroadcastStream::new(receiver).filter_map(move |result| {
+            result.ok().and_then(|event| match event {
+                TorrentEvent::FileProgress {
+                    id,
+                    info_hash,
+                    files,
+                } if id as i32 == torrent_id => Some(TorrentFileProgressEvent {
+                    torrent_id,
+                    info_hash,
+                    files: files.into_iter().map(TorrentFile::from).collect(),
+                }),
+                _ => None,
+            })
+        })
+    }
+
     /// Subscribe to all log events (for real-time log viewing)
     #[graphql(guard = "AuthGuard")]
     async fn log_events<'ctx>(
@@ -266,9 +296,7 @@ impl SubscriptionRoot {
             Vec<LogLevel>,
         >,
     ) -> impl Stream<Item = LogEventSubscription> + 'ctx {
-        let receiver = ctx
-            .data_unchecked::<broadcast::Sender<LogEvent>>()
-            .subscribe();
+        let receiver = ctx.data_unchecked::<Arc<EventBus>>().subscribe::<LogEvent>();
 
         let level_filter: Option<Vec<String>> = levels.map(|ls| {
             ls.into_iter()
@@ -309,9 +337,7 @@ impl SubscriptionRoot {
         &self,
         ctx: &Context<'ctx>,
     ) -> impl Stream<Item = LogEventSubscription> + 'ctx {
-        let receiver = ctx
-            .data_unchecked::<broadcast::Sender<LogEvent>>()
-            .subscribe();
+        let receiver = ctx.data_unchecked::<Arc<EventBus>>().subscribe::<LogEvent>();
 
         BroadcastStream::new(receiver).filter_map(|result| {
             result.ok().and_then(|event| {
@@ -400,6 +426,45 @@ impl SubscriptionRoot {
         })
     }
 
+    // ------------------------------------------------------------------------
+    // Background Job Subscriptions
+    // ------------------------------------------------------------------------
+
+    /// Subscribe to background job updates (queued, progress, finished)
+    ///
+    /// Receives updates for your own jobs, or every job if you can manage the
+    /// server.
+    #[graphql(guard = "AuthGuard")]
+    async fn background_job_updated<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        #[graphql(desc = "Filter to updates for a specific job")] job_id: Option<String>,
+    ) -> impl Stream<Item = BackgroundJob> + 'ctx {
+        let receiver = ctx.data_unchecked::<Arc<BackgroundJobService>>().subscribe();
+
+        let auth = ctx.data_opt::<AuthUser>();
+        let see_all = has_permission(ctx, Permission::ManageServer)
+            .await
+            .unwrap_or(false);
+        let user_id = auth
+            .and_then(|auth| uuid::Uuid::parse_str(&auth.user_id).ok())
+            .unwrap_or(uuid::Uuid::nil());
+
+        BroadcastStream::new(receiver).filter_map(move |result| {
+            result.ok().and_then(|job| {
+                if !see_all && job.user_id != Some(user_id) {
+                    return None;
+                }
+                if let Some(ref id) = job_id {
+                    if &job.id.to_string() != id {
+                        return None;
+                    }
+                }
+                Some(BackgroundJob::from_record(job))
+            })
+        })
+    }
+
     // ------------------------------------------------------------------------
     // Library Subscriptions
     // ------------------------------------------------------------------------
@@ -412,9 +477,7 @@ impl SubscriptionRoot {
         &self,
         ctx: &Context<'ctx>,
     ) -> impl Stream<Item = LibraryChangedEvent> + 'ctx {
-        let receiver = ctx
-            .data_unchecked::<broadcast::Sender<LibraryChangedEvent>>()
-            .subscribe();
+        let receiver = ctx.data_unchecked::<Arc<EventBus>>().subscribe::<LibraryChangedEvent>();
 
         BroadcastStream::new(receiver).filter_map(|result| result.ok())
     }
@@ -430,9 +493,7 @@ impl SubscriptionRoot {
         #[graphql(desc = "Filter to updates for a specific library")] library_id: Option<String>,
         #[graphql(desc = "Filter to updates for a specific episode")] episode_id: Option<String>,
     ) -> impl Stream<Item = MediaFileUpdatedEvent> + 'ctx {
-        let receiver = ctx
-            .data_unchecked::<broadcast::Sender<MediaFileUpdatedEvent>>()
-            .subscribe();
+        let receiver = ctx.data_unchecked::<Arc<EventBus>>().subscribe::<MediaFileUpdatedEvent>();
 
         let filter_library = library_id;
         let filter_episode = episode_id;
@@ -467,9 +528,7 @@ impl SubscriptionRoot {
         #[graphql(desc = "Filter to updates for a specific library")] library_id: Option<String>,
         #[graphql(desc = "Filter to updates for a specific parent (show, album, audiobook)")] parent_id: Option<String>,
     ) -> impl Stream<Item = ContentDownloadProgressEvent> + 'ctx {
-        let receiver = ctx
-            .data_unchecked::<broadcast::Sender<ContentDownloadProgressEvent>>()
-            .subscribe();
+        let receiver = ctx.data_unchecked::<Arc<EventBus>>().subscribe::<ContentDownloadProgressEvent>();
 
         let filter_library = library_id;
         let filter_parent = parent_id;
@@ -614,4 +673,55 @@ impl SubscriptionRoot {
             })
         })
     }
+
+    // ------------------------------------------------------------------------
+    // User Preference Subscriptions
+    // ------------------------------------------------------------------------
+
+    /// Subscribe to changes to your own preferences
+    ///
+    /// Lets every open session apply a change made on another device.
+    #[graphql(guard = "AuthGuard")]
+    async fn my_preferences_changed<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+    ) -> impl Stream<Item = UserPreferences> + 'ctx {
+        let receiver = ctx
+            .data_unchecked::<Arc<EventBus>>()
+            .subscribe::<UserPreferences>();
+
+        let user_id = ctx
+            .data_opt::<AuthUser>()
+            .map(|auth| auth.user_id.clone())
+            .unwrap_or_default();
+
+        BroadcastStream::new(receiver)
+            .filter_map(move |result| result.ok().filter(|prefs| prefs.user_id == user_id))
+    }
+
+    // ------------------------------------------------------------------------
+    // Watch State Subscriptions
+    // ------------------------------------------------------------------------
+
+    /// Watch progress and watched marks of the current user
+    ///
+    /// Sent while any of the user's devices plays something and when an
+    /// item is marked watched or unwatched.
+    #[graphql(guard = "AuthGuard")]
+    async fn watch_state_changed<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+    ) -> impl Stream<Item = WatchState> + 'ctx {
+        let receiver = ctx
+            .data_unchecked::<Arc<EventBus>>()
+            .subscribe::<WatchState>();
+
+        let user_id = ctx
+            .data_opt::<AuthUser>()
+            .map(|auth| auth.user_id.clone())
+            .unwrap_or_default();
+
+        BroadcastStream::new(receiver)
+            .filter_map(move |result| result.ok().filter(|state| state.user_id == user_id))
+    }
 }
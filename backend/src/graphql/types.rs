@@ -2,7 +2,7 @@
 //!
 //! These types mirror our domain models but are decorated with async-graphql attributes.
 
-use async_graphql::{Context, Enum, InputObject, Object, SimpleObject};
+use async_graphql::{ComplexObject, Context, Enum, InputObject, Object, SimpleObject, Union};
 use serde::{Deserialize, Serialize};
 
 use crate::services::{
@@ -45,10 +45,59 @@ pub struct TorrentFile {
     pub path: String,
     /// File size in bytes
     pub size: i64,
+    /// Bytes of the file downloaded so far
+    pub downloaded: i64,
     /// Download progress (0.0 - 1.0)
     pub progress: f64,
 }
 
+impl From<crate::services::torrent::TorrentFile> for TorrentFile {
+    fn from(f: crate::services::torrent::TorrentFile) -> Self {
+        Self {
+            index: f.index as i32,
+            path: f.path,
+            size: f.size as i64,
+            downloaded: f.downloaded as i64,
+            progress: f.progress,
+        }
+    }
+}
+
+/// Files of a torrent whose progress changed
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct TorrentFileProgressEvent {
+    /// Torrent ID within the session
+    pub torrent_id: i32,
+    pub info_hash: String,
+    /// Changed files (paths relative to the torrent root)
+    pub files: Vec<TorrentFile>,
+}
+
+/// Transfer rates sampled at a fixed interval, as parallel arrays for
+/// sparkline graphs
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct TransferRateHistory {
+    /// Seconds between samples
+    pub interval_secs: i32,
+    /// When each sample was taken (ISO 8601 timestamps), oldest first
+    pub timestamps: Vec<String>,
+    /// Download rate of each sample in bytes/second
+    pub download_speeds: Vec<i64>,
+    /// Upload rate of each sample in bytes/second
+    pub upload_speeds: Vec<i64>,
+}
+
+impl From<Vec<crate::services::speed_history::SpeedSample>> for TransferRateHistory {
+    fn from(samples: Vec<crate::services::speed_history::SpeedSample>) -> Self {
+        Self {
+            interval_secs: crate::services::speed_history::SAMPLE_INTERVAL_SECS as i32,
+            timestamps: samples.iter().map(|s| s.at.to_rfc3339()).collect(),
+            download_speeds: samples.iter().map(|s| s.download as i64).collect(),
+            upload_speeds: samples.iter().map(|s| s.upload as i64).collect(),
+        }
+    }
+}
+
 /// A torrent download
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Torrent {
@@ -230,21 +279,44 @@ impl From<ServiceTorrentInfo> for Torrent {
             peers: info.peers as i32,
             seeds: info.seeds as i32,
             save_path: info.save_path,
-            files: info
-                .files
-                .into_iter()
-                .map(|f| TorrentFile {
-                    index: f.index as i32,
-                    path: f.path,
-                    size: f.size as i64,
-                    progress: f.progress,
-                })
-                .collect(),
+            files: info.files.into_iter().map(TorrentFile::from).collect(),
             added_at: None, // Will be populated from database
         }
     }
 }
 
+impl Torrent {
+    /// A torrent from its database record alone, for torrents that aren't
+    /// loaded in the session (`id` is a placeholder as there is no session ID)
+    pub fn from_saved(id: i32, record: crate::db::TorrentRecord) -> Self {
+        let state = match record.state.as_str() {
+            "checking" => TorrentState::Checking,
+            "downloading" => TorrentState::Downloading,
+            "seeding" | "completed" => TorrentState::Seeding,
+            "paused" => TorrentState::Paused,
+            "error" => TorrentState::Error,
+            _ => TorrentState::Queued,
+        };
+        Self {
+            id,
+            info_hash: record.info_hash,
+            name: record.name,
+            state,
+            progress: record.progress as f64,
+            size: record.total_bytes,
+            downloaded: record.downloaded_bytes,
+            uploaded: record.uploaded_bytes,
+            download_speed: 0,
+            upload_speed: 0,
+            peers: 0,
+            seeds: 0,
+            save_path: record.save_path,
+            files: Vec::new(),
+            added_at: Some(record.added_at.to_rfc3339()),
+        }
+    }
+}
+
 /// Database record for a torrent (persistent state)
 /// Note: Media linking is done via pending_file_matches table
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
@@ -321,6 +393,8 @@ pub struct AddTorrentInput {
     pub album_id: Option<String>,
     /// Optional indexer ID (for authenticated .torrent downloads)
     pub indexer_id: Option<String>,
+    /// Grab the release even if it was already grabbed before
+    pub force: Option<bool>,
 }
 
 /// Result of adding a torrent
@@ -544,16 +618,7 @@ impl From<ServiceTorrentDetails> for TorrentDetails {
             download_speed: d.download_speed as i64,
             upload_speed: d.upload_speed as i64,
             save_path: d.save_path,
-            files: d
-                .files
-                .into_iter()
-                .map(|f| TorrentFile {
-                    index: f.index as i32,
-                    path: f.path,
-                    size: f.size as i64,
-                    progress: f.progress,
-                })
-                .collect(),
+            files: d.files.into_iter().map(TorrentFile::from).collect(),
             piece_count: d.piece_count as i64,
             pieces_downloaded: d.pieces_downloaded as i64,
             average_piece_download_ms: d.average_piece_download_ms.map(|v| v as i64),
@@ -652,8 +717,8 @@ pub struct ScanStatus {
     pub message: Option<String>,
 }
 
-/// Result of library consolidation
-#[derive(Debug, SimpleObject)]
+/// Result of library consolidation (stored as the consolidation job's result)
+#[derive(Debug, SimpleObject, Serialize)]
 pub struct ConsolidateLibraryResult {
     pub success: bool,
     pub folders_removed: i32,
@@ -661,6 +726,140 @@ pub struct ConsolidateLibraryResult {
     pub messages: Vec<String>,
 }
 
+// Returned by consolidateLibrary: the ID of the job doing the consolidation
+librarian_macros::job_result!(ConsolidateLibraryJobResult);
+
+/// A group of new files in a scan preview and what the scan would do with it
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScanPreviewItem {
+    /// Show or movie name parsed from the filenames
+    pub parsed_name: String,
+    pub parsed_year: Option<i32>,
+    pub file_count: i32,
+    /// "create", "link_existing", "no_match", "add_unlinked" or "not_checked"
+    pub action: String,
+    pub match_name: Option<String>,
+    pub match_year: Option<i32>,
+    pub provider: Option<String>,
+    pub provider_id: Option<i32>,
+    /// How closely the match fits the parsed name/year (0.0 - 1.0)
+    pub confidence: Option<f64>,
+    pub error: Option<String>,
+}
+
+/// What a library scan would do (dry run; nothing is written)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ScanPreview {
+    pub library_id: String,
+    pub library_name: String,
+    /// Media files found on disk
+    pub total_files: i32,
+    /// Files on disk not yet in the library
+    pub new_file_count: i32,
+    /// New file paths (capped at 1000)
+    pub new_files: Vec<String>,
+    /// Library files no longer on disk
+    pub missing_file_count: i32,
+    /// Missing file paths (capped at 1000)
+    pub missing_files: Vec<String>,
+    pub excluded_files: i32,
+    pub skipped_folders: i32,
+    /// Shows/movies that would be created
+    pub items_to_create: i32,
+    pub items: Vec<ScanPreviewItem>,
+    /// Caveats about what the preview covers
+    pub notes: Vec<String>,
+}
+
+impl ScanPreview {
+    pub fn from_preview(preview: crate::services::scan_preview::ScanPreview) -> Self {
+        Self {
+            items_to_create: preview.items_to_create() as i32,
+            library_id: preview.library_id.to_string(),
+            library_name: preview.library_name,
+            total_files: preview.total_files as i32,
+            new_file_count: preview.new_file_count as i32,
+            new_files: preview.new_files,
+            missing_file_count: preview.missing_file_count as i32,
+            missing_files: preview.missing_files,
+            excluded_files: preview.excluded_files,
+            skipped_folders: preview.skipped_folders,
+            items: preview
+                .items
+                .into_iter()
+                .map(|item| ScanPreviewItem {
+                    parsed_name: item.parsed_name,
+                    parsed_year: item.parsed_year.map(|y| y as i32),
+                    file_count: item.file_count as i32,
+                    action: item.action.as_str().to_string(),
+                    match_name: item.match_name,
+                    match_year: item.match_year,
+                    provider: item.provider,
+                    provider_id: item.provider_id.map(|id| id as i32),
+                    confidence: item.confidence,
+                    error: item.error,
+                })
+                .collect(),
+            notes: preview.notes,
+        }
+    }
+}
+
+/// A pause of auto-hunt and RSS polling ("vacation mode")
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AutomationPause {
+    pub id: String,
+    /// Paused library, or null for a pause covering every library
+    pub library_id: Option<String>,
+    /// When automation resumes on its own (null = until resumed by hand)
+    pub resume_at: Option<String>,
+    pub created_at: String,
+}
+
+impl From<crate::db::AutomationPauseRecord> for AutomationPause {
+    fn from(r: crate::db::AutomationPauseRecord) -> Self {
+        Self {
+            id: r.id.to_string(),
+            library_id: r.library_id.map(|id| id.to_string()),
+            resume_at: r.resume_at.map(|dt| dt.to_rfc3339()),
+            created_at: r.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Files of a library sharing one value of a property
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LibraryBreakdownEntry {
+    /// The property's value, or null for files where it's unknown
+    pub value: Option<String>,
+    pub file_count: i64,
+    /// Total size in bytes
+    pub total_size: i64,
+}
+
+impl From<crate::db::BreakdownBucket> for LibraryBreakdownEntry {
+    fn from(b: crate::db::BreakdownBucket) -> Self {
+        Self {
+            value: b.value,
+            file_count: b.file_count,
+            total_size: b.total_size,
+        }
+    }
+}
+
+/// File counts and sizes of a library grouped by quality and language,
+/// largest groups first
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LibraryBreakdown {
+    pub library_id: String,
+    pub resolutions: Vec<LibraryBreakdownEntry>,
+    pub video_codecs: Vec<LibraryBreakdownEntry>,
+    /// HDR format ('SDR' for video without HDR; null for audio-only files)
+    pub hdr_types: Vec<LibraryBreakdownEntry>,
+    pub audio_languages: Vec<LibraryBreakdownEntry>,
+    pub containers: Vec<LibraryBreakdownEntry>,
+}
+
 /// Type of library change event
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
 #[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
@@ -846,6 +1045,10 @@ pub enum CastDeviceType {
     GoogleHome,
     GoogleNestHub,
     AndroidTv,
+    SpeakerGroup,
+    AppleTv,
+    HomePod,
+    AirPlay,
     Unknown,
 }
 
@@ -857,6 +1060,10 @@ impl From<&str> for CastDeviceType {
             "google_home" => Self::GoogleHome,
             "google_nest_hub" => Self::GoogleNestHub,
             "android_tv" => Self::AndroidTv,
+            "speaker_group" => Self::SpeakerGroup,
+            "apple_tv" => Self::AppleTv,
+            "homepod" => Self::HomePod,
+            "airplay" => Self::AirPlay,
             _ => Self::Unknown,
         }
     }
@@ -898,6 +1105,12 @@ pub struct CastDevice {
     pub model: Option<String>,
     /// Device type
     pub device_type: CastDeviceType,
+    /// Whether this is a multi-room speaker group
+    pub is_group: bool,
+    /// Whether the device only plays audio (music and audiobooks)
+    pub is_audio_only: bool,
+    /// Whether the device is an AirPlay receiver rather than Google Cast
+    pub is_airplay: bool,
     /// Whether this is a favorite device
     pub is_favorite: bool,
     /// Whether this was manually added
@@ -910,6 +1123,7 @@ pub struct CastDevice {
 
 impl CastDevice {
     pub fn from_record(record: crate::db::CastDeviceRecord, is_connected: bool) -> Self {
+        let kind = crate::services::cast::CastDeviceType::from(record.device_type.as_str());
         Self {
             id: record.id.to_string(),
             name: record.name,
@@ -917,6 +1131,9 @@ impl CastDevice {
             port: record.port,
             model: record.model,
             device_type: CastDeviceType::from(record.device_type.as_str()),
+            is_group: kind.is_group(),
+            is_audio_only: kind.is_audio_only(),
+            is_airplay: kind.is_airplay(),
             is_favorite: record.is_favorite,
             is_manual: record.is_manual,
             is_connected,
@@ -1086,22 +1303,140 @@ pub struct User {
     pub email: Option<String>,
     /// User role
     pub role: Option<String>,
+    /// Display name
+    pub display_name: Option<String>,
+    /// Uploaded avatar, served from the artwork cache
+    pub avatar_url: Option<String>,
 }
 
-/// User preferences/settings
+/// User preferences/settings, stored per user so they follow them across
+/// devices
 #[derive(Debug, Clone, SimpleObject)]
 pub struct UserPreferences {
+    pub user_id: String,
     /// Preferred theme (light/dark/system)
     pub theme: String,
+    /// Library opened by default
+    pub default_library_id: Option<String>,
+    /// Poster grid density (compact/comfortable/spacious)
+    pub grid_density: String,
+    /// UI language as a BCP 47 tag, e.g. "en"
+    pub language: String,
     /// Enable notifications
     pub notifications_enabled: bool,
+    /// Notification categories not shown as toasts
+    pub muted_notification_categories: Vec<String>,
+    pub updated_at: String,
+}
+
+impl UserPreferences {
+    pub fn from_record(record: crate::db::UserPreferencesRecord) -> Self {
+        Self {
+            user_id: record.user_id.to_string(),
+            theme: record.theme,
+            default_library_id: record.default_library_id.map(|id| id.to_string()),
+            grid_density: record.grid_density,
+            language: record.language,
+            notifications_enabled: record.notifications_enabled,
+            muted_notification_categories: record.muted_notification_categories,
+            updated_at: record.updated_at.to_rfc3339(),
+        }
+    }
 }
 
-/// Input for updating user preferences
+/// Input for updating user preferences (omitted fields are unchanged)
 #[derive(Debug, InputObject)]
 pub struct UpdatePreferencesInput {
     pub theme: Option<String>,
+    /// Library ID, or an empty string to clear it
+    pub default_library_id: Option<String>,
+    pub grid_density: Option<String>,
+    pub language: Option<String>,
     pub notifications_enabled: Option<bool>,
+    pub muted_notification_categories: Option<Vec<String>>,
+}
+
+// ============================================================================
+// Roles
+// ============================================================================
+
+/// Something a role allows (a role with none is view-only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum RolePermission {
+    ManageLibraries,
+    ManageDownloads,
+    ManageUsers,
+    ManageServer,
+}
+
+impl From<crate::db::roles::Permission> for RolePermission {
+    fn from(permission: crate::db::roles::Permission) -> Self {
+        use crate::db::roles::Permission;
+        match permission {
+            Permission::ManageLibraries => Self::ManageLibraries,
+            Permission::ManageDownloads => Self::ManageDownloads,
+            Permission::ManageUsers => Self::ManageUsers,
+            Permission::ManageServer => Self::ManageServer,
+        }
+    }
+}
+
+impl From<RolePermission> for crate::db::roles::Permission {
+    fn from(permission: RolePermission) -> Self {
+        match permission {
+            RolePermission::ManageLibraries => Self::ManageLibraries,
+            RolePermission::ManageDownloads => Self::ManageDownloads,
+            RolePermission::ManageUsers => Self::ManageUsers,
+            RolePermission::ManageServer => Self::ManageServer,
+        }
+    }
+}
+
+/// A named set of permissions
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Role {
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<RolePermission>,
+    /// admin, member and guest can't be edited or deleted
+    pub is_builtin: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl Role {
+    pub fn from_record(record: crate::db::RoleRecord) -> Self {
+        Self {
+            name: record.name,
+            description: record.description,
+            permissions: record.permissions.into_iter().map(Into::into).collect(),
+            is_builtin: record.is_builtin,
+            created_at: record.created_at.to_rfc3339(),
+            updated_at: record.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, InputObject)]
+pub struct CreateRoleInput {
+    /// Lowercase letters, digits, '-' and '_'
+    pub name: String,
+    pub description: Option<String>,
+    pub permissions: Vec<RolePermission>,
+}
+
+/// Input for updating a custom role (omitted fields are unchanged)
+#[derive(Debug, InputObject)]
+pub struct UpdateRoleInput {
+    pub description: Option<String>,
+    pub permissions: Option<Vec<RolePermission>>,
+}
+
+#[derive(Debug, SimpleObject)]
+pub struct RoleResult {
+    pub success: bool,
+    pub role: Option<Role>,
+    pub error: Option<String>,
 }
 
 // ============================================================================
@@ -1190,6 +1525,130 @@ pub struct LibraryScanProgress {
     pub complete: bool,
 }
 
+// ============================================================================
+// Background Jobs
+// ============================================================================
+
+/// A long-running operation started by a mutation
+///
+/// Heavy mutations return a job ID immediately; poll `backgroundJob` or
+/// subscribe to `backgroundJobUpdated` to follow it to completion.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct BackgroundJob {
+    pub id: String,
+    /// What the job does, e.g. "consolidate_library"
+    pub kind: String,
+    /// "queued", "running", "completed", "failed" or "interrupted"
+    pub status: String,
+    /// Progress (0.0 - 1.0), when the job reports it
+    pub progress: Option<f64>,
+    pub message: Option<String>,
+    /// Input the job was started with
+    pub payload: Option<serde_json::Value>,
+    /// Output of a completed job (shape depends on the kind)
+    pub result: Option<serde_json::Value>,
+    pub error: Option<String>,
+    pub user_id: Option<String>,
+    pub is_finished: bool,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+impl BackgroundJob {
+    pub fn from_record(record: crate::db::BackgroundJobRecord) -> Self {
+        Self {
+            is_finished: record.is_finished(),
+            id: record.id.to_string(),
+            kind: record.kind,
+            status: record.status,
+            progress: record.progress,
+            message: record.message,
+            payload: record.payload,
+            result: record.result,
+            error: record.error,
+            user_id: record.user_id.map(|id| id.to_string()),
+            created_at: record.created_at.to_rfc3339(),
+            started_at: record.started_at.map(|t| t.to_rfc3339()),
+            finished_at: record.finished_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
+// ============================================================================
+// Artwork
+// ============================================================================
+
+/// Kind of item whose artwork can be refreshed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ArtworkEntityType {
+    Show,
+    Movie,
+    Album,
+}
+
+impl From<ArtworkEntityType> for crate::services::ArtworkEntity {
+    fn from(entity_type: ArtworkEntityType) -> Self {
+        match entity_type {
+            ArtworkEntityType::Show => Self::Show,
+            ArtworkEntityType::Movie => Self::Movie,
+            ArtworkEntityType::Album => Self::Album,
+        }
+    }
+}
+
+/// Result of refreshing one item's artwork
+#[derive(Debug, Clone, SimpleObject)]
+pub struct RefreshArtworkResult {
+    pub success: bool,
+    pub error: Option<String>,
+    /// Poster (or album cover) URL after the refresh
+    pub poster_url: Option<String>,
+    pub backdrop_url: Option<String>,
+    /// Images downloaded and cached again
+    pub images_updated: i32,
+    pub images_failed: i32,
+}
+
+// Returned by auditArtwork: the job's result has itemsChecked, imagesOk,
+// imagesRecached, imagesFetched and imagesFailed
+librarian_macros::job_result!(ArtworkAuditJobResult);
+
+// ============================================================================
+// Audit Log
+// ============================================================================
+
+/// A create, update or delete made through a mutation
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AuditLogEntry {
+    pub id: String,
+    /// Who made the change (null if the user was deleted)
+    pub user_id: Option<String>,
+    /// e.g. "library", "rss_feed"
+    pub entity_type: String,
+    pub entity_id: String,
+    /// "create", "update" or "delete"
+    pub action: String,
+    /// Changed fields, each `{ from, to }`; secrets show as "[redacted]"
+    pub changes: serde_json::Value,
+    pub created_at: String,
+}
+
+impl AuditLogEntry {
+    pub fn from_record(record: crate::db::AuditLogRecord) -> Self {
+        Self {
+            id: record.id.to_string(),
+            user_id: record.user_id.map(|id| id.to_string()),
+            entity_type: record.entity_type,
+            entity_id: record.entity_id.to_string(),
+            action: record.action,
+            changes: record.changes,
+            created_at: record.created_at.to_rfc3339(),
+        }
+    }
+}
+
 // ============================================================================
 // Settings Types
 // ============================================================================
@@ -1254,6 +1713,212 @@ pub struct SettingsResult {
     pub error: Option<String>,
 }
 
+// ============================================================================
+// Episode Air Notifications
+// ============================================================================
+
+/// Per-show lead-time override for episode air notifications
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ShowAirNotificationOverride {
+    /// TV show ID
+    pub tv_show_id: String,
+    /// TV show name
+    pub show_name: String,
+    /// Hours before air time to notify (0 = disabled for this show)
+    pub lead_hours: i32,
+}
+
+/// Upcoming episode air notification settings
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EpisodeAirNotificationSettings {
+    /// Hours before air time to notify (0 = disabled)
+    pub lead_hours: i32,
+    /// Number of same-night episodes that are coalesced into one digest (0 = never)
+    pub digest_threshold: i32,
+    /// Shows that override the global lead time
+    pub show_overrides: Vec<ShowAirNotificationOverride>,
+}
+
+/// Input for updating episode air notification settings
+#[derive(Debug, InputObject)]
+pub struct UpdateEpisodeAirNotificationSettingsInput {
+    /// Hours before air time to notify (0 = disabled)
+    pub lead_hours: Option<i32>,
+    /// Number of same-night episodes that are coalesced into one digest (0 = never)
+    pub digest_threshold: Option<i32>,
+}
+
+// ============================================================================
+// Import File Permissions
+// ============================================================================
+
+/// Ownership and mode applied to imported files (libraries can override each)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ImportPermissionSettings {
+    /// Owner uid (null = PUID environment variable, or leave unchanged)
+    pub file_uid: Option<u32>,
+    /// Group gid (null = PGID environment variable, or leave unchanged)
+    pub file_gid: Option<u32>,
+    /// Octal file mode, e.g. "664" (null = derived from UMASK, or leave unchanged)
+    pub file_mode: Option<String>,
+    /// uid from the PUID environment variable
+    pub env_uid: Option<u32>,
+    /// gid from the PGID environment variable
+    pub env_gid: Option<u32>,
+    /// File mode derived from the UMASK environment variable
+    pub env_file_mode: Option<String>,
+}
+
+/// Input for updating import permission settings
+#[derive(Debug, InputObject)]
+pub struct UpdateImportPermissionSettingsInput {
+    /// Owner uid (null = clear)
+    pub file_uid: Option<Option<u32>>,
+    /// Group gid (null = clear)
+    pub file_gid: Option<Option<u32>>,
+    /// Octal file mode, e.g. "664" (null or blank = clear)
+    pub file_mode: Option<Option<String>>,
+}
+
+// ============================================================================
+// Download Bandwidth Schedule
+// ============================================================================
+
+/// Day of the week
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum DayOfWeek {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<chrono::Weekday> for DayOfWeek {
+    fn from(day: chrono::Weekday) -> Self {
+        use chrono::Weekday;
+        match day {
+            Weekday::Mon => DayOfWeek::Monday,
+            Weekday::Tue => DayOfWeek::Tuesday,
+            Weekday::Wed => DayOfWeek::Wednesday,
+            Weekday::Thu => DayOfWeek::Thursday,
+            Weekday::Fri => DayOfWeek::Friday,
+            Weekday::Sat => DayOfWeek::Saturday,
+            Weekday::Sun => DayOfWeek::Sunday,
+        }
+    }
+}
+
+impl From<DayOfWeek> for chrono::Weekday {
+    fn from(day: DayOfWeek) -> Self {
+        use chrono::Weekday;
+        match day {
+            DayOfWeek::Monday => Weekday::Mon,
+            DayOfWeek::Tuesday => Weekday::Tue,
+            DayOfWeek::Wednesday => Weekday::Wed,
+            DayOfWeek::Thursday => Weekday::Thu,
+            DayOfWeek::Friday => Weekday::Fri,
+            DayOfWeek::Saturday => Weekday::Sat,
+            DayOfWeek::Sunday => Weekday::Sun,
+        }
+    }
+}
+
+/// Download speed limits in bytes/sec (0 = unlimited)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DownloadSpeedLimits {
+    pub torrent_download_limit: i64,
+    pub torrent_upload_limit: i64,
+    pub usenet_download_limit: i64,
+}
+
+impl From<crate::services::bandwidth::BandwidthLimits> for DownloadSpeedLimits {
+    fn from(limits: crate::services::bandwidth::BandwidthLimits) -> Self {
+        Self {
+            torrent_download_limit: limits.torrent_download as i64,
+            torrent_upload_limit: limits.torrent_upload as i64,
+            usenet_download_limit: limits.usenet_download as i64,
+        }
+    }
+}
+
+/// A time window that overrides the default download speed limits
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DownloadScheduleWindow {
+    /// Days the window starts on (empty = every day)
+    pub days: Vec<DayOfWeek>,
+    /// Start time in server local time, HH:MM
+    pub start: String,
+    /// End time, HH:MM; at or before the start means the window runs past midnight
+    pub end: String,
+    /// Torrent download limit in bytes/sec while active (null = default)
+    pub torrent_download_limit: Option<i64>,
+    /// Torrent upload limit in bytes/sec while active (null = default)
+    pub torrent_upload_limit: Option<i64>,
+    /// Usenet download limit in bytes/sec while active (null = default)
+    pub usenet_download_limit: Option<i64>,
+}
+
+impl From<crate::services::bandwidth::BandwidthWindow> for DownloadScheduleWindow {
+    fn from(window: crate::services::bandwidth::BandwidthWindow) -> Self {
+        Self {
+            days: window.days.into_iter().map(DayOfWeek::from).collect(),
+            start: window.start.format("%H:%M").to_string(),
+            end: window.end.format("%H:%M").to_string(),
+            torrent_download_limit: window.torrent_download.map(|v| v as i64),
+            torrent_upload_limit: window.torrent_upload.map(|v| v as i64),
+            usenet_download_limit: window.usenet_download.map(|v| v as i64),
+        }
+    }
+}
+
+/// Download speed limits and the time windows that override them
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DownloadSchedule {
+    /// Limits outside every window
+    pub defaults: DownloadSpeedLimits,
+    /// Windows in priority order; the first active one applies
+    pub windows: Vec<DownloadScheduleWindow>,
+    /// Limits in effect now
+    pub active: DownloadSpeedLimits,
+    /// Index of the window in effect now (null = defaults)
+    pub active_window: Option<i32>,
+}
+
+/// Input for a download schedule time window
+#[derive(Debug, InputObject)]
+pub struct DownloadScheduleWindowInput {
+    /// Days the window starts on (empty = every day)
+    #[graphql(default)]
+    pub days: Vec<DayOfWeek>,
+    /// Start time in server local time, HH:MM
+    pub start: String,
+    /// End time, HH:MM; at or before the start means the window runs past midnight
+    pub end: String,
+    /// Torrent download limit in bytes/sec while active (null = default, 0 = unlimited)
+    pub torrent_download_limit: Option<i64>,
+    /// Torrent upload limit in bytes/sec while active (null = default, 0 = unlimited)
+    pub torrent_upload_limit: Option<i64>,
+    /// Usenet download limit in bytes/sec while active (null = default, 0 = unlimited)
+    pub usenet_download_limit: Option<i64>,
+}
+
+/// Input for setting the download schedule
+#[derive(Debug, InputObject)]
+pub struct SetDownloadScheduleInput {
+    /// Default torrent download limit in bytes/sec (0 = unlimited, null = unchanged)
+    pub torrent_download_limit: Option<i64>,
+    /// Default torrent upload limit in bytes/sec (0 = unlimited, null = unchanged)
+    pub torrent_upload_limit: Option<i64>,
+    /// Default usenet download limit in bytes/sec (0 = unlimited, null = unchanged)
+    pub usenet_download_limit: Option<i64>,
+    /// Windows in priority order, replacing the current ones
+    pub windows: Vec<DownloadScheduleWindowInput>,
+}
+
 // ============================================================================
 // UPnP and Port Testing
 // ============================================================================
@@ -1467,6 +2132,7 @@ pub enum MonitorType {
 
 /// A TV show in a library
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct TvShow {
     pub id: String,
     pub library_id: String,
@@ -1519,6 +2185,31 @@ pub struct TvShow {
     pub release_group_whitelist_override: Option<Vec<String>>,
 }
 
+#[ComplexObject]
+impl TvShow {
+    /// Blurhash placeholder for the poster (null if not cached yet)
+    async fn poster_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.poster_url.as_deref()).await
+    }
+
+    /// Blurhash placeholder for the backdrop (null if not cached yet)
+    async fn backdrop_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.backdrop_url.as_deref()).await
+    }
+
+    /// Directors and top billed cast, from TMDB
+    async fn credits(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Credit>> {
+        let db = ctx.data::<crate::db::Database>()?;
+        let id = uuid::Uuid::parse_str(&self.id)?;
+        let credits = db
+            .people()
+            .list_for_show(id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        Ok(credits.into_iter().map(Credit::from).collect())
+    }
+}
+
 impl TvShow {
     /// Convert a TvShowRecord from the database to a TvShow GraphQL type
     pub fn from_record(r: crate::db::TvShowRecord) -> Self {
@@ -1648,6 +2339,9 @@ pub struct TvShowResult {
     pub success: bool,
     pub tv_show: Option<TvShow>,
     pub error: Option<String>,
+    /// Metadata that couldn't be fetched yet ("tmdb", "tvdb", "episodes",
+    /// "artwork"); it is retried in the background
+    pub missing_metadata: Vec<String>,
 }
 
 // ============================================================================
@@ -1657,7 +2351,7 @@ pub struct TvShowResult {
 /// Filter input for TV shows query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct TvShowWhereInput {
-    /// Filter by name (contains, case-insensitive)
+    /// Filter by name
     pub name: Option<crate::graphql::filters::StringFilter>,
     /// Filter by year
     pub year: Option<crate::graphql::filters::IntFilter>,
@@ -1730,6 +2424,7 @@ impl From<&str> for MovieStatus {
 
 /// A movie in a library
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct Movie {
     pub id: String,
     pub library_id: String,
@@ -1766,6 +2461,31 @@ pub struct Movie {
     pub download_progress: Option<f32>,
 }
 
+#[ComplexObject]
+impl Movie {
+    /// Blurhash placeholder for the poster (null if not cached yet)
+    async fn poster_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.poster_url.as_deref()).await
+    }
+
+    /// Blurhash placeholder for the backdrop (null if not cached yet)
+    async fn backdrop_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.backdrop_url.as_deref()).await
+    }
+
+    /// Directors and top billed cast, from TMDB
+    async fn credits(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Credit>> {
+        let db = ctx.data::<crate::db::Database>()?;
+        let id = uuid::Uuid::parse_str(&self.id)?;
+        let credits = db
+            .people()
+            .list_for_movie(id)
+            .await
+            .map_err(|e| async_graphql::Error::new(e.to_string()))?;
+        Ok(credits.into_iter().map(Credit::from).collect())
+    }
+}
+
 /// Movie search result from TMDB
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct MovieSearchResult {
@@ -1782,15 +2502,85 @@ pub struct MovieSearchResult {
     pub popularity: Option<f64>,
 }
 
-/// Input for adding a movie to a library
-#[derive(Debug, InputObject)]
-pub struct AddMovieInput {
-    /// TMDB movie ID
-    pub tmdb_id: i32,
-    /// Whether to monitor for releases
-    pub monitored: Option<bool>,
-    /// Custom path within the library
-    pub path: Option<String>,
+impl From<crate::services::MovieSearchResult> for MovieSearchResult {
+    fn from(m: crate::services::MovieSearchResult) -> Self {
+        Self {
+            provider: "tmdb".to_string(),
+            provider_id: m.provider_id as i32,
+            title: m.title,
+            original_title: m.original_title,
+            year: m.year,
+            overview: m.overview,
+            poster_url: m.poster_url,
+            backdrop_url: m.backdrop_url,
+            imdb_id: m.imdb_id,
+            vote_average: m.vote_average,
+            popularity: m.popularity,
+        }
+    }
+}
+
+// ============================================================================
+// People Types
+// ============================================================================
+
+/// A person's credit on a movie or show
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Credit {
+    pub person_id: String,
+    pub name: String,
+    pub profile_url: Option<String>,
+    /// 'cast' or 'director'
+    pub role: String,
+    /// Character played, for cast
+    pub character: Option<String>,
+    /// Billing position, for cast
+    pub billing_order: Option<i32>,
+}
+
+impl From<crate::db::CreditRecord> for Credit {
+    fn from(record: crate::db::CreditRecord) -> Self {
+        Self {
+            person_id: record.person.id.to_string(),
+            name: record.person.name,
+            profile_url: record.person.profile_url,
+            role: record.role,
+            character: record.character,
+            billing_order: record.billing_order,
+        }
+    }
+}
+
+/// An actor or director, with their credits in the user's libraries
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Person {
+    pub id: String,
+    pub tmdb_id: i32,
+    pub name: String,
+    pub profile_url: Option<String>,
+    /// Credits on the user's movies and shows, newest first
+    pub filmography: Vec<FilmographyEntry>,
+}
+
+/// One of a person's credits; exactly one of movie and tv_show is set
+#[derive(Debug, Clone, SimpleObject)]
+pub struct FilmographyEntry {
+    /// 'cast' or 'director'
+    pub role: String,
+    pub character: Option<String>,
+    pub movie: Option<Movie>,
+    pub tv_show: Option<TvShow>,
+}
+
+/// Input for adding a movie to a library
+#[derive(Debug, InputObject)]
+pub struct AddMovieInput {
+    /// TMDB movie ID
+    pub tmdb_id: i32,
+    /// Whether to monitor for releases
+    pub monitored: Option<bool>,
+    /// Custom path within the library
+    pub path: Option<String>,
 }
 
 /// Input for updating a movie
@@ -1824,7 +2614,7 @@ pub struct MovieResult {
 /// Filter input for movies query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct MovieWhereInput {
-    /// Filter by title (contains, case-insensitive)
+    /// Filter by title
     pub title: Option<crate::graphql::filters::StringFilter>,
     /// Filter by year
     pub year: Option<crate::graphql::filters::IntFilter>,
@@ -1836,6 +2626,12 @@ pub struct MovieWhereInput {
     pub status: Option<crate::graphql::filters::StringFilter>,
     /// Filter by download status (missing, wanted, downloading, downloaded, suboptimal, ignored)
     pub download_status: Option<crate::graphql::filters::StringFilter>,
+    /// Filter by TMDB rating (0-10)
+    pub tmdb_rating: Option<crate::graphql::filters::FloatFilter>,
+    /// Filter by genres
+    pub genres: Option<crate::graphql::filters::ListFilter>,
+    /// Filter by cast members
+    pub cast_names: Option<crate::graphql::filters::ListFilter>,
 }
 
 /// Sortable fields for movies
@@ -1899,7 +2695,7 @@ impl From<crate::db::ArtistRecord> for Artist {
 /// Filter input for artists query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct ArtistWhereInput {
-    /// Filter by name (contains, case-insensitive)
+    /// Filter by name
     pub name: Option<crate::graphql::filters::StringFilter>,
 }
 
@@ -1927,6 +2723,7 @@ crate::define_connection!(ArtistConnection, ArtistEdge, Artist);
 
 /// An album in a music library
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct Album {
     pub id: String,
     pub artist_id: String,
@@ -1951,6 +2748,14 @@ pub struct Album {
     pub downloaded_track_count: Option<i32>,
 }
 
+#[ComplexObject]
+impl Album {
+    /// Blurhash placeholder for the cover art (null if not cached yet)
+    async fn cover_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.cover_url.as_deref()).await
+    }
+}
+
 impl From<crate::db::AlbumRecord> for Album {
     fn from(r: crate::db::AlbumRecord) -> Self {
         Self {
@@ -2015,7 +2820,7 @@ pub struct AlbumResult {
 /// Filter input for albums query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct AlbumWhereInput {
-    /// Filter by name (contains, case-insensitive)
+    /// Filter by name
     pub name: Option<crate::graphql::filters::StringFilter>,
     /// Filter by year
     pub year: Option<crate::graphql::filters::IntFilter>,
@@ -2149,7 +2954,7 @@ impl From<crate::db::TrackWithStatus> for TrackWithStatus {
 /// Filter input for tracks query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct TrackWhereInput {
-    /// Filter by title (contains, case-insensitive)
+    /// Filter by title
     pub title: Option<crate::graphql::filters::StringFilter>,
     /// Filter by artist name
     pub artist_name: Option<crate::graphql::filters::StringFilter>,
@@ -2241,6 +3046,7 @@ impl From<crate::db::AudiobookAuthorRecord> for AudiobookAuthor {
 
 /// An audiobook
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct Audiobook {
     pub id: String,
     pub author_id: Option<String>,
@@ -2266,6 +3072,14 @@ pub struct Audiobook {
     pub downloaded_chapter_count: Option<i32>,
 }
 
+#[ComplexObject]
+impl Audiobook {
+    /// Blurhash placeholder for the cover art (null if not cached yet)
+    async fn cover_blurhash(&self, ctx: &Context<'_>) -> Option<String> {
+        artwork_blurhash(ctx, self.cover_url.as_deref()).await
+    }
+}
+
 impl From<crate::db::AudiobookRecord> for Audiobook {
     fn from(r: crate::db::AudiobookRecord) -> Self {
         Self {
@@ -2330,7 +3144,7 @@ pub struct AudiobookResult {
 /// Filter input for audiobooks query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct AudiobookWhereInput {
-    /// Filter by title (contains, case-insensitive)
+    /// Filter by title
     pub title: Option<crate::graphql::filters::StringFilter>,
     /// Filter by whether audiobook has files
     pub has_files: Option<crate::graphql::filters::BoolFilter>,
@@ -2363,7 +3177,7 @@ crate::define_connection!(AudiobookConnection, AudiobookEdge, Audiobook);
 /// Filter input for audiobook authors query
 #[derive(Debug, Clone, Default, InputObject)]
 pub struct AudiobookAuthorWhereInput {
-    /// Filter by name (contains, case-insensitive)
+    /// Filter by name
     pub name: Option<crate::graphql::filters::StringFilter>,
 }
 
@@ -2761,7 +3575,15 @@ pub struct RssFeed {
     pub name: String,
     pub url: String,
     pub enabled: bool,
+    /// Interval a new feed starts polling at
     pub poll_interval_minutes: i32,
+    /// Bounds the adaptive interval stays within
+    pub min_poll_interval_minutes: i32,
+    pub max_poll_interval_minutes: i32,
+    /// Interval currently used, adapted to how often items arrive
+    pub current_poll_interval_minutes: i32,
+    /// The feed's own <ttl>, which the interval never goes below
+    pub ttl_minutes: Option<i32>,
     pub last_polled_at: Option<String>,
     pub last_successful_at: Option<String>,
     pub last_error: Option<String>,
@@ -2776,6 +3598,8 @@ pub struct CreateRssFeedInput {
     pub url: String,
     pub enabled: Option<bool>,
     pub poll_interval_minutes: Option<i32>,
+    pub min_poll_interval_minutes: Option<i32>,
+    pub max_poll_interval_minutes: Option<i32>,
 }
 
 /// Input for updating an RSS feed
@@ -2786,6 +3610,8 @@ pub struct UpdateRssFeedInput {
     pub url: Option<String>,
     pub enabled: Option<bool>,
     pub poll_interval_minutes: Option<i32>,
+    pub min_poll_interval_minutes: Option<i32>,
+    pub max_poll_interval_minutes: Option<i32>,
 }
 
 /// Result of RSS feed mutation
@@ -2865,6 +3691,28 @@ pub enum PostDownloadAction {
     Hardlink,
 }
 
+/// What a scan does when library files are deleted from disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum MissingFilePolicy {
+    /// Keep the files listed and notify (nothing is re-downloaded)
+    Flag,
+    /// Remove the files and stop monitoring the movie or show
+    Unmonitor,
+    /// Remove the files so monitored items are wanted and re-downloaded
+    Rewant,
+}
+
+impl MissingFilePolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissingFilePolicy::Flag => "flag",
+            MissingFilePolicy::Unmonitor => "unmonitor",
+            MissingFilePolicy::Rewant => "rewant",
+        }
+    }
+}
+
 /// Enhanced library with additional fields
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct LibraryFull {
@@ -2909,6 +3757,16 @@ pub struct LibraryFull {
     pub release_group_blacklist: Vec<String>,
     /// Whitelisted release groups (if set, only allow these).
     pub release_group_whitelist: Vec<String>,
+    /// Scanner exclude globs, relative to the library root (e.g. "**/Extras/**").
+    pub exclude_patterns: Vec<String>,
+    /// Owner uid for imported files (null = global setting)
+    pub file_uid: Option<u32>,
+    /// Group gid for imported files (null = global setting)
+    pub file_gid: Option<u32>,
+    /// Octal mode for imported files, e.g. "664" (null = global setting)
+    pub file_mode: Option<String>,
+    /// What scans do with files deleted from disk
+    pub missing_file_policy: MissingFilePolicy,
 }
 
 impl LibraryFull {
@@ -2958,6 +3816,15 @@ impl LibraryFull {
             allowed_sources: r.allowed_sources,
             release_group_blacklist: r.release_group_blacklist,
             release_group_whitelist: r.release_group_whitelist,
+            exclude_patterns: r.exclude_patterns,
+            file_uid: r.file_uid,
+            file_gid: r.file_gid,
+            file_mode: r.file_mode,
+            missing_file_policy: match r.missing_file_policy.as_str() {
+                "unmonitor" => MissingFilePolicy::Unmonitor,
+                "rewant" => MissingFilePolicy::Rewant,
+                _ => MissingFilePolicy::Flag,
+            },
         }
     }
 }
@@ -3001,6 +3868,16 @@ pub struct CreateLibraryInput {
     pub release_group_blacklist: Option<Vec<String>>,
     /// Whitelisted release groups
     pub release_group_whitelist: Option<Vec<String>>,
+    /// Scanner exclude globs (e.g. "**/Extras/**", "*.sample.*", "@eaDir")
+    pub exclude_patterns: Option<Vec<String>>,
+    /// Owner uid for imported files (null = global setting)
+    pub file_uid: Option<u32>,
+    /// Group gid for imported files (null = global setting)
+    pub file_gid: Option<u32>,
+    /// Octal mode for imported files, e.g. "664" (null = global setting)
+    pub file_mode: Option<String>,
+    /// What scans do with files deleted from disk (default: FLAG)
+    pub missing_file_policy: Option<MissingFilePolicy>,
 }
 
 /// Input for updating a library
@@ -3041,6 +3918,16 @@ pub struct UpdateLibraryInput {
     pub release_group_blacklist: Option<Vec<String>>,
     /// Whitelisted release groups
     pub release_group_whitelist: Option<Vec<String>>,
+    /// Scanner exclude globs (e.g. "**/Extras/**", "*.sample.*", "@eaDir")
+    pub exclude_patterns: Option<Vec<String>>,
+    /// Override owner uid for imported files (null = inherit global setting)
+    pub file_uid: Option<Option<u32>>,
+    /// Override group gid for imported files (null = inherit global setting)
+    pub file_gid: Option<Option<u32>>,
+    /// Override octal file mode, e.g. "664" (null = inherit global setting)
+    pub file_mode: Option<Option<String>>,
+    /// What scans do with files deleted from disk
+    pub missing_file_policy: Option<MissingFilePolicy>,
 }
 
 // ============================================================================
@@ -3301,6 +4188,150 @@ impl PlaybackSession {
     }
 }
 
+/// A user's watch state for one item
+///
+/// Published as `watchStateChanged` whenever it changes so other devices
+/// can update their progress bars and watched marks.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct WatchState {
+    pub user_id: String,
+    pub content_type: PlaybackContentType,
+    /// The episode/movie/track/audiobook ID
+    pub content_id: String,
+    pub media_file_id: Option<String>,
+    /// Resume position in seconds
+    pub position: f64,
+    pub duration: Option<f64>,
+    /// 0.0 to 1.0
+    pub progress: f32,
+    pub is_watched: bool,
+    pub last_watched_at: String,
+}
+
+impl WatchState {
+    /// `None` for rows with an unknown content type or no content ID
+    pub fn from_record(record: crate::db::WatchProgressRecord) -> Option<Self> {
+        use time::format_description::well_known::Rfc3339;
+
+        Some(Self {
+            user_id: record.user_id.to_string(),
+            content_type: PlaybackContentType::from_str(&record.content_type)?,
+            content_id: record.content_id()?.to_string(),
+            media_file_id: record.media_file_id.map(|id| id.to_string()),
+            position: record.current_position,
+            duration: record.duration,
+            progress: record.progress_percent,
+            is_watched: record.is_watched,
+            last_watched_at: record.last_watched_at.format(&Rfc3339).unwrap_or_default(),
+        })
+    }
+
+    /// State of an item with no progress row, e.g. after marking it unwatched
+    pub fn unwatched(user_id: &str, content_type: PlaybackContentType, content_id: &str) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            content_type,
+            content_id: content_id.to_string(),
+            media_file_id: None,
+            position: 0.0,
+            duration: None,
+            progress: 0.0,
+            is_watched: false,
+            last_watched_at: String::new(),
+        }
+    }
+}
+
+/// An item to resume, with the episode or movie it belongs to
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ContinueWatchingItem {
+    pub state: WatchState,
+    /// Set for episodes
+    pub episode: Option<Episode>,
+    /// Show of the episode
+    pub show: Option<TvShow>,
+    /// Set for movies
+    pub movie: Option<Movie>,
+}
+
+/// The next episode to watch of a show the user is following along
+#[derive(Debug, Clone, SimpleObject)]
+pub struct NextUpItem {
+    pub show: TvShow,
+    pub episode: Episode,
+}
+
+/// A movie found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct MovieSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub movie: Movie,
+}
+
+/// A TV show found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ShowSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub show: TvShow,
+}
+
+/// An episode found by global search, with its show
+#[derive(Debug, Clone, SimpleObject)]
+pub struct EpisodeSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub episode: Episode,
+    pub show: TvShow,
+}
+
+/// An album found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AlbumSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub album: Album,
+}
+
+/// An artist found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ArtistSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub artist: Artist,
+}
+
+/// An audiobook found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AudiobookSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub audiobook: Audiobook,
+}
+
+/// A person credited on movies or audiobooks, found by global search
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PersonSearchHit {
+    /// How well it matched, 0.0 to 1.0
+    pub score: f64,
+    pub name: String,
+    /// 'cast', 'director', 'author' and/or 'narrator'
+    pub roles: Vec<String>,
+}
+
+/// A global search hit of any type
+#[derive(Debug, Clone, Union)]
+pub enum GlobalSearchHit {
+    Movie(MovieSearchHit),
+    Show(ShowSearchHit),
+    Episode(EpisodeSearchHit),
+    Album(AlbumSearchHit),
+    Artist(ArtistSearchHit),
+    Audiobook(AudiobookSearchHit),
+    Person(PersonSearchHit),
+}
+
 /// Input for starting playback (unified for all content types)
 #[derive(Debug, InputObject)]
 pub struct StartPlaybackInput {
@@ -3316,6 +4347,11 @@ pub struct StartPlaybackInput {
     pub start_position: Option<f64>,
     /// Duration in seconds
     pub duration: Option<f64>,
+    /// Stream lease from an earlier startPlayback on this client, reused
+    /// instead of opening a new one
+    pub stream_id: Option<String>,
+    /// Client description shown to admins (e.g. "Firefox on Linux")
+    pub client_name: Option<String>,
 }
 
 /// Input for updating playback position
@@ -3340,21 +4376,78 @@ pub struct PlaybackResult {
     pub success: bool,
     /// The playback session
     pub session: Option<PlaybackSession>,
+    /// Stream lease the client must heartbeat while playing (set by startPlayback)
+    pub stream_id: Option<String>,
     /// Error message if failed
     pub error: Option<String>,
 }
 
+/// Result of a playback stream heartbeat
+#[derive(Debug, Clone, SimpleObject)]
+pub struct PlaybackHeartbeatResult {
+    pub success: bool,
+    /// False once the stream has ended; the client should stop playing
+    pub active: bool,
+    /// Why the stream ended ("stopped", "expired" or "terminated")
+    pub end_reason: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A stream currently being played by some client (admin view)
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ActivePlaybackStream {
+    /// Stream lease ID
+    pub id: String,
+    pub user_id: String,
+    pub username: Option<String>,
+    pub content_type: Option<String>,
+    pub media_file_id: Option<String>,
+    pub client_name: Option<String>,
+    /// Current position in seconds
+    pub current_position: Option<f64>,
+    /// Duration in seconds
+    pub duration: Option<f64>,
+    pub is_playing: bool,
+    pub started_at: String,
+    pub last_heartbeat_at: String,
+}
+
+impl ActivePlaybackStream {
+    pub fn from_record(record: crate::db::PlaybackStreamRecord) -> Self {
+        Self {
+            id: record.id.to_string(),
+            user_id: record.user_id.to_string(),
+            username: record.username,
+            content_type: record.content_type,
+            media_file_id: record.media_file_id.map(|id| id.to_string()),
+            client_name: record.client_name,
+            current_position: record.current_position,
+            duration: record.duration,
+            is_playing: record.is_playing,
+            started_at: record.started_at.to_rfc3339(),
+            last_heartbeat_at: record.last_heartbeat_at.to_rfc3339(),
+        }
+    }
+}
+
 /// Playback settings (configurable by user)
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
 pub struct PlaybackSettings {
     /// How often to sync watch progress to database (in seconds)
     pub sync_interval_seconds: i32,
+    /// Most streams a user may play at once (0 = unlimited)
+    pub max_concurrent_streams: i32,
+    /// Seconds without a heartbeat before a stream is considered abandoned
+    pub heartbeat_timeout_seconds: i32,
 }
 
 impl Default for PlaybackSettings {
     fn default() -> Self {
         Self {
             sync_interval_seconds: 15,
+            max_concurrent_streams: crate::jobs::playback_streams::DEFAULT_MAX_STREAMS,
+            heartbeat_timeout_seconds:
+                crate::jobs::playback_streams::DEFAULT_HEARTBEAT_TIMEOUT_SECS,
         }
     }
 }
@@ -3364,6 +4457,227 @@ impl Default for PlaybackSettings {
 pub struct UpdatePlaybackSettingsInput {
     /// How often to sync watch progress to database (in seconds, 5-60)
     pub sync_interval_seconds: Option<i32>,
+    /// Most streams a user may play at once (0 = unlimited, max 20)
+    pub max_concurrent_streams: Option<i32>,
+    /// Seconds without a heartbeat before a stream is abandoned (30-600)
+    pub heartbeat_timeout_seconds: Option<i32>,
+}
+
+// ============================================================================
+// Transcode Cache Types
+// ============================================================================
+
+/// Transcode cache usage
+#[derive(Debug, Clone, SimpleObject)]
+pub struct TranscodeCacheStats {
+    pub total_bytes: i64,
+    /// Size cap in GB (0 = unlimited)
+    pub max_size_gb: f64,
+    /// Number of transcode sessions in the cache
+    pub session_count: i32,
+    /// Sessions currently being played (never evicted)
+    pub pinned_count: i32,
+    pub pinned_bytes: i64,
+    /// Last use of the least recently used session
+    pub oldest_entry_at: Option<String>,
+}
+
+impl TranscodeCacheStats {
+    pub fn from_stats(stats: crate::services::transcode_cache::CacheStats, max_size_gb: f64) -> Self {
+        Self {
+            total_bytes: stats.total_bytes as i64,
+            max_size_gb,
+            session_count: stats.session_count as i32,
+            pinned_count: stats.pinned_count as i32,
+            pinned_bytes: stats.pinned_bytes as i64,
+            oldest_entry_at: stats
+                .oldest_entry
+                .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339()),
+        }
+    }
+}
+
+/// Result of clearing the transcode cache
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ClearTranscodeCacheResult {
+    pub success: bool,
+    pub sessions_removed: i32,
+    pub bytes_freed: i64,
+    /// Sessions left in place because they are being played
+    pub sessions_skipped: i32,
+    pub error: Option<String>,
+}
+
+/// Outcome of an environment diagnostics check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl From<crate::services::diagnostics::DiagnosticStatus> for DiagnosticStatus {
+    fn from(status: crate::services::diagnostics::DiagnosticStatus) -> Self {
+        use crate::services::diagnostics::DiagnosticStatus as Status;
+        match status {
+            Status::Pass => Self::Pass,
+            Status::Warn => Self::Warn,
+            Status::Fail => Self::Fail,
+        }
+    }
+}
+
+/// One environment diagnostics check
+#[derive(Debug, Clone, SimpleObject)]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "path.downloads" or "ffmpeg"
+    pub id: String,
+    pub title: String,
+    pub status: DiagnosticStatus,
+    pub message: String,
+    /// How to fix a warning or failure
+    pub hint: Option<String>,
+}
+
+impl From<crate::services::diagnostics::DiagnosticCheck> for DiagnosticCheck {
+    fn from(check: crate::services::diagnostics::DiagnosticCheck) -> Self {
+        Self {
+            id: check.id,
+            title: check.title,
+            status: check.status.into(),
+            message: check.message,
+            hint: check.hint,
+        }
+    }
+}
+
+/// Health of an external service at its last probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum ExternalServiceHealth {
+    Up,
+    /// Answering, but slowly
+    Degraded,
+    Down,
+}
+
+/// An external service's last probe result
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExternalServiceStatus {
+    /// Stable identifier, e.g. "tmdb" or "indexer:<id>"
+    pub id: String,
+    /// "tmdb", "tvdb", "opensubtitles", "indexer", "usenet", "smtp" or "cast"
+    pub kind: String,
+    pub name: String,
+    pub status: ExternalServiceHealth,
+    pub latency_ms: Option<i64>,
+    /// Why the last probe failed
+    pub last_error: Option<String>,
+    pub last_checked_at: String,
+    /// When the status last changed
+    pub last_changed_at: String,
+}
+
+impl From<crate::db::ExternalServiceStatusRecord> for ExternalServiceStatus {
+    fn from(record: crate::db::ExternalServiceStatusRecord) -> Self {
+        use crate::services::service_health::ServiceStatus;
+        let status = match ServiceStatus::parse(&record.status) {
+            Some(ServiceStatus::Up) => ExternalServiceHealth::Up,
+            Some(ServiceStatus::Degraded) => ExternalServiceHealth::Degraded,
+            _ => ExternalServiceHealth::Down,
+        };
+        Self {
+            id: record.id,
+            kind: record.kind,
+            name: record.name,
+            status,
+            latency_ms: record.latency_ms,
+            last_error: record.last_error,
+            last_checked_at: record.last_checked_at.to_rfc3339(),
+            last_changed_at: record.last_changed_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Health of every configured external service
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ExternalServicesStatus {
+    pub services: Vec<ExternalServiceStatus>,
+    /// Number of services that are down
+    pub down_count: i32,
+    /// Number of services that are slow to answer
+    pub degraded_count: i32,
+}
+
+impl From<Vec<crate::db::ExternalServiceStatusRecord>> for ExternalServicesStatus {
+    fn from(records: Vec<crate::db::ExternalServiceStatusRecord>) -> Self {
+        let services: Vec<ExternalServiceStatus> = records.into_iter().map(Into::into).collect();
+        let count = |health| services.iter().filter(|s| s.status == health).count() as i32;
+        Self {
+            down_count: count(ExternalServiceHealth::Down),
+            degraded_count: count(ExternalServiceHealth::Degraded),
+            services,
+        }
+    }
+}
+
+/// A Librarian release newer than the running server
+#[derive(Debug, Clone, SimpleObject)]
+pub struct UpdateInfo {
+    /// Version of the running server
+    pub current_version: String,
+    /// Version of the new release
+    pub version: String,
+    pub name: Option<String>,
+    /// Release page on GitHub
+    pub url: String,
+    pub published_at: Option<String>,
+    /// Start of the release notes
+    pub changelog: String,
+}
+
+impl From<crate::jobs::update_check::ReleaseInfo> for UpdateInfo {
+    fn from(release: crate::jobs::update_check::ReleaseInfo) -> Self {
+        Self {
+            current_version: crate::jobs::update_check::current_version().to_string(),
+            version: release.version,
+            name: release.name,
+            url: release.url,
+            published_at: release.published_at,
+            changelog: release.changelog,
+        }
+    }
+}
+
+/// Update check settings and the result of the last check
+#[derive(Debug, Clone, SimpleObject)]
+pub struct UpdateCheckSettings {
+    /// Whether the server checks GitHub for new releases every six hours
+    pub enabled: bool,
+    /// Latest release found by the last check
+    pub latest_version: Option<String>,
+    pub last_checked_at: Option<String>,
+    /// Why `applyUpdate` can't be used on this install (e.g. Docker), or null
+    pub self_update_unavailable_reason: Option<String>,
+}
+
+/// Result of checking for updates on demand
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CheckForUpdatesResult {
+    pub success: bool,
+    /// The newer release, or null when up to date
+    pub update: Option<UpdateInfo>,
+    pub error: Option<String>,
+}
+
+/// Result of installing an update in place
+#[derive(Debug, Clone, SimpleObject)]
+pub struct ApplyUpdateResult {
+    pub success: bool,
+    /// Version being installed; the server restarts into it shortly after
+    pub version: Option<String>,
+    pub error: Option<String>,
 }
 
 // ============================================================================
@@ -3451,10 +4765,94 @@ pub struct LibraryUpcomingShow {
     pub library_id: String,
 }
 
+// ============================================================================
+// Recommendation Types (for home page)
+// ============================================================================
+
+/// Kind of recommended item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum RecommendationKind {
+    Movie,
+    TvShow,
+}
+
+/// Where a recommendation came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum RecommendationSource {
+    /// Already in the user's library, not yet watched
+    Library,
+    /// Suggested by TMDB based on watch history
+    Tmdb,
+}
+
+/// A watch-next recommendation
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct Recommendation {
+    /// Movie or TV show
+    pub kind: RecommendationKind,
+    /// Library item or external suggestion
+    pub source: RecommendationSource,
+    /// Title
+    pub title: String,
+    /// Release year
+    pub year: Option<i32>,
+    /// Overview/summary
+    pub overview: Option<String>,
+    /// Poster URL
+    pub poster_url: Option<String>,
+    /// TMDB ID (used to add external suggestions to a library)
+    pub tmdb_id: Option<i32>,
+    /// Movie or show ID in our database (library items only)
+    pub library_item_id: Option<String>,
+    /// Library ID the item belongs to (library items only)
+    pub library_id: Option<String>,
+    /// Why this was recommended (e.g. "Because you watched Heat")
+    pub reason: Option<String>,
+}
+
+impl From<crate::services::Recommendation> for Recommendation {
+    fn from(r: crate::services::Recommendation) -> Self {
+        Self {
+            kind: match r.kind {
+                crate::services::RecommendationKind::Movie => RecommendationKind::Movie,
+                crate::services::RecommendationKind::TvShow => RecommendationKind::TvShow,
+            },
+            source: match r.source {
+                crate::services::RecommendationSource::Library => RecommendationSource::Library,
+                crate::services::RecommendationSource::Tmdb => RecommendationSource::Tmdb,
+            },
+            title: r.title,
+            year: r.year,
+            overview: r.overview,
+            poster_url: r.poster_url,
+            tmdb_id: r.tmdb_id,
+            library_item_id: r.library_item_id.map(|id| id.to_string()),
+            library_id: r.library_id.map(|id| id.to_string()),
+            reason: r.reason,
+        }
+    }
+}
+
 // ============================================================================
 // Helpers
 // ============================================================================
 
+/// Look up the blurhash for an internal artwork URL
+///
+/// External URLs (e.g. TMDB fallbacks when caching failed) have no blurhash.
+pub(crate) async fn artwork_blurhash(ctx: &Context<'_>, url: Option<&str>) -> Option<String> {
+    let (entity_type, entity_id, artwork_type) =
+        crate::services::artwork::parse_artwork_url(url?)?;
+    let db = ctx.data::<crate::db::Database>().ok()?;
+    db.artwork()
+        .get_blurhash(entity_type, entity_id, artwork_type)
+        .await
+        .ok()
+        .flatten()
+}
+
 /// Format bytes as human-readable string
 pub fn format_bytes(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -3481,6 +4879,7 @@ pub fn format_bytes(bytes: u64) -> String {
 
 /// A media file in a library
 #[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+#[graphql(complex)]
 pub struct MediaFile {
     /// Unique identifier
     pub id: String,
@@ -3548,6 +4947,88 @@ pub struct MediaFile {
     pub matched_at: Option<String>,
 }
 
+/// The download a media file was imported from
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct MediaFileDownloadSource {
+    /// Source type: torrent or usenet
+    pub source_type: String,
+    /// Torrent or usenet download ID
+    pub download_id: String,
+    /// Torrent or NZB name
+    pub name: String,
+    /// Torrent info hash (null for usenet)
+    pub info_hash: Option<String>,
+    /// Download state (live from the torrent client when available)
+    pub state: String,
+    /// Whether the torrent is still seeding, so deleting the file would
+    /// break it
+    pub is_seeding: bool,
+}
+
+#[ComplexObject]
+impl MediaFile {
+    /// The torrent or usenet download this file was imported from (null for
+    /// scanned or manually added files, or once the download is removed)
+    async fn download_source(&self, ctx: &Context<'_>) -> Option<MediaFileDownloadSource> {
+        let db = ctx.data::<crate::db::Database>().ok()?;
+        let media_file_id = uuid::Uuid::parse_str(&self.id).ok()?;
+
+        // Files imported before sources were recorded on pending matches
+        // only have the torrent_files link
+        let pending = db
+            .pending_file_matches()
+            .get_by_media_file(media_file_id)
+            .await
+            .ok()
+            .flatten();
+        let (source_type, source_id) = match pending {
+            Some(pending) => (pending.source_type, pending.source_id?),
+            None => {
+                let file = db
+                    .torrent_files()
+                    .get_by_media_file(media_file_id)
+                    .await
+                    .ok()
+                    .flatten()?;
+                ("torrent".to_string(), file.torrent_id)
+            }
+        };
+
+        match source_type.as_str() {
+            "torrent" => {
+                let torrent = db.torrents().get_by_id(source_id).await.ok().flatten()?;
+                let live = match ctx.data_opt::<std::sync::Arc<crate::services::TorrentService>>() {
+                    Some(service) => service.get_torrent_info_by_hash(&torrent.info_hash).await,
+                    None => None,
+                };
+                Some(MediaFileDownloadSource {
+                    source_type,
+                    download_id: torrent.id.to_string(),
+                    name: torrent.name,
+                    state: live
+                        .as_ref()
+                        .map(|t| t.state.to_string())
+                        .unwrap_or(torrent.state),
+                    is_seeding: live.is_some_and(|t| t.state == ServiceTorrentState::Seeding),
+                    info_hash: Some(torrent.info_hash),
+                })
+            }
+            "usenet" => {
+                let download = db.usenet_downloads().get(source_id).await.ok().flatten()?;
+                Some(MediaFileDownloadSource {
+                    source_type,
+                    download_id: download.id.to_string(),
+                    name: download.nzb_name,
+                    info_hash: None,
+                    state: download.state,
+                    is_seeding: false,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
 impl MediaFile {
     pub fn from_record(record: crate::db::MediaFileRecord) -> Self {
         let is_manual = record.match_type.as_deref() == Some("manual");
@@ -3765,6 +5246,14 @@ pub struct AudioStreamInfo {
     pub is_default: bool,
     /// Whether this is a commentary track
     pub is_commentary: bool,
+    /// EBU R128 integrated loudness in LUFS (null unless loudness analysis is on)
+    pub integrated_loudness: Option<f64>,
+    /// Loudness range in LU
+    pub loudness_range: Option<f64>,
+    /// True peak in dBTP
+    pub true_peak: Option<f64>,
+    /// ReplayGain-style gain in dB that brings the stream to -18 LUFS
+    pub replay_gain: Option<f64>,
 }
 
 impl AudioStreamInfo {
@@ -3783,6 +5272,10 @@ impl AudioStreamInfo {
             title: record.title,
             is_default: record.is_default,
             is_commentary: record.is_commentary,
+            integrated_loudness: record.integrated_loudness,
+            loudness_range: record.loudness_range,
+            true_peak: record.true_peak,
+            replay_gain: record.loudness().and_then(|l| l.replay_gain()),
         }
     }
 }
@@ -3967,6 +5460,25 @@ pub struct AnalyzeUnanalyzedResult {
     pub error: Option<String>,
 }
 
+/// Which files to search subtitles for
+#[derive(Debug, Clone, InputObject)]
+pub struct SubtitleSearchWhereInput {
+    pub library_id: String,
+    /// Wanted language ("en", "eng" or "English")
+    pub language: String,
+    /// Only these files (default: every file missing the language)
+    pub media_file_ids: Option<Vec<String>>,
+}
+
+/// Result of queueing subtitle searches
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SearchSubtitlesBulkResult {
+    pub success: bool,
+    /// Number of files queued for a subtitle search
+    pub queued_count: i32,
+    pub error: Option<String>,
+}
+
 /// Result of rematching a media file against library items
 #[derive(Debug, SimpleObject)]
 pub struct RematchMediaFileResult {
@@ -4195,6 +5707,119 @@ pub struct IndexerResult {
     pub indexer: Option<IndexerConfig>,
 }
 
+/// Where a tracker's ratio stands relative to its minimum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum, Serialize, Deserialize)]
+#[graphql(rename_items = "SCREAMING_SNAKE_CASE")]
+pub enum TrackerRatioStatus {
+    /// No minimum set, nothing downloaded yet, or comfortably above it
+    Ok,
+    /// Within 10% of the minimum
+    Approaching,
+    /// Below the minimum
+    Below,
+}
+
+impl From<crate::services::tracker_stats::RatioStatus> for TrackerRatioStatus {
+    fn from(status: crate::services::tracker_stats::RatioStatus) -> Self {
+        use crate::services::tracker_stats::RatioStatus;
+        match status {
+            RatioStatus::Ok => Self::Ok,
+            RatioStatus::Approaching => Self::Approaching,
+            RatioStatus::Below => Self::Below,
+        }
+    }
+}
+
+/// Upload/download totals for torrents grabbed from an indexer
+///
+/// Only traffic seen by Librarian is counted, so this can differ from the
+/// ratio the tracker reports for the account.
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct TrackerStats {
+    pub indexer_id: String,
+    pub indexer_name: String,
+    pub indexer_type: String,
+    /// Whether the tracker is private (and so likely enforces a ratio)
+    pub is_private: bool,
+    pub uploaded_bytes: i64,
+    pub downloaded_bytes: i64,
+    /// Uploaded / downloaded, null before anything has been downloaded
+    pub ratio: Option<f64>,
+    /// Minimum ratio the tracker requires, if configured
+    pub min_ratio: Option<f64>,
+    pub ratio_status: TrackerRatioStatus,
+    /// Tracker error that looked like a rejected passkey, until the passkey
+    /// is updated
+    pub passkey_error: Option<String>,
+    /// When traffic was last recorded
+    pub updated_at: Option<String>,
+}
+
+impl From<crate::db::TrackerStatsRecord> for TrackerStats {
+    fn from(r: crate::db::TrackerStatsRecord) -> Self {
+        use crate::services::tracker_stats::{ratio, ratio_status};
+        let current = ratio(r.uploaded_bytes, r.downloaded_bytes);
+        let is_private = crate::indexer::definitions::get_indexer_info(&r.indexer_type)
+            .map(|info| info.tracker_type == "private")
+            .unwrap_or(false);
+        Self {
+            indexer_id: r.indexer_id.to_string(),
+            indexer_name: r.indexer_name,
+            indexer_type: r.indexer_type,
+            is_private,
+            uploaded_bytes: r.uploaded_bytes,
+            downloaded_bytes: r.downloaded_bytes,
+            ratio: current,
+            min_ratio: r.min_ratio,
+            ratio_status: ratio_status(current, r.min_ratio).into(),
+            passkey_error: r.passkey_error,
+            updated_at: r.updated_at.map(|dt| dt.to_rfc3339()),
+        }
+    }
+}
+
+/// Result of updating a tracker passkey
+#[derive(Debug, SimpleObject)]
+pub struct TrackerPasskeyResult {
+    pub success: bool,
+    pub error: Option<String>,
+    /// Torrents in the session re-announcing with the new passkey
+    pub torrents_updated: i32,
+}
+
+/// A release handed to a download client, from the grab ledger
+#[derive(Debug, Clone, SimpleObject, Serialize, Deserialize)]
+pub struct GrabHistoryEntry {
+    pub id: String,
+    /// 'auto_hunt', 'manual' or 'watch_folder'
+    pub source: String,
+    /// 'torrent' or 'usenet'
+    pub download_type: String,
+    pub release_title: Option<String>,
+    pub info_hash: Option<String>,
+    pub release_guid: Option<String>,
+    pub indexer_id: Option<String>,
+    /// Grabbed with the duplicate check overridden
+    pub forced: bool,
+    pub created_at: String,
+}
+
+impl From<crate::db::GrabHistoryRecord> for GrabHistoryEntry {
+    fn from(r: crate::db::GrabHistoryRecord) -> Self {
+        Self {
+            id: r.id.to_string(),
+            source: r.source,
+            download_type: r.download_type,
+            release_title: r.release_title,
+            info_hash: r.info_hash,
+            release_guid: r.release_guid,
+            indexer_id: r.indexer_id.map(|id| id.to_string()),
+            forced: r.forced,
+            created_at: r.created_at.to_rfc3339(),
+        }
+    }
+}
+
 /// Result of testing an indexer
 #[derive(Debug, Clone, SimpleObject)]
 pub struct IndexerTestResult {
@@ -4217,7 +5842,8 @@ pub struct IndexerSearchResultSet {
     pub total_releases: i32,
     /// Total time taken in milliseconds
     pub total_elapsed_ms: i64,
-    /// Whether search stopped early after finding matches (priority-based search)
+    /// Whether search stopped early, after finding matches (priority-based
+    /// search) or because the request timed out
     pub stopped_early: bool,
     /// Number of sources that were searched
     pub sources_searched: i32,
@@ -4700,6 +6326,87 @@ pub struct AvailableSource {
     pub is_healthy: bool,
 }
 
+// ============================================================================
+// Language Profile Types
+// ============================================================================
+
+/// Audio and subtitle language rules for libraries and shows
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LanguageProfile {
+    pub id: String,
+    pub name: String,
+    /// Audio languages in order of preference (ISO 639-1)
+    pub preferred_audio_languages: Vec<String>,
+    /// Releases without all of these audio languages are rejected
+    pub required_audio_languages: Vec<String>,
+    /// Subtitle languages imported files should have
+    pub required_subtitle_languages: Vec<String>,
+    /// Search again when an imported file lacks a required audio language
+    pub regrab_missing_audio: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<crate::db::LanguageProfileRecord> for LanguageProfile {
+    fn from(record: crate::db::LanguageProfileRecord) -> Self {
+        Self {
+            id: record.id.to_string(),
+            name: record.name,
+            preferred_audio_languages: record.preferred_audio_languages,
+            required_audio_languages: record.required_audio_languages,
+            required_subtitle_languages: record.required_subtitle_languages,
+            regrab_missing_audio: record.regrab_missing_audio,
+            created_at: record.created_at.to_rfc3339(),
+            updated_at: record.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+/// An imported file missing audio languages its language profile requires
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LanguageMismatch {
+    pub media_file_id: String,
+    pub file_name: String,
+    /// ISO 639-1 codes
+    pub missing_audio_languages: Vec<String>,
+    /// Torrent or NZB name the file was imported from
+    pub release_title: Option<String>,
+    /// Auto-hunt will search for another release
+    pub regrab_pending: bool,
+    pub created_at: String,
+}
+
+/// Input for creating a language profile
+///
+/// Languages may be given as ISO 639-1 or 639-2 codes or English names and
+/// are stored as ISO 639-1.
+#[derive(Debug, Clone, InputObject)]
+pub struct CreateLanguageProfileInput {
+    pub name: String,
+    pub preferred_audio_languages: Option<Vec<String>>,
+    pub required_audio_languages: Option<Vec<String>>,
+    pub required_subtitle_languages: Option<Vec<String>>,
+    pub regrab_missing_audio: Option<bool>,
+}
+
+/// Input for updating a language profile; omitted fields are unchanged
+#[derive(Debug, Clone, InputObject)]
+pub struct UpdateLanguageProfileInput {
+    pub name: Option<String>,
+    pub preferred_audio_languages: Option<Vec<String>>,
+    pub required_audio_languages: Option<Vec<String>>,
+    pub required_subtitle_languages: Option<Vec<String>>,
+    pub regrab_missing_audio: Option<bool>,
+}
+
+/// Result of a language profile mutation
+#[derive(Debug, Clone, SimpleObject)]
+pub struct LanguageProfileResult {
+    pub success: bool,
+    pub error: Option<String>,
+    pub profile: Option<LanguageProfile>,
+}
+
 // ============================================================================
 // Usenet Types
 // ============================================================================
@@ -4713,9 +6420,13 @@ pub struct UsenetServer {
     pub port: i32,
     pub use_ssl: bool,
     pub username: Option<String>,
+    /// Maximum simultaneous connections, shared by all downloads
     pub connections: i32,
     pub priority: i32,
     pub enabled: bool,
+    /// Only used for articles the primary servers don't have (block
+    /// accounts)
+    pub is_backup: bool,
     pub retention_days: Option<i32>,
     pub last_success_at: Option<String>,
     pub last_error: Option<String>,
@@ -4734,6 +6445,7 @@ impl From<crate::db::UsenetServerRecord> for UsenetServer {
             connections: record.connections,
             priority: record.priority,
             enabled: record.enabled,
+            is_backup: record.is_backup,
             retention_days: record.retention_days,
             last_success_at: record.last_success_at.map(|dt| dt.to_rfc3339()),
             last_error: record.last_error,
@@ -4753,6 +6465,7 @@ pub struct CreateUsenetServerInput {
     pub password: Option<String>,
     pub connections: Option<i32>,
     pub priority: Option<i32>,
+    pub is_backup: Option<bool>,
     pub retention_days: Option<i32>,
 }
 
@@ -4768,9 +6481,37 @@ pub struct UpdateUsenetServerInput {
     pub connections: Option<i32>,
     pub priority: Option<i32>,
     pub enabled: Option<bool>,
+    pub is_backup: Option<bool>,
     pub retention_days: Option<i32>,
 }
 
+/// A usenet server's usage over a period
+#[derive(Debug, Clone, SimpleObject)]
+pub struct UsenetServerUsage {
+    pub server_id: String,
+    pub server_name: String,
+    pub is_backup: bool,
+    pub bytes_downloaded: i64,
+    pub articles_downloaded: i64,
+    /// Articles the server didn't have, fetched from the next server instead
+    pub articles_missing: i64,
+    /// Articles that failed for other reasons (connection errors)
+    pub articles_failed: i64,
+    /// Usage per day, oldest first (days without downloads are left out)
+    pub daily: Vec<UsenetUsageDay>,
+}
+
+/// A usenet server's usage on one day
+#[derive(Debug, Clone, SimpleObject)]
+pub struct UsenetUsageDay {
+    /// UTC date, YYYY-MM-DD
+    pub day: String,
+    pub bytes_downloaded: i64,
+    pub articles_downloaded: i64,
+    pub articles_missing: i64,
+    pub articles_failed: i64,
+}
+
 /// Result of a usenet server mutation
 #[derive(Debug, Clone, SimpleObject)]
 pub struct UsenetServerResult {
@@ -4784,6 +6525,8 @@ pub struct UsenetServerResult {
 pub struct UsenetDownload {
     pub id: String,
     pub name: String,
+    /// queued, downloading, paused, repairing, completed, failed or
+    /// repair_failed
     pub state: String,
     pub progress: f64,
     pub size: Option<i64>,
@@ -4796,6 +6539,12 @@ pub struct UsenetDownload {
     pub movie_id: Option<String>,
     pub album_id: Option<String>,
     pub audiobook_id: Option<String>,
+    /// PAR2 result: verified, repaired, failed, no_parity or skipped
+    pub repair_status: Option<String>,
+    /// Data blocks that were damaged or missing
+    pub repair_blocks_needed: Option<i64>,
+    /// Recovery blocks that were available
+    pub repair_recovery_blocks: Option<i64>,
 }
 
 impl From<crate::db::UsenetDownloadRecord> for UsenetDownload {
@@ -4818,6 +6567,9 @@ impl From<crate::db::UsenetDownloadRecord> for UsenetDownload {
             movie_id: record.movie_id.map(|id| id.to_string()),
             album_id: record.album_id.map(|id| id.to_string()),
             audiobook_id: record.audiobook_id.map(|id| id.to_string()),
+            repair_status: record.repair_status,
+            repair_blocks_needed: record.repair_blocks_needed,
+            repair_recovery_blocks: record.repair_recovery_blocks,
         }
     }
 }
@@ -4832,6 +6584,30 @@ pub struct AddUsenetDownloadInput {
     pub album_id: Option<String>,
     pub audiobook_id: Option<String>,
     pub indexer_id: Option<String>,
+    /// Release title, used to spot the same release grabbed elsewhere
+    pub title: Option<String>,
+    /// Indexer GUID of the release
+    pub guid: Option<String>,
+    /// Grab the release even if it was already grabbed before
+    pub force: Option<bool>,
+}
+
+/// Input for adding an NZB; give either the NZB document or its URL
+#[derive(Debug, InputObject)]
+pub struct AddNzbInput {
+    /// NZB XML
+    pub nzb: Option<String>,
+    /// URL to download the NZB from
+    pub url: Option<String>,
+    /// Download name, defaults to the NZB's title
+    pub name: Option<String>,
+    pub library_id: Option<String>,
+    pub episode_id: Option<String>,
+    pub movie_id: Option<String>,
+    pub album_id: Option<String>,
+    pub audiobook_id: Option<String>,
+    /// Add the release even if it was already grabbed before
+    pub force: Option<bool>,
 }
 
 /// Result of a usenet download mutation
@@ -4842,6 +6618,25 @@ pub struct UsenetDownloadResult {
     pub download: Option<UsenetDownload>,
 }
 
+/// The current user's key for the SABnzbd-compatible API
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SabnzbdApiKey {
+    /// The key itself, only returned right after it's generated
+    pub key: Option<String>,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+impl From<crate::db::SabnzbdApiKeyRecord> for SabnzbdApiKey {
+    fn from(record: crate::db::SabnzbdApiKeyRecord) -> Self {
+        Self {
+            key: None,
+            created_at: record.created_at.to_rfc3339(),
+            last_used_at: record.last_used_at.map(|t| t.to_rfc3339()),
+        }
+    }
+}
+
 // ============================================================================
 // Notifications
 // ============================================================================
@@ -4888,6 +6683,9 @@ pub enum NotificationCategory {
     Storage,
     Extraction,
     Configuration,
+    Schedule,
+    Update,
+    Services,
 }
 
 impl From<crate::db::NotificationCategory> for NotificationCategory {
@@ -4899,6 +6697,9 @@ impl From<crate::db::NotificationCategory> for NotificationCategory {
             crate::db::NotificationCategory::Storage => NotificationCategory::Storage,
             crate::db::NotificationCategory::Extraction => NotificationCategory::Extraction,
             crate::db::NotificationCategory::Configuration => NotificationCategory::Configuration,
+            crate::db::NotificationCategory::Schedule => NotificationCategory::Schedule,
+            crate::db::NotificationCategory::Update => NotificationCategory::Update,
+            crate::db::NotificationCategory::Services => NotificationCategory::Services,
         }
     }
 }
@@ -4912,6 +6713,9 @@ impl From<NotificationCategory> for crate::db::NotificationCategory {
             NotificationCategory::Storage => crate::db::NotificationCategory::Storage,
             NotificationCategory::Extraction => crate::db::NotificationCategory::Extraction,
             NotificationCategory::Configuration => crate::db::NotificationCategory::Configuration,
+            NotificationCategory::Schedule => crate::db::NotificationCategory::Schedule,
+            NotificationCategory::Update => crate::db::NotificationCategory::Update,
+            NotificationCategory::Services => crate::db::NotificationCategory::Services,
         }
     }
 }
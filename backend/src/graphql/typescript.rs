@@ -0,0 +1,225 @@
+//! TypeScript type emission from the GraphQL schema
+//!
+//! Converts the schema SDL into TypeScript interfaces and string-union enums
+//! that match the GraphQL names, so the frontend can be typed without relying
+//! on runtime introspection (which may be disabled in production).
+//!
+//! Conventions follow `frontend/src/lib/graphql/types.ts`:
+//! - Object fields are always present; nullable fields are `T | null`
+//! - Input fields that are nullable are optional (`field?: T | null`)
+//! - Enums become string unions of their GraphQL values
+
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use async_graphql::parser::types::{
+    BaseType, FieldDefinition, InputValueDefinition, Type, TypeKind, TypeSystemDefinition,
+};
+use async_graphql::parser::{Positioned, parse_schema};
+
+/// Banner written at the top of the generated file
+const HEADER: &str = "// Generated by `librarian --emit-ts`. Do not edit by hand.\n";
+
+/// Map a GraphQL scalar name to its TypeScript representation
+fn scalar_to_ts(name: &str) -> Option<&'static str> {
+    match name {
+        "String" | "ID" => Some("string"),
+        "Int" | "Float" => Some("number"),
+        "Boolean" => Some("boolean"),
+        "JSON" => Some("unknown"),
+        _ => None,
+    }
+}
+
+/// Render a GraphQL type reference as a TypeScript type expression
+fn type_to_ts(ty: &Type) -> String {
+    let base = match &ty.base {
+        BaseType::Named(name) => scalar_to_ts(name.as_str())
+            .map(str::to_string)
+            .unwrap_or_else(|| name.to_string()),
+        BaseType::List(inner) => {
+            let inner_ts = type_to_ts(inner);
+            if inner.nullable {
+                format!("Array<{}>", inner_ts)
+            } else {
+                format!("{}[]", inner_ts)
+            }
+        }
+    };
+
+    if ty.nullable {
+        format!("{} | null", base)
+    } else {
+        base
+    }
+}
+
+/// Write a JSDoc comment for a description, if present
+fn write_doc(out: &mut String, description: Option<&Positioned<String>>, indent: &str) {
+    let Some(desc) = description else {
+        return;
+    };
+    let lines: Vec<&str> = desc.node.lines().filter(|l| !l.trim().is_empty()).collect();
+    match lines.as_slice() {
+        [] => {}
+        [single] => {
+            let _ = writeln!(out, "{}/** {} */", indent, single.trim());
+        }
+        many => {
+            let _ = writeln!(out, "{}/**", indent);
+            for line in many {
+                let _ = writeln!(out, "{} * {}", indent, line.trim());
+            }
+            let _ = writeln!(out, "{} */", indent);
+        }
+    }
+}
+
+fn write_object_fields(out: &mut String, fields: &[Positioned<FieldDefinition>]) {
+    for field in fields {
+        let field = &field.node;
+        write_doc(out, field.description.as_ref(), "  ");
+        let _ = writeln!(out, "  {}: {};", field.name.node, type_to_ts(&field.ty.node));
+    }
+}
+
+fn write_input_fields(out: &mut String, fields: &[Positioned<InputValueDefinition>]) {
+    for field in fields {
+        let field = &field.node;
+        write_doc(out, field.description.as_ref(), "  ");
+        let optional = if field.ty.node.nullable { "?" } else { "" };
+        let _ = writeln!(
+            out,
+            "  {}{}: {};",
+            field.name.node,
+            optional,
+            type_to_ts(&field.ty.node)
+        );
+    }
+}
+
+/// Convert a GraphQL SDL document into TypeScript declarations
+///
+/// Root operation types (Query/Mutation/Subscription) are emitted like any
+/// other object so their field result types are available to the frontend.
+pub fn sdl_to_typescript(sdl: &str) -> Result<String> {
+    let document = parse_schema(sdl).context("Failed to parse GraphQL SDL")?;
+
+    let mut out = String::from(HEADER);
+
+    for definition in &document.definitions {
+        let TypeSystemDefinition::Type(def) = definition else {
+            continue;
+        };
+        let def = &def.node;
+        let name = def.name.node.as_str();
+
+        match &def.kind {
+            TypeKind::Scalar => {
+                if scalar_to_ts(name).is_some() {
+                    continue;
+                }
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                // Custom scalars (DateTime, UUID, ...) serialize as strings
+                let _ = writeln!(out, "export type {} = string;", name);
+            }
+            TypeKind::Enum(e) => {
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                let values: Vec<String> = e
+                    .values
+                    .iter()
+                    .map(|v| format!("\"{}\"", v.node.value.node))
+                    .collect();
+                let _ = writeln!(out, "export type {} = {};", name, values.join(" | "));
+            }
+            TypeKind::Union(u) => {
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                let members: Vec<&str> = u.members.iter().map(|m| m.node.as_str()).collect();
+                let _ = writeln!(out, "export type {} = {};", name, members.join(" | "));
+            }
+            TypeKind::Object(obj) => {
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                let _ = writeln!(out, "export interface {} {{", name);
+                write_object_fields(&mut out, &obj.fields);
+                out.push_str("}\n");
+            }
+            TypeKind::Interface(iface) => {
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                let _ = writeln!(out, "export interface {} {{", name);
+                write_object_fields(&mut out, &iface.fields);
+                out.push_str("}\n");
+            }
+            TypeKind::InputObject(input) => {
+                out.push('\n');
+                write_doc(&mut out, def.description.as_ref(), "");
+                let _ = writeln!(out, "export interface {} {{", name);
+                write_input_fields(&mut out, &input.fields);
+                out.push_str("}\n");
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SDL: &str = r#"
+        "A media library"
+        type Library {
+            id: String!
+            name: String!
+            itemCount: Int
+            tags: [String!]!
+            libraryType: LibraryType!
+        }
+
+        enum LibraryType {
+            MOVIES
+            TV
+        }
+
+        input CreateLibraryInput {
+            name: String!
+            path: String
+        }
+
+        scalar DateTime
+    "#;
+
+    #[test]
+    fn test_object_fields_and_nullability() {
+        let ts = sdl_to_typescript(SDL).unwrap();
+        assert!(ts.contains("/** A media library */\nexport interface Library {"));
+        assert!(ts.contains("  id: string;"));
+        assert!(ts.contains("  itemCount: number | null;"));
+        assert!(ts.contains("  tags: string[];"));
+        assert!(ts.contains("  libraryType: LibraryType;"));
+    }
+
+    #[test]
+    fn test_enum_as_string_union() {
+        let ts = sdl_to_typescript(SDL).unwrap();
+        assert!(ts.contains("export type LibraryType = \"MOVIES\" | \"TV\";"));
+    }
+
+    #[test]
+    fn test_input_optional_fields() {
+        let ts = sdl_to_typescript(SDL).unwrap();
+        assert!(ts.contains("  name: string;"));
+        assert!(ts.contains("  path?: string | null;"));
+    }
+
+    #[test]
+    fn test_custom_scalar_is_string() {
+        let ts = sdl_to_typescript(SDL).unwrap();
+        assert!(ts.contains("export type DateTime = string;"));
+    }
+}
@@ -25,7 +25,7 @@ use std::collections::HashMap;
 use anyhow::{Result, anyhow};
 use async_graphql::async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use reqwest::{Client, header};
+use reqwest::{Client, RequestBuilder, Response, header};
 use scraper::{Html, Selector};
 
 use crate::indexer::categories::CategoryMapping;
@@ -33,6 +33,7 @@ use crate::indexer::{
     BookSearchParam, Indexer, IndexerType, MovieSearchParam, MusicSearchParam, ReleaseInfo,
     TorznabCapabilities, TorznabQuery, TrackerType, TvSearchParam, categories::cats,
 };
+use crate::services::http_replay;
 
 /// IPTorrents alternative site links (for future use)
 #[allow(dead_code)]
@@ -245,6 +246,11 @@ impl IPTorrentsIndexer {
         ]
     }
 
+    /// Send a request, through the record/replay layer
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        http_replay::send(&self.client, "iptorrents", request.build()?).await
+    }
+
     /// Build the search URL
     fn build_search_url(&self, query: &TorznabQuery) -> String {
         let mut url = format!("{}t?", self.site_link);
@@ -439,7 +445,7 @@ impl Indexer for IPTorrentsIndexer {
     async fn test_connection(&self) -> Result<bool> {
         let url = format!("{}t", self.site_link);
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
         let text = response.text().await?;
 
         // Check if we're logged in by looking for the logout link
@@ -459,10 +465,11 @@ impl Indexer for IPTorrentsIndexer {
         let search_url = self.build_search_url(query);
 
         let response = self
-            .client
-            .get(&search_url)
-            .header(header::REFERER, format!("{}t", self.site_link))
-            .send()
+            .send(
+                self.client
+                    .get(&search_url)
+                    .header(header::REFERER, format!("{}t", self.site_link)),
+            )
             .await?;
 
         let text = response.text().await?;
@@ -725,10 +732,11 @@ impl Indexer for IPTorrentsIndexer {
 
     async fn download(&self, link: &str) -> Result<Vec<u8>> {
         let response = self
-            .client
-            .get(link)
-            .header(header::REFERER, &self.site_link)
-            .send()
+            .send(
+                self.client
+                    .get(link)
+                    .header(header::REFERER, &self.site_link),
+            )
             .await?;
 
         if !response.status().is_success() {
@@ -25,7 +25,7 @@ use async_graphql::async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use quick_xml::events::Event;
 use quick_xml::Reader;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, Response};
 use tracing::{debug, error, info, warn};
 
 use crate::indexer::categories::CategoryMapping;
@@ -33,6 +33,7 @@ use crate::indexer::{
     BookSearchParam, Indexer, IndexerType, MovieSearchParam, MusicSearchParam, ReleaseInfo,
     TorznabCapabilities, TorznabQuery, TrackerType, TvSearchParam, categories::cats,
 };
+use crate::services::http_replay;
 
 /// Newznab indexer for Usenet sites
 pub struct NewznabIndexer {
@@ -149,6 +150,11 @@ impl NewznabIndexer {
         ]
     }
 
+    /// Send a request, through the record/replay layer
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        http_replay::send(&self.client, "newznab", request.build()?).await
+    }
+
     /// Build the API URL with query parameters
     fn build_api_url(&self, params: &[(&str, &str)]) -> String {
         let base = self.api_url.trim_end_matches('/');
@@ -532,7 +538,7 @@ impl Indexer for NewznabIndexer {
 
         debug!(url = %url, "Testing Newznab connection");
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(anyhow!(
@@ -622,7 +628,7 @@ impl Indexer for NewznabIndexer {
             "Searching Newznab indexer"
         );
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
 
         if !response.status().is_success() {
             return Err(anyhow!("Search failed: HTTP {}", response.status()));
@@ -671,7 +677,7 @@ impl Indexer for NewznabIndexer {
             format!("{}?apikey={}", link, self.api_key)
         };
 
-        let response = self.client.get(&download_url).send().await?;
+        let response = self.send(self.client.get(&download_url)).await?;
 
         if !response.status().is_success() {
             return Err(anyhow!("Download failed: HTTP {}", response.status()));
@@ -13,13 +13,15 @@ use std::time::{Duration, Instant};
 use anyhow::{Result, anyhow};
 use parking_lot::RwLock;
 use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use uuid::Uuid;
 
 use super::definitions::iptorrents::IPTorrentsIndexer;
 use super::definitions::newznab::NewznabIndexer;
 use super::encryption::CredentialEncryption;
-use super::{Indexer, IndexerSearchResult, ReleaseInfo, TorznabQuery};
+use super::{Indexer, IndexerSearchResult, IndexerType, ReleaseInfo, TorznabQuery};
 use crate::db::Database;
+use crate::usenet::retention::{apply_retention, best_retention_days};
 
 /// Default cache TTL (5 minutes)
 const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
@@ -186,32 +188,7 @@ impl IndexerManager {
             .map(|(id, idx)| (*id, idx.clone()))
             .collect();
 
-        let mut results = Vec::with_capacity(indexers.len());
-
-        // Search all indexers concurrently
-        let mut handles = vec![];
-        for (config_id, indexer) in indexers {
-            let query = query.clone();
-            let cache = self.cache.read().clone();
-            let rate_limiter = self.rate_limiters.read().get(&config_id).cloned();
-
-            let handle = tokio::spawn(async move {
-                Self::search_single(config_id, indexer, &query, cache, rate_limiter).await
-            });
-            handles.push(handle);
-        }
-
-        // Collect results
-        for handle in handles {
-            match handle.await {
-                Ok(result) => results.push(result),
-                Err(e) => {
-                    tracing::error!(error = %e, "Indexer search task panicked");
-                }
-            }
-        }
-
-        results
+        self.search_concurrently(indexers, query).await
     }
 
     /// Search specific indexers
@@ -228,32 +205,99 @@ impl IndexerManager {
             .map(|(id, idx)| (*id, idx.clone()))
             .collect();
 
-        let mut results = Vec::with_capacity(indexers.len());
+        self.search_concurrently(indexers, query).await
+    }
 
-        let mut handles = vec![];
-        for (config_id, indexer) in indexers {
+    /// Search indexers concurrently, returning results in the given order
+    ///
+    /// The searches are tasks in a `JoinSet`, so they're aborted when the
+    /// caller stops waiting, e.g. when a GraphQL request times out or its
+    /// client goes away.
+    async fn search_concurrently(
+        &self,
+        indexers: Vec<(Uuid, Arc<dyn Indexer>)>,
+        query: &TorznabQuery,
+    ) -> Vec<IndexerSearchResult> {
+        let mut tasks = JoinSet::new();
+        for (index, (config_id, indexer)) in indexers.into_iter().enumerate() {
             let query = query.clone();
             let cache = self.cache.read().clone();
             let rate_limiter = self.rate_limiters.read().get(&config_id).cloned();
 
-            let handle = tokio::spawn(async move {
-                Self::search_single(config_id, indexer, &query, cache, rate_limiter).await
+            tasks.spawn(async move {
+                let result =
+                    Self::search_single(config_id, indexer, &query, cache, rate_limiter).await;
+                (index, result)
             });
-            handles.push(handle);
         }
 
-        for handle in handles {
-            match handle.await {
-                Ok(result) => results.push(result),
+        let mut indexed = Vec::with_capacity(tasks.len());
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => indexed.push(result),
                 Err(e) => {
                     tracing::error!(error = %e, "Indexer search task panicked");
                 }
             }
         }
+        indexed.sort_by_key(|(index, _)| *index);
 
+        let mut results: Vec<IndexerSearchResult> =
+            indexed.into_iter().map(|(_, result)| result).collect();
+        self.apply_usenet_retention(&mut results).await;
         results
     }
 
+    /// Drop Newznab results older than the best retention among the indexer
+    /// owner's usenet servers, and try those near it last
+    async fn apply_usenet_retention(&self, results: &mut [IndexerSearchResult]) {
+        let now = chrono::Utc::now();
+        let mut retention_by_user: HashMap<Uuid, Option<i32>> = HashMap::new();
+
+        for result in results.iter_mut() {
+            let Ok(config_id) = Uuid::parse_str(&result.indexer_id) else {
+                continue;
+            };
+            let is_usenet = self
+                .get_indexer(config_id)
+                .is_some_and(|idx| idx.indexer_type() == IndexerType::Newznab);
+            if !is_usenet || result.releases.is_empty() {
+                continue;
+            }
+
+            let Ok(Some(config)) = self.db.indexers().get(config_id).await else {
+                continue;
+            };
+            let retention = match retention_by_user.get(&config.user_id) {
+                Some(retention) => *retention,
+                None => {
+                    let servers = self
+                        .db
+                        .usenet_servers()
+                        .list_enabled_by_user(config.user_id)
+                        .await
+                        .unwrap_or_default();
+                    let retention = best_retention_days(&servers);
+                    retention_by_user.insert(config.user_id, retention);
+                    retention
+                }
+            };
+            let Some(retention_days) = retention else {
+                continue;
+            };
+
+            let dropped = apply_retention(&mut result.releases, retention_days, now);
+            if dropped > 0 {
+                tracing::debug!(
+                    indexer_name = %result.indexer_name,
+                    retention_days,
+                    dropped,
+                    "Dropped usenet results older than server retention"
+                );
+            }
+        }
+    }
+
     /// Search a single indexer
     async fn search_single(
         config_id: Uuid,
@@ -0,0 +1,437 @@
+//! Upcoming episode air notifications
+//!
+//! Reads the cached TV schedule and notifies users a configurable number of
+//! hours before an episode of one of their monitored shows airs. The lead time
+//! is set globally (`notifications.episode_air_lead_hours`) and can be
+//! overridden per show; an override of 0 silences the show.
+//!
+//! When many episodes air on the same night for a user, they are coalesced
+//! into a single digest notification instead of one notification per episode.
+//! The digest goes out when the night's first episode comes due and lists
+//! every episode airing that night, including ones whose own window hasn't
+//! opened yet.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+use time::OffsetDateTime;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::{
+    CreateNotification, Database, NotificationCategory, NotificationType, ScheduleCacheRecord,
+};
+use crate::services::NotificationService;
+
+/// Setting key for the global lead time in hours (0 = disabled)
+pub const LEAD_HOURS_KEY: &str = "notifications.episode_air_lead_hours";
+
+/// Setting key for the digest threshold (0 = never coalesce)
+pub const DIGEST_THRESHOLD_KEY: &str = "notifications.episode_air_digest_threshold";
+
+/// Default global lead time
+pub const DEFAULT_LEAD_HOURS: i32 = 0;
+
+/// Default number of same-night episodes that triggers a digest
+pub const DEFAULT_DIGEST_THRESHOLD: i32 = 3;
+
+/// Longest lead time we honour; the schedule cache only covers two weeks
+pub const MAX_LEAD_HOURS: i32 = 7 * 24;
+
+/// How long sent ledger entries are kept
+const LEDGER_RETENTION_DAYS: i64 = 30;
+
+/// An upcoming airing a user hasn't been notified about yet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpcomingAiring {
+    pub user_id: Uuid,
+    pub library_id: Uuid,
+    pub show_name: String,
+    pub tvmaze_episode_id: i32,
+    pub season: i32,
+    pub episode_number: i32,
+    pub episode_name: String,
+    /// Air date in the network's timezone, i.e. the night it airs
+    pub air_date: time::Date,
+    pub air_stamp: OffsetDateTime,
+    /// The show's effective lead time
+    pub lead_hours: i32,
+}
+
+/// A notification to dispatch for one user
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AirDispatch {
+    Single(UpcomingAiring),
+    Digest {
+        user_id: Uuid,
+        airings: Vec<UpcomingAiring>,
+    },
+}
+
+/// Resolve the lead time for a show: the override wins, otherwise the global value
+pub fn effective_lead_hours(override_hours: Option<i32>, global_hours: i32) -> i32 {
+    override_hours
+        .unwrap_or(global_hours)
+        .clamp(0, MAX_LEAD_HOURS)
+}
+
+/// Whether an airing falls inside its notification window
+///
+/// The window opens `lead_hours` before the air time and closes when the
+/// episode airs, so a missed run still catches up as long as it's before air.
+pub fn is_due(air_stamp: OffsetDateTime, now: OffsetDateTime, lead_hours: i32) -> bool {
+    if lead_hours <= 0 {
+        return false;
+    }
+    let opens_at = air_stamp - time::Duration::hours(lead_hours as i64);
+    now >= opens_at && now < air_stamp
+}
+
+/// Plan the notifications to send now, per user per night
+///
+/// `airings` are the user's unsent upcoming airings. Nothing goes out for a
+/// night until one of its airings comes due. A night with at least
+/// `digest_threshold` airings is then sent as one digest of all of them;
+/// otherwise each airing is sent on its own once it comes due.
+pub fn plan_dispatches(
+    airings: Vec<UpcomingAiring>,
+    now: OffsetDateTime,
+    digest_threshold: i32,
+) -> Vec<AirDispatch> {
+    let mut nights: BTreeMap<(Uuid, time::Date), Vec<UpcomingAiring>> = BTreeMap::new();
+    for airing in airings.into_iter().filter(|a| a.air_stamp > now) {
+        nights
+            .entry((airing.user_id, airing.air_date))
+            .or_default()
+            .push(airing);
+    }
+
+    let mut dispatches = Vec::new();
+    for ((user_id, _), mut night) in nights {
+        if !night.iter().any(|a| is_due(a.air_stamp, now, a.lead_hours)) {
+            continue;
+        }
+        night.sort_by_key(|a| a.air_stamp);
+        if digest_threshold > 0 && night.len() >= digest_threshold as usize {
+            dispatches.push(AirDispatch::Digest {
+                user_id,
+                airings: night,
+            });
+        } else {
+            dispatches.extend(
+                night
+                    .into_iter()
+                    .filter(|a| is_due(a.air_stamp, now, a.lead_hours))
+                    .map(AirDispatch::Single),
+            );
+        }
+    }
+    dispatches
+}
+
+fn episode_label(airing: &UpcomingAiring) -> String {
+    format!(
+        "{} S{:02}E{:02}",
+        airing.show_name, airing.season, airing.episode_number
+    )
+}
+
+fn format_air_time(stamp: OffsetDateTime) -> String {
+    stamp
+        .format(time::macros::format_description!(
+            "[year]-[month]-[day] [hour]:[minute] UTC"
+        ))
+        .unwrap_or_default()
+}
+
+fn build_notification(dispatch: &AirDispatch) -> CreateNotification {
+    match dispatch {
+        AirDispatch::Single(airing) => CreateNotification {
+            user_id: airing.user_id,
+            title: format!("{} airs soon", episode_label(airing)),
+            message: format!(
+                "\"{}\" airs at {}.",
+                airing.episode_name,
+                format_air_time(airing.air_stamp)
+            ),
+            notification_type: NotificationType::Info,
+            category: NotificationCategory::Schedule,
+            library_id: Some(airing.library_id),
+            torrent_id: None,
+            media_file_id: None,
+            pending_match_id: None,
+            action_type: None,
+            action_data: None,
+        },
+        AirDispatch::Digest { user_id, airings } => {
+            let lines: Vec<String> = airings
+                .iter()
+                .map(|a| format!("{} at {}", episode_label(a), format_air_time(a.air_stamp)))
+                .collect();
+            CreateNotification {
+                user_id: *user_id,
+                title: format!("{} episodes airing soon", airings.len()),
+                message: lines.join("\n"),
+                notification_type: NotificationType::Info,
+                category: NotificationCategory::Schedule,
+                library_id: None,
+                torrent_id: None,
+                media_file_id: None,
+                pending_match_id: None,
+                action_type: None,
+                action_data: None,
+            }
+        }
+    }
+}
+
+/// Check the schedule cache and send any due air notifications
+pub async fn send_air_notifications(
+    db: Database,
+    notifications: Arc<NotificationService>,
+) -> Result<()> {
+    let settings = db.settings();
+    let global_lead = settings
+        .get_or_default(LEAD_HOURS_KEY, DEFAULT_LEAD_HOURS)
+        .await
+        .unwrap_or(DEFAULT_LEAD_HOURS);
+    let digest_threshold = settings
+        .get_or_default(DIGEST_THRESHOLD_KEY, DEFAULT_DIGEST_THRESHOLD)
+        .await
+        .unwrap_or(DEFAULT_DIGEST_THRESHOLD);
+
+    let repo = db.air_notifications();
+    let shows: Vec<_> = repo
+        .list_candidate_shows()
+        .await?
+        .into_iter()
+        .filter(|s| effective_lead_hours(s.lead_hours_override, global_lead) > 0)
+        .collect();
+
+    if shows.is_empty() {
+        debug!("No shows have air notifications enabled");
+        return Ok(());
+    }
+
+    let mut shows_by_tvmaze: HashMap<i32, Vec<_>> = HashMap::new();
+    for show in &shows {
+        shows_by_tvmaze.entry(show.tvmaze_id).or_default().push(show);
+    }
+    let tvmaze_ids: Vec<i32> = shows_by_tvmaze.keys().copied().collect();
+
+    let now = OffsetDateTime::now_utc();
+    let max_lead = shows
+        .iter()
+        .map(|s| effective_lead_hours(s.lead_hours_override, global_lead))
+        .max()
+        .unwrap_or(0);
+    // `air_date` is the network's local date, which can be a day either side
+    // of the UTC date (US evenings air after midnight UTC). The extra days
+    // also cover the rest of a night whose first airing is due.
+    let start_date = now.date().previous_day().unwrap_or(now.date());
+    let end_date = (now + time::Duration::hours(max_lead as i64 + 24)).date();
+
+    let entries: Vec<ScheduleCacheRecord> = db
+        .schedule()
+        .get_for_shows_between(&tvmaze_ids, start_date, end_date)
+        .await?;
+
+    // The cache holds one row per country, so the same episode may appear
+    // twice, and a user may have a show in two libraries
+    let mut seen_episodes = HashSet::new();
+    let mut upcoming = Vec::new();
+    for entry in entries {
+        let Some(air_stamp) = entry.air_stamp.filter(|stamp| *stamp > now) else {
+            continue;
+        };
+        let Some(owners) = shows_by_tvmaze.get(&entry.tvmaze_show_id) else {
+            continue;
+        };
+        for show in owners {
+            if !seen_episodes.insert((show.user_id, entry.tvmaze_episode_id)) {
+                continue;
+            }
+            if repo.was_sent(show.user_id, entry.tvmaze_episode_id).await? {
+                continue;
+            }
+            upcoming.push(UpcomingAiring {
+                user_id: show.user_id,
+                library_id: show.library_id,
+                show_name: show.name.clone(),
+                tvmaze_episode_id: entry.tvmaze_episode_id,
+                season: entry.season,
+                episode_number: entry.episode_number,
+                episode_name: entry.episode_name.clone(),
+                air_date: entry.air_date,
+                air_stamp,
+                lead_hours: effective_lead_hours(show.lead_hours_override, global_lead),
+            });
+        }
+    }
+
+    let mut sent = 0;
+    for dispatch in plan_dispatches(upcoming, now, digest_threshold) {
+        let airings = match &dispatch {
+            AirDispatch::Single(airing) => std::slice::from_ref(airing),
+            AirDispatch::Digest { airings, .. } => airings.as_slice(),
+        };
+
+        // Claim the ledger entries first so a concurrent run can't double-send
+        let mut claimed = false;
+        for airing in airings {
+            let stamp = airing
+                .air_stamp
+                .format(&time::format_description::well_known::Rfc3339)
+                .ok();
+            claimed |= repo
+                .mark_sent(airing.user_id, airing.tvmaze_episode_id, stamp.as_deref())
+                .await?;
+        }
+        if !claimed {
+            continue;
+        }
+
+        if let Err(e) = notifications
+            .create_without_dedupe(build_notification(&dispatch))
+            .await
+        {
+            warn!(error = %e, "Failed to create episode air notification");
+            continue;
+        }
+        sent += 1;
+    }
+
+    if sent > 0 {
+        info!("Sent {} episode air notification(s)", sent);
+    }
+
+    let cutoff = chrono::Utc::now() - chrono::Duration::days(LEDGER_RETENTION_DAYS);
+    if let Err(e) = repo.delete_sent_before(cutoff).await {
+        warn!(error = %e, "Failed to prune episode air notification ledger");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::{date, datetime};
+
+    fn airing(user_id: Uuid, episode: i32, stamp: OffsetDateTime) -> UpcomingAiring {
+        UpcomingAiring {
+            user_id,
+            library_id: Uuid::nil(),
+            show_name: "Show".to_string(),
+            tvmaze_episode_id: episode,
+            season: 1,
+            episode_number: episode,
+            episode_name: format!("Episode {}", episode),
+            air_date: date!(2026 - 10 - 16),
+            air_stamp: stamp,
+            lead_hours: 1,
+        }
+    }
+
+    fn episodes(plan: &AirDispatch) -> Vec<i32> {
+        match plan {
+            AirDispatch::Single(airing) => vec![airing.tvmaze_episode_id],
+            AirDispatch::Digest { airings, .. } => {
+                airings.iter().map(|a| a.tvmaze_episode_id).collect()
+            }
+        }
+    }
+
+    #[test]
+    fn test_effective_lead_hours() {
+        assert_eq!(effective_lead_hours(None, 6), 6);
+        assert_eq!(effective_lead_hours(Some(2), 6), 2);
+        assert_eq!(effective_lead_hours(Some(0), 6), 0);
+        assert_eq!(effective_lead_hours(Some(1000), 6), MAX_LEAD_HOURS);
+    }
+
+    #[test]
+    fn test_is_due_window() {
+        let air = datetime!(2026-10-16 20:00 UTC);
+        assert!(!is_due(air, datetime!(2026-10-16 13:59 UTC), 6));
+        assert!(is_due(air, datetime!(2026-10-16 14:00 UTC), 6));
+        assert!(is_due(air, datetime!(2026-10-16 19:59 UTC), 6));
+        assert!(!is_due(air, datetime!(2026-10-16 20:00 UTC), 6));
+        assert!(!is_due(air, datetime!(2026-10-16 19:00 UTC), 0));
+    }
+
+    #[test]
+    fn test_plan_dispatches_below_threshold() {
+        let user = Uuid::new_v4();
+        let airings = vec![
+            airing(user, 1, datetime!(2026-10-16 20:00 UTC)),
+            airing(user, 2, datetime!(2026-10-16 21:00 UTC)),
+        ];
+        // Only the first airing's window is open; the second waits for its own
+        let plan = plan_dispatches(airings, datetime!(2026-10-16 19:15 UTC), 3);
+        assert_eq!(plan.len(), 1);
+        assert!(matches!(&plan[0], AirDispatch::Single(a) if a.tvmaze_episode_id == 1));
+    }
+
+    #[test]
+    fn test_plan_dispatches_digest_for_night() {
+        let user = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let airings = vec![
+            airing(user, 3, datetime!(2026-10-16 22:00 UTC)),
+            airing(user, 1, datetime!(2026-10-16 20:00 UTC)),
+            airing(user, 2, datetime!(2026-10-16 21:00 UTC)),
+            airing(other, 4, datetime!(2026-10-16 20:00 UTC)),
+        ];
+
+        // Nothing before the night's first window opens
+        assert!(plan_dispatches(airings.clone(), datetime!(2026-10-16 18:30 UTC), 3).is_empty());
+
+        // Once the 20:00 airing is due, the whole night goes out as one digest
+        let plan = plan_dispatches(airings, datetime!(2026-10-16 19:15 UTC), 3);
+        assert_eq!(plan.len(), 2);
+        let digest = plan
+            .iter()
+            .find(|d| matches!(d, AirDispatch::Digest { user_id, .. } if *user_id == user))
+            .expect("digest for busy user");
+        assert_eq!(episodes(digest), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_plan_dispatches_night_spans_utc_midnight() {
+        // A US evening: one local date, air stamps either side of midnight UTC
+        let user = Uuid::new_v4();
+        let airings = vec![
+            airing(user, 1, datetime!(2026-10-16 23:00 UTC)),
+            airing(user, 2, datetime!(2026-10-17 00:00 UTC)),
+            airing(user, 3, datetime!(2026-10-17 02:00 UTC)),
+        ];
+        let plan = plan_dispatches(airings, datetime!(2026-10-16 22:30 UTC), 3);
+        assert_eq!(plan.len(), 1);
+        assert_eq!(episodes(&plan[0]), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_plan_dispatches_skips_aired() {
+        let user = Uuid::new_v4();
+        let airings = vec![
+            airing(user, 1, datetime!(2026-10-16 19:00 UTC)),
+            airing(user, 2, datetime!(2026-10-16 20:00 UTC)),
+            airing(user, 3, datetime!(2026-10-16 21:00 UTC)),
+        ];
+        let plan = plan_dispatches(airings, datetime!(2026-10-16 19:30 UTC), 3);
+        assert_eq!(plan.len(), 1);
+        assert!(matches!(&plan[0], AirDispatch::Single(a) if a.tvmaze_episode_id == 2));
+    }
+
+    #[test]
+    fn test_plan_dispatches_threshold_zero_never_coalesces() {
+        let user = Uuid::new_v4();
+        let airings = (1..=5)
+            .map(|i| airing(user, i, datetime!(2026-10-16 20:00 UTC)))
+            .collect();
+        let plan = plan_dispatches(airings, datetime!(2026-10-16 19:30 UTC), 0);
+        assert_eq!(plan.len(), 5);
+    }
+}
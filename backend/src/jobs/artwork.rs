@@ -1,16 +1,46 @@
-//! Artwork fetching job
+//! Artwork jobs
+//!
+//! - Blurhash backfill for artwork cached before placeholders were computed
+//!
+//! Artwork audits run as background jobs (see `services::artwork_audit`).
 
 use anyhow::Result;
+use tracing::{debug, info};
 
-/// Fetch artwork for media items (for future scheduled job use)
-#[allow(dead_code)]
-pub async fn fetch_artwork(_media_id: &str) -> Result<()> {
-    // TODO: Implement artwork fetching
-    // 1. Query TheTVDB/TMDB for artwork URLs
-    // 2. Download posters/backdrops
-    // 3. Store in artwork cache
-    // 4. Update artwork table
+use crate::db::Database;
+use crate::services::artwork::compute_placeholder;
 
-    tracing::info!("Artwork fetch completed");
+/// Number of images decoded per backfill batch
+const BLURHASH_BATCH_SIZE: i64 = 50;
+
+/// Compute blurhashes for cached artwork that doesn't have one yet
+///
+/// Images that can't be decoded get an empty blurhash so they aren't retried.
+pub async fn backfill_blurhashes(db: Database) -> Result<()> {
+    let repo = db.artwork();
+    let mut processed = 0usize;
+
+    loop {
+        let batch = repo.list_missing_blurhash(BLURHASH_BATCH_SIZE).await?;
+        if batch.is_empty() {
+            break;
+        }
+
+        for (id, data) in batch {
+            let placeholder = tokio::task::spawn_blocking(move || compute_placeholder(&data))
+                .await
+                .unwrap_or(None);
+            let blurhash = placeholder.map(|p| p.blurhash).unwrap_or_default();
+            if blurhash.is_empty() {
+                debug!(artwork_id = %id, "Could not decode artwork for blurhash");
+            }
+            repo.set_blurhash(&id, &blurhash).await?;
+            processed += 1;
+        }
+    }
+
+    if processed > 0 {
+        info!("Computed blurhash for {} cached artwork item(s)", processed);
+    }
     Ok(())
 }
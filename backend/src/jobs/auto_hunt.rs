@@ -20,18 +20,24 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::db::Database;
+use crate::db::LanguageProfileRecord;
 use crate::db::libraries::LibraryRecord;
 use crate::db::movies::MovieRecord;
 use crate::db::tv_shows::TvShowRecord;
 use crate::indexer::manager::IndexerManager;
 use crate::indexer::{ReleaseInfo, TorznabQuery};
 use crate::services::TorrentService;
+use crate::services::automation_pause;
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource};
+use crate::services::language_profile;
+use crate::services::release_precheck;
 use crate::services::text_utils::normalize_quality;
 use crate::services::torrent::TorrentInfo;
 use crate::services::torrent_metadata::{
     extract_audio_files, is_single_file_album, parse_torrent_files,
 };
 use crate::services::track_matcher::{TrackMatchResult, match_tracks};
+use crate::services::tracker_passkey;
 // Use the unified FileMatcher for all matching operations
 use crate::services::file_matcher::{FileInfo, FileMatcher, KnownMatchTarget};
 
@@ -114,15 +120,163 @@ mod categories {
     }
 }
 
-/// Download a release using the indexer's authentication
+/// Download a release unless it was already grabbed
+///
+/// The grab is claimed in the grab ledger first, so a release another
+/// pipeline (or the user) already grabbed is skipped with an error.
+/// `runtime_minutes` is the movie or episode runtime the release's size is
+/// pre-checked against.
+async fn download_release(
+    db: &Database,
+    release: &ReleaseInfo,
+    torrent_service: &Arc<TorrentService>,
+    indexer_manager: &Arc<IndexerManager>,
+    user_id: Option<Uuid>,
+    runtime_minutes: Option<i32>,
+) -> Result<TorrentInfo> {
+    let claim_id = claim_grab(db, release, user_id).await?;
+    let result = add_release(
+        db,
+        release,
+        torrent_service,
+        indexer_manager,
+        user_id,
+        runtime_minutes,
+    )
+    .await;
+    finish_grab(db, claim_id, &result).await;
+    if let Err(e) = &result {
+        check_passkey_error(db, release, e).await;
+    }
+    result
+}
+
+/// Ask for a new passkey if the indexer refused a download because of it
+async fn check_passkey_error(db: &Database, release: &ReleaseInfo, error: &anyhow::Error) {
+    let message = format!("{:#}", error);
+    if let Some(indexer_id) = release
+        .indexer_id
+        .as_deref()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        && tracker_passkey::is_passkey_error(&message)
+        && let Err(e) = tracker_passkey::record_passkey_error(db, indexer_id, &message).await
+    {
+        warn!(job = "auto_hunt", error = %e, "Failed to record passkey error");
+    }
+}
+
+/// Add a .torrent already fetched (and validated) for a release, unless the
+/// release was already grabbed
+async fn add_validated_release(
+    db: &Database,
+    release: &ReleaseInfo,
+    torrent_bytes: &[u8],
+    torrent_service: &Arc<TorrentService>,
+    user_id: Option<Uuid>,
+) -> Result<TorrentInfo> {
+    release_precheck::check_torrent(db, &release.title, torrent_bytes, None).await?;
+    let claim_id = claim_grab(db, release, user_id).await?;
+    let result = torrent_service.add_torrent_bytes(torrent_bytes, user_id).await;
+    finish_grab(db, claim_id, &result).await;
+    result
+}
+
+/// Claim a release in the grab ledger, failing if it was already grabbed
+async fn claim_grab(
+    db: &Database,
+    release: &ReleaseInfo,
+    user_id: Option<Uuid>,
+) -> Result<Option<Uuid>> {
+    let Some(user_id) = user_id else {
+        return Ok(None);
+    };
+
+    let request = GrabRequest::torrent_release(release, user_id, GrabSource::AutoHunt);
+    match grab_ledger::claim(db, request).await? {
+        Claim::Claimed(id) => Ok(Some(id)),
+        Claim::Duplicate(earlier) => Err(anyhow::anyhow!(grab_ledger::duplicate_message(
+            earlier.as_ref()
+        ))),
+    }
+}
+
+/// Record the info hash of a started grab, or drop the claim if the torrent
+/// client rejected it
+async fn finish_grab(db: &Database, claim_id: Option<Uuid>, result: &Result<TorrentInfo>) {
+    let Some(claim_id) = claim_id else {
+        return;
+    };
+
+    let recorded = match result {
+        Ok(info) => grab_ledger::confirm(db, claim_id, &info.info_hash).await,
+        Err(_) => grab_ledger::abandon(db, claim_id).await,
+    };
+    if let Err(e) = recorded {
+        warn!(job = "auto_hunt", error = %e, "Failed to update grab ledger");
+    }
+}
+
+/// Drop the release an item's file came from when the file is being
+/// replaced for missing audio languages, so the next best one is picked
+async fn exclude_mismatched_release(
+    db: &Database,
+    media_file_id: Option<Uuid>,
+    releases: &mut Vec<ReleaseInfo>,
+) {
+    let Some(media_file_id) = media_file_id else {
+        return;
+    };
+
+    match db.language_mismatches().get(media_file_id).await {
+        Ok(Some(mismatch)) => {
+            if let Some(title) = mismatch.release_title {
+                let mislabeled = grab_ledger::normalize_release_name(&title);
+                releases.retain(|r| grab_ledger::normalize_release_name(&r.title) != mislabeled);
+            }
+        }
+        Ok(None) => {}
+        Err(e) => warn!(job = "auto_hunt", error = %e, "Failed to read language mismatch"),
+    }
+}
+
+/// Stop hunting for a replacement of a file missing audio languages once
+/// one is downloading
+async fn clear_language_regrab(db: &Database, media_file_id: Uuid) {
+    if let Err(e) = db.language_mismatches().clear_regrab(media_file_id).await {
+        warn!(job = "auto_hunt", error = %e, "Failed to clear language re-grab");
+    }
+}
+
+/// Whether the user has paused automation for a library (vacation mode)
+///
+/// Errors reading the pause are logged and treated as not paused.
+async fn automation_paused(db: &Database, library: &LibraryRecord) -> bool {
+    match automation_pause::is_library_paused(db, library.user_id, library.id).await {
+        Ok(paused) => paused,
+        Err(e) => {
+            warn!(
+                job = "auto_hunt",
+                library_id = %library.id,
+                error = %e,
+                "Failed to check automation pause"
+            );
+            false
+        }
+    }
+}
+
+/// Add a release to the torrent client using the indexer's authentication
 ///
 /// This function handles both magnet URIs (no auth needed) and torrent file URLs
 /// (requires downloading via the indexer with proper cookies/headers).
-async fn download_release(
+/// Downloaded .torrent files are pre-checked before they're added.
+async fn add_release(
+    db: &Database,
     release: &ReleaseInfo,
     torrent_service: &Arc<TorrentService>,
     indexer_manager: &Arc<IndexerManager>,
     user_id: Option<Uuid>,
+    runtime_minutes: Option<i32>,
 ) -> Result<TorrentInfo> {
     // Prefer magnet URI if available (no authentication needed)
     if let Some(ref magnet) = release.magnet_uri {
@@ -130,7 +284,13 @@ async fn download_release(
             release_title = %release.title,
             "Downloading via magnet URI"
         );
-        return torrent_service.add_magnet(magnet, user_id).await;
+        let info = torrent_service.add_magnet(magnet, user_id).await?;
+        if let Some(ref indexer_id) = release.indexer_id {
+            torrent_service
+                .set_source_indexer(&info.info_hash, indexer_id)
+                .await;
+        }
+        return Ok(info);
     }
 
     // For torrent file URLs, we need to download via the indexer to get proper auth
@@ -144,11 +304,17 @@ async fn download_release(
 
             // Download the torrent file bytes using the indexer's authentication
             let torrent_bytes = indexer_manager.download_torrent(indexer_id, link).await?;
+            release_precheck::check_torrent(db, &release.title, &torrent_bytes, runtime_minutes)
+                .await?;
 
             // Add the torrent from bytes
-            return torrent_service
+            let info = torrent_service
                 .add_torrent_bytes(&torrent_bytes, user_id)
+                .await?;
+            torrent_service
+                .set_source_indexer(&info.info_hash, indexer_id)
                 .await;
+            return Ok(info);
         } else {
             // No indexer_id - try direct download (might fail for private trackers)
             warn!(
@@ -257,6 +423,8 @@ pub struct EffectiveQualitySettings {
     pub allowed_sources: Vec<String>,
     pub release_group_blacklist: Vec<String>,
     pub release_group_whitelist: Vec<String>,
+    /// Language profile of the show or library, if one is assigned
+    pub language_profile: Option<LanguageProfileRecord>,
 }
 
 impl EffectiveQualitySettings {
@@ -271,6 +439,7 @@ impl EffectiveQualitySettings {
             allowed_sources: library.allowed_sources.clone(),
             release_group_blacklist: library.release_group_blacklist.clone(),
             release_group_whitelist: library.release_group_whitelist.clone(),
+            language_profile: None,
         }
     }
 
@@ -314,8 +483,30 @@ impl EffectiveQualitySettings {
                 .release_group_whitelist_override
                 .clone()
                 .unwrap_or_else(|| library.release_group_whitelist.clone()),
+            language_profile: None,
         }
     }
+
+    /// Attach the language profile that applies to the library or show
+    ///
+    /// Lookup failures are logged and leave the releases unfiltered by
+    /// language rather than stopping the hunt.
+    async fn with_language_profile(
+        mut self,
+        db: &Database,
+        library_id: Uuid,
+        show_id: Option<Uuid>,
+    ) -> Self {
+        match db
+            .language_profiles()
+            .get_effective(library_id, show_id)
+            .await
+        {
+            Ok(profile) => self.language_profile = profile,
+            Err(e) => warn!(error = %e, "Failed to load language profile"),
+        }
+        self
+    }
 }
 
 /// Parsed quality info from a release title
@@ -629,6 +820,11 @@ fn score_release(
         score += 25;
     }
 
+    // Preferred audio language
+    if let Some(ref profile) = settings.language_profile {
+        score += language_profile::release_score(profile, &release.title);
+    }
+
     score
 }
 
@@ -641,6 +837,14 @@ fn select_best_release<'a>(
         .iter()
         .filter_map(|r| {
             let parsed = ParsedQualityInfo::from_title(&r.title);
+            if let Some(reason) = settings
+                .language_profile
+                .as_ref()
+                .and_then(|profile| language_profile::release_rejection(profile, &r.title))
+            {
+                debug!("Language filter: '{}' rejected, {}", r.title, reason);
+                return None;
+            }
             if matches_quality_settings(&parsed, settings) {
                 let score = score_release(r, &parsed, settings);
                 Some((r, score))
@@ -690,7 +894,9 @@ pub async fn run_auto_hunt(
                l.allowed_resolutions, l.allowed_video_codecs, l.allowed_audio_formats,
                l.require_hdr, l.allowed_hdr_types, l.allowed_sources,
                l.release_group_blacklist, l.release_group_whitelist,
-               l.auto_download_subtitles, l.preferred_subtitle_languages
+               l.auto_download_subtitles, l.preferred_subtitle_languages,
+               l.exclude_patterns, l.file_uid, l.file_gid, l.file_mode,
+               l.missing_file_policy
         FROM libraries l
         WHERE l.auto_hunt = true
            OR EXISTS (SELECT 1 FROM tv_shows s WHERE s.library_id = l.id AND s.auto_hunt_override = true AND s.monitored = true)
@@ -713,6 +919,15 @@ pub async fn run_auto_hunt(
     let mut total_result = HuntResult::default();
 
     for library in libraries {
+        if automation_paused(&db, &library).await {
+            info!(
+                job = "auto_hunt",
+                library_name = %library.name,
+                "Automation is paused for library, skipping"
+            );
+            continue;
+        }
+
         // Load indexers for this library's user
         if let Err(e) = indexer_manager.load_user_indexers(library.user_id).await {
             warn!(
@@ -825,6 +1040,15 @@ pub async fn hunt_single_movie(
         return Ok(HuntResult::default());
     }
 
+    if automation_paused(db, library).await {
+        info!(
+            job = "auto_hunt",
+            movie_title = %movie.title,
+            "Automation is paused for library, skipping hunt"
+        );
+        return Ok(HuntResult::default());
+    }
+
     info!(
         job = "auto_hunt",
         movie_id = %movie.id,
@@ -836,7 +1060,9 @@ pub async fn hunt_single_movie(
     let mut result = HuntResult::default();
     result.searched = 1;
 
-    let quality_settings = EffectiveQualitySettings::from_library_and_movie(library, movie);
+    let quality_settings = EffectiveQualitySettings::from_library_and_movie(library, movie)
+        .with_language_profile(db, library.id, None)
+        .await;
 
     // Build search query
     let search_term = if let Some(year) = movie.year {
@@ -915,7 +1141,7 @@ pub async fn hunt_single_movie(
         );
 
         // Download using the indexer's authentication
-        match download_release(best, torrent_service, indexer_manager, Some(library.user_id)).await {
+        match download_release(db, best, torrent_service, indexer_manager, Some(library.user_id), movie.runtime).await {
             Ok(torrent_info) => {
                 info!(
                     job = "auto_hunt",
@@ -1022,7 +1248,8 @@ async fn hunt_movies_impl(
         );
     }
 
-    // Get monitored movies without files (media_file_id IS NULL = no file linked)
+    // Get monitored movies without files (media_file_id IS NULL = no file linked),
+    // plus those whose file is waiting for a language re-grab
     let movies: Vec<MovieRecord> = sqlx::query_as(
         r#"
         SELECT id, library_id, user_id, title, sort_title, original_title, year,
@@ -1035,7 +1262,9 @@ async fn hunt_movies_impl(
         FROM movies
         WHERE library_id = ?1
           AND monitored = 1
-          AND media_file_id IS NULL
+          AND (media_file_id IS NULL OR media_file_id IN (
+              SELECT media_file_id FROM language_mismatches WHERE regrab_pending = 1
+          ))
         ORDER BY created_at DESC
         LIMIT ?2
         "#,
@@ -1069,7 +1298,9 @@ async fn hunt_movies_impl(
         let _permit = semaphore.acquire().await?;
         result.searched += 1;
 
-        let quality_settings = EffectiveQualitySettings::from_library_and_movie(library, &movie);
+        let quality_settings = EffectiveQualitySettings::from_library_and_movie(library, &movie)
+            .with_language_profile(db, library.id, None)
+            .await;
 
         // Build search query
         let search_term = if let Some(year) = movie.year {
@@ -1134,6 +1365,8 @@ async fn hunt_movies_impl(
             "Found releases, selecting best match"
         );
 
+        exclude_mismatched_release(db, movie.media_file_id, &mut all_releases).await;
+
         // Select best release based on quality settings
         if let Some(best) = select_best_release(&all_releases, &quality_settings) {
             result.matched += 1;
@@ -1148,7 +1381,7 @@ async fn hunt_movies_impl(
             );
 
             // Download using the indexer's authentication
-            match download_release(best, torrent_service, indexer_manager, Some(library.user_id)).await {
+            match download_release(db, best, torrent_service, indexer_manager, Some(library.user_id), movie.runtime).await {
                 Ok(torrent_info) => {
                     info!(
                         job = "auto_hunt",
@@ -1169,6 +1402,10 @@ async fn hunt_movies_impl(
                         );
                     }
 
+                    if let Some(file_id) = movie.media_file_id {
+                        clear_language_regrab(db, file_id).await;
+                    }
+
                     result.downloaded += 1;
                 }
                 Err(e) => {
@@ -1277,14 +1514,16 @@ async fn hunt_tv_episodes_impl(
         // Get missing episodes for this show, respecting monitor_type
         // - ALL: Get all episodes without files that have aired
         // - FUTURE: Get only episodes that aired after the show was added
-        let episodes: Vec<(String, i32, i32)> = if show.monitor_type.to_uppercase() == "FUTURE" {
+        let episodes: Vec<(String, i32, i32, Option<String>)> = if show.monitor_type.to_uppercase() == "FUTURE" {
             // FUTURE: Only episodes that aired after the show was added to the library
             sqlx::query_as(
                 r#"
-                SELECT id, season, episode
+                SELECT id, season, episode, media_file_id
                 FROM episodes
                 WHERE tv_show_id = ?1
-                  AND media_file_id IS NULL
+                  AND (media_file_id IS NULL OR media_file_id IN (
+                      SELECT media_file_id FROM language_mismatches WHERE regrab_pending = 1
+                  ))
                   AND air_date IS NOT NULL
                   AND air_date <= date('now')
                   AND air_date >= date(?2)
@@ -1301,10 +1540,12 @@ async fn hunt_tv_episodes_impl(
             // ALL: Get all episodes without files that have aired
             sqlx::query_as(
                 r#"
-                SELECT id, season, episode
+                SELECT id, season, episode, media_file_id
                 FROM episodes
                 WHERE tv_show_id = ?1
-                  AND media_file_id IS NULL
+                  AND (media_file_id IS NULL OR media_file_id IN (
+                      SELECT media_file_id FROM language_mismatches WHERE regrab_pending = 1
+                  ))
                   AND (air_date IS NULL OR air_date <= date('now'))
                 ORDER BY season, episode
                 LIMIT ?2
@@ -1328,10 +1569,13 @@ async fn hunt_tv_episodes_impl(
             episodes.len(), show.name
         );
 
-        let quality_settings = EffectiveQualitySettings::from_library_and_show(library, &show);
+        let quality_settings = EffectiveQualitySettings::from_library_and_show(library, &show)
+            .with_language_profile(db, library.id, Some(show.id))
+            .await;
 
-        for (episode_id_str, season, episode) in episodes {
+        for (episode_id_str, season, episode, media_file_id) in episodes {
             let episode_id = Uuid::parse_str(&episode_id_str)?;
+            let media_file_id = media_file_id.and_then(|id| Uuid::parse_str(&id).ok());
             let _permit = semaphore.acquire().await?;
             result.searched += 1;
 
@@ -1370,6 +1614,7 @@ async fn hunt_tv_episodes_impl(
                 }
             }
 
+            exclude_mismatched_release(db, media_file_id, &mut all_releases).await;
             if all_releases.is_empty() {
                 result.skipped += 1;
                 continue;
@@ -1378,7 +1623,7 @@ async fn hunt_tv_episodes_impl(
             if let Some(best) = select_best_release(&all_releases, &quality_settings) {
                 result.matched += 1;
 
-                let add_result = download_release(best, torrent_service, indexer_manager, Some(library.user_id)).await;
+                let add_result = download_release(db, best, torrent_service, indexer_manager, Some(library.user_id), show.runtime).await;
 
                 match add_result {
                     Ok(torrent_info) => {
@@ -1397,6 +1642,10 @@ async fn hunt_tv_episodes_impl(
                             error!(job = "auto_hunt", error = %e, "Failed to create file matches for episode");
                         }
 
+                        if let Some(file_id) = media_file_id {
+                            clear_language_regrab(db, file_id).await;
+                        }
+
                         result.downloaded += 1;
                     }
                     Err(e) => {
@@ -1601,9 +1850,14 @@ async fn hunt_music(
                                     // Good match! Add the torrent
                                     result.matched += 1;
 
-                                    match torrent_service
-                                        .add_torrent_bytes(&torrent_bytes, Some(library.user_id))
-                                        .await
+                                    match add_validated_release(
+                                        db,
+                                        release,
+                                        &torrent_bytes,
+                                        torrent_service,
+                                        Some(library.user_id),
+                                    )
+                                    .await
                                     {
                                         Ok(info) => {
                                             info!(
@@ -1614,6 +1868,9 @@ async fn hunt_music(
                                                 matched_tracks = match_result.matched_count,
                                                 "Music download started (validated)"
                                             );
+                                            torrent_service
+                                                .set_source_indexer(&info.info_hash, indexer_id)
+                                                .await;
 
                                             // Create file-level matches for the album
                                             if let Err(e) =
@@ -1696,7 +1953,7 @@ async fn hunt_music(
 
                 result.matched += 1;
 
-                match download_release(release, torrent_service, indexer_manager, Some(library.user_id)).await {
+                match download_release(db, release, torrent_service, indexer_manager, Some(library.user_id), None).await {
                     Ok(info) => {
                         info!(
                             job = "auto_hunt",
@@ -1928,6 +2185,15 @@ pub async fn hunt_single_album(
         return Ok(HuntResult::default());
     }
 
+    if automation_paused(db, library).await {
+        info!(
+            job = "auto_hunt",
+            album_name = %album.name,
+            "Automation is paused for library, skipping hunt"
+        );
+        return Ok(HuntResult::default());
+    }
+
     info!(
         job = "auto_hunt",
         album_id = %album.id,
@@ -2072,9 +2338,14 @@ pub async fn hunt_single_album(
                             if match_result.meets_threshold(TrackMatchResult::DEFAULT_THRESHOLD) {
                                 result.matched += 1;
 
-                                match torrent_service
-                                    .add_torrent_bytes(&torrent_bytes, Some(library.user_id))
-                                    .await
+                                match add_validated_release(
+                                    db,
+                                    release,
+                                    &torrent_bytes,
+                                    torrent_service,
+                                    Some(library.user_id),
+                                )
+                                .await
                                 {
                                     Ok(info) => {
                                         info!(
@@ -2085,6 +2356,9 @@ pub async fn hunt_single_album(
                                             matched_tracks = match_result.matched_count,
                                             "Album download started (validated)"
                                         );
+                                        torrent_service
+                                            .set_source_indexer(&info.info_hash, indexer_id)
+                                            .await;
 
                                         // Create file-level matches for the album
                                         if let Err(e) =
@@ -2164,7 +2438,7 @@ pub async fn hunt_single_album(
 
             result.matched += 1;
 
-            match download_release(release, torrent_service, indexer_manager, Some(library.user_id)).await {
+            match download_release(db, release, torrent_service, indexer_manager, Some(library.user_id), None).await {
                 Ok(info) => {
                     info!(
                         job = "auto_hunt",
@@ -2238,6 +2512,15 @@ pub async fn hunt_single_audiobook(
         return Ok(HuntResult::default());
     }
 
+    if automation_paused(db, library).await {
+        info!(
+            job = "auto_hunt",
+            audiobook_title = %audiobook.title,
+            "Automation is paused for library, skipping hunt"
+        );
+        return Ok(HuntResult::default());
+    }
+
     info!(
         job = "auto_hunt",
         audiobook_id = %audiobook.id,
@@ -2331,7 +2614,7 @@ pub async fn hunt_single_audiobook(
         );
 
         // Download the release
-        match download_release(release, torrent_service, indexer_manager, Some(library.user_id)).await {
+        match download_release(db, release, torrent_service, indexer_manager, Some(library.user_id), None).await {
             Ok(_) => {
                 info!(
                     job = "auto_hunt",
@@ -2470,7 +2753,7 @@ async fn hunt_audiobooks_impl(
                 "Attempting to download audiobook"
             );
 
-            match download_release(release, torrent_service, indexer_manager, Some(library.user_id)).await {
+            match download_release(db, release, torrent_service, indexer_manager, Some(library.user_id), None).await {
                 Ok(_) => {
                     info!(
                         job = "auto_hunt",
@@ -2620,7 +2903,9 @@ pub async fn run_auto_hunt_for_library(
                allowed_resolutions, allowed_video_codecs, allowed_audio_formats,
                require_hdr, allowed_hdr_types, allowed_sources,
                release_group_blacklist, release_group_whitelist,
-               auto_download_subtitles, preferred_subtitle_languages
+               auto_download_subtitles, preferred_subtitle_languages,
+               exclude_patterns, file_uid, file_gid, file_mode,
+               missing_file_policy
         FROM libraries
         WHERE id = ?1
         "#,
@@ -2662,6 +2947,16 @@ pub async fn run_auto_hunt_for_library(
         return Ok(HuntResult::default());
     }
 
+    if automation_paused(&db, &library).await {
+        info!(
+            job = "auto_hunt",
+            library_id = %library_id,
+            library_name = %library.name,
+            "Automation is paused for library, skipping"
+        );
+        return Ok(HuntResult::default());
+    }
+
     info!(
         job = "auto_hunt",
         library_id = %library_id,
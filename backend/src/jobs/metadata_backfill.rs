@@ -0,0 +1,77 @@
+//! Show metadata backfill job
+//!
+//! Shows added while a metadata provider was down or slow are created with
+//! what could be fetched. This job retries the missing parts (provider
+//! details, episodes, artwork) with backoff, and gives up after
+//! `MAX_BACKFILL_ATTEMPTS`.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use tracing::{debug, info, warn};
+
+use crate::db::Database;
+use crate::services::MetadataService;
+use crate::services::metadata::missing_strings;
+use crate::services::metadata_chain::{MAX_BACKFILL_ATTEMPTS, MissingMetadata, backfill_delay};
+
+/// Shows retried per run
+const BACKFILL_BATCH_SIZE: i64 = 20;
+
+/// Retry missing metadata for shows that are due
+pub async fn backfill_show_metadata(db: Database, metadata: Arc<MetadataService>) -> Result<()> {
+    let tv_shows = db.tv_shows();
+    let due = tv_shows
+        .list_missing_metadata_due(BACKFILL_BATCH_SIZE)
+        .await?;
+    if due.is_empty() {
+        return Ok(());
+    }
+    debug!("Retrying missing metadata for {} show(s)", due.len());
+
+    for record in due {
+        let Some(show) = tv_shows.get_by_id(record.show_id).await? else {
+            continue;
+        };
+        let missing: Vec<MissingMetadata> = record
+            .missing
+            .iter()
+            .filter_map(|m| MissingMetadata::parse(m))
+            .collect();
+
+        let still_missing = match metadata.backfill_show_metadata(&show, &missing).await {
+            Ok(still_missing) => still_missing,
+            Err(e) => {
+                warn!(show_id = %show.id, error = %e, "Metadata backfill failed");
+                missing
+            }
+        };
+
+        let attempts = record.attempts + 1;
+        let retry_at = if still_missing.is_empty() {
+            info!("Metadata for '{}' is complete", show.name);
+            None
+        } else if attempts >= MAX_BACKFILL_ATTEMPTS {
+            warn!(
+                show_id = %show.id,
+                missing = ?still_missing,
+                "Giving up on missing metadata for '{}'",
+                show.name
+            );
+            None
+        } else {
+            Some(chrono::Utc::now() + backfill_delay(attempts))
+        };
+
+        tv_shows
+            .set_missing_metadata(
+                show.id,
+                &missing_strings(&still_missing),
+                attempts,
+                retry_at,
+            )
+            .await?;
+    }
+
+    Ok(())
+}
@@ -5,15 +5,20 @@
 //! - Job execution with retry logic
 //! - Basic failure tracking and logging
 
+pub mod air_notifications;
 pub mod artwork;
 pub mod auto_download;
 pub mod auto_hunt;
 pub mod content_progress;
 pub mod download_monitor;
+pub mod metadata_backfill;
+pub mod nzb_watch_folder;
+pub mod playback_streams;
 pub mod rss_poller;
 pub mod scanner;
 pub mod schedule_sync;
 pub mod transcode_gc;
+pub mod update_check;
 
 use std::future::Future;
 use std::sync::Arc;
@@ -24,7 +29,11 @@ type DbPool = crate::db::Pool;
 use tracing::{error, info, warn};
 
 use crate::indexer::manager::IndexerManager;
-use crate::services::{ScannerService, TorrentService};
+use crate::services::bandwidth::BandwidthManager;
+use crate::services::{
+    NotificationService, ScannerService, ServiceHealthService, TorrentService, TranscodeService,
+    UsenetService,
+};
 
 /// Configuration for job retry behavior
 #[derive(Debug, Clone)]
@@ -166,6 +175,9 @@ pub async fn start_scheduler(
     analysis_queue: Option<Arc<crate::services::MediaAnalysisQueue>>,
     metadata_service: Option<Arc<crate::services::MetadataService>>,
     indexer_manager: Option<Arc<IndexerManager>>,
+    notification_service: Arc<NotificationService>,
+    transcode_service: Arc<TranscodeService>,
+    usenet_service: Arc<UsenetService>,
 ) -> anyhow::Result<JobScheduler> {
     let scheduler = JobScheduler::new().await?;
     let default_retry = JobRetryConfig::default();
@@ -188,14 +200,16 @@ pub async fn start_scheduler(
     })?;
     scheduler.add(scanner_job).await?;
 
-    // RSS/Indexer poller - run every 15 minutes (with retries for network issues)
+    // RSS/Indexer poller - check every minute; each feed has its own adaptive
+    // interval (with retries for network issues)
+    let rss_pool = pool.clone();
     let rss_retry = default_retry.clone();
-    let rss_job = Job::new_async("0 */15 * * * *", move |_uuid, _l| {
+    let rss_job = Job::new_async("0 * * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(rss_pool.clone());
         let retry_cfg = rss_retry.clone();
         Box::pin(async move {
-            info!("Running RSS poller");
             let _ = run_with_retry("rss_poller", &retry_cfg, || async {
-                rss_poller::poll_feeds().await
+                rss_poller::poll_feeds(&db).await
             })
             .await;
         })
@@ -230,17 +244,84 @@ pub async fn start_scheduler(
     })?;
     scheduler.add(download_job).await?;
 
-    // Transcode cache cleanup - run daily at 3 AM (no retry needed - not critical)
-    let gc_job = Job::new_async("0 0 3 * * *", |_uuid, _l| {
+    // Bandwidth schedule - apply the limits for the current time every minute
+    // (no retry - the next run applies them anyway)
+    let bandwidth_pool = pool.clone();
+    let bandwidth_torrent_svc = torrent_service.clone();
+    let bandwidth_job = Job::new_async("0 * * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(bandwidth_pool.clone());
+        let torrent = bandwidth_torrent_svc.clone();
+        Box::pin(async move {
+            if let Err(e) = BandwidthManager::global().apply(&db, &torrent).await {
+                error!("Bandwidth schedule error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(bandwidth_job).await?;
+
+    // Transcode cache cleanup - run every 15 minutes so idle transcode sessions
+    // are closed and the size cap is enforced promptly (no retry needed - not
+    // critical)
+    let gc_pool = pool.clone();
+    let gc_job = Job::new_async("0 */15 * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(gc_pool.clone());
+        let transcode = transcode_service.clone();
         Box::pin(async move {
             info!("Running transcode cache cleanup");
-            if let Err(e) = transcode_gc::cleanup_cache().await {
+            if let Err(e) = transcode_gc::cleanup_cache(db, &transcode).await {
                 error!("Transcode GC error: {}", e);
             }
         })
     })?;
     scheduler.add(gc_job).await?;
 
+    // Artwork blurhash backfill - run hourly (new artwork gets one when cached)
+    let blurhash_pool = pool.clone();
+    let blurhash_job = Job::new_async("0 30 * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(blurhash_pool.clone());
+        Box::pin(async move {
+            if let Err(e) = artwork::backfill_blurhashes(db).await {
+                error!("Artwork blurhash backfill error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(blurhash_job).await?;
+
+    // Show metadata backfill - run every 10 minutes; each show has its own
+    // retry time (no retry - the job backs off per show)
+    if let Some(metadata) = metadata_service.clone() {
+        let backfill_pool = pool.clone();
+        let backfill_job = Job::new_async("0 */10 * * * *", move |_uuid, _l| {
+            let db = crate::db::Database::new(backfill_pool.clone());
+            let metadata = metadata.clone();
+            Box::pin(async move {
+                if let Err(e) = metadata_backfill::backfill_show_metadata(db, metadata).await {
+                    error!("Show metadata backfill error: {}", e);
+                }
+            })
+        })?;
+        scheduler.add(backfill_job).await?;
+    }
+
+    // External service health - probe every 5 minutes (no retry - the probe
+    // results are the point, failures included)
+    let health_service = Arc::new(ServiceHealthService::new(
+        crate::db::Database::new(pool.clone()),
+        metadata_service.clone(),
+        indexer_manager.clone(),
+        notification_service.clone(),
+        usenet_service.clone(),
+    ));
+    let health_job = Job::new_async("0 2/5 * * * *", move |_uuid, _l| {
+        let health = health_service.clone();
+        Box::pin(async move {
+            if let Err(e) = health.probe_all().await {
+                error!("External service health probe error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(health_job).await?;
+
     // TV Schedule sync - run every 6 hours (with retries for API issues)
     let schedule_pool = pool.clone();
     let schedule_retry = default_retry.clone();
@@ -258,6 +339,58 @@ pub async fn start_scheduler(
     })?;
     scheduler.add(schedule_job).await?;
 
+    // Update check - every 6 hours (no retry)
+    let update_pool = pool.clone();
+    let update_notifications = notification_service.clone();
+    let update_check_job = Job::new_async("0 17 */6 * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(update_pool.clone());
+        let notifications = update_notifications.clone();
+        Box::pin(async move {
+            if let Err(e) = update_check::run_scheduled_check(db, &notifications).await {
+                warn!("Update check error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(update_check_job).await?;
+
+    // Episode air notifications - run every 15 minutes (no retry - next run catches up)
+    let air_pool = pool.clone();
+    let air_notifications_job = Job::new_async("0 */15 * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(air_pool.clone());
+        let notifications = notification_service.clone();
+        Box::pin(async move {
+            if let Err(e) = air_notifications::send_air_notifications(db, notifications).await {
+                error!("Episode air notification error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(air_notifications_job).await?;
+
+    // Playback stream expiry - run every minute so crashed clients free their slot quickly
+    let streams_pool = pool.clone();
+    let streams_job = Job::new_async("0 * * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(streams_pool.clone());
+        Box::pin(async move {
+            if let Err(e) = playback_streams::expire_stale_streams(db).await {
+                error!("Playback stream expiry error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(streams_job).await?;
+
+    // NZB watch folder - check every minute (no retry - next run picks files up)
+    let watch_pool = pool.clone();
+    let watch_job = Job::new_async("30 * * * * *", move |_uuid, _l| {
+        let db = crate::db::Database::new(watch_pool.clone());
+        let usenet = usenet_service.clone();
+        Box::pin(async move {
+            if let Err(e) = nzb_watch_folder::poll_watch_folder(db, &usenet).await {
+                error!("NZB watch folder error: {}", e);
+            }
+        })
+    })?;
+    scheduler.add(watch_job).await?;
+
     // NOTE: Auto-hunt no longer runs on an independent schedule.
     // It now runs in two scenarios:
     // 1. Immediately when a new movie is added (via add_movie mutation)
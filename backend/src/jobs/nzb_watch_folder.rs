@@ -0,0 +1,200 @@
+//! NZB watch folder
+//!
+//! Polls a folder for `.nzb` files and adds each one as a usenet download,
+//! for the user who set the folder up. Imported files are deleted, like
+//! SABnzbd does; files that fail to import are renamed to `.nzb.failed` so
+//! they aren't retried every minute.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::Database;
+use crate::services::grab_ledger::{self, Claim, GrabRequest, GrabSource};
+use crate::services::usenet::UsenetService;
+use crate::usenet::nzb::NzbFile;
+
+/// Settings key for the watch folder (unset = off)
+pub const WATCH_FOLDER_KEY: &str = "usenet.watch_folder";
+
+/// Files modified more recently than this may still be being written
+const SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// Suffix given to files that couldn't be imported
+const FAILED_SUFFIX: &str = ".failed";
+
+/// The watch folder and the user its downloads belong to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchFolder {
+    pub path: String,
+    pub user_id: Uuid,
+}
+
+/// Import the NZB files waiting in the watch folder
+pub async fn poll_watch_folder(db: Database, usenet_service: &UsenetService) -> Result<()> {
+    let Some(folder) = db
+        .settings()
+        .get_value::<WatchFolder>(WATCH_FOLDER_KEY)
+        .await?
+    else {
+        return Ok(());
+    };
+
+    let entries = match std::fs::read_dir(&folder.path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!(path = %folder.path, error = %e, "Cannot read NZB watch folder");
+            return Ok(());
+        }
+    };
+
+    let now = SystemTime::now();
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            entry
+                .metadata()
+                .is_ok_and(|m| m.is_file() && is_settled(m.modified().ok(), now))
+        })
+        .map(|entry| entry.path())
+        .filter(|path| is_nzb_file(path))
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Ok(());
+    }
+    debug!(count = files.len(), "Importing NZBs from watch folder");
+
+    for path in files {
+        match import_file(&db, usenet_service, folder.user_id, &path).await {
+            Ok(name) => {
+                info!(file = %path.display(), "Added '{}' from NZB watch folder", name);
+                if let Err(e) = std::fs::remove_file(&path) {
+                    warn!(file = %path.display(), error = %e, "Failed to remove imported NZB");
+                }
+            }
+            Err(e) => {
+                warn!(file = %path.display(), error = %e, "Failed to import NZB from watch folder");
+                let mut failed = path.clone().into_os_string();
+                failed.push(FAILED_SUFFIX);
+                if let Err(e) = std::fs::rename(&path, &failed) {
+                    warn!(file = %path.display(), error = %e, "Failed to set aside NZB");
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Add one NZB file, returning the download's name
+async fn import_file(
+    db: &Database,
+    usenet_service: &UsenetService,
+    user_id: Uuid,
+    path: &Path,
+) -> Result<String> {
+    let data = std::fs::read(path)?;
+    let nzb = NzbFile::parse(&data)?;
+    let name = nzb
+        .metadata
+        .title
+        .clone()
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| release_name(path));
+
+    // Skip releases already grabbed by another pipeline
+    let request = GrabRequest {
+        user_id,
+        source: GrabSource::WatchFolder,
+        download_type: "usenet",
+        title: Some(name.clone()),
+        info_hash: None,
+        guid: None,
+        indexer_id: None,
+        force: false,
+    };
+    let claim_id = match grab_ledger::claim(db, request).await? {
+        Claim::Claimed(id) => id,
+        Claim::Duplicate(earlier) => {
+            anyhow::bail!(grab_ledger::duplicate_message(earlier.as_ref()));
+        }
+    };
+
+    let result = usenet_service
+        .add_nzb(
+            &data,
+            name.clone(),
+            user_id,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await;
+    if result.is_err()
+        && let Err(e) = grab_ledger::abandon(db, claim_id).await
+    {
+        warn!(error = %e, "Failed to drop grab claim");
+    }
+    result?;
+
+    Ok(name)
+}
+
+/// Whether a path names an NZB file
+pub fn is_nzb_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("nzb"))
+}
+
+/// Whether a file was last written long enough ago to be complete
+pub fn is_settled(modified: Option<SystemTime>, now: SystemTime) -> bool {
+    modified
+        .and_then(|modified| now.duration_since(modified).ok())
+        .is_some_and(|age| age >= SETTLE_TIME)
+}
+
+/// Release name from an NZB's file name, without the extension
+pub fn release_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_nzb_file() {
+        assert!(is_nzb_file(Path::new("/watch/Show.S01E01.nzb")));
+        assert!(is_nzb_file(Path::new("/watch/Movie.2024.NZB")));
+        assert!(!is_nzb_file(Path::new("/watch/Movie.2024.nzb.failed")));
+        assert!(!is_nzb_file(Path::new("/watch/readme.txt")));
+    }
+
+    #[test]
+    fn test_is_settled() {
+        let now = SystemTime::now();
+        assert!(is_settled(Some(now - Duration::from_secs(60)), now));
+        assert!(!is_settled(Some(now - Duration::from_secs(2)), now));
+        assert!(!is_settled(Some(now + Duration::from_secs(5)), now));
+        assert!(!is_settled(None, now));
+    }
+
+    #[test]
+    fn test_release_name() {
+        assert_eq!(
+            release_name(Path::new("/watch/Show.S01E01.1080p.nzb")),
+            "Show.S01E01.1080p"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Playback stream lease expiry
+//!
+//! Clients heartbeat their stream lease while playing. When a client goes
+//! away without stopping (closed tab, crash, lost network) its lease would
+//! otherwise stay active forever and count against the concurrent stream
+//! limit; this job ends leases that missed their heartbeats and prunes old
+//! ended leases.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use tracing::{debug, info};
+
+use crate::db::Database;
+
+/// Settings key for the per-user concurrent stream limit (0 = unlimited)
+pub const MAX_STREAMS_KEY: &str = "playback_max_concurrent_streams";
+
+/// Settings key for how long a lease survives without a heartbeat (seconds)
+pub const HEARTBEAT_TIMEOUT_KEY: &str = "playback_heartbeat_timeout";
+
+pub const DEFAULT_MAX_STREAMS: i32 = 0;
+pub const DEFAULT_HEARTBEAT_TIMEOUT_SECS: i32 = 90;
+
+/// Shortest allowed timeout, so a slow client isn't expired between heartbeats
+pub const MIN_HEARTBEAT_TIMEOUT_SECS: i32 = 30;
+
+/// How long ended leases are kept for the record
+const ENDED_RETENTION_DAYS: i64 = 7;
+
+/// Whether a user with `active` streams may not open another one
+pub fn stream_limit_reached(active: i64, max_streams: i32) -> bool {
+    max_streams > 0 && active >= i64::from(max_streams)
+}
+
+/// Leases whose last heartbeat is before this instant are stale
+pub fn heartbeat_cutoff(now: DateTime<Utc>, timeout_secs: i32) -> DateTime<Utc> {
+    now - Duration::seconds(i64::from(timeout_secs.max(MIN_HEARTBEAT_TIMEOUT_SECS)))
+}
+
+/// Expire stream leases that stopped heartbeating
+pub async fn expire_stale_streams(db: Database) -> Result<()> {
+    let timeout = db
+        .settings()
+        .get_or_default::<i32>(HEARTBEAT_TIMEOUT_KEY, DEFAULT_HEARTBEAT_TIMEOUT_SECS)
+        .await?;
+
+    let now = Utc::now();
+    let repo = db.playback_streams();
+
+    let expired = repo.expire_stale(heartbeat_cutoff(now, timeout)).await?;
+    if expired > 0 {
+        info!(
+            "Expired {} playback stream(s) without a heartbeat for {}s",
+            expired, timeout
+        );
+    }
+
+    let pruned = repo
+        .delete_ended_before(now - Duration::days(ENDED_RETENTION_DAYS))
+        .await?;
+    if pruned > 0 {
+        debug!("Pruned {} ended playback stream(s)", pruned);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_limit_reached() {
+        assert!(!stream_limit_reached(5, 0), "0 means unlimited");
+        assert!(!stream_limit_reached(1, 2));
+        assert!(stream_limit_reached(2, 2));
+        assert!(stream_limit_reached(3, 2));
+    }
+
+    #[test]
+    fn test_heartbeat_cutoff_enforces_minimum() {
+        let now = Utc::now();
+        assert_eq!(heartbeat_cutoff(now, 90), now - Duration::seconds(90));
+        assert_eq!(
+            heartbeat_cutoff(now, 5),
+            now - Duration::seconds(i64::from(MIN_HEARTBEAT_TIMEOUT_SECS))
+        );
+    }
+}
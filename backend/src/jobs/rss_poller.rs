@@ -12,7 +12,7 @@
 //! Episode matching is no longer performed here - use auto-hunt instead.
 
 use anyhow::Result;
-use chrono::Datelike;
+use chrono::{DateTime, Datelike, Utc};
 use regex::Regex;
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,11 +20,14 @@ use tokio::sync::Semaphore;
 use tracing::{debug, error, info};
 use uuid::Uuid;
 
+use crate::db::LanguageProfileRecord;
 use crate::db::libraries::LibraryRecord;
 use crate::db::tv_shows::TvShowRecord;
 use crate::db::{CreateRssFeedItem, Database, RssFeedRecord};
 use crate::services::ParsedRssItem;
 use crate::services::RssService;
+use crate::services::automation_pause;
+use crate::services::language_profile;
 use crate::services::text_utils::{normalize_quality, normalize_show_name};
 
 /// Maximum concurrent feed fetches
@@ -33,31 +36,34 @@ const MAX_CONCURRENT_FEEDS: usize = 5;
 /// Delay between feed fetch batches (ms)
 const FEED_BATCH_DELAY_MS: u64 = 200;
 
-/// Poll all RSS feeds that are due for polling
-pub async fn poll_feeds() -> Result<()> {
-    debug!("Starting RSS feed poll job");
-
-    // Get database path/URL from environment
-    let mut database_url = std::env::var("DATABASE_PATH")
-        .or_else(|_| std::env::var("DATABASE_URL"))
-        .unwrap_or_else(|_| "./data/librarian.db".to_string());
-    if !database_url.starts_with("sqlite:") {
-        database_url = format!("sqlite://{}", database_url);
-    }
-
-    let db = Database::connect(&database_url).await?;
+/// Most recent items used to estimate how often a feed gets new ones
+const ARRIVAL_SAMPLE_SIZE: usize = 20;
 
-    poll_feeds_with_db(&db).await
-}
-
-/// Poll feeds with a provided database connection
+/// Poll all RSS feeds that are due for polling
 ///
-/// Uses bounded concurrency to prevent overwhelming external RSS servers.
-pub async fn poll_feeds_with_db(db: &Database) -> Result<()> {
+/// Runs every minute; each feed is only fetched once its own (adaptive)
+/// interval has passed. Uses bounded concurrency to prevent overwhelming
+/// external RSS servers.
+pub async fn poll_feeds(db: &Database) -> Result<()> {
     let rss_service = Arc::new(RssService::new());
 
     // Get feeds that need polling
-    let feeds = db.rss_feeds().list_due_for_poll().await?;
+    let due = db.rss_feeds().list_due_for_poll().await?;
+
+    // Feeds of paused libraries (or users with a global pause) wait until
+    // automation resumes
+    let mut feeds = Vec::with_capacity(due.len());
+    for feed in due {
+        if feed_paused(db, &feed).await {
+            debug!(
+                job = "rss_poller",
+                feed_name = %feed.name,
+                "Automation is paused, skipping feed"
+            );
+        } else {
+            feeds.push(feed);
+        }
+    }
 
     if feeds.is_empty() {
         debug!(job = "rss_poller", "No feeds due for polling");
@@ -134,6 +140,27 @@ pub async fn poll_feeds_with_db(db: &Database) -> Result<()> {
     Ok(())
 }
 
+/// Whether automation is paused for a feed's library, or globally for its user
+async fn feed_paused(db: &Database, feed: &RssFeedRecord) -> bool {
+    match automation_pause::active_pauses(db, feed.user_id).await {
+        Ok(pauses) => match feed.library_id {
+            Some(library_id) => {
+                automation_pause::pause_for_library(&pauses, library_id, Utc::now()).is_some()
+            }
+            None => pauses.iter().any(|p| p.library_id.is_none()),
+        },
+        Err(e) => {
+            error!(
+                job = "rss_poller",
+                feed_id = %feed.id,
+                error = %e,
+                "Failed to check automation pause"
+            );
+            false
+        }
+    }
+}
+
 /// Poll a single RSS feed by ID (for manual polling via GraphQL)
 pub async fn poll_single_feed_by_id(db: &Database, feed_id: Uuid) -> Result<(i32, i32)> {
     let feed = db
@@ -161,7 +188,8 @@ async fn poll_single_feed(
     );
 
     // Fetch and parse the feed
-    let items = rss_service.fetch_feed(&feed.url).await?;
+    let fetched = rss_service.fetch_feed(&feed.url).await?;
+    let items = fetched.items;
     info!(
         job = "rss_poller",
         feed_name = %feed.name,
@@ -169,6 +197,7 @@ async fn poll_single_feed(
         "Fetched items from feed: {}",
         feed.name
     );
+    let arrival_gap = arrival_gap_minutes(items.iter().filter_map(|item| item.pub_date).collect());
 
     let mut new_items = 0;
     let mut matched_episodes = 0;
@@ -193,6 +222,7 @@ async fn poll_single_feed(
             hdr: item.parsed_hdr.clone(),
             source: item.parsed_source.clone(),
             release_group: extract_release_group(&item.title),
+            title: item.title.clone(),
         };
 
         // Store the new item with the transformed download link
@@ -285,14 +315,26 @@ async fn poll_single_feed(
         }
     }
 
-    // Mark the feed as successfully polled
-    db.rss_feeds().mark_poll_success(feed.id).await?;
+    // Mark the feed as successfully polled, adapting when it's polled next
+    let next_interval = next_poll_interval(
+        feed.current_poll_interval_minutes
+            .unwrap_or(feed.poll_interval_minutes),
+        new_items,
+        arrival_gap,
+        fetched.ttl_minutes,
+        feed.min_poll_interval_minutes,
+        feed.max_poll_interval_minutes,
+    );
+    db.rss_feeds()
+        .mark_poll_success(feed.id, next_interval, fetched.ttl_minutes)
+        .await?;
 
     info!(
         job = "rss_poller",
         feed_name = %feed.name,
         new_items = new_items,
         matched_episodes = matched_episodes,
+        next_poll_minutes = next_interval,
         "Feed poll complete for {}: {} new items, {} matched episodes",
         feed.name, new_items, matched_episodes
     );
@@ -300,6 +342,52 @@ async fn poll_single_feed(
     Ok((new_items, matched_episodes))
 }
 
+/// Median gap in minutes between the publish dates of a feed's most recent
+/// items
+///
+/// None with fewer than three dated items, which can't tell a busy feed
+/// from a one-off burst.
+fn arrival_gap_minutes(mut dates: Vec<DateTime<Utc>>) -> Option<i64> {
+    dates.sort_unstable_by(|a, b| b.cmp(a));
+    dates.truncate(ARRIVAL_SAMPLE_SIZE);
+    if dates.len() < 3 {
+        return None;
+    }
+    let mut gaps: Vec<i64> = dates
+        .windows(2)
+        .map(|pair| (pair[0] - pair[1]).num_minutes())
+        .collect();
+    gaps.sort_unstable();
+    Some(gaps[gaps.len() / 2])
+}
+
+/// Minutes until a feed is polled again
+///
+/// A poll that found new items aims for two polls per item arrival (or
+/// halves the interval when the feed's dates don't say how often that is);
+/// an empty poll backs off by half again. Each poll only moves halfway to
+/// that target, so one burst or quiet spell doesn't swing the interval.
+/// The result stays within the feed's bounds and never below its TTL.
+fn next_poll_interval(
+    current: i32,
+    new_items: i32,
+    arrival_gap: Option<i64>,
+    ttl: Option<i32>,
+    min: i32,
+    max: i32,
+) -> i32 {
+    let current = f64::from(current);
+    let target = if new_items > 0 {
+        arrival_gap.map_or(current / 2.0, |gap| gap as f64 / 2.0)
+    } else {
+        current * 1.5
+    };
+    let next = ((current + target) / 2.0).round() as i32;
+
+    let min = min.max(ttl.unwrap_or(0));
+    next.clamp(min, max.max(min))
+}
+
 /// Effective quality settings for a show (merged from library + show overrides)
 #[derive(Debug, Default)]
 struct EffectiveQualitySettings {
@@ -311,6 +399,8 @@ struct EffectiveQualitySettings {
     pub allowed_sources: Vec<String>,
     pub release_group_blacklist: Vec<String>,
     pub release_group_whitelist: Vec<String>,
+    /// Language profile of the show or its library, if one is assigned
+    pub language_profile: Option<LanguageProfileRecord>,
 }
 
 impl EffectiveQualitySettings {
@@ -347,6 +437,7 @@ impl EffectiveQualitySettings {
                 .release_group_whitelist_override
                 .clone()
                 .unwrap_or_else(|| library.release_group_whitelist.clone()),
+            language_profile: None,
         }
     }
 }
@@ -368,9 +459,13 @@ async fn get_effective_quality_settings(
         None => return Ok(None),
     };
 
-    Ok(Some(EffectiveQualitySettings::from_library_and_show(
-        &library, &show,
-    )))
+    let mut settings = EffectiveQualitySettings::from_library_and_show(&library, &show);
+    settings.language_profile = db
+        .language_profiles()
+        .get_effective(library.id, Some(show.id))
+        .await?;
+
+    Ok(Some(settings))
 }
 
 /// Parsed quality info from an RSS item for matching
@@ -382,6 +477,8 @@ struct ParsedQualityInfo {
     pub hdr: Option<String>,
     pub source: Option<String>,
     pub release_group: Option<String>,
+    /// Full item title, for language tags
+    pub title: String,
 }
 
 /// Check if an RSS item matches the quality settings
@@ -535,6 +632,14 @@ fn matches_quality_settings(
         }
     }
 
+    // Check the language profile's required audio languages
+    if let Some(ref profile) = settings.language_profile
+        && let Some(reason) = language_profile::release_rejection(profile, &parsed.title)
+    {
+        debug!("Language filter: '{}' rejected, {}", parsed.title, reason);
+        return false;
+    }
+
     true
 }
 
@@ -922,4 +1027,30 @@ mod tests {
             "power book iii raising kanan"
         );
     }
+
+    #[test]
+    fn test_arrival_gap_minutes() {
+        let now = Utc::now();
+        let dates = [0, 10, 20, 30, 200]
+            .into_iter()
+            .map(|m| now - chrono::Duration::minutes(m))
+            .collect();
+        assert_eq!(arrival_gap_minutes(dates), Some(10));
+        assert_eq!(arrival_gap_minutes(vec![now, now]), None);
+    }
+
+    #[test]
+    fn test_next_poll_interval() {
+        // Busy feed: items every 10 minutes, so aim for 5
+        assert_eq!(next_poll_interval(15, 3, Some(10), None, 5, 120), 10);
+        assert_eq!(next_poll_interval(10, 3, Some(10), None, 5, 120), 8);
+        // Quiet feed backs off, up to the max
+        assert_eq!(next_poll_interval(40, 0, Some(10), None, 5, 120), 50);
+        assert_eq!(next_poll_interval(120, 0, None, None, 5, 120), 120);
+        // New items in an undated feed halve the interval, halfway at a time
+        assert_eq!(next_poll_interval(20, 1, None, None, 5, 120), 15);
+        // TTL wins over the minimum, even past the max
+        assert_eq!(next_poll_interval(15, 3, Some(2), Some(30), 5, 120), 30);
+        assert_eq!(next_poll_interval(15, 0, None, Some(180), 5, 120), 180);
+    }
 }
@@ -1,14 +1,33 @@
 //! Transcode cache garbage collection
+//!
+//! Closes transcode sessions that have gone idle, then removes stale sessions
+//! and keeps the cache under its configured size cap, evicting the least
+//! recently used unpinned sessions first.
 
 use anyhow::Result;
 
-/// Clean up stale transcode cache files
-pub async fn cleanup_cache() -> Result<()> {
-    // TODO: Implement cache cleanup
-    // 1. Walk cache directory
-    // 2. Find sessions older than threshold
-    // 3. Delete stale session directories
+use crate::db::Database;
+use crate::services::TranscodeService;
+use crate::services::transcode_cache::{DEFAULT_MAX_SIZE_GB, MAX_SIZE_GB_KEY, max_bytes_from_gb};
 
-    tracing::info!("Transcode cache cleanup completed");
+/// Close idle transcode sessions, clean up stale cache files and enforce the size cap
+pub async fn cleanup_cache(db: Database, transcode: &TranscodeService) -> Result<()> {
+    // Closing a session unpins it, so its segments can be evicted below
+    let closed = transcode.stop_idle_sessions();
+
+    let max_gb = db
+        .settings()
+        .get_or_default::<f64>(MAX_SIZE_GB_KEY, DEFAULT_MAX_SIZE_GB)
+        .await?;
+
+    let result = transcode.cache().evict(max_bytes_from_gb(max_gb)).await?;
+
+    tracing::info!(
+        idle_sessions_closed = closed,
+        removed = result.sessions_removed,
+        freed_bytes = result.bytes_freed,
+        pinned = result.sessions_skipped,
+        "Transcode cache cleanup completed"
+    );
     Ok(())
 }
@@ -0,0 +1,356 @@
+//! Release update checker
+//!
+//! Every six hours, asks the GitHub releases API for the latest Librarian
+//! release and compares it with the running version. The result is cached in
+//! settings for the `updateAvailable` query, and users who can manage the
+//! server get one notification per new release. Turned off by setting
+//! `updates.check_enabled` to false.
+
+use std::cmp::Ordering;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::roles::Permission;
+use crate::db::{CreateNotification, Database, NotificationCategory, NotificationType};
+use crate::services::NotificationService;
+
+/// Setting key for whether the scheduled check runs
+pub const CHECK_ENABLED_KEY: &str = "updates.check_enabled";
+
+pub const DEFAULT_CHECK_ENABLED: bool = true;
+
+/// Setting key for the latest release seen (a `ReleaseInfo`)
+const LATEST_RELEASE_KEY: &str = "updates.latest_release";
+
+/// Setting key for the last version admins were notified about
+const NOTIFIED_VERSION_KEY: &str = "updates.notified_version";
+
+/// Latest published release (drafts and pre-releases are excluded by GitHub)
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/Dastari/librarian/releases/latest";
+
+/// Longest changelog excerpt kept, in characters
+const CHANGELOG_EXCERPT_CHARS: usize = 600;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// The latest release, as cached in settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    /// Version without a leading "v", e.g. "0.4.0"
+    pub version: String,
+    pub name: Option<String>,
+    /// Release page on GitHub
+    pub url: String,
+    pub published_at: Option<String>,
+    /// Start of the release notes
+    pub changelog: String,
+    /// When the release was fetched
+    pub checked_at: String,
+    /// Downloadable files attached to the release
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub download_url: String,
+}
+
+impl ReleaseInfo {
+    /// Whether this release is newer than the running server
+    pub fn is_update(&self) -> bool {
+        compare_versions(&self.version, current_version()) == Some(Ordering::Greater)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    name: Option<String>,
+    html_url: String,
+    published_at: Option<String>,
+    body: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+/// Version of the running server
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Whether the scheduled check is turned on
+pub async fn is_enabled(db: &Database) -> Result<bool> {
+    db.settings()
+        .get_or_default(CHECK_ENABLED_KEY, DEFAULT_CHECK_ENABLED)
+        .await
+}
+
+/// The latest release from the last check, if there has been one
+pub async fn cached_release(db: &Database) -> Result<Option<ReleaseInfo>> {
+    db.settings().get_value(LATEST_RELEASE_KEY).await
+}
+
+/// Scheduled entry point; does nothing when the check is turned off
+pub async fn run_scheduled_check(db: Database, notifications: &NotificationService) -> Result<()> {
+    if !is_enabled(&db).await? {
+        debug!("Update check disabled, skipping");
+        return Ok(());
+    }
+    check_for_updates(&db, notifications).await?;
+    Ok(())
+}
+
+/// Fetch the latest release, cache it, and notify about it if it's new
+///
+/// Returns the release when it is newer than the running version.
+pub async fn check_for_updates(
+    db: &Database,
+    notifications: &NotificationService,
+) -> Result<Option<ReleaseInfo>> {
+    let release = fetch_latest_release().await?;
+    db.settings()
+        .set_with_category(
+            LATEST_RELEASE_KEY,
+            &release,
+            "updates",
+            Some("Latest Librarian release seen by the update check"),
+        )
+        .await?;
+
+    if !release.is_update() {
+        debug!(
+            latest = %release.version,
+            current = current_version(),
+            "Librarian is up to date"
+        );
+        return Ok(None);
+    }
+
+    let notified: Option<String> = db.settings().get_value(NOTIFIED_VERSION_KEY).await?;
+    if notified.as_deref() != Some(release.version.as_str()) {
+        info!(
+            latest = %release.version,
+            current = current_version(),
+            "New Librarian release available"
+        );
+        notify_admins(db, notifications, &release).await?;
+        db.settings()
+            .set_with_category(NOTIFIED_VERSION_KEY, &release.version, "updates", None)
+            .await?;
+    }
+
+    Ok(Some(release))
+}
+
+/// Fetch the latest release from GitHub without caching it
+pub async fn fetch_latest_release() -> Result<ReleaseInfo> {
+    let client = reqwest::Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .build()?;
+    let response = client
+        .get(LATEST_RELEASE_URL)
+        // GitHub rejects API requests without a user agent
+        .header(
+            reqwest::header::USER_AGENT,
+            format!("Librarian/{}", current_version()),
+        )
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .send()
+        .await
+        .context("Failed to reach the GitHub releases API")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("GitHub releases API returned status: {}", response.status());
+    }
+
+    let release: GithubRelease = response
+        .json()
+        .await
+        .context("Failed to parse the latest release")?;
+
+    Ok(ReleaseInfo {
+        version: release.tag_name.trim_start_matches('v').to_string(),
+        name: release.name.filter(|n| !n.trim().is_empty()),
+        url: release.html_url,
+        published_at: release.published_at,
+        changelog: changelog_excerpt(
+            release.body.as_deref().unwrap_or(""),
+            CHANGELOG_EXCERPT_CHARS,
+        ),
+        checked_at: chrono::Utc::now().to_rfc3339(),
+        assets: release
+            .assets
+            .into_iter()
+            .map(|asset| ReleaseAsset {
+                name: asset.name,
+                download_url: asset.browser_download_url,
+            })
+            .collect(),
+    })
+}
+
+/// Tell every active user who can manage the server about the release
+async fn notify_admins(
+    db: &Database,
+    notifications: &NotificationService,
+    release: &ReleaseInfo,
+) -> Result<()> {
+    let title = format!("Librarian {} is available", release.version);
+    let mut message = format!(
+        "You are running {}. Pull the new image, or install it from Settings > System.",
+        current_version()
+    );
+    if !release.changelog.is_empty() {
+        message.push_str("\n\n");
+        message.push_str(&release.changelog);
+    }
+    message.push_str(&format!("\n\nRelease notes: {}", release.url));
+
+    for user in db.users().list_all().await? {
+        let Ok(user_id) = Uuid::parse_str(&user.id) else {
+            continue;
+        };
+        // Inactive users have no role, so they are skipped here
+        let can_update = db
+            .roles()
+            .get_for_user(user_id)
+            .await?
+            .is_some_and(|role| role.has(Permission::ManageServer));
+        if !can_update {
+            continue;
+        }
+
+        // Each version is only announced once (tracked in settings), and an
+        // unresolved notice for an older version mustn't swallow this one
+        if let Err(e) = notifications
+            .create_without_dedupe(CreateNotification {
+                user_id,
+                title: title.clone(),
+                message: message.clone(),
+                notification_type: NotificationType::Info,
+                category: NotificationCategory::Update,
+                library_id: None,
+                torrent_id: None,
+                media_file_id: None,
+                pending_match_id: None,
+                action_type: None,
+                action_data: None,
+            })
+            .await
+        {
+            warn!(user_id = %user.id, error = %e, "Failed to create update notification");
+        }
+    }
+
+    Ok(())
+}
+
+/// Numeric parts and pre-release tag of a version like "1.2.3-rc.1"
+fn parse_version(version: &str) -> Option<(Vec<u64>, Option<&str>)> {
+    let version = version.trim().trim_start_matches('v');
+    // Build metadata doesn't affect precedence
+    let version = version.split('+').next()?;
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let parts = core
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    (!parts.is_empty()).then_some((parts, pre))
+}
+
+/// Compare two versions, or None if either doesn't parse
+///
+/// Missing parts count as zero ("1.2" == "1.2.0") and a pre-release sorts
+/// before its release. Pre-release tags are compared as plain strings.
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (a_parts, a_pre) = parse_version(a)?;
+    let (b_parts, b_pre) = parse_version(b)?;
+
+    let len = a_parts.len().max(b_parts.len());
+    let part = |parts: &[u64], i: usize| parts.get(i).copied().unwrap_or(0);
+    for i in 0..len {
+        match part(&a_parts, i).cmp(&part(&b_parts, i)) {
+            Ordering::Equal => continue,
+            other => return Some(other),
+        }
+    }
+
+    Some(match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(b),
+    })
+}
+
+/// The start of the release notes, cut at a line break where possible
+fn changelog_excerpt(body: &str, max_chars: usize) -> String {
+    let body = body.trim().replace("\r\n", "\n");
+    if body.chars().count() <= max_chars {
+        return body;
+    }
+
+    let cut: String = body.chars().take(max_chars).collect();
+    let cut = match cut.rfind('\n') {
+        // Don't throw away most of the excerpt to end on a line break
+        Some(i) if i >= max_chars / 2 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    format!("{}…", cut.trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("0.2.0", "0.1.9"), Some(Ordering::Greater));
+        assert_eq!(
+            compare_versions("v0.10.0", "0.9.0"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_versions("1.2", "1.2.0"), Some(Ordering::Equal));
+        assert_eq!(
+            compare_versions("1.0.0-rc.1", "1.0.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.0.0", "0.9.0-beta"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_versions("1.0.0+build.5", "1.0.0"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(compare_versions("nightly", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_changelog_excerpt() {
+        assert_eq!(changelog_excerpt("  short notes \r\n", 600), "short notes");
+
+        let body = format!("{}\n{}", "a".repeat(40), "b".repeat(40));
+        assert_eq!(changelog_excerpt(&body, 50), format!("{}…", "a".repeat(40)));
+
+        // No usable line break: cut mid-line
+        let body = "c".repeat(100);
+        assert_eq!(changelog_excerpt(&body, 10), format!("{}…", "c".repeat(10)));
+    }
+}
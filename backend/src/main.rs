@@ -11,10 +11,11 @@ mod db;
 mod graphql;
 mod indexer;
 mod jobs;
-mod media;
 mod services;
 #[cfg(feature = "embed-frontend")]
 mod static_assets;
+#[cfg(test)]
+mod testing;
 mod tui;
 mod usenet;
 
@@ -26,8 +27,8 @@ use async_graphql::http::GraphiQLSource;
 use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::Router;
 use axum::extract::WebSocketUpgrade;
-use axum::http::HeaderMap;
 use axum::http::header::AUTHORIZATION;
+use axum::http::{HeaderMap, HeaderName, HeaderValue};
 use axum::response::IntoResponse;
 use axum::routing::get;
 use tower_http::cors::{Any, CorsLayer};
@@ -45,8 +46,9 @@ use crate::graphql::{LibraryChangedEvent, MediaFileUpdatedEvent};
 use crate::services::{
     AuthConfig, AuthService, CastService, CastServiceConfig, DatabaseLoggerConfig, FfmpegService,
     FilesystemService, FilesystemServiceConfig, MediaAnalysisQueue, MetadataServiceConfig,
-    ScannerService, TorrentService, TorrentServiceConfig, create_database_layer,
+    ScannerService, TorrentService, TorrentServiceConfig, UsenetService, create_database_layer,
     create_media_analysis_queue, create_metadata_service_with_artwork, create_metrics_collector,
+    create_subtitle_download_queue,
 };
 use crate::tui::{TuiApp, TuiConfig, create_tui_layer, should_use_tui};
 
@@ -56,11 +58,16 @@ pub struct AppState {
     pub config: Arc<Config>,
     pub db: Database,
     pub schema: LibrarianSchema,
+    /// Hash of the GraphQL schema, sent as the `x-schema-version` header
+    pub schema_version: HeaderValue,
     pub torrent_service: Arc<TorrentService>,
     pub scanner_service: Arc<ScannerService>,
     pub cast_service: Arc<CastService>,
     pub filesystem_service: Arc<FilesystemService>,
     pub analysis_queue: Arc<MediaAnalysisQueue>,
+    pub usenet_service: Arc<UsenetService>,
+    /// Sessions of apps logged in to the qBittorrent API
+    pub qbittorrent_sessions: Arc<api::qbittorrent::SessionStore>,
 }
 
 #[tokio::main]
@@ -70,6 +77,24 @@ async fn main() -> anyhow::Result<()> {
     // Load configuration first (before tracing, so we can use the database for logging)
     dotenvy::dotenv().ok();
     let cli = CliOptions::from_args();
+
+    // Also how the self-updater checks a downloaded binary runs
+    if cli.version {
+        println!("librarian {}", env!("CARGO_PKG_VERSION"));
+        return Ok(());
+    }
+
+    // Offline tooling: emit frontend types and exit before touching the database
+    if let Some(path) = cli.emit_ts.as_ref() {
+        let ts = graphql::typescript::sdl_to_typescript(&graphql::schema_sdl())?;
+        std::fs::write(path, ts)?;
+        eprintln!("Wrote GraphQL TypeScript types to {}", path.display());
+        return Ok(());
+    }
+
+    // Finish (or roll back) a self-update before the database is opened
+    let pending_update = services::self_update::resume_pending();
+
     let config = Config::from_env()?;
     let run_mode = cli.run_mode_override.unwrap_or(config.run_mode);
     let config = Arc::new(config);
@@ -147,6 +172,10 @@ async fn main() -> anyhow::Result<()> {
     tracing::info!("Configuration loaded");
     tracing::info!("Database connected");
 
+    if cli.seed_demo {
+        services::demo::seed_demo(&db).await?;
+    }
+
     // Initialize torrent service with database for persistence
     let torrent_config = TorrentServiceConfig {
         download_dir: PathBuf::from(&config.downloads_path),
@@ -158,6 +187,14 @@ async fn main() -> anyhow::Result<()> {
     let torrent_service = Arc::new(TorrentService::new(torrent_config, db.clone()).await?);
     tracing::info!("Torrent service initialized with database persistence");
 
+    // Apply download speed limits now; the scheduler keeps them current
+    if let Err(e) = services::bandwidth::BandwidthManager::global()
+        .apply(&db, &torrent_service)
+        .await
+    {
+        tracing::warn!(error = %e, "Failed to apply download bandwidth limits");
+    }
+
     // Initialize artwork service
     let artwork_service = {
         use crate::services::artwork::{ArtworkService, ensure_artwork_storage};
@@ -232,23 +269,28 @@ async fn main() -> anyhow::Result<()> {
         );
     }
 
-    // Create media file updated broadcast channel for real-time UI updates
-    let (media_file_tx, _) = tokio::sync::broadcast::channel::<MediaFileUpdatedEvent>(100);
+    // Event bus for real-time UI updates; services that publish hold senders
+    // taken from it, and GraphQL subscriptions subscribe through it
+    let events = Arc::new(services::EventBus::new());
+    events.register(log_broadcast_sender);
+    let media_file_tx = events.sender::<MediaFileUpdatedEvent>();
+
+    // Subtitle downloads from OpenSubtitles, one at a time
+    let subtitle_queue = Arc::new(create_subtitle_download_queue(db.clone()));
 
-    // Initialize media analysis queue for FFmpeg metadata extraction
+    // Initialize media analysis queue for FFmpeg metadata extraction; it
+    // queues subtitle downloads for languages a file's profile requires
     let analysis_queue = Arc::new(create_media_analysis_queue(
-        ffmpeg_service,
+        ffmpeg_service.clone(),
         db.clone(),
-        None, // subtitle_queue - TODO: wire up when subtitle download is implemented
-        Some(media_file_tx.clone()), // Clone so we can also use it in GraphQL schema
+        Some(subtitle_queue.clone()),
+        Some(media_file_tx),
     ));
     tracing::info!("Media analysis queue initialized");
 
-    // Create library changed broadcast channel for real-time scan status updates
-    let (library_changed_tx, _) = tokio::sync::broadcast::channel::<LibraryChangedEvent>(100);
-
-    // Create content download progress broadcast channel for real-time download progress on content pages
-    let (content_progress_tx, _) = tokio::sync::broadcast::channel::<graphql::ContentDownloadProgressEvent>(100);
+    // Library changes (scan status) and download progress on content pages
+    let library_changed_tx = events.sender::<LibraryChangedEvent>();
+    let content_progress_tx = events.sender::<graphql::ContentDownloadProgressEvent>();
 
     // Initialize IndexerManager early so we can pass it to ScannerService for auto-hunt
     let indexer_manager = match db.settings().get_or_create_indexer_encryption_key().await {
@@ -309,6 +351,14 @@ async fn main() -> anyhow::Result<()> {
     }
     tracing::info!("Cast service initialized");
 
+    // Initialize usenet service, shared by the jobs and APIs that add NZBs
+    let usenet_config = services::usenet::UsenetServiceConfig {
+        downloads_path: PathBuf::from(&config.downloads_path),
+        ..Default::default()
+    };
+    let usenet_service = Arc::new(UsenetService::new(db.clone(), usenet_config));
+    tracing::info!("Usenet service initialized");
+
     // Initialize filesystem service for file operations
     let filesystem_config = FilesystemServiceConfig {
         allow_unrestricted: false, // Only allow operations within library paths
@@ -322,23 +372,59 @@ async fn main() -> anyhow::Result<()> {
     let auth_service = Arc::new(AuthService::new(db.clone(), auth_config));
     tracing::info!("Auth service initialized");
 
+    // Initialize recommendation service (per-user daily cache of watch-next suggestions)
+    let recommendation_service = Arc::new(services::RecommendationService::new(
+        db.clone(),
+        metadata_service.clone(),
+    ));
+
+    // Transcode cache (size-bounded; sessions being played are pinned)
+    let transcode_cache = Arc::new(services::transcode_cache::TranscodeCache::new(
+        &config.cache_path,
+    ));
+
+    // HLS transcoding for clients that can't play a file directly
+    let transcode_service = Arc::new(services::TranscodeService::new(
+        ffmpeg_service,
+        transcode_cache.clone(),
+    ));
+
+    // Background jobs for long-running mutations; jobs the last run left
+    // unfinished can't resume, so record them as interrupted
+    let background_jobs = Arc::new(services::BackgroundJobService::new(db.clone()));
+    if let Err(e) = background_jobs.recover().await {
+        tracing::warn!(error = %e, "Failed to recover background jobs");
+    }
+
+    // Audit log of changes made through mutations
+    let audit_service = Arc::new(services::AuditService::new(db.clone()));
+
     // Build GraphQL schema
     let schema = graphql::build_schema(
         torrent_service.clone(),
+        usenet_service.clone(),
         metadata_service.clone(),
         scanner_service.clone(),
         cast_service.clone(),
         filesystem_service.clone(),
         notification_service.clone(),
         auth_service.clone(),
+        recommendation_service,
+        transcode_cache,
+        background_jobs,
+        audit_service,
         db.clone(),
         analysis_queue.clone(),
-        Some(log_broadcast_sender),
-        Some(library_changed_tx),
-        Some(media_file_tx),
-        Some(content_progress_tx.clone()),
+        subtitle_queue,
+        events,
+        config.graphql_introspection,
+    );
+    let schema_version = HeaderValue::from_str(&graphql::schema_version())?;
+    tracing::info!(
+        schema_version = ?schema_version,
+        introspection = config.graphql_introspection,
+        "GraphQL schema built"
     );
-    tracing::info!("GraphQL schema built");
 
     // Start job scheduler
     let _scheduler = jobs::start_scheduler(
@@ -348,6 +434,9 @@ async fn main() -> anyhow::Result<()> {
         Some(analysis_queue.clone()),
         Some(metadata_service.clone()),
         indexer_manager,
+        notification_service.clone(),
+        transcode_service.clone(),
+        usenet_service.clone(),
     )
     .await?;
     tracing::info!("Job scheduler started");
@@ -450,6 +539,34 @@ async fn main() -> anyhow::Result<()> {
         });
     }
 
+    // Startup self-check: log environment problems (unwritable paths, missing
+    // ffmpeg, low disk space, ...). The same checks back the diagnostics query.
+    {
+        let diagnostics = services::DiagnosticsService::new(
+            db.clone(),
+            torrent_service.clone(),
+            std::path::PathBuf::from(&config.cache_path),
+        );
+        tokio::spawn(async move {
+            // Let startup finish first; the port and clock checks are slow
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            diagnostics.log_problems().await;
+        });
+    }
+
+    // Check for a newer release once startup has settled; the scheduler
+    // repeats this every six hours
+    let update_db = db.clone();
+    let update_notifications = notification_service.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+        if let Err(e) =
+            jobs::update_check::run_scheduled_check(update_db, &update_notifications).await
+        {
+            tracing::warn!("Initial update check failed (will retry on schedule): {}", e);
+        }
+    });
+
     // Trigger initial schedule sync in the background
     // This ensures the schedule cache is populated on first startup
     let startup_pool = db.pool().clone();
@@ -514,15 +631,21 @@ async fn main() -> anyhow::Result<()> {
         config: config.clone(),
         db: db.clone(),
         schema,
+        schema_version,
         torrent_service,
         scanner_service,
         cast_service,
         filesystem_service,
         analysis_queue,
+        usenet_service,
+        qbittorrent_sessions: Arc::new(Default::default()),
     };
 
     // Build media state for streaming routes
-    let media_state = MediaState { db };
+    let media_state = MediaState {
+        db,
+        transcode: transcode_service,
+    };
 
     // Build router - GraphQL is the primary API
     let app = Router::new()
@@ -530,9 +653,14 @@ async fn main() -> anyhow::Result<()> {
         .merge(api::health::router())
         // REST API endpoints
         .nest("/api", api::torrents::router())
+        .nest("/api", api::account::router())
         .nest("/api", api::filesystem::router())
         // Torznab API for external apps (Sonarr, Radarr, etc.)
         .nest("/api", api::torznab::router())
+        // SABnzbd API so those apps can use the usenet downloader
+        .nest("/api", api::sabnzbd::router())
+        // qBittorrent API so they can use the torrent client too
+        .nest("/api", api::qbittorrent::router())
         // Artwork serving endpoint (SQLite mode - images stored as BLOBs)
         .nest("/api", api::artwork::router())
         // Media streaming endpoints for cast devices and browser playback
@@ -545,7 +673,11 @@ async fn main() -> anyhow::Result<()> {
             CorsLayer::new()
                 .allow_origin(Any)
                 .allow_methods(Any)
-                .allow_headers(Any),
+                .allow_headers(Any)
+                .expose_headers([
+                    HeaderName::from_static(SCHEMA_VERSION_HEADER),
+                    HeaderName::from_static(SERVER_VERSION_HEADER),
+                ]),
         )
         .layer(TraceLayer::new_for_http())
         .with_state(state);
@@ -562,12 +694,19 @@ async fn main() -> anyhow::Result<()> {
     // Start server
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("Listening on {}", addr);
-    tracing::info!(
-        "GraphQL playground: http://localhost:{}/graphql",
-        config.port
-    );
+    if config.graphql_introspection {
+        tracing::info!(
+            "GraphQL playground: http://localhost:{}/graphql",
+            config.port
+        );
+    }
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
+    let listener = bind_listener(addr, pending_update.is_some()).await?;
+
+    // The first start after a self-update keeps it only once it reports ready
+    if let Some(pending) = pending_update {
+        tokio::spawn(services::self_update::verify_health(pending, config.port));
+    }
 
     // Run with TUI or headless based on detection
     if let Some(tui_rx) = tui_log_rx {
@@ -599,6 +738,26 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Bind the server port
+///
+/// After a self-update the previous process may still be shutting down, so
+/// the port gets a few seconds to free up.
+async fn bind_listener(
+    addr: SocketAddr,
+    after_update: bool,
+) -> std::io::Result<tokio::net::TcpListener> {
+    let mut attempts_left = if after_update { 20 } else { 0 };
+    loop {
+        match tokio::net::TcpListener::bind(addr).await {
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse && attempts_left > 0 => {
+                attempts_left -= 1;
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
 fn install_rustls_crypto_provider() {
     // Avoid rustls panics when multiple providers are available in dependency graph.
     if rustls::crypto::ring::default_provider()
@@ -672,6 +831,12 @@ async fn initialize_jwt_secret(db: &Database) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Response header carrying the GraphQL schema hash
+const SCHEMA_VERSION_HEADER: &str = "x-schema-version";
+
+/// Response header carrying the server version
+const SERVER_VERSION_HEADER: &str = "x-server-version";
+
 /// Extract bearer token from Authorization header
 fn extract_token(headers: &HeaderMap) -> Option<String> {
     headers
@@ -686,7 +851,7 @@ async fn graphql_handler(
     axum::extract::State(state): axum::extract::State<AppState>,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> GraphQLResponse {
+) -> axum::response::Response {
     // Extract and verify auth token if present
     let mut request = req.into_inner();
 
@@ -705,11 +870,34 @@ async fn graphql_handler(
         tracing::debug!("No auth token in request headers");
     }
 
-    state.schema.execute(request).await.into()
+    // Version headers let clients notice a server upgrade and prompt a refresh
+    let response = graphql::cancellation::execute_with_deadline(
+        &state.schema,
+        request,
+        &state.config.graphql_timeouts,
+    )
+    .await;
+    let mut response = GraphQLResponse::from(response).into_response();
+    let response_headers = response.headers_mut();
+    response_headers.insert(SCHEMA_VERSION_HEADER, state.schema_version.clone());
+    response_headers.insert(
+        SERVER_VERSION_HEADER,
+        HeaderValue::from_static(env!("CARGO_PKG_VERSION")),
+    );
+    response
 }
 
 /// GraphiQL interactive playground (only for browsers)
-async fn graphiql(headers: HeaderMap) -> impl IntoResponse {
+async fn graphiql(
+    axum::extract::State(state): axum::extract::State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    // The playground is useless without introspection, and hiding it keeps
+    // publicly exposed servers from advertising the API
+    if !state.config.graphql_introspection {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+
     // Check if this is a browser request (accepts HTML)
     let accepts_html = headers
         .get(axum::http::header::ACCEPT)
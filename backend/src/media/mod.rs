@@ -1,6 +0,0 @@
-//! Media handling - transcoding, streaming
-//!
-//! Note: The primary organizer is in `services/organizer.rs`.
-//! The primary metadata service is in `services/metadata.rs`.
-
-pub mod transcoder;
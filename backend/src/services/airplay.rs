@@ -0,0 +1,214 @@
+//! AirPlay sender
+//!
+//! Talks to Apple TVs over the AirPlay HTTP video protocol. The receiver pulls
+//! the stream URL itself (progressive file or HLS playlist), and playback is
+//! controlled with `/rate`, `/scrub` and `/stop`.
+//!
+//! Receivers must allow AirPlay from everyone on the same network without a
+//! password; HomeKit pairing is not supported. The receiver stops playback as
+//! soon as the connection that started it closes, so a client is kept per
+//! device for the lifetime of the session and its connection is never idled out.
+//!
+//! HomePods only accept RAOP (ALAC audio pushed by the sender), which is not
+//! implemented, so they are discovered but cannot be cast to yet.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tracing::debug;
+use uuid::Uuid;
+
+/// Service name for AirPlay mDNS discovery
+pub const AIRPLAY_SERVICE_TYPE: &str = "_airplay._tcp.local.";
+
+/// Default AirPlay port
+pub const DEFAULT_AIRPLAY_PORT: u16 = 7000;
+
+const USER_AGENT: &str = "MediaControl/1.0";
+
+/// Playback position reported by the receiver
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AirPlayPosition {
+    pub duration: f64,
+    pub position: f64,
+}
+
+/// Parse the body of `GET /scrub` ("duration: 5400.0\nposition: 12.5")
+///
+/// Returns None until the receiver has loaded the media (duration 0).
+pub fn parse_scrub_response(body: &str) -> Option<AirPlayPosition> {
+    let mut duration = None;
+    let mut position = None;
+    for line in body.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().parse::<f64>().ok();
+        match key.trim() {
+            "duration" => duration = value,
+            "position" => position = value,
+            _ => {}
+        }
+    }
+
+    match (duration, position) {
+        (Some(duration), Some(position)) if duration > 0.0 => {
+            Some(AirPlayPosition { duration, position })
+        }
+        _ => None,
+    }
+}
+
+/// Friendly name from an mDNS instance name ("Living Room._airplay._tcp.local.")
+pub fn instance_name(fullname: &str) -> &str {
+    fullname
+        .strip_suffix(AIRPLAY_SERVICE_TYPE)
+        .map(|name| name.trim_end_matches('.'))
+        .filter(|name| !name.is_empty())
+        .unwrap_or(fullname)
+}
+
+/// HTTP client for a single AirPlay receiver
+pub struct AirPlayClient {
+    http: reqwest::Client,
+    base_url: String,
+    session_id: String,
+}
+
+impl AirPlayClient {
+    pub fn new(address: &str, port: u16) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(10))
+            .pool_idle_timeout(None)
+            .pool_max_idle_per_host(1)
+            .build()
+            .context("Failed to build AirPlay HTTP client")?;
+
+        let host = if address.contains(':') {
+            format!("[{}]", address)
+        } else {
+            address.to_string()
+        };
+
+        Ok(Self {
+            http,
+            base_url: format!("http://{}:{}", host, port),
+            session_id: Uuid::new_v4().to_string(),
+        })
+    }
+
+    /// Start playing a URL, optionally from a fraction (0.0 - 1.0) of its duration
+    pub async fn play(&self, url: &str, start_fraction: f64) -> Result<()> {
+        let body = format!(
+            "Content-Location: {}\nStart-Position: {:.6}\n",
+            url,
+            start_fraction.clamp(0.0, 1.0)
+        );
+        self.post("/play", Some(body)).await
+    }
+
+    /// Set the playback rate (0.0 = paused, 1.0 = playing)
+    pub async fn set_rate(&self, rate: f32) -> Result<()> {
+        self.post(&format!("/rate?value={:.6}", rate), None).await
+    }
+
+    /// Seek to an absolute position in seconds
+    pub async fn scrub(&self, position: f64) -> Result<()> {
+        self.post(&format!("/scrub?position={:.6}", position.max(0.0)), None)
+            .await
+    }
+
+    /// Set the receiver volume (0.0 - 1.0)
+    pub async fn set_volume(&self, volume: f32) -> Result<()> {
+        self.post(
+            &format!("/volume?volume={:.6}", volume.clamp(0.0, 1.0)),
+            None,
+        )
+        .await
+    }
+
+    /// Stop playback
+    pub async fn stop(&self) -> Result<()> {
+        self.post("/stop", None).await
+    }
+
+    /// Current position and duration (None while the media is still loading)
+    pub async fn position(&self) -> Result<Option<AirPlayPosition>> {
+        let response = self
+            .http
+            .get(format!("{}/scrub", self.base_url))
+            .header("X-Apple-Session-ID", &self.session_id)
+            .send()
+            .await
+            .context("Failed to reach AirPlay receiver")?
+            .error_for_status()
+            .context("AirPlay receiver rejected the request")?;
+
+        Ok(parse_scrub_response(&response.text().await?))
+    }
+
+    async fn post(&self, path: &str, body: Option<String>) -> Result<()> {
+        debug!(base_url = %self.base_url, path = %path, "AirPlay request");
+        let mut request = self
+            .http
+            .post(format!("{}{}", self.base_url, path))
+            .header("X-Apple-Session-ID", &self.session_id);
+        if let Some(body) = body {
+            request = request
+                .header(reqwest::header::CONTENT_TYPE, "text/parameters")
+                .body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to reach AirPlay receiver")?;
+        if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            || response.status() == reqwest::StatusCode::FORBIDDEN
+        {
+            anyhow::bail!(
+                "AirPlay receiver requires a password or pairing; allow AirPlay access for everyone on the network"
+            );
+        }
+        response
+            .error_for_status()
+            .context("AirPlay receiver rejected the request")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scrub_response() {
+        let parsed = parse_scrub_response("duration: 5400.250000\nposition: 12.500000\n");
+        assert_eq!(
+            parsed,
+            Some(AirPlayPosition {
+                duration: 5400.25,
+                position: 12.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_scrub_response_while_loading() {
+        assert_eq!(
+            parse_scrub_response("duration: 0.000000\nposition: 0.000000"),
+            None
+        );
+        assert_eq!(parse_scrub_response(""), None);
+    }
+
+    #[test]
+    fn test_instance_name() {
+        assert_eq!(
+            instance_name("Living Room._airplay._tcp.local."),
+            "Living Room"
+        );
+        assert_eq!(instance_name("odd-name"), "odd-name");
+    }
+}
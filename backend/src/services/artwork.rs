@@ -2,11 +2,16 @@
 //!
 //! Stores artwork in SQLite BLOB storage for self-hosted deployment.
 
+use std::time::Duration;
+
 use anyhow::{Context, Result};
 use sha2::{Digest, Sha256};
 use tracing::{debug, info, warn};
 
-use crate::db::Database;
+use crate::db::{ArtworkRecord, Database};
+
+/// How long a HEAD check waits for a provider to answer
+const HEAD_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Artwork service for managing poster/backdrop images
 pub struct ArtworkService {
@@ -23,6 +28,8 @@ pub enum ArtworkType {
     Backdrop,
     Thumbnail,
     Banner,
+    /// A user's uploaded profile picture
+    Avatar,
 }
 
 impl ArtworkType {
@@ -32,6 +39,7 @@ impl ArtworkType {
             ArtworkType::Backdrop => "backdrops",
             ArtworkType::Thumbnail => "thumbnails",
             ArtworkType::Banner => "banners",
+            ArtworkType::Avatar => "avatars",
         }
     }
 }
@@ -62,8 +70,6 @@ impl ArtworkService {
         entity_type: &str,
         entity_id: &str,
     ) -> Result<String> {
-        use crate::db::UpsertArtwork;
-
         info!(
             url = %source_url,
             artwork_type = ?artwork_type,
@@ -98,19 +104,56 @@ impl ArtworkService {
             .await
             .context("Failed to read image bytes")?;
 
+        self.store_image(
+            bytes.to_vec(),
+            content_type,
+            artwork_type,
+            entity_type,
+            entity_id,
+            Some(source_url),
+        )
+        .await
+    }
+
+    /// Store image bytes in the artwork cache, replacing any image the entity
+    /// already has of this type
+    ///
+    /// Returns the internal URL for serving the stored image
+    pub async fn store_image(
+        &self,
+        bytes: Vec<u8>,
+        content_type: String,
+        artwork_type: ArtworkType,
+        entity_type: &str,
+        entity_id: &str,
+        source_url: Option<&str>,
+    ) -> Result<String> {
+        use crate::db::UpsertArtwork;
+
         // Generate hash for deduplication
         let mut hasher = Sha256::new();
         hasher.update(&bytes);
         let hash = format!("{:x}", hasher.finalize());
 
-        // Try to detect image dimensions (optional)
-        let (width, height) = self.detect_image_dimensions(&bytes);
+        // Decode once for the blurhash placeholder; this also gives us dimensions
+        // for formats the header sniffing below can't handle (e.g. JPEG)
+        let decode_bytes = bytes.clone();
+        let placeholder = tokio::task::spawn_blocking(move || compute_placeholder(&decode_bytes))
+            .await
+            .unwrap_or(None);
+
+        let (width, height) = match &placeholder {
+            Some(p) => (Some(p.width as i32), Some(p.height as i32)),
+            None => self.detect_image_dimensions(&bytes),
+        };
+        let blurhash = placeholder.map(|p| p.blurhash);
 
         debug!(
             hash = %hash,
             size = bytes.len(),
             width = ?width,
             height = ?height,
+            blurhash = ?blurhash,
             "Storing artwork in database"
         );
 
@@ -121,25 +164,74 @@ impl ArtworkService {
             artwork_type: artwork_type.as_str().to_string(),
             content_hash: hash,
             mime_type: content_type,
-            data: bytes.to_vec(),
-            source_url: Some(source_url.to_string()),
+            data: bytes,
+            source_url: source_url.map(str::to_string),
             width,
             height,
+            blurhash,
         }).await?;
 
         // Return internal URL
-        let internal_url = format!(
-            "{}/api/artwork/{}/{}/{}",
-            self.base_url,
-            entity_type,
-            entity_id,
-            artwork_type.as_str()
-        );
+        let internal_url = self.internal_url(entity_type, entity_id, artwork_type.as_str());
 
         info!(url = %internal_url, "Artwork cached successfully");
         Ok(internal_url)
     }
 
+    /// The URL cached artwork is served from
+    pub fn internal_url(&self, entity_type: &str, entity_id: &str, artwork_type: &str) -> String {
+        format!(
+            "{}/api/artwork/{}/{}/{}",
+            self.base_url, entity_type, entity_id, artwork_type
+        )
+    }
+
+    /// The cache entry behind an internal artwork URL, if there still is one
+    pub async fn cached_entry(&self, url: &str) -> Result<Option<ArtworkRecord>> {
+        let Some((entity_type, entity_id, artwork_type)) = parse_artwork_url(url) else {
+            return Ok(None);
+        };
+        self.db
+            .artwork()
+            .get(entity_type, entity_id, artwork_type)
+            .await
+    }
+
+    /// HEAD-check an image URL
+    ///
+    /// Some image hosts don't allow HEAD, so a 405 is retried as a GET (the
+    /// body is never read).
+    pub async fn is_reachable(&self, url: &str) -> bool {
+        let head = self
+            .http_client
+            .head(url)
+            .timeout(HEAD_CHECK_TIMEOUT)
+            .send()
+            .await;
+        let response = match head {
+            Ok(r) if r.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED => {
+                self.http_client
+                    .get(url)
+                    .timeout(HEAD_CHECK_TIMEOUT)
+                    .send()
+                    .await
+            }
+            other => other,
+        };
+
+        match response {
+            Ok(r) if r.status().is_success() => true,
+            Ok(r) => {
+                debug!(url = %url, status = %r.status(), "Artwork URL check failed");
+                false
+            }
+            Err(e) => {
+                debug!(url = %url, error = %e, "Artwork URL check failed");
+                false
+            }
+        }
+    }
+
     /// Try to detect image dimensions (basic implementation)
     fn detect_image_dimensions(&self, data: &[u8]) -> (Option<i32>, Option<i32>) {
         // PNG signature check
@@ -232,6 +324,65 @@ impl ArtworkService {
     }
 }
 
+/// Blurhash placeholder computed from an image
+#[derive(Debug, Clone)]
+pub struct ImagePlaceholder {
+    pub width: u32,
+    pub height: u32,
+    pub blurhash: String,
+}
+
+/// Longest edge the image is scaled to before encoding (blurhash only needs a few pixels)
+const BLURHASH_SAMPLE_SIZE: u32 = 64;
+
+/// Decode an image and compute its blurhash
+///
+/// Uses 4x3 components for landscape images and 3x4 for portrait (posters).
+/// Returns None if the image format is unsupported or the data is corrupt.
+/// This is CPU-bound; call it from a blocking task.
+pub fn compute_placeholder(data: &[u8]) -> Option<ImagePlaceholder> {
+    let image = image::load_from_memory(data).ok()?;
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let sample = image
+        .thumbnail(BLURHASH_SAMPLE_SIZE, BLURHASH_SAMPLE_SIZE)
+        .to_rgba8();
+    let (components_x, components_y) = if width >= height { (4, 3) } else { (3, 4) };
+    let blurhash = blurhash::encode(
+        components_x,
+        components_y,
+        sample.width(),
+        sample.height(),
+        sample.as_raw(),
+    )
+    .ok()?;
+
+    Some(ImagePlaceholder {
+        width,
+        height,
+        blurhash,
+    })
+}
+
+/// Extract the (entity_type, entity_id, artwork_type) key from an internal artwork URL
+///
+/// Internal URLs look like `{base}/api/artwork/{entity_type}/{entity_id}/{artwork_type}`.
+pub fn parse_artwork_url(url: &str) -> Option<(&str, &str, &str)> {
+    let (_, path) = url.split_once("/api/artwork/")?;
+    let path = path.split(['?', '#']).next()?;
+    let mut parts = path.split('/');
+    let entity_type = parts.next().filter(|s| !s.is_empty())?;
+    let entity_id = parts.next().filter(|s| !s.is_empty())?;
+    let artwork_type = parts.next().filter(|s| !s.is_empty())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((entity_type, entity_id, artwork_type))
+}
+
 /// Artwork storage statistics
 #[derive(Debug, Clone)]
 pub struct ArtworkStorageStats {
@@ -245,3 +396,47 @@ pub async fn ensure_artwork_storage(_db: &Database) -> Result<()> {
     info!("Artwork storage ready (SQLite)");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_png(width: u32, height: u32) -> Vec<u8> {
+        let image = image::RgbaImage::from_fn(width, height, |x, y| {
+            image::Rgba([(x * 255 / width) as u8, (y * 255 / height) as u8, 128, 255])
+        });
+        let mut out = std::io::Cursor::new(Vec::new());
+        image::DynamicImage::ImageRgba8(image)
+            .write_to(&mut out, image::ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_compute_placeholder_portrait() {
+        let placeholder = compute_placeholder(&encode_png(200, 300)).unwrap();
+        assert_eq!(placeholder.width, 200);
+        assert_eq!(placeholder.height, 300);
+        // 3x4 components: 1 size char + 1 max AC char + 4 DC chars + 2 chars per AC component
+        assert_eq!(placeholder.blurhash.len(), 6 + 2 * (3 * 4 - 1));
+    }
+
+    #[test]
+    fn test_compute_placeholder_rejects_garbage() {
+        assert!(compute_placeholder(b"not an image").is_none());
+    }
+
+    #[test]
+    fn test_parse_artwork_url() {
+        assert_eq!(
+            parse_artwork_url("http://localhost:3001/api/artwork/movie/abc-123/posters"),
+            Some(("movie", "abc-123", "posters"))
+        );
+        assert_eq!(
+            parse_artwork_url("/api/artwork/show/xyz/backdrops?v=2"),
+            Some(("show", "xyz", "backdrops"))
+        );
+        assert_eq!(parse_artwork_url("https://image.tmdb.org/t/p/w500/a.jpg"), None);
+        assert_eq!(parse_artwork_url("/api/artwork/show/xyz"), None);
+    }
+}
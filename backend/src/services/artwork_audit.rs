@@ -0,0 +1,527 @@
+//! Artwork audit: finding and fixing images that no longer show
+//!
+//! Artwork goes missing in a few ways: the cache entry behind an internal
+//! URL is deleted, caching failed when the item was added and the provider
+//! URL was stored instead (which the provider may since have removed), or
+//! the provider had no image yet. The audit HEAD-checks every image of the
+//! items in a library, downloads broken ones again, and asks the metadata
+//! provider for images the item doesn't have.
+//!
+//! Cached URLs are checked against the cache itself rather than over HTTP:
+//! the artwork endpoint answers from the same table, and the server can't
+//! always reach its own public URL.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::artwork::{ArtworkService, ArtworkType, parse_artwork_url};
+use super::background_jobs::JobContext;
+use super::metadata::{MetadataProvider, MetadataService};
+use crate::db::{AlbumRecord, Database, MovieRecord, TvShowRecord};
+
+/// Items between progress updates
+const PROGRESS_EVERY: usize = 10;
+
+/// Kind of item whose artwork is audited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtworkEntity {
+    Show,
+    Movie,
+    Album,
+}
+
+impl ArtworkEntity {
+    /// The entity type artwork is cached under
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Show => "show",
+            Self::Movie => "movie",
+            Self::Album => "album",
+        }
+    }
+}
+
+/// Where an artwork URL points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArtworkUrl<'a> {
+    /// No image set
+    None,
+    /// An image in the artwork cache
+    Cached,
+    /// A provider URL, stored when caching failed or was unavailable
+    External(&'a str),
+}
+
+fn classify_url(url: Option<&str>) -> ArtworkUrl<'_> {
+    match url.map(str::trim).filter(|u| !u.is_empty()) {
+        None => ArtworkUrl::None,
+        Some(u) if parse_artwork_url(u).is_some() => ArtworkUrl::Cached,
+        Some(u) => ArtworkUrl::External(u),
+    }
+}
+
+/// Counts from auditing artwork (stored as the audit job's result)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtworkAuditReport {
+    pub items_checked: i32,
+    /// Images that were cached and still are
+    pub images_ok: i32,
+    /// Broken or uncached images downloaded again from their source
+    pub images_recached: i32,
+    /// Images fetched from the metadata provider (missing or dead)
+    pub images_fetched: i32,
+    /// Images that couldn't be fixed
+    pub images_failed: i32,
+}
+
+impl ArtworkAuditReport {
+    fn add(&mut self, other: &ArtworkAuditReport) {
+        self.items_checked += other.items_checked;
+        self.images_ok += other.images_ok;
+        self.images_recached += other.images_recached;
+        self.images_fetched += other.images_fetched;
+        self.images_failed += other.images_failed;
+    }
+}
+
+/// An item's artwork after an audit or refresh
+#[derive(Debug, Clone)]
+pub struct ArtworkRefresh {
+    /// Poster (or album cover) URL
+    pub poster_url: Option<String>,
+    /// Backdrop URL (always None for albums)
+    pub backdrop_url: Option<String>,
+    pub report: ArtworkAuditReport,
+}
+
+/// One artwork slot of an item
+struct Slot {
+    artwork_type: ArtworkType,
+    url: Option<String>,
+}
+
+/// What to do with a slot after checking it
+enum SlotAction {
+    Keep,
+    /// Download this URL into the cache
+    Recache(String),
+    /// Ask the provider for the image, falling back to where it was cached
+    /// from (if known)
+    Fetch {
+        fallback: Option<String>,
+    },
+}
+
+/// Where an item's artwork comes from
+#[derive(Debug, Clone, Copy)]
+enum ArtworkSource {
+    Show(MetadataProvider, u32),
+    /// TMDB ID
+    Movie(u32),
+    /// MusicBrainz release group ID
+    Album(Uuid),
+}
+
+/// An item being audited
+struct AuditItem {
+    entity: ArtworkEntity,
+    id: Uuid,
+    user_id: Uuid,
+    /// None when the item has no provider ID
+    source: Option<ArtworkSource>,
+    /// Entity ID the artwork is cached under
+    cache_id: String,
+    slots: Vec<Slot>,
+}
+
+/// Audits and refreshes artwork for shows, movies and albums
+pub struct ArtworkAuditService {
+    db: Database,
+    metadata: Arc<MetadataService>,
+}
+
+impl ArtworkAuditService {
+    pub fn new(db: Database, metadata: Arc<MetadataService>) -> Self {
+        Self { db, metadata }
+    }
+
+    fn artwork(&self) -> Result<&Arc<ArtworkService>> {
+        self.metadata
+            .artwork_service()
+            .ok_or_else(|| anyhow::anyhow!("Artwork caching is not enabled"))
+    }
+
+    /// Audit the artwork of every show, movie and album in the given
+    /// libraries, reporting progress to the job
+    pub async fn audit_libraries(
+        &self,
+        library_ids: &[Uuid],
+        job: &JobContext,
+    ) -> Result<ArtworkAuditReport> {
+        self.artwork()?;
+
+        let mut items = Vec::new();
+        for &library_id in library_ids {
+            for show in self.db.tv_shows().list_by_library(library_id).await? {
+                items.push(show_item(&show));
+            }
+            for movie in self.db.movies().list_by_library(library_id).await? {
+                items.push(movie_item(&movie));
+            }
+            for album in self.db.albums().list_by_library(library_id).await? {
+                items.push(album_item(&album));
+            }
+        }
+
+        let total = items.len();
+        let mut report = ArtworkAuditReport::default();
+        job.progress(0.0, Some(&format!("Checking artwork for {} items", total)))
+            .await;
+
+        for (done, item) in items.into_iter().enumerate() {
+            match self.audit_item(item, false).await {
+                Ok(refresh) => report.add(&refresh.report),
+                Err(e) => {
+                    warn!(error = %e, "Artwork audit failed for an item");
+                    report.items_checked += 1;
+                    report.images_failed += 1;
+                }
+            }
+
+            let done = done + 1;
+            if done % PROGRESS_EVERY == 0 || done == total {
+                let message = format!("Checked artwork for {} of {} items", done, total);
+                job.progress(done as f64 / total as f64, Some(&message))
+                    .await;
+            }
+        }
+
+        info!(
+            items = report.items_checked,
+            recached = report.images_recached,
+            fetched = report.images_fetched,
+            failed = report.images_failed,
+            "Artwork audit complete"
+        );
+        Ok(report)
+    }
+
+    /// Fetch an item's artwork from its provider and cache it again
+    ///
+    /// Images the provider no longer has are re-downloaded from where they
+    /// were cached from. Returns None if the user has no such item.
+    pub async fn refresh(
+        &self,
+        entity: ArtworkEntity,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<ArtworkRefresh>> {
+        self.artwork()?;
+
+        let item = match entity {
+            ArtworkEntity::Show => self
+                .db
+                .tv_shows()
+                .get_by_id(id)
+                .await?
+                .map(|s| show_item(&s)),
+            ArtworkEntity::Movie => self
+                .db
+                .movies()
+                .get_by_id(id)
+                .await?
+                .map(|m| movie_item(&m)),
+            ArtworkEntity::Album => self
+                .db
+                .albums()
+                .get_by_id(id)
+                .await?
+                .map(|a| album_item(&a)),
+        };
+        match item.filter(|item| item.user_id == user_id) {
+            Some(item) => Ok(Some(self.audit_item(item, true).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Check and fix an item's artwork; `force` fetches every image from
+    /// the provider whether or not it is broken
+    async fn audit_item(&self, item: AuditItem, force: bool) -> Result<ArtworkRefresh> {
+        let artwork = self.artwork()?;
+        let entity_type = item.entity.as_str();
+        let mut report = ArtworkAuditReport {
+            items_checked: 1,
+            ..Default::default()
+        };
+
+        // Whether each slot's image works, and what to do about it
+        let mut checks = Vec::with_capacity(item.slots.len());
+        for slot in &item.slots {
+            let check = match classify_url(slot.url.as_deref()) {
+                ArtworkUrl::None => (false, SlotAction::Fetch { fallback: None }),
+                ArtworkUrl::Cached => {
+                    let entry = artwork
+                        .cached_entry(slot.url.as_deref().unwrap_or_default())
+                        .await?;
+                    let working = entry.as_ref().is_some_and(|e| e.size_bytes > 0);
+                    if working && !force {
+                        report.images_ok += 1;
+                        (true, SlotAction::Keep)
+                    } else {
+                        let fallback = entry.and_then(|e| e.source_url);
+                        (working, SlotAction::Fetch { fallback })
+                    }
+                }
+                ArtworkUrl::External(url) if force => (
+                    false,
+                    SlotAction::Fetch {
+                        fallback: Some(url.to_string()),
+                    },
+                ),
+                ArtworkUrl::External(url) => {
+                    if artwork.is_reachable(url).await {
+                        (false, SlotAction::Recache(url.to_string()))
+                    } else {
+                        (false, SlotAction::Fetch { fallback: None })
+                    }
+                }
+            };
+            checks.push(check);
+        }
+
+        let needs_provider = checks
+            .iter()
+            .any(|(_, action)| matches!(action, SlotAction::Fetch { .. }));
+        let provider_urls = if needs_provider {
+            self.provider_urls(&item).await.unwrap_or_else(|e| {
+                warn!(
+                    entity = entity_type,
+                    id = %item.id,
+                    error = %e,
+                    "Failed to fetch artwork from provider"
+                );
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        let mut urls: Vec<Option<String>> = item.slots.iter().map(|s| s.url.clone()).collect();
+        for (i, (slot, (working, action))) in item.slots.iter().zip(checks).enumerate() {
+            let (source, from_provider) = match action {
+                SlotAction::Keep => continue,
+                SlotAction::Recache(url) => (url, false),
+                SlotAction::Fetch { fallback } => {
+                    match (provider_urls.get(i).cloned().flatten(), fallback) {
+                        (Some(url), _) => (url, true),
+                        (None, Some(url)) => (url, false),
+                        (None, None) => {
+                            // No image anywhere is only a failure if there was one
+                            if slot.url.is_some() && !working {
+                                report.images_failed += 1;
+                            }
+                            debug!(entity = entity_type, id = %item.id, "No artwork source");
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            match artwork
+                .cache_image(&source, slot.artwork_type, entity_type, &item.cache_id)
+                .await
+            {
+                Ok(cached_url) => {
+                    if from_provider {
+                        report.images_fetched += 1;
+                    } else {
+                        report.images_recached += 1;
+                    }
+                    urls[i] = Some(cached_url);
+                }
+                Err(e) => {
+                    warn!(url = %source, error = %e, "Failed to cache artwork");
+                    report.images_failed += 1;
+                    // A provider URL beats a broken or missing image
+                    if !working {
+                        urls[i] = Some(source);
+                    }
+                }
+            }
+        }
+
+        let changed = urls
+            .iter()
+            .zip(&item.slots)
+            .any(|(url, slot)| *url != slot.url);
+        if changed {
+            self.save_urls(&item, &urls).await?;
+        }
+
+        let mut urls = urls.into_iter();
+        Ok(ArtworkRefresh {
+            poster_url: urls.next().flatten(),
+            backdrop_url: urls.next().flatten(),
+            report,
+        })
+    }
+
+    /// Image URLs from the item's metadata provider, in slot order
+    async fn provider_urls(&self, item: &AuditItem) -> Result<Vec<Option<String>>> {
+        match item.source {
+            Some(ArtworkSource::Show(provider, provider_id)) => {
+                let (details, _) = self
+                    .metadata
+                    .get_show_chained(provider, provider_id)
+                    .await?;
+                Ok(vec![details.poster_url, details.backdrop_url])
+            }
+            Some(ArtworkSource::Movie(tmdb_id)) => {
+                let details = self.metadata.get_movie(tmdb_id).await?;
+                Ok(vec![details.poster_url, details.backdrop_url])
+            }
+            Some(ArtworkSource::Album(musicbrainz_id)) => {
+                Ok(vec![self.metadata.get_album_cover(musicbrainz_id).await?])
+            }
+            None => anyhow::bail!("{} has no provider ID", item.entity.as_str()),
+        }
+    }
+
+    async fn save_urls(&self, item: &AuditItem, urls: &[Option<String>]) -> Result<()> {
+        let url = |i: usize| urls.get(i).and_then(|u| u.as_deref());
+        match item.entity {
+            ArtworkEntity::Show => {
+                self.db
+                    .tv_shows()
+                    .set_artwork_urls(item.id, url(0), url(1))
+                    .await
+            }
+            ArtworkEntity::Movie => {
+                self.db
+                    .movies()
+                    .set_artwork_urls(item.id, url(0), url(1))
+                    .await
+            }
+            ArtworkEntity::Album => self.db.albums().set_cover_url(item.id, url(0)).await,
+        }
+    }
+}
+
+/// The provider a show's metadata comes from, as in `refreshTvShow`
+fn show_provider(show: &TvShowRecord) -> Option<(MetadataProvider, u32)> {
+    if let Some(id) = show.tvmaze_id {
+        Some((MetadataProvider::TvMaze, id as u32))
+    } else if let Some(id) = show.tmdb_id {
+        Some((MetadataProvider::Tmdb, id as u32))
+    } else {
+        show.tvdb_id.map(|id| (MetadataProvider::TvDb, id as u32))
+    }
+}
+
+fn show_item(show: &TvShowRecord) -> AuditItem {
+    let provider = show_provider(show);
+    AuditItem {
+        entity: ArtworkEntity::Show,
+        id: show.id,
+        user_id: show.user_id,
+        source: provider.map(|(provider, id)| ArtworkSource::Show(provider, id)),
+        // Shows cache artwork under `{provider_id}_{library_id}`
+        cache_id: match provider {
+            Some((_, provider_id)) => format!("{}_{}", provider_id, show.library_id),
+            None => show.id.to_string(),
+        },
+        slots: vec![
+            Slot {
+                artwork_type: ArtworkType::Poster,
+                url: show.poster_url.clone(),
+            },
+            Slot {
+                artwork_type: ArtworkType::Backdrop,
+                url: show.backdrop_url.clone(),
+            },
+        ],
+    }
+}
+
+fn movie_item(movie: &MovieRecord) -> AuditItem {
+    AuditItem {
+        entity: ArtworkEntity::Movie,
+        id: movie.id,
+        user_id: movie.user_id,
+        source: movie.tmdb_id.map(|id| ArtworkSource::Movie(id as u32)),
+        cache_id: match movie.tmdb_id {
+            Some(tmdb_id) => format!("{}_{}", tmdb_id, movie.library_id),
+            None => movie.id.to_string(),
+        },
+        slots: vec![
+            Slot {
+                artwork_type: ArtworkType::Poster,
+                url: movie.poster_url.clone(),
+            },
+            Slot {
+                artwork_type: ArtworkType::Backdrop,
+                url: movie.backdrop_url.clone(),
+            },
+        ],
+    }
+}
+
+fn album_item(album: &AlbumRecord) -> AuditItem {
+    AuditItem {
+        entity: ArtworkEntity::Album,
+        id: album.id,
+        user_id: album.user_id,
+        source: album.musicbrainz_id.map(ArtworkSource::Album),
+        cache_id: match album.musicbrainz_id {
+            Some(musicbrainz_id) => format!("album_{}", musicbrainz_id),
+            None => album.id.to_string(),
+        },
+        slots: vec![Slot {
+            artwork_type: ArtworkType::Poster,
+            url: album.cover_url.clone(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_url() {
+        assert_eq!(classify_url(None), ArtworkUrl::None);
+        assert_eq!(classify_url(Some("  ")), ArtworkUrl::None);
+        assert_eq!(
+            classify_url(Some(
+                "http://localhost:3001/api/artwork/show/169_abc/posters"
+            )),
+            ArtworkUrl::Cached
+        );
+        assert_eq!(
+            classify_url(Some("https://static.tvmaze.com/poster.jpg")),
+            ArtworkUrl::External("https://static.tvmaze.com/poster.jpg")
+        );
+    }
+
+    #[test]
+    fn test_report_add() {
+        let mut total = ArtworkAuditReport::default();
+        let item = ArtworkAuditReport {
+            items_checked: 1,
+            images_ok: 1,
+            images_fetched: 1,
+            ..Default::default()
+        };
+        total.add(&item);
+        total.add(&item);
+        assert_eq!(total.items_checked, 2);
+        assert_eq!(total.images_ok, 2);
+        assert_eq!(total.images_fetched, 2);
+        assert_eq!(total.images_failed, 0);
+    }
+}
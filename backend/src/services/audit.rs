@@ -0,0 +1,240 @@
+//! Audit log of changes made through mutations
+//!
+//! Create, update and delete mutations hand [`AuditService`] a snapshot of the
+//! entity (any `Serialize` type, usually its database record) and it stores
+//! who changed which fields. Updates store only the fields that differ, so
+//! an entity's history reads as a list of diffs.
+
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::db::{CreateAuditLogEntry, Database};
+
+/// Fields that change on every write and say nothing about what was changed
+const IGNORED_FIELDS: &[&str] = &["created_at", "updated_at"];
+
+/// Fields whose values are never stored, only that they changed
+///
+/// Feed and indexer URLs often embed a passkey or API key.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "password_hash",
+    "api_key",
+    "passkey",
+    "token",
+    "secret",
+    "credentials",
+    "url",
+];
+
+const REDACTED: &str = "[redacted]";
+
+/// What a mutation did to an entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+}
+
+/// Records create, update and delete mutations in the audit log
+pub struct AuditService {
+    db: Database,
+}
+
+impl AuditService {
+    pub fn new(db: Database) -> Self {
+        Self { db }
+    }
+
+    /// Record a created entity (every field, as "to")
+    pub async fn record_create<T: Serialize>(
+        &self,
+        user_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        entity: &T,
+    ) {
+        let changes = field_changes(None, snapshot(entity).as_ref());
+        self.record(
+            user_id,
+            entity_type,
+            entity_id,
+            AuditAction::Create,
+            changes,
+        )
+        .await;
+    }
+
+    /// Record an updated entity (changed fields only; nothing if none changed)
+    pub async fn record_update<T: Serialize>(
+        &self,
+        user_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        before: &T,
+        after: &T,
+    ) {
+        let changes = field_changes(snapshot(before).as_ref(), snapshot(after).as_ref());
+        if changes.is_empty() {
+            return;
+        }
+        self.record(
+            user_id,
+            entity_type,
+            entity_id,
+            AuditAction::Update,
+            changes,
+        )
+        .await;
+    }
+
+    /// Record a deleted entity (every field, as "from")
+    pub async fn record_delete<T: Serialize>(
+        &self,
+        user_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        entity: &T,
+    ) {
+        let changes = field_changes(snapshot(entity).as_ref(), None);
+        self.record(
+            user_id,
+            entity_type,
+            entity_id,
+            AuditAction::Delete,
+            changes,
+        )
+        .await;
+    }
+
+    /// Failing to audit is logged rather than failing the mutation, which has
+    /// already been applied
+    async fn record(
+        &self,
+        user_id: Uuid,
+        entity_type: &str,
+        entity_id: Uuid,
+        action: AuditAction,
+        changes: Map<String, Value>,
+    ) {
+        let entry = CreateAuditLogEntry {
+            user_id: Some(user_id),
+            entity_type: entity_type.to_string(),
+            entity_id,
+            action: action.as_str().to_string(),
+            changes: Value::Object(changes),
+        };
+        if let Err(e) = self.db.audit_log().create(entry).await {
+            warn!(
+                entity_type = %entity_type,
+                entity_id = %entity_id,
+                action = action.as_str(),
+                error = %e,
+                "Failed to record audit log entry"
+            );
+        }
+    }
+}
+
+fn snapshot<T: Serialize>(entity: &T) -> Option<Value> {
+    serde_json::to_value(entity).ok()
+}
+
+/// Per-field `{ "from", "to" }` between two snapshots of an entity
+///
+/// A missing `before` is a create (only "to"), a missing `after` a delete
+/// (only "from"). Fields equal on both sides are left out, as are
+/// [`IGNORED_FIELDS`]; [`SENSITIVE_FIELDS`] are recorded as changed with
+/// their values redacted.
+pub fn field_changes(before: Option<&Value>, after: Option<&Value>) -> Map<String, Value> {
+    let empty = Map::new();
+    let before = before.and_then(Value::as_object);
+    let after = after.and_then(Value::as_object);
+
+    let mut fields: Vec<&String> = before
+        .unwrap_or(&empty)
+        .keys()
+        .chain(after.unwrap_or(&empty).keys())
+        .filter(|field| !IGNORED_FIELDS.contains(&field.as_str()))
+        .collect();
+    fields.sort();
+    fields.dedup();
+
+    let mut changes = Map::new();
+    for field in fields {
+        let from = before.and_then(|b| b.get(field));
+        let to = after.and_then(|a| a.get(field));
+        if before.is_some() && after.is_some() && from == to {
+            continue;
+        }
+
+        let sensitive = SENSITIVE_FIELDS.contains(&field.as_str());
+        let show = |value: Option<&Value>| match value {
+            Some(Value::Null) | None => Value::Null,
+            Some(_) if sensitive => json!(REDACTED),
+            Some(value) => value.clone(),
+        };
+        let mut change = Map::new();
+        if before.is_some() {
+            change.insert("from".to_string(), show(from));
+        }
+        if after.is_some() {
+            change.insert("to".to_string(), show(to));
+        }
+        changes.insert(field.clone(), Value::Object(change));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_changed_fields_only() {
+        let before = json!({ "name": "Movies", "auto_scan": true, "updated_at": "a" });
+        let after = json!({ "name": "Films", "auto_scan": true, "updated_at": "b" });
+        let changes = field_changes(Some(&before), Some(&after));
+        assert_eq!(
+            Value::Object(changes),
+            json!({ "name": { "from": "Movies", "to": "Films" } })
+        );
+    }
+
+    #[test]
+    fn test_create_and_delete() {
+        let entity = json!({ "name": "Feed", "created_at": "a" });
+        assert_eq!(
+            Value::Object(field_changes(None, Some(&entity))),
+            json!({ "name": { "to": "Feed" } })
+        );
+        assert_eq!(
+            Value::Object(field_changes(Some(&entity), None)),
+            json!({ "name": { "from": "Feed" } })
+        );
+    }
+
+    #[test]
+    fn test_sensitive_fields_are_redacted() {
+        let before = json!({ "url": "https://t.example/rss?passkey=old" });
+        let after = json!({ "url": "https://t.example/rss?passkey=new" });
+        assert_eq!(
+            Value::Object(field_changes(Some(&before), Some(&after))),
+            json!({ "url": { "from": "[redacted]", "to": "[redacted]" } })
+        );
+        assert!(field_changes(Some(&before), Some(&before)).is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 //! - Password hashing with bcrypt
 //! - JWT token generation and validation
 //! - Refresh token management
+//! - Password strength rules, password reset and email change confirmation
 //! - Library access control
 
 use anyhow::{anyhow, Result};
@@ -20,6 +21,84 @@ use crate::db::{
 };
 use crate::db::sqlite_helpers::now_iso8601;
 
+/// How long an email change confirmation link stays valid
+const EMAIL_CHANGE_TOKEN_HOURS: i64 = 24;
+
+/// How long a password reset link stays valid
+const PASSWORD_RESET_TOKEN_MINUTES: i64 = 60;
+
+/// Minimum time between password reset mails to one account
+const PASSWORD_RESET_COOLDOWN_MINUTES: i64 = 5;
+
+// ============================================================================
+// Validation
+// ============================================================================
+
+/// Whether a reset requested at `last_requested_at` (RFC 3339) is too recent
+/// for another
+fn password_reset_on_cooldown(last_requested_at: &str, now: chrono::DateTime<Utc>) -> bool {
+    chrono::DateTime::parse_from_rfc3339(last_requested_at)
+        .is_ok_and(|last| now < last + Duration::minutes(PASSWORD_RESET_COOLDOWN_MINUTES))
+}
+
+/// Check a new password against the strength rules
+///
+/// At least 8 characters, and at most 72 bytes since bcrypt ignores anything
+/// past that. It can't contain the account's username or the local part of
+/// its email, and needs three of lowercase, uppercase, digits and symbols
+/// unless it's a passphrase of 16 characters or more.
+pub fn validate_password_strength(password: &str, personal: &[&str]) -> Result<()> {
+    let length = password.chars().count();
+    if length < 8 {
+        return Err(anyhow!("Password must be at least 8 characters"));
+    }
+    if password.len() > 72 {
+        return Err(anyhow!("Password must be at most 72 bytes"));
+    }
+
+    let lowered = password.to_lowercase();
+    for value in personal {
+        let local = value.split('@').next().unwrap_or_default().to_lowercase();
+        if local.chars().count() >= 3 && lowered.contains(&local) {
+            return Err(anyhow!("Password can't contain your username or email"));
+        }
+    }
+
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ];
+    if length < 16 && classes.iter().filter(|&&present| present).count() < 3 {
+        return Err(anyhow!(
+            "Password needs three of: lowercase letters, uppercase letters, digits, symbols \
+             (or use 16 or more characters)"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Basic shape check for an email address; the confirmation mail is the real test
+pub fn validate_email(email: &str) -> Result<()> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(|c| c.is_whitespace())
+                && !domain.contains('@')
+        }
+        None => false,
+    };
+    if !valid || email.len() > 254 {
+        return Err(anyhow!("Invalid email address"));
+    }
+    Ok(())
+}
+
 // ============================================================================
 // JWT Claims
 // ============================================================================
@@ -194,6 +273,9 @@ impl AuthService {
             return Err(anyhow!("Email already registered"));
         }
 
+        validate_email(&input.email)?;
+        validate_password_strength(&input.password, &[&input.email])?;
+
         // Determine role - first user becomes admin
         let role = if users.has_admin().await? {
             "member".to_string()
@@ -240,6 +322,9 @@ impl AuthService {
             return Err(anyhow!("Email already registered"));
         }
 
+        validate_email(&input.email)?;
+        validate_password_strength(&input.password, &[&input.email])?;
+
         // Use email as username
         let username = input.email.clone();
 
@@ -394,6 +479,13 @@ impl AuthService {
             return Err(anyhow!("Current password is incorrect"));
         }
 
+        if new_password == current_password {
+            return Err(anyhow!(
+                "New password must be different from the current one"
+            ));
+        }
+        validate_password_strength(new_password, &Self::personal_values(&user))?;
+
         // Hash new password
         let new_hash = self.hash_password(new_password)?;
 
@@ -413,6 +505,10 @@ impl AuthService {
     pub async fn admin_reset_password(&self, user_id: &str, new_password: &str) -> Result<()> {
         let users = self.db.users();
 
+        let user = users.get_by_id(user_id).await?
+            .ok_or_else(|| anyhow!("User not found"))?;
+        validate_password_strength(new_password, &Self::personal_values(&user))?;
+
         let new_hash = self.hash_password(new_password)?;
 
         users.update(user_id, UpdateUser {
@@ -426,6 +522,155 @@ impl AuthService {
         Ok(())
     }
 
+    // ========================================================================
+    // Password Reset
+    // ========================================================================
+
+    /// Start a password reset for the account with this email
+    ///
+    /// Returns the raw token to mail to the user, or None when no active
+    /// account has the address or a reset was requested for it in the last
+    /// few minutes (callers shouldn't reveal which). The cooldown keeps the
+    /// form from being used to flood someone's inbox. Only the token's hash
+    /// is stored.
+    pub async fn request_password_reset(&self, email: &str) -> Result<Option<String>> {
+        let users = self.db.users();
+
+        let user = match users.get_by_email(email).await? {
+            Some(user) if user.is_active => user,
+            _ => return Ok(None),
+        };
+
+        if let Some(last) = users.last_password_reset_requested_at(&user.id).await?
+            && password_reset_on_cooldown(&last, Utc::now())
+        {
+            return Ok(None);
+        }
+
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let expires_at =
+            (Utc::now() + Duration::minutes(PASSWORD_RESET_TOKEN_MINUTES)).to_rfc3339();
+        users.create_password_reset_token(&user.id, &self.hash_token(&token), &expires_at).await?;
+
+        Ok(Some(token))
+    }
+
+    /// Drop a pending password reset, e.g. when the reset mail couldn't be sent
+    pub async fn cancel_password_reset(&self, token: &str) -> Result<()> {
+        self.db.users().take_password_reset_token(&self.hash_token(token)).await?;
+        Ok(())
+    }
+
+    /// Set a new password with the token from a reset mail
+    ///
+    /// Tokens are single-use, but a password that fails the strength rules
+    /// doesn't use the token up. All refresh tokens are invalidated. Returns
+    /// the user ID.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<String> {
+        let users = self.db.users();
+        let token_hash = self.hash_token(token);
+
+        let stored = users.get_password_reset_token(&token_hash).await?
+            .ok_or_else(|| anyhow!("Invalid or already used reset link"))?;
+
+        if stored.expires_at.as_str() < now_iso8601().as_str() {
+            return Err(anyhow!("Reset link has expired"));
+        }
+
+        let user = users.get_by_id(&stored.user_id).await?
+            .filter(|user| user.is_active)
+            .ok_or_else(|| anyhow!("Account is disabled"))?;
+
+        validate_password_strength(new_password, &Self::personal_values(&user))?;
+
+        // Claim the token; a concurrent reset with the same link loses here
+        if users.take_password_reset_token(&token_hash).await?.is_none() {
+            return Err(anyhow!("Invalid or already used reset link"));
+        }
+
+        let new_hash = self.hash_password(new_password)?;
+        users.update(&user.id, UpdateUser {
+            password_hash: Some(new_hash),
+            ..Default::default()
+        }).await?;
+
+        // Whoever had the old password shouldn't stay signed in
+        users.delete_user_refresh_tokens(&user.id).await?;
+
+        Ok(user.id)
+    }
+
+    // ========================================================================
+    // Email Change
+    // ========================================================================
+
+    /// Start changing a user's email
+    ///
+    /// Returns the raw confirmation token to mail to the new address; only its
+    /// hash is stored. Nothing changes until the token is confirmed.
+    pub async fn request_email_change(
+        &self,
+        user_id: &str,
+        current_password: &str,
+        new_email: &str,
+    ) -> Result<String> {
+        let users = self.db.users();
+
+        let user = users.get_by_id(user_id).await?
+            .ok_or_else(|| anyhow!("User not found"))?;
+
+        if !self.verify_password(current_password, &user.password_hash)? {
+            return Err(anyhow!("Current password is incorrect"));
+        }
+
+        validate_email(new_email)?;
+        if user.email.as_deref().is_some_and(|email| email.eq_ignore_ascii_case(new_email)) {
+            return Err(anyhow!("That is already your email address"));
+        }
+        if users.get_by_email(new_email).await?.is_some()
+            || users.get_by_username(new_email).await?.is_some()
+        {
+            return Err(anyhow!("Email already registered"));
+        }
+
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let expires_at = (Utc::now() + Duration::hours(EMAIL_CHANGE_TOKEN_HOURS)).to_rfc3339();
+        users
+            .create_email_change_token(user_id, new_email, &self.hash_token(&token), &expires_at)
+            .await?;
+
+        Ok(token)
+    }
+
+    /// Drop a pending email change, e.g. when the confirmation mail couldn't be sent
+    pub async fn cancel_email_change(&self, token: &str) -> Result<()> {
+        self.db.users().take_email_change_token(&self.hash_token(token)).await?;
+        Ok(())
+    }
+
+    /// Confirm an email change with the token from the confirmation mail
+    ///
+    /// Tokens are single-use. Returns the user ID.
+    pub async fn confirm_email_change(&self, token: &str) -> Result<String> {
+        let users = self.db.users();
+
+        let stored = users.take_email_change_token(&self.hash_token(token)).await?
+            .ok_or_else(|| anyhow!("Invalid or already used confirmation link"))?;
+
+        if stored.expires_at.as_str() < now_iso8601().as_str() {
+            return Err(anyhow!("Confirmation link has expired"));
+        }
+
+        // The address may have been registered since the link was sent
+        if users.get_by_email(&stored.new_email).await?.is_some() {
+            return Err(anyhow!("Email already registered"));
+        }
+
+        users.change_email(&stored.user_id, &stored.new_email).await?;
+
+        Ok(stored.user_id)
+    }
+
     // ========================================================================
     // Library Access
     // ========================================================================
@@ -468,6 +713,13 @@ impl AuthService {
             .map_err(|e| anyhow!("Failed to verify password: {}", e))
     }
 
+    /// Values a password must not contain for this user
+    fn personal_values(user: &UserRecord) -> Vec<&str> {
+        let mut values = vec![user.username.as_str()];
+        values.extend(user.email.as_deref());
+        values
+    }
+
     /// Hash a token for storage (using SHA-256)
     fn hash_token(&self, token: &str) -> String {
         let mut hasher = Sha256::new();
@@ -600,9 +852,13 @@ impl AuthService {
         Ok(!self.db.users().has_admin().await?)
     }
 
-    /// Clean up expired refresh tokens
+    /// Clean up expired refresh, email change and password reset tokens
     pub async fn cleanup_expired_tokens(&self) -> Result<u64> {
-        self.db.users().cleanup_expired_refresh_tokens().await
+        let users = self.db.users();
+        let refresh = users.cleanup_expired_refresh_tokens().await?;
+        let email_change = users.cleanup_expired_email_change_tokens().await?;
+        let password_reset = users.cleanup_expired_password_reset_tokens().await?;
+        Ok(refresh + email_change + password_reset)
     }
 }
 
@@ -633,3 +889,55 @@ pub fn verify_token(token: &str) -> async_graphql::Result<crate::graphql::auth::
         role: Some(token_data.claims.role),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_reset_cooldown() {
+        let now = chrono::DateTime::parse_from_rfc3339("2025-03-12T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert!(password_reset_on_cooldown("2025-03-12T11:58:00Z", now));
+        assert!(!password_reset_on_cooldown("2025-03-12T11:50:00Z", now));
+        assert!(!password_reset_on_cooldown("garbage", now));
+    }
+
+    #[test]
+    fn test_password_length() {
+        assert!(validate_password_strength("Ab1!", &[]).is_err());
+        assert!(validate_password_strength("Abcdef1!", &[]).is_ok());
+        assert!(validate_password_strength(&format!("Ab1{}", "x".repeat(70)), &[]).is_err());
+    }
+
+    #[test]
+    fn test_password_character_classes() {
+        assert!(validate_password_strength("abcdefgh", &[]).is_err());
+        assert!(validate_password_strength("abcdefg1", &[]).is_err());
+        assert!(validate_password_strength("abcdefG1", &[]).is_ok());
+        assert!(validate_password_strength("abcdef-1", &[]).is_ok());
+        // Long passphrases don't need the mix
+        assert!(validate_password_strength("correct horse battery", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_password_personal_values() {
+        let personal = ["jane.doe@example.com"];
+        assert!(validate_password_strength("Jane.Doe-2024", &personal).is_err());
+        assert!(validate_password_strength("Tulip-Garden-7", &personal).is_ok());
+        // Very short values aren't checked
+        assert!(validate_password_strength("Jo-Secret-99", &["jo"]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(validate_email("user@example.com").is_ok());
+        assert!(validate_email("user@localhost").is_err());
+        assert!(validate_email("@example.com").is_err());
+        assert!(validate_email("user@.com").is_err());
+        assert!(validate_email("us er@example.com").is_err());
+        assert!(validate_email("user@a@b.com").is_err());
+        assert!(validate_email("user").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Pausing automation ("vacation mode")
+//!
+//! Users away from home, or short on remote storage, can stop new downloads
+//! without turning off monitoring: while a pause is active, scheduled and
+//! post-scan auto-hunt, the hunt started when an item is added, and RSS
+//! polling skip the paused libraries. Scans, metadata refresh and hunts the
+//! user starts by hand are unaffected. A pause with a resume time lifts
+//! itself once that time has passed.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::db::{AutomationPauseRecord, Database};
+
+/// Whether a pause is still in effect
+pub fn is_active(resume_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    resume_at.is_none_or(|resume_at| now < resume_at)
+}
+
+/// The pause in effect for a library, if any
+///
+/// A global pause takes precedence over the library's own.
+pub fn pause_for_library(
+    pauses: &[AutomationPauseRecord],
+    library_id: Uuid,
+    now: DateTime<Utc>,
+) -> Option<&AutomationPauseRecord> {
+    let active = || pauses.iter().filter(|p| is_active(p.resume_at, now));
+    active()
+        .find(|p| p.library_id.is_none())
+        .or_else(|| active().find(|p| p.library_id == Some(library_id)))
+}
+
+/// Pauses of a user that are still in effect
+pub async fn active_pauses(db: &Database, user_id: Uuid) -> Result<Vec<AutomationPauseRecord>> {
+    let now = Utc::now();
+    let pauses = db.automation_pauses().list_by_user(user_id).await?;
+    Ok(pauses
+        .into_iter()
+        .filter(|p| is_active(p.resume_at, now))
+        .collect())
+}
+
+/// Whether automation is paused for a library
+pub async fn is_library_paused(db: &Database, user_id: Uuid, library_id: Uuid) -> Result<bool> {
+    let pauses = db.automation_pauses().list_by_user(user_id).await?;
+    let pause = pause_for_library(&pauses, library_id, Utc::now());
+    if let Some(pause) = pause {
+        debug!(
+            library_id = %library_id,
+            global = pause.library_id.is_none(),
+            resume_at = ?pause.resume_at,
+            "Automation is paused for library"
+        );
+    }
+    Ok(pause.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn pause(library_id: Option<Uuid>, resume_at: Option<DateTime<Utc>>) -> AutomationPauseRecord {
+        AutomationPauseRecord {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            library_id,
+            resume_at,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_is_active() {
+        let now = Utc::now();
+        assert!(is_active(None, now));
+        assert!(is_active(Some(now + Duration::days(1)), now));
+        assert!(!is_active(Some(now - Duration::minutes(1)), now));
+        assert!(!is_active(Some(now), now));
+    }
+
+    #[test]
+    fn test_pause_for_library() {
+        let now = Utc::now();
+        let movies = Uuid::new_v4();
+        let tv = Uuid::new_v4();
+
+        let pauses = vec![pause(Some(movies), None)];
+        assert!(pause_for_library(&pauses, movies, now).is_some());
+        assert!(pause_for_library(&pauses, tv, now).is_none());
+
+        // An expired global pause doesn't count; the library's own still does
+        let pauses = vec![
+            pause(None, Some(now - Duration::hours(1))),
+            pause(Some(movies), Some(now + Duration::days(3))),
+        ];
+        assert!(pause_for_library(&pauses, tv, now).is_none());
+        let found = pause_for_library(&pauses, movies, now).unwrap();
+        assert_eq!(found.library_id, Some(movies));
+
+        let pauses = vec![pause(Some(movies), None), pause(None, None)];
+        assert_eq!(
+            pause_for_library(&pauses, movies, now).unwrap().library_id,
+            None
+        );
+    }
+}
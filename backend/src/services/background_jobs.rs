@@ -0,0 +1,154 @@
+//! Background jobs for long-running mutations
+//!
+//! Mutations such as library consolidation can take minutes, longer than
+//! clients wait for a response. They call [`BackgroundJobService::enqueue`]
+//! instead, which persists a job, runs the work on its own task and returns the
+//! job straight away. Status changes are written to the database and broadcast,
+//! so the outcome is available through the job queries and subscription.
+
+use std::future::Future;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::db::{BackgroundJobRecord, CreateBackgroundJob, Database};
+
+/// Finished jobs are kept this long so clients can still fetch their results
+pub const RETENTION_DAYS: i64 = 7;
+
+/// Handle given to a running job for reporting progress
+#[derive(Clone)]
+pub struct JobContext {
+    id: Uuid,
+    db: Database,
+    tx: broadcast::Sender<BackgroundJobRecord>,
+}
+
+impl JobContext {
+    /// Record progress (clamped to 0.0 - 1.0) with an optional message
+    pub async fn progress(&self, progress: f64, message: Option<&str>) {
+        let progress = progress.clamp(0.0, 1.0);
+        if let Err(e) = self
+            .db
+            .background_jobs()
+            .update_progress(self.id, Some(progress), message)
+            .await
+        {
+            warn!(job_id = %self.id, error = %e, "Failed to record job progress");
+            return;
+        }
+        publish(&self.db, &self.tx, self.id).await;
+    }
+}
+
+/// Runs and tracks background jobs
+pub struct BackgroundJobService {
+    db: Database,
+    tx: broadcast::Sender<BackgroundJobRecord>,
+}
+
+impl BackgroundJobService {
+    pub fn new(db: Database) -> Self {
+        let (tx, _) = broadcast::channel(100);
+        Self { db, tx }
+    }
+
+    /// Subscribe to job status updates
+    pub fn subscribe(&self) -> broadcast::Receiver<BackgroundJobRecord> {
+        self.tx.subscribe()
+    }
+
+    /// Tidy up after the previous process: jobs it left unfinished can't
+    /// resume, and old finished jobs are pruned
+    pub async fn recover(&self) -> Result<()> {
+        let jobs = self.db.background_jobs();
+        let interrupted = jobs.mark_interrupted().await?;
+        if interrupted > 0 {
+            warn!(
+                "Marked {} unfinished background job(s) as interrupted",
+                interrupted
+            );
+        }
+        jobs.delete_finished_before(Utc::now() - chrono::Duration::days(RETENTION_DAYS))
+            .await?;
+        Ok(())
+    }
+
+    /// Persist a job and start running it in the background
+    ///
+    /// Returns as soon as the job is recorded. The work's output is serialized
+    /// to JSON and stored as the job result; an error marks the job failed.
+    pub async fn enqueue<T, F, Fut>(
+        &self,
+        kind: &str,
+        user_id: Option<Uuid>,
+        payload: Option<serde_json::Value>,
+        work: F,
+    ) -> Result<BackgroundJobRecord>
+    where
+        T: Serialize + Send + 'static,
+        F: FnOnce(JobContext) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T>> + Send + 'static,
+    {
+        let job = self
+            .db
+            .background_jobs()
+            .create(CreateBackgroundJob {
+                kind: kind.to_string(),
+                payload,
+                user_id,
+            })
+            .await?;
+        let _ = self.tx.send(job.clone());
+
+        let ctx = JobContext {
+            id: job.id,
+            db: self.db.clone(),
+            tx: self.tx.clone(),
+        };
+        let kind = job.kind.clone();
+        tokio::spawn(async move {
+            let jobs = ctx.db.background_jobs();
+            if let Err(e) = jobs.mark_running(ctx.id).await {
+                error!(job_id = %ctx.id, error = %e, "Failed to start background job");
+                return;
+            }
+            publish(&ctx.db, &ctx.tx, ctx.id).await;
+
+            let outcome = work(ctx.clone())
+                .await
+                .and_then(|output| serde_json::to_value(output).map_err(Into::into));
+            let stored = match &outcome {
+                Ok(result) => {
+                    info!(job_id = %ctx.id, kind = %kind, "Background job completed");
+                    jobs.complete(ctx.id, result).await
+                }
+                Err(e) => {
+                    error!(job_id = %ctx.id, kind = %kind, error = %e, "Background job failed");
+                    jobs.fail(ctx.id, &e.to_string()).await
+                }
+            };
+            if let Err(e) = stored {
+                error!(job_id = %ctx.id, error = %e, "Failed to store background job outcome");
+            }
+            publish(&ctx.db, &ctx.tx, ctx.id).await;
+        });
+
+        Ok(job)
+    }
+}
+
+/// Broadcast the job's current state
+async fn publish(db: &Database, tx: &broadcast::Sender<BackgroundJobRecord>, id: Uuid) {
+    match db.background_jobs().get(id).await {
+        Ok(Some(job)) => {
+            let _ = tx.send(job);
+        }
+        Ok(None) => {}
+        Err(e) => warn!(job_id = %id, error = %e, "Failed to load background job"),
+    }
+}
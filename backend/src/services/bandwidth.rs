@@ -0,0 +1,333 @@
+//! Download bandwidth limits
+//!
+//! Torrents and usenet each have a default speed limit, and a schedule of time
+//! windows can override them, e.g. throttle usenet to 5 MB/s from 08:00 until
+//! midnight on weekdays. Windows are in the server's local time; a window whose
+//! end isn't after its start runs past midnight. The first matching window
+//! wins, and limits it leaves unset keep their default.
+//!
+//! The scheduler applies the limits for the current time every minute. Torrent
+//! limits go to the librqbit session; the usenet limit is a process-wide byte
+//! limiter that every usenet download waits on after fetching an article, so
+//! it holds across all downloads however many `UsenetService`s are running.
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use anyhow::{Result, bail};
+use chrono::{Datelike, Local, NaiveDateTime, NaiveTime, Weekday};
+use governor::{
+    Quota, RateLimiter,
+    clock::DefaultClock,
+    state::{InMemoryState, NotKeyed},
+};
+use once_cell::sync::Lazy;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::db::Database;
+use crate::services::TorrentService;
+
+/// Settings key for the schedule's time windows (JSON list of windows)
+pub const SCHEDULE_KEY: &str = "downloads.bandwidth_schedule";
+
+/// Settings key for the default torrent download limit in bytes/sec (0 = unlimited)
+pub const TORRENT_DOWNLOAD_LIMIT_KEY: &str = "torrent.download_limit";
+
+/// Settings key for the default torrent upload limit in bytes/sec (0 = unlimited)
+pub const TORRENT_UPLOAD_LIMIT_KEY: &str = "torrent.upload_limit";
+
+/// Settings key for the default usenet download limit in bytes/sec (0 = unlimited)
+pub const USENET_DOWNLOAD_LIMIT_KEY: &str = "usenet.download_limit";
+
+/// Speed limits in bytes/sec (0 = unlimited)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthLimits {
+    pub torrent_download: u64,
+    pub torrent_upload: u64,
+    pub usenet_download: u64,
+}
+
+/// A time window that overrides the default limits
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BandwidthWindow {
+    /// Days the window starts on (empty = every day)
+    #[serde(default)]
+    pub days: Vec<Weekday>,
+    pub start: NaiveTime,
+    /// End time; at or before `start` means the window runs past midnight
+    pub end: NaiveTime,
+    /// Limits in bytes/sec while the window is active (None = keep the default)
+    #[serde(default)]
+    pub torrent_download: Option<u64>,
+    #[serde(default)]
+    pub torrent_upload: Option<u64>,
+    #[serde(default)]
+    pub usenet_download: Option<u64>,
+}
+
+impl BandwidthWindow {
+    /// Whether the window is active at a local time
+    pub fn contains(&self, at: NaiveDateTime) -> bool {
+        let day = at.weekday();
+        let time = at.time();
+
+        if self.start < self.end {
+            self.starts_on(day) && time >= self.start && time < self.end
+        } else {
+            // Runs past midnight: the tail end belongs to the previous day's window
+            (self.starts_on(day) && time >= self.start)
+                || (self.starts_on(day.pred()) && time < self.end)
+        }
+    }
+
+    fn starts_on(&self, day: Weekday) -> bool {
+        self.days.is_empty() || self.days.contains(&day)
+    }
+
+    fn apply(&self, defaults: BandwidthLimits) -> BandwidthLimits {
+        BandwidthLimits {
+            torrent_download: self.torrent_download.unwrap_or(defaults.torrent_download),
+            torrent_upload: self.torrent_upload.unwrap_or(defaults.torrent_upload),
+            usenet_download: self.usenet_download.unwrap_or(defaults.usenet_download),
+        }
+    }
+}
+
+/// Limits in effect at a local time, and the index of the window that set them
+pub fn limits_at(
+    defaults: BandwidthLimits,
+    windows: &[BandwidthWindow],
+    at: NaiveDateTime,
+) -> (BandwidthLimits, Option<usize>) {
+    match windows.iter().position(|w| w.contains(at)) {
+        Some(index) => (windows[index].apply(defaults), Some(index)),
+        None => (defaults, None),
+    }
+}
+
+/// Parse a window time, "HH:MM" or "HH:MM:SS"
+pub fn parse_time(value: &str) -> Result<NaiveTime> {
+    let value = value.trim();
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M:%S"))
+        .or_else(|_| bail!("Invalid time '{}': expected HH:MM", value))
+}
+
+/// A bytes/sec limit as a rate limiter quota (None = unlimited)
+pub fn bps_limit(bytes_per_second: u64) -> Option<NonZeroU32> {
+    NonZeroU32::new(bytes_per_second.min(u32::MAX as u64) as u32)
+}
+
+/// Default limits from settings
+pub async fn load_defaults(db: &Database) -> Result<BandwidthLimits> {
+    let settings = db.settings();
+    let torrent_download: i64 = settings
+        .get_or_default(TORRENT_DOWNLOAD_LIMIT_KEY, 0)
+        .await?;
+    let torrent_upload: i64 = settings.get_or_default(TORRENT_UPLOAD_LIMIT_KEY, 0).await?;
+    let usenet_download: i64 = settings
+        .get_or_default(USENET_DOWNLOAD_LIMIT_KEY, 0)
+        .await?;
+
+    Ok(BandwidthLimits {
+        torrent_download: torrent_download.max(0) as u64,
+        torrent_upload: torrent_upload.max(0) as u64,
+        usenet_download: usenet_download.max(0) as u64,
+    })
+}
+
+/// The schedule's time windows from settings
+pub async fn load_windows(db: &Database) -> Result<Vec<BandwidthWindow>> {
+    db.settings().get_or_default(SCHEDULE_KEY, Vec::new()).await
+}
+
+type ByteLimiter = RateLimiter<NotKeyed, InMemoryState, DefaultClock>;
+
+/// Usenet byte limiter for one rate, allowing a second's worth of burst
+struct UsenetLimiter {
+    bytes_per_second: NonZeroU32,
+    limiter: ByteLimiter,
+}
+
+static MANAGER: Lazy<BandwidthManager> = Lazy::new(BandwidthManager::default);
+
+/// Applies the bandwidth schedule to torrent and usenet downloads
+#[derive(Default)]
+pub struct BandwidthManager {
+    usenet: RwLock<Option<Arc<UsenetLimiter>>>,
+    /// Limits last applied, to log changes
+    applied: Mutex<Option<BandwidthLimits>>,
+}
+
+impl BandwidthManager {
+    /// The process-wide manager
+    pub fn global() -> &'static BandwidthManager {
+        &MANAGER
+    }
+
+    /// Apply the limits for the current time from settings
+    pub async fn apply(&self, db: &Database, torrent: &TorrentService) -> Result<BandwidthLimits> {
+        let defaults = load_defaults(db).await?;
+        let windows = load_windows(db).await?;
+        let (limits, window) = limits_at(defaults, &windows, Local::now().naive_local());
+
+        torrent.set_speed_limits(limits.torrent_download, limits.torrent_upload);
+        self.set_usenet_limit(limits.usenet_download);
+
+        if self.applied.lock().replace(limits) != Some(limits) {
+            info!(
+                torrent_download = limits.torrent_download,
+                torrent_upload = limits.torrent_upload,
+                usenet_download = limits.usenet_download,
+                window = ?window,
+                "Applied download bandwidth limits"
+            );
+        }
+        Ok(limits)
+    }
+
+    fn set_usenet_limit(&self, bytes_per_second: u64) {
+        let rate = bps_limit(bytes_per_second);
+        let mut usenet = self.usenet.write();
+        // Keep the current limiter if the rate hasn't changed, so its state carries over
+        if usenet.as_ref().map(|l| l.bytes_per_second) == rate {
+            return;
+        }
+        *usenet = rate.map(|rate| {
+            Arc::new(UsenetLimiter {
+                bytes_per_second: rate,
+                limiter: RateLimiter::direct(Quota::per_second(rate)),
+            })
+        });
+    }
+
+    /// Wait until the usenet limit allows `bytes` more to have been downloaded
+    pub async fn throttle_usenet(&self, bytes: usize) {
+        let Some(usenet) = self.usenet.read().clone() else {
+            return;
+        };
+
+        // Articles can be bigger than the burst, which governor won't grant at once
+        let burst = usenet.bytes_per_second.get() as u64;
+        let mut remaining = bytes as u64;
+        while let Some(chunk) = NonZeroU32::new(remaining.min(burst) as u32) {
+            let _ = usenet.limiter.until_n_ready(chunk).await;
+            remaining -= chunk.get() as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(date: (i32, u32, u32), time: &str) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(date.0, date.1, date.2)
+            .unwrap()
+            .and_time(parse_time(time).unwrap())
+    }
+
+    fn window(days: Vec<Weekday>, start: &str, end: &str) -> BandwidthWindow {
+        BandwidthWindow {
+            days,
+            start: parse_time(start).unwrap(),
+            end: parse_time(end).unwrap(),
+            torrent_download: None,
+            torrent_upload: None,
+            usenet_download: Some(5_000_000),
+        }
+    }
+
+    // 2026-10-12 is a Monday
+    const MONDAY: (i32, u32, u32) = (2026, 10, 12);
+    const TUESDAY: (i32, u32, u32) = (2026, 10, 13);
+    const SATURDAY: (i32, u32, u32) = (2026, 10, 17);
+
+    #[test]
+    fn test_window_same_day() {
+        let w = window(vec![Weekday::Mon], "09:00", "17:00");
+        assert!(w.contains(at(MONDAY, "09:00")));
+        assert!(w.contains(at(MONDAY, "16:59")));
+        assert!(!w.contains(at(MONDAY, "17:00")));
+        assert!(!w.contains(at(MONDAY, "08:59")));
+        assert!(!w.contains(at(TUESDAY, "12:00")));
+    }
+
+    #[test]
+    fn test_window_past_midnight() {
+        // 8am until midnight
+        let w = window(vec![Weekday::Mon], "08:00", "00:00");
+        assert!(w.contains(at(MONDAY, "08:00")));
+        assert!(w.contains(at(MONDAY, "23:59")));
+        assert!(!w.contains(at(TUESDAY, "00:00")));
+        assert!(!w.contains(at(MONDAY, "07:59")));
+
+        // 22:00 until 06:00 the next morning
+        let w = window(vec![Weekday::Mon], "22:00", "06:00");
+        assert!(w.contains(at(MONDAY, "23:00")));
+        assert!(w.contains(at(TUESDAY, "05:59")));
+        assert!(!w.contains(at(MONDAY, "05:00")));
+        assert!(!w.contains(at(TUESDAY, "22:30")));
+    }
+
+    #[test]
+    fn test_window_every_day() {
+        let w = window(vec![], "00:00", "00:00");
+        assert!(w.contains(at(MONDAY, "00:00")));
+        assert!(w.contains(at(SATURDAY, "13:37")));
+    }
+
+    #[test]
+    fn test_limits_at() {
+        let defaults = BandwidthLimits {
+            torrent_download: 1_000,
+            torrent_upload: 500,
+            usenet_download: 0,
+        };
+        let weekend = BandwidthWindow {
+            torrent_download: Some(0),
+            ..window(vec![Weekday::Sat, Weekday::Sun], "00:00", "00:00")
+        };
+        let windows = vec![weekend, window(vec![], "08:00", "00:00")];
+
+        assert_eq!(
+            limits_at(defaults, &windows, at(MONDAY, "07:00")),
+            (defaults, None)
+        );
+
+        let (limits, index) = limits_at(defaults, &windows, at(MONDAY, "12:00"));
+        assert_eq!(index, Some(1));
+        assert_eq!(limits.usenet_download, 5_000_000);
+        assert_eq!(limits.torrent_download, 1_000);
+
+        // The first matching window wins
+        let (limits, index) = limits_at(defaults, &windows, at(SATURDAY, "12:00"));
+        assert_eq!(index, Some(0));
+        assert_eq!(limits.torrent_download, 0);
+        assert_eq!(limits.torrent_upload, 500);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("08:30").unwrap(),
+            NaiveTime::from_hms_opt(8, 30, 0).unwrap()
+        );
+        assert_eq!(
+            parse_time(" 23:59:59 ").unwrap(),
+            NaiveTime::from_hms_opt(23, 59, 59).unwrap()
+        );
+        assert!(parse_time("24:00").is_err());
+        assert!(parse_time("8am").is_err());
+    }
+
+    #[test]
+    fn test_bps_limit() {
+        assert_eq!(bps_limit(0), None);
+        assert_eq!(bps_limit(1024).map(NonZeroU32::get), Some(1024));
+        assert_eq!(bps_limit(u64::MAX), Some(NonZeroU32::MAX));
+    }
+}
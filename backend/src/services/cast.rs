@@ -2,9 +2,19 @@
 //!
 //! This module provides device discovery via mDNS and media casting
 //! functionality using the rust_cast library for CASTV2 protocol communication.
+//!
+//! Google speaker groups are discovered like any other device, but are
+//! audio-only and advertised by whichever member currently leads the group.
+//! When the leader drops out, the group is re-advertised from another member;
+//! discovery follows the group to its new endpoint and resynchronizes any
+//! session that was playing on it.
+//!
+//! AirPlay receivers are discovered alongside Chromecasts and share the same
+//! devices, sessions and controls; commands are routed to the AirPlay sender
+//! (see `airplay`) based on the device type.
 
 use std::collections::HashMap;
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -12,13 +22,15 @@ use anyhow::{Context, Result};
 use mdns_sd::{ServiceDaemon, ServiceEvent};
 use parking_lot::RwLock;
 use rust_cast::CastDevice as RustCastDevice;
-use rust_cast::channels::media::{Media, StreamType};
+use rust_cast::channels::media::{Media, PlayerState, StreamType};
 use rust_cast::channels::receiver::CastDeviceApp;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::airplay::{self, AIRPLAY_SERVICE_TYPE, AirPlayClient, DEFAULT_AIRPLAY_PORT};
+use super::transcode::is_chromecast_compatible;
 use crate::db::{
     CastDeviceRecord, CastSessionRecord, CreateCastDevice, CreateCastSession, Database,
     UpdateCastSession,
@@ -39,6 +51,12 @@ pub enum CastDeviceType {
     GoogleHome,
     GoogleNestHub,
     AndroidTv,
+    /// Google speaker group (multi-room audio)
+    SpeakerGroup,
+    AppleTv,
+    HomePod,
+    /// Third-party AirPlay receiver (smart TVs, etc.)
+    AirPlay,
     Unknown,
 }
 
@@ -50,13 +68,20 @@ impl CastDeviceType {
             Self::GoogleHome => "google_home",
             Self::GoogleNestHub => "google_nest_hub",
             Self::AndroidTv => "android_tv",
+            Self::SpeakerGroup => "speaker_group",
+            Self::AppleTv => "apple_tv",
+            Self::HomePod => "homepod",
+            Self::AirPlay => "airplay",
             Self::Unknown => "unknown",
         }
     }
 
     pub fn from_model(model: &str) -> Self {
         let model_lower = model.to_lowercase();
-        if model_lower.contains("chromecast audio") {
+        // Groups advertise themselves with the model "Google Cast Group"
+        if model_lower.contains("cast group") {
+            Self::SpeakerGroup
+        } else if model_lower.contains("chromecast audio") {
             Self::ChromecastAudio
         } else if model_lower.contains("chromecast") {
             Self::Chromecast
@@ -70,6 +95,52 @@ impl CastDeviceType {
             Self::Unknown
         }
     }
+
+    /// Device type from the AirPlay TXT "model" record (e.g. "AppleTV6,2")
+    pub fn from_airplay_model(model: &str) -> Self {
+        if model.starts_with("AppleTV") {
+            Self::AppleTv
+        } else if model.starts_with("AudioAccessory") {
+            Self::HomePod
+        } else {
+            Self::AirPlay
+        }
+    }
+
+    /// Whether the device is controlled over AirPlay rather than Google Cast
+    pub fn is_airplay(&self) -> bool {
+        matches!(self, Self::AppleTv | Self::HomePod | Self::AirPlay)
+    }
+
+    /// Whether this is a multi-room speaker group rather than a single device
+    pub fn is_group(&self) -> bool {
+        matches!(self, Self::SpeakerGroup)
+    }
+
+    /// Whether the device can only play audio (no screen)
+    pub fn is_audio_only(&self) -> bool {
+        matches!(
+            self,
+            Self::SpeakerGroup | Self::ChromecastAudio | Self::GoogleHome | Self::HomePod
+        )
+    }
+}
+
+impl From<&str> for CastDeviceType {
+    fn from(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "chromecast" => Self::Chromecast,
+            "chromecast_audio" => Self::ChromecastAudio,
+            "google_home" => Self::GoogleHome,
+            "google_nest_hub" => Self::GoogleNestHub,
+            "android_tv" => Self::AndroidTv,
+            "speaker_group" => Self::SpeakerGroup,
+            "apple_tv" => Self::AppleTv,
+            "homepod" => Self::HomePod,
+            "airplay" => Self::AirPlay,
+            _ => Self::Unknown,
+        }
+    }
 }
 
 impl std::fmt::Display for CastDeviceType {
@@ -108,6 +179,17 @@ impl CastPlayerState {
     }
 }
 
+impl From<&PlayerState> for CastPlayerState {
+    fn from(state: &PlayerState) -> Self {
+        match state {
+            PlayerState::Buffering => Self::Buffering,
+            PlayerState::Playing => Self::Playing,
+            PlayerState::Paused => Self::Paused,
+            PlayerState::Idle => Self::Idle,
+        }
+    }
+}
+
 impl std::fmt::Display for CastPlayerState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -117,6 +199,8 @@ impl std::fmt::Display for CastPlayerState {
 /// A discovered cast device
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscoveredCastDevice {
+    /// mDNS service instance name, used to match removal events
+    pub service_name: String,
     pub name: String,
     pub address: IpAddr,
     pub port: u16,
@@ -136,6 +220,36 @@ pub struct CastSessionEvent {
     pub is_muted: bool,
 }
 
+/// Membership change of a speaker group, as observed through discovery
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupMembershipEvent {
+    /// The member leading the group disappeared from the network
+    LeaderLost,
+    /// The group was advertised again (possibly by a new leader), which reported
+    /// this player state (None = no media loaded any more)
+    LeaderResolved(Option<CastPlayerState>),
+}
+
+/// Next state of a session on a speaker group after a membership change
+///
+/// Losing the leader interrupts playback until another member takes over, so
+/// a playing session is held in buffering rather than ended. Once the group is
+/// back, the new leader's own report wins; if it has no media the session is
+/// over.
+pub fn next_group_session_state(
+    current: CastPlayerState,
+    event: GroupMembershipEvent,
+) -> CastPlayerState {
+    match event {
+        GroupMembershipEvent::LeaderLost => match current {
+            CastPlayerState::Playing | CastPlayerState::Buffering => CastPlayerState::Buffering,
+            other => other,
+        },
+        GroupMembershipEvent::LeaderResolved(Some(remote)) => remote,
+        GroupMembershipEvent::LeaderResolved(None) => CastPlayerState::Idle,
+    }
+}
+
 /// Cast devices changed event for subscriptions
 #[derive(Debug, Clone)]
 pub struct CastDevicesEvent {
@@ -175,10 +289,12 @@ impl Default for CastServiceConfig {
 pub struct CastService {
     db: Database,
     config: CastServiceConfig,
-    /// Discovered devices from mDNS (address -> device info)
-    discovered_devices: Arc<RwLock<HashMap<IpAddr, DiscoveredCastDevice>>>,
+    /// Discovered devices from mDNS (address:port -> device info)
+    discovered_devices: Arc<RwLock<HashMap<SocketAddr, DiscoveredCastDevice>>>,
     /// Active connections to devices
     connections: Arc<RwLock<HashMap<Uuid, ActiveConnection>>>,
+    /// AirPlay clients per device, kept open for the lifetime of a session
+    airplay_clients: Arc<RwLock<HashMap<Uuid, Arc<AirPlayClient>>>>,
     /// Broadcast channel for session updates
     session_tx: broadcast::Sender<CastSessionEvent>,
     /// Broadcast channel for device changes
@@ -196,6 +312,7 @@ impl CastService {
             config,
             discovered_devices: Arc::new(RwLock::new(HashMap::new())),
             connections: Arc::new(RwLock::new(HashMap::new())),
+            airplay_clients: Arc::new(RwLock::new(HashMap::new())),
             session_tx,
             devices_tx,
         }
@@ -218,6 +335,7 @@ impl CastService {
         let discovered = self.discovered_devices.clone();
         let db = self.db.clone();
         let devices_tx = self.devices_tx.clone();
+        let session_tx = self.session_tx.clone();
 
         // Spawn discovery task
         tokio::task::spawn_blocking(move || {
@@ -263,6 +381,7 @@ impl CastService {
 
                                 for addr in info.get_addresses() {
                                     let device = DiscoveredCastDevice {
+                                        service_name: info.get_fullname().to_string(),
                                         name: name.clone(),
                                         address: *addr,
                                         port: info.get_port(),
@@ -276,32 +395,52 @@ impl CastService {
                                     );
 
                                     // Store in discovered devices map
-                                    discovered.write().insert(*addr, device.clone());
+                                    discovered.write().insert(
+                                        SocketAddr::new(*addr, device.port),
+                                        device.clone(),
+                                    );
 
                                     // Save to database (upsert)
-                                    let db_clone = db.clone();
-                                    let devices_tx_clone = devices_tx.clone();
-                                    let device_clone = device.clone();
+                                    tokio::spawn(Self::persist_discovered_device(
+                                        db.clone(),
+                                        devices_tx.clone(),
+                                        session_tx.clone(),
+                                        device,
+                                    ));
+                                }
+                            }
+                            ServiceEvent::ServiceRemoved(_, fullname) => {
+                                debug!("Chromecast removed: {}", fullname);
 
+                                let mut removed = Vec::new();
+                                discovered.write().retain(|_, device| {
+                                    if device.service_name == fullname {
+                                        removed.push(device.clone());
+                                        false
+                                    } else {
+                                        true
+                                    }
+                                });
+
+                                // A vanished group means its leader dropped out
+                                if let Some(group) =
+                                    removed.into_iter().find(|d| d.device_type.is_group())
+                                {
+                                    let db_clone = db.clone();
+                                    let session_tx_clone = session_tx.clone();
                                     tokio::spawn(async move {
-                                        if let Err(e) =
-                                            Self::save_discovered_device(&db_clone, &device_clone)
-                                                .await
+                                        if let Err(e) = Self::handle_group_leader_lost(
+                                            &db_clone,
+                                            &session_tx_clone,
+                                            &group,
+                                        )
+                                        .await
                                         {
-                                            warn!("Failed to save discovered device: {}", e);
-                                        }
-
-                                        // Broadcast device change
-                                        if let Ok(devices) = db_clone.cast().list_devices().await {
-                                            let _ =
-                                                devices_tx_clone.send(CastDevicesEvent { devices });
+                                            warn!("Failed to handle speaker group change: {}", e);
                                         }
                                     });
                                 }
                             }
-                            ServiceEvent::ServiceRemoved(_, fullname) => {
-                                debug!("Chromecast removed: {}", fullname);
-                            }
                             _ => {}
                         }
                     }
@@ -316,11 +455,121 @@ impl CastService {
             }
         });
 
+        self.start_airplay_discovery();
+
         Ok(())
     }
 
+    /// Start mDNS discovery of AirPlay receivers
+    fn start_airplay_discovery(&self) {
+        let discovered = self.discovered_devices.clone();
+        let db = self.db.clone();
+        let devices_tx = self.devices_tx.clone();
+        let session_tx = self.session_tx.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let mdns = match ServiceDaemon::new() {
+                Ok(mdns) => mdns,
+                Err(e) => {
+                    error!("Failed to create mDNS daemon: {}", e);
+                    return;
+                }
+            };
+
+            let receiver = match mdns.browse(AIRPLAY_SERVICE_TYPE) {
+                Ok(receiver) => receiver,
+                Err(e) => {
+                    error!("Failed to browse for AirPlay receivers: {}", e);
+                    return;
+                }
+            };
+
+            info!("mDNS discovery started, listening for AirPlay receivers");
+
+            loop {
+                match receiver.recv_timeout(Duration::from_secs(5)) {
+                    Ok(ServiceEvent::ServiceResolved(info)) => {
+                        let model = info
+                            .get_properties()
+                            .get("model")
+                            .map(|v| v.val_str().to_string());
+
+                        let device_type = model
+                            .as_deref()
+                            .map(CastDeviceType::from_airplay_model)
+                            .unwrap_or(CastDeviceType::AirPlay);
+
+                        let name = airplay::instance_name(info.get_fullname()).to_string();
+
+                        for addr in info.get_addresses() {
+                            let device = DiscoveredCastDevice {
+                                service_name: info.get_fullname().to_string(),
+                                name: name.clone(),
+                                address: *addr,
+                                port: info.get_port(),
+                                model: model.clone(),
+                                device_type,
+                            };
+
+                            debug!(
+                                "Discovered AirPlay receiver: {} at {}:{}",
+                                device.name, device.address, device.port
+                            );
+
+                            discovered
+                                .write()
+                                .insert(SocketAddr::new(*addr, device.port), device.clone());
+
+                            tokio::spawn(Self::persist_discovered_device(
+                                db.clone(),
+                                devices_tx.clone(),
+                                session_tx.clone(),
+                                device,
+                            ));
+                        }
+                    }
+                    Ok(ServiceEvent::ServiceRemoved(_, fullname)) => {
+                        debug!("AirPlay receiver removed: {}", fullname);
+                        discovered
+                            .write()
+                            .retain(|_, device| device.service_name != fullname);
+                    }
+                    Ok(_) => {}
+                    Err(flume::RecvTimeoutError::Timeout) => {
+                        // Continue listening
+                    }
+                    Err(flume::RecvTimeoutError::Disconnected) => {
+                        warn!("mDNS receiver disconnected, stopping AirPlay discovery");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// Save a discovered device and broadcast the updated device list
+    async fn persist_discovered_device(
+        db: Database,
+        devices_tx: broadcast::Sender<CastDevicesEvent>,
+        session_tx: broadcast::Sender<CastSessionEvent>,
+        device: DiscoveredCastDevice,
+    ) {
+        if let Err(e) = Self::save_discovered_device(&db, &session_tx, &device).await {
+            warn!("Failed to save discovered device: {}", e);
+        }
+
+        // Broadcast device change
+        if let Ok(devices) = db.cast().list_devices().await {
+            let _ = devices_tx.send(CastDevicesEvent { devices });
+        }
+    }
+
     /// Save a discovered device to the database
-    async fn save_discovered_device(db: &Database, device: &DiscoveredCastDevice) -> Result<()> {
+    async fn save_discovered_device(
+        db: &Database,
+        session_tx: &broadcast::Sender<CastSessionEvent>,
+        device: &DiscoveredCastDevice,
+    ) -> Result<()> {
         let input = CreateCastDevice {
             name: device.name.clone(),
             address: device.address.to_string(),
@@ -329,7 +578,111 @@ impl CastService {
             device_type: device.device_type.to_string(),
             is_manual: false,
         };
-        db.cast().upsert_device(input).await?;
+
+        if !device.device_type.is_group() {
+            db.cast().upsert_device(input).await?;
+            return Ok(());
+        }
+
+        let previous = db.cast().get_group_by_name(&device.name).await?;
+        let group = db.cast().upsert_group(input).await?;
+        let moved = previous.is_some_and(|p| p.address != group.address || p.port != group.port);
+        if moved {
+            info!(
+                group = %group.name,
+                address = %group.address,
+                port = group.port,
+                "Speaker group moved to a new leader"
+            );
+        }
+        Self::resync_group_session(db, session_tx, &group, moved).await
+    }
+
+    /// Hold a group's active session while the group looks for a new leader
+    async fn handle_group_leader_lost(
+        db: &Database,
+        session_tx: &broadcast::Sender<CastSessionEvent>,
+        lost: &DiscoveredCastDevice,
+    ) -> Result<()> {
+        let Some(group) = db.cast().get_group_by_name(&lost.name).await? else {
+            return Ok(());
+        };
+        // Only the endpoint we were using matters; a stale advertisement from
+        // an earlier leader going away changes nothing
+        if group.address != lost.address.to_string() || group.port != lost.port as i32 {
+            return Ok(());
+        }
+        let Some(session) = db.cast().get_active_session_for_device(group.id).await? else {
+            return Ok(());
+        };
+
+        let current = CastPlayerState::from_str(&session.player_state);
+        let next = next_group_session_state(current, GroupMembershipEvent::LeaderLost);
+        warn!(
+            group = %group.name,
+            session_id = %session.id,
+            "Speaker group leader dropped out, waiting for the group to regroup"
+        );
+        Self::apply_session_state(db, session_tx, &session, next, None).await
+    }
+
+    /// Bring a group's active session in line with what the (new) leader reports
+    ///
+    /// Only runs when the group moved or its session is waiting on a regroup, so
+    /// routine re-advertisements don't probe the device.
+    async fn resync_group_session(
+        db: &Database,
+        session_tx: &broadcast::Sender<CastSessionEvent>,
+        group: &CastDeviceRecord,
+        moved: bool,
+    ) -> Result<()> {
+        let Some(session) = db.cast().get_active_session_for_device(group.id).await? else {
+            return Ok(());
+        };
+        let current = CastPlayerState::from_str(&session.player_state);
+        if !moved && current != CastPlayerState::Buffering {
+            return Ok(());
+        }
+
+        let addr = group.address.clone();
+        let port = group.port as u16;
+        let remote = tokio::task::spawn_blocking(move || Self::media_state_blocking(&addr, port))
+            .await?
+            .unwrap_or_else(|e| {
+                debug!(group = %group.name, error = %e, "Could not query speaker group status");
+                None
+            });
+
+        let next = next_group_session_state(
+            current,
+            GroupMembershipEvent::LeaderResolved(remote.map(|(state, _)| state)),
+        );
+        Self::apply_session_state(db, session_tx, &session, next, remote.and_then(|(_, p)| p)).await
+    }
+
+    /// Persist a session state transition and notify subscribers
+    async fn apply_session_state(
+        db: &Database,
+        session_tx: &broadcast::Sender<CastSessionEvent>,
+        session: &CastSessionRecord,
+        state: CastPlayerState,
+        position: Option<f64>,
+    ) -> Result<()> {
+        let updated = if state == CastPlayerState::Idle {
+            info!(session_id = %session.id, "Cast session ended after speaker group change");
+            db.cast().end_session(session.id).await?
+        } else {
+            let input = UpdateCastSession {
+                player_state: Some(state.to_string()),
+                current_position: position,
+                ..Default::default()
+            };
+            db.cast().update_session(session.id, input).await?
+        };
+
+        if let Some(event) = updated.as_ref().and_then(Self::session_event) {
+            let _ = session_tx.send(event);
+        }
         Ok(())
     }
 
@@ -343,19 +696,25 @@ impl CastService {
         let port = port.unwrap_or(DEFAULT_CAST_PORT);
         let name = name.unwrap_or_else(|| format!("Cast Device ({})", address));
 
-        // Try to connect to get device info
-        let model = match self.probe_device(address, port).await {
-            Ok(info) => info,
-            Err(e) => {
-                warn!("Could not probe device at {}:{}: {}", address, port, e);
-                None
-            }
-        };
+        // AirPlay receivers listen on their own port and can't be probed over CASTV2
+        let (model, device_type) = if port == DEFAULT_AIRPLAY_PORT {
+            (None, CastDeviceType::AirPlay)
+        } else {
+            // Try to connect to get device info
+            let model = match self.probe_device(address, port).await {
+                Ok(info) => info,
+                Err(e) => {
+                    warn!("Could not probe device at {}:{}: {}", address, port, e);
+                    None
+                }
+            };
 
-        let device_type = model
-            .as_ref()
-            .map(|m| CastDeviceType::from_model(m))
-            .unwrap_or(CastDeviceType::Unknown);
+            let device_type = model
+                .as_ref()
+                .map(|m| CastDeviceType::from_model(m))
+                .unwrap_or(CastDeviceType::Unknown);
+            (model, device_type)
+        };
 
         let input = CreateCastDevice {
             name,
@@ -419,6 +778,7 @@ impl CastService {
 
         // Remove from connections
         self.connections.write().remove(&id);
+        self.airplay_clients.write().remove(&id);
 
         let result = self.db.cast().delete_device(id).await?;
 
@@ -473,11 +833,51 @@ impl CastService {
             .await?
             .context("Media file not found")?;
 
+        // Determine media type from file
+        let content_type = Self::get_content_type(&media_file.path);
+
+        let device_type = CastDeviceType::from(device.device_type.as_str());
+        if device_type.is_audio_only() && !content_type.starts_with("audio/") {
+            anyhow::bail!(
+                "{} is an audio-only device; only music and audiobooks can be cast to it",
+                device.name
+            );
+        }
+        if device_type == CastDeviceType::HomePod {
+            anyhow::bail!(
+                "{} is a HomePod; AirPlay audio streaming to HomePods isn't supported yet",
+                device.name
+            );
+        }
+
+        // Chromecasts only play a few formats directly; other videos are
+        // streamed as an HLS transcode
+        let transcode = !device_type.is_airplay()
+            && content_type.starts_with("video/")
+            && !is_chromecast_compatible(
+                media_file.container.as_deref(),
+                media_file.video_codec.as_deref(),
+                media_file.audio_codec.as_deref(),
+            );
+
         // Generate stream URL
-        let stream_url = format!(
-            "{}/api/media/{}/stream",
-            self.config.media_base_url, media_file_id
-        );
+        let (stream_url, content_type) = if transcode {
+            (
+                format!(
+                    "{}/api/media/{}/hls.m3u8",
+                    self.config.media_base_url, media_file_id
+                ),
+                "application/x-mpegURL".to_string(),
+            )
+        } else {
+            (
+                format!(
+                    "{}/api/media/{}/stream",
+                    self.config.media_base_url, media_file_id
+                ),
+                content_type,
+            )
+        };
 
         // End any existing session on this device
         self.db.cast().end_sessions_for_device(device_id).await?;
@@ -502,9 +902,6 @@ impl CastService {
         let _session_tx = self.session_tx.clone();
         let start_pos = start_position.unwrap_or(0.0);
 
-        // Determine media type from file
-        let content_type = Self::get_content_type(&media_file.path);
-
         info!(
             device_id = %device_id,
             device_name = %device.name,
@@ -517,6 +914,13 @@ impl CastService {
             "Casting media to device"
         );
 
+        if let Some(client) = self.airplay_client(&device)? {
+            tokio::spawn(Self::start_airplay(
+                client, db, session_id, stream_url, start_pos,
+            ));
+            return Ok(session);
+        }
+
         tokio::task::spawn_blocking(move || {
             match Self::cast_media_blocking(&addr, port, &stream_url, &content_type, start_pos) {
                 Ok((_transport_id, duration)) => {
@@ -555,6 +959,76 @@ impl CastService {
         Ok(session)
     }
 
+    /// Start playback on an AirPlay receiver and record the outcome on the session
+    async fn start_airplay(
+        client: Arc<AirPlayClient>,
+        db: Database,
+        session_id: Uuid,
+        stream_url: String,
+        start_position: f64,
+    ) {
+        let result = async {
+            client.play(&stream_url, 0.0).await?;
+
+            // The receiver only reports a duration once the media has loaded
+            let mut loaded = None;
+            for _ in 0..20 {
+                tokio::time::sleep(Duration::from_millis(500)).await;
+                if let Some(position) = client.position().await? {
+                    loaded = Some(position);
+                    break;
+                }
+            }
+
+            if start_position > 0.0 {
+                client.scrub(start_position).await?;
+            }
+            anyhow::Ok(loaded)
+        }
+        .await;
+
+        match result {
+            Ok(loaded) => {
+                info!("Started AirPlay playback (session: {})", session_id);
+                let position = if start_position > 0.0 {
+                    start_position
+                } else {
+                    loaded.map_or(0.0, |p| p.position)
+                };
+                let input = UpdateCastSession {
+                    player_state: Some("playing".to_string()),
+                    duration: loaded.map(|p| p.duration),
+                    current_position: Some(position),
+                    ..Default::default()
+                };
+                if let Err(e) = db.cast().update_session(session_id, input).await {
+                    error!("Failed to update session: {}", e);
+                }
+            }
+            Err(e) => {
+                error!("Failed to start AirPlay playback: {}", e);
+                let _ = db.cast().end_session(session_id).await;
+            }
+        }
+    }
+
+    /// AirPlay client for a device, or None if it is a Google Cast device
+    fn airplay_client(&self, device: &CastDeviceRecord) -> Result<Option<Arc<AirPlayClient>>> {
+        if !CastDeviceType::from(device.device_type.as_str()).is_airplay() {
+            return Ok(None);
+        }
+
+        if let Some(client) = self.airplay_clients.read().get(&device.id) {
+            return Ok(Some(client.clone()));
+        }
+
+        let client = Arc::new(AirPlayClient::new(&device.address, device.port as u16)?);
+        self.airplay_clients
+            .write()
+            .insert(device.id, client.clone());
+        Ok(Some(client))
+    }
+
     /// Cast media (blocking, runs in spawn_blocking)
     fn cast_media_blocking(
         addr: &str,
@@ -660,7 +1134,9 @@ impl CastService {
             "mp3" => "audio/mpeg",
             "flac" => "audio/flac",
             "aac" => "audio/aac",
-            "m4a" => "audio/mp4",
+            "m4a" | "m4b" => "audio/mp4",
+            "ogg" | "opus" => "audio/ogg",
+            "wav" => "audio/wav",
             _ => "video/mp4",
         }
         .to_string()
@@ -686,10 +1162,14 @@ impl CastService {
         let port = device.port as u16;
 
         info!(session_id = %session_id, addr = %addr, port = port, "Cast play");
-        tokio::task::spawn_blocking(move || {
-            Self::control_playback_blocking(&addr, port, PlaybackCommand::Play)
-        })
-        .await??;
+        if let Some(client) = self.airplay_client(&device)? {
+            client.set_rate(1.0).await?;
+        } else {
+            tokio::task::spawn_blocking(move || {
+                Self::control_playback_blocking(&addr, port, PlaybackCommand::Play)
+            })
+            .await??;
+        }
 
         let input = UpdateCastSession {
             player_state: Some("playing".to_string()),
@@ -727,10 +1207,14 @@ impl CastService {
         let port = device.port as u16;
 
         info!(session_id = %session_id, addr = %addr, port = port, "Cast pause");
-        tokio::task::spawn_blocking(move || {
-            Self::control_playback_blocking(&addr, port, PlaybackCommand::Pause)
-        })
-        .await??;
+        if let Some(client) = self.airplay_client(&device)? {
+            client.set_rate(0.0).await?;
+        } else {
+            tokio::task::spawn_blocking(move || {
+                Self::control_playback_blocking(&addr, port, PlaybackCommand::Pause)
+            })
+            .await??;
+        }
 
         let input = UpdateCastSession {
             player_state: Some("paused".to_string()),
@@ -763,10 +1247,15 @@ impl CastService {
                 let port = device.port as u16;
 
                 info!(session_id = %session_id, addr = %addr, port = port, "Cast stop");
-                let _ = tokio::task::spawn_blocking(move || {
-                    Self::control_playback_blocking(&addr, port, PlaybackCommand::Stop)
-                })
-                .await;
+                if let Ok(Some(client)) = self.airplay_client(&device) {
+                    let _ = client.stop().await;
+                    self.airplay_clients.write().remove(&device_id);
+                } else {
+                    let _ = tokio::task::spawn_blocking(move || {
+                        Self::control_playback_blocking(&addr, port, PlaybackCommand::Stop)
+                    })
+                    .await;
+                }
             }
         }
 
@@ -775,7 +1264,7 @@ impl CastService {
         Ok(())
     }
 
-    /// Seek to a position in the media
+    /// Seek to an absolute position in the media (clamped to the media duration)
     pub async fn seek(&self, session_id: Uuid, position: f64) -> Result<CastSessionRecord> {
         let session = self
             .db
@@ -784,6 +1273,11 @@ impl CastService {
             .await?
             .context("Session not found")?;
 
+        let position = match session.duration {
+            Some(duration) => position.clamp(0.0, duration),
+            None => position.max(0.0),
+        };
+
         let device = self
             .db
             .cast()
@@ -801,10 +1295,14 @@ impl CastService {
             position = position,
             "Cast seek"
         );
-        tokio::task::spawn_blocking(move || {
-            Self::control_playback_blocking(&addr, port, PlaybackCommand::Seek(position))
-        })
-        .await??;
+        if let Some(client) = self.airplay_client(&device)? {
+            client.scrub(position).await?;
+        } else {
+            tokio::task::spawn_blocking(move || {
+                Self::control_playback_blocking(&addr, port, PlaybackCommand::Seek(position))
+            })
+            .await??;
+        }
 
         let input = UpdateCastSession {
             current_position: Some(position),
@@ -849,8 +1347,14 @@ impl CastService {
             volume = vol,
             "Cast set volume"
         );
-        tokio::task::spawn_blocking(move || Self::control_volume_blocking(&addr, port, vol, None))
+        if let Some(client) = self.airplay_client(&device)? {
+            client.set_volume(vol).await?;
+        } else {
+            tokio::task::spawn_blocking(move || {
+                Self::control_volume_blocking(&addr, port, vol, None)
+            })
             .await??;
+        }
 
         let input = UpdateCastSession {
             volume: Some(vol),
@@ -894,10 +1398,17 @@ impl CastService {
             muted = muted,
             "Cast set muted"
         );
-        tokio::task::spawn_blocking(move || {
-            Self::control_volume_blocking(&addr, port, 0.0, Some(muted))
-        })
-        .await??;
+        if let Some(client) = self.airplay_client(&device)? {
+            // AirPlay has no mute; drop to zero and restore the session volume after
+            client
+                .set_volume(if muted { 0.0 } else { session.volume })
+                .await?;
+        } else {
+            tokio::task::spawn_blocking(move || {
+                Self::control_volume_blocking(&addr, port, 0.0, Some(muted))
+            })
+            .await??;
+        }
 
         let input = UpdateCastSession {
             is_muted: Some(muted),
@@ -917,20 +1428,53 @@ impl CastService {
 
     /// Broadcast a session update event
     fn broadcast_session_update(&self, session: &CastSessionRecord) {
-        if let Some(device_id) = session.device_id {
-            let event = CastSessionEvent {
-                session_id: session.id,
-                device_id,
-                player_state: CastPlayerState::from_str(&session.player_state),
-                current_position: session.current_position,
-                duration: session.duration,
-                volume: session.volume,
-                is_muted: session.is_muted,
-            };
+        if let Some(event) = Self::session_event(session) {
             let _ = self.session_tx.send(event);
         }
     }
 
+    /// Build the subscription event for a session (None if it has no device)
+    fn session_event(session: &CastSessionRecord) -> Option<CastSessionEvent> {
+        let device_id = session.device_id?;
+        Some(CastSessionEvent {
+            session_id: session.id,
+            device_id,
+            player_state: CastPlayerState::from_str(&session.player_state),
+            current_position: session.current_position,
+            duration: session.duration,
+            volume: session.volume,
+            is_muted: session.is_muted,
+        })
+    }
+
+    /// Query the player state and position of whatever the device is playing
+    ///
+    /// Returns None when no app or media is loaded on the device.
+    fn media_state_blocking(
+        addr: &str,
+        port: u16,
+    ) -> Result<Option<(CastPlayerState, Option<f64>)>> {
+        let device = RustCastDevice::connect_without_host_verification(addr, port)
+            .context("Failed to connect to cast device")?;
+
+        device.connection.connect("receiver-0")?;
+
+        let status = device.receiver.get_status()?;
+        let Some(app) = status.applications.first() else {
+            return Ok(None);
+        };
+
+        device.connection.connect(&app.transport_id)?;
+
+        let media_status = device.media.get_status(&app.transport_id, None)?;
+        Ok(media_status.entries.first().map(|entry| {
+            (
+                CastPlayerState::from(&entry.player_state),
+                entry.current_time.map(|t| t as f64),
+            )
+        }))
+    }
+
     /// Control playback (blocking)
     fn control_playback_blocking(addr: &str, port: u16, command: PlaybackCommand) -> Result<()> {
         let device = RustCastDevice::connect_without_host_verification(addr, port)
@@ -1019,3 +1563,75 @@ enum PlaybackCommand {
     Stop,
     Seek(f64),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_model_detects_speaker_groups() {
+        let group = CastDeviceType::from_model("Google Cast Group");
+        assert_eq!(group, CastDeviceType::SpeakerGroup);
+        assert!(group.is_group());
+        assert!(group.is_audio_only());
+
+        assert_eq!(
+            CastDeviceType::from_model("Chromecast Ultra"),
+            CastDeviceType::Chromecast
+        );
+        assert!(!CastDeviceType::Chromecast.is_audio_only());
+        assert_eq!(
+            CastDeviceType::from("speaker_group"),
+            CastDeviceType::SpeakerGroup
+        );
+    }
+
+    #[test]
+    fn test_from_airplay_model() {
+        assert_eq!(
+            CastDeviceType::from_airplay_model("AppleTV6,2"),
+            CastDeviceType::AppleTv
+        );
+        let homepod = CastDeviceType::from_airplay_model("AudioAccessory5,1");
+        assert_eq!(homepod, CastDeviceType::HomePod);
+        assert!(homepod.is_airplay());
+        assert!(homepod.is_audio_only());
+        assert!(!CastDeviceType::SpeakerGroup.is_airplay());
+    }
+
+    #[test]
+    fn test_leader_lost_holds_playing_session() {
+        use CastPlayerState::*;
+        let lost = GroupMembershipEvent::LeaderLost;
+        assert_eq!(next_group_session_state(Playing, lost), Buffering);
+        assert_eq!(next_group_session_state(Buffering, lost), Buffering);
+        assert_eq!(next_group_session_state(Paused, lost), Paused);
+        assert_eq!(next_group_session_state(Idle, lost), Idle);
+    }
+
+    #[test]
+    fn test_leader_resolved_follows_new_leader() {
+        use CastPlayerState::*;
+        assert_eq!(
+            next_group_session_state(
+                Buffering,
+                GroupMembershipEvent::LeaderResolved(Some(Playing))
+            ),
+            Playing
+        );
+        assert_eq!(
+            next_group_session_state(Buffering, GroupMembershipEvent::LeaderResolved(None)),
+            Idle
+        );
+    }
+
+    #[test]
+    fn test_content_type_for_audiobooks() {
+        assert_eq!(CastService::get_content_type("/books/a.m4b"), "audio/mp4");
+        assert_eq!(CastService::get_content_type("/music/a.flac"), "audio/flac");
+        assert_eq!(
+            CastService::get_content_type("/tv/a.mkv"),
+            "video/x-matroska"
+        );
+    }
+}
@@ -0,0 +1,535 @@
+//! Demo data for `--seed-demo`
+//!
+//! Fills the database with a small made-up library — TV shows with episodes,
+//! movies and torrents — so the UI can be developed and screenshotted without
+//! real media or tracker accounts. Nothing is written to disk: media files and
+//! torrents exist only as records, and artwork is generated SVG placeholders.
+//! Seeding runs once; later starts with the flag leave the data alone.
+
+use anyhow::Result;
+use chrono::{Duration, NaiveDate, Utc};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::{
+    CreateEpisode, CreateLibrary, CreateMediaFile, CreateMovie, CreateTorrent, CreateTvShow,
+    CreateUser, Database,
+};
+
+/// Setting recording that demo data has been seeded
+pub const DEMO_SEEDED_KEY: &str = "demo.seeded";
+
+/// Login created when the database has no users yet
+pub const DEMO_USERNAME: &str = "demo";
+pub const DEMO_PASSWORD: &str = "demo";
+
+const TV_PATH: &str = "/demo/tv";
+const MOVIES_PATH: &str = "/demo/movies";
+const DOWNLOADS_PATH: &str = "/demo/downloads";
+
+struct DemoShow {
+    name: &'static str,
+    year: i32,
+    status: &'static str,
+    network: &'static str,
+    genres: &'static [&'static str],
+    overview: &'static str,
+    /// Episodes per season
+    seasons: &'static [i32],
+    /// Seasons (from the first) whose episodes have files
+    downloaded_seasons: usize,
+}
+
+struct DemoMovie {
+    title: &'static str,
+    year: i32,
+    runtime: i32,
+    genres: &'static [&'static str],
+    director: &'static str,
+    overview: &'static str,
+    downloaded: bool,
+}
+
+struct DemoTorrent {
+    name: &'static str,
+    size_bytes: i64,
+    progress: f64,
+    state: &'static str,
+}
+
+const SHOWS: &[DemoShow] = &[
+    DemoShow {
+        name: "The Lighthouse Keepers",
+        year: 2019,
+        status: "ended",
+        network: "Northwind",
+        genres: &["Drama", "Mystery"],
+        overview: "Three generations of keepers guard a lighthouse that seems to guide more than ships.",
+        seasons: &[8, 8, 10],
+        downloaded_seasons: 3,
+    },
+    DemoShow {
+        name: "Quantum Kitchen",
+        year: 2022,
+        status: "continuing",
+        network: "Tasty+",
+        genres: &["Comedy", "Reality"],
+        overview: "Chefs compete with ingredients that only exist in one dimension at a time.",
+        seasons: &[10, 10],
+        downloaded_seasons: 1,
+    },
+    DemoShow {
+        name: "Northbound",
+        year: 2024,
+        status: "continuing",
+        network: "Atlas",
+        genres: &["Adventure", "Science Fiction"],
+        overview: "A convoy of strangers drives the last open road to the arctic colonies.",
+        seasons: &[6],
+        downloaded_seasons: 0,
+    },
+];
+
+const MOVIES: &[DemoMovie] = &[
+    DemoMovie {
+        title: "Paper Satellites",
+        year: 2021,
+        runtime: 112,
+        genres: &["Drama", "Science Fiction"],
+        director: "Ines Marlow",
+        overview: "A retired engineer builds a satellite out of paper to reach her estranged son.",
+        downloaded: true,
+    },
+    DemoMovie {
+        title: "The Long Quiet",
+        year: 2018,
+        runtime: 97,
+        genres: &["Thriller"],
+        director: "Tomas Reyes",
+        overview: "A radio operator on a remote island stops hearing from the mainland.",
+        downloaded: true,
+    },
+    DemoMovie {
+        title: "Midnight Cartography",
+        year: 2023,
+        runtime: 128,
+        genres: &["Adventure", "Fantasy"],
+        director: "Ada Whitcombe",
+        overview: "A mapmaker discovers the streets of her city rearrange themselves at night.",
+        downloaded: false,
+    },
+    DemoMovie {
+        title: "Glass Harbor",
+        year: 2025,
+        runtime: 104,
+        genres: &["Crime", "Drama"],
+        director: "Jun Park",
+        overview: "A harbor inspector uncovers a smuggling ring hiding in plain sight.",
+        downloaded: false,
+    },
+];
+
+const TORRENTS: &[DemoTorrent] = &[
+    DemoTorrent {
+        name: "Quantum.Kitchen.S02.1080p.WEB-DL.DDP5.1.H.264-DEMO",
+        size_bytes: 14_500_000_000,
+        progress: 0.42,
+        state: "downloading",
+    },
+    DemoTorrent {
+        name: "Midnight.Cartography.2023.2160p.UHD.BluRay.x265-DEMO",
+        size_bytes: 31_200_000_000,
+        progress: 0.0,
+        state: "queued",
+    },
+    DemoTorrent {
+        name: "The.Long.Quiet.2018.1080p.BluRay.x264-DEMO",
+        size_bytes: 9_800_000_000,
+        progress: 1.0,
+        state: "seeding",
+    },
+    DemoTorrent {
+        name: "Northbound.S01E01.720p.HDTV.x264-DEMO",
+        size_bytes: 1_100_000_000,
+        progress: 0.87,
+        state: "paused",
+    },
+];
+
+/// Seed the demo library, unless it has been seeded before
+pub async fn seed_demo(db: &Database) -> Result<()> {
+    if db
+        .settings()
+        .get_value::<bool>(DEMO_SEEDED_KEY)
+        .await?
+        .unwrap_or(false)
+    {
+        info!("Demo data already seeded, skipping");
+        return Ok(());
+    }
+
+    let user_id = demo_owner(db).await?;
+    seed_tv(db, user_id).await?;
+    seed_movies(db, user_id).await?;
+    seed_torrents(db, user_id).await?;
+
+    db.settings()
+        .set_with_category(
+            DEMO_SEEDED_KEY,
+            true,
+            "system",
+            Some("Demo data was seeded with --seed-demo"),
+        )
+        .await?;
+    info!(
+        shows = SHOWS.len(),
+        movies = MOVIES.len(),
+        torrents = TORRENTS.len(),
+        "Seeded demo data"
+    );
+    Ok(())
+}
+
+/// Owner for the demo libraries: the first admin, or a new demo login if the
+/// database has no users
+async fn demo_owner(db: &Database) -> Result<Uuid> {
+    let users = db.users().list_all().await?;
+    if let Some(user) = users
+        .iter()
+        .find(|u| u.role == "admin")
+        .or_else(|| users.first())
+    {
+        return Ok(Uuid::parse_str(&user.id)?);
+    }
+
+    let user = db
+        .users()
+        .create(CreateUser {
+            username: DEMO_USERNAME.to_string(),
+            email: None,
+            password_hash: bcrypt::hash(DEMO_PASSWORD, bcrypt::DEFAULT_COST)?,
+            role: "admin".to_string(),
+            display_name: Some("Demo".to_string()),
+        })
+        .await?;
+    warn!(
+        username = DEMO_USERNAME,
+        password = DEMO_PASSWORD,
+        "Created demo admin login; don't use --seed-demo on a server others can reach"
+    );
+    Ok(Uuid::parse_str(&user.id)?)
+}
+
+async fn seed_tv(db: &Database, user_id: Uuid) -> Result<()> {
+    let library = db
+        .libraries()
+        .create(demo_library(user_id, "Demo TV", TV_PATH, "tv"))
+        .await?;
+
+    for (i, show) in SHOWS.iter().enumerate() {
+        let record = db
+            .tv_shows()
+            .create(CreateTvShow {
+                library_id: library.id,
+                user_id,
+                name: show.name.to_string(),
+                sort_name: None,
+                year: Some(show.year),
+                status: Some(show.status.to_string()),
+                tvmaze_id: None,
+                tmdb_id: None,
+                tvdb_id: None,
+                imdb_id: None,
+                overview: Some(show.overview.to_string()),
+                network: Some(show.network.to_string()),
+                runtime: Some(45),
+                genres: show.genres.iter().map(|g| g.to_string()).collect(),
+                poster_url: Some(placeholder_art(show.name, i, 2, 3)),
+                backdrop_url: Some(placeholder_art(show.name, i, 16, 9)),
+                monitored: true,
+                monitor_type: "all".to_string(),
+                path: Some(format!("{}/{}", TV_PATH, show.name)),
+                auto_download_override: None,
+                backfill_existing: true,
+                organize_files_override: None,
+                rename_style_override: None,
+                auto_hunt_override: None,
+                allowed_resolutions_override: None,
+                allowed_video_codecs_override: None,
+                allowed_audio_formats_override: None,
+                require_hdr_override: None,
+                allowed_hdr_types_override: None,
+                allowed_sources_override: None,
+                release_group_blacklist_override: None,
+                release_group_whitelist_override: None,
+            })
+            .await?;
+
+        let first_aired = NaiveDate::from_ymd_opt(show.year, 1, 10).expect("valid demo date");
+        for (season_index, &episode_count) in show.seasons.iter().enumerate() {
+            let season = season_index as i32 + 1;
+            for episode in 1..=episode_count {
+                let air_date = episode_air_date(first_aired, season_index as i32, episode);
+                let record_episode = db
+                    .episodes()
+                    .create(CreateEpisode {
+                        tv_show_id: record.id,
+                        season,
+                        episode,
+                        absolute_number: None,
+                        title: Some(format!("Chapter {}", episode)),
+                        overview: None,
+                        air_date: Some(air_date),
+                        runtime: Some(45),
+                        tvmaze_id: None,
+                        tmdb_id: None,
+                        tvdb_id: None,
+                    })
+                    .await?;
+
+                if season_index >= show.downloaded_seasons || air_date > Utc::now().date_naive() {
+                    continue;
+                }
+                let file_name = format!("{} - S{:02}E{:02}.mkv", show.name, season, episode);
+                let file = db
+                    .media_files()
+                    .create(demo_file(
+                        library.id,
+                        format!(
+                            "{}/{}/Season {:02}/{}",
+                            TV_PATH, show.name, season, file_name
+                        ),
+                        1_400_000_000,
+                        Some(record_episode.id),
+                        None,
+                    ))
+                    .await?;
+                db.episodes()
+                    .mark_downloaded(record_episode.id, file.id)
+                    .await?;
+            }
+        }
+        db.tv_shows().update_stats(record.id).await?;
+    }
+    Ok(())
+}
+
+async fn seed_movies(db: &Database, user_id: Uuid) -> Result<()> {
+    let library = db
+        .libraries()
+        .create(demo_library(user_id, "Demo Movies", MOVIES_PATH, "movies"))
+        .await?;
+
+    for (i, movie) in MOVIES.iter().enumerate() {
+        let record = db
+            .movies()
+            .create(CreateMovie {
+                library_id: library.id,
+                user_id,
+                title: movie.title.to_string(),
+                sort_title: None,
+                original_title: None,
+                year: Some(movie.year),
+                tmdb_id: None,
+                imdb_id: None,
+                overview: Some(movie.overview.to_string()),
+                tagline: None,
+                runtime: Some(movie.runtime),
+                genres: movie.genres.iter().map(|g| g.to_string()).collect(),
+                production_countries: Vec::new(),
+                spoken_languages: vec!["English".to_string()],
+                director: Some(movie.director.to_string()),
+                cast_names: Vec::new(),
+                tmdb_rating: None,
+                tmdb_vote_count: None,
+                poster_url: Some(placeholder_art(movie.title, SHOWS.len() + i, 2, 3)),
+                backdrop_url: Some(placeholder_art(movie.title, SHOWS.len() + i, 16, 9)),
+                collection_id: None,
+                collection_name: None,
+                collection_poster_url: None,
+                release_date: NaiveDate::from_ymd_opt(movie.year, 6, 1),
+                certification: None,
+                status: Some("released".to_string()),
+                monitored: true,
+            })
+            .await?;
+
+        if !movie.downloaded {
+            continue;
+        }
+        let folder = format!("{} ({})", movie.title, movie.year);
+        let file = db
+            .media_files()
+            .create(demo_file(
+                library.id,
+                format!("{}/{}/{}.mkv", MOVIES_PATH, folder, folder),
+                8_500_000_000,
+                None,
+                Some(record.id),
+            ))
+            .await?;
+        db.movies().set_media_file(record.id, file.id).await?;
+    }
+    Ok(())
+}
+
+/// Torrents are records only, without a magnet link, so the session never
+/// tries to resume them
+async fn seed_torrents(db: &Database, user_id: Uuid) -> Result<()> {
+    let torrents = db.torrents();
+    for torrent in TORRENTS {
+        let info_hash = demo_info_hash(torrent.name);
+        torrents
+            .create(CreateTorrent {
+                user_id,
+                info_hash: info_hash.clone(),
+                magnet_uri: None,
+                name: torrent.name.to_string(),
+                save_path: DOWNLOADS_PATH.to_string(),
+                total_bytes: torrent.size_bytes,
+            })
+            .await?;
+        let downloaded = (torrent.size_bytes as f64 * torrent.progress) as i64;
+        torrents
+            .update_progress(&info_hash, torrent.state, torrent.progress, downloaded, 0)
+            .await?;
+        if torrent.state == "seeding" {
+            // Nothing on disk to import
+            torrents.mark_processed(&info_hash).await?;
+        }
+    }
+    Ok(())
+}
+
+fn demo_library(user_id: Uuid, name: &str, path: &str, library_type: &str) -> CreateLibrary {
+    CreateLibrary {
+        user_id,
+        name: name.to_string(),
+        path: path.to_string(),
+        library_type: library_type.to_string(),
+        icon: None,
+        color: None,
+        // The paths don't exist, so nothing should scan, watch or hunt them
+        auto_scan: false,
+        scan_interval_minutes: 60,
+        watch_for_changes: false,
+        post_download_action: "copy".to_string(),
+        organize_files: false,
+        rename_style: "none".to_string(),
+        naming_pattern: None,
+        auto_add_discovered: false,
+        auto_download: false,
+        auto_hunt: false,
+        allowed_resolutions: Vec::new(),
+        allowed_video_codecs: Vec::new(),
+        allowed_audio_formats: Vec::new(),
+        require_hdr: false,
+        allowed_hdr_types: Vec::new(),
+        allowed_sources: Vec::new(),
+        release_group_blacklist: Vec::new(),
+        release_group_whitelist: Vec::new(),
+        exclude_patterns: Vec::new(),
+        file_uid: None,
+        file_gid: None,
+        file_mode: None,
+        missing_file_policy: "flag".to_string(),
+    }
+}
+
+fn demo_file(
+    library_id: Uuid,
+    path: String,
+    size_bytes: i64,
+    episode_id: Option<Uuid>,
+    movie_id: Option<Uuid>,
+) -> CreateMediaFile {
+    CreateMediaFile {
+        library_id,
+        original_name: path.rsplit('/').next().map(String::from),
+        path,
+        size_bytes,
+        container: Some("mkv".to_string()),
+        video_codec: Some("h264".to_string()),
+        audio_codec: Some("aac".to_string()),
+        width: Some(1920),
+        height: Some(1080),
+        resolution: Some("1080p".to_string()),
+        is_hdr: Some(false),
+        episode_id,
+        movie_id,
+        ..Default::default()
+    }
+}
+
+/// Weekly air dates, with each season starting a year after the previous one
+fn episode_air_date(first_aired: NaiveDate, season_index: i32, episode: i32) -> NaiveDate {
+    first_aired + Duration::days(365 * season_index as i64 + 7 * (episode as i64 - 1))
+}
+
+/// Stable fake info hash (40 hex characters) for a torrent name
+pub fn demo_info_hash(name: &str) -> String {
+    let hash = format!(
+        "{:x}",
+        Sha256::digest(format!("librarian-demo:{}", name).as_bytes())
+    );
+    hash[..40].to_string()
+}
+
+/// Placeholder artwork: an SVG data URL with the title on a colored
+/// background (`width`:`height` is the aspect ratio)
+pub fn placeholder_art(title: &str, index: usize, width: u32, height: u32) -> String {
+    const HUES: [u32; 7] = [210, 340, 160, 30, 270, 190, 0];
+    let hue = HUES[index % HUES.len()];
+    let title = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    let svg = format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 {w} {h}'>\
+         <rect width='{w}' height='{h}' fill='hsl({hue},45%,30%)'/>\
+         <text x='50%' y='50%' fill='white' font-family='sans-serif' font-size='{size}' \
+         text-anchor='middle' dominant-baseline='middle'>{title}</text></svg>",
+        w = width * 100,
+        h = height * 100,
+        size = width * 100 / 12,
+    );
+    format!("data:image/svg+xml,{}", urlencoding::encode(&svg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_info_hash() {
+        let hash = demo_info_hash("Some.Release-DEMO");
+        assert_eq!(hash.len(), 40);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(hash, demo_info_hash("Some.Release-DEMO"));
+        assert_ne!(hash, demo_info_hash("Other.Release-DEMO"));
+    }
+
+    #[test]
+    fn test_placeholder_art_escapes_title() {
+        let url = placeholder_art("Fish & <Chips>", 0, 2, 3);
+        assert!(url.starts_with("data:image/svg+xml,"));
+        let svg = urlencoding::decode(url.trim_start_matches("data:image/svg+xml,")).unwrap();
+        assert!(svg.contains("Fish &amp; &lt;Chips&gt;"));
+        assert!(svg.contains("viewBox='0 0 200 300'"));
+    }
+
+    #[test]
+    fn test_episode_air_date() {
+        let first = NaiveDate::from_ymd_opt(2020, 1, 10).unwrap();
+        assert_eq!(episode_air_date(first, 0, 1), first);
+        assert_eq!(
+            episode_air_date(first, 0, 3),
+            NaiveDate::from_ymd_opt(2020, 1, 24).unwrap()
+        );
+        assert_eq!(
+            episode_air_date(first, 1, 1),
+            NaiveDate::from_ymd_opt(2021, 1, 9).unwrap()
+        );
+    }
+}
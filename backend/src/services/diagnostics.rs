@@ -0,0 +1,662 @@
+//! Environment diagnostics
+//!
+//! Checks the parts of the environment that most often break an install:
+//! writable paths, ffmpeg/ffprobe, the database journal mode, free disk
+//! space, whether the torrent listen port is reachable and clock skew. Each
+//! check reports pass, warn or fail with a hint on how to fix it. The
+//! external services are included as the service health prober last saw
+//! them, rather than probed again.
+//!
+//! The checks run once at startup (problems are logged) and on demand
+//! through the `diagnostics` query on the system settings page.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sysinfo::Disks;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use crate::db::{Database, ExternalServiceStatusRecord};
+use crate::services::TorrentService;
+use crate::services::metrics::format_bytes_short;
+use crate::services::service_health::ServiceStatus;
+
+/// Free space below this fails the check
+const FREE_SPACE_FAIL_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Free space below this warns
+const FREE_SPACE_WARN_BYTES: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Clock skew up to this many seconds passes
+const CLOCK_SKEW_WARN_SECS: i64 = 30;
+
+/// Clock skew beyond this many seconds fails
+const CLOCK_SKEW_FAIL_SECS: i64 = 300;
+
+/// Server whose Date header the local clock is compared against
+const CLOCK_REFERENCE_URL: &str = "https://api.github.com";
+
+/// How long to wait for ffmpeg/ffprobe or a remote server to answer
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a single check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+/// Result of a single check
+#[derive(Debug, Clone)]
+pub struct DiagnosticCheck {
+    /// Stable identifier, e.g. "path.downloads" or "ffmpeg"
+    pub id: String,
+    pub title: String,
+    pub status: DiagnosticStatus,
+    pub message: String,
+    /// How to fix a warning or failure
+    pub hint: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(id: impl Into<String>, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status: DiagnosticStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn problem(
+        status: DiagnosticStatus,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            status,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// A directory the server needs to write to
+struct CheckedPath {
+    id: String,
+    title: String,
+    path: PathBuf,
+    /// Status to report when the path isn't writable
+    severity: DiagnosticStatus,
+}
+
+/// Runs the environment checks
+pub struct DiagnosticsService {
+    db: Database,
+    torrent_service: Arc<TorrentService>,
+    cache_root: PathBuf,
+}
+
+impl DiagnosticsService {
+    pub fn new(db: Database, torrent_service: Arc<TorrentService>, cache_root: PathBuf) -> Self {
+        Self {
+            db,
+            torrent_service,
+            cache_root,
+        }
+    }
+
+    /// Run every check
+    pub async fn run(&self) -> Vec<DiagnosticCheck> {
+        let paths = self.checked_paths().await;
+
+        let mut checks = Vec::new();
+        for path in &paths {
+            checks.push(check_writable(path).await);
+        }
+        checks.push(check_binary("ffmpeg").await);
+        checks.push(check_binary("ffprobe").await);
+        checks.push(self.check_journal_mode().await);
+        checks.extend(check_free_space(&paths));
+
+        // The network checks wait on remote servers, so run them together
+        let (port, clock) = tokio::join!(self.check_listen_port(), check_clock_skew());
+        checks.push(port);
+        checks.push(clock);
+        checks.extend(self.check_external_services().await);
+
+        checks
+    }
+
+    /// Run every check and log the ones that didn't pass
+    pub async fn log_problems(&self) {
+        let checks = self.run().await;
+        let mut problems = 0;
+        for check in checks.iter().filter(|c| c.status != DiagnosticStatus::Pass) {
+            problems += 1;
+            let hint = check.hint.as_deref().unwrap_or("");
+            if check.status == DiagnosticStatus::Fail {
+                error!(check = %check.id, hint, "Self-check failed: {}", check.message);
+            } else {
+                warn!(check = %check.id, hint, "Self-check warning: {}", check.message);
+            }
+        }
+        info!(
+            checks = checks.len(),
+            problems, "Startup self-check completed"
+        );
+    }
+
+    /// Downloads, transcode cache and every library path
+    async fn checked_paths(&self) -> Vec<CheckedPath> {
+        let mut paths = vec![
+            CheckedPath {
+                id: "path.downloads".to_string(),
+                title: "Download folder".to_string(),
+                path: self.torrent_service.download_dir().clone(),
+                severity: DiagnosticStatus::Fail,
+            },
+            CheckedPath {
+                id: "path.cache".to_string(),
+                title: "Transcode cache".to_string(),
+                path: self.cache_root.clone(),
+                severity: DiagnosticStatus::Fail,
+            },
+        ];
+
+        // Organizing into a library that can't be written is a problem, but
+        // a read-only library can still be scanned and played
+        match self.db.libraries().list_paths().await {
+            Ok(libraries) => paths.extend(libraries.into_iter().map(|(name, path)| CheckedPath {
+                id: format!("path.library.{}", name),
+                title: format!("Library \"{}\"", name),
+                path: PathBuf::from(path),
+                severity: DiagnosticStatus::Warn,
+            })),
+            Err(e) => warn!(error = %e, "Failed to list library paths for diagnostics"),
+        }
+
+        paths
+    }
+
+    async fn check_journal_mode(&self) -> DiagnosticCheck {
+        const ID: &str = "database.journal_mode";
+        const TITLE: &str = "Database journal mode";
+
+        match sqlx::query_scalar::<_, String>("PRAGMA journal_mode")
+            .fetch_one(self.db.pool())
+            .await
+        {
+            Ok(mode) if mode.eq_ignore_ascii_case("wal") => {
+                DiagnosticCheck::pass(ID, TITLE, "Write-ahead logging (WAL) is enabled")
+            }
+            Ok(mode) => DiagnosticCheck::problem(
+                DiagnosticStatus::Warn,
+                ID,
+                TITLE,
+                format!("Journal mode is '{}' rather than WAL", mode),
+                "WAL lets scans and playback read while downloads write. It can't be \
+                 enabled on network filesystems such as NFS or SMB, so keep the \
+                 database on a local disk.",
+            ),
+            Err(e) => DiagnosticCheck::problem(
+                DiagnosticStatus::Fail,
+                ID,
+                TITLE,
+                format!("Couldn't read the journal mode: {}", e),
+                "Check that the database file is readable and not corrupted.",
+            ),
+        }
+    }
+
+    async fn check_external_services(&self) -> Vec<DiagnosticCheck> {
+        match self.db.external_services().list().await {
+            Ok(services) => services.iter().map(check_external_service).collect(),
+            Err(e) => {
+                warn!(error = %e, "Failed to list external service status for diagnostics");
+                Vec::new()
+            }
+        }
+    }
+
+    async fn check_listen_port(&self) -> DiagnosticCheck {
+        const ID: &str = "torrent.listen_port";
+        const TITLE: &str = "Torrent listen port";
+
+        let port = self.torrent_service.listen_port();
+        if port == 0 {
+            return DiagnosticCheck::problem(
+                DiagnosticStatus::Warn,
+                ID,
+                TITLE,
+                "No listen port is configured",
+                "Set a listen port in Settings → Torrent Client so peers can connect to you.",
+            );
+        }
+
+        let forward_hint = format!(
+            "Forward port {} (TCP and UDP) to this machine in your router, or enable \
+             UPnP. Without it downloads may be slower and some peers unreachable.",
+            port
+        );
+        match self.torrent_service.test_port_accessibility(port).await {
+            Ok(result) if result.port_open => DiagnosticCheck::pass(
+                ID,
+                TITLE,
+                format!(
+                    "Port {} is reachable at {}",
+                    port,
+                    result.external_ip.as_deref().unwrap_or("your external IP")
+                ),
+            ),
+            Ok(result) if result.success => DiagnosticCheck::problem(
+                DiagnosticStatus::Warn,
+                ID,
+                TITLE,
+                format!("Port {} isn't reachable from the internet", port),
+                forward_hint,
+            ),
+            Ok(result) => DiagnosticCheck::problem(
+                DiagnosticStatus::Warn,
+                ID,
+                TITLE,
+                format!(
+                    "Couldn't test port {}: {}",
+                    port,
+                    result.error.as_deref().unwrap_or("unknown error")
+                ),
+                forward_hint,
+            ),
+            Err(e) => DiagnosticCheck::problem(
+                DiagnosticStatus::Warn,
+                ID,
+                TITLE,
+                format!("Couldn't test port {}: {}", port, e),
+                forward_hint,
+            ),
+        }
+    }
+}
+
+/// Write and remove a probe file in the directory
+async fn check_writable(path: &CheckedPath) -> DiagnosticCheck {
+    let display = path.path.display();
+    if !path.path.is_dir() {
+        return DiagnosticCheck::problem(
+            path.severity,
+            &path.id,
+            &path.title,
+            format!("{} doesn't exist or isn't a directory", display),
+            "Create the directory or point the setting at one that exists.",
+        );
+    }
+
+    let probe = path
+        .path
+        .join(format!(".librarian-write-test-{}", uuid::Uuid::new_v4()));
+    match tokio::fs::write(&probe, b"").await {
+        Ok(()) => {
+            let _ = tokio::fs::remove_file(&probe).await;
+            DiagnosticCheck::pass(&path.id, &path.title, format!("{} is writable", display))
+        }
+        Err(e) => DiagnosticCheck::problem(
+            path.severity,
+            &path.id,
+            &path.title,
+            format!("{} isn't writable: {}", display, e),
+            "Give the user Librarian runs as write access to the directory. In \
+             Docker, check the volume mount and the PUID/PGID of the container.",
+        ),
+    }
+}
+
+/// Run `<program> -version` and report the version it prints
+async fn check_binary(program: &str) -> DiagnosticCheck {
+    let hint = format!(
+        "Install {} (it ships with FFmpeg) and make sure it is on the PATH of the \
+         Librarian process.",
+        program
+    );
+    let output = tokio::time::timeout(
+        CHECK_TIMEOUT,
+        Command::new(program)
+            .arg("-version")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+
+    match output {
+        Ok(Ok(output)) if output.status.success() => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let version =
+                parse_version_line(&stdout, program).unwrap_or_else(|| "unknown".to_string());
+            DiagnosticCheck::pass(program, program, format!("{} version {}", program, version))
+        }
+        Ok(Ok(output)) => DiagnosticCheck::problem(
+            DiagnosticStatus::Fail,
+            program,
+            program,
+            format!("{} -version exited with {}", program, output.status),
+            hint,
+        ),
+        Ok(Err(e)) => DiagnosticCheck::problem(
+            DiagnosticStatus::Fail,
+            program,
+            program,
+            format!("Couldn't run {}: {}", program, e),
+            hint,
+        ),
+        Err(_) => DiagnosticCheck::problem(
+            DiagnosticStatus::Fail,
+            program,
+            program,
+            format!("{} -version didn't finish", program),
+            hint,
+        ),
+    }
+}
+
+/// One check per disk holding a checked path
+fn check_free_space(paths: &[CheckedPath]) -> Vec<DiagnosticCheck> {
+    let disks = Disks::new_with_refreshed_list();
+    let mounts: Vec<PathBuf> = disks
+        .list()
+        .iter()
+        .map(|d| d.mount_point().to_path_buf())
+        .collect();
+
+    // Several paths usually share a disk, which should only be reported once
+    let mut checks: Vec<DiagnosticCheck> = Vec::new();
+    let mut seen: Vec<usize> = Vec::new();
+    for path in paths {
+        let resolved = path
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| path.path.clone());
+        let Some(index) = mount_point_for(&resolved, &mounts) else {
+            continue;
+        };
+        if seen.contains(&index) {
+            continue;
+        }
+        seen.push(index);
+
+        let available = disks.list()[index].available_space();
+        let mount = mounts[index].display();
+        let id = format!("disk.{}", mount);
+        let title = format!("Free space on {}", mount);
+        let message = format!(
+            "{} free on {} (holds the {})",
+            format_bytes_short(available),
+            mount,
+            path.title.to_lowercase()
+        );
+        checks.push(match space_status(available) {
+            DiagnosticStatus::Pass => DiagnosticCheck::pass(id, title, message),
+            status => DiagnosticCheck::problem(
+                status,
+                id,
+                title,
+                message,
+                "Free up space or move downloads and the transcode cache to a \
+                 larger disk. Downloads and transcodes fail once the disk is full.",
+            ),
+        });
+    }
+
+    checks
+}
+
+async fn check_clock_skew() -> DiagnosticCheck {
+    const ID: &str = "clock";
+    const TITLE: &str = "System clock";
+    let unchecked = |message: String| {
+        DiagnosticCheck::problem(
+            DiagnosticStatus::Warn,
+            ID,
+            TITLE,
+            message,
+            "Check that this machine can reach the internet over HTTPS.",
+        )
+    };
+
+    let client = match reqwest::Client::builder().timeout(CHECK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => return unchecked(format!("Couldn't check the clock: {}", e)),
+    };
+
+    let sent = Utc::now();
+    let response = match client
+        .head(CLOCK_REFERENCE_URL)
+        .header(reqwest::header::USER_AGENT, "Librarian")
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return unchecked(format!("Couldn't check the clock: {}", e)),
+    };
+    let received = Utc::now();
+
+    let Some(server_time) = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| DateTime::parse_from_rfc2822(v).ok())
+    else {
+        return unchecked("The reference server didn't send its time".to_string());
+    };
+
+    // Compare against the middle of the round trip
+    let local_time = sent + (received - sent) / 2;
+    let skew = (local_time - server_time.with_timezone(&Utc)).num_seconds();
+    let direction = if skew >= 0 { "ahead" } else { "behind" };
+    let message = format!(
+        "Clock is {}s {} of {}",
+        skew.abs(),
+        direction,
+        CLOCK_REFERENCE_URL
+    );
+
+    match clock_status(skew) {
+        DiagnosticStatus::Pass => DiagnosticCheck::pass(ID, TITLE, message),
+        status => DiagnosticCheck::problem(
+            status,
+            ID,
+            TITLE,
+            message,
+            "Enable time synchronisation (NTP), e.g. `timedatectl set-ntp true`. A \
+             wrong clock breaks TLS certificates, tracker announces and RSS timing.",
+        ),
+    }
+}
+
+/// Version from the first line of `-version` output, e.g. "6.1.1" from
+/// "ffmpeg version 6.1.1 Copyright (c) 2000-2023 the FFmpeg developers"
+fn parse_version_line(output: &str, program: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .trim()
+        .strip_prefix(program)?
+        .trim_start()
+        .strip_prefix("version")?
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+}
+
+/// Index of the mount point the path lives on (the longest matching one)
+fn mount_point_for(path: &Path, mounts: &[PathBuf]) -> Option<usize> {
+    mounts
+        .iter()
+        .enumerate()
+        .filter(|(_, mount)| path.starts_with(mount))
+        .max_by_key(|(_, mount)| mount.components().count())
+        .map(|(index, _)| index)
+}
+
+fn space_status(available: u64) -> DiagnosticStatus {
+    if available < FREE_SPACE_FAIL_BYTES {
+        DiagnosticStatus::Fail
+    } else if available < FREE_SPACE_WARN_BYTES {
+        DiagnosticStatus::Warn
+    } else {
+        DiagnosticStatus::Pass
+    }
+}
+
+fn check_external_service(service: &ExternalServiceStatusRecord) -> DiagnosticCheck {
+    let id = format!("service.{}", service.id);
+    let title = match service.kind.as_str() {
+        "indexer" => format!("Indexer \"{}\"", service.name),
+        "usenet" => format!("Usenet server \"{}\"", service.name),
+        "cast" => format!("Cast device \"{}\"", service.name),
+        _ => service.name.clone(),
+    };
+    let latency = service.latency_ms.unwrap_or_default();
+
+    match ServiceStatus::parse(&service.status) {
+        Some(ServiceStatus::Up) => {
+            DiagnosticCheck::pass(id, title, format!("Answered in {} ms", latency))
+        }
+        Some(ServiceStatus::Degraded) => DiagnosticCheck::problem(
+            DiagnosticStatus::Warn,
+            id,
+            title,
+            format!("Slow to answer ({} ms)", latency),
+            "The service may be overloaded. If several services are slow, check this \
+             server's internet connection.",
+        ),
+        _ => DiagnosticCheck::problem(
+            DiagnosticStatus::Fail,
+            id,
+            title,
+            service
+                .last_error
+                .clone()
+                .unwrap_or_else(|| "Unreachable".to_string()),
+            external_service_hint(&service.kind),
+        ),
+    }
+}
+
+fn external_service_hint(kind: &str) -> &'static str {
+    match kind {
+        "tmdb" | "tvdb" => {
+            "Check the API key in Settings → Metadata and that this server can reach \
+             the internet."
+        }
+        "opensubtitles" => {
+            "Check the API key in Settings → Subtitles and that this server can reach \
+             the internet."
+        }
+        "indexer" => {
+            "The site may be down or the credentials expired. Test the indexer in \
+             Settings → Indexers."
+        }
+        "usenet" => "Check the host, port, SSL and credentials in Settings → Usenet.",
+        "smtp" => "Check the SMTP_* environment variables.",
+        "cast" => "Check the device is switched on and on the same network.",
+        _ => "Check the service is running and reachable from this server.",
+    }
+}
+
+fn clock_status(skew_secs: i64) -> DiagnosticStatus {
+    match skew_secs.abs() {
+        s if s <= CLOCK_SKEW_WARN_SECS => DiagnosticStatus::Pass,
+        s if s <= CLOCK_SKEW_FAIL_SECS => DiagnosticStatus::Warn,
+        _ => DiagnosticStatus::Fail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_line() {
+        let ffmpeg = "ffmpeg version 6.1.1-3ubuntu5 Copyright (c) 2000-2023 the FFmpeg developers\nbuilt with gcc 13";
+        assert_eq!(
+            parse_version_line(ffmpeg, "ffmpeg").as_deref(),
+            Some("6.1.1-3ubuntu5")
+        );
+        let ffprobe = "ffprobe version n7.0 Copyright (c) 2007-2024 the FFmpeg developers";
+        assert_eq!(
+            parse_version_line(ffprobe, "ffprobe").as_deref(),
+            Some("n7.0")
+        );
+        assert_eq!(parse_version_line(ffprobe, "ffmpeg"), None);
+        assert_eq!(parse_version_line("", "ffmpeg"), None);
+    }
+
+    #[test]
+    fn test_mount_point_for_picks_longest_match() {
+        let mounts = vec![
+            PathBuf::from("/"),
+            PathBuf::from("/mnt/media"),
+            PathBuf::from("/mnt/media-archive"),
+        ];
+        assert_eq!(
+            mount_point_for(Path::new("/mnt/media/tv"), &mounts),
+            Some(1)
+        );
+        assert_eq!(
+            mount_point_for(Path::new("/mnt/media-archive/films"), &mounts),
+            Some(2)
+        );
+        assert_eq!(mount_point_for(Path::new("/var/lib"), &mounts), Some(0));
+        assert_eq!(mount_point_for(Path::new("/var/lib"), &mounts[1..]), None);
+    }
+
+    #[test]
+    fn test_space_and_clock_status() {
+        const GIB: u64 = 1024 * 1024 * 1024;
+        assert_eq!(space_status(GIB / 2), DiagnosticStatus::Fail);
+        assert_eq!(space_status(5 * GIB), DiagnosticStatus::Warn);
+        assert_eq!(space_status(50 * GIB), DiagnosticStatus::Pass);
+
+        assert_eq!(clock_status(-12), DiagnosticStatus::Pass);
+        assert_eq!(clock_status(120), DiagnosticStatus::Warn);
+        assert_eq!(clock_status(-3600), DiagnosticStatus::Fail);
+    }
+
+    #[test]
+    fn test_check_external_service() {
+        let mut service = ExternalServiceStatusRecord {
+            id: "indexer:1".to_string(),
+            kind: "indexer".to_string(),
+            name: "Example".to_string(),
+            status: "up".to_string(),
+            latency_ms: Some(180),
+            last_error: None,
+            last_checked_at: Utc::now(),
+            last_changed_at: Utc::now(),
+        };
+        let check = check_external_service(&service);
+        assert_eq!(check.id, "service.indexer:1");
+        assert_eq!(check.title, "Indexer \"Example\"");
+        assert_eq!(check.status, DiagnosticStatus::Pass);
+
+        service.status = "degraded".to_string();
+        assert_eq!(
+            check_external_service(&service).status,
+            DiagnosticStatus::Warn
+        );
+
+        service.status = "down".to_string();
+        service.last_error = Some("Connection refused".to_string());
+        let check = check_external_service(&service);
+        assert_eq!(check.status, DiagnosticStatus::Fail);
+        assert_eq!(check.message, "Connection refused");
+    }
+}
@@ -0,0 +1,397 @@
+//! DVD and Blu-ray folder rips
+//!
+//! A disc copied to disk keeps its structure: a movie folder holding
+//! `VIDEO_TS` (DVD) or `BDMV` (Blu-ray). The video is spread over many files
+//! (menus, extras and the film itself), and a DVD title is further split into
+//! 1 GB `.VOB` parts. The scanner treats the folder as one movie: its media
+//! file is the first file of the main title, the longest by ffprobe duration,
+//! sized as the whole title. The organizer moves the folder as a unit, and
+//! transcoding joins a DVD title's parts into one stream.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use super::ffmpeg::FfmpegService;
+
+/// How many of a disc's largest titles are probed for their duration
+const MAX_PROBED_TITLES: usize = 4;
+
+/// Disc format of a folder rip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscKind {
+    Dvd,
+    BluRay,
+}
+
+impl DiscKind {
+    /// Kind of disc a structure folder (`VIDEO_TS` or `BDMV`) belongs to
+    pub fn from_folder_name(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("VIDEO_TS") {
+            Some(DiscKind::Dvd)
+        } else if name.eq_ignore_ascii_case("BDMV") {
+            Some(DiscKind::BluRay)
+        } else {
+            None
+        }
+    }
+}
+
+/// Files played in sequence as one title, and their total size
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscTitle {
+    pub files: Vec<PathBuf>,
+    pub size: u64,
+}
+
+/// A disc folder and its titles, largest first
+#[derive(Debug, Clone)]
+pub struct DiscFolder {
+    /// The folder holding `VIDEO_TS` or `BDMV`
+    pub root: PathBuf,
+    pub kind: DiscKind,
+    pub titles: Vec<DiscTitle>,
+}
+
+/// Title set and part of a DVD video file, e.g. `VTS_01_2.VOB` is (1, 2)
+///
+/// Part 0 is the title set's menu.
+fn dvd_title_part(file_name: &str) -> Option<(u32, u32)> {
+    let upper = file_name.to_ascii_uppercase();
+    let (title, part) = upper
+        .strip_prefix("VTS_")?
+        .strip_suffix(".VOB")?
+        .split_once('_')?;
+    Some((title.parse().ok()?, part.parse().ok()?))
+}
+
+fn has_extension(path: &Path, ext: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(ext))
+}
+
+/// Group a disc's video files into titles, largest first
+///
+/// A DVD title is a title set's parts in order, leaving out its menu. Each
+/// Blu-ray stream file is a title of its own.
+pub fn group_titles(kind: DiscKind, files: Vec<(PathBuf, u64)>) -> Vec<DiscTitle> {
+    let mut titles: Vec<DiscTitle> = match kind {
+        DiscKind::Dvd => {
+            let mut parts: Vec<(u32, u32, PathBuf, u64)> = files
+                .into_iter()
+                .filter_map(|(path, size)| {
+                    let name = path.file_name()?.to_str()?;
+                    let (title, part) = dvd_title_part(name)?;
+                    (part > 0).then_some((title, part, path, size))
+                })
+                .collect();
+            parts.sort_by_key(|(title, part, _, _)| (*title, *part));
+
+            let mut titles: Vec<(u32, DiscTitle)> = Vec::new();
+            for (number, _, path, size) in parts {
+                match titles.last_mut() {
+                    Some((last, title)) if *last == number => {
+                        title.files.push(path);
+                        title.size += size;
+                    }
+                    _ => titles.push((
+                        number,
+                        DiscTitle {
+                            files: vec![path],
+                            size,
+                        },
+                    )),
+                }
+            }
+            titles.into_iter().map(|(_, title)| title).collect()
+        }
+        DiscKind::BluRay => files
+            .into_iter()
+            .filter(|(path, _)| has_extension(path, "m2ts"))
+            .map(|(path, size)| DiscTitle {
+                files: vec![path],
+                size,
+            })
+            .collect(),
+    };
+    titles.sort_by_key(|t| std::cmp::Reverse(t.size));
+    titles
+}
+
+/// Files in a folder with their sizes
+fn list_files(dir: &Path) -> Vec<(PathBuf, u64)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let metadata = e.metadata().ok()?;
+            metadata.is_file().then(|| (e.path(), metadata.len()))
+        })
+        .collect()
+}
+
+/// Read a disc from its structure folder (`VIDEO_TS` or `BDMV`)
+///
+/// Returns None if the folder holds no titles.
+pub fn read_disc(structure_dir: &Path, kind: DiscKind) -> Option<DiscFolder> {
+    let files = match kind {
+        DiscKind::Dvd => list_files(structure_dir),
+        DiscKind::BluRay => {
+            let stream = std::fs::read_dir(structure_dir)
+                .ok()?
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .find(|p| {
+                    p.is_dir()
+                        && p.file_name()
+                            .and_then(|n| n.to_str())
+                            .is_some_and(|n| n.eq_ignore_ascii_case("STREAM"))
+                })?;
+            list_files(&stream)
+        }
+    };
+
+    let titles = group_titles(kind, files);
+    if titles.is_empty() {
+        return None;
+    }
+    Some(DiscFolder {
+        root: structure_dir.parent()?.to_path_buf(),
+        kind,
+        titles,
+    })
+}
+
+/// Index of the main title: the longest, or the largest if no duration is known
+///
+/// `durations` lines up with the start of `titles`, which are largest first.
+pub fn main_title_index(titles: &[DiscTitle], durations: &[Option<f64>]) -> Option<usize> {
+    let longest = durations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, d)| d.map(|d| (i, d)))
+        // Earlier (larger) titles win ties
+        .fold(None, |best: Option<(usize, f64)>, (i, d)| match best {
+            Some((_, best_d)) if best_d >= d => best,
+            _ => Some((i, d)),
+        });
+    match longest {
+        Some((i, _)) => Some(i),
+        None => (!titles.is_empty()).then_some(0),
+    }
+}
+
+/// Total duration of a title's files, if ffprobe reads all of them
+pub async fn title_duration(ffmpeg: &FfmpegService, files: &[PathBuf]) -> Option<f64> {
+    let mut total = 0.0;
+    for file in files {
+        total += ffmpeg.probe_duration(file).await.ok()?;
+    }
+    Some(total)
+}
+
+/// A disc's main title, probing its largest titles' durations
+pub async fn main_title<'a>(disc: &'a DiscFolder, ffmpeg: &FfmpegService) -> Option<&'a DiscTitle> {
+    let mut durations = Vec::new();
+    for title in disc.titles.iter().take(MAX_PROBED_TITLES) {
+        durations.push(title_duration(ffmpeg, &title.files).await);
+    }
+    main_title_index(&disc.titles, &durations).map(|i| &disc.titles[i])
+}
+
+/// The disc a file belongs to: the folder holding its `VIDEO_TS` or `BDMV`
+pub fn disc_root(path: &Path) -> Option<(PathBuf, DiscKind)> {
+    path.ancestors().skip(1).find_map(|dir| {
+        let kind = DiscKind::from_folder_name(dir.file_name()?.to_str()?)?;
+        Some((dir.parent()?.to_path_buf(), kind))
+    })
+}
+
+/// Files of the title a disc file starts, in playback order
+///
+/// For the first part of a DVD title that's every part of it; for anything
+/// else, just the file.
+pub fn title_parts(path: &Path) -> Vec<PathBuf> {
+    let dvd_title = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(dvd_title_part)
+        .filter(|_| matches!(disc_root(path), Some((_, DiscKind::Dvd))));
+    let (Some((title, _)), Some(dir)) = (dvd_title, path.parent()) else {
+        return vec![path.to_path_buf()];
+    };
+
+    let siblings = list_files(dir)
+        .into_iter()
+        .filter(|(p, _)| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .and_then(dvd_title_part)
+                .is_some_and(|(t, _)| t == title)
+        })
+        .collect();
+    group_titles(DiscKind::Dvd, siblings)
+        .into_iter()
+        .find(|t| t.files.first().map(PathBuf::as_path) == Some(path))
+        .map(|t| t.files)
+        .unwrap_or_else(|| vec![path.to_path_buf()])
+}
+
+/// ffmpeg input for a title: the file, or a `concat:` URL joining its parts
+pub fn ffmpeg_input(parts: &[PathBuf]) -> OsString {
+    match parts {
+        [single] => single.clone().into_os_string(),
+        _ => {
+            let mut input = OsString::from("concat:");
+            for (i, part) in parts.iter().enumerate() {
+                if i > 0 {
+                    input.push("|");
+                }
+                input.push(part);
+            }
+            input
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(names: &[(&str, u64)]) -> Vec<(PathBuf, u64)> {
+        names
+            .iter()
+            .map(|(name, size)| (PathBuf::from(name), *size))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_folder_name() {
+        assert_eq!(DiscKind::from_folder_name("VIDEO_TS"), Some(DiscKind::Dvd));
+        assert_eq!(DiscKind::from_folder_name("video_ts"), Some(DiscKind::Dvd));
+        assert_eq!(DiscKind::from_folder_name("BDMV"), Some(DiscKind::BluRay));
+        assert_eq!(DiscKind::from_folder_name("AUDIO_TS"), None);
+    }
+
+    #[test]
+    fn test_dvd_title_part() {
+        assert_eq!(dvd_title_part("VTS_01_2.VOB"), Some((1, 2)));
+        assert_eq!(dvd_title_part("vts_12_0.vob"), Some((12, 0)));
+        assert_eq!(dvd_title_part("VIDEO_TS.VOB"), None);
+        assert_eq!(dvd_title_part("VTS_01_1.IFO"), None);
+    }
+
+    #[test]
+    fn test_group_titles_dvd() {
+        let titles = group_titles(
+            DiscKind::Dvd,
+            files(&[
+                ("VIDEO_TS.VOB", 1_000),
+                ("VTS_01_0.VOB", 500),
+                ("VTS_01_1.VOB", 2_000),
+                ("VTS_02_2.VOB", 900),
+                ("VTS_02_1.VOB", 1_000),
+                ("VTS_02_0.VOB", 500),
+                ("VTS_02_1.IFO", 10),
+            ]),
+        );
+        assert_eq!(titles.len(), 2);
+        assert_eq!(
+            titles[0].files,
+            vec![PathBuf::from("VTS_01_1.VOB")],
+            "largest first"
+        );
+        assert_eq!(
+            titles[1].files,
+            vec![PathBuf::from("VTS_02_1.VOB"), PathBuf::from("VTS_02_2.VOB")]
+        );
+        assert_eq!(titles[1].size, 1_900);
+    }
+
+    #[test]
+    fn test_group_titles_bluray() {
+        let titles = group_titles(
+            DiscKind::BluRay,
+            files(&[("00001.m2ts", 50), ("00800.M2TS", 9_000), ("index.bdmv", 1)]),
+        );
+        assert_eq!(titles.len(), 2);
+        assert_eq!(titles[0].files, vec![PathBuf::from("00800.M2TS")]);
+    }
+
+    #[test]
+    fn test_main_title_index() {
+        let titles = group_titles(
+            DiscKind::BluRay,
+            files(&[("a.m2ts", 300), ("b.m2ts", 200), ("c.m2ts", 100)]),
+        );
+        // A high-bitrate extra can outsize the film
+        assert_eq!(
+            main_title_index(&titles, &[Some(600.0), Some(7_200.0), None]),
+            Some(1)
+        );
+        assert_eq!(
+            main_title_index(&titles, &[None, Some(60.0), Some(60.0)]),
+            Some(1)
+        );
+        assert_eq!(main_title_index(&titles, &[None, None]), Some(0));
+        assert_eq!(main_title_index(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_disc_root() {
+        assert_eq!(
+            disc_root(Path::new("/movies/Heat (1995)/VIDEO_TS/VTS_01_1.VOB")),
+            Some((PathBuf::from("/movies/Heat (1995)"), DiscKind::Dvd))
+        );
+        assert_eq!(
+            disc_root(Path::new("/movies/Heat (1995)/BDMV/STREAM/00800.m2ts")),
+            Some((PathBuf::from("/movies/Heat (1995)"), DiscKind::BluRay))
+        );
+        assert_eq!(disc_root(Path::new("/movies/Heat (1995)/Heat.mkv")), None);
+    }
+
+    #[test]
+    fn test_title_parts() {
+        let temp = tempfile::tempdir().unwrap();
+        let video_ts = temp.path().join("Heat (1995)").join("VIDEO_TS");
+        std::fs::create_dir_all(&video_ts).unwrap();
+        for name in [
+            "VTS_01_0.VOB",
+            "VTS_01_1.VOB",
+            "VTS_01_2.VOB",
+            "VTS_02_1.VOB",
+        ] {
+            std::fs::write(video_ts.join(name), b"x").unwrap();
+        }
+
+        assert_eq!(
+            title_parts(&video_ts.join("VTS_01_1.VOB")),
+            vec![video_ts.join("VTS_01_1.VOB"), video_ts.join("VTS_01_2.VOB")]
+        );
+        assert_eq!(
+            title_parts(&video_ts.join("VTS_02_1.VOB")),
+            vec![video_ts.join("VTS_02_1.VOB")]
+        );
+
+        let disc = read_disc(&video_ts, DiscKind::Dvd).unwrap();
+        assert_eq!(disc.root, temp.path().join("Heat (1995)"));
+        assert_eq!(disc.titles.len(), 2);
+    }
+
+    #[test]
+    fn test_ffmpeg_input() {
+        assert_eq!(
+            ffmpeg_input(&[PathBuf::from("/d/VTS_01_1.VOB")]),
+            OsString::from("/d/VTS_01_1.VOB")
+        );
+        assert_eq!(
+            ffmpeg_input(&[
+                PathBuf::from("/d/VTS_01_1.VOB"),
+                PathBuf::from("/d/VTS_01_2.VOB")
+            ]),
+            OsString::from("concat:/d/VTS_01_1.VOB|/d/VTS_01_2.VOB")
+        );
+    }
+}
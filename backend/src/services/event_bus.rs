@@ -0,0 +1,118 @@
+//! Typed in-process event bus
+//!
+//! Holds one broadcast channel per event type, created on first use.
+//! Publishers and subscribers only need the bus, so a subscription can no
+//! longer come up empty (or panic) because its channel was never added to the
+//! schema data.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use tokio::sync::broadcast;
+
+/// Capacity of channels the bus creates itself
+pub const DEFAULT_CAPACITY: usize = 100;
+
+/// Registry of broadcast channels keyed by event type
+#[derive(Default)]
+pub struct EventBus {
+    channels: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sender for an event type, creating its channel on first use
+    ///
+    /// For services that hold on to a sender rather than the bus.
+    pub fn sender<T: Clone + Send + 'static>(&self) -> broadcast::Sender<T> {
+        if let Some(tx) = self.existing::<T>() {
+            return tx;
+        }
+
+        let mut channels = self.channels.write().unwrap_or_else(|e| e.into_inner());
+        channels
+            .entry(TypeId::of::<broadcast::Sender<T>>())
+            .or_insert_with(|| Box::new(broadcast::channel::<T>(DEFAULT_CAPACITY).0))
+            .downcast_ref::<broadcast::Sender<T>>()
+            .expect("event bus channel stored under the wrong type")
+            .clone()
+    }
+
+    /// Adopt a channel created elsewhere (e.g. by the logging layer)
+    ///
+    /// Returns false, leaving the existing channel in place, if the event type
+    /// already has one.
+    pub fn register<T: Clone + Send + 'static>(&self, tx: broadcast::Sender<T>) -> bool {
+        let mut channels = self.channels.write().unwrap_or_else(|e| e.into_inner());
+        let key = TypeId::of::<broadcast::Sender<T>>();
+        if channels.contains_key(&key) {
+            return false;
+        }
+        channels.insert(key, Box::new(tx));
+        true
+    }
+
+    /// Subscribe to an event type
+    pub fn subscribe<T: Clone + Send + 'static>(&self) -> broadcast::Receiver<T> {
+        self.sender::<T>().subscribe()
+    }
+
+    /// Publish an event, returning how many subscribers received it
+    ///
+    /// Having no subscribers is normal (nobody has the page open), not an error.
+    pub fn publish<T: Clone + Send + 'static>(&self, event: T) -> usize {
+        self.sender::<T>().send(event).unwrap_or(0)
+    }
+
+    fn existing<T: Clone + Send + 'static>(&self) -> Option<broadcast::Sender<T>> {
+        let channels = self.channels.read().unwrap_or_else(|e| e.into_inner());
+        channels
+            .get(&TypeId::of::<broadcast::Sender<T>>())
+            .and_then(|tx| tx.downcast_ref::<broadcast::Sender<T>>())
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ping(u32);
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Pong(u32);
+
+    #[test]
+    fn test_publish_reaches_subscribers_of_that_type() {
+        let bus = EventBus::new();
+        let mut pings = bus.subscribe::<Ping>();
+        let mut pongs = bus.subscribe::<Pong>();
+
+        assert_eq!(bus.publish(Ping(1)), 1);
+        assert_eq!(pings.try_recv().unwrap(), Ping(1));
+        assert!(pongs.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        let bus = EventBus::new();
+        assert_eq!(bus.publish(Ping(1)), 0);
+    }
+
+    #[test]
+    fn test_register_adopts_existing_channel() {
+        let bus = EventBus::new();
+        let (tx, _) = broadcast::channel::<Ping>(10);
+        assert!(bus.register(tx.clone()));
+        assert!(!bus.register(broadcast::channel::<Ping>(10).0));
+
+        let mut rx = bus.subscribe::<Ping>();
+        tx.send(Ping(7)).unwrap();
+        assert_eq!(rx.try_recv().unwrap(), Ping(7));
+    }
+}
@@ -5,6 +5,9 @@
 //!
 //! This approach is more reliable than Rust FFmpeg bindings as ffprobe's JSON
 //! output format is stable and well-documented.
+//!
+//! Loudness is measured separately with ffmpeg's `ebur128` filter, since it
+//! has to decode the whole stream.
 
 use std::collections::HashMap;
 use std::path::Path;
@@ -149,10 +152,52 @@ pub struct AudioStream {
     /// Whether this is a commentary track
     pub is_commentary: bool,
 
+    /// EBU R128 loudness, when loudness analysis is enabled
+    #[serde(default)]
+    pub loudness: Option<Loudness>,
+
     /// Stream-level metadata
     pub metadata: HashMap<String, String>,
 }
 
+/// EBU R128 loudness of an audio stream
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Loudness {
+    /// Integrated loudness in LUFS
+    pub integrated: f64,
+
+    /// Loudness range in LU
+    pub range: Option<f64>,
+
+    /// True peak in dBTP
+    pub true_peak: Option<f64>,
+}
+
+/// Loudness ReplayGain 2.0 normalizes to, in LUFS
+pub const REPLAYGAIN_REFERENCE_LUFS: f64 = -18.0;
+
+/// Integrated loudness ffmpeg reports for silence
+const SILENCE_LUFS: f64 = -70.0;
+
+impl Loudness {
+    /// ReplayGain-style track gain in dB: how much to turn the stream up
+    /// (or down, when negative) to reach the reference loudness
+    ///
+    /// `None` for silent streams, which would need an absurd gain.
+    pub fn replay_gain(&self) -> Option<f64> {
+        if self.integrated <= SILENCE_LUFS {
+            return None;
+        }
+        Some(((REPLAYGAIN_REFERENCE_LUFS - self.integrated) * 100.0).round() / 100.0)
+    }
+
+    /// True peak as a linear sample value (1.0 = full scale), the unit
+    /// ReplayGain peaks use
+    pub fn peak_amplitude(&self) -> Option<f64> {
+        self.true_peak.map(|db| 10f64.powf(db / 20.0))
+    }
+}
+
 /// Subtitle stream information (embedded in container)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubtitleStream {
@@ -361,6 +406,7 @@ impl FfmpegService {
             .args(["-print_format", "json"])
             .args(["-show_format", "-show_streams", "-show_chapters"])
             .arg(path)
+            .kill_on_drop(true)
             .output()
             .await
             .with_context(|| format!("Failed to execute ffprobe for '{}'", path.display()))?;
@@ -416,6 +462,69 @@ impl FfmpegService {
         Ok(analysis)
     }
 
+    /// Read just a file's container duration, in seconds
+    ///
+    /// Much cheaper than a full analysis, for sizing up many files at once.
+    pub async fn probe_duration(&self, path: &Path) -> Result<f64> {
+        let output = Command::new(&self.ffprobe_path)
+            .args(["-v", "error"])
+            .args(["-show_entries", "format=duration"])
+            .args(["-of", "default=noprint_wrappers=1:nokey=1"])
+            .arg(path)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .with_context(|| format!("Failed to execute ffprobe for '{}'", path.display()))?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "ffprobe failed for '{}': {}",
+                path.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .with_context(|| format!("No duration reported for '{}'", path.display()))
+    }
+
+    /// Measure the EBU R128 loudness of one audio stream
+    ///
+    /// Decodes the whole stream, so this takes about as long as ffmpeg
+    /// needs to read the file.
+    pub async fn measure_loudness(&self, path: &Path, stream_index: usize) -> Result<Loudness> {
+        // Per-frame measurements go to the verbose log level so only the
+        // summary is printed
+        let output = Command::new("ffmpeg")
+            .args(["-hide_banner", "-nostats", "-nostdin", "-i"])
+            .arg(path)
+            .args(["-map", &format!("0:{}", stream_index)])
+            .args(["-af", "ebur128=peak=true:framelog=verbose"])
+            .args(["-f", "null", "-"])
+            .stdout(Stdio::null())
+            .output()
+            .await
+            .with_context(|| format!("Failed to execute ffmpeg for '{}'", path.display()))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() {
+            anyhow::bail!(
+                "ffmpeg loudness analysis failed for '{}': {}",
+                path.display(),
+                stderr.lines().last().unwrap_or("no error output")
+            );
+        }
+
+        parse_ebur128_summary(&stderr).with_context(|| {
+            format!(
+                "No loudness summary in ffmpeg output for '{}'",
+                path.display()
+            )
+        })
+    }
+
     /// Convert ffprobe output to our MediaAnalysis structure
     fn convert_probe_output(
         &self,
@@ -603,6 +712,7 @@ impl FfmpegService {
             title,
             is_default,
             is_commentary,
+            loudness: None,
             metadata: tags,
         })
     }
@@ -737,6 +847,41 @@ fn detect_bit_depth(pixel_format: Option<&str>) -> Option<u8> {
     }
 }
 
+/// Parse the summary the `ebur128` filter logs when it finishes
+///
+/// ```text
+///   Integrated loudness:
+///     I:         -16.5 LUFS
+///     Threshold: -26.9 LUFS
+///
+///   Loudness range:
+///     LRA:         6.9 LU
+///     ...
+///
+///   True peak:
+///     Peak:        0.5 dBFS
+/// ```
+fn parse_ebur128_summary(log: &str) -> Option<Loudness> {
+    let summary = &log[log.rfind("Summary:")?..];
+
+    let value = |label: &str| {
+        summary.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(label)?
+                .split_whitespace()
+                .next()?
+                .parse::<f64>()
+                .ok()
+        })
+    };
+
+    Some(Loudness {
+        integrated: value("I:")?,
+        range: value("LRA:"),
+        true_peak: value("Peak:"),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -791,4 +936,54 @@ mod tests {
             None
         );
     }
+
+    const EBUR128_LOG: &str = "\
+[Parsed_ebur128_0 @ 0x55d5c8a1e2c0] Summary:
+
+  Integrated loudness:
+    I:         -16.5 LUFS
+    Threshold: -26.9 LUFS
+
+  Loudness range:
+    LRA:         6.9 LU
+    Threshold: -36.9 LUFS
+    LRA low:   -21.7 LUFS
+    LRA high:  -14.8 LUFS
+
+  True peak:
+    Peak:        0.5 dBFS
+";
+
+    #[test]
+    fn test_parse_ebur128_summary() {
+        let loudness = parse_ebur128_summary(EBUR128_LOG).unwrap();
+        assert_eq!(loudness.integrated, -16.5);
+        assert_eq!(loudness.range, Some(6.9));
+        assert_eq!(loudness.true_peak, Some(0.5));
+
+        assert_eq!(parse_ebur128_summary("Output #0, null, to 'pipe:':"), None);
+    }
+
+    #[test]
+    fn test_replay_gain() {
+        let loud = Loudness {
+            integrated: -9.25,
+            range: None,
+            true_peak: Some(0.0),
+        };
+        assert_eq!(loud.replay_gain(), Some(-8.75));
+        assert_eq!(loud.peak_amplitude(), Some(1.0));
+
+        let quiet = Loudness {
+            integrated: -23.0,
+            ..loud
+        };
+        assert_eq!(quiet.replay_gain(), Some(5.0));
+
+        let silent = Loudness {
+            integrated: -70.0,
+            ..loud
+        };
+        assert_eq!(silent.replay_gain(), None);
+    }
 }
@@ -0,0 +1,241 @@
+//! Ownership and permissions for imported files
+//!
+//! When the server runs as root (the usual case in Docker), files copied into a
+//! library end up root-owned and may be unreadable by Plex/Jellyfin sharing the
+//! same path. After an import the file, and every folder between it and the
+//! library root, is given a target owner and mode. Global defaults come from
+//! settings, falling back to the PUID/PGID/UMASK environment variables used by
+//! most media containers; each library can override any of them.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use tracing::{debug, warn};
+
+use crate::db::{Database, LibraryRecord};
+
+/// Settings key for the owner uid of imported files (null = leave unchanged)
+pub const FILE_UID_KEY: &str = "import.file_uid";
+
+/// Settings key for the group gid of imported files (null = leave unchanged)
+pub const FILE_GID_KEY: &str = "import.file_gid";
+
+/// Settings key for the octal mode of imported files, e.g. "664" (null = leave unchanged)
+pub const FILE_MODE_KEY: &str = "import.file_mode";
+
+/// Parse an octal mode string ("664", "0664" or "0o664")
+pub fn parse_mode(value: &str) -> Result<u32> {
+    let value = value.trim();
+    let digits = value.strip_prefix("0o").unwrap_or(value);
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if !digits.is_empty() && mode <= 0o7777 => Ok(mode),
+        _ => bail!("Invalid file mode '{}': expected octal such as 664", value),
+    }
+}
+
+/// File mode left by a umask, as applied to newly created files
+pub fn mode_from_umask(umask: u32) -> u32 {
+    0o666 & !umask
+}
+
+/// Folder mode for a file mode: folders need execute wherever read is allowed
+pub fn dir_mode(file_mode: u32) -> u32 {
+    file_mode | ((file_mode & 0o444) >> 2)
+}
+
+/// Folders between the library root and a file, outermost first
+///
+/// These are the folders the import may have created (show, season, movie...).
+/// The library root itself is never included.
+pub fn folders_between(root: &Path, file: &Path) -> Vec<PathBuf> {
+    let mut folders: Vec<PathBuf> = file
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(root) && *dir != root)
+        .map(Path::to_path_buf)
+        .collect();
+    folders.reverse();
+    folders
+}
+
+/// Target ownership and mode for imported files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportPermissions {
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub file_mode: Option<u32>,
+}
+
+impl ImportPermissions {
+    /// Defaults from the PUID/PGID/UMASK environment variables
+    pub fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok();
+        Self {
+            uid: var("PUID").and_then(|v| v.trim().parse().ok()),
+            gid: var("PGID").and_then(|v| v.trim().parse().ok()),
+            file_mode: var("UMASK")
+                .and_then(|v| parse_mode(&v).ok())
+                .map(mode_from_umask),
+        }
+    }
+
+    /// Global defaults: settings first, then the environment
+    pub async fn global(db: &Database) -> Result<Self> {
+        let env = Self::from_env();
+        let settings = db.settings();
+
+        let uid = settings
+            .get_or_default::<Option<u32>>(FILE_UID_KEY, None)
+            .await?;
+        let gid = settings
+            .get_or_default::<Option<u32>>(FILE_GID_KEY, None)
+            .await?;
+        let mode = settings
+            .get_or_default::<Option<String>>(FILE_MODE_KEY, None)
+            .await?;
+        let file_mode = match mode.as_deref().map(str::trim).filter(|m| !m.is_empty()) {
+            Some(mode) => Some(parse_mode(mode)?),
+            None => env.file_mode,
+        };
+
+        Ok(Self {
+            uid: uid.or(env.uid),
+            gid: gid.or(env.gid),
+            file_mode,
+        })
+    }
+
+    /// Effective permissions for a library (its overrides win field by field)
+    pub async fn for_library(db: &Database, library: &LibraryRecord) -> Result<Self> {
+        let global = Self::global(db).await?;
+        let file_mode = match library.file_mode.as_deref() {
+            Some(mode) => Some(parse_mode(mode)?),
+            None => None,
+        };
+        Ok(global.with_overrides(library.file_uid, library.file_gid, file_mode))
+    }
+
+    /// Replace any value that has an override
+    pub fn with_overrides(
+        self,
+        uid: Option<u32>,
+        gid: Option<u32>,
+        file_mode: Option<u32>,
+    ) -> Self {
+        Self {
+            uid: uid.or(self.uid),
+            gid: gid.or(self.gid),
+            file_mode: file_mode.or(self.file_mode),
+        }
+    }
+
+    pub fn is_noop(&self) -> bool {
+        self.uid.is_none() && self.gid.is_none() && self.file_mode.is_none()
+    }
+
+    /// Apply to an imported file and the folders created for it
+    ///
+    /// Failures are logged rather than returned: a file that was imported but
+    /// couldn't be chowned (e.g. not running as root) is still imported.
+    pub async fn apply(&self, library_root: &Path, file: &Path) {
+        if self.is_noop() {
+            return;
+        }
+        let perms = *self;
+        let root = library_root.to_path_buf();
+        let file = file.to_path_buf();
+        let _ = tokio::task::spawn_blocking(move || {
+            for folder in folders_between(&root, &file) {
+                if let Err(e) = perms.set(&folder, perms.file_mode.map(dir_mode)) {
+                    warn!(path = %folder.display(), error = %e, "Failed to set folder ownership/permissions");
+                }
+            }
+            match perms.set(&file, perms.file_mode) {
+                Ok(()) => debug!(path = %file.display(), ?perms, "Applied import permissions"),
+                Err(e) => {
+                    warn!(path = %file.display(), error = %e, "Failed to set file ownership/permissions")
+                }
+            }
+        })
+        .await;
+    }
+
+    #[cfg(unix)]
+    fn set(&self, path: &Path, mode: Option<u32>) -> std::io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        if self.uid.is_some() || self.gid.is_some() {
+            std::os::unix::fs::chown(path, self.uid, self.gid)?;
+        }
+        if let Some(mode) = mode {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+        }
+        Ok(())
+    }
+
+    /// Ownership and Unix modes don't apply on Windows; files inherit folder ACLs
+    #[cfg(not(unix))]
+    fn set(&self, _path: &Path, _mode: Option<u32>) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(parse_mode("664").unwrap(), 0o664);
+        assert_eq!(parse_mode("0664").unwrap(), 0o664);
+        assert_eq!(parse_mode("0o775").unwrap(), 0o775);
+        assert!(parse_mode("").is_err());
+        assert!(parse_mode("689").is_err());
+        assert!(parse_mode("17777").is_err());
+    }
+
+    #[test]
+    fn test_dir_and_umask_modes() {
+        assert_eq!(dir_mode(0o664), 0o775);
+        assert_eq!(dir_mode(0o644), 0o755);
+        assert_eq!(dir_mode(0o600), 0o700);
+        assert_eq!(mode_from_umask(0o002), 0o664);
+        assert_eq!(mode_from_umask(0o022), 0o644);
+    }
+
+    #[test]
+    fn test_folders_between() {
+        let folders = folders_between(
+            Path::new("/media/tv"),
+            Path::new("/media/tv/Show/Season 01/Show - S01E01.mkv"),
+        );
+        assert_eq!(
+            folders,
+            vec![
+                PathBuf::from("/media/tv/Show"),
+                PathBuf::from("/media/tv/Show/Season 01")
+            ]
+        );
+        assert!(folders_between(Path::new("/media/tv"), Path::new("/media/tv/a.mkv")).is_empty());
+        assert!(folders_between(Path::new("/media/tv"), Path::new("/other/a.mkv")).is_empty());
+    }
+
+    #[test]
+    fn test_library_overrides_win() {
+        let global = ImportPermissions {
+            uid: Some(1000),
+            gid: Some(1000),
+            file_mode: Some(0o644),
+        };
+        let merged = global.with_overrides(None, Some(100), Some(0o664));
+        assert_eq!(
+            merged,
+            ImportPermissions {
+                uid: Some(1000),
+                gid: Some(100),
+                file_mode: Some(0o664),
+            }
+        );
+        assert!(ImportPermissions::default().is_noop());
+    }
+}
@@ -20,6 +20,7 @@ use crate::db::{
     ActionType, CreateMediaFile, CreateNotification, Database, LibraryRecord, MediaFileRecord,
     NotificationCategory, NotificationType, PendingFileMatchRecord,
 };
+use crate::services::file_permissions::ImportPermissions;
 use crate::services::file_utils::get_container;
 use crate::services::organizer::{
     apply_audiobook_naming_pattern, apply_movie_naming_pattern, apply_music_naming_pattern,
@@ -235,6 +236,21 @@ impl FileProcessor {
             .mark_copied(pending_match.id)
             .await?;
 
+        // Remember which download produced the file
+        if let Err(e) = self
+            .db
+            .pending_file_matches()
+            .set_media_file(pending_match.id, media_file.id)
+            .await
+        {
+            warn!(
+                pending_match_id = %pending_match.id,
+                media_file_id = %media_file.id,
+                error = %e,
+                "Failed to link pending match to media file"
+            );
+        }
+
         // Link the torrent_file to the media_file (if source is a torrent)
         if pending_match.source_type == "torrent" {
             if let (Some(source_id), Some(file_index)) = (pending_match.source_id, pending_match.source_file_index) {
@@ -946,6 +962,16 @@ impl FileProcessor {
             file_size
         );
 
+        // Hand the file (and any folders created for it) to the configured owner
+        match ImportPermissions::for_library(&self.db, library).await {
+            Ok(perms) => perms.apply(Path::new(&library.path), dest_path).await,
+            Err(e) => warn!(
+                library_id = %library.id,
+                error = %e,
+                "Invalid import permission settings, leaving ownership unchanged"
+            ),
+        }
+
         // Create media_file record
         let dest_path_str = dest_path.to_string_lossy().to_string();
         let media_file = self
@@ -189,6 +189,70 @@ pub fn normalize_display_path_buf(path: &std::path::Path) -> String {
     normalize_display_path(&path.to_string_lossy())
 }
 
+/// Build a relative path from a rendered naming pattern.
+///
+/// Patterns are written with `/` but may contain `\` when entered on Windows;
+/// both are treated as separators so the result uses the platform's own.
+/// Empty, `.` and `..` segments are dropped so a pattern can never escape the
+/// library root.
+pub fn relative_path_from_pattern(rendered: &str) -> std::path::PathBuf {
+    rendered
+        .split(['/', '\\'])
+        .map(str::trim)
+        .filter(|part| !part.is_empty() && *part != "." && *part != "..")
+        .collect()
+}
+
+/// Comparison key for a path string.
+///
+/// Separators are unified, trailing separators and Windows verbatim prefixes
+/// are removed, and on Windows (case-insensitive filesystems) the key is
+/// lowercased, so `D:/TV/show` and `\\?\D:\TV\Show\` compare equal there.
+pub fn path_key(path: &str) -> String {
+    path_key_with(&normalize_display_path(path), cfg!(windows))
+}
+
+fn path_key_with(path: &str, windows: bool) -> String {
+    if !windows {
+        let trimmed = path.trim_end_matches('/');
+        return if trimmed.is_empty() {
+            path.to_string()
+        } else {
+            trimmed.to_string()
+        };
+    }
+    let unified = path.replace('/', "\\");
+    let trimmed = unified.trim_end_matches('\\');
+    // Keep the separator on drive roots ("C:\") so they don't become "C:"
+    let key = if trimmed.ends_with(':') || trimmed.is_empty() {
+        unified.as_str()
+    } else {
+        trimmed
+    };
+    key.to_lowercase()
+}
+
+/// Whether two path strings refer to the same location on this platform.
+pub fn paths_equal(a: &str, b: &str) -> bool {
+    path_key(a) == path_key(b)
+}
+
+/// Whether `path` is `root` or inside it, using platform path comparison.
+pub fn path_is_within(path: &str, root: &str) -> bool {
+    path_is_within_with(
+        &path_key(path),
+        &path_key(root),
+        if cfg!(windows) { '\\' } else { '/' },
+    )
+}
+
+fn path_is_within_with(path_key: &str, root_key: &str, separator: char) -> bool {
+    path_key == root_key
+        || path_key
+            .strip_prefix(root_key)
+            .is_some_and(|rest| root_key.ends_with(separator) || rest.starts_with(separator))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -238,6 +302,54 @@ mod tests {
         assert!(!result.contains('/'));
     }
 
+    #[test]
+    fn test_relative_path_from_pattern() {
+        let expected: std::path::PathBuf = ["Show (2020)", "Season 01", "Show - S01E01.mkv"]
+            .iter()
+            .collect();
+        assert_eq!(
+            relative_path_from_pattern("Show (2020)/Season 01/Show - S01E01.mkv"),
+            expected
+        );
+        assert_eq!(
+            relative_path_from_pattern("Show (2020)\\Season 01\\Show - S01E01.mkv"),
+            expected
+        );
+        assert_eq!(
+            relative_path_from_pattern("/../Show (2020)//Season 01/./Show - S01E01.mkv"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_path_key_windows() {
+        assert_eq!(path_key_with(r"D:\TV\Show\", true), r"d:\tv\show");
+        assert_eq!(path_key_with("D:/TV/Show", true), r"d:\tv\show");
+        assert_eq!(path_key_with(r"C:\", true), r"c:\");
+        assert_eq!(path_key_with(r"\\NAS\Media\TV", true), r"\\nas\media\tv");
+    }
+
+    #[test]
+    fn test_path_key_unix() {
+        assert_eq!(path_key_with("/media/TV/", false), "/media/TV");
+        assert_eq!(path_key_with("/", false), "/");
+        assert_ne!(
+            path_key_with("/media/TV", false),
+            path_key_with("/media/tv", false)
+        );
+    }
+
+    #[test]
+    fn test_path_is_within() {
+        assert!(path_is_within_with(r"d:\tv\show\a.mkv", r"d:\tv", '\\'));
+        assert!(path_is_within_with(r"d:\tv", r"d:\tv", '\\'));
+        assert!(path_is_within_with(r"c:\tv", r"c:\", '\\'));
+        assert!(!path_is_within_with(r"d:\tv shows\a.mkv", r"d:\tv", '\\'));
+        assert!(path_is_within_with("/media/tv/a.mkv", "/media/tv", '/'));
+        assert!(path_is_within_with("/media/tv/a.mkv", "/", '/'));
+        assert!(!path_is_within_with("/media/tv2/a.mkv", "/media/tv", '/'));
+    }
+
     #[test]
     fn test_format_bytes() {
         assert_eq!(format_bytes(0), "0 B");
@@ -15,7 +15,9 @@ use tracing::{debug, info, warn};
 use uuid::Uuid;
 
 use crate::db::{Database, libraries::LibraryRecord};
-use crate::services::file_utils::{normalize_display_path, normalize_display_path_buf};
+use crate::services::file_utils::{
+    normalize_display_path, normalize_display_path_buf, sanitize_for_filename,
+};
 
 /// Event emitted when directory contents change
 #[derive(Debug, Clone)]
@@ -284,17 +286,16 @@ impl FilesystemService {
         show_hidden: bool,
         user_id: Uuid,
     ) -> Result<BrowseResult> {
+        // Windows has no single root: "/" lists the drive letters instead
+        if cfg!(windows) && path.is_none_or(|p| matches!(p, "" | "/" | "\\")) {
+            return Ok(Self::browse_drives());
+        }
+
         // Determine the path to browse (default to root)
         let requested_path = match path {
             Some(p) if !p.is_empty() => PathBuf::from(p),
             _ => default_browse_path(),
         };
-        #[cfg(windows)]
-        let requested_path = if requested_path == PathBuf::from("/") {
-            default_browse_path()
-        } else {
-            requested_path
-        };
 
         // Canonicalize the path to resolve symlinks and ..
         let canonical_path = match requested_path.canonicalize() {
@@ -306,9 +307,9 @@ impl FilesystemService {
                     path = path.parent().unwrap().to_path_buf();
                 }
                 if path.exists() {
-                    path.canonicalize().unwrap_or_else(|_| PathBuf::from("/"))
+                    path.canonicalize().unwrap_or_else(|_| default_browse_path())
                 } else {
-                    PathBuf::from("/")
+                    default_browse_path()
                 }
             }
         };
@@ -345,7 +346,7 @@ impl FilesystemService {
 
             // Skip hidden files unless show_hidden
             let name = entry.file_name().to_string_lossy().to_string();
-            if !show_hidden && name.starts_with('.') {
+            if !show_hidden && is_hidden(&name, &metadata) {
                 continue;
             }
 
@@ -394,10 +395,12 @@ impl FilesystemService {
             _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
         });
 
-        // Get parent path
-        let parent_path = canonical_path
-            .parent()
-            .map(|p| normalize_display_path_buf(p));
+        // Get parent path (drive and UNC share roots go back up to the drive list)
+        let parent_path = match canonical_path.parent() {
+            Some(p) => Some(normalize_display_path_buf(p)),
+            None if cfg!(windows) => Some("/".to_string()),
+            None => None,
+        };
 
         Ok(BrowseResult {
             current_path: normalize_display_path_buf(&canonical_path),
@@ -409,6 +412,32 @@ impl FilesystemService {
         })
     }
 
+    /// List the drive letters as the entries of a virtual root (Windows)
+    fn browse_drives() -> BrowseResult {
+        let entries = windows_drive_paths()
+            .into_iter()
+            .map(|drive| FileEntry {
+                name: drive.to_string_lossy().trim_end_matches('\\').to_string(),
+                path: normalize_display_path_buf(&drive),
+                is_dir: true,
+                size: 0,
+                readable: true,
+                writable: true,
+                mime_type: None,
+                modified_at: None,
+            })
+            .collect();
+
+        BrowseResult {
+            current_path: "/".to_string(),
+            parent_path: None,
+            entries,
+            quick_paths: Self::get_quick_paths(),
+            is_library_path: false,
+            library_id: None,
+        }
+    }
+
     /// Create a directory
     pub async fn create_directory(&self, path: &str, _user_id: Uuid) -> Result<String> {
         // For create, we allow creating directories at any location for now
@@ -792,6 +821,10 @@ impl FilesystemService {
         if new_name.contains('/') || new_name.contains('\\') {
             return Err(anyhow!("New name cannot contain path separators"));
         }
+        // Windows also rejects reserved characters and names (CON, NUL...)
+        if cfg!(windows) && sanitize_for_filename(new_name) != new_name {
+            return Err(anyhow!("New name contains characters not allowed on Windows"));
+        }
 
         let source_path = PathBuf::from(path);
         let parent = source_path
@@ -850,6 +883,24 @@ fn windows_drive_paths() -> Vec<PathBuf> {
     }
 }
 
+/// Whether a directory entry is hidden (dot-files, plus the hidden and system
+/// attributes on Windows)
+fn is_hidden(name: &str, metadata: &std::fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+        if metadata.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0 {
+            return true;
+        }
+    }
+    #[cfg(not(windows))]
+    let _ = metadata;
+
+    name.starts_with('.')
+}
+
 fn default_browse_path() -> PathBuf {
     #[cfg(windows)]
     {
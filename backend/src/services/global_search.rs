@@ -0,0 +1,238 @@
+//! Search across every kind of library item
+//!
+//! Backs the omnibox: one query is looked up in movies, shows, episodes,
+//! albums, artists, audiobooks and the people credited on movies and
+//! audiobooks. Candidates are fetched with a `LIKE` on the start of the
+//! query's longest word, so typos after its first few letters still find
+//! them, then scored by fuzzy similarity to the whole query and merged into
+//! one ranking.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use super::text_utils::{normalize_title, string_similarity};
+use crate::db::{Database, SearchCandidate};
+
+/// Candidates fetched per entity type before scoring
+const CANDIDATES_PER_TYPE: i64 = 50;
+
+/// Letters of the longest query word used for the `LIKE` lookup
+const PATTERN_LETTERS: usize = 3;
+
+/// Hits scoring lower than this are dropped
+pub const MIN_SCORE: f64 = 0.45;
+
+/// What a search hit is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+    Movie,
+    Show,
+    Episode,
+    Album,
+    Artist,
+    Audiobook,
+    Person,
+}
+
+/// A scored search hit
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    /// Not set for people, who are only names
+    pub id: Option<Uuid>,
+    pub name: String,
+    /// For people: 'cast', 'director', 'author' and/or 'narrator'
+    pub roles: Vec<String>,
+    /// 0.0 to 1.0
+    pub score: f64,
+}
+
+/// Search a user's library, best hits first
+pub async fn search(
+    db: &Database,
+    user_id: Uuid,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let Some(pattern) = like_pattern(query) else {
+        return Ok(Vec::new());
+    };
+
+    let repo = db.search();
+    let mut hits = Vec::new();
+    let typed = [
+        (
+            SearchKind::Movie,
+            repo.movies(user_id, &pattern, CANDIDATES_PER_TYPE).await?,
+        ),
+        (
+            SearchKind::Show,
+            repo.shows(user_id, &pattern, CANDIDATES_PER_TYPE).await?,
+        ),
+        (
+            SearchKind::Episode,
+            repo.episodes(user_id, &pattern, CANDIDATES_PER_TYPE)
+                .await?,
+        ),
+        (
+            SearchKind::Album,
+            repo.albums(user_id, &pattern, CANDIDATES_PER_TYPE).await?,
+        ),
+        (
+            SearchKind::Artist,
+            repo.artists(user_id, &pattern, CANDIDATES_PER_TYPE).await?,
+        ),
+        (
+            SearchKind::Audiobook,
+            repo.audiobooks(user_id, &pattern, CANDIDATES_PER_TYPE)
+                .await?,
+        ),
+    ];
+    for (kind, candidates) in typed {
+        hits.extend(
+            candidates
+                .into_iter()
+                .map(|SearchCandidate { id, name }| SearchHit {
+                    kind,
+                    id: Some(id),
+                    score: match_score(query, &name),
+                    name,
+                    roles: Vec::new(),
+                }),
+        );
+    }
+
+    // The same person can be credited in several roles
+    let mut people: HashMap<String, SearchHit> = HashMap::new();
+    for person in repo.people(user_id, &pattern, CANDIDATES_PER_TYPE).await? {
+        people
+            .entry(person.name.clone())
+            .or_insert_with(|| SearchHit {
+                kind: SearchKind::Person,
+                id: None,
+                score: match_score(query, &person.name),
+                name: person.name,
+                roles: Vec::new(),
+            })
+            .roles
+            .push(person.role);
+    }
+    hits.extend(people.into_values());
+
+    Ok(rank(hits, limit))
+}
+
+/// Drop weak hits, then order by score, shorter names first on ties
+pub fn rank(mut hits: Vec<SearchHit>, limit: usize) -> Vec<SearchHit> {
+    hits.retain(|hit| hit.score >= MIN_SCORE);
+    hits.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then_with(|| a.name.len().cmp(&b.name.len()))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    hits.truncate(limit);
+    hits
+}
+
+/// How well a name matches a query, from 0.0 to 1.0
+///
+/// Exact matches score 1.0, then names starting with the query, names with
+/// a word starting with it and names containing it. Anything else scores
+/// by edit distance, against the whole name or its first words, whichever
+/// is closer.
+pub fn match_score(query: &str, name: &str) -> f64 {
+    let query = normalize_title(query);
+    let name = normalize_title(name);
+    if query.is_empty() || name.is_empty() {
+        return 0.0;
+    }
+
+    if name == query {
+        1.0
+    } else if name.starts_with(&query) {
+        0.9
+    } else if name.contains(&format!(" {}", query)) {
+        0.8
+    } else if name.contains(&query) {
+        0.7
+    } else {
+        let words = query.split(' ').count();
+        let leading: Vec<&str> = name.split(' ').take(words).collect();
+        let similarity =
+            string_similarity(&query, &name).max(string_similarity(&query, &leading.join(" ")));
+        0.7 * similarity
+    }
+}
+
+/// `LIKE` pattern for the first letters of the query's longest word
+pub fn like_pattern(query: &str) -> Option<String> {
+    let longest = normalize_title(query)
+        .split(' ')
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+        })
+        .max_by_key(|word| word.chars().count())?;
+    if longest.is_empty() {
+        return None;
+    }
+
+    let start: String = longest.chars().take(PATTERN_LETTERS).collect();
+    Some(format!("%{}%", start))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hit(name: &str, score: f64) -> SearchHit {
+        SearchHit {
+            kind: SearchKind::Movie,
+            id: None,
+            name: name.to_string(),
+            roles: Vec::new(),
+            score,
+        }
+    }
+
+    #[test]
+    fn test_match_score_order() {
+        let exact = match_score("Alien", "Alien");
+        let prefix = match_score("alien", "Aliens");
+        let word = match_score("alien", "The Alien Tapes");
+        let contained = match_score("lien", "Aliens");
+        assert_eq!(exact, 1.0);
+        assert!(exact > prefix && prefix > word && word > contained);
+    }
+
+    #[test]
+    fn test_match_score_tolerates_typos() {
+        assert!(match_score("breking bad", "Breaking Bad") >= MIN_SCORE);
+        assert!(match_score("breking", "Breaking Bad") >= MIN_SCORE);
+        assert!(match_score("breaking bad", "Bread") < MIN_SCORE);
+    }
+
+    #[test]
+    fn test_like_pattern() {
+        assert_eq!(like_pattern("The Breking Bad"), Some("%bre%".to_string()));
+        assert_eq!(like_pattern("Up"), Some("%up%".to_string()));
+        assert_eq!(like_pattern("100%"), Some("%100%".to_string()));
+        assert_eq!(like_pattern("  "), None);
+    }
+
+    #[test]
+    fn test_rank() {
+        let hits = vec![
+            hit("Aliens", 0.9),
+            hit("Bread", 0.2),
+            hit("Alien", 0.9),
+            hit("Alien 3", 1.0),
+        ];
+        let ranked: Vec<String> = rank(hits, 2).into_iter().map(|h| h.name).collect();
+        assert_eq!(ranked, ["Alien 3", "Alien"]);
+    }
+}
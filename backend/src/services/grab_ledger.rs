@@ -0,0 +1,253 @@
+//! Duplicate-release suppression
+//!
+//! Auto-hunt and manual grabs go through [`claim`] before handing a release
+//! to a download client. A release counts as already grabbed when the user
+//! has an earlier grab with the same info hash, indexer GUID or normalized
+//! title, so the same upload found on two indexers, or picked by two
+//! pipelines at once, is only downloaded once. Manual grabs can pass `force`
+//! to override this.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::db::{CreateGrab, Database, GrabHistoryRecord};
+use crate::indexer::ReleaseInfo;
+
+/// Pipeline that grabbed a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrabSource {
+    AutoHunt,
+    Manual,
+    WatchFolder,
+}
+
+impl GrabSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AutoHunt => "auto_hunt",
+            Self::Manual => "manual",
+            Self::WatchFolder => "watch_folder",
+        }
+    }
+}
+
+/// What's known about a release before it is grabbed
+#[derive(Debug, Clone)]
+pub struct GrabRequest {
+    pub user_id: Uuid,
+    pub source: GrabSource,
+    /// 'torrent' or 'usenet'
+    pub download_type: &'static str,
+    pub title: Option<String>,
+    pub info_hash: Option<String>,
+    pub guid: Option<String>,
+    pub indexer_id: Option<Uuid>,
+    pub force: bool,
+}
+
+impl GrabRequest {
+    /// A torrent grab of an indexer search result
+    pub fn torrent_release(release: &ReleaseInfo, user_id: Uuid, source: GrabSource) -> Self {
+        let info_hash = release
+            .info_hash
+            .as_deref()
+            .and_then(normalize_info_hash)
+            .or_else(|| {
+                release
+                    .magnet_uri
+                    .as_deref()
+                    .and_then(info_hash_from_magnet)
+            });
+
+        Self {
+            user_id,
+            source,
+            download_type: "torrent",
+            title: Some(release.title.clone()),
+            info_hash,
+            guid: (!release.guid.is_empty()).then(|| release.guid.clone()),
+            indexer_id: release
+                .indexer_id
+                .as_deref()
+                .and_then(|id| Uuid::parse_str(id).ok()),
+            force: false,
+        }
+    }
+}
+
+/// Outcome of [`claim`]
+#[derive(Debug)]
+pub enum Claim {
+    /// Grab recorded; pass the ID to [`confirm`] or [`abandon`]
+    Claimed(Uuid),
+    /// An earlier grab matched (None if it couldn't be looked up)
+    Duplicate(Option<GrabHistoryRecord>),
+}
+
+/// Record a grab unless the same release was already grabbed
+pub async fn claim(db: &Database, request: GrabRequest) -> Result<Claim> {
+    let normalized_title = request
+        .title
+        .as_deref()
+        .map(normalize_release_name)
+        .filter(|t| !t.is_empty());
+
+    let repo = db.grab_history();
+    let claimed = repo
+        .claim(CreateGrab {
+            user_id: request.user_id,
+            source: request.source.as_str().to_string(),
+            download_type: request.download_type.to_string(),
+            release_title: request.title.clone(),
+            normalized_title: normalized_title.clone(),
+            info_hash: request.info_hash.clone(),
+            release_guid: request.guid.clone(),
+            indexer_id: request.indexer_id,
+            forced: request.force,
+        })
+        .await?;
+
+    if let Some(id) = claimed {
+        return Ok(Claim::Claimed(id));
+    }
+
+    let earlier = repo
+        .find_duplicate(
+            request.user_id,
+            request.info_hash.as_deref(),
+            request.guid.as_deref(),
+            normalized_title.as_deref(),
+        )
+        .await?;
+    info!(
+        title = ?request.title,
+        source = request.source.as_str(),
+        earlier_source = ?earlier.as_ref().map(|g| g.source.as_str()),
+        "Skipping release that was already grabbed"
+    );
+    Ok(Claim::Duplicate(earlier))
+}
+
+/// Attach the info hash reported by the download client to a claim
+pub async fn confirm(db: &Database, claim_id: Uuid, info_hash: &str) -> Result<()> {
+    db.grab_history().set_info_hash(claim_id, info_hash).await
+}
+
+/// Drop a claim whose download failed to start, so the release can be
+/// grabbed again
+pub async fn abandon(db: &Database, claim_id: Uuid) -> Result<()> {
+    db.grab_history().delete(claim_id).await
+}
+
+/// Error message for a grab skipped as a duplicate
+pub fn duplicate_message(earlier: Option<&GrabHistoryRecord>) -> String {
+    match earlier {
+        Some(grab) => format!(
+            "Release was already grabbed ({}, {}); force the grab to download it again",
+            grab.source.replace('_', "-"),
+            format_grab_time(grab.created_at)
+        ),
+        None => "Release was already grabbed; force the grab to download it again".to_string(),
+    }
+}
+
+fn format_grab_time(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+/// Lowercased alphanumeric tokens of a release name, joined with '.'
+///
+/// "Show.Name.S01E02.1080p.WEB-DL" and "Show Name S01E02 1080p WEB DL"
+/// normalize to the same key.
+pub fn normalize_release_name(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Lowercase hex info hash, decoding the 32-character base32 form
+pub fn normalize_info_hash(hash: &str) -> Option<String> {
+    let hash = hash.trim();
+    match hash.len() {
+        40 if hash.chars().all(|c| c.is_ascii_hexdigit()) => Some(hash.to_lowercase()),
+        32 => base32_to_hex(hash),
+        _ => None,
+    }
+}
+
+/// Info hash from a magnet URI's `xt=urn:btih:` parameter
+pub fn info_hash_from_magnet(magnet: &str) -> Option<String> {
+    let query = magnet.split_once('?')?.1;
+    query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .filter(|(key, _)| key.eq_ignore_ascii_case("xt"))
+        .find_map(|(_, value)| {
+            let value = value.to_ascii_lowercase();
+            value
+                .strip_prefix("urn:btih:")
+                .and_then(normalize_info_hash)
+        })
+}
+
+fn base32_to_hex(encoded: &str) -> Option<String> {
+    let mut bits: u64 = 0;
+    let mut bit_count = 0;
+    let mut hex = String::with_capacity(40);
+    for c in encoded.chars() {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => c as u64 - 'A' as u64,
+            c @ '2'..='7' => c as u64 - '2' as u64 + 26,
+            _ => return None,
+        };
+        bits = (bits << 5) | value;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            hex.push_str(&format!("{:02x}", (bits >> bit_count) & 0xff));
+        }
+    }
+    Some(hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_release_name() {
+        assert_eq!(
+            normalize_release_name("Show.Name.S01E02.1080p.WEB-DL.x264-GRP"),
+            "show.name.s01e02.1080p.web.dl.x264.grp"
+        );
+        assert_eq!(
+            normalize_release_name("Show Name - S01E02 [1080p WEB-DL x264-GRP]"),
+            normalize_release_name("Show.Name.S01E02.1080p.WEB-DL.x264-GRP")
+        );
+        assert_eq!(normalize_release_name(" - "), "");
+    }
+
+    #[test]
+    fn test_info_hash_from_magnet() {
+        let hex = "c12fe1c06bba254a9dc9f519b335aa7c1367a88a";
+        assert_eq!(
+            info_hash_from_magnet(&format!(
+                "magnet:?xt=urn:btih:{}&dn=Some+Release",
+                hex.to_uppercase()
+            )),
+            Some(hex.to_string())
+        );
+        // Same hash in base32
+        assert_eq!(
+            info_hash_from_magnet("magnet:?dn=x&xt=urn:btih:YEX6DQDLXISUVHOJ6UM3GNNKPQJWPKEK"),
+            Some(hex.to_string())
+        );
+        assert_eq!(info_hash_from_magnet("magnet:?dn=no+hash"), None);
+        assert_eq!(normalize_info_hash("not-a-hash"), None);
+    }
+}
@@ -0,0 +1,323 @@
+//! Record/replay of outbound HTTP
+//!
+//! Lets the scan → identify → import pipeline run without network access, for
+//! integration tests and demos. With `LIBRARIAN_HTTP_RECORD=1`, responses from
+//! metadata providers and indexers are saved as fixtures as they come in. With
+//! `LIBRARIAN_OFFLINE=1`, the same requests are answered from those fixtures
+//! and nothing leaves the machine; a request without a fixture fails.
+//!
+//! Fixtures are JSON files under `LIBRARIAN_HTTP_FIXTURES` (default
+//! `fixtures/http`), one per request, named after the client and a hash of the
+//! method and URL. Credentials (API keys, passkeys, user info) are stripped
+//! from the URL before hashing and storing, so recordings can be committed and
+//! replayed without the keys they were recorded with.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use reqwest::header::{CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::{Client, Request, Response, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, warn};
+
+/// Default fixture directory, relative to the working directory
+pub const DEFAULT_FIXTURES_DIR: &str = "fixtures/http";
+
+/// Query parameters that carry credentials
+const SECRET_PARAMS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "key",
+    "token",
+    "access_token",
+    "passkey",
+    "password",
+];
+
+/// Whether outbound HTTP goes to the network, fixtures, or both
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMode {
+    /// Normal operation
+    Live,
+    /// Send requests and save the responses as fixtures
+    Record,
+    /// Answer requests from fixtures only
+    Offline,
+}
+
+impl HttpMode {
+    /// Mode from the `LIBRARIAN_OFFLINE` / `LIBRARIAN_HTTP_RECORD` values
+    /// (offline wins if both are set)
+    fn from_flags(offline: Option<&str>, record: Option<&str>) -> Self {
+        if is_enabled(offline) {
+            Self::Offline
+        } else if is_enabled(record) {
+            Self::Record
+        } else {
+            Self::Live
+        }
+    }
+}
+
+fn is_enabled(value: Option<&str>) -> bool {
+    matches!(
+        value.map(|v| v.trim().to_ascii_lowercase()).as_deref(),
+        Some("1" | "true" | "yes")
+    )
+}
+
+/// Current mode, read from the environment once
+pub fn mode() -> HttpMode {
+    static MODE: OnceLock<HttpMode> = OnceLock::new();
+    *MODE.get_or_init(|| {
+        let mode = HttpMode::from_flags(
+            std::env::var("LIBRARIAN_OFFLINE").ok().as_deref(),
+            std::env::var("LIBRARIAN_HTTP_RECORD").ok().as_deref(),
+        );
+        match mode {
+            HttpMode::Live => {}
+            HttpMode::Record => info!(
+                dir = %fixtures_dir().display(),
+                "Recording outbound HTTP responses as fixtures"
+            ),
+            HttpMode::Offline => info!(
+                dir = %fixtures_dir().display(),
+                "Offline mode: outbound HTTP is answered from fixtures"
+            ),
+        }
+        mode
+    })
+}
+
+fn fixtures_dir() -> &'static Path {
+    static DIR: OnceLock<PathBuf> = OnceLock::new();
+    DIR.get_or_init(|| {
+        std::env::var("LIBRARIAN_HTTP_FIXTURES")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_FIXTURES_DIR))
+    })
+}
+
+/// A recorded response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Fixture {
+    method: String,
+    /// Request URL with credentials redacted
+    url: String,
+    status: u16,
+    content_type: Option<String>,
+    body: String,
+    /// Body is base64 (it wasn't valid UTF-8, e.g. a .torrent file)
+    #[serde(default)]
+    base64: bool,
+}
+
+/// Send a request according to the current [`mode`]
+///
+/// `source` names the client (e.g. "tmdb", "newznab") and groups its fixtures.
+pub async fn send(client: &Client, source: &str, request: Request) -> Result<Response> {
+    match mode() {
+        HttpMode::Live => Ok(client.execute(request).await?),
+        HttpMode::Record => record(client, source, request).await,
+        HttpMode::Offline => replay(source, &request).await,
+    }
+}
+
+async fn record(client: &Client, source: &str, request: Request) -> Result<Response> {
+    let path = fixtures_dir().join(fixture_key(
+        source,
+        request.method().as_str(),
+        request.url(),
+    ));
+    let method = request.method().to_string();
+    let url = redacted_url(request.url());
+
+    let response = client.execute(request).await?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let bytes = response.bytes().await?;
+
+    let (body, base64) = match std::str::from_utf8(&bytes) {
+        Ok(text) => (text.to_string(), false),
+        Err(_) => (BASE64.encode(&bytes), true),
+    };
+    let fixture = Fixture {
+        method,
+        url,
+        status: status.as_u16(),
+        content_type: headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from),
+        body,
+        base64,
+    };
+    match write_fixture(&path, &fixture).await {
+        Ok(()) => debug!(path = %path.display(), url = %fixture.url, "Recorded HTTP fixture"),
+        Err(e) => warn!(path = %path.display(), error = %e, "Failed to write HTTP fixture"),
+    }
+
+    Ok(build_response(status, headers, bytes.to_vec()))
+}
+
+async fn replay(source: &str, request: &Request) -> Result<Response> {
+    let path = fixtures_dir().join(fixture_key(
+        source,
+        request.method().as_str(),
+        request.url(),
+    ));
+    let raw = match tokio::fs::read(&path).await {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => bail!(
+            "Offline mode: no recorded response for {} {} (expected {})",
+            request.method(),
+            redacted_url(request.url()),
+            path.display()
+        ),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    let fixture: Fixture = serde_json::from_slice(&raw)
+        .with_context(|| format!("Invalid HTTP fixture {}", path.display()))?;
+    debug!(path = %path.display(), url = %fixture.url, "Replaying HTTP fixture");
+
+    let body = if fixture.base64 {
+        BASE64
+            .decode(&fixture.body)
+            .with_context(|| format!("Invalid base64 body in {}", path.display()))?
+    } else {
+        fixture.body.into_bytes()
+    };
+    let mut headers = HeaderMap::new();
+    if let Some(value) = fixture
+        .content_type
+        .as_deref()
+        .and_then(|v| HeaderValue::from_str(v).ok())
+    {
+        headers.insert(CONTENT_TYPE, value);
+    }
+    let status = StatusCode::from_u16(fixture.status)
+        .with_context(|| format!("Invalid status in {}", path.display()))?;
+
+    Ok(build_response(status, headers, body))
+}
+
+async fn write_fixture(path: &Path, fixture: &Fixture) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(path, serde_json::to_vec_pretty(fixture)?).await?;
+    Ok(())
+}
+
+fn build_response(status: StatusCode, mut headers: HeaderMap, body: Vec<u8>) -> Response {
+    // The body has already been decompressed and may differ in length
+    headers.remove(CONTENT_ENCODING);
+    headers.remove(CONTENT_LENGTH);
+
+    let mut response = axum::http::Response::new(body);
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    Response::from(response)
+}
+
+/// URL with credentials replaced and query parameters sorted, so the same
+/// request maps to the same fixture whatever key it was made with
+pub fn redacted_url(url: &Url) -> String {
+    let mut url = url.clone();
+    let _ = url.set_username("");
+    let _ = url.set_password(None);
+
+    let mut pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let value = if SECRET_PARAMS.contains(&k.to_ascii_lowercase().as_str()) {
+                "REDACTED".to_string()
+            } else {
+                v.into_owned()
+            };
+            (k.into_owned(), value)
+        })
+        .collect();
+    if pairs.is_empty() {
+        url.set_query(None);
+    } else {
+        pairs.sort();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// Fixture path (relative to the fixture directory) for a request
+pub fn fixture_key(source: &str, method: &str, url: &Url) -> String {
+    let method = method.to_ascii_uppercase();
+    let hash = format!(
+        "{:x}",
+        Sha256::digest(format!("{} {}", method, redacted_url(url)).as_bytes())
+    );
+    let source: String = source
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!(
+        "{}/{}-{}-{}.json",
+        source,
+        method.to_ascii_lowercase(),
+        url.host_str().unwrap_or("local"),
+        &hash[..16]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_from_flags() {
+        assert_eq!(HttpMode::from_flags(None, None), HttpMode::Live);
+        assert_eq!(HttpMode::from_flags(Some("0"), None), HttpMode::Live);
+        assert_eq!(HttpMode::from_flags(Some("1"), None), HttpMode::Offline);
+        assert_eq!(HttpMode::from_flags(None, Some("true")), HttpMode::Record);
+        assert_eq!(
+            HttpMode::from_flags(Some("yes"), Some("1")),
+            HttpMode::Offline
+        );
+    }
+
+    #[test]
+    fn test_redacted_url() {
+        let url = Url::parse("https://user:pw@api.example.com/3/search?query=Dune&api_key=secret")
+            .unwrap();
+        assert_eq!(
+            redacted_url(&url),
+            "https://api.example.com/3/search?api_key=REDACTED&query=Dune"
+        );
+
+        let url = Url::parse("https://api.tvmaze.com/shows/1").unwrap();
+        assert_eq!(redacted_url(&url), "https://api.tvmaze.com/shows/1");
+    }
+
+    #[test]
+    fn test_fixture_key_ignores_credentials_and_param_order() {
+        let a = Url::parse("https://nzb.example.com/api?t=search&q=Dune&apikey=one").unwrap();
+        let b = Url::parse("https://nzb.example.com/api?apikey=two&q=Dune&t=search").unwrap();
+        let c = Url::parse("https://nzb.example.com/api?t=search&q=Arrival&apikey=one").unwrap();
+
+        let key = fixture_key("newznab", "GET", &a);
+        assert!(key.starts_with("newznab/get-nzb.example.com-"));
+        assert!(key.ends_with(".json"));
+        assert_eq!(key, fixture_key("newznab", "get", &b));
+        assert_ne!(key, fixture_key("newznab", "GET", &c));
+    }
+}
@@ -0,0 +1,307 @@
+//! Language profile rules
+//!
+//! Applies a [`LanguageProfileRecord`] in two places:
+//!
+//! - Release ranking: auto-hunt and RSS read the audio languages from the
+//!   release title, reject releases missing a required language and rank
+//!   the rest by the profile's preferred order.
+//! - Post-import verification: after media analysis, the file's audio
+//!   tracks and subtitles are compared with the profile. Missing subtitle
+//!   languages are queued for download; a file missing audio languages is
+//!   flagged, its owner notified, and, if the profile asks for it,
+//!   auto-hunt looks for another release.
+//!
+//! Scene releases only tag their language when it isn't English, so an
+//! untagged title counts as English. `MULTi` and `DUAL` releases carry the
+//! original audio plus the tagged languages; the original is assumed to be
+//! English as well.
+
+use crate::db::LanguageProfileRecord;
+use crate::services::subtitle_download::iso639_1;
+
+/// Release title tags and the ISO 639-1 language they mean
+///
+/// Two-letter codes are left out on purpose: "It", "No" and "De" show up
+/// in titles far more often than as language tags.
+const RELEASE_LANGUAGE_TAGS: &[(&str, &str)] = &[
+    ("english", "en"),
+    ("eng", "en"),
+    ("french", "fr"),
+    ("truefrench", "fr"),
+    ("vff", "fr"),
+    ("vfq", "fr"),
+    ("vf2", "fr"),
+    ("german", "de"),
+    ("ger", "de"),
+    ("deutsch", "de"),
+    ("italian", "it"),
+    ("ita", "it"),
+    ("spanish", "es"),
+    ("spa", "es"),
+    ("esp", "es"),
+    ("castellano", "es"),
+    ("latino", "es"),
+    ("dutch", "nl"),
+    ("flemish", "nl"),
+    ("russian", "ru"),
+    ("rus", "ru"),
+    ("japanese", "ja"),
+    ("jpn", "ja"),
+    ("korean", "ko"),
+    ("kor", "ko"),
+    ("chinese", "zh"),
+    ("mandarin", "zh"),
+    ("cantonese", "zh"),
+    ("hindi", "hi"),
+    ("polish", "pl"),
+    ("swedish", "sv"),
+    ("swe", "sv"),
+    ("norwegian", "no"),
+    ("danish", "da"),
+    ("finnish", "fi"),
+    ("portuguese", "pt"),
+    ("hungarian", "hu"),
+    ("czech", "cs"),
+    ("turkish", "tr"),
+];
+
+/// Audio languages read from a release title
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReleaseLanguages {
+    /// ISO 639-1 codes, in the order they appear
+    pub languages: Vec<&'static str>,
+    /// Tagged `MULTi` or `DUAL`
+    pub multi: bool,
+}
+
+impl ReleaseLanguages {
+    /// Parse the language tags of a release title
+    pub fn from_title(title: &str) -> Self {
+        let mut parsed = Self::default();
+        for token in title
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .map(|t| t.to_ascii_lowercase())
+        {
+            if token == "multi" || token == "dual" {
+                parsed.multi = true;
+            } else if let Some((_, code)) =
+                RELEASE_LANGUAGE_TAGS.iter().find(|(tag, _)| *tag == token)
+                && !parsed.languages.contains(code)
+            {
+                parsed.languages.push(code);
+            }
+        }
+        parsed
+    }
+
+    /// Whether the release has audio in a language (ISO 639-1)
+    pub fn has(&self, language: &str) -> bool {
+        let assumed_english = self.multi || self.languages.is_empty();
+        self.languages.contains(&language) || (assumed_english && language == "en")
+    }
+}
+
+/// Normalized ISO 639-1 codes of a profile's language list, skipping
+/// anything unrecognized
+fn normalized(languages: &[String]) -> Vec<&'static str> {
+    languages.iter().filter_map(|l| iso639_1(l)).collect()
+}
+
+/// Why a release doesn't satisfy the profile, or None if it does
+pub fn release_rejection(profile: &LanguageProfileRecord, title: &str) -> Option<String> {
+    let release = ReleaseLanguages::from_title(title);
+    let missing: Vec<&str> = normalized(&profile.required_audio_languages)
+        .into_iter()
+        .filter(|lang| !release.has(lang))
+        .collect();
+
+    if missing.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "missing required audio language(s) {} for profile '{}'",
+            missing.join(", "),
+            profile.name
+        ))
+    }
+}
+
+/// Ranking bonus for a release's audio languages
+///
+/// The most preferred language the release has earns the most: with three
+/// preferred languages, the first scores 30, the second 20 and the third 10.
+pub fn release_score(profile: &LanguageProfileRecord, title: &str) -> i32 {
+    let release = ReleaseLanguages::from_title(title);
+    let preferred = normalized(&profile.preferred_audio_languages);
+    preferred
+        .iter()
+        .position(|lang| release.has(lang))
+        .map(|i| ((preferred.len() - i) * 10) as i32)
+        .unwrap_or(0)
+}
+
+/// Languages an imported file is missing according to its profile
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageCheck {
+    /// Required audio languages with no matching audio track
+    pub missing_audio: Vec<String>,
+    /// Required subtitle languages with no embedded or external subtitle
+    pub missing_subtitles: Vec<String>,
+}
+
+impl LanguageCheck {
+    pub fn is_satisfied(&self) -> bool {
+        self.missing_audio.is_empty() && self.missing_subtitles.is_empty()
+    }
+}
+
+/// Compare a file's audio and subtitle languages with a profile
+///
+/// Track languages are compared in any tagged form ("eng", "en",
+/// "English"). Untagged audio tracks are not assumed to be anything.
+pub fn verify_file(
+    profile: &LanguageProfileRecord,
+    audio_languages: &[String],
+    subtitle_languages: &[String],
+) -> LanguageCheck {
+    let audio = normalized(audio_languages);
+    let subtitles = normalized(subtitle_languages);
+
+    LanguageCheck {
+        missing_audio: normalized(&profile.required_audio_languages)
+            .into_iter()
+            .filter(|lang| !audio.contains(lang))
+            .map(str::to_string)
+            .collect(),
+        missing_subtitles: normalized(&profile.required_subtitle_languages)
+            .into_iter()
+            .filter(|lang| !subtitles.contains(lang))
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// Notification text for a file missing required audio languages
+pub fn missing_audio_message(
+    profile: &LanguageProfileRecord,
+    file_name: &str,
+    release_title: Option<&str>,
+    missing_audio: &[String],
+) -> String {
+    let languages = missing_audio
+        .iter()
+        .map(|l| l.to_uppercase())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut message = format!(
+        "'{}' has no {} audio, which the '{}' language profile requires.",
+        file_name, languages, profile.name
+    );
+    if let Some(release) = release_title {
+        message.push_str(&format!(
+            " It came from '{}', whose language tags are wrong.",
+            release
+        ));
+    }
+    message.push_str(if profile.regrab_missing_audio {
+        " Auto-hunt will search for another release."
+    } else {
+        " Replace the file, or turn on re-grabbing in the language profile."
+    });
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn profile(
+        preferred: &[&str],
+        required_audio: &[&str],
+        subs: &[&str],
+    ) -> LanguageProfileRecord {
+        let list = |l: &[&str]| l.iter().map(|s| s.to_string()).collect();
+        LanguageProfileRecord {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Test".to_string(),
+            preferred_audio_languages: list(preferred),
+            required_audio_languages: list(required_audio),
+            required_subtitle_languages: list(subs),
+            regrab_missing_audio: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_release_languages_from_title() {
+        let untagged = ReleaseLanguages::from_title("Show.S01E01.1080p.WEB.h264-GRP");
+        assert!(untagged.languages.is_empty());
+        assert!(untagged.has("en"));
+        assert!(!untagged.has("fr"));
+
+        let french = ReleaseLanguages::from_title("Movie.2020.FRENCH.1080p.BluRay.x264-GRP");
+        assert_eq!(french.languages, vec!["fr"]);
+        assert!(!french.has("en"));
+
+        let multi = ReleaseLanguages::from_title("Movie.2020.MULTi.GERMAN.2160p.UHD-GRP");
+        assert!(multi.has("de") && multi.has("en"));
+
+        // "It" is a title here, not Italian
+        assert!(
+            ReleaseLanguages::from_title("It.2017.1080p")
+                .languages
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_release_rejection_and_score() {
+        let p = profile(&["fr", "en"], &["fr"], &[]);
+        assert!(release_rejection(&p, "Movie.2020.1080p.WEB-GRP").is_some());
+        assert!(release_rejection(&p, "Movie.2020.TRUEFRENCH.1080p.WEB-GRP").is_none());
+        assert!(release_rejection(&p, "Movie.2020.MULTi.VFF.1080p.WEB-GRP").is_none());
+
+        assert_eq!(release_score(&p, "Movie.2020.FRENCH.1080p"), 20);
+        assert_eq!(release_score(&p, "Movie.2020.1080p"), 10);
+        assert_eq!(release_score(&p, "Movie.2020.GERMAN.1080p"), 0);
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let p = profile(&[], &["en"], &["en", "Spanish"]);
+        let check = verify_file(
+            &p,
+            &["eng".to_string(), "jpn".to_string()],
+            &["English".to_string()],
+        );
+        assert!(check.missing_audio.is_empty());
+        assert_eq!(check.missing_subtitles, vec!["es".to_string()]);
+        assert!(!check.is_satisfied());
+
+        let check = verify_file(&p, &[], &["en".to_string(), "spa".to_string()]);
+        assert_eq!(check.missing_audio, vec!["en".to_string()]);
+        assert!(check.missing_subtitles.is_empty());
+    }
+
+    #[test]
+    fn test_missing_audio_message() {
+        let mut p = profile(&[], &["fr", "de"], &[]);
+        let missing = vec!["fr".to_string(), "de".to_string()];
+        assert_eq!(
+            missing_audio_message(&p, "Movie.mkv", Some("Movie.2020.MULTi-GRP"), &missing),
+            "'Movie.mkv' has no FR, DE audio, which the 'Test' language profile requires. \
+             It came from 'Movie.2020.MULTi-GRP', whose language tags are wrong. \
+             Replace the file, or turn on re-grabbing in the language profile."
+        );
+
+        p.regrab_missing_audio = true;
+        assert!(
+            missing_audio_message(&p, "Movie.mkv", None, &missing)
+                .ends_with("requires. Auto-hunt will search for another release.")
+        );
+    }
+}
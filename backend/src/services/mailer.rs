@@ -0,0 +1,184 @@
+//! Outgoing email over SMTP
+//!
+//! Used for account mail such as email change confirmations. Configured from
+//! the environment; when `SMTP_HOST` isn't set there is no mailer and
+//! features that need one report that email isn't configured.
+
+use anyhow::{Context, Result, anyhow};
+use lettre::message::{Mailbox, header::ContentType};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+/// How the SMTP connection is secured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    StartTls,
+    /// TLS from the first byte (usually port 465)
+    Tls,
+    /// No encryption, for a relay on localhost
+    None,
+}
+
+impl SmtpTls {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "starttls" | "" => Some(Self::StartTls),
+            "tls" | "ssl" | "smtps" => Some(Self::Tls),
+            "none" | "off" | "plain" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// The usual port for this mode
+    pub fn default_port(&self) -> u16 {
+        match self {
+            Self::StartTls => 587,
+            Self::Tls => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+/// SMTP settings
+#[derive(Debug, Clone)]
+pub struct MailerConfig {
+    pub host: String,
+    pub port: u16,
+    pub tls: SmtpTls,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Sender address, e.g. "Librarian <librarian@example.com>"
+    pub from: String,
+}
+
+impl MailerConfig {
+    /// Read SMTP_HOST, SMTP_PORT, SMTP_TLS, SMTP_USERNAME, SMTP_PASSWORD and
+    /// SMTP_FROM. Returns None when SMTP_HOST isn't set.
+    pub fn from_env() -> Result<Option<Self>> {
+        let Some(host) = std::env::var("SMTP_HOST")
+            .ok()
+            .filter(|h| !h.trim().is_empty())
+        else {
+            return Ok(None);
+        };
+
+        let tls_value = std::env::var("SMTP_TLS").unwrap_or_default();
+        let tls = SmtpTls::parse(&tls_value).ok_or_else(|| {
+            anyhow!(
+                "Invalid SMTP_TLS '{}': use starttls, tls or none",
+                tls_value
+            )
+        })?;
+        let port = match std::env::var("SMTP_PORT") {
+            Ok(port) => port
+                .parse()
+                .with_context(|| format!("Invalid SMTP_PORT '{}'", port))?,
+            Err(_) => tls.default_port(),
+        };
+        let username = std::env::var("SMTP_USERNAME")
+            .ok()
+            .filter(|u| !u.is_empty());
+        let password = std::env::var("SMTP_PASSWORD").ok();
+        let from = std::env::var("SMTP_FROM")
+            .ok()
+            .or_else(|| username.clone())
+            .ok_or_else(|| anyhow!("SMTP_FROM must be set when SMTP_HOST is"))?;
+
+        Ok(Some(Self {
+            host,
+            port,
+            tls,
+            username,
+            password,
+            from,
+        }))
+    }
+}
+
+/// Sends plain-text mail through the configured SMTP server
+pub struct Mailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl Mailer {
+    pub fn new(config: MailerConfig) -> Result<Self> {
+        let builder = match config.tls {
+            SmtpTls::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+            }
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        };
+        let mut builder = builder.port(config.port);
+        if let Some(username) = config.username {
+            builder = builder.credentials(Credentials::new(
+                username,
+                config.password.unwrap_or_default(),
+            ));
+        }
+
+        let from = config
+            .from
+            .parse()
+            .with_context(|| format!("Invalid SMTP_FROM '{}'", config.from))?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+
+    /// Build a mailer from the environment, or None if SMTP isn't configured
+    pub fn from_env() -> Result<Option<Self>> {
+        MailerConfig::from_env()?.map(Self::new).transpose()
+    }
+
+    /// Connect to the SMTP server and check it accepts the credentials
+    pub async fn test_connection(&self) -> Result<()> {
+        let connected = self
+            .transport
+            .test_connection()
+            .await
+            .context("Failed to connect to SMTP server")?;
+        if !connected {
+            anyhow::bail!("SMTP server didn't accept the connection");
+        }
+        Ok(())
+    }
+
+    /// Send a plain-text message
+    pub async fn send(&self, to: &str, subject: &str, body: &str) -> Result<()> {
+        let to: Mailbox = to
+            .parse()
+            .with_context(|| format!("Invalid recipient '{}'", to))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())?;
+
+        self.transport
+            .send(message)
+            .await
+            .context("Failed to send email")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smtp_tls_parse() {
+        assert_eq!(SmtpTls::parse(""), Some(SmtpTls::StartTls));
+        assert_eq!(SmtpTls::parse("STARTTLS"), Some(SmtpTls::StartTls));
+        assert_eq!(SmtpTls::parse("ssl"), Some(SmtpTls::Tls));
+        assert_eq!(SmtpTls::parse("none"), Some(SmtpTls::None));
+        assert_eq!(SmtpTls::parse("maybe"), None);
+        assert_eq!(SmtpTls::Tls.default_port(), 465);
+    }
+}
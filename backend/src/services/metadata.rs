@@ -1,9 +1,10 @@
 //! Unified metadata service
 //!
 //! Provides a unified interface for fetching TV show metadata from multiple providers.
-//! Priority: TVMaze (free, no key) → TMDB (if configured) → TheTVDB (if configured)
+//! Shows are searched on TVMaze (free, no key); their details are filled in from
+//! TMDB and TheTVDB when configured (see `metadata_chain`).
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
@@ -11,16 +12,18 @@ use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::artwork::{ArtworkService, ArtworkType};
+use super::artwork::{ArtworkService, ArtworkType, parse_artwork_url};
 use super::audible::AudiobookMetadataClient;
 use super::cache::{SharedCache, create_cache};
 use super::filename_parser::{ParsedEpisode, parse_episode};
+use super::metadata_chain::{MissingMetadata, backfill_delay, merge_show_details, with_timeout};
 use super::musicbrainz::{MusicBrainzClient, MusicBrainzReleaseGroup};
-use super::tmdb::{TmdbClient, normalize_movie_status};
+use super::tmdb::{TmdbClient, TmdbCredits, TmdbMovie, TmdbTvShow, normalize_movie_status};
+use super::tvdb::{TvdbClient, TvdbSeries};
 use super::tvmaze::{TvMazeClient, TvMazeEpisode, TvMazeScheduleEntry, TvMazeShow};
 use crate::db::{
-    AlbumRecord, AudiobookRecord, CreateEpisode, CreateMovie, CreateTvShow, Database, MovieRecord,
-    TvShowRecord,
+    AlbumRecord, AudiobookRecord, CreateCredit, CreateEpisode, CreateMovie, CreateTvShow, Database,
+    FillTvShowMetadata, MovieRecord, TvShowRecord,
 };
 
 /// Metadata provider enum
@@ -124,6 +127,8 @@ pub struct MovieDetails {
     pub imdb_id: Option<String>,
     pub director: Option<String>,
     pub cast_names: Vec<String>,
+    /// Directors and top billed cast
+    pub credits: Vec<PersonCredit>,
     pub production_countries: Vec<String>,
     pub spoken_languages: Vec<String>,
     pub vote_average: Option<f64>,
@@ -135,6 +140,32 @@ pub struct MovieDetails {
     pub release_date: Option<String>,
 }
 
+/// A person credited on a movie or show
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PersonCredit {
+    /// TMDB person ID
+    pub tmdb_id: i32,
+    pub name: String,
+    pub profile_url: Option<String>,
+    /// 'cast' or 'director'
+    pub role: String,
+    pub character: Option<String>,
+    pub billing_order: Option<i32>,
+}
+
+impl From<&PersonCredit> for CreateCredit {
+    fn from(credit: &PersonCredit) -> Self {
+        Self {
+            tmdb_id: credit.tmdb_id,
+            name: credit.name.clone(),
+            profile_url: credit.profile_url.clone(),
+            role: credit.role.clone(),
+            character: credit.character.clone(),
+            billing_order: credit.billing_order,
+        }
+    }
+}
+
 /// Options for adding a movie from a metadata provider
 #[derive(Debug, Clone)]
 pub struct AddMovieOptions {
@@ -250,6 +281,8 @@ pub struct MetadataService {
     tvmaze: TvMazeClient,
     /// TMDB client - wrapped in RwLock to allow dynamic reloading when settings change
     tmdb: RwLock<Option<TmdbClient>>,
+    /// TheTVDB client - reloaded with the TMDB client
+    tvdb: RwLock<Option<TvdbClient>>,
     musicbrainz: MusicBrainzClient,
     openlibrary: AudiobookMetadataClient,
     config: RwLock<MetadataServiceConfig>,
@@ -262,6 +295,9 @@ pub struct MetadataService {
 /// Default cache TTL for TVMaze schedule: 30 minutes
 const SCHEDULE_CACHE_TTL: Duration = Duration::from_secs(30 * 60);
 
+/// Top billed cast members stored as credits
+const CREDITED_CAST: usize = 20;
+
 impl MetadataService {
     pub fn new(db: Database, config: MetadataServiceConfig) -> Self {
         let tmdb = config
@@ -269,10 +305,16 @@ impl MetadataService {
             .as_ref()
             .filter(|k| !k.is_empty())
             .map(|k| TmdbClient::new(k.clone()));
+        let tvdb = config
+            .tvdb_api_key
+            .as_ref()
+            .filter(|k| !k.is_empty())
+            .map(|k| TvdbClient::new(k.clone()));
 
         Self {
             tvmaze: TvMazeClient::new(),
             tmdb: RwLock::new(tmdb),
+            tvdb: RwLock::new(tvdb),
             musicbrainz: MusicBrainzClient::new_default(),
             openlibrary: AudiobookMetadataClient::new(),
             config: RwLock::new(config),
@@ -298,10 +340,16 @@ impl MetadataService {
             .as_ref()
             .filter(|k| !k.is_empty())
             .map(|k| TmdbClient::new(k.clone()));
+        let tvdb = config
+            .tvdb_api_key
+            .as_ref()
+            .filter(|k| !k.is_empty())
+            .map(|k| TvdbClient::new(k.clone()));
 
         Self {
             tvmaze: TvMazeClient::new(),
             tmdb: RwLock::new(tmdb),
+            tvdb: RwLock::new(tvdb),
             musicbrainz: MusicBrainzClient::new_default(),
             openlibrary: AudiobookMetadataClient::new(),
             config: RwLock::new(config),
@@ -316,6 +364,23 @@ impl MetadataService {
         self.tmdb.read().await.is_some()
     }
 
+    /// Check if TheTVDB is configured
+    pub async fn has_tvdb(&self) -> bool {
+        self.tvdb.read().await.is_some()
+    }
+
+    /// Check TMDB is reachable with the configured key
+    pub async fn ping_tmdb(&self) -> Result<()> {
+        let client = self.tmdb.read().await.clone();
+        client.context("TMDB is not configured")?.ping().await
+    }
+
+    /// Check TheTVDB is reachable with the configured key
+    pub async fn ping_tvdb(&self) -> Result<()> {
+        let client = self.tvdb.read().await.clone();
+        client.context("TheTVDB is not configured")?.ping().await
+    }
+
     /// Reload configuration from the database
     /// 
     /// This should be called when settings are updated via the UI to pick up
@@ -355,12 +420,13 @@ impl MetadataService {
         // Update config
         let new_config = MetadataServiceConfig {
             tmdb_api_key: tmdb_api_key.clone(),
-            tvdb_api_key,
+            tvdb_api_key: tvdb_api_key.clone(),
             openai_api_key,
         };
 
-        // Update TMDB client
+        // Update TMDB and TheTVDB clients
         let new_tmdb = tmdb_api_key.map(|k| TmdbClient::new(k));
+        let new_tvdb = tvdb_api_key.map(TvdbClient::new);
         
         // Update both under write locks
         {
@@ -379,6 +445,7 @@ impl MetadataService {
                 warn!("TMDB client disabled after config reload (API key removed)");
             }
         }
+        *self.tvdb.write().await = new_tvdb;
 
         Ok(())
     }
@@ -488,12 +555,17 @@ impl MetadataService {
                 Ok(self.tvmaze_show_to_details(&show))
             }
             MetadataProvider::Tmdb => {
-                // TODO: Implement TMDB
-                anyhow::bail!("TMDB not yet implemented")
+                let tmdb = self.get_tmdb_client().await?;
+                let show = tmdb.get_tv_show(provider_id as i32).await?;
+                Ok(self.tmdb_show_to_details(&tmdb, &show))
             }
             MetadataProvider::TvDb => {
-                // TODO: Implement TheTVDB
-                anyhow::bail!("TheTVDB not yet implemented")
+                let tvdb = self.tvdb.read().await.clone();
+                let Some(tvdb) = tvdb else {
+                    anyhow::bail!("TheTVDB API key not configured")
+                };
+                let series = tvdb.get_series(provider_id).await?;
+                Ok(self.tvdb_series_to_details(&series))
             }
             MetadataProvider::MusicBrainz => {
                 anyhow::bail!("MusicBrainz is not for TV shows")
@@ -504,6 +576,97 @@ impl MetadataService {
         }
     }
 
+    /// Get show details from a provider, filled in by the other configured
+    /// providers
+    ///
+    /// The primary provider must answer; TMDB and TheTVDB are asked at the
+    /// same time afterwards (they're looked up by the IDs it returns), each
+    /// with its own timeout. Returns the merged details and the providers
+    /// that failed.
+    pub async fn get_show_chained(
+        &self,
+        provider: MetadataProvider,
+        provider_id: u32,
+    ) -> Result<(ShowDetails, Vec<MissingMetadata>)> {
+        let primary = with_timeout(provider, self.get_show(provider, provider_id)).await?;
+
+        let tmdb_client = self.tmdb.read().await.clone();
+        let tvdb_client = self.tvdb.read().await.clone();
+
+        let tmdb_lookup = async {
+            let tmdb = tmdb_client.as_ref()?;
+            if provider == MetadataProvider::Tmdb {
+                return None;
+            }
+            Some(
+                with_timeout(MetadataProvider::Tmdb, async {
+                    let tmdb_id = match primary.tmdb_id {
+                        Some(id) => Some(id as i32),
+                        None => self.find_tmdb_tv_id(tmdb, &primary).await?,
+                    };
+                    match tmdb_id {
+                        Some(id) => {
+                            let show = tmdb.get_tv_show(id).await?;
+                            Ok(Some(self.tmdb_show_to_details(tmdb, &show)))
+                        }
+                        None => Ok(None),
+                    }
+                })
+                .await,
+            )
+        };
+        let tvdb_lookup = async {
+            let tvdb = tvdb_client.as_ref()?;
+            let tvdb_id = primary.tvdb_id?;
+            if provider == MetadataProvider::TvDb {
+                return None;
+            }
+            Some(
+                with_timeout(MetadataProvider::TvDb, tvdb.get_series(tvdb_id))
+                    .await
+                    .map(|series| Some(self.tvdb_series_to_details(&series))),
+            )
+        };
+        let (tmdb_result, tvdb_result) = tokio::join!(tmdb_lookup, tvdb_lookup);
+
+        // Priority order: TMDB, then TheTVDB, then the primary provider
+        let mut sources = Vec::new();
+        let mut failed = Vec::new();
+        for (part, result) in [
+            (MissingMetadata::Tmdb, tmdb_result),
+            (MissingMetadata::Tvdb, tvdb_result),
+        ] {
+            match result {
+                Some(Ok(Some(details))) => sources.push(details),
+                Some(Ok(None)) | None => {}
+                Some(Err(e)) => {
+                    warn!(
+                        provider_id,
+                        source = part.as_str(),
+                        error = %e,
+                        "Metadata provider failed, continuing without it"
+                    );
+                    failed.push(part);
+                }
+            }
+        }
+
+        Ok((merge_show_details(primary, &sources), failed))
+    }
+
+    /// Find a show's TMDB ID from its TheTVDB or IMDb ID
+    async fn find_tmdb_tv_id(&self, tmdb: &TmdbClient, show: &ShowDetails) -> Result<Option<i32>> {
+        if let Some(tvdb_id) = show.tvdb_id
+            && let Some(id) = tmdb.find_tv_id(&tvdb_id.to_string(), "tvdb_id").await?
+        {
+            return Ok(Some(id));
+        }
+        match &show.imdb_id {
+            Some(imdb_id) => tmdb.find_tv_id(imdb_id, "imdb_id").await,
+            None => Ok(None),
+        }
+    }
+
     /// Get episodes for a show from a provider
     pub async fn get_episodes(
         &self,
@@ -583,32 +746,119 @@ impl MetadataService {
 
         let results: Vec<MovieSearchResult> = movies
             .into_iter()
-            .map(|m| {
-                // Compute year before moving fields
-                let year = m.year();
-                let poster_url = tmdb.poster_url(m.poster_path.as_deref());
-                let backdrop_url = tmdb.backdrop_url(m.backdrop_path.as_deref());
-
-                MovieSearchResult {
-                    provider: MetadataProvider::Tmdb,
-                    provider_id: m.id as u32,
-                    title: m.title,
-                    original_title: m.original_title,
-                    year,
-                    overview: m.overview,
-                    poster_url,
-                    backdrop_url,
-                    imdb_id: m.imdb_id,
-                    vote_average: m.vote_average,
-                    popularity: m.popularity,
-                }
-            })
+            .map(|m| Self::movie_search_result(&tmdb, m))
             .collect();
 
         debug!(count = results.len(), "Found movies");
         Ok(results)
     }
 
+    /// Directors and top billed cast from TMDB credits
+    fn person_credits(tmdb: &TmdbClient, credits: &TmdbCredits) -> Vec<PersonCredit> {
+        let profile_url = |path: Option<&str>| path.map(|p| tmdb.image_url(p, "w185"));
+        let directors = credits.directors().into_iter().map(|c| PersonCredit {
+            tmdb_id: c.id,
+            name: c.name.clone(),
+            profile_url: profile_url(c.profile_path.as_deref()),
+            role: "director".to_string(),
+            character: None,
+            billing_order: None,
+        });
+        let cast = credits
+            .top_cast_members(CREDITED_CAST)
+            .into_iter()
+            .map(|c| PersonCredit {
+                tmdb_id: c.id,
+                name: c.name.clone(),
+                profile_url: profile_url(c.profile_path.as_deref()),
+                role: "cast".to_string(),
+                character: c.character.clone().filter(|ch| !ch.is_empty()),
+                billing_order: c.order,
+            });
+        directors.chain(cast).collect()
+    }
+
+    /// Store a movie's credits, replacing any it had
+    pub async fn save_movie_credits(&self, movie_id: Uuid, credits: &[PersonCredit]) -> Result<()> {
+        let credits: Vec<CreateCredit> = credits.iter().map(CreateCredit::from).collect();
+        self.db.people().set_movie_credits(movie_id, &credits).await
+    }
+
+    /// Fetch a show's credits from TMDB and store them, replacing any it had
+    pub async fn sync_show_credits(&self, tv_show_id: Uuid, tmdb_id: u32) -> Result<()> {
+        let tmdb = self.get_tmdb_client().await?;
+        let credits = tmdb.get_tv_credits(tmdb_id as i32).await?;
+        let credits: Vec<CreateCredit> = Self::person_credits(&tmdb, &credits)
+            .iter()
+            .map(CreateCredit::from)
+            .collect();
+        self.db
+            .people()
+            .set_show_credits(tv_show_id, &credits)
+            .await
+    }
+
+    /// Movies a person directed, most popular first
+    pub async fn get_directed_movies(&self, person_tmdb_id: u32) -> Result<Vec<MovieSearchResult>> {
+        let tmdb = self.get_tmdb_client().await?;
+        let credits = tmdb.get_person_movie_credits(person_tmdb_id as i32).await?;
+
+        let mut movies: Vec<TmdbMovie> = credits
+            .crew
+            .into_iter()
+            .filter(|c| c.job.as_deref() == Some("Director"))
+            .map(|c| c.movie)
+            .collect();
+        movies.sort_by(|a, b| {
+            b.popularity
+                .unwrap_or(0.0)
+                .total_cmp(&a.popularity.unwrap_or(0.0))
+        });
+        let mut seen = std::collections::HashSet::new();
+        movies.retain(|m| seen.insert(m.id));
+
+        Ok(movies
+            .into_iter()
+            .map(|m| Self::movie_search_result(&tmdb, m))
+            .collect())
+    }
+
+    /// Get movies TMDB recommends for a movie, falling back to similar titles
+    pub async fn get_movie_recommendations(&self, tmdb_id: u32) -> Result<Vec<MovieSearchResult>> {
+        let tmdb = self.get_tmdb_client().await?;
+
+        let mut movies = tmdb.get_recommendations(tmdb_id as i32).await?;
+        if movies.is_empty() {
+            movies = tmdb.get_similar(tmdb_id as i32).await?;
+        }
+
+        Ok(movies
+            .into_iter()
+            .map(|m| Self::movie_search_result(&tmdb, m))
+            .collect())
+    }
+
+    fn movie_search_result(tmdb: &TmdbClient, m: TmdbMovie) -> MovieSearchResult {
+        // Compute year before moving fields
+        let year = m.year();
+        let poster_url = tmdb.poster_url(m.poster_path.as_deref());
+        let backdrop_url = tmdb.backdrop_url(m.backdrop_path.as_deref());
+
+        MovieSearchResult {
+            provider: MetadataProvider::Tmdb,
+            provider_id: m.id as u32,
+            title: m.title,
+            original_title: m.original_title,
+            year,
+            overview: m.overview,
+            poster_url,
+            backdrop_url,
+            imdb_id: m.imdb_id,
+            vote_average: m.vote_average,
+            popularity: m.popularity,
+        }
+    }
+
     /// Get movie details from TMDB
     pub async fn get_movie(&self, tmdb_id: u32) -> Result<MovieDetails> {
         debug!("Fetching movie details from TMDB (ID: {})", tmdb_id);
@@ -622,6 +872,10 @@ impl MetadataService {
         let credits = tmdb.get_credits(tmdb_id as i32).await.ok();
         let director = credits.as_ref().and_then(|c| c.director());
         let cast_names = credits.as_ref().map(|c| c.top_cast(10)).unwrap_or_default();
+        let person_credits = credits
+            .as_ref()
+            .map(|c| Self::person_credits(&tmdb, c))
+            .unwrap_or_default();
 
         // Fetch release dates for certification
         let release_dates = tmdb.get_release_dates(tmdb_id as i32).await.ok();
@@ -655,6 +909,7 @@ impl MetadataService {
             imdb_id: movie.imdb_id.clone(),
             director,
             cast_names,
+            credits: person_credits,
             production_countries: movie.country_codes(),
             spoken_languages: movie.language_codes(),
             vote_average: movie.vote_average,
@@ -777,6 +1032,13 @@ impl MetadataService {
 
         info!("Added movie '{}' to library", movie.title);
 
+        if let Err(e) = self
+            .save_movie_credits(movie.id, &movie_details.credits)
+            .await
+        {
+            warn!(movie_id = %movie.id, error = %e, "Failed to save movie credits");
+        }
+
         Ok(movie)
     }
 
@@ -897,6 +1159,11 @@ impl MetadataService {
         self.musicbrainz.get_release_group(musicbrainz_id).await
     }
 
+    /// Get an album's front cover URL from the Cover Art Archive
+    pub async fn get_album_cover(&self, musicbrainz_id: Uuid) -> Result<Option<String>> {
+        self.musicbrainz.get_cover_art(musicbrainz_id).await
+    }
+
     /// Add an album from MusicBrainz to a library
     ///
     /// This fetches album metadata, cover art, and track listings from MusicBrainz.
@@ -1327,46 +1594,26 @@ impl MetadataService {
             }
         }
 
-        // Get show details from provider
-        let show_details = self.get_show(options.provider, options.provider_id).await?;
-
-        // Cache artwork if artwork service is available
-        let (cached_poster_url, cached_backdrop_url) =
-            if let Some(ref artwork_service) = self.artwork_service {
-                let entity_id = format!("{}_{}", options.provider_id, options.library_id);
-
-                let poster_url = artwork_service
-                    .cache_image_optional(
-                        show_details.poster_url.as_deref(),
-                        ArtworkType::Poster,
-                        "show",
-                        &entity_id,
-                    )
-                    .await;
-
-                let backdrop_url = artwork_service
-                    .cache_image_optional(
-                        show_details.backdrop_url.as_deref(),
-                        ArtworkType::Backdrop,
-                        "show",
-                        &entity_id,
-                    )
-                    .await;
-
-                debug!(
-                    "Cached artwork for show (poster: {}, backdrop: {})",
-                    poster_url.is_some(),
-                    backdrop_url.is_some()
-                );
+        // Get show details from the provider chain; only the primary provider
+        // failing fails the add
+        let (show_details, mut missing) = self
+            .get_show_chained(options.provider, options.provider_id)
+            .await?;
 
-                (poster_url, backdrop_url)
-            } else {
-                // No artwork service, use original URLs
-                (
-                    show_details.poster_url.clone(),
-                    show_details.backdrop_url.clone(),
-                )
-            };
+        // Fetch episodes and cache artwork at the same time
+        let entity_id = format!("{}_{}", options.provider_id, options.library_id);
+        let (episodes, (cached_poster_url, cached_backdrop_url)) = tokio::join!(
+            with_timeout(
+                options.provider,
+                self.get_episodes(options.provider, options.provider_id)
+            ),
+            self.cache_show_artwork(&show_details, &entity_id),
+        );
+        if self.is_uncached_artwork(cached_poster_url.as_deref())
+            || self.is_uncached_artwork(cached_backdrop_url.as_deref())
+        {
+            missing.push(MissingMetadata::Artwork);
+        }
 
         // Create the TV show in the database
         let tv_shows_repo = self.db.tv_shows();
@@ -1414,51 +1661,19 @@ impl MetadataService {
 
         info!("Added TV show '{}' to library", tv_show.name);
 
-        // Fetch and create episodes
-        match self
-            .get_episodes(options.provider, options.provider_id)
-            .await
+        // Credits come from TMDB whichever provider the show was found on
+        if let Some(tmdb_id) = show_details.tmdb_id
+            && let Err(e) = self.sync_show_credits(tv_show.id, tmdb_id).await
         {
-            Ok(episodes) => {
-                let episodes_repo = self.db.episodes();
-                let mut created_count = 0;
-
-                for ep in episodes {
-                    match episodes_repo
-                        .create(CreateEpisode {
-                            tv_show_id: tv_show.id,
-                            season: ep.season,
-                            episode: ep.episode,
-                            absolute_number: ep.absolute_number,
-                            title: ep.title,
-                            overview: ep.overview,
-                            air_date: ep.air_date.and_then(|d| {
-                                chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()
-                            }),
-                            runtime: ep.runtime,
-                            tvmaze_id: if options.provider == MetadataProvider::TvMaze {
-                                Some(ep.provider_id as i32)
-                            } else {
-                                None
-                            },
-                            tmdb_id: None,
-                            tvdb_id: None,
-                        })
-                        .await
-                    {
-                        Ok(_) => created_count += 1,
-                        Err(e) => {
-                            warn!(
-                                show_id = %tv_show.id,
-                                season = ep.season,
-                                episode = ep.episode,
-                                error = %e,
-                                "Failed to create episode"
-                            );
-                        }
-                    }
-                }
+            warn!(show_id = %tv_show.id, error = %e, "Failed to save show credits");
+        }
 
+        // Create episodes
+        match episodes {
+            Ok(episodes) => {
+                let created_count = self
+                    .create_episodes(&tv_show, options.provider, episodes)
+                    .await;
                 info!("Created {} episodes for '{}'", created_count, tv_show.name);
             }
             Err(e) => {
@@ -1467,6 +1682,24 @@ impl MetadataService {
                     error = %e,
                     "Failed to fetch episodes, show created without episodes"
                 );
+                missing.push(MissingMetadata::Episodes);
+            }
+        }
+
+        // Leave what's missing for the metadata backfill job
+        if !missing.is_empty() {
+            info!(
+                show_id = %tv_show.id,
+                missing = ?missing,
+                "Added '{}' with incomplete metadata, will retry later",
+                tv_show.name
+            );
+            let retry_at = chrono::Utc::now() + backfill_delay(0);
+            if let Err(e) = tv_shows_repo
+                .set_missing_metadata(tv_show.id, &missing_strings(&missing), 0, Some(retry_at))
+                .await
+            {
+                warn!(show_id = %tv_show.id, error = %e, "Failed to record missing metadata");
             }
         }
 
@@ -1497,6 +1730,175 @@ impl MetadataService {
         Ok(tv_show)
     }
 
+    /// Cache a show's poster and backdrop (at the same time)
+    ///
+    /// Images that fail to cache keep their provider URL. Without an artwork
+    /// service the provider URLs are used as they are.
+    async fn cache_show_artwork(
+        &self,
+        details: &ShowDetails,
+        entity_id: &str,
+    ) -> (Option<String>, Option<String>) {
+        let Some(ref artwork_service) = self.artwork_service else {
+            return (details.poster_url.clone(), details.backdrop_url.clone());
+        };
+
+        let (poster_url, backdrop_url) = tokio::join!(
+            artwork_service.cache_image_optional(
+                details.poster_url.as_deref(),
+                ArtworkType::Poster,
+                "show",
+                entity_id,
+            ),
+            artwork_service.cache_image_optional(
+                details.backdrop_url.as_deref(),
+                ArtworkType::Backdrop,
+                "show",
+                entity_id,
+            ),
+        );
+
+        debug!(
+            "Cached artwork for show (poster: {}, backdrop: {})",
+            poster_url.is_some(),
+            backdrop_url.is_some()
+        );
+
+        (poster_url, backdrop_url)
+    }
+
+    /// Whether an artwork URL is a provider URL that should have been cached
+    /// (caching falls back to the provider URL when the download fails)
+    fn is_uncached_artwork(&self, url: Option<&str>) -> bool {
+        self.artwork_service.is_some() && url.is_some_and(|url| parse_artwork_url(url).is_none())
+    }
+
+    /// Create episode records for a show, returning how many were created
+    async fn create_episodes(
+        &self,
+        tv_show: &TvShowRecord,
+        provider: MetadataProvider,
+        episodes: Vec<EpisodeDetails>,
+    ) -> usize {
+        let episodes_repo = self.db.episodes();
+        let mut created_count = 0;
+
+        for ep in episodes {
+            match episodes_repo
+                .create(CreateEpisode {
+                    tv_show_id: tv_show.id,
+                    season: ep.season,
+                    episode: ep.episode,
+                    absolute_number: ep.absolute_number,
+                    title: ep.title,
+                    overview: ep.overview,
+                    air_date: ep
+                        .air_date
+                        .and_then(|d| chrono::NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok()),
+                    runtime: ep.runtime,
+                    tvmaze_id: if provider == MetadataProvider::TvMaze {
+                        Some(ep.provider_id as i32)
+                    } else {
+                        None
+                    },
+                    tmdb_id: None,
+                    tvdb_id: None,
+                })
+                .await
+            {
+                Ok(_) => created_count += 1,
+                Err(e) => {
+                    warn!(
+                        show_id = %tv_show.id,
+                        season = ep.season,
+                        episode = ep.episode,
+                        error = %e,
+                        "Failed to create episode"
+                    );
+                }
+            }
+        }
+
+        created_count
+    }
+
+    /// Retry the metadata a show was added without
+    ///
+    /// Fills fields that are still empty (never overwriting what's there),
+    /// caches artwork the show doesn't have, and creates episodes if their
+    /// fetch failed. Returns the parts that are still missing.
+    pub async fn backfill_show_metadata(
+        &self,
+        tv_show: &TvShowRecord,
+        missing: &[MissingMetadata],
+    ) -> Result<Vec<MissingMetadata>> {
+        let (provider, provider_id) = match (tv_show.tvmaze_id, tv_show.tmdb_id, tv_show.tvdb_id) {
+            (Some(id), _, _) => (MetadataProvider::TvMaze, id as u32),
+            (None, Some(id), _) => (MetadataProvider::Tmdb, id as u32),
+            (None, None, Some(id)) => (MetadataProvider::TvDb, id as u32),
+            (None, None, None) => anyhow::bail!("Show has no provider ID"),
+        };
+
+        let (details, mut still_missing) = self.get_show_chained(provider, provider_id).await?;
+
+        // Artwork the show doesn't have, or only has as a provider URL
+        let entity_id = format!("{}_{}", provider_id, tv_show.library_id);
+        let wanted = ShowDetails {
+            poster_url: details.poster_url.clone().filter(|_| {
+                tv_show.poster_url.is_none()
+                    || self.is_uncached_artwork(tv_show.poster_url.as_deref())
+            }),
+            backdrop_url: details.backdrop_url.clone().filter(|_| {
+                tv_show.backdrop_url.is_none()
+                    || self.is_uncached_artwork(tv_show.backdrop_url.as_deref())
+            }),
+            ..details.clone()
+        };
+        let (poster_url, backdrop_url) = self.cache_show_artwork(&wanted, &entity_id).await;
+        if self.is_uncached_artwork(poster_url.as_deref())
+            || self.is_uncached_artwork(backdrop_url.as_deref())
+        {
+            still_missing.push(MissingMetadata::Artwork);
+        }
+
+        self.db
+            .tv_shows()
+            .fill_missing_metadata(
+                tv_show.id,
+                FillTvShowMetadata {
+                    year: details.year,
+                    overview: details.overview,
+                    network: details.network,
+                    runtime: details.runtime,
+                    genres: details.genres,
+                    poster_url,
+                    backdrop_url,
+                    tmdb_id: details.tmdb_id.map(|id| id as i32),
+                    tvdb_id: details.tvdb_id.map(|id| id as i32),
+                    imdb_id: details.imdb_id,
+                },
+            )
+            .await?;
+
+        if missing.contains(&MissingMetadata::Episodes) {
+            match with_timeout(provider, self.get_episodes(provider, provider_id)).await {
+                Ok(episodes) => {
+                    let created_count = self.create_episodes(tv_show, provider, episodes).await;
+                    info!("Created {} episodes for '{}'", created_count, tv_show.name);
+                    if let Err(e) = self.db.tv_shows().update_stats(tv_show.id).await {
+                        warn!(show_id = %tv_show.id, error = %e, "Failed to update show stats");
+                    }
+                }
+                Err(e) => {
+                    debug!(show_id = %tv_show.id, error = %e, "Episodes still unavailable");
+                    still_missing.push(MissingMetadata::Episodes);
+                }
+            }
+        }
+
+        Ok(still_missing)
+    }
+
     /// Backfill episode availability from cached RSS feed items
     ///
     /// When a show is added to the library, check if we have any cached RSS items
@@ -1646,6 +2048,47 @@ impl MetadataService {
         }
     }
 
+    /// Convert a TMDB TV show to unified ShowDetails
+    fn tmdb_show_to_details(&self, tmdb: &TmdbClient, show: &TmdbTvShow) -> ShowDetails {
+        let external_ids = show.external_ids.as_ref();
+        ShowDetails {
+            provider: MetadataProvider::Tmdb,
+            provider_id: show.id as u32,
+            name: show.name.clone(),
+            year: show.year(),
+            status: normalize_show_status(show.status.as_deref()),
+            network: show.networks.first().map(|n| n.name.clone()),
+            overview: show.overview.clone().filter(|o| !o.is_empty()),
+            genres: show.genres.iter().map(|g| g.name.clone()).collect(),
+            runtime: show.episode_run_time.first().copied(),
+            poster_url: tmdb.original_url(show.poster_path.as_deref()),
+            backdrop_url: tmdb.original_url(show.backdrop_path.as_deref()),
+            tvdb_id: external_ids.and_then(|ids| ids.tvdb_id).map(|id| id as u32),
+            tmdb_id: Some(show.id as u32),
+            imdb_id: external_ids.and_then(|ids| ids.imdb_id.clone()),
+        }
+    }
+
+    /// Convert a TheTVDB series to unified ShowDetails
+    fn tvdb_series_to_details(&self, series: &TvdbSeries) -> ShowDetails {
+        ShowDetails {
+            provider: MetadataProvider::TvDb,
+            provider_id: series.id,
+            name: series.name.clone(),
+            year: series.year(),
+            status: normalize_show_status(series.status.as_ref().and_then(|s| s.name.as_deref())),
+            network: series.original_network.as_ref().map(|n| n.name.clone()),
+            overview: series.overview.clone().filter(|o| !o.is_empty()),
+            genres: series.genre_names(),
+            runtime: series.average_runtime,
+            poster_url: series.image.clone(),
+            backdrop_url: series.backdrop_url().map(String::from),
+            tvdb_id: Some(series.id),
+            tmdb_id: None,
+            imdb_id: None,
+        }
+    }
+
     /// Convert TVMaze episode to unified EpisodeDetails
     fn tvmaze_episode_to_details(&self, episode: &TvMazeEpisode) -> EpisodeDetails {
         EpisodeDetails {
@@ -1684,23 +2127,29 @@ pub fn create_metadata_service_with_artwork(
     ))
 }
 
+/// Missing metadata parts as stored on the show
+pub fn missing_strings(missing: &[MissingMetadata]) -> Vec<String> {
+    missing.iter().map(|m| m.as_str().to_string()).collect()
+}
+
 /// Normalize show status from metadata providers to database-compatible values.
 ///
 /// Database constraint allows: 'continuing', 'ended', 'upcoming', 'cancelled', 'unknown'
 ///
 /// This function maps various provider-specific status strings to these values:
-/// - "Running" (TVMaze) → "continuing"
+/// - "Running" (TVMaze), "Returning Series" (TMDB) → "continuing"
 /// - "Ended" → "ended"  
 /// - "Cancelled"/"Canceled" → "cancelled"
-/// - "To Be Determined"/"TBD"/"In Development" → "upcoming"
+/// - "To Be Determined"/"TBD"/"In Development"/"Planned"/"In Production" → "upcoming"
 /// - Anything else → "unknown"
 pub fn normalize_show_status(status: Option<&str>) -> Option<String> {
     status.map(|s| {
         match s.to_lowercase().as_str() {
-            "running" | "continuing" => "continuing",
+            "running" | "continuing" | "returning series" => "continuing",
             "ended" => "ended",
             "cancelled" | "canceled" => "cancelled",
-            "to be determined" | "tbd" | "in development" | "upcoming" => "upcoming",
+            "to be determined" | "tbd" | "in development" | "upcoming" | "planned"
+            | "in production" | "pilot" => "upcoming",
             _ => "unknown",
         }
         .to_string()
@@ -0,0 +1,194 @@
+//! Show metadata provider chain
+//!
+//! Shows are identified on one provider (usually TVMaze), but TMDB and
+//! TheTVDB often have better overviews and artwork, and TVMaze has no
+//! backdrops. When a show is added, every configured provider is asked at
+//! once, each with its own timeout, and each field comes from the first
+//! provider that has it, in the order TMDB → TheTVDB → TVMaze.
+//!
+//! A provider that fails or times out doesn't fail the add. The show is
+//! created with what was fetched, and the parts still missing are recorded
+//! on it for the metadata backfill job to retry.
+
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+
+use super::metadata::{MetadataProvider, ShowDetails};
+
+/// How long a single provider gets before the chain moves on without it
+pub const PROVIDER_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Retries before the backfill job gives up on a show
+pub const MAX_BACKFILL_ATTEMPTS: i32 = 8;
+
+/// Part of a show's metadata that couldn't be fetched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingMetadata {
+    /// TMDB failed or timed out
+    Tmdb,
+    /// TheTVDB failed or timed out
+    Tvdb,
+    /// The episode list failed or timed out
+    Episodes,
+    /// A poster or backdrop couldn't be cached
+    Artwork,
+}
+
+impl MissingMetadata {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tmdb => "tmdb",
+            Self::Tvdb => "tvdb",
+            Self::Episodes => "episodes",
+            Self::Artwork => "artwork",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "tmdb" => Some(Self::Tmdb),
+            "tvdb" => Some(Self::Tvdb),
+            "episodes" => Some(Self::Episodes),
+            "artwork" => Some(Self::Artwork),
+            _ => None,
+        }
+    }
+}
+
+/// Run a provider request, failing it after [`PROVIDER_TIMEOUT`]
+pub async fn with_timeout<T>(
+    provider: MetadataProvider,
+    request: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    tokio::time::timeout(PROVIDER_TIMEOUT, request)
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "{:?} timed out after {}s",
+                provider,
+                PROVIDER_TIMEOUT.as_secs()
+            )
+        })?
+}
+
+/// Merge show details from several providers
+///
+/// `sources` are in priority order; each field comes from the first source
+/// that has it. The provider and ID stay those of `primary`, which is the
+/// provider the show was picked from, and is used last.
+pub fn merge_show_details(primary: ShowDetails, sources: &[ShowDetails]) -> ShowDetails {
+    let chain: Vec<&ShowDetails> = sources.iter().chain(std::iter::once(&primary)).collect();
+    let first = |field: fn(&ShowDetails) -> Option<&String>| {
+        chain
+            .iter()
+            .filter_map(|s| field(s))
+            .find(|v| !v.trim().is_empty())
+            .cloned()
+    };
+
+    ShowDetails {
+        year: chain.iter().find_map(|s| s.year),
+        status: first(|s| s.status.as_ref()),
+        network: first(|s| s.network.as_ref()),
+        overview: first(|s| s.overview.as_ref()),
+        genres: chain
+            .iter()
+            .map(|s| &s.genres)
+            .find(|g| !g.is_empty())
+            .cloned()
+            .unwrap_or_default(),
+        runtime: chain.iter().find_map(|s| s.runtime),
+        poster_url: first(|s| s.poster_url.as_ref()),
+        backdrop_url: first(|s| s.backdrop_url.as_ref()),
+        tvdb_id: chain.iter().find_map(|s| s.tvdb_id),
+        tmdb_id: chain.iter().find_map(|s| s.tmdb_id),
+        imdb_id: first(|s| s.imdb_id.as_ref()),
+        ..primary
+    }
+}
+
+/// How long to wait before the next backfill attempt: 30 minutes, doubling
+/// up to a day
+pub fn backfill_delay(attempts: i32) -> chrono::Duration {
+    let minutes = 30i64 << attempts.clamp(0, 6);
+    chrono::Duration::minutes(minutes.min(24 * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn details(provider: MetadataProvider, provider_id: u32) -> ShowDetails {
+        ShowDetails {
+            provider,
+            provider_id,
+            name: format!("{:?}", provider),
+            year: None,
+            status: None,
+            network: None,
+            overview: None,
+            genres: Vec::new(),
+            runtime: None,
+            poster_url: None,
+            backdrop_url: None,
+            tvdb_id: None,
+            tmdb_id: None,
+            imdb_id: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_prefers_earlier_sources() {
+        let primary = ShowDetails {
+            overview: Some("From TVMaze".to_string()),
+            poster_url: Some("tvmaze.jpg".to_string()),
+            genres: vec!["Drama".to_string()],
+            tvdb_id: Some(81189),
+            ..details(MetadataProvider::TvMaze, 169)
+        };
+        let tmdb = ShowDetails {
+            overview: Some("  ".to_string()),
+            backdrop_url: Some("tmdb-backdrop.jpg".to_string()),
+            tmdb_id: Some(1396),
+            ..details(MetadataProvider::Tmdb, 1396)
+        };
+        let tvdb = ShowDetails {
+            overview: Some("From TheTVDB".to_string()),
+            poster_url: Some("tvdb.jpg".to_string()),
+            ..details(MetadataProvider::TvDb, 81189)
+        };
+
+        let merged = merge_show_details(primary, &[tmdb, tvdb]);
+        assert_eq!(merged.provider, MetadataProvider::TvMaze);
+        assert_eq!(merged.provider_id, 169);
+        assert_eq!(merged.name, "TvMaze");
+        assert_eq!(merged.overview.as_deref(), Some("From TheTVDB"));
+        assert_eq!(merged.poster_url.as_deref(), Some("tvdb.jpg"));
+        assert_eq!(merged.backdrop_url.as_deref(), Some("tmdb-backdrop.jpg"));
+        assert_eq!(merged.genres, vec!["Drama".to_string()]);
+        assert_eq!(merged.tmdb_id, Some(1396));
+        assert_eq!(merged.tvdb_id, Some(81189));
+    }
+
+    #[test]
+    fn test_backfill_delay() {
+        assert_eq!(backfill_delay(0), chrono::Duration::minutes(30));
+        assert_eq!(backfill_delay(2), chrono::Duration::hours(2));
+        assert_eq!(backfill_delay(20), chrono::Duration::hours(24));
+    }
+
+    #[test]
+    fn test_missing_metadata_round_trip() {
+        for part in [
+            MissingMetadata::Tmdb,
+            MissingMetadata::Tvdb,
+            MissingMetadata::Episodes,
+            MissingMetadata::Artwork,
+        ] {
+            assert_eq!(MissingMetadata::parse(part.as_str()), Some(part));
+        }
+        assert_eq!(MissingMetadata::parse("tvmaze"), None);
+    }
+}
@@ -0,0 +1,128 @@
+//! Deleted-from-disk handling
+//!
+//! When a scan finds that library files have disappeared from disk, the
+//! library's missing-file policy decides what happens to the items they
+//! belonged to. Wanted status is derived from monitoring plus a linked file,
+//! so dropping the file record is what re-adds an item to wanted; keeping it
+//! leaves the item counted as downloaded.
+
+/// What a scan does with library files that are no longer on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingFilePolicy {
+    /// Keep the file records and notify, so nothing is re-downloaded
+    #[default]
+    Flag,
+    /// Remove the file records and stop monitoring the movie or show
+    Unmonitor,
+    /// Remove the file records so monitored items become wanted again and
+    /// auto-hunt can re-download them
+    Rewant,
+}
+
+impl MissingFilePolicy {
+    /// Parse a stored policy (unknown values fall back to flagging)
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "unmonitor" => Self::Unmonitor,
+            "rewant" => Self::Rewant,
+            _ => Self::Flag,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Flag => "flag",
+            Self::Unmonitor => "unmonitor",
+            Self::Rewant => "rewant",
+        }
+    }
+
+    /// Whether the policy removes the missing file records
+    pub fn removes_records(&self) -> bool {
+        !matches!(self, Self::Flag)
+    }
+
+    /// What happened to the affected items, for the notification
+    pub fn outcome(&self) -> &'static str {
+        match self {
+            Self::Flag => "They are still listed in the library and will not be re-downloaded.",
+            Self::Unmonitor => {
+                "Their records were removed and the affected movies and shows are no longer monitored."
+            }
+            Self::Rewant => {
+                "Their records were removed and monitored items are wanted again, so auto-hunt can re-download them."
+            }
+        }
+    }
+}
+
+/// Whether every known file going missing at once looks like an unmounted
+/// share rather than deletions
+///
+/// An empty mount point still exists, so the scan finds no files and every
+/// record would be treated as deleted. Records are left alone in that case.
+pub fn looks_unmounted(discovered_count: usize, missing_count: usize) -> bool {
+    discovered_count == 0 && missing_count > 0
+}
+
+/// Notification body for files that went missing
+pub fn missing_files_message(
+    policy: MissingFilePolicy,
+    library_name: &str,
+    missing: &[String],
+) -> String {
+    const LISTED: usize = 5;
+
+    let mut message = format!(
+        "{} file(s) in '{}' are no longer on disk. {}",
+        missing.len(),
+        library_name,
+        policy.outcome()
+    );
+    for path in missing.iter().take(LISTED) {
+        message.push_str("\n• ");
+        message.push_str(path);
+    }
+    if missing.len() > LISTED {
+        message.push_str(&format!("\n…and {} more", missing.len() - LISTED));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_round_trip() {
+        for policy in [
+            MissingFilePolicy::Flag,
+            MissingFilePolicy::Unmonitor,
+            MissingFilePolicy::Rewant,
+        ] {
+            assert_eq!(MissingFilePolicy::parse(policy.as_str()), policy);
+        }
+        assert_eq!(MissingFilePolicy::parse("bogus"), MissingFilePolicy::Flag);
+        assert!(!MissingFilePolicy::Flag.removes_records());
+        assert!(MissingFilePolicy::Rewant.removes_records());
+    }
+
+    #[test]
+    fn test_looks_unmounted() {
+        assert!(looks_unmounted(0, 12));
+        assert!(!looks_unmounted(0, 0));
+        assert!(!looks_unmounted(40, 12));
+    }
+
+    #[test]
+    fn test_missing_files_message() {
+        let missing: Vec<String> = (1..=7)
+            .map(|i| format!("/tv/Show/S01E0{}.mkv", i))
+            .collect();
+        let message = missing_files_message(MissingFilePolicy::Flag, "TV", &missing);
+        assert!(message.starts_with("7 file(s) in 'TV' are no longer on disk."));
+        assert!(message.contains("/tv/Show/S01E05.mkv"));
+        assert!(!message.contains("/tv/Show/S01E06.mkv"));
+        assert!(message.ends_with("…and 2 more"));
+    }
+}
@@ -4,53 +4,91 @@
 
 #![allow(unused_imports)]
 
+pub mod airplay;
 pub mod artwork;
+pub mod artwork_audit;
+pub mod audit;
 pub mod auth;
 pub mod audible;
+pub mod automation_pause;
+pub mod background_jobs;
+pub mod bandwidth;
 pub mod cache;
 pub mod cast;
+pub mod demo;
+pub mod diagnostics;
+pub mod disc_structure;
 pub mod download_source;
+pub mod event_bus;
 pub mod extractor;
 pub mod ffmpeg;
 pub mod file_matcher;
+pub mod file_permissions;
 pub mod file_processor;
 pub mod file_utils;
 pub mod filename_parser;
 pub mod filesystem;
 pub mod fingerprint;
+pub mod global_search;
+pub mod grab_ledger;
+pub mod http_replay;
 pub mod hunt;
 pub mod job_queue;
+pub mod language_profile;
 pub mod logging;
+pub mod mailer;
 pub mod match_scorer;
 pub mod metadata;
+pub mod metadata_chain;
 pub mod metrics;
+pub mod missing_files;
 pub mod musicbrainz;
 pub mod notifications;
 pub mod ollama;
 pub mod opensubtitles;
 pub mod organizer;
+pub mod par2;
 pub mod quality_evaluator;
 pub mod queues;
 pub mod rate_limiter;
+pub mod recommendations;
+pub mod release_precheck;
 pub mod rss;
+pub mod scan_exclusions;
+pub mod scan_preview;
 pub mod scanner;
+pub mod self_update;
+pub mod service_health;
+pub mod speed_history;
+pub mod subtitle_download;
 pub mod text_utils;
 pub mod tmdb;
 pub mod torrent;
 pub mod torrent_completion_handler;
 pub mod torrent_metadata;
 pub mod track_matcher;
+pub mod tracker_passkey;
+pub mod tracker_stats;
+pub mod transcode;
+pub mod transcode_cache;
+pub mod tvdb;
 pub mod tvmaze;
 pub mod usenet;
+pub mod watch_state;
 
 pub use artwork::ArtworkService;
+pub use artwork_audit::{ArtworkAuditService, ArtworkEntity};
+pub use audit::{AuditAction, AuditService};
 pub use auth::{
     AccessTokenClaims, AuthConfig, AuthService, AuthTokens, AuthenticatedUser, LoginResult,
     RefreshTokenClaims, RegisterInput, verify_token as verify_auth_token,
 };
+pub use background_jobs::{BackgroundJobService, JobContext};
 pub use cast::{
     CastDevicesEvent, CastPlayerState, CastService, CastServiceConfig, CastSessionEvent,
 };
+pub use diagnostics::DiagnosticsService;
+pub use event_bus::EventBus;
 pub use ffmpeg::{
     AudioStream, Chapter, FfmpegService, HdrType, MediaAnalysis, SubtitleStream, VideoStream,
 };
@@ -71,6 +109,7 @@ pub use job_queue::{
     process_in_chunks,
 };
 pub use logging::{DatabaseLoggerConfig, LogEvent, create_database_layer};
+pub use mailer::Mailer;
 pub use metadata::{
     AddMovieOptions, AddTvShowOptions, MetadataProvider, MetadataService, MetadataServiceConfig,
     MovieDetails, MovieSearchResult, create_metadata_service_with_artwork,
@@ -100,10 +139,14 @@ pub use queues::{
     media_analysis_queue_config, subtitle_download_queue_config,
 };
 pub use rate_limiter::{RateLimitConfig, RateLimitedClient, RetryConfig, retry_async};
+pub use recommendations::{
+    Recommendation, RecommendationKind, RecommendationService, RecommendationSource,
+};
 pub use rss::{ParsedRssItem, RssService, validate_url_for_ssrf};
 pub use scanner::{
     ScannerConfig, ScannerService, create_scanner_service, create_scanner_service_with_config,
 };
+pub use service_health::ServiceHealthService;
 pub use text_utils::{
     levenshtein_distance, normalize_quality, normalize_show_name, normalize_show_name_no_articles,
     normalize_title, normalize_track_title, show_name_similarity, string_similarity,
@@ -123,6 +166,7 @@ pub use torrent_metadata::{
     TorrentFileInfo, audio_summary, extract_audio_files, is_single_file_album, parse_torrent_files,
 };
 pub use track_matcher::{MatchType, TrackMatch, TrackMatchResult, match_tracks};
+pub use transcode::TranscodeService;
 pub use hunt::{HuntConfig, HuntSearchResult, HuntService};
 pub use download_source::{DownloadSource, DownloadSourceType, LinkedItem};
 pub use usenet::{UsenetDownloadInfo, UsenetEvent, UsenetService};
@@ -164,6 +164,16 @@ impl NotificationService {
             }
         }
 
+        self.create_without_dedupe(notification).await
+    }
+
+    /// Create a notification without duplicate prevention
+    ///
+    /// For callers that track what they have already sent themselves (e.g. the
+    /// episode air notification ledger), where several notifications in the
+    /// same category are expected.
+    pub async fn create_without_dedupe(&self, notification: CreateNotification) -> Result<NotificationRecord> {
+        let user_id = notification.user_id;
         let record = self.db.notifications().create(notification).await?;
 
         info!(
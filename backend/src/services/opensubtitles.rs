@@ -286,6 +286,32 @@ impl OpenSubtitlesClient {
         Ok(())
     }
 
+    /// Check OpenSubtitles answers and accepts the API key
+    ///
+    /// Doesn't log in: logins are rate limited far more strictly than
+    /// other requests.
+    pub async fn ping(&self) -> Result<()> {
+        let response = self
+            .client
+            .get_with_headers_and_query(
+                &format!("{}/infos/formats", BASE_URL),
+                &[
+                    ("Api-Key", self.api_key.as_str()),
+                    ("User-Agent", self.user_agent.as_str()),
+                ],
+                &[] as &[(&str, &str)],
+            )
+            .await?;
+
+        if response.status().as_u16() == 401 || response.status().as_u16() == 403 {
+            anyhow::bail!("OpenSubtitles API key is invalid");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("OpenSubtitles returned status: {}", response.status());
+        }
+        Ok(())
+    }
+
     /// Check if we're logged in
     pub fn is_logged_in(&self) -> bool {
         self.auth_state.read().is_some()
@@ -507,6 +533,7 @@ impl OpenSubtitlesClient {
             file_name: download_info.file_name,
             content,
             remaining_downloads: download_info.remaining,
+            quota_reset_at: Some(download_info.reset_time_utc),
         })
     }
 
@@ -575,6 +602,8 @@ pub struct DownloadedSubtitle {
     pub content: String,
     /// Remaining downloads for the day
     pub remaining_downloads: i32,
+    /// When the daily quota resets (as reported by the API, in UTC)
+    pub quota_reset_at: Option<String>,
 }
 
 impl DownloadedSubtitle {
@@ -603,6 +632,7 @@ mod tests {
             file_name: "movie.eng.srt".to_string(),
             content: "test".to_string(),
             remaining_downloads: 10,
+            quota_reset_at: None,
         };
         assert_eq!(sub.format(), "srt");
 
@@ -611,6 +641,7 @@ mod tests {
             file_name: "movie.ass".to_string(),
             content: "test".to_string(),
             remaining_downloads: 10,
+            quota_reset_at: None,
         };
         assert_eq!(sub2.format(), "ass");
     }
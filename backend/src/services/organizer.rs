@@ -12,7 +12,10 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use walkdir::WalkDir;
 
-use super::file_utils::{is_video_file, sanitize_for_filename};
+use super::disc_structure;
+use super::file_utils::{
+    is_video_file, path_is_within, paths_equal, relative_path_from_pattern, sanitize_for_filename,
+};
 use crate::db::libraries::LibraryRecord;
 use crate::db::{Database, EpisodeRecord, MediaFileRecord, TvShowRecord};
 
@@ -276,7 +279,7 @@ impl OrganizerService {
         let new_path_str = new_path.to_string_lossy().to_string();
 
         // Skip if already at the correct location
-        if original_path == new_path_str {
+        if paths_equal(&original_path, &new_path_str) {
             debug!(
                 file_id = %file.id,
                 path = %original_path,
@@ -397,7 +400,7 @@ impl OrganizerService {
                         );
 
                         // Delete the source file from disk (if it's different from target)
-                        if original_path != new_path_str && source_path.exists() {
+                        if !paths_equal(&original_path, &new_path_str) && source_path.exists() {
                             if let Err(e) = tokio::fs::remove_file(source_path).await {
                                 warn!(
                                     path = %original_path,
@@ -454,7 +457,7 @@ impl OrganizerService {
         // Determine the effective action:
         // - If source is already in the library folder, use "move" (rename in-place)
         // - If source is outside the library folder, always copy (download pipeline is copy-only)
-        let source_in_library = source_path.starts_with(library_path);
+        let source_in_library = path_is_within(&original_path, library_path);
         let effective_action = if source_in_library {
             // File is already in library - just rename it, don't create duplicates
             "move"
@@ -602,6 +605,19 @@ impl OrganizerService {
             }
         };
 
+        if let Some((disc_root, _)) = disc_structure::disc_root(Path::new(&original_path)) {
+            return self
+                .organize_movie_disc(
+                    file,
+                    movie,
+                    &disc_root,
+                    library_path,
+                    &effective_pattern,
+                    dry_run,
+                )
+                .await;
+        }
+
         let new_path = self.generate_movie_organized_path(
             library_path,
             movie,
@@ -612,7 +628,7 @@ impl OrganizerService {
         let new_path_str = new_path.to_string_lossy().to_string();
 
         // Skip if already at the correct location
-        if original_path == new_path_str {
+        if paths_equal(&original_path, &new_path_str) {
             debug!(
                 file_id = %file.id,
                 path = %original_path,
@@ -735,7 +751,7 @@ impl OrganizerService {
                         );
 
                         // Delete the source file from disk (if it's different from target)
-                        if original_path != new_path_str && source_path.exists() {
+                        if !paths_equal(&original_path, &new_path_str) && source_path.exists() {
                             if let Err(e) = tokio::fs::remove_file(source_path).await {
                                 warn!(
                                     path = %original_path,
@@ -785,7 +801,7 @@ impl OrganizerService {
         // Determine the effective action:
         // - If source is already in the library folder, use "move" (rename in-place)
         // - If source is outside the library folder, always copy (download pipeline is copy-only)
-        let source_in_library = source_path.starts_with(library_path);
+        let source_in_library = path_is_within(&original_path, library_path);
         let effective_action = if source_in_library { "move" } else { "copy" };
 
         if !source_in_library && action != "copy" {
@@ -891,6 +907,164 @@ impl OrganizerService {
         })
     }
 
+    /// Organize a movie stored as a disc folder rip (`VIDEO_TS` or `BDMV`)
+    ///
+    /// The disc folder is moved (or copied, from outside the library) as a
+    /// whole to become the movie's folder, so its structure stays playable.
+    /// The naming pattern only decides the folder; the disc's files keep their
+    /// names. An existing folder at the target is left alone as a conflict.
+    async fn organize_movie_disc(
+        &self,
+        file: &MediaFileRecord,
+        movie: &crate::db::MovieRecord,
+        disc_root: &Path,
+        library_path: &str,
+        naming_pattern: &str,
+        dry_run: bool,
+    ) -> Result<OrganizeResult> {
+        let original_path = file.path.clone();
+        let original_filename = Path::new(&original_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let movie_path = self.generate_movie_organized_path(
+            library_path,
+            movie,
+            original_filename,
+            Some(naming_pattern),
+        );
+        let result = |new_path: &Path, error: Option<String>| OrganizeResult {
+            file_id: file.id,
+            original_path: original_path.clone(),
+            new_path: new_path.to_string_lossy().to_string(),
+            success: error.is_none(),
+            error,
+        };
+
+        let Some(target_root) = movie_path.parent().map(Path::to_path_buf) else {
+            return Ok(result(&movie_path, Some("Invalid movie path".to_string())));
+        };
+        let source_root = disc_root.to_string_lossy().to_string();
+        let target_root_str = target_root.to_string_lossy().to_string();
+
+        // Keep the file's place in the disc structure
+        let new_path = match Path::new(&original_path).strip_prefix(disc_root) {
+            Ok(relative) => target_root.join(relative),
+            Err(_) => {
+                return Ok(result(
+                    &movie_path,
+                    Some("File is not in its disc folder".to_string()),
+                ));
+            }
+        };
+        let new_path_str = new_path.to_string_lossy().to_string();
+
+        if paths_equal(&source_root, &target_root_str) {
+            debug!(
+                file_id = %file.id,
+                path = %source_root,
+                "Movie disc folder already at correct location"
+            );
+            if !file.organized {
+                self.db
+                    .media_files()
+                    .mark_organized(file.id, &original_path, &original_path)
+                    .await?;
+            }
+            return Ok(result(&new_path, None));
+        }
+
+        // The disc has to move as a folder of its own
+        if paths_equal(&source_root, library_path)
+            || paths_equal(&target_root_str, library_path)
+            || path_is_within(&target_root_str, &source_root)
+        {
+            let error_msg = format!(
+                "Can't organize disc folder '{}' to '{}': the naming pattern must give each movie its own folder",
+                source_root, target_root_str
+            );
+            warn!(file_id = %file.id, movie = %movie.title, "{}", error_msg);
+            return Ok(result(&new_path, Some(error_msg)));
+        }
+
+        if dry_run {
+            info!(
+                file_id = %file.id,
+                original = %source_root,
+                new = %target_root_str,
+                "[DRY RUN] Would organize movie disc folder"
+            );
+            return Ok(result(&new_path, None));
+        }
+
+        if target_root.exists() {
+            let error_msg = format!(
+                "Target folder '{}' already exists; not merging disc folder '{}' into it",
+                target_root_str, source_root
+            );
+            warn!(file_id = %file.id, movie = %movie.title, "{}", error_msg);
+            self.db
+                .media_files()
+                .mark_conflicted(file.id, &error_msg)
+                .await?;
+            return Ok(result(&new_path, Some(error_msg)));
+        }
+
+        if let Some(parent) = target_root.parent()
+            && let Err(e) = tokio::fs::create_dir_all(parent).await
+        {
+            return Ok(result(
+                &new_path,
+                Some(format!("Failed to create directory: {}", e)),
+            ));
+        }
+
+        // Same rule as single files: move within the library, copy from outside
+        let effective_action = if path_is_within(&source_root, library_path) {
+            "move"
+        } else {
+            "copy"
+        };
+        let operation_result = match effective_action {
+            "move" => tokio::fs::rename(disc_root, &target_root).await,
+            _ => {
+                let (from, to) = (disc_root.to_path_buf(), target_root.clone());
+                tokio::task::spawn_blocking(move || copy_dir_all(&from, &to)).await?
+            }
+        };
+        if let Err(e) = operation_result {
+            error!(
+                file_id = %file.id,
+                action = %effective_action,
+                error = %e,
+                source = %source_root,
+                target = %target_root_str,
+                "Failed to organize movie disc folder"
+            );
+            return Ok(result(
+                &new_path,
+                Some(format!("Failed to {} disc folder: {}", effective_action, e)),
+            ));
+        }
+
+        self.db
+            .media_files()
+            .mark_organized(file.id, &new_path_str, &original_path)
+            .await?;
+
+        info!(
+            file_id = %file.id,
+            movie = %movie.title,
+            action = %effective_action,
+            original = %source_root,
+            new = %target_root_str,
+            "Organized movie disc '{}' ({}) → {}",
+            movie.title, effective_action, target_root_str
+        );
+
+        Ok(result(&new_path, None))
+    }
+
     /// Generate the organized path for a music album file
     pub fn generate_music_organized_path(
         &self,
@@ -953,7 +1127,7 @@ impl OrganizerService {
         let new_path_str = new_path.to_string_lossy().to_string();
 
         // Skip if already at the correct location
-        if original_path == new_path_str {
+        if paths_equal(&original_path, &new_path_str) {
             debug!(
                 file_id = %file.id,
                 path = %original_path,
@@ -1023,7 +1197,7 @@ impl OrganizerService {
                     );
 
                     // Delete the source file from disk (if it's different from target)
-                    if original_path != new_path_str && source_path.exists() {
+                    if !paths_equal(&original_path, &new_path_str) && source_path.exists() {
                         if let Err(e) = tokio::fs::remove_file(source_path).await {
                             warn!(
                                 path = %original_path,
@@ -1089,7 +1263,7 @@ impl OrganizerService {
         }
 
         // Determine effective action (copy-only for downloads)
-        let source_in_library = source_path.starts_with(library_path);
+        let source_in_library = path_is_within(&original_path, library_path);
         let effective_action = if source_in_library { "move" } else { "copy" };
 
         // Perform file operation
@@ -1245,7 +1419,7 @@ impl OrganizerService {
         let new_path_str = new_path.to_string_lossy().to_string();
 
         // Skip if already at the correct location
-        if original_path == new_path_str {
+        if paths_equal(&original_path, &new_path_str) {
             debug!(
                 file_id = %file.id,
                 path = %original_path,
@@ -1315,7 +1489,7 @@ impl OrganizerService {
                     );
 
                     // Delete the source file from disk (if it's different from target)
-                    if original_path != new_path_str && source_path.exists() {
+                    if !paths_equal(&original_path, &new_path_str) && source_path.exists() {
                         if let Err(e) = tokio::fs::remove_file(source_path).await {
                             warn!(
                                 path = %original_path,
@@ -1381,7 +1555,7 @@ impl OrganizerService {
         }
 
         // Determine effective action (copy-only for downloads)
-        let source_in_library = source_path.starts_with(library_path);
+        let source_in_library = path_is_within(&original_path, library_path);
         let effective_action = if source_in_library { "move" } else { "copy" };
 
         // Perform file operation
@@ -1706,6 +1880,11 @@ impl OrganizerService {
                 continue;
             }
 
+            // Only a disc's main title is tracked; its other files belong to it
+            if disc_structure::disc_root(path).is_some() {
+                continue;
+            }
+
             // This file is not tracked - check if it has multiple links
             #[cfg(unix)]
             if let Ok(metadata) = std::fs::metadata(path) {
@@ -1783,6 +1962,28 @@ impl OrganizerService {
         let mut results = Vec::new();
 
         for file in unorganized_files {
+            // Disc folder rips are organized here; other movie files when imported
+            if let Some(movie_id) = file.movie_id
+                && disc_structure::disc_root(Path::new(&file.path)).is_some()
+            {
+                let Some(movie) = self.db.movies().get_by_id(movie_id).await? else {
+                    warn!(file_id = %file.id, movie_id = %movie_id, "Movie not found");
+                    continue;
+                };
+                let result = self
+                    .organize_movie_file(
+                        &file,
+                        &movie,
+                        &library.path,
+                        library.naming_pattern.as_deref(),
+                        &library.post_download_action,
+                        false,
+                    )
+                    .await?;
+                results.push(result);
+                continue;
+            }
+
             // Get the episode this file is linked to
             let episode_id = match file.episode_id {
                 Some(id) => id,
@@ -2680,6 +2881,20 @@ async fn collect_all_folders(root: &Path) -> Result<Vec<PathBuf>> {
     Ok(folders)
 }
 
+/// Recursively copy a folder's contents into a new folder
+fn copy_dir_all(from: &Path, to: &Path) -> std::io::Result<()> {
+    for entry in WalkDir::new(from) {
+        let entry = entry.map_err(std::io::Error::other)?;
+        let target = to.join(entry.path().strip_prefix(from).unwrap_or(entry.path()));
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
 /// Check if a folder is empty of files (may contain empty subdirectories)
 ///
 /// Returns true if the folder and all its subdirectories contain no files
@@ -2866,7 +3081,7 @@ pub fn apply_naming_pattern(
         })
         .to_string();
 
-    PathBuf::from(result)
+    relative_path_from_pattern(&result)
 }
 
 /// Apply a naming pattern to generate a movie file path
@@ -2910,7 +3125,7 @@ pub fn apply_movie_naming_pattern(
         .unwrap_or(original_filename);
     result = result.replace("{original}", original_stem);
 
-    PathBuf::from(result)
+    relative_path_from_pattern(&result)
 }
 
 /// Apply a naming pattern to generate a music album file path
@@ -2995,7 +3210,7 @@ pub fn apply_music_naming_pattern(
     // Replace {title} with track title (from database or parsed from filename)
     result = result.replace("{title}", &track_title);
 
-    PathBuf::from(result)
+    relative_path_from_pattern(&result)
 }
 
 /// Apply a naming pattern to generate an audiobook file path
@@ -3057,7 +3272,7 @@ pub fn apply_audiobook_naming_pattern(
         .unwrap_or(original_filename);
     result = result.replace("{original}", original_stem);
 
-    PathBuf::from(result)
+    relative_path_from_pattern(&result)
 }
 
 /// Default naming pattern used when library doesn't have one set (TV shows)
@@ -0,0 +1,381 @@
+//! PAR2 verification and repair
+//!
+//! Usenet posts usually come with PAR2 recovery files. Once every article of a
+//! download has been assembled, the set is checked with `par2` (par2cmdline,
+//! or `PAR2_PATH`), which repairs damaged or missing blocks from the recovery
+//! volumes when it can. This runs before the download is marked completed, so
+//! extraction and import only ever see verified files. par2's progress lines
+//! are passed on as they're printed, so a long repair isn't a silent wait.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+/// Binary used when `PAR2_PATH` isn't set
+const DEFAULT_PAR2_BINARY: &str = "par2";
+
+/// Outcome of checking a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Par2Status {
+    /// The download has no PAR2 files
+    NoParity,
+    /// par2 isn't installed, so the files weren't checked
+    Skipped,
+    /// All files were intact
+    Verified,
+    /// Damaged files were repaired
+    Repaired,
+    /// Too few recovery blocks to repair
+    Failed,
+}
+
+impl Par2Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NoParity => "no_parity",
+            Self::Skipped => "skipped",
+            Self::Verified => "verified",
+            Self::Repaired => "repaired",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// What a running par2 check is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Par2Phase {
+    /// Checking the downloaded files against the PAR2 index
+    Verifying,
+    /// Rebuilding damaged or missing blocks
+    Repairing,
+}
+
+impl Par2Phase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Verifying => "verifying",
+            Self::Repairing => "repairing",
+        }
+    }
+}
+
+/// Error for a download with too few recovery blocks to repair
+#[derive(Debug)]
+pub struct RepairFailed(pub String);
+
+impl std::fmt::Display for RepairFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for RepairFailed {}
+
+/// Result of verification (and repair, if it was needed)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Par2Outcome {
+    pub status: Par2Status,
+    /// Data blocks that were damaged or missing
+    pub blocks_needed: Option<i64>,
+    /// Recovery blocks available to repair them
+    pub recovery_blocks: Option<i64>,
+    pub duration_ms: i64,
+}
+
+impl Par2Outcome {
+    fn without_check(status: Par2Status) -> Self {
+        Self {
+            status,
+            blocks_needed: None,
+            recovery_blocks: None,
+            duration_ms: 0,
+        }
+    }
+}
+
+/// What par2's output says about the set
+#[derive(Debug, Default, PartialEq)]
+struct Par2Report {
+    all_correct: bool,
+    repair_possible: Option<bool>,
+    repair_complete: bool,
+    blocks_needed: Option<i64>,
+    recovery_blocks: Option<i64>,
+}
+
+fn parse_output(output: &str) -> Par2Report {
+    let mut report = Par2Report::default();
+    // Progress lines end in a carriage return, not a newline
+    for line in output.split(['\r', '\n']).map(str::trim) {
+        if line.starts_with("All files are correct") {
+            report.all_correct = true;
+        } else if line.starts_with("Repair is possible") {
+            report.repair_possible = Some(true);
+        } else if line.starts_with("Repair is not possible") {
+            report.repair_possible = Some(false);
+        } else if line.starts_with("Repair complete") {
+            report.repair_complete = true;
+        } else if let Some(rest) = line.strip_prefix("You have ") {
+            // "You have 95 out of 100 data blocks available."
+            // "You have 10 recovery blocks available."
+            let words: Vec<&str> = rest.split_whitespace().collect();
+            match words.as_slice() {
+                [have, "out", "of", total, "data", ..] => {
+                    if let (Ok(have), Ok(total)) = (have.parse::<i64>(), total.parse::<i64>()) {
+                        report.blocks_needed = Some(total - have);
+                    }
+                }
+                [count, "recovery", ..] => {
+                    report.recovery_blocks = count.parse().ok();
+                }
+                _ => {}
+            }
+        }
+    }
+    report
+}
+
+/// Phase and fraction done from one of par2's progress lines, like
+/// `Scanning: "show.mkv": 45.2%` or `Repairing: 12.5%`
+fn parse_progress(line: &str) -> Option<(Par2Phase, f64)> {
+    let line = line.trim();
+    let phase = if line.starts_with("Repairing:") {
+        Par2Phase::Repairing
+    } else if line.starts_with("Scanning:") || line.starts_with("Verifying:") {
+        Par2Phase::Verifying
+    } else {
+        return None;
+    };
+    let percent: f64 = line
+        .strip_suffix('%')?
+        .rsplit([' ', ':'])
+        .next()?
+        .parse()
+        .ok()?;
+    Some((phase, (percent / 100.0).clamp(0.0, 1.0)))
+}
+
+/// Read par2's output to the end, reporting progress lines as they arrive
+async fn read_output(
+    mut stdout: impl AsyncRead + Unpin,
+    on_progress: &mut impl FnMut(Par2Phase, f64),
+) -> std::io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    let mut line = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let read = stdout.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        output.extend_from_slice(&buf[..read]);
+        for &byte in &buf[..read] {
+            if byte == b'\r' || byte == b'\n' {
+                if let Some((phase, progress)) = parse_progress(&String::from_utf8_lossy(&line)) {
+                    on_progress(phase, progress);
+                }
+                line.clear();
+            } else {
+                line.push(byte);
+            }
+        }
+    }
+    Ok(output)
+}
+
+/// The index file of a PAR2 set (the one that isn't a `.volNN+NN` volume),
+/// falling back to the smallest volume
+pub fn find_main_par2(files: &[PathBuf]) -> Option<PathBuf> {
+    let mut par2: Vec<&PathBuf> = files
+        .iter()
+        .filter(|p| {
+            p.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("par2"))
+        })
+        .collect();
+    par2.sort_by_key(|p| {
+        let is_volume = p
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s.to_ascii_lowercase().contains(".vol"));
+        (is_volume, p.as_os_str().len())
+    });
+    par2.first().map(|p| (*p).clone())
+}
+
+fn par2_binary() -> String {
+    std::env::var("PAR2_PATH").unwrap_or_else(|_| DEFAULT_PAR2_BINARY.to_string())
+}
+
+/// Verify the files in a download directory and repair them if needed,
+/// calling `on_progress` as par2 reports progress
+pub async fn verify_and_repair(
+    dir: &Path,
+    mut on_progress: impl FnMut(Par2Phase, f64),
+) -> Result<Par2Outcome> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to read {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        files.push(entry.path());
+    }
+
+    let Some(main) = find_main_par2(&files) else {
+        debug!(dir = %dir.display(), "No PAR2 files, skipping verification");
+        return Ok(Par2Outcome::without_check(Par2Status::NoParity));
+    };
+
+    let start = Instant::now();
+    // `repair` verifies first and only rewrites files when something is wrong
+    let mut child = match Command::new(par2_binary())
+        .arg("r")
+        .arg(&main)
+        .current_dir(dir)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            warn!("par2 is not installed; usenet downloads are not verified");
+            return Ok(Par2Outcome::without_check(Par2Status::Skipped));
+        }
+        Err(e) => return Err(e).context("Failed to run par2"),
+    };
+    let stdout = child.stdout.take().context("par2 stdout not captured")?;
+    let mut stderr = child.stderr.take().context("par2 stderr not captured")?;
+    let mut errors = Vec::new();
+    let (stdout, _) = tokio::try_join!(
+        read_output(stdout, &mut on_progress),
+        stderr.read_to_end(&mut errors)
+    )
+    .context("Failed to read par2 output")?;
+    let exit = child.wait().await.context("Failed to run par2")?;
+    let duration_ms = start.elapsed().as_millis() as i64;
+
+    let stdout = String::from_utf8_lossy(&stdout);
+    let report = parse_output(&stdout);
+    let status = if report.all_correct {
+        Par2Status::Verified
+    } else if exit.success() && report.repair_complete {
+        Par2Status::Repaired
+    } else if report.repair_possible == Some(false) || !exit.success() {
+        Par2Status::Failed
+    } else {
+        // Exit 0 without either message: nothing was wrong
+        Par2Status::Verified
+    };
+
+    info!(
+        par2 = %main.display(),
+        status = status.as_str(),
+        blocks_needed = ?report.blocks_needed,
+        recovery_blocks = ?report.recovery_blocks,
+        duration_ms,
+        "PAR2 check finished"
+    );
+    if status == Par2Status::Failed {
+        debug!(stderr = %String::from_utf8_lossy(&errors), "par2 output");
+    }
+
+    Ok(Par2Outcome {
+        status,
+        blocks_needed: report.blocks_needed,
+        recovery_blocks: report.recovery_blocks,
+        duration_ms,
+    })
+}
+
+/// Error message for a download that couldn't be repaired
+pub fn repair_failed_message(outcome: &Par2Outcome) -> String {
+    match (outcome.blocks_needed, outcome.recovery_blocks) {
+        (Some(needed), Some(available)) => format!(
+            "PAR2 repair not possible: {} blocks damaged or missing, {} recovery blocks available",
+            needed, available
+        ),
+        _ => "PAR2 repair not possible".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_repairable_output() {
+        let report = parse_output(
+            "Repair is required.\n\
+             You have 95 out of 100 data blocks available.\n\
+             You have 10 recovery blocks available.\n\
+             Repair is possible.\n\
+             Repair complete.\n",
+        );
+        assert_eq!(report.blocks_needed, Some(5));
+        assert_eq!(report.recovery_blocks, Some(10));
+        assert_eq!(report.repair_possible, Some(true));
+        assert!(report.repair_complete);
+        assert!(!report.all_correct);
+    }
+
+    #[test]
+    fn test_parse_unrepairable_and_clean_output() {
+        let report = parse_output(
+            "You have 80 out of 100 data blocks available.\n\
+             You have 4 recovery blocks available.\n\
+             Repair is not possible.\n\
+             You need 16 more recovery blocks to be able to repair.\n",
+        );
+        assert_eq!(report.repair_possible, Some(false));
+        assert_eq!(report.blocks_needed, Some(20));
+
+        assert!(parse_output("All files are correct, repair is not required.").all_correct);
+    }
+
+    #[test]
+    fn test_parse_progress() {
+        assert_eq!(
+            parse_progress("Repairing: 12.5%"),
+            Some((Par2Phase::Repairing, 0.125))
+        );
+        assert_eq!(
+            parse_progress("Scanning: \"show: part 2.mkv\": 45.0%"),
+            Some((Par2Phase::Verifying, 0.45))
+        );
+        assert_eq!(parse_progress("Repair complete."), None);
+        assert_eq!(parse_progress("Loading: 50.0%"), None);
+    }
+
+    #[test]
+    fn test_parse_output_with_progress_lines() {
+        let report = parse_output("Repairing: 50.0%\rRepairing: 100.0%\rRepair complete.\n");
+        assert!(report.repair_complete);
+    }
+
+    #[test]
+    fn test_find_main_par2() {
+        let files = vec![
+            PathBuf::from("/dl/show.vol07+08.par2"),
+            PathBuf::from("/dl/show.mkv"),
+            PathBuf::from("/dl/show.par2"),
+            PathBuf::from("/dl/show.vol00+01.par2"),
+        ];
+        assert_eq!(find_main_par2(&files), Some(PathBuf::from("/dl/show.par2")));
+
+        let volumes_only = vec![
+            PathBuf::from("/dl/show.vol07+08.PAR2"),
+            PathBuf::from("/dl/show.vol0+1.PAR2"),
+        ];
+        assert_eq!(
+            find_main_par2(&volumes_only),
+            Some(PathBuf::from("/dl/show.vol0+1.PAR2"))
+        );
+        assert_eq!(find_main_par2(&[PathBuf::from("/dl/show.mkv")]), None);
+    }
+}
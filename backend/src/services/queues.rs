@@ -14,14 +14,23 @@ use uuid::Uuid;
 
 use sqlx;
 
+use super::disc_structure;
 use super::ffmpeg::{FfmpegService, MediaAnalysis};
 use super::job_queue::{JobQueueConfig, WorkQueue};
 use super::quality_evaluator::{EffectiveQualitySettings, QualityEvaluator, QualityStatus};
-use crate::db::Database;
 #[cfg(feature = "sqlite")]
 use crate::db::sqlite_helpers::uuid_to_str;
+use crate::db::{
+    CreateNotification, Database, LanguageProfileRecord, NotificationCategory, NotificationType,
+    UpsertLanguageMismatch,
+};
 use crate::graphql::types::MediaFileUpdatedEvent;
 
+/// Setting that turns on EBU R128 loudness measurement during analysis
+pub const LOUDNESS_ANALYSIS_KEY: &str = "media_analysis.loudness";
+/// Off by default: it decodes every audio stream in full
+pub const DEFAULT_LOUDNESS_ANALYSIS: bool = false;
+
 /// Job payload for media file analysis
 #[derive(Debug, Clone)]
 pub struct MediaAnalysisJob {
@@ -140,7 +149,7 @@ pub fn create_media_analysis_queue(
 async fn process_media_analysis(
     ffmpeg: Arc<FfmpegService>,
     db: Database,
-    _subtitle_queue: Option<Arc<SubtitleDownloadQueue>>,
+    subtitle_queue: Option<Arc<SubtitleDownloadQueue>>,
     event_sender: Option<broadcast::Sender<MediaFileUpdatedEvent>>,
     job: MediaAnalysisJob,
 ) -> Result<()> {
@@ -171,7 +180,7 @@ async fn process_media_analysis(
     );
 
     // Run FFmpeg analysis
-    let analysis = match ffmpeg.analyze(&job.path).await {
+    let mut analysis = match ffmpeg.analyze(&job.path).await {
         Ok(analysis) => analysis,
         Err(e) => {
             warn!(
@@ -185,6 +194,32 @@ async fn process_media_analysis(
         }
     };
 
+    // A DVD title runs on past its first part
+    let parts = disc_structure::title_parts(&job.path);
+    if parts.len() > 1
+        && let Some(duration) = disc_structure::title_duration(&ffmpeg, &parts).await
+    {
+        analysis.duration_secs = Some(duration);
+    }
+
+    let measure_loudness = db
+        .settings()
+        .get_or_default(LOUDNESS_ANALYSIS_KEY, DEFAULT_LOUDNESS_ANALYSIS)
+        .await
+        .unwrap_or(DEFAULT_LOUDNESS_ANALYSIS);
+    if measure_loudness {
+        for audio in &mut analysis.audio_streams {
+            match ffmpeg.measure_loudness(&job.path, audio.index).await {
+                Ok(loudness) => audio.loudness = Some(loudness),
+                Err(e) => warn!(
+                    stream_index = audio.index,
+                    error = %e,
+                    "Failed to measure loudness of '{}'", filename
+                ),
+            }
+        }
+    }
+
     // Store analysis results in database and get updated file info
     // Note: We check again inside store_media_analysis in case the file was deleted
     // between our check and now (during the FFmpeg analysis)
@@ -203,6 +238,21 @@ async fn process_media_analysis(
         }
     };
 
+    // File-level loudness is the primary audio stream's, as with the codec
+    let primary_loudness = FfmpegService::primary_audio_stream(&analysis).and_then(|a| a.loudness);
+    if let Some(loudness) = primary_loudness
+        && let Err(e) = db
+            .media_files()
+            .update_loudness(
+                job.media_file_id,
+                Some(loudness.integrated),
+                loudness.true_peak,
+            )
+            .await
+    {
+        warn!("Failed to store loudness of '{}': {}", filename, e);
+    }
+
     // Build summary of analysis
     let video_summary = analysis.video_streams.first().map(|v| {
         let res = format!("{}x{}", v.width, v.height);
@@ -256,8 +306,206 @@ async fn process_media_analysis(
         }
     }
 
-    // TODO: If check_subtitles is true and auto-download is enabled,
-    // queue subtitle download jobs for missing languages
+    // Check the file against its language profile, queueing subtitle
+    // downloads for missing languages when the job asks for it
+    let subtitle_queue = subtitle_queue.filter(|_| job.check_subtitles);
+    if let Err(e) = verify_languages(
+        &db,
+        subtitle_queue.as_deref(),
+        job.media_file_id,
+        filename,
+        &updated_info,
+        &analysis,
+    )
+    .await
+    {
+        warn!("Failed to verify languages for '{}': {}", filename, e);
+    }
+
+    Ok(())
+}
+
+/// Compare an analyzed file with the language profile of its show or
+/// library
+///
+/// A file missing audio languages is flagged (see [`flag_missing_audio`]);
+/// missing subtitle languages are queued for download.
+async fn verify_languages(
+    db: &Database,
+    subtitle_queue: Option<&SubtitleDownloadQueue>,
+    media_file_id: Uuid,
+    filename: &str,
+    info: &AnalysisStoredInfo,
+    analysis: &MediaAnalysis,
+) -> Result<()> {
+    let episode = match info.episode_id {
+        Some(id) => db.episodes().get_by_id(id).await?,
+        None => None,
+    };
+    let show_id = episode.as_ref().map(|e| e.tv_show_id);
+
+    let Some(profile) = db
+        .language_profiles()
+        .get_effective(info.library_id, show_id)
+        .await?
+    else {
+        db.language_mismatches().delete(media_file_id).await?;
+        return Ok(());
+    };
+
+    let audio_languages: Vec<String> = analysis
+        .audio_streams
+        .iter()
+        .filter_map(|a| a.language.clone())
+        .collect();
+    let subtitle_languages: Vec<String> = db
+        .subtitles()
+        .list_by_media_file(media_file_id)
+        .await?
+        .into_iter()
+        .filter_map(|s| s.language)
+        .collect();
+
+    let check =
+        super::language_profile::verify_file(&profile, &audio_languages, &subtitle_languages);
+    if check.missing_audio.is_empty() {
+        // Re-analysis clears an earlier mismatch, e.g. after a remux or a
+        // profile change
+        db.language_mismatches().delete(media_file_id).await?;
+    }
+    if check.is_satisfied() {
+        return Ok(());
+    }
+
+    if !check.missing_audio.is_empty() {
+        flag_missing_audio(
+            db,
+            media_file_id,
+            filename,
+            info,
+            &profile,
+            &check.missing_audio,
+        )
+        .await?;
+    }
+
+    if let Some(queue) = subtitle_queue
+        && !check.missing_subtitles.is_empty()
+    {
+        info!(
+            media_file_id = %media_file_id,
+            profile = %profile.name,
+            "Queueing subtitle download for missing languages {:?}",
+            check.missing_subtitles
+        );
+        queue
+            .submit(SubtitleDownloadJob {
+                media_file_id,
+                episode_id: info.episode_id,
+                languages: check.missing_subtitles,
+                imdb_id: None,
+                show_name: None,
+                season: None,
+                episode: None,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("Subtitle download queue is closed"))?;
+    }
+
+    Ok(())
+}
+
+/// Flag a file that lacks required audio languages
+///
+/// "MULTi" and language tags in release titles are often wrong, so this is
+/// only caught after import. The file is recorded as a language mismatch
+/// and its library's owner notified; with the profile's re-grab option on,
+/// auto-hunt then searches for the item again. The grab ledger keeps it
+/// from picking the same release. Re-analyzing a flagged file doesn't
+/// notify or request a re-grab a second time.
+async fn flag_missing_audio(
+    db: &Database,
+    media_file_id: Uuid,
+    filename: &str,
+    info: &AnalysisStoredInfo,
+    profile: &LanguageProfileRecord,
+    missing_audio: &[String],
+) -> Result<()> {
+    warn!(
+        media_file_id = %media_file_id,
+        profile = %profile.name,
+        missing = ?missing_audio,
+        "'{}' is missing required audio languages {:?}",
+        filename,
+        missing_audio
+    );
+
+    let already_flagged = db.language_mismatches().get(media_file_id).await?.is_some();
+
+    // The torrent or NZB the file was imported from
+    let source = db
+        .pending_file_matches()
+        .get_by_media_file(media_file_id)
+        .await?;
+    let torrent_id = source
+        .as_ref()
+        .filter(|m| m.source_type == "torrent")
+        .and_then(|m| m.source_id);
+    let release_title = match source
+        .as_ref()
+        .and_then(|m| m.source_id.map(|id| (m.source_type.as_str(), id)))
+    {
+        Some(("torrent", id)) => db.torrents().get_by_id(id).await?.map(|t| t.name),
+        Some(("usenet", id)) => db.usenet_downloads().get(id).await?.map(|d| d.nzb_name),
+        _ => None,
+    };
+
+    db.language_mismatches()
+        .upsert(UpsertLanguageMismatch {
+            media_file_id,
+            library_id: info.library_id,
+            missing_audio_languages: missing_audio.to_vec(),
+            release_title: release_title.clone(),
+            regrab_pending: profile.regrab_missing_audio,
+        })
+        .await?;
+
+    if already_flagged {
+        return Ok(());
+    }
+    let Some(library) = db.libraries().get_by_id(info.library_id).await? else {
+        return Ok(());
+    };
+
+    let notification = CreateNotification {
+        user_id: library.user_id,
+        title: "Missing audio language".to_string(),
+        message: super::language_profile::missing_audio_message(
+            profile,
+            filename,
+            release_title.as_deref(),
+            missing_audio,
+        ),
+        notification_type: NotificationType::Warning,
+        category: NotificationCategory::Quality,
+        library_id: Some(info.library_id),
+        torrent_id,
+        media_file_id: Some(media_file_id),
+        pending_match_id: source.as_ref().map(|m| m.id),
+        action_type: None,
+        action_data: Some(serde_json::json!({
+            "missing_audio_languages": missing_audio,
+            "release_title": release_title,
+            "regrab": profile.regrab_missing_audio,
+        })),
+    };
+    if let Err(e) = db.notifications().create(notification).await {
+        warn!(
+            media_file_id = %media_file_id,
+            error = %e,
+            "Failed to create missing audio language notification"
+        );
+    }
 
     Ok(())
 }
@@ -460,8 +708,9 @@ pub async fn store_media_analysis(
             INSERT INTO audio_streams (
                 id, media_file_id, stream_index, codec, codec_long_name,
                 channels, channel_layout, sample_rate, bitrate, bit_depth,
-                language, title, is_default, is_commentary, metadata
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+                language, title, is_default, is_commentary, metadata,
+                integrated_loudness, loudness_range, true_peak
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)
             "#,
         )
         .bind(&stream_id)
@@ -479,6 +728,9 @@ pub async fn store_media_analysis(
         .bind(audio.is_default)
         .bind(audio.is_commentary)
         .bind(&metadata_json)
+        .bind(audio.loudness.map(|l| l.integrated))
+        .bind(audio.loudness.and_then(|l| l.range))
+        .bind(audio.loudness.and_then(|l| l.true_peak))
         .execute(pool)
         .await?;
     }
@@ -551,23 +803,28 @@ pub async fn store_media_analysis(
     })
 }
 
-/// Placeholder for subtitle download queue processor
-/// Will be implemented when OpenSubtitles client is ready
+/// Create the subtitle download queue with its processor
+///
+/// One job runs at a time, keeping OpenSubtitles requests under its rate
+/// limit; see [`super::subtitle_download`] for quota handling.
 pub fn create_subtitle_download_queue(db: Database) -> SubtitleDownloadQueue {
     let config = subtitle_download_queue_config();
+    let downloader = Arc::new(super::subtitle_download::SubtitleDownloader::new(db));
 
     WorkQueue::new(
         "subtitle_download",
         config,
         move |job: SubtitleDownloadJob| {
-            let _db = db.clone();
+            let downloader = downloader.clone();
             async move {
-                // TODO: Implement when OpenSubtitles client is ready
-                debug!(
-                    media_file_id = %job.media_file_id,
-                    languages = ?job.languages,
-                    "Subtitle download job queued (not yet implemented)"
-                );
+                let media_file_id = job.media_file_id;
+                if let Err(e) = downloader.process(job).await {
+                    warn!(
+                        media_file_id = %media_file_id,
+                        error = %e,
+                        "Subtitle download job failed"
+                    );
+                }
             }
         },
     )
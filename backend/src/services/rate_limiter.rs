@@ -15,9 +15,11 @@ use governor::{
     clock::DefaultClock,
     state::{InMemoryState, NotKeyed},
 };
-use reqwest::{Client, Response};
+use reqwest::{Client, RequestBuilder, Response};
 use tracing::{debug, warn};
 
+use super::http_replay;
+
 /// Configuration for rate limiting
 #[derive(Debug, Clone)]
 pub struct RateLimitConfig {
@@ -151,11 +153,7 @@ impl RateLimitedClient {
         self.wait_for_permit().await;
         debug!(client = %self.name, url = %url, "Making rate-limited GET request");
 
-        self.client
-            .get(url)
-            .send()
-            .await
-            .context("HTTP request failed")
+        self.send(self.client.get(url)).await
     }
 
     /// Wait for rate limit and make a GET request with query parameters
@@ -167,12 +165,7 @@ impl RateLimitedClient {
         self.wait_for_permit().await;
         debug!(client = %self.name, url = %url, "Making rate-limited GET request with query");
 
-        self.client
-            .get(url)
-            .query(query)
-            .send()
-            .await
-            .context("HTTP request failed")
+        self.send(self.client.get(url).query(query)).await
     }
 
     /// Wait for rate limit and make a GET request with headers and query parameters
@@ -189,9 +182,13 @@ impl RateLimitedClient {
         for (key, value) in headers {
             request = request.header(*key, *value);
         }
-        request
-            .query(query)
-            .send()
+        self.send(request.query(query)).await
+    }
+
+    /// Send a built request, through the record/replay layer
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.build().context("Invalid HTTP request")?;
+        http_replay::send(&self.client, &self.name, request)
             .await
             .context("HTTP request failed")
     }
@@ -0,0 +1,320 @@
+//! Watch-next recommendations
+//!
+//! Mixes two sources into a single shelf:
+//! - TMDB recommendations for the user's recently watched movies (titles the
+//!   user doesn't own yet, fetched server-side so the client needs no TMDB key)
+//! - Unwatched movies and unstarted shows already in the user's libraries
+//!
+//! Results are cached per user for the day, since neither source changes much
+//! and TMDB lookups are rate limited.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use super::cache::{SharedCache, create_cache};
+use super::metadata::MetadataService;
+use crate::db::Database;
+
+/// Cache TTL for a user's recommendations (the key also rolls over daily)
+const CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Number of recently watched movies used as TMDB seeds
+const SEED_COUNT: i64 = 5;
+
+/// Most recommendations computed and cached per user
+const MAX_RECOMMENDATIONS: usize = 50;
+
+/// Kind of item being recommended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendationKind {
+    Movie,
+    TvShow,
+}
+
+/// Where a recommendation came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecommendationSource {
+    /// Already in the user's library, not yet watched
+    Library,
+    /// Suggested by TMDB based on watch history
+    Tmdb,
+}
+
+/// A single recommendation
+#[derive(Debug, Clone)]
+pub struct Recommendation {
+    pub kind: RecommendationKind,
+    pub source: RecommendationSource,
+    pub title: String,
+    pub year: Option<i32>,
+    pub overview: Option<String>,
+    pub poster_url: Option<String>,
+    pub tmdb_id: Option<i32>,
+    /// Library item ID (movie or show) when the item is in a library
+    pub library_item_id: Option<Uuid>,
+    pub library_id: Option<Uuid>,
+    /// Human-readable reason, e.g. "Because you watched Heat"
+    pub reason: Option<String>,
+}
+
+/// Interleave library and TMDB recommendations, dropping duplicates
+///
+/// Library items come first in each pair since they can be played right away.
+/// TMDB suggestions for movies the user already owns are dropped; the owned
+/// copy is surfaced through the library list instead.
+pub fn merge_recommendations(
+    library: Vec<Recommendation>,
+    external: Vec<Recommendation>,
+    limit: usize,
+) -> Vec<Recommendation> {
+    let owned_tmdb_ids: HashSet<i32> = library
+        .iter()
+        .filter(|r| r.kind == RecommendationKind::Movie)
+        .filter_map(|r| r.tmdb_id)
+        .collect();
+
+    let mut seen_tmdb_ids = HashSet::new();
+    let external: Vec<Recommendation> = external
+        .into_iter()
+        .filter(|r| match r.tmdb_id {
+            Some(id) => !owned_tmdb_ids.contains(&id) && seen_tmdb_ids.insert(id),
+            None => true,
+        })
+        .collect();
+
+    let mut merged = Vec::with_capacity(limit);
+    let mut library = library.into_iter();
+    let mut external = external.into_iter();
+    while merged.len() < limit {
+        let next_library = library.next();
+        let next_external = external.next();
+        if next_library.is_none() && next_external.is_none() {
+            break;
+        }
+        merged.extend(next_library);
+        if merged.len() < limit {
+            merged.extend(next_external);
+        }
+    }
+    merged
+}
+
+/// Service that builds and caches per-user recommendations
+pub struct RecommendationService {
+    db: Database,
+    metadata: Arc<MetadataService>,
+    cache: SharedCache<Vec<Recommendation>>,
+}
+
+impl RecommendationService {
+    pub fn new(db: Database, metadata: Arc<MetadataService>) -> Self {
+        Self {
+            db,
+            metadata,
+            cache: create_cache(CACHE_TTL),
+        }
+    }
+
+    /// Get recommendations for a user, computing them at most once a day
+    pub async fn get_for_user(&self, user_id: Uuid, limit: usize) -> Result<Vec<Recommendation>> {
+        let cache_key = format!("{}:{}", user_id, chrono::Utc::now().date_naive());
+        if let Some(cached) = self.cache.get(&cache_key) {
+            debug!(user_id = %user_id, "Returning cached recommendations");
+            return Ok(cached.into_iter().take(limit).collect());
+        }
+
+        let library = self.library_recommendations(user_id).await?;
+        let external = self.tmdb_recommendations(user_id).await;
+        let merged = merge_recommendations(library, external, MAX_RECOMMENDATIONS);
+
+        self.cache.set(cache_key, merged.clone());
+        Ok(merged.into_iter().take(limit).collect())
+    }
+
+    async fn library_recommendations(&self, user_id: Uuid) -> Result<Vec<Recommendation>> {
+        let half = (MAX_RECOMMENDATIONS / 2) as i64;
+        let movies = self
+            .db
+            .movies()
+            .list_unwatched_with_files(user_id, half)
+            .await?;
+        let shows = self
+            .db
+            .tv_shows()
+            .list_unstarted_with_files(user_id, half)
+            .await?;
+
+        let movies = movies.into_iter().map(|m| Recommendation {
+            kind: RecommendationKind::Movie,
+            source: RecommendationSource::Library,
+            title: m.title,
+            year: m.year,
+            overview: m.overview,
+            poster_url: m.poster_url,
+            tmdb_id: m.tmdb_id,
+            library_item_id: Some(m.id),
+            library_id: Some(m.library_id),
+            reason: Some("Unwatched in your library".to_string()),
+        });
+        let shows = shows.into_iter().map(|s| Recommendation {
+            kind: RecommendationKind::TvShow,
+            source: RecommendationSource::Library,
+            title: s.name,
+            year: s.year,
+            overview: s.overview,
+            poster_url: s.poster_url,
+            tmdb_id: s.tmdb_id,
+            library_item_id: Some(s.id),
+            library_id: Some(s.library_id),
+            reason: Some("Unwatched in your library".to_string()),
+        });
+
+        // Alternate movies and shows so neither dominates the shelf
+        let mut movies = movies.peekable();
+        let mut shows = shows.peekable();
+        let mut combined = Vec::new();
+        while movies.peek().is_some() || shows.peek().is_some() {
+            combined.extend(movies.next());
+            combined.extend(shows.next());
+        }
+        Ok(combined)
+    }
+
+    /// TMDB suggestions seeded from recently watched movies
+    ///
+    /// Failures (no API key, rate limits) only drop this source; the library
+    /// half of the shelf is still returned.
+    async fn tmdb_recommendations(&self, user_id: Uuid) -> Vec<Recommendation> {
+        if !self.metadata.has_tmdb().await {
+            return Vec::new();
+        }
+
+        let seeds = match self
+            .db
+            .movies()
+            .list_recently_watched(user_id, SEED_COUNT)
+            .await
+        {
+            Ok(seeds) => seeds,
+            Err(e) => {
+                warn!(error = %e, "Failed to load watch history for recommendations");
+                return Vec::new();
+            }
+        };
+
+        let mut per_seed = Vec::new();
+        for seed in seeds {
+            let Some(tmdb_id) = seed.tmdb_id else {
+                continue;
+            };
+            match self.metadata.get_movie_recommendations(tmdb_id as u32).await {
+                Ok(results) => per_seed.push(
+                    results
+                        .into_iter()
+                        .map(|r| Recommendation {
+                            kind: RecommendationKind::Movie,
+                            source: RecommendationSource::Tmdb,
+                            title: r.title,
+                            year: r.year,
+                            overview: r.overview,
+                            poster_url: r.poster_url,
+                            tmdb_id: Some(r.provider_id as i32),
+                            library_item_id: None,
+                            library_id: None,
+                            reason: Some(format!("Because you watched {}", seed.title)),
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+                Err(e) => {
+                    warn!(tmdb_id, error = %e, "Failed to fetch TMDB recommendations");
+                }
+            }
+        }
+
+        // Round-robin across seeds so the most recent watch doesn't crowd out the rest
+        let mut iters: Vec<_> = per_seed.into_iter().map(|v| v.into_iter()).collect();
+        let mut out = Vec::new();
+        loop {
+            let mut progressed = false;
+            for it in iters.iter_mut() {
+                if let Some(r) = it.next() {
+                    out.push(r);
+                    progressed = true;
+                }
+            }
+            if !progressed {
+                break;
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rec(source: RecommendationSource, title: &str, tmdb_id: Option<i32>) -> Recommendation {
+        Recommendation {
+            kind: RecommendationKind::Movie,
+            source,
+            title: title.to_string(),
+            year: None,
+            overview: None,
+            poster_url: None,
+            tmdb_id,
+            library_item_id: None,
+            library_id: None,
+            reason: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_interleaves_sources() {
+        let library = vec![
+            rec(RecommendationSource::Library, "A", Some(1)),
+            rec(RecommendationSource::Library, "B", Some(2)),
+        ];
+        let external = vec![
+            rec(RecommendationSource::Tmdb, "X", Some(10)),
+            rec(RecommendationSource::Tmdb, "Y", Some(11)),
+            rec(RecommendationSource::Tmdb, "Z", Some(12)),
+        ];
+        let titles: Vec<String> = merge_recommendations(library, external, 10)
+            .into_iter()
+            .map(|r| r.title)
+            .collect();
+        assert_eq!(titles, vec!["A", "X", "B", "Y", "Z"]);
+    }
+
+    #[test]
+    fn test_merge_drops_owned_and_duplicate_suggestions() {
+        let library = vec![rec(RecommendationSource::Library, "Owned", Some(1))];
+        let external = vec![
+            rec(RecommendationSource::Tmdb, "Owned", Some(1)),
+            rec(RecommendationSource::Tmdb, "New", Some(2)),
+            rec(RecommendationSource::Tmdb, "New again", Some(2)),
+        ];
+        let titles: Vec<String> = merge_recommendations(library, external, 10)
+            .into_iter()
+            .map(|r| r.title)
+            .collect();
+        assert_eq!(titles, vec!["Owned", "New"]);
+    }
+
+    #[test]
+    fn test_merge_respects_limit() {
+        let library = (0..5)
+            .map(|i| rec(RecommendationSource::Library, &format!("L{}", i), None))
+            .collect();
+        let external = (0..5)
+            .map(|i| rec(RecommendationSource::Tmdb, &format!("T{}", i), Some(i)))
+            .collect();
+        assert_eq!(merge_recommendations(library, external, 3).len(), 3);
+    }
+}
@@ -0,0 +1,378 @@
+//! Release content pre-check
+//!
+//! Before a release is handed to a download client, its file list is read
+//! from the .torrent or NZB and screened for common fakes:
+//!
+//! - Nothing but executables or disc images
+//! - A total size far off for the item's runtime (movies and episodes)
+//! - A file matching the blocklist, such as a password note next to a
+//!   locked archive or a `.mkv.exe`
+//!
+//! Magnet links have no file list until the download starts, so they aren't
+//! checked. Pre-checks can be turned off with `downloads.precheck_enabled`.
+
+use std::fmt;
+
+use anyhow::Result;
+use tracing::{info, warn};
+
+use super::torrent_metadata::parse_torrent_files;
+use crate::db::Database;
+use crate::usenet::nzb::NzbFile;
+
+/// Settings key for turning pre-checks on or off
+pub const ENABLED_KEY: &str = "downloads.precheck_enabled";
+
+/// Pre-checks run unless turned off
+pub const DEFAULT_ENABLED: bool = true;
+
+/// Settings key for the file name blocklist (JSON array of patterns)
+pub const BLOCKLIST_KEY: &str = "downloads.precheck_blocklist";
+
+/// File names that mark a release as fake or password-protected
+///
+/// Patterns are matched case-insensitively against whole file names, with
+/// `*` matching any run of characters.
+pub const DEFAULT_BLOCKLIST: [&str; 11] = [
+    "*.lnk",
+    "*.scr",
+    "*.vbs",
+    "*.mkv.exe",
+    "*.mp4.exe",
+    "*.avi.exe",
+    "*password*.txt",
+    "*password*.url",
+    "*passwort*.txt",
+    "*codec*.exe",
+    "*setup*.exe",
+];
+
+/// Extensions of files no library wants
+const JUNK_EXTENSIONS: [&str; 12] = [
+    "exe", "msi", "bat", "cmd", "com", "scr", "lnk", "vbs", "jar", "apk", "iso", "dmg",
+];
+
+/// Files smaller than this (NFOs, checksums, samples of text) don't count
+/// when deciding whether a release is only junk
+const NEGLIGIBLE_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Smallest believable size per minute of runtime, below even low-bitrate SD
+const MIN_BYTES_PER_MINUTE: u64 = 1024 * 1024;
+
+/// Largest believable size per minute of runtime, far above UHD remuxes so
+/// that season packs still pass
+const MAX_BYTES_PER_MINUTE: u64 = 2 * 1024 * 1024 * 1024;
+
+/// A file in a release
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecheckFile {
+    pub name: String,
+    pub size: u64,
+}
+
+/// Why a release was rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    /// Every file is an executable or disc image
+    OnlyJunk,
+    TooSmall {
+        total: u64,
+        runtime_minutes: u32,
+    },
+    TooLarge {
+        total: u64,
+        runtime_minutes: u32,
+    },
+    Blocklisted {
+        file: String,
+        pattern: String,
+    },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OnlyJunk => write!(f, "release only contains executables or disc images"),
+            Self::TooSmall {
+                total,
+                runtime_minutes,
+            } => write!(
+                f,
+                "{} MiB is too small for a {} minute runtime",
+                total / (1024 * 1024),
+                runtime_minutes
+            ),
+            Self::TooLarge {
+                total,
+                runtime_minutes,
+            } => write!(
+                f,
+                "{} MiB is too large for a {} minute runtime",
+                total / (1024 * 1024),
+                runtime_minutes
+            ),
+            Self::Blocklisted { file, pattern } => {
+                write!(f, "file '{}' matches blocklist pattern '{}'", file, pattern)
+            }
+        }
+    }
+}
+
+/// Pre-check settings
+#[derive(Debug, Clone)]
+pub struct Precheck {
+    pub blocklist: Vec<String>,
+}
+
+impl Default for Precheck {
+    fn default() -> Self {
+        Self {
+            blocklist: DEFAULT_BLOCKLIST.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+impl Precheck {
+    /// Settings from the database, `None` when pre-checks are off
+    pub async fn load(db: &Database) -> Result<Option<Self>> {
+        let settings = db.settings();
+        if !settings
+            .get_or_default(ENABLED_KEY, DEFAULT_ENABLED)
+            .await?
+        {
+            return Ok(None);
+        }
+
+        let blocklist = settings
+            .get_or_default(BLOCKLIST_KEY, Self::default().blocklist)
+            .await?;
+        Ok(Some(Self { blocklist }))
+    }
+
+    /// Screen a release's files, with the runtime of the item it's for
+    /// when that's a movie or episode
+    pub fn check(
+        &self,
+        files: &[PrecheckFile],
+        runtime_minutes: Option<u32>,
+    ) -> std::result::Result<(), Rejection> {
+        for file in files {
+            let name = file_name(&file.name);
+            if let Some(pattern) = self.blocklist.iter().find(|p| glob_matches(p, name)) {
+                return Err(Rejection::Blocklisted {
+                    file: name.to_string(),
+                    pattern: pattern.clone(),
+                });
+            }
+        }
+
+        let significant: Vec<&PrecheckFile> = files
+            .iter()
+            .filter(|f| f.size >= NEGLIGIBLE_FILE_BYTES)
+            .collect();
+        let counted = if significant.is_empty() {
+            files.iter().collect()
+        } else {
+            significant
+        };
+        if !counted.is_empty() && counted.iter().all(|f| is_junk(&f.name)) {
+            return Err(Rejection::OnlyJunk);
+        }
+
+        if let Some(runtime_minutes) = runtime_minutes.filter(|m| *m > 0) {
+            let total: u64 = files.iter().map(|f| f.size).sum();
+            let per_minute = total / runtime_minutes as u64;
+            if per_minute < MIN_BYTES_PER_MINUTE {
+                return Err(Rejection::TooSmall {
+                    total,
+                    runtime_minutes,
+                });
+            }
+            if per_minute > MAX_BYTES_PER_MINUTE {
+                return Err(Rejection::TooLarge {
+                    total,
+                    runtime_minutes,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Files of a .torrent
+pub fn torrent_files(torrent_bytes: &[u8]) -> Result<Vec<PrecheckFile>> {
+    Ok(parse_torrent_files(torrent_bytes)?
+        .into_iter()
+        .map(|f| PrecheckFile {
+            name: f.path.to_string_lossy().to_string(),
+            size: f.size,
+        })
+        .collect())
+}
+
+/// Files of an NZB
+pub fn nzb_files(nzb: &NzbFile) -> Vec<PrecheckFile> {
+    nzb.files
+        .iter()
+        .map(|f| PrecheckFile {
+            name: f.filename.clone(),
+            size: f.size,
+        })
+        .collect()
+}
+
+/// Screen a .torrent before it's added, failing if it's rejected
+///
+/// A .torrent that can't be parsed or settings that can't be read are
+/// logged and let through; the download client has the final word.
+pub async fn check_torrent(
+    db: &Database,
+    title: &str,
+    torrent_bytes: &[u8],
+    runtime_minutes: Option<i32>,
+) -> Result<()> {
+    let files = match torrent_files(torrent_bytes) {
+        Ok(files) => files,
+        Err(e) => {
+            warn!(release_title = %title, error = %e, "Failed to read torrent for pre-check");
+            return Ok(());
+        }
+    };
+    check_files(db, title, &files, runtime_minutes).await
+}
+
+/// Screen a release's files before it's added, failing if it's rejected
+pub async fn check_files(
+    db: &Database,
+    title: &str,
+    files: &[PrecheckFile],
+    runtime_minutes: Option<i32>,
+) -> Result<()> {
+    let precheck = match Precheck::load(db).await {
+        Ok(Some(precheck)) => precheck,
+        Ok(None) => return Ok(()),
+        Err(e) => {
+            warn!(error = %e, "Failed to load pre-check settings");
+            return Ok(());
+        }
+    };
+
+    let runtime_minutes = runtime_minutes.and_then(|m| u32::try_from(m).ok());
+    if let Err(rejection) = precheck.check(files, runtime_minutes) {
+        info!(release_title = %title, reason = %rejection, "Release rejected by pre-check");
+        anyhow::bail!("Release rejected by pre-check: {}", rejection);
+    }
+
+    Ok(())
+}
+
+/// Last component of a path inside a release
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn is_junk(name: &str) -> bool {
+    let name = file_name(name).to_lowercase();
+    name.rsplit_once('.')
+        .is_some_and(|(_, ext)| JUNK_EXTENSIONS.contains(&ext))
+}
+
+/// Case-insensitive match of a whole name against a pattern where `*`
+/// matches any run of characters
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let name = name.to_lowercase();
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().expect("split yields at least one part");
+    let Some(mut remaining) = name.strip_prefix(first) else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return remaining.is_empty();
+    };
+
+    for part in middle {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    fn file(name: &str, size: u64) -> PrecheckFile {
+        PrecheckFile {
+            name: name.to_string(),
+            size,
+        }
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*.lnk", "Movie.LNK"));
+        assert!(glob_matches("*password*.txt", "READ_PASSWORD_HERE.txt"));
+        assert!(glob_matches("*.mkv.exe", "movie.2024.mkv.exe"));
+        assert!(glob_matches("readme.txt", "README.txt"));
+        assert!(!glob_matches("*.mkv.exe", "movie.mkv"));
+        assert!(!glob_matches("*password*.txt", "Password.2019.1080p.mkv"));
+        assert!(!glob_matches("readme.txt", "readme.txt.bak"));
+    }
+
+    #[test]
+    fn test_normal_release_passes() {
+        let files = [
+            file("Movie.2024.1080p/Movie.2024.1080p.mkv", 8000 * MIB),
+            file("Movie.2024.1080p/movie.nfo", 4096),
+        ];
+        assert_eq!(Precheck::default().check(&files, Some(120)), Ok(()));
+    }
+
+    #[test]
+    fn test_only_junk_is_rejected() {
+        let files = [
+            file("Movie.2024.1080p/Movie.2024.1080p.iso", 700 * MIB),
+            file("Movie.2024.1080p/readme.txt", 2048),
+        ];
+        assert_eq!(
+            Precheck::default().check(&files, None),
+            Err(Rejection::OnlyJunk)
+        );
+
+        let files = [file("movie.mkv", 700 * MIB), file("player.exe", 2 * MIB)];
+        assert_eq!(Precheck::default().check(&files, None), Ok(()));
+    }
+
+    #[test]
+    fn test_blocklisted_file_is_rejected() {
+        let files = [
+            file("Movie.2024/Movie.2024.rar", 4000 * MIB),
+            file("Movie.2024/Password.txt", 100),
+        ];
+        assert!(matches!(
+            Precheck::default().check(&files, None),
+            Err(Rejection::Blocklisted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_size_for_runtime() {
+        let precheck = Precheck::default();
+        assert!(matches!(
+            precheck.check(&[file("movie.mkv", 50 * MIB)], Some(120)),
+            Err(Rejection::TooSmall { .. })
+        ));
+        assert!(matches!(
+            precheck.check(&[file("episode.mkv", 200 * 1024 * MIB)], Some(45)),
+            Err(Rejection::TooLarge { .. })
+        ));
+        assert_eq!(precheck.check(&[file("movie.mkv", 50 * MIB)], None), Ok(()));
+    }
+}
@@ -178,6 +178,14 @@ pub struct ParsedRssItem {
     pub title_hash: String,
 }
 
+/// A fetched RSS feed
+#[derive(Debug, Clone)]
+pub struct ParsedRssFeed {
+    pub items: Vec<ParsedRssItem>,
+    /// The channel's <ttl>: minutes the feed may be cached before refreshing
+    pub ttl_minutes: Option<i32>,
+}
+
 /// RSS feed service for fetching and parsing feeds
 pub struct RssService {
     client: Client,
@@ -198,7 +206,7 @@ impl RssService {
     /// Fetch and parse an RSS feed from a URL
     ///
     /// This method includes SSRF protection to prevent requests to internal networks.
-    pub async fn fetch_feed(&self, url: &str) -> Result<Vec<ParsedRssItem>> {
+    pub async fn fetch_feed(&self, url: &str) -> Result<ParsedRssFeed> {
         // Validate URL for SSRF protection
         validate_url_for_ssrf(url).context("URL validation failed")?;
 
@@ -220,7 +228,38 @@ impl RssService {
             .await
             .context("Failed to read RSS feed content")?;
 
-        self.parse_feed(&content)
+        Ok(ParsedRssFeed {
+            items: self.parse_feed(&content)?,
+            ttl_minutes: Self::parse_ttl(&content),
+        })
+    }
+
+    /// Parse the channel's <ttl> (minutes), ignoring missing or zero values
+    pub fn parse_ttl(content: &str) -> Option<i32> {
+        use quick_xml::Reader;
+        use quick_xml::events::Event;
+
+        let mut reader = Reader::from_str(content);
+        reader.config_mut().trim_text(true);
+
+        let mut in_ttl = false;
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                    b"ttl" => in_ttl = true,
+                    // <ttl> belongs to the channel, before any item
+                    b"item" => return None,
+                    _ => {}
+                },
+                Ok(Event::Text(ref e)) if in_ttl => {
+                    let text = e.unescape().ok()?;
+                    return text.trim().parse().ok().filter(|ttl: &i32| *ttl > 0);
+                }
+                Ok(Event::End(_)) => in_ttl = false,
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {}
+            }
+        }
     }
 
     /// Parse RSS XML content into items
@@ -474,4 +513,26 @@ mod tests {
         assert!(validate_url_for_ssrf("https://example.com/feed.xml").is_ok());
         assert!(validate_url_for_ssrf("http://feeds.example.org/rss").is_ok());
     }
+
+    #[test]
+    fn test_parse_ttl() {
+        let content = r#"
+        <rss version="2.0">
+        <channel>
+            <title>Test Feed</title>
+            <ttl>30</ttl>
+            <item><title>Show S01E01</title><link>https://example.com/1</link></item>
+        </channel>
+        </rss>
+        "#;
+        assert_eq!(RssService::parse_ttl(content), Some(30));
+        assert_eq!(
+            RssService::parse_ttl("<rss><channel><ttl>0</ttl></channel></rss>"),
+            None
+        );
+        assert_eq!(
+            RssService::parse_ttl("<rss><channel></channel></rss>"),
+            None
+        );
+    }
 }
@@ -0,0 +1,148 @@
+//! Scanner exclusion rules
+//!
+//! Decides which folders and files the library scanner skips. System folders
+//! created by NAS appliances and operating systems (Synology `@eaDir`,
+//! `#recycle`, `$RECYCLE.BIN`, dot-folders...) are always skipped, since they
+//! hold thumbnails and trash rather than media. Libraries can add their own
+//! glob patterns on top, matched against the path relative to the library root.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+
+/// Folders that never contain library media
+const SYSTEM_FOLDERS: &[&str] = &[
+    "@eadir",
+    "@recycle",
+    "@recently-snapshot",
+    "#recycle",
+    "#snapshot",
+    "$recycle.bin",
+    "system volume information",
+    "lost+found",
+];
+
+/// Whether a folder name is a NAS/OS system folder or a dot-folder
+pub fn is_system_folder(name: &str) -> bool {
+    name.starts_with('.') || SYSTEM_FOLDERS.contains(&name.to_lowercase().as_str())
+}
+
+/// Compiled per-library exclusion patterns
+#[derive(Debug, Clone)]
+pub struct ScanExclusions {
+    globs: GlobSet,
+}
+
+impl ScanExclusions {
+    /// Compile a library's exclude patterns
+    ///
+    /// Matching is case-insensitive. A pattern without a `/` or glob
+    /// metacharacters (e.g. `@eaDir`, `Featurettes`) is treated as a name and
+    /// matches that file or folder anywhere in the library.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+            for expanded in expand_pattern(pattern) {
+                let glob = GlobBuilder::new(&expanded)
+                    .case_insensitive(true)
+                    .literal_separator(true)
+                    .build()
+                    .with_context(|| format!("Invalid exclude pattern '{}'", pattern))?;
+                builder.add(glob);
+            }
+        }
+
+        Ok(Self {
+            globs: builder.build()?,
+        })
+    }
+
+    /// No library patterns (system folders are still skipped)
+    pub fn none() -> Self {
+        Self {
+            globs: GlobSet::empty(),
+        }
+    }
+
+    /// Whether a path (relative to the library root) matches a library pattern
+    pub fn is_excluded(&self, relative_path: &Path) -> bool {
+        if self.globs.is_empty() {
+            return false;
+        }
+        // Match with forward slashes so patterns work the same on Windows
+        let normalized = relative_path.to_string_lossy().replace('\\', "/");
+        self.globs.is_match(normalized.as_str())
+    }
+}
+
+/// Expand user shorthand into the globs actually matched
+fn expand_pattern(pattern: &str) -> Vec<String> {
+    let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+    let is_name = !pattern.contains('/') && !pattern.contains(['*', '?', '[', '{']);
+    if is_name {
+        // The item itself, or anything inside it
+        vec![format!("**/{}", pattern), format!("**/{}/**", pattern)]
+    } else if !pattern.contains('/') {
+        // "*.sample.*" should match at any depth, not just the library root
+        vec![format!("**/{}", pattern)]
+    } else {
+        vec![pattern.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exclusions(patterns: &[&str]) -> ScanExclusions {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_string()).collect();
+        ScanExclusions::new(&patterns).unwrap()
+    }
+
+    #[test]
+    fn test_is_system_folder() {
+        assert!(is_system_folder("@eaDir"));
+        assert!(is_system_folder("#recycle"));
+        assert!(is_system_folder("$RECYCLE.BIN"));
+        assert!(is_system_folder(".AppleDouble"));
+        assert!(!is_system_folder("Season 01"));
+        assert!(!is_system_folder("Extras"));
+    }
+
+    #[test]
+    fn test_directory_glob() {
+        let ex = exclusions(&["**/Extras/**"]);
+        assert!(ex.is_excluded(Path::new("Show/Extras/Behind the Scenes.mkv")));
+        assert!(ex.is_excluded(Path::new("Show/extras/clip.mkv")));
+        assert!(!ex.is_excluded(Path::new("Show/Season 01/Show - S01E01.mkv")));
+    }
+
+    #[test]
+    fn test_filename_glob_matches_at_any_depth() {
+        let ex = exclusions(&["*.sample.*"]);
+        assert!(ex.is_excluded(Path::new("Movie (2020)/movie.sample.mkv")));
+        assert!(ex.is_excluded(Path::new("movie.SAMPLE.mkv")));
+        assert!(!ex.is_excluded(Path::new("Movie (2020)/movie.mkv")));
+    }
+
+    #[test]
+    fn test_bare_name_matches_folder_anywhere() {
+        let ex = exclusions(&["Featurettes"]);
+        assert!(ex.is_excluded(Path::new("Movie (2020)/Featurettes")));
+        assert!(ex.is_excluded(Path::new("Movie (2020)/Featurettes/making-of.mkv")));
+        assert!(!ex.is_excluded(Path::new("Movie (2020)/Featurettes Special.mkv")));
+    }
+
+    #[test]
+    fn test_windows_separators() {
+        let ex = exclusions(&["**/Extras/**"]);
+        assert!(ex.is_excluded(Path::new("Show\\Extras\\clip.mkv")));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(ScanExclusions::new(&["Season [01".to_string()]).is_err());
+        assert!(!ScanExclusions::none().is_excluded(Path::new("anything.mkv")));
+    }
+}
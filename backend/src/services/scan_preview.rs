@@ -0,0 +1,171 @@
+//! Scan preview (dry run)
+//!
+//! Reports what a library scan would do — files it would add, files that have
+//! gone missing, and the shows/movies new files would be matched to or
+//! created as — without writing anything. Lets users sanity-check a library
+//! after changing exclude patterns or match settings before running a real
+//! scan.
+
+use std::collections::HashSet;
+
+use super::file_utils::path_key;
+use super::match_scorer::{fuzzy_similarity, year_match};
+
+/// Cap on metadata lookups per preview, so a first preview of a large
+/// library doesn't hammer the providers
+pub const MAX_PREVIEW_LOOKUPS: usize = 50;
+
+/// Cap on file paths listed in a preview (counts are always complete)
+pub const MAX_PREVIEW_FILES: usize = 1000;
+
+/// What a scan would do with a group of new files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewAction {
+    /// Match found that isn't in the library yet; it would be created
+    Create,
+    /// Match found that is already in the library; files would be linked to it
+    LinkExisting,
+    /// No metadata match; files would be added unlinked
+    NoMatch,
+    /// Auto-add is off; files would be added unlinked without matching
+    AddUnlinked,
+    /// Not looked up (over the lookup cap, or the lookup failed)
+    NotChecked,
+}
+
+impl PreviewAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::LinkExisting => "link_existing",
+            Self::NoMatch => "no_match",
+            Self::AddUnlinked => "add_unlinked",
+            Self::NotChecked => "not_checked",
+        }
+    }
+}
+
+/// A group of new files and what the scan would match them to
+#[derive(Debug, Clone)]
+pub struct PreviewItem {
+    /// Show or movie name parsed from the filenames
+    pub parsed_name: String,
+    pub parsed_year: Option<u32>,
+    pub file_count: usize,
+    pub action: PreviewAction,
+    pub match_name: Option<String>,
+    pub match_year: Option<i32>,
+    pub provider: Option<String>,
+    pub provider_id: Option<u32>,
+    /// How closely the match fits the parsed name/year (0.0 - 1.0)
+    pub confidence: Option<f64>,
+    /// Set when the lookup failed
+    pub error: Option<String>,
+}
+
+/// Report of what a scan would do
+#[derive(Debug, Clone, Default)]
+pub struct ScanPreview {
+    pub library_id: uuid::Uuid,
+    pub library_name: String,
+    /// Media files found on disk
+    pub total_files: usize,
+    /// Files on disk not yet in the library
+    pub new_file_count: usize,
+    pub new_files: Vec<String>,
+    /// Library files no longer on disk
+    pub missing_file_count: usize,
+    pub missing_files: Vec<String>,
+    pub excluded_files: i32,
+    pub skipped_folders: i32,
+    /// Shows/movies new files would be matched to or created as
+    pub items: Vec<PreviewItem>,
+    /// Caveats, e.g. lookups skipped or types without a matching preview
+    pub notes: Vec<String>,
+}
+
+impl ScanPreview {
+    /// Items a real scan would create
+    pub fn items_to_create(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|i| i.action == PreviewAction::Create)
+            .count()
+    }
+}
+
+/// Split paths into those not yet known (new) and known ones the walk didn't
+/// find (missing, unless they are still on disk but now excluded)
+///
+/// Paths are compared with [`path_key`], so case-only differences on Windows
+/// don't show up as a new file plus a missing one.
+pub fn diff_paths<'a>(
+    discovered: &'a [String],
+    known: &'a [String],
+) -> (Vec<&'a str>, Vec<&'a str>) {
+    let known_keys: HashSet<String> = known.iter().map(|p| path_key(p)).collect();
+    let discovered_keys: HashSet<String> = discovered.iter().map(|p| path_key(p)).collect();
+
+    let new = discovered
+        .iter()
+        .filter(|p| !known_keys.contains(&path_key(p)))
+        .map(String::as_str)
+        .collect();
+    let missing = known
+        .iter()
+        .filter(|p| !discovered_keys.contains(&path_key(p)))
+        .map(String::as_str)
+        .collect();
+    (new, missing)
+}
+
+/// Confidence that a metadata result is the title parsed from filenames
+///
+/// Name similarity dominates; when both sides have a year it counts for a
+/// fifth of the score.
+pub fn match_confidence(
+    parsed_name: &str,
+    parsed_year: Option<u32>,
+    match_name: &str,
+    match_year: Option<i32>,
+) -> f64 {
+    let name = fuzzy_similarity(parsed_name, match_name);
+    match (parsed_year, match_year) {
+        (Some(parsed), Some(_)) => name * 0.8 + year_match(Some(parsed as i32), match_year) * 0.2,
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_paths() {
+        let discovered = vec![
+            "/tv/Show/S01E01.mkv".to_string(),
+            "/tv/Show/S01E02.mkv".to_string(),
+        ];
+        let known = vec![
+            "/tv/Show/S01E01.mkv".to_string(),
+            "/tv/Old/S01E01.mkv".to_string(),
+        ];
+        let (new, missing) = diff_paths(&discovered, &known);
+        assert_eq!(new, vec!["/tv/Show/S01E02.mkv"]);
+        assert_eq!(missing, vec!["/tv/Old/S01E01.mkv"]);
+    }
+
+    #[test]
+    fn test_match_confidence() {
+        let exact = match_confidence("Breaking Bad", Some(2008), "Breaking Bad", Some(2008));
+        assert!(exact > 0.99);
+
+        let wrong_year = match_confidence("Breaking Bad", Some(2008), "Breaking Bad", Some(2015));
+        assert!(wrong_year < exact && wrong_year >= 0.79);
+
+        let no_year = match_confidence("Breaking Bad", None, "Breaking Bad", Some(2008));
+        assert!(no_year > 0.99);
+
+        assert!(match_confidence("Breaking Bad", None, "The Office", None) < no_year);
+    }
+}
@@ -17,7 +17,7 @@
 //! Shows are processed in parallel with a configurable concurrency limit
 //! (default: 3 concurrent metadata fetches).
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -29,6 +29,8 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 use walkdir::WalkDir;
 
+use super::disc_structure::{self, DiscFolder, DiscKind};
+use super::ffmpeg::FfmpegService;
 use super::file_matcher::{FileMatcher, FileInfo, FileMatchTarget};
 use super::file_processor::{FileProcessor, ProcessTarget};
 use super::filename_parser::{self, ParsedEpisode};
@@ -38,7 +40,14 @@ use super::metadata::{
 };
 use super::organizer::OrganizerService;
 use super::queues::{MediaAnalysisJob, MediaAnalysisQueue};
-use crate::db::{CreateEpisode, CreateMediaFile, Database};
+use super::file_utils::path_key;
+use super::missing_files::{MissingFilePolicy, looks_unmounted, missing_files_message};
+use super::scan_exclusions::{ScanExclusions, is_system_folder};
+use super::scan_preview::{
+    MAX_PREVIEW_FILES, MAX_PREVIEW_LOOKUPS, PreviewAction, PreviewItem, ScanPreview, diff_paths,
+    match_confidence,
+};
+use crate::db::{CreateEpisode, CreateMediaFile, Database, LibraryRecord};
 
 /// Configuration for scanner concurrency
 #[derive(Debug, Clone)]
@@ -100,6 +109,10 @@ pub struct ScanProgress {
     pub removed_files: i32,
     pub shows_added: i32,
     pub episodes_linked: i32,
+    /// Media files skipped because they matched a library exclude pattern
+    pub excluded_files: i32,
+    /// System folders (NAS metadata, recycle bins, dot-folders) not descended into
+    pub skipped_folders: i32,
 }
 
 /// Discovered file with parsed info
@@ -266,6 +279,8 @@ impl ScannerService {
                 removed_files: 0,
                 shows_added: 0,
                 episodes_linked: 0,
+                excluded_files: 0,
+                skipped_folders: 0,
             });
         }
 
@@ -298,6 +313,8 @@ impl ScannerService {
                 removed_files: 0,
                 shows_added: 0,
                 episodes_linked: 0,
+                excluded_files: 0,
+                skipped_folders: 0,
             });
         }
 
@@ -324,51 +341,9 @@ impl ScannerService {
             }
         }
 
-        // Get extensions for this library type
-        let valid_extensions = get_extensions_for_library_type(&library.library_type);
-
-        // First pass: collect all media files
-        let mut video_files: Vec<DiscoveredFile> = Vec::new();
-
-        for entry in WalkDir::new(library_path)
-            .follow_links(true)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            let path = entry.path();
-            if path.is_file()
-                && let Some(ext) = path.extension().and_then(|e| e.to_str())
-                && valid_extensions.contains(&ext.to_lowercase().as_str())
-            {
-                let path_str = path.to_string_lossy().to_string();
-                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
-                let filename = path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("")
-                    .to_string();
-
-                // Parse based on library type
-                let parsed = match library.library_type.as_str() {
-                    "tv" => filename_parser::parse_episode(&filename),
-                    "movies" => filename_parser::parse_movie(&filename),
-                    _ => filename_parser::parse_episode(&filename), // Fallback
-                };
-
-                let relative_path = path
-                    .strip_prefix(library_path)
-                    .map(|p| p.to_string_lossy().to_string())
-                    .ok();
-
-                video_files.push(DiscoveredFile {
-                    path: path_str,
-                    size,
-                    filename,
-                    parsed,
-                    relative_path,
-                });
-            }
-        }
+        let (video_files, excluded_files, skipped_folders) =
+            Self::discover_library(&library).await?;
+        let discovered_paths: Vec<String> = video_files.iter().map(|f| f.path.clone()).collect();
 
         let total_files = video_files.len() as i32;
         info!(
@@ -388,6 +363,8 @@ impl ScannerService {
             removed_files: 0,
             shows_added: 0,
             episodes_linked: 0,
+            excluded_files,
+            skipped_folders,
         };
         let _ = self.progress_tx.send(progress.clone());
 
@@ -445,8 +422,12 @@ impl ScannerService {
                 .await?;
         }
 
-        // Note: File removal is handled in process_* methods
-        // The scan tracks new files added; removed files would need a separate cleanup job
+        // Files deleted from disk are handled after processing, so items whose
+        // file was replaced (e.g. an upgrade under a new name) are relinked first
+        match self.handle_missing_files(&library, &discovered_paths).await {
+            Ok(removed) => progress.removed_files = removed,
+            Err(e) => error!("Failed to process missing files for '{}': {}", library.name, e),
+        }
 
         // Update library last_scanned_at
         self.db.libraries().update_last_scanned(library_id).await?;
@@ -479,6 +460,8 @@ impl ScannerService {
             removed = progress.removed_files,
             shows_added = progress.shows_added,
             episodes_linked = progress.episodes_linked,
+            excluded = progress.excluded_files,
+            skipped_folders = progress.skipped_folders,
             "Library scan completed for '{}': {} files ({} new, {} removed)",
             library.name, progress.total_files, progress.new_files, progress.removed_files
         );
@@ -565,6 +548,383 @@ impl ScannerService {
         Ok(progress)
     }
 
+    /// Report what a scan of the library would do, without writing anything
+    ///
+    /// Walks the library with the same rules as a real scan, compares the result
+    /// with the files already in the database, and looks up metadata matches for
+    /// groups of new TV/movie files (up to [`MAX_PREVIEW_LOOKUPS`]).
+    pub async fn preview_scan(&self, library_id: Uuid) -> Result<ScanPreview> {
+        let library = self
+            .db
+            .libraries()
+            .get_by_id(library_id)
+            .await
+            .context("Failed to query library from database")?
+            .context("Library not found")?;
+
+        if !Path::new(&library.path).exists() {
+            anyhow::bail!("Library path does not exist: {}", library.path);
+        }
+
+        let (files, excluded_files, skipped_folders) = Self::discover_library(&library).await?;
+
+        let known: Vec<String> = self
+            .db
+            .media_files()
+            .list_by_library(library_id)
+            .await?
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        let discovered: Vec<String> = files.iter().map(|f| f.path.clone()).collect();
+        let (new_paths, not_found) = diff_paths(&discovered, &known);
+        // Files still on disk but no longer discovered are now excluded, not missing
+        let missing: Vec<&str> = not_found
+            .into_iter()
+            .filter(|p| !Path::new(p).exists())
+            .collect();
+
+        let mut preview = ScanPreview {
+            library_id,
+            library_name: library.name.clone(),
+            total_files: files.len(),
+            new_file_count: new_paths.len(),
+            new_files: new_paths
+                .iter()
+                .take(MAX_PREVIEW_FILES)
+                .map(|p| p.to_string())
+                .collect(),
+            missing_file_count: missing.len(),
+            missing_files: missing
+                .iter()
+                .take(MAX_PREVIEW_FILES)
+                .map(|p| p.to_string())
+                .collect(),
+            excluded_files,
+            skipped_folders,
+            ..Default::default()
+        };
+
+        let library_type = library.library_type.as_str();
+        if library_type != "tv" && library_type != "movies" {
+            preview.notes.push(format!(
+                "Metadata matches aren't previewed for {} libraries; they are matched from file tags during the scan",
+                library_type
+            ));
+            return Ok(preview);
+        }
+
+        // Group new files the way the scan does: by show name, or movie title + year
+        let new_keys: std::collections::HashSet<String> =
+            new_paths.iter().map(|p| path_key(p)).collect();
+        let mut groups: HashMap<(String, Option<u32>), (String, usize)> = HashMap::new();
+        let mut unparsed = 0;
+        for file in files
+            .iter()
+            .filter(|f| new_keys.contains(&path_key(&f.path)))
+        {
+            let Some(name) = file.parsed.show_name.as_deref() else {
+                unparsed += 1;
+                continue;
+            };
+            let year = if library_type == "movies" {
+                file.parsed.year
+            } else {
+                None
+            };
+            groups
+                .entry((name.to_lowercase(), year))
+                .or_insert_with(|| (name.to_string(), 0))
+                .1 += 1;
+        }
+        if unparsed > 0 {
+            preview.notes.push(format!(
+                "{} new file(s) have no recognizable title and would be added unlinked",
+                unparsed
+            ));
+        }
+
+        let mut groups: Vec<((String, Option<u32>), (String, usize))> =
+            groups.into_iter().collect();
+        // Largest groups first, so the lookup cap covers the most files
+        groups.sort_by(|a, b| b.1.1.cmp(&a.1.1).then_with(|| a.0.cmp(&b.0)));
+        if library.auto_add_discovered && groups.len() > MAX_PREVIEW_LOOKUPS {
+            preview.notes.push(format!(
+                "Only the {} largest of {} groups were looked up",
+                MAX_PREVIEW_LOOKUPS,
+                groups.len()
+            ));
+        }
+
+        for (index, ((_, year), (name, file_count))) in groups.into_iter().enumerate() {
+            let mut item = PreviewItem {
+                parsed_name: name,
+                parsed_year: year,
+                file_count,
+                action: PreviewAction::NotChecked,
+                match_name: None,
+                match_year: None,
+                provider: None,
+                provider_id: None,
+                confidence: None,
+                error: None,
+            };
+
+            if !library.auto_add_discovered {
+                item.action = PreviewAction::AddUnlinked;
+            } else if index < MAX_PREVIEW_LOOKUPS
+                && let Err(e) = self.preview_match(&library, &mut item).await
+            {
+                item.action = PreviewAction::NotChecked;
+                item.error = Some(e.to_string());
+            }
+            preview.items.push(item);
+        }
+
+        if !library.auto_add_discovered {
+            preview
+                .notes
+                .push("Auto-add is off, so new files would be added without matching".to_string());
+        }
+
+        Ok(preview)
+    }
+
+    /// Look up the metadata match a scan would pick for a preview group
+    async fn preview_match(&self, library: &LibraryRecord, item: &mut PreviewItem) -> Result<()> {
+        if library.library_type == "tv" {
+            let mut results = self
+                .metadata_service
+                .search_shows(&item.parsed_name)
+                .await?;
+            if results.is_empty() {
+                item.action = PreviewAction::NoMatch;
+                return Ok(());
+            }
+            let best = results.remove(0);
+            let existing = best.provider == MetadataProvider::TvMaze
+                && self
+                    .db
+                    .tv_shows()
+                    .get_by_tvmaze_id(library.id, best.provider_id as i32)
+                    .await?
+                    .is_some();
+            item.confidence = Some(match_confidence(
+                &item.parsed_name,
+                None,
+                &best.name,
+                best.year,
+            ));
+            item.action = if existing {
+                PreviewAction::LinkExisting
+            } else {
+                PreviewAction::Create
+            };
+            item.match_name = Some(best.name);
+            item.match_year = best.year;
+            item.provider = Some(format!("{:?}", best.provider).to_lowercase());
+            item.provider_id = Some(best.provider_id);
+        } else {
+            let year = item.parsed_year.map(|y| y as i32);
+            let mut results = self
+                .metadata_service
+                .search_movies(&item.parsed_name, year)
+                .await?;
+            if results.is_empty() && year.is_some() {
+                results = self
+                    .metadata_service
+                    .search_movies(&item.parsed_name, None)
+                    .await?;
+            }
+            if results.is_empty() {
+                item.action = PreviewAction::NoMatch;
+                return Ok(());
+            }
+            let best = results.remove(0);
+            let existing = self
+                .db
+                .movies()
+                .get_by_tmdb_id(library.id, best.provider_id as i32)
+                .await?
+                .is_some();
+            item.confidence = Some(match_confidence(
+                &item.parsed_name,
+                item.parsed_year,
+                &best.title,
+                best.year,
+            ));
+            item.action = if existing {
+                PreviewAction::LinkExisting
+            } else {
+                PreviewAction::Create
+            };
+            item.match_name = Some(best.title);
+            item.match_year = best.year;
+            item.provider = Some(format!("{:?}", best.provider).to_lowercase());
+            item.provider_id = Some(best.provider_id);
+        }
+        Ok(())
+    }
+
+    /// Walk a library and collect its media files, treating disc folder rips
+    /// as one movie each
+    ///
+    /// Returns the files plus counts of excluded media files and skipped
+    /// system/excluded folders.
+    async fn discover_library(library: &LibraryRecord) -> Result<(Vec<DiscoveredFile>, i32, i32)> {
+        let walk_library = library.clone();
+        let (mut files, discs, excluded_files, skipped_folders) =
+            tokio::task::spawn_blocking(move || Self::discover_files(&walk_library)).await?;
+
+        if !discs.is_empty() {
+            let ffmpeg = FfmpegService::new();
+            for disc in discs {
+                match Self::discover_disc(library, &disc, &ffmpeg).await {
+                    Some(file) => files.push(file),
+                    None => warn!(root = %disc.root.display(), "Disc folder has no playable title"),
+                }
+            }
+        }
+
+        Ok((files, excluded_files, skipped_folders))
+    }
+
+    /// A disc folder rip as one media file: its main title, named after the folder
+    async fn discover_disc(
+        library: &LibraryRecord,
+        disc: &DiscFolder,
+        ffmpeg: &FfmpegService,
+    ) -> Option<DiscoveredFile> {
+        let title = disc_structure::main_title(disc, ffmpeg).await?;
+        let path = title.files.first()?;
+        let filename = disc.root.file_name()?.to_string_lossy().to_string();
+        debug!(
+            root = %disc.root.display(),
+            kind = ?disc.kind,
+            titles = disc.titles.len(),
+            main_title = %path.display(),
+            "Found disc folder"
+        );
+
+        Some(DiscoveredFile {
+            path: path.to_string_lossy().to_string(),
+            size: title.size,
+            parsed: filename_parser::parse_movie(&filename),
+            filename,
+            relative_path: path
+                .strip_prefix(&library.path)
+                .map(|p| p.to_string_lossy().to_string())
+                .ok(),
+        })
+    }
+
+    /// Walk a library and collect its media files with parsed filename info
+    ///
+    /// In movie libraries, `VIDEO_TS` and `BDMV` folders aren't walked but
+    /// returned as discs. Also returns counts of excluded media files and
+    /// skipped system/excluded folders.
+    fn discover_files(library: &LibraryRecord) -> (Vec<DiscoveredFile>, Vec<DiscFolder>, i32, i32) {
+        let library_path = Path::new(&library.path);
+        let find_discs = library.library_type == "movies";
+
+        // Get extensions for this library type
+        let valid_extensions = get_extensions_for_library_type(&library.library_type);
+
+        // Library exclude patterns; a bad pattern shouldn't block the whole scan
+        let exclusions = ScanExclusions::new(&library.exclude_patterns).unwrap_or_else(|e| {
+            warn!(
+                "Ignoring exclude patterns for '{}': {}",
+                library.name, e
+            );
+            ScanExclusions::none()
+        });
+
+        // First pass: collect all media files
+        let mut video_files: Vec<DiscoveredFile> = Vec::new();
+        let mut discs: Vec<DiscFolder> = Vec::new();
+        let mut excluded_files = 0;
+        let mut skipped_folders = 0;
+
+        let walker = WalkDir::new(library_path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|entry| {
+                // Never descend into system folders or excluded folders
+                if entry.depth() == 0 || !entry.file_type().is_dir() {
+                    return true;
+                }
+                let relative = entry.path().strip_prefix(library_path).unwrap_or(entry.path());
+                let skip = entry.file_name().to_str().is_some_and(is_system_folder)
+                    || exclusions.is_excluded(relative);
+                if skip {
+                    skipped_folders += 1;
+                    return false;
+                }
+                // A disc's files are read as a whole, not walked
+                if find_discs
+                    && let Some(kind) = entry
+                        .file_name()
+                        .to_str()
+                        .and_then(DiscKind::from_folder_name)
+                {
+                    discs.extend(disc_structure::read_disc(entry.path(), kind));
+                    return false;
+                }
+                true
+            });
+
+        for entry in walker.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            if path.is_file()
+                && let Some(ext) = path.extension().and_then(|e| e.to_str())
+                && valid_extensions.contains(&ext.to_lowercase().as_str())
+            {
+                let relative = path.strip_prefix(library_path).unwrap_or(path);
+                if exclusions.is_excluded(relative) {
+                    excluded_files += 1;
+                    continue;
+                }
+
+                let path_str = path.to_string_lossy().to_string();
+                let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                let filename = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("")
+                    .to_string();
+
+                // Parse based on library type
+                let parsed = match library.library_type.as_str() {
+                    "tv" => filename_parser::parse_episode(&filename),
+                    "movies" => filename_parser::parse_movie(&filename),
+                    _ => filename_parser::parse_episode(&filename), // Fallback
+                };
+
+                let relative_path = path
+                    .strip_prefix(library_path)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .ok();
+
+                video_files.push(DiscoveredFile {
+                    path: path_str,
+                    size,
+                    filename,
+                    parsed,
+                    relative_path,
+                });
+            }
+        }
+
+        if excluded_files > 0 || skipped_folders > 0 {
+            info!(
+                "Skipped {} excluded media files and {} system/excluded folders in '{}'",
+                excluded_files, skipped_folders, library.name
+            );
+        }
+
+        (video_files, discs, excluded_files, skipped_folders)
+    }
+
     /// Process TV library with auto-add discovered shows
     ///
     /// Uses bounded concurrency to prevent overwhelming external APIs:
@@ -639,6 +999,8 @@ impl ScannerService {
                 let show_files = show_files.clone();
                 let library_name = progress.library_name.clone();
                 let total_files = progress.total_files;
+                let excluded_files = progress.excluded_files;
+                let skipped_folders = progress.skipped_folders;
                 let analysis_queue = self.analysis_queue.clone();
 
                 let handle = tokio::spawn(async move {
@@ -698,6 +1060,8 @@ impl ScannerService {
                                 removed_files: 0,
                                 shows_added: shows_added.load(Ordering::SeqCst),
                                 episodes_linked: episodes_linked.load(Ordering::SeqCst),
+                                excluded_files,
+                                skipped_folders,
                             });
                         }
 
@@ -855,6 +1219,8 @@ impl ScannerService {
                 let movie_files = movie_files.clone();
                 let library_name = progress.library_name.clone();
                 let total_files = progress.total_files;
+                let excluded_files = progress.excluded_files;
+                let skipped_folders = progress.skipped_folders;
                 let analysis_queue = self.analysis_queue.clone();
                 let year = *year;
                 let notification_service = self.notification_service.clone();
@@ -960,6 +1326,8 @@ impl ScannerService {
                                 removed_files: 0,
                                 shows_added: movies_added.load(Ordering::SeqCst),
                                 episodes_linked: files_linked.load(Ordering::SeqCst),
+                                excluded_files,
+                                skipped_folders,
                             });
                         }
 
@@ -1168,13 +1536,10 @@ impl ScannerService {
             source_name: None,
         };
 
-        let filename = std::path::Path::new(&file.path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(&file.path);
-
-        // Match video file against the library
-        let matches = file_matcher.match_video_file(&file_info, filename, &[library]).await?;
+        // Match video file against the library; a disc is matched by its folder name
+        let matches = file_matcher
+            .match_video_file(&file_info, &file.filename, &[library])
+            .await?;
 
         // Check if this is a sample file (FileMatcher detects samples)
         let is_sample = matches.iter().any(|m| matches!(m.match_target, FileMatchTarget::Sample));
@@ -1320,6 +1685,8 @@ impl ScannerService {
                 let album_files: Vec<_> = album_files.clone();
                 let library_name = progress.library_name.clone();
                 let total_files = progress.total_files;
+                let excluded_files = progress.excluded_files;
+                let skipped_folders = progress.skipped_folders;
                 let analysis_queue = self.analysis_queue.clone();
 
                 let handle = tokio::spawn(async move {
@@ -1386,6 +1753,8 @@ impl ScannerService {
                                 removed_files: 0,
                                 shows_added: albums_added.load(Ordering::SeqCst),
                                 episodes_linked: files_linked.load(Ordering::SeqCst),
+                                excluded_files,
+                                skipped_folders,
                             });
                         }
 
@@ -1600,6 +1969,8 @@ impl ScannerService {
                 let audiobook_files: Vec<_> = audiobook_files.clone();
                 let library_name = progress.library_name.clone();
                 let total_files = progress.total_files;
+                let excluded_files = progress.excluded_files;
+                let skipped_folders = progress.skipped_folders;
                 let analysis_queue = self.analysis_queue.clone();
                 let search_query = search_query.clone();
 
@@ -1668,6 +2039,8 @@ impl ScannerService {
                                 removed_files: 0,
                                 shows_added: audiobooks_added.load(Ordering::SeqCst),
                                 episodes_linked: files_linked.load(Ordering::SeqCst),
+                                excluded_files,
+                                skipped_folders,
                             });
                         }
 
@@ -2909,6 +3282,115 @@ impl ScannerService {
         Ok(progress)
     }
 
+    /// Apply the library's missing-file policy to files no longer on disk
+    ///
+    /// Returns the number of file records removed.
+    async fn handle_missing_files(
+        &self,
+        library: &LibraryRecord,
+        discovered: &[String],
+    ) -> Result<i32> {
+        let known_files = self.db.media_files().list_by_library(library.id).await?;
+        let known: Vec<String> = known_files.iter().map(|f| f.path.clone()).collect();
+        let (_, not_found) = diff_paths(discovered, &known);
+        // Files still on disk but no longer discovered are now excluded, not deleted
+        let missing_keys: HashSet<String> = not_found
+            .into_iter()
+            .filter(|p| !Path::new(p).exists())
+            .map(path_key)
+            .collect();
+        if missing_keys.is_empty() {
+            return Ok(0);
+        }
+
+        let missing_files: Vec<_> = known_files
+            .iter()
+            .filter(|f| missing_keys.contains(&path_key(&f.path)))
+            .collect();
+        let missing_paths: Vec<String> = missing_files.iter().map(|f| f.path.clone()).collect();
+
+        let mut policy = MissingFilePolicy::parse(&library.missing_file_policy);
+        if policy.removes_records() && looks_unmounted(discovered.len(), missing_files.len()) {
+            warn!(
+                "Every file in '{}' is missing; treating it as unmounted and keeping the records",
+                library.name
+            );
+            policy = MissingFilePolicy::Flag;
+        }
+
+        info!(
+            library_id = %library.id,
+            missing = missing_files.len(),
+            policy = policy.as_str(),
+            "Found files deleted from disk in '{}'",
+            library.name
+        );
+
+        let mut removed = 0;
+        if policy.removes_records() {
+            let mut movie_ids = HashSet::new();
+            let mut episode_ids = HashSet::new();
+            for file in &missing_files {
+                movie_ids.extend(file.movie_id);
+                episode_ids.extend(file.episode_id);
+                if self.db.media_files().delete(file.id).await? {
+                    removed += 1;
+                }
+            }
+
+            // Deleting the record unlinks it, which is what makes monitored
+            // items wanted again. Items relinked to a replacement file this
+            // scan still have a file and are left alone.
+            let mut show_ids = HashSet::new();
+            for episode_id in episode_ids {
+                if let Some(episode) = self.db.episodes().get_by_id(episode_id).await?
+                    && episode.media_file_id.is_none()
+                {
+                    show_ids.insert(episode.tv_show_id);
+                }
+            }
+            for show_id in &show_ids {
+                if policy == MissingFilePolicy::Unmonitor {
+                    self.db.tv_shows().set_monitored(*show_id, false).await?;
+                }
+                if let Err(e) = self.db.tv_shows().update_stats(*show_id).await {
+                    warn!(show_id = %show_id, error = %e, "Failed to update show stats");
+                }
+            }
+            if policy == MissingFilePolicy::Unmonitor {
+                for movie_id in movie_ids {
+                    if let Some(movie) = self.db.movies().get_by_id(movie_id).await?
+                        && movie.media_file_id.is_none()
+                    {
+                        self.db.movies().set_monitored(movie_id, false).await?;
+                    }
+                }
+            }
+        }
+
+        if let Some(notif_svc) = &self.notification_service
+            && let Err(e) = notif_svc
+                .create_full(crate::db::CreateNotification {
+                    user_id: library.user_id,
+                    title: format!("Files missing from {}", library.name),
+                    message: missing_files_message(policy, &library.name, &missing_paths),
+                    notification_type: crate::db::NotificationType::Warning,
+                    category: crate::db::NotificationCategory::Storage,
+                    library_id: Some(library.id),
+                    torrent_id: None,
+                    media_file_id: None,
+                    pending_match_id: None,
+                    action_type: None,
+                    action_data: None,
+                })
+                .await
+        {
+            error!(error = %e, "Failed to create missing files notification");
+        }
+
+        Ok(removed)
+    }
+
     /// Add a file without linking to a show
     async fn add_unlinked_file(
         &self,
@@ -0,0 +1,608 @@
+//! In-place self-update for bare-metal installs
+//!
+//! `apply_update` downloads the latest release archive for this platform,
+//! checks it against the release's `SHA256SUMS` file, unpacks it and makes
+//! sure the new binary runs and reports the expected version. It then
+//! snapshots the database, swaps the binary and leaves a marker next to it.
+//!
+//! The first start of the new version counts an attempt in the marker and
+//! clears it once `/readyz` reports ready. A start that finds an attempt
+//! already recorded (the last one crashed or never got ready) puts the old
+//! binary and the database snapshot back and restarts into the old version.
+//! The snapshot matters: the old binary refuses to start against migrations
+//! it doesn't know about.
+//!
+//! Releases are checksummed but not signed, so the checksum only protects
+//! against corrupt or tampered downloads from a mirror, not a compromised
+//! release. Docker installs update by pulling a new image instead.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncWriteExt;
+use tracing::{error, info, warn};
+
+use crate::db::Database;
+use crate::jobs::update_check::{self, ReleaseInfo};
+
+/// Release asset listing the SHA-256 of every other asset
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Directory next to the binary where downloads are unpacked
+const STAGING_DIR: &str = ".librarian-update";
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// How long the new binary gets to answer `--version`
+const SMOKE_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a freshly updated server gets to report ready
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Exit code used to ask a service manager for a restart (EX_TEMPFAIL)
+const RESTART_EXIT_CODE: i32 = 75;
+
+/// An installed update that hasn't passed its health check yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingUpdate {
+    pub from_version: String,
+    pub to_version: String,
+    /// Where the previous binary was kept
+    pub previous_binary: PathBuf,
+    pub database_path: Option<PathBuf>,
+    /// Database copy taken just before the swap
+    pub database_snapshot: Option<PathBuf>,
+    /// Starts of the new version so far
+    pub attempts: u32,
+    pub applied_at: String,
+}
+
+/// Why this install can't update itself, or None if it can
+pub fn unsupported_reason() -> Option<String> {
+    if cfg!(debug_assertions) {
+        return Some("Development builds don't update themselves".to_string());
+    }
+    if running_in_container() {
+        return Some("Running in a container; pull the new image to update".to_string());
+    }
+    if asset_name("0.0.0", std::env::consts::OS, std::env::consts::ARCH).is_none() {
+        return Some(format!(
+            "No release builds are published for {}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ));
+    }
+    let dir = match install_dir() {
+        Ok(dir) => dir,
+        Err(e) => return Some(e.to_string()),
+    };
+    // The service account often can't write to a system-wide install
+    let probe = dir.join(".librarian-write-test");
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            None
+        }
+        Err(e) => Some(format!(
+            "The install directory {} isn't writable: {}",
+            dir.display(),
+            e
+        )),
+    }
+}
+
+/// Download, verify and install the latest release
+///
+/// Returns the installed release. The server keeps running the old version
+/// until `restart` is called.
+pub async fn apply_update(db: &Database) -> Result<ReleaseInfo> {
+    if let Some(reason) = unsupported_reason() {
+        bail!(reason);
+    }
+    let exe = std::env::current_exe().context("Failed to locate the running binary")?;
+    if marker_path(&exe).exists() {
+        bail!("An update has already been installed; restart Librarian to finish it");
+    }
+
+    let release = update_check::fetch_latest_release().await?;
+    if !release.is_update() {
+        bail!(
+            "Already running the latest release ({})",
+            update_check::current_version()
+        );
+    }
+
+    let Some(name) = asset_name(
+        &release.version,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    ) else {
+        bail!("No release build for this platform");
+    };
+    let Some(archive) = release.assets.iter().find(|a| a.name == name) else {
+        bail!("Release {} has no {} download", release.version, name);
+    };
+    let Some(checksums) = release.assets.iter().find(|a| a.name == CHECKSUMS_ASSET) else {
+        bail!(
+            "Release {} publishes no {}; refusing to install an unverified binary",
+            release.version,
+            CHECKSUMS_ASSET
+        );
+    };
+
+    let dir = install_dir()?;
+    let staging = dir.join(STAGING_DIR);
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging)?;
+    }
+    std::fs::create_dir_all(&staging)?;
+
+    let result = stage_and_install(
+        db,
+        &release,
+        &archive.download_url,
+        &checksums.download_url,
+        &name,
+        &staging,
+        &exe,
+    )
+    .await;
+    if let Err(e) = std::fs::remove_dir_all(&staging) {
+        warn!(error = %e, "Failed to clean up the update staging directory");
+    }
+    result?;
+
+    info!(
+        from = update_check::current_version(),
+        to = %release.version,
+        "Update installed, restart pending"
+    );
+    Ok(release)
+}
+
+async fn stage_and_install(
+    db: &Database,
+    release: &ReleaseInfo,
+    archive_url: &str,
+    checksums_url: &str,
+    archive_name: &str,
+    staging: &Path,
+    exe: &Path,
+) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .timeout(DOWNLOAD_TIMEOUT)
+        .user_agent(format!("Librarian/{}", update_check::current_version()))
+        .build()?;
+
+    let sums = client
+        .get(checksums_url)
+        .send()
+        .await?
+        .error_for_status()
+        .context("Failed to download the release checksums")?
+        .text()
+        .await?;
+    let Some(expected) = find_checksum(&sums, archive_name) else {
+        bail!("{} has no entry for {}", CHECKSUMS_ASSET, archive_name);
+    };
+
+    let archive_path = staging.join(archive_name);
+    let actual = download(&client, archive_url, &archive_path).await?;
+    if actual != expected {
+        bail!(
+            "Checksum mismatch for {} (expected {}, got {})",
+            archive_name,
+            expected,
+            actual
+        );
+    }
+
+    // tar reads zip archives too on Windows 10 and later
+    let status = tokio::process::Command::new("tar")
+        .arg("-xf")
+        .arg(&archive_path)
+        .arg("-C")
+        .arg(staging)
+        .status()
+        .await
+        .context("Failed to run tar")?;
+    if !status.success() {
+        bail!("Failed to unpack {} ({})", archive_name, status);
+    }
+
+    let new_binary = staging.join(binary_name());
+    if !new_binary.is_file() {
+        bail!("{} doesn't contain {}", archive_name, binary_name());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&new_binary, std::fs::Permissions::from_mode(0o755))?;
+    }
+    smoke_test(&new_binary, &release.version).await?;
+
+    let (database_path, database_snapshot) = snapshot_database(db, &release.version).await?;
+
+    let pending = PendingUpdate {
+        from_version: update_check::current_version().to_string(),
+        to_version: release.version.clone(),
+        previous_binary: previous_binary_path(exe),
+        database_path,
+        database_snapshot,
+        attempts: 0,
+        applied_at: chrono::Utc::now().to_rfc3339(),
+    };
+    // Written first so a crash mid-swap still leads to a rollback
+    pending.save(&marker_path(exe))?;
+    if let Err(e) = swap_binary(exe, &new_binary, &pending.previous_binary) {
+        let _ = std::fs::remove_file(marker_path(exe));
+        return Err(e.context("Failed to replace the running binary"));
+    }
+    Ok(())
+}
+
+/// Stream a download to disk, returning its SHA-256 as lowercase hex
+async fn download(client: &reqwest::Client, url: &str, path: &Path) -> Result<String> {
+    let mut response = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()
+        .context("Failed to download the release")?;
+
+    let mut file = tokio::fs::File::create(path).await?;
+    let mut hasher = Sha256::new();
+    while let Some(chunk) = response.chunk().await? {
+        hasher.update(&chunk);
+        file.write_all(&chunk).await?;
+    }
+    file.flush().await?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Make sure the new binary starts on this machine and is the version we expect
+async fn smoke_test(binary: &Path, expected_version: &str) -> Result<()> {
+    let output = tokio::time::timeout(
+        SMOKE_TEST_TIMEOUT,
+        tokio::process::Command::new(binary)
+            .arg("--version")
+            .output(),
+    )
+    .await
+    .context("The new binary didn't answer --version in time")?
+    .context("The new binary failed to start")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let reported = stdout.split_whitespace().last().unwrap_or("");
+    if !output.status.success() || reported != expected_version {
+        bail!(
+            "The new binary reported version {:?} instead of {}",
+            stdout.trim(),
+            expected_version
+        );
+    }
+    Ok(())
+}
+
+/// Copy the database aside so a rollback can undo the new version's migrations
+///
+/// Returns the database file and the snapshot, or neither for an in-memory
+/// database.
+async fn snapshot_database(
+    db: &Database,
+    version: &str,
+) -> Result<(Option<PathBuf>, Option<PathBuf>)> {
+    let file: String =
+        sqlx::query_scalar("SELECT file FROM pragma_database_list WHERE name = 'main'")
+            .fetch_one(db.pool())
+            .await?;
+    if file.is_empty() {
+        return Ok((None, None));
+    }
+
+    let database_path = PathBuf::from(file);
+    let mut snapshot_name = database_path.file_name().unwrap_or_default().to_os_string();
+    snapshot_name.push(format!(".pre-{}.bak", version));
+    let snapshot = database_path.with_file_name(snapshot_name);
+    if snapshot.exists() {
+        std::fs::remove_file(&snapshot)?;
+    }
+
+    // VACUUM INTO gives a consistent copy while the server keeps writing
+    sqlx::query("VACUUM INTO ?")
+        .bind(snapshot.to_string_lossy().to_string())
+        .execute(db.pool())
+        .await
+        .context("Failed to snapshot the database")?;
+
+    Ok((Some(database_path), Some(snapshot)))
+}
+
+/// Restart into whatever binary is installed now
+///
+/// Under systemd, exits non-zero and lets the unit's `Restart=` policy start
+/// the new binary. Otherwise starts it with the same arguments and exits.
+pub fn restart() -> ! {
+    if std::env::var_os("INVOCATION_ID").is_some() {
+        info!("Exiting so the service manager restarts Librarian");
+        std::process::exit(RESTART_EXIT_CODE);
+    }
+
+    let spawned = std::env::current_exe().and_then(|exe| {
+        std::process::Command::new(exe)
+            .args(std::env::args_os().skip(1))
+            .spawn()
+    });
+    match spawned {
+        Ok(_) => std::process::exit(0),
+        Err(e) => {
+            error!(error = %e, "Failed to start the updated binary, start Librarian again by hand");
+            std::process::exit(RESTART_EXIT_CODE);
+        }
+    }
+}
+
+/// Check for an update in progress; called first thing on startup
+///
+/// Returns the update when this is its first start. When an earlier start of
+/// the same version never got healthy, rolls back and restarts into the old
+/// version instead of returning. Runs before logging is set up, so it reports
+/// on stderr like the rest of early startup.
+pub fn resume_pending() -> Option<PendingUpdate> {
+    let exe = std::env::current_exe().ok()?;
+    let marker = marker_path(&exe);
+    if !marker.exists() {
+        return None;
+    }
+    let mut pending = match PendingUpdate::load(&marker) {
+        Ok(pending) => pending,
+        Err(e) => {
+            eprintln!(
+                "Ignoring unreadable update marker {}: {}",
+                marker.display(),
+                e
+            );
+            let _ = std::fs::remove_file(&marker);
+            return None;
+        }
+    };
+
+    // A binary replaced by hand, or the old binary after a rollback
+    if pending.to_version != update_check::current_version() {
+        let _ = std::fs::remove_file(&marker);
+        return None;
+    }
+
+    if pending.attempts > 0 {
+        eprintln!(
+            "Librarian {} didn't pass its health check after updating, rolling back to {}",
+            pending.to_version, pending.from_version
+        );
+        match rollback(&exe, &pending) {
+            Ok(()) => restart(),
+            Err(e) => {
+                eprintln!(
+                    "Rollback failed, continuing with {}: {:#}",
+                    pending.to_version, e
+                );
+                let _ = std::fs::remove_file(&marker);
+                return None;
+            }
+        }
+    }
+
+    pending.attempts += 1;
+    if let Err(e) = pending.save(&marker) {
+        eprintln!("Failed to update {}: {}", marker.display(), e);
+    }
+    Some(pending)
+}
+
+/// Wait for the freshly updated server to report ready
+///
+/// Keeps the update once it does. If it doesn't in time, restarts; the next
+/// start finds the attempt recorded and rolls back.
+pub async fn verify_health(pending: PendingUpdate, port: u16) {
+    #[derive(Deserialize)]
+    struct Ready {
+        ready: bool,
+    }
+
+    let url = format!("http://127.0.0.1:{}/readyz", port);
+    let client = reqwest::Client::new();
+    let deadline = tokio::time::Instant::now() + HEALTH_CHECK_TIMEOUT;
+
+    while tokio::time::Instant::now() < deadline {
+        let ready = match client
+            .get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+        {
+            Ok(response) => response.json::<Ready>().await.is_ok_and(|r| r.ready),
+            Err(_) => false,
+        };
+        if ready {
+            finish(&pending);
+            info!(
+                from = %pending.from_version,
+                to = %pending.to_version,
+                "Update complete"
+            );
+            return;
+        }
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+
+    error!(
+        version = %pending.to_version,
+        "Server didn't become ready after updating, restarting to roll back"
+    );
+    restart();
+}
+
+/// Drop the marker and the database snapshot; the previous binary is kept
+fn finish(pending: &PendingUpdate) {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::fs::remove_file(marker_path(&exe));
+    }
+    if let Some(snapshot) = &pending.database_snapshot
+        && let Err(e) = std::fs::remove_file(snapshot)
+    {
+        warn!(path = %snapshot.display(), error = %e, "Failed to remove the pre-update database snapshot");
+    }
+}
+
+/// Put the previous binary and database back
+fn rollback(exe: &Path, pending: &PendingUpdate) -> Result<()> {
+    // The failed binary is kept for bug reports
+    let mut failed_name = exe.file_name().unwrap_or_default().to_os_string();
+    failed_name.push(".failed");
+    swap_binary(
+        exe,
+        &pending.previous_binary,
+        &exe.with_file_name(failed_name),
+    )?;
+
+    if let (Some(database), Some(snapshot)) = (&pending.database_path, &pending.database_snapshot) {
+        std::fs::copy(snapshot, database).context("Failed to restore the database snapshot")?;
+        // The new version's WAL belongs to the database we just replaced
+        for suffix in ["-wal", "-shm"] {
+            let mut name = database.file_name().unwrap_or_default().to_os_string();
+            name.push(suffix);
+            let _ = std::fs::remove_file(database.with_file_name(name));
+        }
+    }
+
+    std::fs::remove_file(marker_path(exe))?;
+    Ok(())
+}
+
+/// Put `replacement` at `target`, keeping what was there as `displaced`
+#[cfg(unix)]
+fn swap_binary(target: &Path, replacement: &Path, displaced: &Path) -> Result<()> {
+    // Keep a copy of the old binary, then replace it with a single rename so
+    // there is never a moment without a binary in place
+    if displaced.exists() {
+        std::fs::remove_file(displaced)?;
+    }
+    if std::fs::hard_link(target, displaced).is_err() {
+        std::fs::copy(target, displaced)?;
+    }
+    std::fs::rename(replacement, target)?;
+    Ok(())
+}
+
+/// Put `replacement` at `target`, keeping what was there as `displaced`
+#[cfg(not(unix))]
+fn swap_binary(target: &Path, replacement: &Path, displaced: &Path) -> Result<()> {
+    // A running executable can be renamed on Windows but not overwritten
+    if displaced.exists() {
+        std::fs::remove_file(displaced)?;
+    }
+    std::fs::rename(target, displaced)?;
+    if let Err(e) = std::fs::rename(replacement, target) {
+        // Don't leave the install without a binary
+        let _ = std::fs::rename(displaced, target);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+impl PendingUpdate {
+    fn load(path: &Path) -> Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn running_in_container() -> bool {
+    Path::new("/.dockerenv").exists() || std::env::var_os("container").is_some()
+}
+
+fn install_dir() -> Result<PathBuf> {
+    let exe = std::env::current_exe().context("Failed to locate the running binary")?;
+    exe.parent()
+        .map(Path::to_path_buf)
+        .context("The running binary has no parent directory")
+}
+
+fn binary_name() -> String {
+    format!("librarian{}", std::env::consts::EXE_SUFFIX)
+}
+
+fn marker_path(exe: &Path) -> PathBuf {
+    exe.with_file_name("librarian-update.json")
+}
+
+fn previous_binary_path(exe: &Path) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(".previous");
+    exe.with_file_name(name)
+}
+
+/// Release archive name for a platform, as produced by scripts/build-distro.sh
+fn asset_name(version: &str, os: &str, arch: &str) -> Option<String> {
+    let suffix = match (os, arch) {
+        ("linux", "x86_64") => "linux-x86_64.tar.gz",
+        ("windows", "x86_64") => "windows-x86_64.zip",
+        _ => return None,
+    };
+    Some(format!("librarian-{}-{}", version, suffix))
+}
+
+/// Look up a file's hash in `sha256sum` output
+fn find_checksum(sums: &str, file_name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        // Binary mode entries are prefixed with '*'
+        let name = name.trim_start().trim_start_matches('*');
+        (name == file_name && hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| hash.to_ascii_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_name() {
+        assert_eq!(
+            asset_name("0.5.0", "linux", "x86_64").as_deref(),
+            Some("librarian-0.5.0-linux-x86_64.tar.gz")
+        );
+        assert_eq!(
+            asset_name("0.5.0", "windows", "x86_64").as_deref(),
+            Some("librarian-0.5.0-windows-x86_64.zip")
+        );
+        assert_eq!(asset_name("0.5.0", "macos", "aarch64"), None);
+    }
+
+    #[test]
+    fn test_find_checksum() {
+        let hash = "ab".repeat(32);
+        let sums = format!(
+            "{}  librarian-0.5.0-linux-x86_64.tar.gz\n{} *librarian-0.5.0-windows-x86_64.zip\n",
+            "0".repeat(64),
+            hash.to_uppercase()
+        );
+        assert_eq!(
+            find_checksum(&sums, "librarian-0.5.0-linux-x86_64.tar.gz"),
+            Some("0".repeat(64))
+        );
+        assert_eq!(
+            find_checksum(&sums, "librarian-0.5.0-windows-x86_64.zip"),
+            Some(hash)
+        );
+        assert_eq!(find_checksum(&sums, "librarian-0.5.0.msi"), None);
+        assert_eq!(find_checksum("nothex  file.zip", "file.zip"), None);
+    }
+}
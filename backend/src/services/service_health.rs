@@ -0,0 +1,414 @@
+//! External service health
+//!
+//! Probes each configured external dependency: TMDB, TheTVDB,
+//! OpenSubtitles, loaded indexers, enabled usenet servers, SMTP and
+//! manually added cast devices (discovered ones come and go with the TV's
+//! power, so they aren't probed). Each probe's status, latency and error is
+//! stored; a service going down or coming back up notifies every user.
+//!
+//! Probes run every few minutes from the job scheduler. The stored results
+//! back the `externalServicesStatus` query and the diagnostics checks.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::{BoxFuture, FutureExt, join_all};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::db::{
+    CreateNotification, Database, NotificationCategory, NotificationType,
+    UpsertExternalServiceStatus,
+};
+use crate::indexer::Indexer;
+use crate::indexer::manager::IndexerManager;
+use crate::services::opensubtitles::OpenSubtitlesClient;
+use crate::services::usenet::UsenetService;
+use crate::services::{Mailer, MetadataService, NotificationService};
+
+/// How long a service gets to answer before it counts as down
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Services answering slower than this count as degraded
+pub const SLOW_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Health of a service at its last probe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceStatus {
+    Up,
+    /// Answering, but slowly
+    Degraded,
+    Down,
+}
+
+impl ServiceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceStatus::Up => "up",
+            ServiceStatus::Degraded => "degraded",
+            ServiceStatus::Down => "down",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "up" => Some(ServiceStatus::Up),
+            "degraded" => Some(ServiceStatus::Degraded),
+            "down" => Some(ServiceStatus::Down),
+            _ => None,
+        }
+    }
+
+    /// Status for a probe's outcome and how long it took
+    pub fn from_probe(outcome: &Result<()>, latency: Duration) -> Self {
+        match outcome {
+            Err(_) => ServiceStatus::Down,
+            Ok(()) if latency >= SLOW_THRESHOLD => ServiceStatus::Degraded,
+            Ok(()) => ServiceStatus::Up,
+        }
+    }
+}
+
+/// A status change worth telling users about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    WentDown,
+    Recovered,
+}
+
+/// The notification-worthy change between two probes, if any
+///
+/// Only going down and coming back count; slowing down doesn't. A service
+/// that is down on its first probe counts as going down.
+pub fn transition(previous: Option<ServiceStatus>, current: ServiceStatus) -> Option<Transition> {
+    match (previous, current) {
+        (Some(ServiceStatus::Down), ServiceStatus::Down) => None,
+        (_, ServiceStatus::Down) => Some(Transition::WentDown),
+        (Some(ServiceStatus::Down), _) => Some(Transition::Recovered),
+        _ => None,
+    }
+}
+
+/// Outcome of probing one service
+struct ProbeResult {
+    id: String,
+    kind: &'static str,
+    name: String,
+    status: ServiceStatus,
+    latency: Duration,
+    error: Option<String>,
+}
+
+/// Probes the configured external services
+pub struct ServiceHealthService {
+    db: Database,
+    metadata: Option<Arc<MetadataService>>,
+    indexer_manager: Option<Arc<IndexerManager>>,
+    notifications: Arc<NotificationService>,
+    usenet: Arc<UsenetService>,
+}
+
+impl ServiceHealthService {
+    pub fn new(
+        db: Database,
+        metadata: Option<Arc<MetadataService>>,
+        indexer_manager: Option<Arc<IndexerManager>>,
+        notifications: Arc<NotificationService>,
+        usenet: Arc<UsenetService>,
+    ) -> Self {
+        Self {
+            db,
+            metadata,
+            indexer_manager,
+            notifications,
+            usenet,
+        }
+    }
+
+    /// Probe every configured service, store the results and notify users
+    /// of services that went down or recovered
+    pub async fn probe_all(&self) -> Result<()> {
+        let previous: HashMap<String, ServiceStatus> = self
+            .db
+            .external_services()
+            .list()
+            .await?
+            .into_iter()
+            .filter_map(|record| ServiceStatus::parse(&record.status).map(|s| (record.id, s)))
+            .collect();
+
+        let results = join_all(self.probes().await?).await;
+        debug!(count = results.len(), "Probed external services");
+
+        let checked_at = Utc::now();
+        let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+        for result in results {
+            match transition(previous.get(&result.id).copied(), result.status) {
+                Some(Transition::WentDown) => {
+                    warn!(service = %result.name, error = ?result.error, "External service is down");
+                    self.notify_all(
+                        format!("{} is unreachable", result.name),
+                        result
+                            .error
+                            .clone()
+                            .unwrap_or_else(|| "The service didn't answer".to_string()),
+                        NotificationType::Warning,
+                    )
+                    .await;
+                }
+                Some(Transition::Recovered) => {
+                    info!(service = %result.name, "External service recovered");
+                    self.notify_all(
+                        format!("{} is back", result.name),
+                        format!("{} is answering again", result.name),
+                        NotificationType::Info,
+                    )
+                    .await;
+                }
+                None => {}
+            }
+
+            self.db
+                .external_services()
+                .upsert(UpsertExternalServiceStatus {
+                    id: result.id,
+                    kind: result.kind.to_string(),
+                    name: result.name,
+                    status: result.status.as_str().to_string(),
+                    latency_ms: Some(result.latency.as_millis() as i64),
+                    last_error: result.error,
+                    checked_at,
+                })
+                .await?;
+        }
+
+        self.db.external_services().retain(&ids).await?;
+        Ok(())
+    }
+
+    /// A probe for each configured service
+    async fn probes(&self) -> Result<Vec<BoxFuture<'_, ProbeResult>>> {
+        let mut probes = Vec::new();
+
+        if let Some(metadata) = &self.metadata {
+            if metadata.has_tmdb().await {
+                probes.push(probe(
+                    "tmdb".to_string(),
+                    "tmdb",
+                    "TMDB".to_string(),
+                    metadata.ping_tmdb(),
+                ));
+            }
+            if metadata.has_tvdb().await {
+                probes.push(probe(
+                    "tvdb".to_string(),
+                    "tvdb",
+                    "TheTVDB".to_string(),
+                    metadata.ping_tvdb(),
+                ));
+            }
+        }
+
+        if let Some(api_key) = self
+            .db
+            .settings()
+            .get_value::<String>("subtitles.opensubtitles_api_key")
+            .await?
+            .filter(|key| !key.is_empty())
+        {
+            let client = OpenSubtitlesClient::new(api_key);
+            probes.push(probe(
+                "opensubtitles".to_string(),
+                "opensubtitles",
+                "OpenSubtitles".to_string(),
+                async move { client.ping().await },
+            ));
+        }
+
+        if let Some(manager) = &self.indexer_manager {
+            for indexer in manager.get_all_indexers() {
+                probes.push(probe(
+                    format!("indexer:{}", indexer.id()),
+                    "indexer",
+                    indexer.name().to_string(),
+                    async move {
+                        if !indexer.test_connection().await? {
+                            anyhow::bail!("Indexer test failed");
+                        }
+                        Ok(())
+                    },
+                ));
+            }
+        }
+
+        for server in self.db.usenet_servers().list_enabled().await? {
+            let usenet = self.usenet.clone();
+            probes.push(probe(
+                format!("usenet:{}", server.id),
+                "usenet",
+                server.name.clone(),
+                async move { usenet.test_server(&server, PROBE_TIMEOUT).await },
+            ));
+        }
+
+        match Mailer::from_env() {
+            Ok(Some(mailer)) => {
+                probes.push(probe(
+                    "smtp".to_string(),
+                    "smtp",
+                    "SMTP".to_string(),
+                    async move { mailer.test_connection().await },
+                ));
+            }
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "SMTP is misconfigured, not probing it"),
+        }
+
+        for device in self.db.cast().list_devices().await? {
+            if !device.is_manual {
+                continue;
+            }
+            let address = device.address;
+            let port = device.port as u16;
+            probes.push(probe(
+                format!("cast:{}", device.id),
+                "cast",
+                device.name,
+                async move {
+                    tokio::net::TcpStream::connect((address.as_str(), port)).await?;
+                    Ok(())
+                },
+            ));
+        }
+
+        Ok(probes)
+    }
+
+    /// Send a notification to every user
+    ///
+    /// Bypasses duplicate prevention: the stored statuses already make sure
+    /// each transition is only sent once, and several services can be down
+    /// at the same time.
+    async fn notify_all(
+        &self,
+        title: String,
+        message: String,
+        notification_type: NotificationType,
+    ) {
+        let users = match self.db.users().list_all().await {
+            Ok(users) => users,
+            Err(e) => {
+                warn!(error = %e, "Failed to list users for service notification");
+                return;
+            }
+        };
+
+        for user in users {
+            let Ok(user_id) = Uuid::parse_str(&user.id) else {
+                continue;
+            };
+            let notification = CreateNotification {
+                user_id,
+                title: title.clone(),
+                message: message.clone(),
+                notification_type,
+                category: NotificationCategory::Services,
+                library_id: None,
+                torrent_id: None,
+                media_file_id: None,
+                pending_match_id: None,
+                action_type: None,
+                action_data: None,
+            };
+            if let Err(e) = self.notifications.create_without_dedupe(notification).await {
+                warn!(user_id = %user_id, error = %e, "Failed to send service notification");
+            }
+        }
+    }
+}
+
+/// Time a check, giving up after `PROBE_TIMEOUT`
+fn probe<'a>(
+    id: String,
+    kind: &'static str,
+    name: String,
+    check: impl Future<Output = Result<()>> + Send + 'a,
+) -> BoxFuture<'a, ProbeResult> {
+    async move {
+        let started = Instant::now();
+        let outcome = match tokio::time::timeout(PROBE_TIMEOUT, check).await {
+            Ok(outcome) => outcome,
+            Err(_) => Err(anyhow::anyhow!(
+                "No answer within {} seconds",
+                PROBE_TIMEOUT.as_secs()
+            )),
+        };
+        let latency = started.elapsed();
+
+        ProbeResult {
+            id,
+            kind,
+            name,
+            status: ServiceStatus::from_probe(&outcome, latency),
+            latency,
+            error: outcome.err().map(|e| format!("{:#}", e)),
+        }
+    }
+    .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_from_probe() {
+        let ok: Result<()> = Ok(());
+        let failed: Result<()> = Err(anyhow::anyhow!("refused"));
+        assert_eq!(
+            ServiceStatus::from_probe(&ok, Duration::from_millis(200)),
+            ServiceStatus::Up
+        );
+        assert_eq!(
+            ServiceStatus::from_probe(&ok, Duration::from_secs(6)),
+            ServiceStatus::Degraded
+        );
+        assert_eq!(
+            ServiceStatus::from_probe(&failed, Duration::from_millis(5)),
+            ServiceStatus::Down
+        );
+    }
+
+    #[test]
+    fn test_transition() {
+        use ServiceStatus::*;
+
+        assert_eq!(transition(None, Up), None);
+        assert_eq!(transition(None, Down), Some(Transition::WentDown));
+        assert_eq!(transition(Some(Up), Down), Some(Transition::WentDown));
+        assert_eq!(transition(Some(Degraded), Down), Some(Transition::WentDown));
+        assert_eq!(transition(Some(Down), Down), None);
+        assert_eq!(transition(Some(Down), Up), Some(Transition::Recovered));
+        assert_eq!(
+            transition(Some(Down), Degraded),
+            Some(Transition::Recovered)
+        );
+        assert_eq!(transition(Some(Up), Degraded), None);
+    }
+
+    #[test]
+    fn test_status_round_trip() {
+        for status in [
+            ServiceStatus::Up,
+            ServiceStatus::Degraded,
+            ServiceStatus::Down,
+        ] {
+            assert_eq!(ServiceStatus::parse(status.as_str()), Some(status));
+        }
+        assert_eq!(ServiceStatus::parse("unknown"), None);
+    }
+}
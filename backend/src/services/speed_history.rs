@@ -0,0 +1,175 @@
+//! Transfer rate history for sparkline graphs
+//!
+//! The torrent progress monitor samples every torrent's download and upload
+//! rate every [`SAMPLE_INTERVAL_SECS`] seconds and records them here, along
+//! with the totals across all torrents. Samples are kept in memory for
+//! [`RETENTION_MINUTES`], so the UI can draw the last few minutes of activity
+//! instead of a single instantaneous rate. History starts over when the
+//! server restarts.
+
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Duration, Utc};
+
+/// Seconds between samples
+pub const SAMPLE_INTERVAL_SECS: u64 = 5;
+
+/// How far back samples are kept
+pub const RETENTION_MINUTES: u32 = 60;
+
+/// Rates at one point in time, in bytes per second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpeedSample {
+    pub at: DateTime<Utc>,
+    pub download: u64,
+    pub upload: u64,
+}
+
+/// Fixed-size buffer of the most recent samples
+#[derive(Debug, Clone)]
+pub struct SpeedRing {
+    samples: VecDeque<SpeedSample>,
+    capacity: usize,
+}
+
+impl SpeedRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Add a sample, dropping the oldest once the buffer is full
+    pub fn push(&mut self, sample: SpeedSample) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Samples taken within the last `minutes`, oldest first
+    pub fn since(&self, minutes: u32, now: DateTime<Utc>) -> Vec<SpeedSample> {
+        let cutoff = now - Duration::minutes(minutes as i64);
+        self.samples
+            .iter()
+            .filter(|s| s.at > cutoff)
+            .copied()
+            .collect()
+    }
+}
+
+/// Rate history for each torrent and for all of them together
+#[derive(Debug)]
+pub struct SpeedHistory {
+    global: SpeedRing,
+    torrents: HashMap<usize, SpeedRing>,
+    capacity: usize,
+}
+
+impl Default for SpeedHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeedHistory {
+    pub fn new() -> Self {
+        let capacity = (RETENTION_MINUTES as u64 * 60 / SAMPLE_INTERVAL_SECS) as usize;
+        Self {
+            global: SpeedRing::new(capacity),
+            torrents: HashMap::new(),
+            capacity,
+        }
+    }
+
+    /// Record the current (torrent ID, download, upload) rates
+    ///
+    /// The global sample is the sum of all of them. Torrents missing from
+    /// `rates` have been removed, so their history is dropped.
+    pub fn record(&mut self, at: DateTime<Utc>, rates: &[(usize, u64, u64)]) {
+        self.torrents
+            .retain(|id, _| rates.iter().any(|(rate_id, _, _)| rate_id == id));
+
+        let mut total = SpeedSample {
+            at,
+            download: 0,
+            upload: 0,
+        };
+        for &(id, download, upload) in rates {
+            total.download += download;
+            total.upload += upload;
+            self.torrents
+                .entry(id)
+                .or_insert_with(|| SpeedRing::new(self.capacity))
+                .push(SpeedSample {
+                    at,
+                    download,
+                    upload,
+                });
+        }
+        self.global.push(total);
+    }
+
+    /// Totals across all torrents over the last `minutes`
+    pub fn global(&self, minutes: u32, now: DateTime<Utc>) -> Vec<SpeedSample> {
+        self.global.since(minutes, now)
+    }
+
+    /// One torrent's rates over the last `minutes` (empty if unknown)
+    pub fn torrent(&self, id: usize, minutes: u32, now: DateTime<Utc>) -> Vec<SpeedSample> {
+        self.torrents
+            .get(&id)
+            .map(|ring| ring.since(minutes, now))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(at: DateTime<Utc>, download: u64) -> SpeedSample {
+        SpeedSample {
+            at,
+            download,
+            upload: 0,
+        }
+    }
+
+    #[test]
+    fn test_ring_drops_oldest_and_filters_by_age() {
+        let now = Utc::now();
+        let mut ring = SpeedRing::new(3);
+        for i in 0..5 {
+            ring.push(sample(now - Duration::minutes(4 - i), i as u64));
+        }
+        let all: Vec<u64> = ring.since(60, now).iter().map(|s| s.download).collect();
+        assert_eq!(all, vec![2, 3, 4]);
+
+        let recent: Vec<u64> = ring.since(1, now).iter().map(|s| s.download).collect();
+        assert_eq!(recent, vec![4]);
+    }
+
+    #[test]
+    fn test_history_sums_and_forgets_removed_torrents() {
+        let now = Utc::now();
+        let earlier = now - Duration::seconds(SAMPLE_INTERVAL_SECS as i64);
+        let mut history = SpeedHistory::new();
+        history.record(earlier, &[(1, 100, 10), (2, 50, 5)]);
+        history.record(now, &[(2, 70, 0)]);
+
+        let global = history.global(5, now);
+        assert_eq!(global.len(), 2);
+        assert_eq!((global[0].download, global[0].upload), (150, 15));
+        assert_eq!((global[1].download, global[1].upload), (70, 0));
+
+        assert!(history.torrent(1, 5, now).is_empty());
+        let second: Vec<u64> = history
+            .torrent(2, 5, now)
+            .iter()
+            .map(|s| s.download)
+            .collect();
+        assert_eq!(second, vec![50, 70]);
+    }
+}
@@ -0,0 +1,383 @@
+//! Subtitle search and download
+//!
+//! Jobs on the subtitle download queue end up here. The media file's movie or
+//! episode is looked up on OpenSubtitles (by IMDb ID when known, by title
+//! otherwise), and the most downloaded match in each wanted language the file
+//! doesn't have yet is saved next to the video as `<name>.<lang>.<ext>` and
+//! recorded as a downloaded subtitle.
+//!
+//! The queue runs one job at a time with a pause between jobs, which keeps
+//! requests under the API's rate limit. Accounts also have a daily download
+//! quota; once it is used up, jobs are skipped until the reset time the API
+//! reported, instead of failing one by one.
+//!
+//! OpenSubtitles needs an API key and an account, read from the
+//! `subtitles.opensubtitles_api_key`, `subtitles.opensubtitles_username` and
+//! `subtitles.opensubtitles_password` settings.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use tracing::{debug, info, warn};
+
+use super::opensubtitles::{OpenSubtitlesClient, SubtitleSearchResult};
+use super::queues::SubtitleDownloadJob;
+use crate::db::{CreateDownloadedSubtitle, Database};
+
+/// ISO 639-1 code, ISO 639-2/B code, ISO 639-2/T code and English name
+const LANGUAGES: &[(&str, &str, &str, &str)] = &[
+    ("ar", "ara", "ara", "arabic"),
+    ("cs", "cze", "ces", "czech"),
+    ("da", "dan", "dan", "danish"),
+    ("de", "ger", "deu", "german"),
+    ("el", "gre", "ell", "greek"),
+    ("en", "eng", "eng", "english"),
+    ("es", "spa", "spa", "spanish"),
+    ("fi", "fin", "fin", "finnish"),
+    ("fr", "fre", "fra", "french"),
+    ("he", "heb", "heb", "hebrew"),
+    ("hi", "hin", "hin", "hindi"),
+    ("hu", "hun", "hun", "hungarian"),
+    ("id", "ind", "ind", "indonesian"),
+    ("it", "ita", "ita", "italian"),
+    ("ja", "jpn", "jpn", "japanese"),
+    ("ko", "kor", "kor", "korean"),
+    ("nl", "dut", "nld", "dutch"),
+    ("no", "nor", "nor", "norwegian"),
+    ("pl", "pol", "pol", "polish"),
+    ("pt", "por", "por", "portuguese"),
+    ("ro", "rum", "ron", "romanian"),
+    ("ru", "rus", "rus", "russian"),
+    ("sv", "swe", "swe", "swedish"),
+    ("th", "tha", "tha", "thai"),
+    ("tr", "tur", "tur", "turkish"),
+    ("uk", "ukr", "ukr", "ukrainian"),
+    ("vi", "vie", "vie", "vietnamese"),
+    ("zh", "chi", "zho", "chinese"),
+];
+
+fn find_language(
+    language: &str,
+) -> Option<&'static (&'static str, &'static str, &'static str, &'static str)> {
+    // "pt-BR", "en_US" and the like count as their base language
+    let base = language
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(iso1, iso2b, iso2t, name)| [*iso1, *iso2b, *iso2t, *name].contains(&base.as_str()))
+}
+
+/// ISO 639-1 code of a language given in any of the forms subtitles are
+/// tagged with ("en", "eng", "English")
+pub fn iso639_1(language: &str) -> Option<&'static str> {
+    find_language(language).map(|(iso1, _, _, _)| *iso1)
+}
+
+/// Lowercase spellings a language may be stored under
+///
+/// Embedded tracks are usually tagged with ISO 639-2 codes and external files
+/// with ISO 639-1, so a file has "English" subtitles if any of these match.
+/// Unknown languages only match themselves.
+pub fn language_variants(language: &str) -> Vec<String> {
+    match find_language(language) {
+        Some((iso1, iso2b, iso2t, name)) => {
+            let mut variants = vec![iso1.to_string(), iso2b.to_string()];
+            if iso2t != iso2b {
+                variants.push(iso2t.to_string());
+            }
+            variants.push(name.to_string());
+            variants
+        }
+        None => vec![language.trim().to_lowercase()],
+    }
+}
+
+/// File ID of the best search result in a language: most downloaded,
+/// preferring versions that aren't for the hearing impaired
+pub fn pick_best(results: &[SubtitleSearchResult], language: &str) -> Option<(i64, bool)> {
+    let wanted = iso639_1(language)?;
+    results
+        .iter()
+        .filter(|r| r.attributes.language.as_deref().and_then(iso639_1) == Some(wanted))
+        .filter_map(|r| {
+            let file_id = r.attributes.files.as_ref()?.first()?.file_id;
+            let hearing_impaired = r.attributes.hearing_impaired.unwrap_or(false);
+            let downloads = r.attributes.download_count.unwrap_or(0);
+            Some(((!hearing_impaired, downloads), (file_id, hearing_impaired)))
+        })
+        .max_by_key(|(rank, _)| *rank)
+        .map(|(_, best)| best)
+}
+
+/// Where a downloaded subtitle is saved: next to the video, named after it
+pub fn subtitle_path(media_path: &Path, language: &str, format: &str) -> PathBuf {
+    media_path.with_extension(format!("{}.{}", language, format.to_lowercase()))
+}
+
+/// Tracks the daily download quota between jobs
+#[derive(Debug, Default)]
+pub struct DownloadQuota {
+    exhausted_until: Option<DateTime<Utc>>,
+}
+
+impl DownloadQuota {
+    pub fn is_exhausted(&self, now: DateTime<Utc>) -> bool {
+        self.exhausted_until.is_some_and(|until| now < until)
+    }
+
+    /// Record the remaining downloads reported after a download
+    ///
+    /// Without a reset time, the quota is assumed to come back in a day.
+    pub fn record(&mut self, remaining: i32, reset_at: Option<DateTime<Utc>>, now: DateTime<Utc>) {
+        self.exhausted_until =
+            (remaining <= 0).then(|| reset_at.unwrap_or(now + Duration::days(1)));
+    }
+}
+
+/// Processes subtitle download jobs
+pub struct SubtitleDownloader {
+    db: Database,
+    /// Client for the configured API key and account, once logged in
+    client: tokio::sync::Mutex<Option<(String, Arc<OpenSubtitlesClient>)>>,
+    quota: parking_lot::Mutex<DownloadQuota>,
+}
+
+impl SubtitleDownloader {
+    pub fn new(db: Database) -> Self {
+        Self {
+            db,
+            client: tokio::sync::Mutex::new(None),
+            quota: parking_lot::Mutex::new(DownloadQuota::default()),
+        }
+    }
+
+    /// Logged-in client, or None if OpenSubtitles isn't configured
+    ///
+    /// Settings are read on every job, so changed credentials take effect
+    /// without a restart.
+    async fn client(&self) -> Result<Option<Arc<OpenSubtitlesClient>>> {
+        let settings = self.db.settings();
+        let api_key = settings
+            .get_value::<String>("subtitles.opensubtitles_api_key")
+            .await?;
+        let username = settings
+            .get_value::<String>("subtitles.opensubtitles_username")
+            .await?;
+        let password = settings
+            .get_value::<String>("subtitles.opensubtitles_password")
+            .await?;
+        let (Some(api_key), Some(username), Some(password)) = (api_key, username, password) else {
+            return Ok(None);
+        };
+
+        let identity = format!("{}:{}:{}", api_key, username, password);
+        let mut cached = self.client.lock().await;
+        if let Some((cached_identity, client)) = cached.as_ref()
+            && *cached_identity == identity
+        {
+            return Ok(Some(client.clone()));
+        }
+
+        let client = Arc::new(OpenSubtitlesClient::new(api_key));
+        client
+            .login(&username, &password)
+            .await
+            .context("OpenSubtitles login failed")?;
+        *cached = Some((identity, client.clone()));
+        Ok(Some(client))
+    }
+
+    /// Download the subtitles a job asks for that the file doesn't have yet
+    pub async fn process(&self, job: SubtitleDownloadJob) -> Result<()> {
+        if self.quota.lock().is_exhausted(Utc::now()) {
+            debug!(media_file_id = %job.media_file_id, "Subtitle download quota used up, skipping");
+            return Ok(());
+        }
+
+        let Some(file) = self.db.media_files().get_by_id(job.media_file_id).await? else {
+            return Ok(());
+        };
+
+        let existing: Vec<String> = self
+            .db
+            .subtitles()
+            .list_by_media_file(file.id)
+            .await?
+            .into_iter()
+            .filter_map(|s| s.language.map(|l| l.to_lowercase()))
+            .collect();
+        let wanted: Vec<&'static str> = job
+            .languages
+            .iter()
+            .filter(|lang| {
+                !language_variants(lang)
+                    .iter()
+                    .any(|variant| existing.contains(variant))
+            })
+            .filter_map(|lang| iso639_1(lang))
+            .collect();
+        if wanted.is_empty() {
+            return Ok(());
+        }
+
+        let Some(client) = self.client().await? else {
+            debug!("OpenSubtitles is not configured, skipping subtitle download");
+            return Ok(());
+        };
+        let languages: Vec<String> = wanted.iter().map(|l| l.to_string()).collect();
+
+        let results = if let Some(movie_id) = file.movie_id {
+            let Some(movie) = self.db.movies().get_by_id(movie_id).await? else {
+                return Ok(());
+            };
+            client
+                .search_movie(
+                    movie.imdb_id.as_deref(),
+                    Some(&movie.title),
+                    movie.year,
+                    &languages,
+                )
+                .await?
+        } else if let Some(episode_id) = file.episode_id.or(job.episode_id) {
+            let Some(episode) = self.db.episodes().get_by_id(episode_id).await? else {
+                return Ok(());
+            };
+            let show = self.db.tv_shows().get_by_id(episode.tv_show_id).await?;
+            let imdb_id = show
+                .as_ref()
+                .and_then(|s| s.imdb_id.clone())
+                .or(job.imdb_id.clone());
+            let show_name = show.map(|s| s.name).or(job.show_name.clone());
+            client
+                .search_episode(
+                    imdb_id.as_deref(),
+                    show_name.as_deref(),
+                    episode.season,
+                    episode.episode,
+                    &languages,
+                )
+                .await?
+        } else {
+            debug!(media_file_id = %file.id, "File isn't matched, can't search for subtitles");
+            return Ok(());
+        };
+
+        for language in wanted {
+            let Some((file_id, hearing_impaired)) = pick_best(&results, language) else {
+                debug!(media_file_id = %file.id, language, "No subtitles found");
+                continue;
+            };
+
+            let downloaded = match client.download(file_id).await {
+                Ok(downloaded) => downloaded,
+                Err(e) if e.to_string().contains("406") => {
+                    // 406 is the API's answer once the daily quota is used up
+                    warn!("OpenSubtitles download quota used up");
+                    self.quota.lock().record(0, None, Utc::now());
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
+            let reset_at = downloaded
+                .quota_reset_at
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc));
+            self.quota
+                .lock()
+                .record(downloaded.remaining_downloads, reset_at, Utc::now());
+
+            let path = subtitle_path(Path::new(&file.path), language, downloaded.format());
+            tokio::fs::write(&path, &downloaded.content)
+                .await
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            self.db
+                .subtitles()
+                .create_downloaded(CreateDownloadedSubtitle {
+                    media_file_id: file.id,
+                    file_path: path.to_string_lossy().to_string(),
+                    language: Some(language.to_string()),
+                    opensubtitles_id: Some(file_id.to_string()),
+                    is_hearing_impaired: hearing_impaired,
+                })
+                .await?;
+
+            info!(
+                media_file_id = %file.id,
+                language,
+                path = %path.display(),
+                remaining_downloads = downloaded.remaining_downloads,
+                "Downloaded subtitle"
+            );
+
+            if self.quota.lock().is_exhausted(Utc::now()) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_variants() {
+        assert_eq!(iso639_1("eng"), Some("en"));
+        assert_eq!(iso639_1("French"), Some("fr"));
+        assert_eq!(iso639_1("pt-BR"), Some("pt"));
+        assert_eq!(iso639_1("xx"), None);
+        assert_eq!(language_variants("de"), vec!["de", "ger", "deu", "german"]);
+        assert_eq!(language_variants("EN"), vec!["en", "eng", "english"]);
+        assert_eq!(language_variants("Klingon"), vec!["klingon"]);
+    }
+
+    #[test]
+    fn test_pick_best() {
+        let result = |language: &str, file_id: i64, hearing_impaired: bool, downloads: i64| {
+            serde_json::from_value::<SubtitleSearchResult>(serde_json::json!({
+                "id": file_id.to_string(),
+                "attributes": {
+                    "language": language,
+                    "download_count": downloads,
+                    "hearing_impaired": hearing_impaired,
+                    "files": [{ "file_id": file_id }]
+                }
+            }))
+            .unwrap()
+        };
+        let results = vec![
+            result("en", 1, true, 900),
+            result("en", 2, false, 100),
+            result("fr", 3, false, 5000),
+            result("en", 4, false, 50),
+        ];
+        assert_eq!(pick_best(&results, "eng"), Some((2, false)));
+        assert_eq!(pick_best(&results, "fr"), Some((3, false)));
+        assert_eq!(pick_best(&results, "de"), None);
+    }
+
+    #[test]
+    fn test_subtitle_path_and_quota() {
+        assert_eq!(
+            subtitle_path(Path::new("/movies/Heat (1995)/Heat.1995.mkv"), "en", "SRT"),
+            PathBuf::from("/movies/Heat (1995)/Heat.1995.en.srt")
+        );
+
+        let now = Utc::now();
+        let mut quota = DownloadQuota::default();
+        quota.record(3, None, now);
+        assert!(!quota.is_exhausted(now));
+        quota.record(0, Some(now + Duration::hours(2)), now);
+        assert!(quota.is_exhausted(now + Duration::hours(1)));
+        assert!(!quota.is_exhausted(now + Duration::hours(3)));
+    }
+}
@@ -92,7 +92,7 @@ pub struct TmdbSpokenLanguage {
     pub english_name: Option<String>,
 }
 
-/// Movie credits from TMDB
+/// Movie or TV show credits from TMDB
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TmdbCredits {
     pub id: i32,
@@ -118,6 +118,23 @@ pub struct TmdbCrewMember {
     pub profile_path: Option<String>,
 }
 
+/// A person's movie credits from TMDB
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmdbPersonMovieCredits {
+    pub id: i32,
+    pub cast: Vec<TmdbPersonMovieCredit>,
+    pub crew: Vec<TmdbPersonMovieCredit>,
+}
+
+/// A movie a person is credited on, with their character or job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmdbPersonMovieCredit {
+    #[serde(flatten)]
+    pub movie: TmdbMovie,
+    pub character: Option<String>,
+    pub job: Option<String>,
+}
+
 /// Collection details from TMDB
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TmdbCollection {
@@ -150,6 +167,37 @@ pub struct TmdbReleaseDate {
     pub release_type: Option<i32>,
 }
 
+/// TV show details from TMDB (`/tv/{id}` with `external_ids` appended)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmdbTvShow {
+    pub id: i32,
+    pub name: String,
+    pub overview: Option<String>,
+    pub first_air_date: Option<String>,
+    pub status: Option<String>,
+    pub poster_path: Option<String>,
+    pub backdrop_path: Option<String>,
+    #[serde(default)]
+    pub genres: Vec<TmdbGenre>,
+    #[serde(default)]
+    pub episode_run_time: Vec<i32>,
+    #[serde(default)]
+    pub networks: Vec<TmdbNetwork>,
+    pub external_ids: Option<TmdbExternalIds>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmdbNetwork {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TmdbExternalIds {
+    pub imdb_id: Option<String>,
+    pub tvdb_id: Option<i32>,
+}
+
 /// Configuration for TMDB image URLs
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TmdbConfiguration {
@@ -193,6 +241,23 @@ impl TmdbClient {
         !self.api_key.is_empty()
     }
 
+    /// Check TMDB answers and accepts the API key, without retrying
+    pub async fn ping(&self) -> Result<()> {
+        let url = format!("{}/configuration", self.base_url);
+        let response = self
+            .client
+            .get_with_query(&url, &[("api_key", self.api_key.as_str())])
+            .await?;
+
+        if response.status().as_u16() == 401 {
+            anyhow::bail!("TMDB API key is invalid");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("TMDB returned status: {}", response.status());
+        }
+        Ok(())
+    }
+
     /// Get the image base URL for poster/backdrop images
     pub fn image_url(&self, path: &str, size: &str) -> String {
         format!("https://image.tmdb.org/t/p/{}{}", size, path)
@@ -329,11 +394,21 @@ impl TmdbClient {
 
     /// Get movie credits (cast and crew)
     pub async fn get_credits(&self, tmdb_id: i32) -> Result<TmdbCredits> {
+        self.get_credits_for("movie", tmdb_id).await
+    }
+
+    /// Get TV show credits (cast and crew of the latest season)
+    pub async fn get_tv_credits(&self, tmdb_id: i32) -> Result<TmdbCredits> {
+        self.get_credits_for("tv", tmdb_id).await
+    }
+
+    /// Fetch credits (`/movie/{id}/credits` or `/tv/{id}/credits`)
+    async fn get_credits_for(&self, kind: &'static str, tmdb_id: i32) -> Result<TmdbCredits> {
         if !self.has_api_key() {
             anyhow::bail!("TMDB API key not configured");
         }
 
-        let url = format!("{}/movie/{}/credits", self.base_url, tmdb_id);
+        let url = format!("{}/{}/{}/credits", self.base_url, kind, tmdb_id);
         let client = self.client.clone();
         let api_key = self.api_key.clone();
         let retry_config = self.retry_config.clone();
@@ -369,6 +444,50 @@ impl TmdbClient {
     }
 
     /// Get release dates (for certification/rating)
+    /// Get the movies a person is credited on, in front of and behind the camera
+    pub async fn get_person_movie_credits(&self, person_id: i32) -> Result<TmdbPersonMovieCredits> {
+        if !self.has_api_key() {
+            anyhow::bail!("TMDB API key not configured");
+        }
+
+        let url = format!("{}/person/{}/movie_credits", self.base_url, person_id);
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let retry_config = self.retry_config.clone();
+
+        retry_async(
+            || {
+                let url = url.clone();
+                let client = client.clone();
+                let key = api_key.clone();
+                async move {
+                    let response = client.get_with_query(&url, &[("api_key", &key)]).await?;
+
+                    if response.status().as_u16() == 429 {
+                        anyhow::bail!("Rate limited (429)");
+                    }
+
+                    if !response.status().is_success() {
+                        anyhow::bail!(
+                            "TMDB get person credits failed with status: {}",
+                            response.status()
+                        );
+                    }
+
+                    let credits: TmdbPersonMovieCredits = response
+                        .json()
+                        .await
+                        .context("Failed to parse TMDB person credits")?;
+
+                    Ok(credits)
+                }
+            },
+            &retry_config,
+            "tmdb_get_person_credits",
+        )
+        .await
+    }
+
     pub async fn get_release_dates(&self, tmdb_id: i32) -> Result<TmdbReleaseDates> {
         if !self.has_api_key() {
             anyhow::bail!("TMDB API key not configured");
@@ -512,6 +631,175 @@ impl TmdbClient {
 
         Ok(result)
     }
+
+    /// Find a TV show's TMDB ID by an external ID
+    ///
+    /// `source` is TMDB's name for the ID, e.g. "tvdb_id" or "imdb_id".
+    pub async fn find_tv_id(&self, external_id: &str, source: &str) -> Result<Option<i32>> {
+        if !self.has_api_key() {
+            anyhow::bail!("TMDB API key not configured");
+        }
+
+        debug!("Looking up {} {} on TMDB", source, external_id);
+
+        let url = format!("{}/find/{}", self.base_url, external_id);
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let source = source.to_string();
+        let retry_config = self.retry_config.clone();
+
+        #[derive(Deserialize)]
+        struct FindId {
+            id: i32,
+        }
+
+        #[derive(Deserialize)]
+        struct FindResult {
+            tv_results: Vec<FindId>,
+        }
+
+        retry_async(
+            || {
+                let url = url.clone();
+                let client = client.clone();
+                let key = api_key.clone();
+                let source = source.clone();
+                async move {
+                    let response = client
+                        .get_with_query(&url, &[("api_key", key), ("external_source", source)])
+                        .await?;
+
+                    if response.status().as_u16() == 429 {
+                        anyhow::bail!("Rate limited (429)");
+                    }
+
+                    if !response.status().is_success() {
+                        anyhow::bail!("TMDB find failed with status: {}", response.status());
+                    }
+
+                    let find_result: FindResult = response
+                        .json()
+                        .await
+                        .context("Failed to parse TMDB find result")?;
+
+                    Ok(find_result.tv_results.into_iter().next().map(|r| r.id))
+                }
+            },
+            &retry_config,
+            "tmdb_find_tv",
+        )
+        .await
+    }
+
+    /// Get TV show details by TMDB ID
+    pub async fn get_tv_show(&self, tmdb_id: i32) -> Result<TmdbTvShow> {
+        if !self.has_api_key() {
+            anyhow::bail!("TMDB API key not configured");
+        }
+
+        debug!("Fetching TV show details from TMDB (ID: {})", tmdb_id);
+
+        let url = format!("{}/tv/{}", self.base_url, tmdb_id);
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let retry_config = self.retry_config.clone();
+
+        retry_async(
+            || {
+                let url = url.clone();
+                let client = client.clone();
+                let key = api_key.clone();
+                async move {
+                    let response = client
+                        .get_with_query(
+                            &url,
+                            &[
+                                ("api_key", key),
+                                ("append_to_response", "external_ids".to_string()),
+                            ],
+                        )
+                        .await?;
+
+                    if response.status().as_u16() == 429 {
+                        warn!("TMDB rate limit hit, will retry");
+                        anyhow::bail!("Rate limited (429)");
+                    }
+
+                    if response.status().as_u16() == 404 {
+                        anyhow::bail!("TV show not found on TMDB");
+                    }
+
+                    if !response.status().is_success() {
+                        anyhow::bail!("TMDB get TV show failed with status: {}", response.status());
+                    }
+
+                    let show: TmdbTvShow = response
+                        .json()
+                        .await
+                        .context("Failed to parse TMDB TV show")?;
+
+                    Ok(show)
+                }
+            },
+            &retry_config,
+            "tmdb_get_tv_show",
+        )
+        .await
+    }
+
+    /// Get TMDB's recommended movies for a movie (first page)
+    pub async fn get_recommendations(&self, tmdb_id: i32) -> Result<Vec<TmdbMovie>> {
+        self.get_related_movies(tmdb_id, "recommendations").await
+    }
+
+    /// Get movies similar to a movie by genre/keywords (first page)
+    pub async fn get_similar(&self, tmdb_id: i32) -> Result<Vec<TmdbMovie>> {
+        self.get_related_movies(tmdb_id, "similar").await
+    }
+
+    /// Fetch a related-movie list (`/movie/{id}/recommendations` or `/movie/{id}/similar`)
+    async fn get_related_movies(&self, tmdb_id: i32, kind: &'static str) -> Result<Vec<TmdbMovie>> {
+        if !self.has_api_key() {
+            anyhow::bail!("TMDB API key not configured");
+        }
+
+        debug!("Fetching {} for movie from TMDB (ID: {})", kind, tmdb_id);
+
+        let url = format!("{}/movie/{}/{}", self.base_url, tmdb_id, kind);
+        let client = self.client.clone();
+        let api_key = self.api_key.clone();
+        let retry_config = self.retry_config.clone();
+
+        retry_async(
+            || {
+                let url = url.clone();
+                let client = client.clone();
+                let key = api_key.clone();
+                async move {
+                    let response = client.get_with_query(&url, &[("api_key", &key)]).await?;
+
+                    if response.status().as_u16() == 429 {
+                        warn!("TMDB rate limit hit, will retry");
+                        anyhow::bail!("Rate limited (429)");
+                    }
+
+                    if !response.status().is_success() {
+                        anyhow::bail!("TMDB {} failed with status: {}", kind, response.status());
+                    }
+
+                    let results: TmdbMovieSearchResult = response
+                        .json()
+                        .await
+                        .with_context(|| format!("Failed to parse TMDB {}", kind))?;
+
+                    Ok(results.results)
+                }
+            },
+            &retry_config,
+            "tmdb_related_movies",
+        )
+        .await
+    }
 }
 
 impl TmdbMovie {
@@ -547,6 +835,15 @@ impl TmdbMovie {
     }
 }
 
+impl TmdbTvShow {
+    /// Get the premiere year from the first_air_date
+    pub fn year(&self) -> Option<i32> {
+        self.first_air_date
+            .as_ref()
+            .and_then(|d| d.split('-').next().and_then(|y| y.parse().ok()))
+    }
+}
+
 impl TmdbCredits {
     /// Get director name from crew
     pub fn director(&self) -> Option<String> {
@@ -558,9 +855,23 @@ impl TmdbCredits {
 
     /// Get top billed cast names (first 10)
     pub fn top_cast(&self, limit: usize) -> Vec<String> {
-        let mut cast = self.cast.clone();
-        cast.sort_by(|a, b| a.order.cmp(&b.order));
-        cast.into_iter().take(limit).map(|c| c.name).collect()
+        self.top_cast_members(limit)
+            .into_iter()
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    /// Get top billed cast members
+    pub fn top_cast_members(&self, limit: usize) -> Vec<&TmdbCastMember> {
+        let mut cast: Vec<&TmdbCastMember> = self.cast.iter().collect();
+        cast.sort_by_key(|c| c.order);
+        cast.truncate(limit);
+        cast
+    }
+
+    /// Get every director in the crew
+    pub fn directors(&self) -> Vec<&TmdbCrewMember> {
+        self.crew.iter().filter(|c| c.job == "Director").collect()
     }
 }
 
@@ -647,4 +958,52 @@ mod tests {
         };
         assert_eq!(movie.year(), Some(2023));
     }
+
+    #[test]
+    fn test_credits_people() {
+        let credits: TmdbCredits = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "cast": [
+                {"id": 3, "name": "Second", "character": "B", "order": 1, "profile_path": null},
+                {"id": 2, "name": "First", "character": "A", "order": 0, "profile_path": null}
+            ],
+            "crew": [
+                {"id": 4, "name": "Ana", "job": "Director", "department": "Directing", "profile_path": null},
+                {"id": 5, "name": "Ben", "job": "Screenplay", "department": "Writing", "profile_path": null},
+                {"id": 6, "name": "Cleo", "job": "Director", "department": "Directing", "profile_path": null}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(credits.top_cast(1), ["First"]);
+        let directors: Vec<&str> = credits
+            .directors()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(directors, ["Ana", "Cleo"]);
+    }
+
+    #[test]
+    fn test_person_movie_credits() {
+        let credits: TmdbPersonMovieCredits = serde_json::from_value(serde_json::json!({
+            "id": 4,
+            "cast": [],
+            "crew": [{
+                "id": 10,
+                "title": "Directed",
+                "release_date": "2001-02-03",
+                "adult": false,
+                "video": false,
+                "job": "Director",
+                "department": "Directing",
+                "credit_id": "abc"
+            }]
+        }))
+        .unwrap();
+
+        assert_eq!(credits.crew[0].movie.id, 10);
+        assert_eq!(credits.crew[0].movie.year(), Some(2001));
+        assert_eq!(credits.crew[0].job.as_deref(), Some("Director"));
+    }
 }
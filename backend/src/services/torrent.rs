@@ -3,6 +3,7 @@
 //! This module provides a wrapper around librqbit for managing torrent downloads
 //! with real-time status updates via broadcast channels and database persistence.
 
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
@@ -27,6 +28,10 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::db::{CreateTorrent, Database, TorrentRepository, UpsertTorrentFile};
+use crate::services::bandwidth::bps_limit;
+use crate::services::speed_history::{SAMPLE_INTERVAL_SECS, SpeedHistory, SpeedSample};
+use crate::services::tracker_passkey;
+use crate::services::tracker_stats::{self, TransferTracker};
 
 /// UPnP port forwarding result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,6 +83,12 @@ pub enum TorrentEvent {
         peers: usize,
         state: TorrentState,
     },
+    /// Files whose downloaded bytes changed since the last update
+    FileProgress {
+        id: usize,
+        info_hash: String,
+        files: Vec<TorrentFile>,
+    },
     Completed {
         id: usize,
         info_hash: String,
@@ -144,6 +155,7 @@ pub struct TorrentFile {
     pub index: usize,
     pub path: String,
     pub size: u64,
+    pub downloaded: u64,
     pub progress: f64,
 }
 
@@ -214,6 +226,7 @@ pub struct TorrentService {
     event_tx: broadcast::Sender<TorrentEvent>,
     completed: Arc<RwLock<std::collections::HashSet<String>>>,
     upnp_result: Arc<RwLock<Option<UpnpResult>>>,
+    speed_history: Arc<RwLock<SpeedHistory>>,
 }
 
 impl TorrentService {
@@ -334,6 +347,7 @@ impl TorrentService {
             event_tx,
             completed: Arc::new(RwLock::new(std::collections::HashSet::new())),
             upnp_result: Arc::new(RwLock::new(None)),
+            speed_history: Arc::new(RwLock::new(SpeedHistory::new())),
         };
 
         // First, sync any torrents loaded from session files TO the database
@@ -696,6 +710,12 @@ impl TorrentService {
 
         // Check if it's a valid bencoded torrent (should start with 'd')
         if bytes[0] != b'd' {
+            let page = String::from_utf8_lossy(&bytes[..std::cmp::min(2048, bytes.len())]);
+            if tracker_passkey::is_passkey_error(&page) {
+                anyhow::bail!(
+                    "Tracker refused the download because of the passkey - it may have issued a new one"
+                );
+            }
             let preview = String::from_utf8_lossy(&bytes[..std::cmp::min(100, bytes.len())]);
             if preview.contains("<!DOCTYPE") || preview.contains("<html") {
                 anyhow::bail!("Received HTML page instead of torrent file - authentication may have failed or the download link has expired");
@@ -758,6 +778,128 @@ impl TorrentService {
         }
     }
 
+    /// Remember which indexer a torrent was grabbed from, so its traffic
+    /// counts towards that tracker's ratio
+    pub async fn set_source_indexer(&self, info_hash: &str, indexer_id: &str) {
+        let Ok(indexer_id) = Uuid::parse_str(indexer_id) else {
+            return;
+        };
+        if let Err(e) = self
+            .db
+            .torrents()
+            .set_source_indexer(info_hash, indexer_id)
+            .await
+        {
+            warn!(error = %e, info_hash = %info_hash, "Failed to record torrent source indexer");
+        }
+    }
+
+    /// Swap the passkey in the announce URLs of every torrent grabbed from an
+    /// indexer, returning how many torrents in the session were updated
+    ///
+    /// librqbit can't change the trackers of a live torrent, so each one is
+    /// removed from the session (keeping its files) and re-added from its
+    /// rewritten .torrent; the data on disk is rechecked, not downloaded
+    /// again. The stored magnet or download URL is rewritten too, so resumes
+    /// use the new passkey.
+    pub async fn update_announce_passkey(
+        &self,
+        indexer_id: Uuid,
+        old_passkey: Option<&str>,
+        new_passkey: &str,
+    ) -> Result<usize> {
+        let repo = self.db.torrents();
+        let records = repo.list_by_source_indexer(indexer_id).await?;
+
+        let mut updated = 0;
+        let mut failed = Vec::new();
+        for record in records {
+            if let Some(uri) = record.magnet_uri.as_deref()
+                && let Some(rewritten) =
+                    tracker_passkey::rewrite_source_uri(uri, old_passkey, new_passkey)
+                && let Err(e) = repo.set_magnet_uri(&record.info_hash, &rewritten).await
+            {
+                warn!(error = %e, info_hash = %record.info_hash, "Failed to update torrent source URI");
+            }
+
+            let Some((id, handle)) = self.find_by_info_hash(&record.info_hash) else {
+                continue;
+            };
+            let Some(torrent_bytes) = handle.metadata.load_full().and_then(|metadata| {
+                tracker_passkey::rewrite_torrent_announces(
+                    &metadata.torrent_bytes,
+                    old_passkey,
+                    new_passkey,
+                )
+            }) else {
+                continue;
+            };
+            let paused = matches!(handle.stats().state, librqbit::TorrentStatsState::Paused);
+            let name = handle.name().unwrap_or_else(|| record.name.clone());
+
+            self.session
+                .delete(TorrentIdOrHash::Id(id), false)
+                .await
+                .with_context(|| format!("Failed to remove {} for re-adding", name))?;
+            let _ = self.event_tx.send(TorrentEvent::Removed {
+                id,
+                info_hash: record.info_hash.clone(),
+            });
+
+            let opts = AddTorrentOptions {
+                paused,
+                ..add_torrent_opts()
+            };
+            match self
+                .session
+                .add_torrent(AddTorrent::from_bytes(torrent_bytes), Some(opts))
+                .await
+            {
+                Ok(AddTorrentResponse::Added(new_id, _)) => {
+                    let _ = self.event_tx.send(TorrentEvent::Added {
+                        id: new_id,
+                        name,
+                        info_hash: record.info_hash,
+                    });
+                    updated += 1;
+                }
+                Ok(_) => updated += 1,
+                Err(e) => {
+                    error!(error = %e, torrent = %name, "Failed to re-add torrent with new passkey");
+                    failed.push(name);
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            anyhow::bail!(
+                "Updated {} torrent(s), but could not re-add: {}",
+                updated,
+                failed.join(", ")
+            );
+        }
+        info!(indexer_id = %indexer_id, updated, "Updated tracker passkey on torrents");
+        Ok(updated)
+    }
+
+    /// Find a torrent in the session by info_hash
+    fn find_by_info_hash(&self, info_hash: &str) -> Option<(usize, Arc<librqbit::ManagedTorrent>)> {
+        self.session.with_torrents(|iter| {
+            for (id, handle) in iter {
+                if get_info_hash_hex(&handle) == info_hash {
+                    return Some((id, handle.clone()));
+                }
+            }
+            None
+        })
+    }
+
+    /// Get torrent info by info_hash, if the torrent is still in the session
+    pub async fn get_torrent_info_by_hash(&self, info_hash: &str) -> Option<TorrentInfo> {
+        let (id, _) = self.find_by_info_hash(info_hash)?;
+        self.get_torrent_info(id).await.ok()
+    }
+
     pub async fn get_torrent_info(&self, id: usize) -> Result<TorrentInfo> {
         let handle = self
             .session
@@ -909,6 +1051,7 @@ impl TorrentService {
                     index: idx,
                     path: file_info.relative_filename.to_string_lossy().to_string(),
                     size,
+                    downloaded: file_progress.min(size),
                     progress: progress.min(1.0),
                 });
             }
@@ -919,17 +1062,7 @@ impl TorrentService {
 
     /// Get files for a torrent by info_hash
     pub async fn get_files_for_torrent(&self, info_hash: &str) -> Result<Vec<TorrentFile>> {
-        // Find the torrent by info_hash
-        let handle = self.session.with_torrents(|iter| {
-            for (id, handle) in iter {
-                if get_info_hash_hex(&handle) == info_hash {
-                    return Some((id, handle.clone()));
-                }
-            }
-            None
-        });
-
-        match handle {
+        match self.find_by_info_hash(info_hash) {
             Some((_, h)) => Ok(self.get_torrent_files(&h)),
             None => anyhow::bail!("Torrent not found: {}", info_hash),
         }
@@ -980,6 +1113,7 @@ impl TorrentService {
                     index: idx,
                     path: full_path,
                     size,
+                    downloaded: file_progress.min(size),
                     progress: progress.min(1.0),
                 });
             }
@@ -1085,13 +1219,22 @@ impl TorrentService {
         let session = self.session.clone();
         let event_tx = self.event_tx.clone();
         let completed = self.completed.clone();
+        let speed_history = self.speed_history.clone();
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(1));
+            // Per-file downloaded bytes at the last tick, to report only
+            // files that moved
+            let mut last_file_bytes: HashMap<usize, Vec<u64>> = HashMap::new();
+            let mut ticks: u64 = 0;
             loop {
                 interval.tick().await;
+                ticks += 1;
+                let sample_rates = ticks % SAMPLE_INTERVAL_SECS == 0;
+                let mut rates: Vec<(usize, u64, u64)> = Vec::new();
                 let ids: Vec<usize> =
                     session.with_torrents(|iter| iter.map(|(id, _)| id).collect());
+                last_file_bytes.retain(|id, _| ids.contains(id));
 
                 for id in ids {
                     if let Some(handle) = session.get(TorrentIdOrHash::Id(id)) {
@@ -1119,6 +1262,7 @@ impl TorrentService {
                                 (dl, ul, live.snapshot.peer_stats.live)
                             })
                             .unwrap_or((0, 0, 0));
+                        rates.push((id, download_speed, upload_speed));
 
                         let state = {
                             use librqbit::TorrentStatsState;
@@ -1131,6 +1275,47 @@ impl TorrentService {
                             }
                         };
 
+                        let previous = last_file_bytes.insert(id, stats.file_progress.clone());
+                        if let Some(previous) = previous
+                            && previous != stats.file_progress
+                            && let Some(metadata) = handle.metadata.load_full()
+                        {
+                            let files: Vec<TorrentFile> = metadata
+                                .file_infos
+                                .iter()
+                                .enumerate()
+                                .filter(|(idx, _)| {
+                                    previous.get(*idx) != stats.file_progress.get(*idx)
+                                })
+                                .map(|(idx, file_info)| {
+                                    let size = file_info.len;
+                                    let downloaded =
+                                        stats.file_progress.get(idx).copied().unwrap_or(0);
+                                    TorrentFile {
+                                        index: idx,
+                                        path: file_info
+                                            .relative_filename
+                                            .to_string_lossy()
+                                            .to_string(),
+                                        size,
+                                        downloaded: downloaded.min(size),
+                                        progress: if size > 0 {
+                                            (downloaded as f64 / size as f64).min(1.0)
+                                        } else {
+                                            0.0
+                                        },
+                                    }
+                                })
+                                .collect();
+                            if !files.is_empty() {
+                                let _ = event_tx.send(TorrentEvent::FileProgress {
+                                    id,
+                                    info_hash: info_hash.clone(),
+                                    files,
+                                });
+                            }
+                        }
+
                         let _ = event_tx.send(TorrentEvent::Progress {
                             id,
                             info_hash,
@@ -1142,10 +1327,34 @@ impl TorrentService {
                         });
                     }
                 }
+
+                if sample_rates {
+                    speed_history.write().record(chrono::Utc::now(), &rates);
+                }
             }
         });
     }
 
+    /// Transfer rates over the last `minutes`, oldest first
+    ///
+    /// With a torrent ID, that torrent's rates; otherwise the totals across
+    /// all torrents.
+    pub fn speed_history(&self, id: Option<usize>, minutes: u32) -> Vec<SpeedSample> {
+        let history = self.speed_history.read();
+        let now = chrono::Utc::now();
+        match id {
+            Some(id) => history.torrent(id, minutes, now),
+            None => history.global(minutes, now),
+        }
+    }
+
+    /// Limit the session's transfer rates, in bytes/sec (0 = unlimited)
+    pub fn set_speed_limits(&self, download_bps: u64, upload_bps: u64) {
+        let limits = &self.session.ratelimits;
+        limits.set_download_bps(bps_limit(download_bps));
+        limits.set_upload_bps(bps_limit(upload_bps));
+    }
+
     pub fn session(&self) -> &Arc<Session> {
         &self.session
     }
@@ -1180,6 +1389,10 @@ impl TorrentService {
                 repo.get_default_user_id().await.ok().flatten()
             };
 
+            // Per-indexer upload/download totals are built from what each
+            // torrent transferred between syncs
+            let mut transfers = TransferTracker::new();
+
             loop {
                 interval.tick().await;
 
@@ -1187,10 +1400,31 @@ impl TorrentService {
                     session.with_torrents(|iter| iter.map(|(id, h)| (id, h.clone())).collect());
                 let repo = db.torrents();
                 let files_repo = db.torrent_files();
+                let mut active = HashSet::with_capacity(torrents.len());
 
                 for (_id, handle) in torrents {
                     let stats = handle.stats();
                     let info_hash = get_info_hash_hex(&handle);
+                    active.insert(info_hash.clone());
+
+                    // The first sync after startup needs the progress saved by
+                    // the previous run, before the upsert below overwrites it
+                    let stored_downloaded = if transfers.is_tracked(&info_hash) {
+                        0
+                    } else {
+                        repo.get_by_info_hash(&info_hash)
+                            .await
+                            .ok()
+                            .flatten()
+                            .map(|r| r.downloaded_bytes.max(0) as u64)
+                            .unwrap_or(0)
+                    };
+                    let (uploaded_delta, downloaded_delta) = transfers.observe(
+                        &info_hash,
+                        stats.uploaded_bytes,
+                        stats.progress_bytes,
+                        stored_downloaded,
+                    );
                     let name = handle.name().unwrap_or_else(|| "Unknown".to_string());
                     let progress = stats.progress_bytes as f64 / stats.total_bytes.max(1) as f64;
 
@@ -1251,6 +1485,29 @@ impl TorrentService {
                     // Sync torrent files to database
                     // Get the torrent_id from database to link files
                     if let Ok(Some(torrent_record)) = repo.get_by_info_hash(&info_hash).await {
+                        if let Some(indexer_id) = torrent_record.source_indexer_id
+                            && (uploaded_delta > 0 || downloaded_delta > 0)
+                            && let Err(e) = tracker_stats::record_transfer(
+                                &db,
+                                indexer_id,
+                                uploaded_delta,
+                                downloaded_delta,
+                            )
+                            .await
+                        {
+                            warn!(error = %e, info_hash = %info_hash, "Failed to record tracker stats");
+                        }
+
+                        // Ask for a new passkey if the tracker rejected the old one
+                        if let Some(indexer_id) = torrent_record.source_indexer_id
+                            && let Some(error) = stats.error.as_deref()
+                            && tracker_passkey::is_passkey_error(error)
+                            && let Err(e) =
+                                tracker_passkey::record_passkey_error(&db, indexer_id, error).await
+                        {
+                            warn!(error = %e, info_hash = %info_hash, "Failed to record passkey error");
+                        }
+
                         // Build file list from librqbit metadata
                         if let Some(metadata) = handle.metadata.load_full() {
                             let torrent_name = handle.name().unwrap_or_else(|| "unknown".to_string());
@@ -1303,6 +1560,8 @@ impl TorrentService {
                         }
                     }
                 }
+
+                transfers.retain(&active);
             }
         });
     }
@@ -231,6 +231,20 @@ async fn process_completed_torrent(
     analysis_queue: Option<Arc<MediaAnalysisQueue>>,
     _metadata_service: Option<Arc<MetadataService>>,
     trigger_reason: &str,
+) -> Result<()> {
+    process_completed_download(db, info_hash, analysis_queue, trigger_reason).await
+}
+
+/// Import the pending file matches of a completed torrent and record the
+/// post-processing outcome
+///
+/// Only needs the database record, not the session, so it can also be driven
+/// by the test harness.
+pub async fn process_completed_download(
+    db: Database,
+    info_hash: &str,
+    analysis_queue: Option<Arc<MediaAnalysisQueue>>,
+    trigger_reason: &str,
 ) -> Result<()> {
     use crate::services::file_matcher::FileMatcher;
     use crate::services::file_processor::FileProcessor;
@@ -244,6 +258,15 @@ async fn process_completed_torrent(
         }
     };
 
+    // Left for the app that added it through the qBittorrent API
+    if torrent.post_process_status.as_deref() == Some("external") {
+        debug!(
+            "Skipping '{}', it is imported by an external app",
+            torrent.name
+        );
+        return Ok(());
+    }
+
     info!(
         "Processing '{}' (triggered by {})",
         torrent.name, trigger_reason
@@ -332,6 +355,10 @@ async fn match_torrent_files_on_add(
         }
     };
 
+    if torrent.post_process_status.as_deref() == Some("external") {
+        return Ok(());
+    }
+
     // Check if matches already exist for this torrent (e.g., created by auto_hunt)
     // If so, skip re-matching to avoid duplicate key errors
     let existing_matches = db
@@ -0,0 +1,332 @@
+//! Private tracker passkey rotation
+//!
+//! Private trackers put a per-user passkey in every announce URL, and in the
+//! download links of their RSS feeds. When the tracker rotates it (or the
+//! user resets it), every torrent grabbed with the old passkey starts
+//! failing. This module swaps the passkey in announce URLs, magnet links and
+//! .torrent files, and recognizes the errors trackers give for a stale
+//! passkey so the user can be asked for the new one.
+
+use anyhow::Result;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::{CreateNotification, Database, NotificationCategory, NotificationType};
+
+/// Query parameters trackers use for the passkey
+const PASSKEY_PARAMS: &[&str] = &["passkey", "torrent_pass", "pk", "key", "tp"];
+
+/// Phrases in tracker errors that mean the passkey was rejected
+///
+/// "Unregistered torrent" is deliberately absent: it means the torrent was
+/// removed from the tracker, which a new passkey won't fix.
+const PASSKEY_ERROR_PHRASES: &[&str] = &[
+    "passkey",
+    "torrent_pass",
+    "invalid key",
+    "unknown key",
+    "invalid user",
+    "unregistered user",
+    "user not found",
+];
+
+/// Whether a tracker or download error looks like a rejected passkey
+pub fn is_passkey_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    PASSKEY_ERROR_PHRASES
+        .iter()
+        .any(|phrase| message.contains(phrase))
+}
+
+/// Passkeys are long runs of letters and digits (usually 32 hex characters)
+fn looks_like_passkey(value: &str) -> bool {
+    value.len() >= 16 && value.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+/// The passkey in an announce or download URL, if it has a recognizable one
+///
+/// Looks for a passkey query parameter first, then for a passkey-like path
+/// segment (`/announce/<key>` or `/<key>/announce`).
+pub fn find_passkey(url: &str) -> Option<&str> {
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (url, None),
+    };
+
+    if let Some(query) = query {
+        for pair in query.split(['&', ';']) {
+            if let Some((name, value)) = pair.split_once('=')
+                && PASSKEY_PARAMS.iter().any(|p| name.eq_ignore_ascii_case(p))
+                && looks_like_passkey(value)
+            {
+                return Some(value);
+            }
+        }
+    }
+
+    let path = path.split_once("://").map(|(_, rest)| rest).unwrap_or(path);
+    path.split('/')
+        .skip(1)
+        .find(|segment| looks_like_passkey(segment))
+}
+
+/// Swap the passkey in an announce or download URL
+///
+/// The old passkey is replaced where it appears; when it is unknown (or the
+/// URL has a different one) the passkey found by [`find_passkey`] is.
+/// Returns None if the URL has no passkey to swap.
+pub fn replace_passkey(url: &str, old: Option<&str>, new: &str) -> Option<String> {
+    let old = old
+        .filter(|old| !old.is_empty() && url.contains(old))
+        .or_else(|| find_passkey(url))?;
+    if old == new {
+        return None;
+    }
+    Some(url.replace(old, new))
+}
+
+/// Swap the passkey in the trackers of a magnet link, or in a .torrent
+/// download URL (both are stored as a torrent's `magnet_uri`)
+pub fn rewrite_source_uri(uri: &str, old: Option<&str>, new: &str) -> Option<String> {
+    let Some(params) = uri.strip_prefix("magnet:?") else {
+        return replace_passkey(uri, old, new);
+    };
+
+    let mut changed = false;
+    let params: Vec<String> = params
+        .split('&')
+        .map(|param| {
+            let rewritten = param.strip_prefix("tr=").and_then(|tracker| {
+                let tracker = urlencoding::decode(tracker).ok()?;
+                replace_passkey(&tracker, old, new)
+            });
+            match rewritten {
+                Some(tracker) => {
+                    changed = true;
+                    format!("tr={}", urlencoding::encode(&tracker))
+                }
+                None => param.to_string(),
+            }
+        })
+        .collect();
+
+    changed.then(|| format!("magnet:?{}", params.join("&")))
+}
+
+/// The bytes of the bencoded string at `pos`, and the offset just past it
+fn bencode_string(data: &[u8], pos: usize) -> Option<(&[u8], usize)> {
+    let colon = pos + data.get(pos..)?.iter().position(|&b| b == b':')?;
+    let len: usize = std::str::from_utf8(&data[pos..colon]).ok()?.parse().ok()?;
+    let end = (colon + 1).checked_add(len)?;
+    Some((data.get(colon + 1..end)?, end))
+}
+
+/// The offset just past the bencoded value at `pos`
+fn bencode_end(data: &[u8], pos: usize) -> Option<usize> {
+    match *data.get(pos)? {
+        b'i' => Some(pos + data[pos..].iter().position(|&b| b == b'e')? + 1),
+        b'l' | b'd' => {
+            let mut pos = pos + 1;
+            while *data.get(pos)? != b'e' {
+                pos = bencode_end(data, pos)?;
+            }
+            Some(pos + 1)
+        }
+        b'0'..=b'9' => bencode_string(data, pos).map(|(_, end)| end),
+        _ => None,
+    }
+}
+
+/// Copy the announce value at `pos` into `out`, swapping the passkey in
+/// every URL string in it (`announce-list` is a list of lists of URLs)
+fn rewrite_announce_value(
+    data: &[u8],
+    pos: usize,
+    out: &mut Vec<u8>,
+    old: Option<&str>,
+    new: &str,
+    changed: &mut bool,
+) -> Option<usize> {
+    match *data.get(pos)? {
+        b'l' => {
+            out.push(b'l');
+            let mut pos = pos + 1;
+            while *data.get(pos)? != b'e' {
+                pos = rewrite_announce_value(data, pos, out, old, new, changed)?;
+            }
+            out.push(b'e');
+            Some(pos + 1)
+        }
+        b'0'..=b'9' => {
+            let (url, end) = bencode_string(data, pos)?;
+            match std::str::from_utf8(url)
+                .ok()
+                .and_then(|url| replace_passkey(url, old, new))
+            {
+                Some(url) => {
+                    *changed = true;
+                    out.extend_from_slice(format!("{}:{}", url.len(), url).as_bytes());
+                }
+                None => out.extend_from_slice(&data[pos..end]),
+            }
+            Some(end)
+        }
+        _ => {
+            let end = bencode_end(data, pos)?;
+            out.extend_from_slice(&data[pos..end]);
+            Some(end)
+        }
+    }
+}
+
+/// Swap the passkey in the announce URLs of a .torrent file
+///
+/// Only `announce` and `announce-list` are touched; the info dictionary is
+/// copied byte for byte, so the info hash doesn't change. Returns None if
+/// the file can't be parsed or has no passkey to swap.
+pub fn rewrite_torrent_announces(torrent: &[u8], old: Option<&str>, new: &str) -> Option<Vec<u8>> {
+    if torrent.first() != Some(&b'd') {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(torrent.len());
+    out.push(b'd');
+    let mut changed = false;
+    let mut pos = 1;
+    while *torrent.get(pos)? != b'e' {
+        let (key, value_pos) = bencode_string(torrent, pos)?;
+        out.extend_from_slice(&torrent[pos..value_pos]);
+        pos = if key == b"announce" || key == b"announce-list" {
+            rewrite_announce_value(torrent, value_pos, &mut out, old, new, &mut changed)?
+        } else {
+            let end = bencode_end(torrent, value_pos)?;
+            out.extend_from_slice(&torrent[value_pos..end]);
+            end
+        };
+    }
+    out.push(b'e');
+
+    changed.then_some(out)
+}
+
+/// Record that an indexer's tracker rejected the passkey, and ask its owner
+/// for the new one
+///
+/// Only the first error is notified; the flag stays until the passkey is
+/// updated with `updateTrackerPasskey`.
+pub async fn record_passkey_error(db: &Database, indexer_id: Uuid, error: &str) -> Result<()> {
+    let indexers = db.indexers();
+    if !indexers.set_passkey_error(indexer_id, error).await? {
+        return Ok(());
+    }
+    let Some(stats) = indexers.get_tracker_stats(indexer_id).await? else {
+        return Ok(());
+    };
+    info!(
+        indexer = %stats.indexer_name,
+        error = %error,
+        "Tracker appears to have rejected the passkey"
+    );
+
+    let notification = CreateNotification {
+        user_id: stats.user_id,
+        title: format!("{} rejected your passkey", stats.indexer_name),
+        message: format!(
+            "The tracker reported \"{}\". If it issued a new passkey, update it in the indexer settings so existing torrents keep announcing.",
+            error
+        ),
+        notification_type: NotificationType::ActionRequired,
+        category: NotificationCategory::Configuration,
+        library_id: None,
+        torrent_id: None,
+        media_file_id: None,
+        pending_match_id: None,
+        action_type: None,
+        action_data: Some(serde_json::json!({
+            "indexer_id": indexer_id.to_string(),
+            "error": error,
+        })),
+    };
+    if let Err(e) = db.notifications().create(notification).await {
+        warn!(indexer_id = %indexer_id, error = %e, "Failed to create passkey notification");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "0123456789abcdef0123456789abcdef";
+    const NEW: &str = "fedcba9876543210fedcba9876543210";
+
+    #[test]
+    fn test_is_passkey_error() {
+        assert!(is_passkey_error("Invalid passkey"));
+        assert!(is_passkey_error("failure reason: Unregistered user"));
+        assert!(!is_passkey_error("Unregistered torrent"));
+        assert!(!is_passkey_error("connection timed out"));
+    }
+
+    #[test]
+    fn test_replace_passkey() {
+        let path = format!("https://tracker.example/announce/{}", OLD);
+        assert_eq!(
+            replace_passkey(&path, None, NEW),
+            Some(format!("https://tracker.example/announce/{}", NEW))
+        );
+
+        let query = format!("https://tracker.example/announce.php?passkey={}&x=1", OLD);
+        assert_eq!(find_passkey(&query), Some(OLD));
+        assert_eq!(
+            replace_passkey(&query, Some("stale"), NEW),
+            Some(format!(
+                "https://tracker.example/announce.php?passkey={}&x=1",
+                NEW
+            ))
+        );
+
+        assert_eq!(
+            replace_passkey("udp://open.tracker:1337/announce", None, NEW),
+            None
+        );
+        assert_eq!(replace_passkey(&path, Some(OLD), OLD), None);
+    }
+
+    #[test]
+    fn test_rewrite_magnet() {
+        let magnet = format!(
+            "magnet:?xt=urn:btih:abc&tr=https%3A%2F%2Ftracker.example%2F{}%2Fannounce",
+            OLD
+        );
+        assert_eq!(
+            rewrite_source_uri(&magnet, None, NEW),
+            Some(format!(
+                "magnet:?xt=urn:btih:abc&tr=https%3A%2F%2Ftracker.example%2F{}%2Fannounce",
+                NEW
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rewrite_torrent_announces() {
+        let announce = format!("https://t.example/a/{}", OLD);
+        let info = "d6:lengthi5e4:name1:xe";
+        let torrent = format!(
+            "d8:announce{}:{}13:announce-listll{}:{}ee4:info{}e",
+            announce.len(),
+            announce,
+            announce.len(),
+            announce,
+            info
+        );
+
+        let rewritten = rewrite_torrent_announces(torrent.as_bytes(), Some(OLD), NEW).unwrap();
+        let expected = torrent.replace(OLD, NEW);
+        assert_eq!(String::from_utf8(rewritten).unwrap(), expected);
+        assert!(expected.contains(&format!("4:info{}", info)));
+
+        let open = "d8:announce17:udp://open/annnce4:infod6:lengthi5eee";
+        assert_eq!(rewrite_torrent_announces(open.as_bytes(), None, NEW), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! Per-tracker ratio tracking
+//!
+//! librqbit reports uploaded bytes for the current session only (the counter
+//! restarts when the server does, or when a torrent is paused and resumed), so
+//! cumulative totals are built from deltas: the torrent sync loop feeds each
+//! torrent's counters through a [`TransferTracker`] and adds the difference to
+//! the indexer the torrent was grabbed from. Torrents added by hand, without
+//! an indexer, are not counted.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::db::{CreateNotification, Database, NotificationCategory, NotificationType};
+
+/// How close to the minimum (as a fraction of it) counts as approaching it
+pub const RATIO_WARNING_MARGIN: f64 = 0.1;
+
+/// Upload/download ratio, or None before anything has been downloaded
+pub fn ratio(uploaded_bytes: i64, downloaded_bytes: i64) -> Option<f64> {
+    (downloaded_bytes > 0).then(|| uploaded_bytes as f64 / downloaded_bytes as f64)
+}
+
+/// Where a tracker's ratio stands relative to its required minimum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RatioStatus {
+    /// No minimum configured, nothing downloaded yet, or comfortably above
+    Ok,
+    /// Within [`RATIO_WARNING_MARGIN`] of the minimum
+    Approaching,
+    /// Below the minimum
+    Below,
+}
+
+impl RatioStatus {
+    pub fn needs_warning(&self) -> bool {
+        !matches!(self, Self::Ok)
+    }
+}
+
+pub fn ratio_status(ratio: Option<f64>, min_ratio: Option<f64>) -> RatioStatus {
+    match (ratio, min_ratio) {
+        (Some(ratio), Some(min)) if min > 0.0 => {
+            if ratio < min {
+                RatioStatus::Below
+            } else if ratio < min * (1.0 + RATIO_WARNING_MARGIN) {
+                RatioStatus::Approaching
+            } else {
+                RatioStatus::Ok
+            }
+        }
+        _ => RatioStatus::Ok,
+    }
+}
+
+/// Turns librqbit's per-session counters into deltas between syncs
+#[derive(Debug, Default)]
+pub struct TransferTracker {
+    /// Last (uploaded, downloaded) seen per info hash
+    last: HashMap<String, (u64, u64)>,
+}
+
+impl TransferTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a torrent has been seen since startup
+    pub fn is_tracked(&self, info_hash: &str) -> bool {
+        self.last.contains_key(info_hash)
+    }
+
+    /// Record a torrent's current counters and return (uploaded, downloaded)
+    /// since the previous call
+    ///
+    /// The first time a torrent is seen, upload is counted from zero (the
+    /// session counter starts there) and download from `stored_downloaded`,
+    /// the progress saved before this run, so resumed data isn't counted
+    /// twice. A counter that went backwards was reset and counts from zero.
+    pub fn observe(
+        &mut self,
+        info_hash: &str,
+        uploaded: u64,
+        downloaded: u64,
+        stored_downloaded: u64,
+    ) -> (u64, u64) {
+        let (last_up, last_down) = self
+            .last
+            .insert(info_hash.to_string(), (uploaded, downloaded))
+            .unwrap_or((0, stored_downloaded.min(downloaded)));
+
+        let delta = |current: u64, last: u64| current.checked_sub(last).unwrap_or(current);
+        (delta(uploaded, last_up), delta(downloaded, last_down))
+    }
+
+    /// Forget torrents that are no longer in the session
+    pub fn retain(&mut self, active: &HashSet<String>) {
+        self.last.retain(|hash, _| active.contains(hash));
+    }
+}
+
+/// Add traffic to an indexer's totals and warn its owner once if the ratio
+/// has dropped near or below the configured minimum
+pub async fn record_transfer(
+    db: &Database,
+    indexer_id: Uuid,
+    uploaded_bytes: u64,
+    downloaded_bytes: u64,
+) -> Result<()> {
+    let indexers = db.indexers();
+    indexers
+        .add_tracker_transfer(indexer_id, uploaded_bytes as i64, downloaded_bytes as i64)
+        .await?;
+
+    let Some(stats) = indexers.get_tracker_stats(indexer_id).await? else {
+        return Ok(());
+    };
+    let current = ratio(stats.uploaded_bytes, stats.downloaded_bytes);
+    let status = ratio_status(current, stats.min_ratio);
+
+    if !status.needs_warning() {
+        if stats.ratio_warned {
+            indexers.set_ratio_warned(indexer_id, false).await?;
+        }
+        return Ok(());
+    }
+    if stats.ratio_warned {
+        return Ok(());
+    }
+
+    let (Some(current), Some(min_ratio)) = (current, stats.min_ratio) else {
+        return Ok(());
+    };
+    let title = match status {
+        RatioStatus::Below => format!("{} ratio below minimum", stats.indexer_name),
+        _ => format!("{} ratio approaching minimum", stats.indexer_name),
+    };
+    let message = format!(
+        "Your ratio on {} is {:.2} (minimum {:.2}). Keep seeding to avoid a warning from the tracker.",
+        stats.indexer_name, current, min_ratio
+    );
+    info!(
+        indexer = %stats.indexer_name,
+        ratio = current,
+        min_ratio = min_ratio,
+        "Tracker ratio is close to the minimum"
+    );
+
+    let notification = CreateNotification {
+        user_id: stats.user_id,
+        title,
+        message,
+        notification_type: NotificationType::Warning,
+        category: NotificationCategory::Configuration,
+        library_id: None,
+        torrent_id: None,
+        media_file_id: None,
+        pending_match_id: None,
+        action_type: None,
+        action_data: Some(serde_json::json!({
+            "indexer_id": indexer_id.to_string(),
+            "ratio": current,
+            "min_ratio": min_ratio,
+        })),
+    };
+    if let Err(e) = db.notifications().create(notification).await {
+        warn!(indexer_id = %indexer_id, error = %e, "Failed to create ratio notification");
+        return Ok(());
+    }
+    indexers.set_ratio_warned(indexer_id, true).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(ratio(100, 0), None);
+        assert_eq!(ratio(150, 100), Some(1.5));
+    }
+
+    #[test]
+    fn test_ratio_status() {
+        assert_eq!(ratio_status(Some(0.5), None), RatioStatus::Ok);
+        assert_eq!(ratio_status(None, Some(1.0)), RatioStatus::Ok);
+        assert_eq!(ratio_status(Some(1.5), Some(1.0)), RatioStatus::Ok);
+        assert_eq!(
+            ratio_status(Some(1.05), Some(1.0)),
+            RatioStatus::Approaching
+        );
+        assert_eq!(ratio_status(Some(0.9), Some(1.0)), RatioStatus::Below);
+        assert_eq!(ratio_status(Some(0.0), Some(0.0)), RatioStatus::Ok);
+    }
+
+    #[test]
+    fn test_transfer_tracker_deltas() {
+        let mut tracker = TransferTracker::new();
+
+        // Resumed torrent: 600 bytes were downloaded before this run
+        assert_eq!(tracker.observe("a", 10, 700, 600), (10, 100));
+        assert_eq!(tracker.observe("a", 25, 750, 600), (15, 50));
+
+        // Paused and resumed: the upload counter restarts
+        assert_eq!(tracker.observe("a", 5, 750, 600), (5, 0));
+
+        // New torrent with nothing stored
+        assert_eq!(tracker.observe("b", 0, 300, 0), (0, 300));
+
+        tracker.retain(&HashSet::from(["b".to_string()]));
+        assert!(!tracker.is_tracked("a"));
+        assert!(tracker.is_tracked("b"));
+    }
+}
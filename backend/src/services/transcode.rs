@@ -0,0 +1,696 @@
+//! On-the-fly HLS transcoding
+//!
+//! Files a client can't play directly (anything but H.264/AAC on Chromecast,
+//! HEVC or DTS in most browsers) are streamed as HLS instead. Opening a
+//! session probes the file with `FfmpegService` and returns a complete VOD
+//! playlist of fixed-length segments, so players know the full duration and
+//! can seek anywhere. One ffmpeg process per session produces the segments:
+//!
+//! - a segment already on disk is served from the session's cache directory
+//! - one just ahead of the running encode is waited for
+//! - anything else is a seek, and restarts ffmpeg at that segment
+//!
+//! The encode runs at most `WINDOW_AHEAD` segments past the last segment
+//! requested and is stopped there; serving a segment close to the end of what
+//! has been produced starts it again. Video is always re-encoded so segment
+//! boundaries match the playlist, on a hardware encoder when one is found and
+//! works. Sessions are pinned in the transcode cache while open; the
+//! transcode_gc job closes idle ones and the cache evicts them later.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+use parking_lot::{Mutex, RwLock};
+use tokio::process::{Child, Command};
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use super::FfmpegService;
+use super::disc_structure;
+use super::transcode_cache::TranscodeCache;
+
+/// Settings key for whether hardware encoders may be used
+pub const HARDWARE_ACCELERATION_KEY: &str = "transcode.hardware_acceleration";
+
+pub const DEFAULT_HARDWARE_ACCELERATION: bool = true;
+
+/// Length of each HLS segment in seconds
+pub const SEGMENT_SECS: f64 = 6.0;
+
+/// Segments the encoder may run ahead of the last one requested (2 minutes)
+const WINDOW_AHEAD: u32 = 20;
+
+/// A request further than this past the encoder's position is a seek
+const SEEK_THRESHOLD: u32 = 3;
+
+/// How long a segment request waits for the encoder
+const SEGMENT_WAIT: Duration = Duration::from_secs(30);
+
+/// Sessions without requests for this long are closed by transcode_gc
+pub const IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Render node used for VAAPI encoding
+const VAAPI_DEVICE: &str = "/dev/dri/renderD128";
+
+/// Output size and bitrate of a transcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscodeQuality {
+    P1080,
+    P720,
+    P480,
+}
+
+impl TranscodeQuality {
+    /// Parse a quality like "720p"
+    pub fn from_param(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().trim_end_matches('p') {
+            "1080" => Some(Self::P1080),
+            "720" => Some(Self::P720),
+            "480" => Some(Self::P480),
+            _ => None,
+        }
+    }
+
+    fn max_height(self) -> u32 {
+        match self {
+            Self::P1080 => 1080,
+            Self::P720 => 720,
+            Self::P480 => 480,
+        }
+    }
+
+    fn video_bitrate_kbps(self) -> u32 {
+        match self {
+            Self::P1080 => 6000,
+            Self::P720 => 3000,
+            Self::P480 => 1200,
+        }
+    }
+}
+
+/// H.264 encoder used for transcoding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoEncoder {
+    Software,
+    Nvenc,
+    Qsv,
+    VideoToolbox,
+    Vaapi,
+}
+
+impl VideoEncoder {
+    /// Hardware encoders, most preferred first
+    const HARDWARE: [VideoEncoder; 4] = [
+        VideoEncoder::Nvenc,
+        VideoEncoder::Qsv,
+        VideoEncoder::VideoToolbox,
+        VideoEncoder::Vaapi,
+    ];
+
+    /// ffmpeg encoder name
+    pub fn codec(self) -> &'static str {
+        match self {
+            Self::Software => "libx264",
+            Self::Nvenc => "h264_nvenc",
+            Self::Qsv => "h264_qsv",
+            Self::VideoToolbox => "h264_videotoolbox",
+            Self::Vaapi => "h264_vaapi",
+        }
+    }
+
+    /// Arguments that go before `-i`
+    fn input_args(self) -> Vec<&'static str> {
+        match self {
+            Self::Vaapi => vec!["-vaapi_device", VAAPI_DEVICE],
+            _ => Vec::new(),
+        }
+    }
+
+    fn preset_args(self) -> Vec<&'static str> {
+        match self {
+            Self::Software => vec!["-preset", "veryfast"],
+            Self::Nvenc => vec!["-preset", "p4"],
+            Self::Qsv => vec!["-preset", "veryfast"],
+            Self::VideoToolbox | Self::Vaapi => Vec::new(),
+        }
+    }
+
+    /// Video filter chain, scaling to `height` when given
+    fn filter(self, height: Option<u32>) -> String {
+        let scale = height
+            .map(|h| format!("scale=-2:{},", h))
+            .unwrap_or_default();
+        match self {
+            // VAAPI encodes from GPU surfaces
+            Self::Vaapi => format!("{}format=nv12,hwupload", scale),
+            _ => format!("{}format=yuv420p", scale),
+        }
+    }
+}
+
+/// Hardware encoders listed in `ffmpeg -encoders` output, most preferred first
+pub fn listed_hardware_encoders(encoders_output: &str) -> Vec<VideoEncoder> {
+    let listed: Vec<&str> = encoders_output
+        .lines()
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .collect();
+    VideoEncoder::HARDWARE
+        .into_iter()
+        .filter(|encoder| listed.contains(&encoder.codec()))
+        .collect()
+}
+
+/// Output height for a source, or None to keep the source size
+///
+/// Only ever scales down, and keeps the height even as H.264 requires.
+pub fn target_height(source_height: u32, quality: TranscodeQuality) -> Option<u32> {
+    let max = quality.max_height();
+    (source_height > max).then_some(max - max % 2)
+}
+
+/// Number of segments needed to cover `duration` seconds
+pub fn segment_count(duration: f64) -> u32 {
+    ((duration / SEGMENT_SECS).ceil() as u32).max(1)
+}
+
+/// A VOD playlist covering the whole file; segment `n` is at `{prefix}{n}.ts`
+pub fn media_playlist(duration: f64, prefix: &str) -> String {
+    let count = segment_count(duration);
+    let mut playlist = format!(
+        "#EXTM3U\n#EXT-X-VERSION:3\n#EXT-X-TARGETDURATION:{}\n#EXT-X-MEDIA-SEQUENCE:0\n\
+         #EXT-X-PLAYLIST-TYPE:VOD\n#EXT-X-INDEPENDENT-SEGMENTS\n",
+        SEGMENT_SECS.ceil() as u32
+    );
+    for n in 0..count {
+        let length = if n + 1 == count {
+            duration - SEGMENT_SECS * n as f64
+        } else {
+            SEGMENT_SECS
+        };
+        playlist.push_str(&format!(
+            "#EXTINF:{:.6},\n{}{}.ts\n",
+            length.max(0.0),
+            prefix,
+            n
+        ));
+    }
+    playlist.push_str("#EXT-X-ENDLIST\n");
+    playlist
+}
+
+/// Segment number from a segment file name like "12.ts"
+pub fn parse_segment_name(name: &str) -> Option<u32> {
+    name.strip_suffix(".ts")?.parse().ok()
+}
+
+/// Is the file playable on a Chromecast without transcoding?
+///
+/// Chromecast supports:
+/// - Containers: MP4, WebM
+/// - Video: H.264 (up to level 4.2), VP8, VP9
+/// - Audio: AAC, MP3, Opus, Vorbis, FLAC
+pub fn is_chromecast_compatible(
+    container: Option<&str>,
+    video_codec: Option<&str>,
+    audio_codec: Option<&str>,
+) -> bool {
+    let container_ok = match container {
+        Some(c) => {
+            let c = c.to_lowercase();
+            c.contains("mp4") || c.contains("m4v") || c.contains("webm")
+        }
+        None => false,
+    };
+
+    let video_ok = match video_codec {
+        Some(v) => {
+            let v = v.to_lowercase();
+            v.contains("h264") || v.contains("avc") || v.contains("vp8") || v.contains("vp9")
+        }
+        None => true, // No video is OK (audio only)
+    };
+
+    let audio_ok = match audio_codec {
+        Some(a) => {
+            let a = a.to_lowercase();
+            a.contains("aac")
+                || a.contains("mp3")
+                || a.contains("opus")
+                || a.contains("vorbis")
+                || a.contains("flac")
+        }
+        None => true, // No audio is OK (video only, though uncommon)
+    };
+
+    container_ok && video_ok && audio_ok
+}
+
+/// A running ffmpeg process
+struct Encode {
+    /// Distinguishes restarts, so a stale watcher knows to stop
+    generation: u64,
+    start_segment: u32,
+    child: Child,
+}
+
+#[derive(Default)]
+struct EncodeState {
+    encode: Option<Encode>,
+    /// Whether the last encode exited with an error
+    last_failed: bool,
+}
+
+/// One client's transcode of one file
+pub struct TranscodeSession {
+    pub id: String,
+    pub media_file_id: Uuid,
+    /// ffmpeg input: the file, or all parts of a DVD title
+    input: OsString,
+    dir: PathBuf,
+    duration: f64,
+    segment_count: u32,
+    video_stream: usize,
+    audio_stream: Option<usize>,
+    height: Option<u32>,
+    quality: TranscodeQuality,
+    encoder: VideoEncoder,
+    last_requested: AtomicU32,
+    last_access: Mutex<Instant>,
+    state: Mutex<EncodeState>,
+}
+
+impl TranscodeSession {
+    fn segment_path(&self, n: u32) -> PathBuf {
+        self.dir.join(format!("segment_{:05}.ts", n))
+    }
+
+    /// First segment at or after `from` that hasn't been written yet
+    fn first_missing(&self, from: u32) -> u32 {
+        (from..self.segment_count)
+            .find(|&n| !self.segment_path(n).exists())
+            .unwrap_or(self.segment_count)
+    }
+
+    fn stop_encode(state: &mut EncodeState) {
+        if let Some(mut encode) = state.encode.take() {
+            let _ = encode.child.start_kill();
+        }
+    }
+}
+
+/// Manages HLS transcode sessions
+pub struct TranscodeService {
+    ffmpeg: Arc<FfmpegService>,
+    cache: Arc<TranscodeCache>,
+    sessions: RwLock<HashMap<String, Arc<TranscodeSession>>>,
+    /// Best working hardware encoder, detected on first use
+    hardware_encoder: OnceCell<Option<VideoEncoder>>,
+    generation: AtomicU64,
+}
+
+impl TranscodeService {
+    pub fn new(ffmpeg: Arc<FfmpegService>, cache: Arc<TranscodeCache>) -> Self {
+        Self {
+            ffmpeg,
+            cache,
+            sessions: RwLock::new(HashMap::new()),
+            hardware_encoder: OnceCell::new(),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub fn cache(&self) -> &Arc<TranscodeCache> {
+        &self.cache
+    }
+
+    /// The encoder new sessions use
+    pub async fn encoder(&self, hardware_allowed: bool) -> VideoEncoder {
+        if !hardware_allowed {
+            return VideoEncoder::Software;
+        }
+        self.hardware_encoder
+            .get_or_init(detect_hardware_encoder)
+            .await
+            .unwrap_or(VideoEncoder::Software)
+    }
+
+    /// Open a session for a file; segments are produced as they are requested
+    pub async fn start_session(
+        &self,
+        media_file_id: Uuid,
+        source: &Path,
+        quality: TranscodeQuality,
+        hardware_allowed: bool,
+    ) -> Result<Arc<TranscodeSession>> {
+        let analysis = self.ffmpeg.analyze(source).await?;
+        let parts = disc_structure::title_parts(source);
+        let duration = match parts.len() {
+            1 => analysis.duration_secs,
+            _ => disc_structure::title_duration(&self.ffmpeg, &parts).await,
+        }
+        .filter(|d| *d > 0.0)
+        .context("Can't transcode a file without a known duration")?;
+        let video =
+            FfmpegService::primary_video_stream(&analysis).context("File has no video stream")?;
+        let audio_stream = FfmpegService::primary_audio_stream(&analysis).map(|a| a.index);
+
+        let id = Uuid::new_v4().to_string();
+        let dir = self.cache.session_dir(&id);
+        tokio::fs::create_dir_all(&dir).await?;
+        self.cache.pin(&id);
+
+        let session = Arc::new(TranscodeSession {
+            id: id.clone(),
+            media_file_id,
+            input: disc_structure::ffmpeg_input(&parts),
+            dir,
+            duration,
+            segment_count: segment_count(duration),
+            video_stream: video.index,
+            audio_stream,
+            height: target_height(video.height, quality),
+            quality,
+            encoder: self.encoder(hardware_allowed).await,
+            last_requested: AtomicU32::new(0),
+            last_access: Mutex::new(Instant::now()),
+            state: Mutex::new(EncodeState::default()),
+        });
+        self.sessions.write().insert(id, session.clone());
+
+        info!(
+            session_id = %session.id,
+            media_file_id = %media_file_id,
+            source_codec = %video.codec,
+            encoder = session.encoder.codec(),
+            height = ?session.height,
+            "Started transcode session"
+        );
+        Ok(session)
+    }
+
+    pub fn session(&self, session_id: &str) -> Option<Arc<TranscodeSession>> {
+        self.sessions.read().get(session_id).cloned()
+    }
+
+    /// The playlist for a session, with segment URIs relative to it
+    pub fn playlist(&self, session: &TranscodeSession, segment_prefix: &str) -> String {
+        media_playlist(session.duration, segment_prefix)
+    }
+
+    /// Path of segment `n`, encoding it first if needed
+    ///
+    /// Returns None if the segment doesn't exist and won't be produced.
+    pub async fn segment(
+        &self,
+        session: &Arc<TranscodeSession>,
+        n: u32,
+    ) -> Result<Option<PathBuf>> {
+        if n >= session.segment_count {
+            return Ok(None);
+        }
+        *session.last_access.lock() = Instant::now();
+        session.last_requested.store(n, Ordering::Relaxed);
+
+        let path = session.segment_path(n);
+        if path.exists() {
+            self.keep_ahead(session, n)?;
+            return Ok(Some(path));
+        }
+
+        {
+            let mut state = session.state.lock();
+            let seek = match &state.encode {
+                Some(encode) => {
+                    n < encode.start_segment
+                        || n > session.first_missing(encode.start_segment) + SEEK_THRESHOLD
+                }
+                None => true,
+            };
+            if seek {
+                self.start_encode(session, &mut state, n)?;
+            }
+        }
+
+        let deadline = Instant::now() + SEGMENT_WAIT;
+        while Instant::now() < deadline {
+            if path.exists() {
+                return Ok(Some(path));
+            }
+            {
+                let state = session.state.lock();
+                if state.encode.is_none() {
+                    // The encode finished or was stopped; check once more
+                    // since the segment may have landed just before it exited
+                    if path.exists() {
+                        return Ok(Some(path));
+                    }
+                    if state.last_failed {
+                        bail!(
+                            "Transcoding failed, see {}",
+                            session.dir.join("ffmpeg.log").display()
+                        );
+                    }
+                    return Ok(None);
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        bail!("Timed out waiting for segment {}", n)
+    }
+
+    /// Restart a stopped encode when playback nears the end of what's on disk
+    fn keep_ahead(&self, session: &Arc<TranscodeSession>, n: u32) -> Result<()> {
+        let mut state = session.state.lock();
+        if state.encode.is_some() {
+            return Ok(());
+        }
+        let next = session.first_missing(n);
+        if next < session.segment_count && next - n < WINDOW_AHEAD / 2 {
+            self.start_encode(session, &mut state, next)?;
+        }
+        Ok(())
+    }
+
+    fn start_encode(
+        &self,
+        session: &Arc<TranscodeSession>,
+        state: &mut EncodeState,
+        start_segment: u32,
+    ) -> Result<()> {
+        TranscodeSession::stop_encode(state);
+
+        let log = std::fs::File::create(session.dir.join("ffmpeg.log"))?;
+        let child = encode_command(session, start_segment)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::from(log))
+            .kill_on_drop(true)
+            .spawn()
+            .context("Failed to start ffmpeg")?;
+
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed);
+        state.encode = Some(Encode {
+            generation,
+            start_segment,
+            child,
+        });
+        state.last_failed = false;
+        debug!(session_id = %session.id, start_segment, "Started transcode encode");
+
+        tokio::spawn(watch_encode(session.clone(), generation));
+        Ok(())
+    }
+
+    /// Stop a session's encode and release its cache pin
+    pub fn stop_session(&self, session_id: &str) -> bool {
+        let Some(session) = self.sessions.write().remove(session_id) else {
+            return false;
+        };
+        TranscodeSession::stop_encode(&mut session.state.lock());
+        self.cache.unpin(session_id);
+        debug!(session_id, "Stopped transcode session");
+        true
+    }
+
+    /// Close sessions nobody has requested anything from in a while
+    pub fn stop_idle_sessions(&self) -> usize {
+        let idle: Vec<String> = self
+            .sessions
+            .read()
+            .values()
+            .filter(|s| s.last_access.lock().elapsed() > IDLE_TIMEOUT)
+            .map(|s| s.id.clone())
+            .collect();
+        idle.iter().filter(|id| self.stop_session(id)).count()
+    }
+}
+
+/// ffmpeg invocation producing segments from `start_segment` onwards
+fn encode_command(session: &TranscodeSession, start_segment: u32) -> Command {
+    let start = SEGMENT_SECS * start_segment as f64;
+    let bitrate = session.quality.video_bitrate_kbps();
+    let keyframes = format!("expr:gte(t,n_forced*{})", SEGMENT_SECS);
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-hide_banner", "-loglevel", "error", "-nostdin"])
+        .args(session.encoder.input_args())
+        .args(["-ss", &format!("{:.3}", start)])
+        .arg("-i")
+        .arg(&session.input)
+        .args(["-map", &format!("0:{}", session.video_stream)]);
+    if let Some(audio) = session.audio_stream {
+        cmd.args(["-map", &format!("0:{}", audio)]);
+    }
+    cmd.args(["-c:v", session.encoder.codec()])
+        .args(session.encoder.preset_args())
+        .args(["-b:v", &format!("{}k", bitrate)])
+        .args(["-maxrate", &format!("{}k", bitrate)])
+        .args(["-bufsize", &format!("{}k", bitrate * 2)])
+        .args(["-vf", &session.encoder.filter(session.height)])
+        // Keyframes on segment boundaries, so segments match the playlist
+        .args(["-force_key_frames", &keyframes])
+        .args(["-c:a", "aac", "-b:a", "192k", "-ac", "2"])
+        // Keep timestamps continuous across restarts
+        .args(["-output_ts_offset", &format!("{:.3}", start)])
+        .args(["-f", "hls", "-hls_time", &SEGMENT_SECS.to_string()])
+        .args(["-hls_list_size", "0", "-hls_segment_type", "mpegts"])
+        // Segments appear under their final name only once complete
+        .args(["-hls_flags", "temp_file+independent_segments"])
+        .args(["-start_number", &start_segment.to_string()])
+        .arg("-hls_segment_filename")
+        .arg(session.dir.join("segment_%05d.ts"))
+        .arg(session.dir.join("ffmpeg.m3u8"));
+    cmd
+}
+
+/// Notice when an encode exits, and stop it once it's far enough ahead
+async fn watch_encode(session: Arc<TranscodeSession>, generation: u64) {
+    loop {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let mut state = session.state.lock();
+        let Some(encode) = state.encode.as_mut() else {
+            return;
+        };
+        if encode.generation != generation {
+            return;
+        }
+
+        match encode.child.try_wait() {
+            Ok(Some(status)) => {
+                if !status.success() {
+                    warn!(session_id = %session.id, %status, "ffmpeg exited with an error");
+                }
+                state.encode = None;
+                state.last_failed = !status.success();
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(session_id = %session.id, error = %e, "Failed to check on ffmpeg");
+                return;
+            }
+        }
+
+        let requested = session.last_requested.load(Ordering::Relaxed);
+        if session.first_missing(requested) >= requested + WINDOW_AHEAD {
+            debug!(session_id = %session.id, "Transcode far enough ahead, pausing");
+            TranscodeSession::stop_encode(&mut state);
+            return;
+        }
+    }
+}
+
+/// Find the most preferred hardware encoder that actually works here
+///
+/// ffmpeg lists encoders it was built with whether or not the hardware is
+/// present, so each candidate encodes a single test frame first.
+async fn detect_hardware_encoder() -> Option<VideoEncoder> {
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-encoders"])
+        .output()
+        .await
+        .ok()?;
+    let listed = listed_hardware_encoders(&String::from_utf8_lossy(&output.stdout));
+
+    for encoder in listed {
+        let works = Command::new("ffmpeg")
+            .args(["-hide_banner", "-loglevel", "error", "-nostdin"])
+            .args(encoder.input_args())
+            .args(["-f", "lavfi", "-i", "color=c=black:s=320x240:d=1"])
+            .args(["-frames:v", "1", "-vf", &encoder.filter(None)])
+            .args(["-c:v", encoder.codec(), "-f", "null", "-"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .is_ok_and(|s| s.success());
+        if works {
+            info!(
+                encoder = encoder.codec(),
+                "Using hardware video encoder for transcoding"
+            );
+            return Some(encoder);
+        }
+        debug!(
+            encoder = encoder.codec(),
+            "Hardware encoder listed but not usable"
+        );
+    }
+
+    info!("No usable hardware video encoder, transcoding in software");
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_playlist() {
+        let playlist = media_playlist(15.5, "hls/abc/");
+        assert!(playlist.starts_with("#EXTM3U\n"));
+        assert!(playlist.contains("#EXT-X-TARGETDURATION:6\n"));
+        assert!(playlist.contains("#EXTINF:6.000000,\nhls/abc/0.ts\n"));
+        assert!(playlist.contains("#EXTINF:3.500000,\nhls/abc/2.ts\n"));
+        assert!(!playlist.contains("hls/abc/3.ts"));
+        assert!(playlist.ends_with("#EXT-X-ENDLIST\n"));
+
+        assert_eq!(segment_count(12.0), 2);
+        assert_eq!(segment_count(12.1), 3);
+        assert_eq!(parse_segment_name("12.ts"), Some(12));
+        assert_eq!(parse_segment_name("12.m4s"), None);
+    }
+
+    #[test]
+    fn test_listed_hardware_encoders() {
+        let output = " V....D libx264              libx264 H.264 (codec h264)\n \
+                      V....D h264_vaapi           H.264/AVC (VAAPI) (codec h264)\n \
+                      V....D h264_nvenc           NVIDIA NVENC H.264 encoder (codec h264)\n \
+                      V....D hevc_nvenc           NVIDIA NVENC hevc encoder (codec hevc)\n";
+        assert_eq!(
+            listed_hardware_encoders(output),
+            vec![VideoEncoder::Nvenc, VideoEncoder::Vaapi]
+        );
+        assert!(listed_hardware_encoders("").is_empty());
+    }
+
+    #[test]
+    fn test_target_height_and_quality() {
+        assert_eq!(target_height(2160, TranscodeQuality::P1080), Some(1080));
+        assert_eq!(target_height(720, TranscodeQuality::P1080), None);
+        assert_eq!(target_height(1080, TranscodeQuality::P720), Some(720));
+        assert_eq!(
+            TranscodeQuality::from_param("720p"),
+            Some(TranscodeQuality::P720)
+        );
+        assert_eq!(TranscodeQuality::from_param("4k"), None);
+    }
+}
@@ -0,0 +1,301 @@
+//! Transcode cache management
+//!
+//! Each transcode session writes its playlist and segments to its own
+//! directory under the cache root. Sessions that are still being played are
+//! pinned so they are never evicted; everything else is evicted least recently
+//! used first once the cache grows past its size cap, or once it goes stale.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use parking_lot::RwLock;
+use tracing::{debug, info, warn};
+
+/// Settings key for the cache size cap in GB (0 = unlimited)
+pub const MAX_SIZE_GB_KEY: &str = "transcode.cache_max_size_gb";
+
+pub const DEFAULT_MAX_SIZE_GB: f64 = 20.0;
+
+/// Unpinned sessions untouched for this long are removed regardless of size
+pub const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// Convert a GB setting to a byte cap (None = unlimited)
+pub fn max_bytes_from_gb(gb: f64) -> Option<u64> {
+    (gb > 0.0).then_some((gb * BYTES_PER_GB) as u64)
+}
+
+/// One session directory in the cache
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    pub session_id: String,
+    pub size_bytes: u64,
+    /// Most recent modification of any file in the session
+    pub last_used: SystemTime,
+}
+
+/// Snapshot of the cache's contents
+#[derive(Debug, Clone)]
+pub struct CacheStats {
+    pub total_bytes: u64,
+    pub session_count: usize,
+    pub pinned_count: usize,
+    pub pinned_bytes: u64,
+    pub oldest_entry: Option<SystemTime>,
+}
+
+/// Outcome of an eviction pass or clear
+#[derive(Debug, Clone, Default)]
+pub struct EvictionResult {
+    pub sessions_removed: usize,
+    pub bytes_freed: u64,
+    /// Pinned sessions that were left in place
+    pub sessions_skipped: usize,
+}
+
+/// Pick the sessions to remove, least recently used first
+///
+/// Stale sessions always go; after that, sessions are removed until the total
+/// fits under `max_bytes`. Pinned sessions are never selected, so the cache can
+/// stay over its cap while they are in use.
+pub fn select_evictions(
+    entries: &[CacheEntry],
+    pinned: &HashSet<String>,
+    max_bytes: Option<u64>,
+    stale_before: SystemTime,
+) -> Vec<String> {
+    let mut candidates: Vec<&CacheEntry> = entries
+        .iter()
+        .filter(|e| !pinned.contains(&e.session_id))
+        .collect();
+    candidates.sort_by_key(|e| e.last_used);
+
+    let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    let mut evict = Vec::new();
+    for entry in candidates {
+        let over_cap = max_bytes.is_some_and(|max| total > max);
+        if entry.last_used < stale_before || over_cap {
+            total = total.saturating_sub(entry.size_bytes);
+            evict.push(entry.session_id.clone());
+        }
+    }
+    evict
+}
+
+/// Shared handle to the transcode cache directory
+pub struct TranscodeCache {
+    root: PathBuf,
+    /// Pin counts per session (a session may be streamed by several clients)
+    pins: RwLock<HashMap<String, usize>>,
+}
+
+impl TranscodeCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            pins: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Directory all sessions are written under
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory holding a session's playlist and segments
+    pub fn session_dir(&self, session_id: &str) -> PathBuf {
+        self.root.join(session_id)
+    }
+
+    /// Protect a session's segments from eviction while it is being played
+    pub fn pin(&self, session_id: &str) {
+        *self.pins.write().entry(session_id.to_string()).or_insert(0) += 1;
+    }
+
+    /// Release a pin taken with [`pin`](Self::pin)
+    pub fn unpin(&self, session_id: &str) {
+        let mut pins = self.pins.write();
+        if let Some(count) = pins.get_mut(session_id) {
+            *count -= 1;
+            if *count == 0 {
+                pins.remove(session_id);
+            }
+        }
+    }
+
+    fn pinned(&self) -> HashSet<String> {
+        self.pins.read().keys().cloned().collect()
+    }
+
+    /// Scan the cache root for session directories
+    pub async fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let root = self.root.clone();
+        Ok(tokio::task::spawn_blocking(move || scan_entries(&root)).await??)
+    }
+
+    /// Current cache usage
+    pub async fn stats(&self) -> Result<CacheStats> {
+        let entries = self.entries().await?;
+        let pinned = self.pinned();
+        let pinned_entries = || entries.iter().filter(|e| pinned.contains(&e.session_id));
+
+        Ok(CacheStats {
+            total_bytes: entries.iter().map(|e| e.size_bytes).sum(),
+            session_count: entries.len(),
+            pinned_count: pinned_entries().count(),
+            pinned_bytes: pinned_entries().map(|e| e.size_bytes).sum(),
+            oldest_entry: entries.iter().map(|e| e.last_used).min(),
+        })
+    }
+
+    /// Evict stale sessions and shrink the cache to fit under `max_bytes`
+    pub async fn evict(&self, max_bytes: Option<u64>) -> Result<EvictionResult> {
+        let entries = self.entries().await?;
+        let stale_before = SystemTime::now()
+            .checked_sub(STALE_AFTER)
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let selected = select_evictions(&entries, &self.pinned(), max_bytes, stale_before);
+        self.remove(&entries, &selected).await
+    }
+
+    /// Remove every session that isn't pinned
+    pub async fn clear(&self) -> Result<EvictionResult> {
+        let entries = self.entries().await?;
+        let pinned = self.pinned();
+        let selected: Vec<String> = entries
+            .iter()
+            .filter(|e| !pinned.contains(&e.session_id))
+            .map(|e| e.session_id.clone())
+            .collect();
+        self.remove(&entries, &selected).await
+    }
+
+    async fn remove(&self, entries: &[CacheEntry], selected: &[String]) -> Result<EvictionResult> {
+        let pinned = self.pinned();
+        let mut result = EvictionResult {
+            sessions_skipped: entries
+                .iter()
+                .filter(|e| pinned.contains(&e.session_id))
+                .count(),
+            ..Default::default()
+        };
+
+        for entry in entries.iter().filter(|e| selected.contains(&e.session_id)) {
+            // Re-check: the session may have been pinned since the scan
+            if self.pins.read().contains_key(&entry.session_id) {
+                result.sessions_skipped += 1;
+                continue;
+            }
+            match tokio::fs::remove_dir_all(self.session_dir(&entry.session_id)).await {
+                Ok(()) => {
+                    debug!(session_id = %entry.session_id, bytes = entry.size_bytes, "Evicted transcode session");
+                    result.sessions_removed += 1;
+                    result.bytes_freed += entry.size_bytes;
+                }
+                Err(e) => {
+                    warn!(session_id = %entry.session_id, error = %e, "Failed to remove transcode session");
+                }
+            }
+        }
+
+        if result.sessions_removed > 0 {
+            info!(
+                "Removed {} transcode session(s), freed {}",
+                result.sessions_removed,
+                super::format_bytes(result.bytes_freed)
+            );
+        }
+        Ok(result)
+    }
+}
+
+fn scan_entries(root: &Path) -> std::io::Result<Vec<CacheEntry>> {
+    let dir = match std::fs::read_dir(root) {
+        Ok(dir) => dir,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut entries = Vec::new();
+    for entry in dir.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let mut size_bytes = 0;
+        let mut last_used = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        for file in walkdir::WalkDir::new(entry.path()).into_iter().flatten() {
+            let Ok(meta) = file.metadata() else {
+                continue;
+            };
+            if meta.is_file() {
+                size_bytes += meta.len();
+            }
+            if let Ok(modified) = meta.modified() {
+                last_used = last_used.max(modified);
+            }
+        }
+        entries.push(CacheEntry {
+            session_id: entry.file_name().to_string_lossy().into_owned(),
+            size_bytes,
+            last_used,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: &str, size_bytes: u64, age_secs: u64, now: SystemTime) -> CacheEntry {
+        CacheEntry {
+            session_id: id.to_string(),
+            size_bytes,
+            last_used: now - Duration::from_secs(age_secs),
+        }
+    }
+
+    #[test]
+    fn test_select_evictions_lru_until_under_cap() {
+        let now = SystemTime::now();
+        let entries = vec![
+            entry("new", 40, 10, now),
+            entry("old", 40, 300, now),
+            entry("mid", 40, 100, now),
+        ];
+        let evicted = select_evictions(&entries, &HashSet::new(), Some(80), now - STALE_AFTER);
+        assert_eq!(evicted, vec!["old"]);
+    }
+
+    #[test]
+    fn test_select_evictions_skips_pinned() {
+        let now = SystemTime::now();
+        let entries = vec![entry("old", 100, 300, now), entry("new", 100, 10, now)];
+        let pinned = HashSet::from(["old".to_string()]);
+        let evicted = select_evictions(&entries, &pinned, Some(50), now - STALE_AFTER);
+        assert_eq!(evicted, vec!["new"]);
+    }
+
+    #[test]
+    fn test_select_evictions_removes_stale_without_cap() {
+        let now = SystemTime::now();
+        let entries = vec![
+            entry("stale", 1, STALE_AFTER.as_secs() + 60, now),
+            entry("fresh", 1, 60, now),
+        ];
+        let evicted = select_evictions(&entries, &HashSet::new(), None, now - STALE_AFTER);
+        assert_eq!(evicted, vec!["stale"]);
+    }
+
+    #[test]
+    fn test_max_bytes_from_gb() {
+        assert_eq!(max_bytes_from_gb(0.0), None);
+        assert_eq!(max_bytes_from_gb(1.5), Some(1_610_612_736));
+    }
+}
@@ -0,0 +1,241 @@
+//! TheTVDB API client for TV show metadata
+//!
+//! TheTVDB v4 needs an API key, exchanged at `/login` for a bearer token
+//! that lasts a month. The token is kept in memory and fetched again when
+//! the API rejects it.
+//! Base URL: https://api4.thetvdb.com/v4
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use super::rate_limiter::{RateLimitConfig, RateLimitedClient, RetryConfig, retry_async};
+
+/// Artwork type ID TheTVDB uses for series backgrounds
+const SERIES_BACKGROUND_TYPE: i32 = 3;
+
+/// TheTVDB API client with rate limiting and retry logic
+#[derive(Clone)]
+pub struct TvdbClient {
+    client: Arc<RateLimitedClient>,
+    base_url: String,
+    api_key: String,
+    token: Arc<RwLock<Option<String>>>,
+    retry_config: RetryConfig,
+}
+
+/// Envelope around every TheTVDB response
+#[derive(Debug, Deserialize)]
+struct TvdbResponse<T> {
+    data: T,
+}
+
+#[derive(Debug, Deserialize)]
+struct TvdbLogin {
+    token: String,
+}
+
+/// Series details from TheTVDB (`/series/{id}/extended`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TvdbSeries {
+    pub id: u32,
+    pub name: String,
+    pub overview: Option<String>,
+    pub image: Option<String>,
+    pub first_aired: Option<String>,
+    pub average_runtime: Option<i32>,
+    pub status: Option<TvdbStatus>,
+    pub original_network: Option<TvdbNetwork>,
+    #[serde(default)]
+    pub genres: Vec<TvdbGenre>,
+    #[serde(default)]
+    pub artworks: Vec<TvdbArtwork>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TvdbStatus {
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TvdbNetwork {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TvdbGenre {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TvdbArtwork {
+    pub image: String,
+    #[serde(rename = "type")]
+    pub artwork_type: i32,
+    pub score: Option<f64>,
+}
+
+impl TvdbClient {
+    /// Create a new TheTVDB client with the given API key
+    pub fn new(api_key: String) -> Self {
+        Self {
+            // No published limit; stay polite
+            client: Arc::new(RateLimitedClient::new(
+                "tvdb",
+                RateLimitConfig {
+                    requests_per_second: 2,
+                    burst_size: 5,
+                },
+            )),
+            base_url: "https://api4.thetvdb.com/v4".to_string(),
+            api_key,
+            token: Arc::new(RwLock::new(None)),
+            retry_config: RetryConfig {
+                max_retries: 2,
+                initial_interval: Duration::from_millis(500),
+                max_interval: Duration::from_secs(5),
+                multiplier: 2.0,
+            },
+        }
+    }
+
+    /// The current bearer token, logging in if there isn't one
+    async fn token(&self) -> Result<String> {
+        if let Some(token) = self.token.read().await.clone() {
+            return Ok(token);
+        }
+
+        debug!("Logging in to TheTVDB");
+        self.client.wait_for_permit().await;
+        let response = self
+            .client
+            .inner()
+            .post(format!("{}/login", self.base_url))
+            .json(&serde_json::json!({ "apikey": self.api_key }))
+            .send()
+            .await
+            .context("TheTVDB login request failed")?;
+
+        if response.status().as_u16() == 401 {
+            anyhow::bail!("TheTVDB API key is invalid");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("TheTVDB login failed with status: {}", response.status());
+        }
+
+        let login: TvdbResponse<TvdbLogin> = response
+            .json()
+            .await
+            .context("Failed to parse TheTVDB login")?;
+        *self.token.write().await = Some(login.data.token.clone());
+        Ok(login.data.token)
+    }
+
+    /// Check TheTVDB answers and accepts the API key, without retrying
+    pub async fn ping(&self) -> Result<()> {
+        let token = self.token().await?;
+        let auth = format!("Bearer {}", token);
+        let response = self
+            .client
+            .get_with_headers_and_query(
+                &format!("{}/languages", self.base_url),
+                &[("Authorization", auth.as_str())],
+                &[] as &[(&str, &str)],
+            )
+            .await?;
+
+        if response.status().as_u16() == 401 {
+            *self.token.write().await = None;
+            anyhow::bail!("TheTVDB token rejected");
+        }
+        if !response.status().is_success() {
+            anyhow::bail!("TheTVDB returned status: {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// Get series details by TheTVDB ID
+    pub async fn get_series(&self, tvdb_id: u32) -> Result<TvdbSeries> {
+        debug!("Fetching series details from TheTVDB (ID: {})", tvdb_id);
+
+        let url = format!("{}/series/{}/extended", self.base_url, tvdb_id);
+        let retry_config = self.retry_config.clone();
+
+        retry_async(
+            || {
+                let url = url.clone();
+                async move {
+                    let token = self.token().await?;
+                    let auth = format!("Bearer {}", token);
+                    let response = self
+                        .client
+                        .get_with_headers_and_query(
+                            &url,
+                            &[("Authorization", auth.as_str())],
+                            &[("short", "true")],
+                        )
+                        .await?;
+
+                    if response.status().as_u16() == 401 {
+                        // Token expired; log in again on the retry
+                        *self.token.write().await = None;
+                        anyhow::bail!("TheTVDB token rejected");
+                    }
+
+                    if response.status().as_u16() == 429 {
+                        warn!("TheTVDB rate limit hit, will retry");
+                        anyhow::bail!("Rate limited (429)");
+                    }
+
+                    if response.status().as_u16() == 404 {
+                        anyhow::bail!("Series not found on TheTVDB");
+                    }
+
+                    if !response.status().is_success() {
+                        anyhow::bail!(
+                            "TheTVDB get series failed with status: {}",
+                            response.status()
+                        );
+                    }
+
+                    let series: TvdbResponse<TvdbSeries> = response
+                        .json()
+                        .await
+                        .context("Failed to parse TheTVDB series")?;
+
+                    Ok(series.data)
+                }
+            },
+            &retry_config,
+            "tvdb_get_series",
+        )
+        .await
+    }
+}
+
+impl TvdbSeries {
+    /// Get the premiere year from first_aired
+    pub fn year(&self) -> Option<i32> {
+        self.first_aired
+            .as_ref()
+            .and_then(|d| d.split('-').next().and_then(|y| y.parse().ok()))
+    }
+
+    /// Highest-scored series background, if any
+    pub fn backdrop_url(&self) -> Option<&str> {
+        self.artworks
+            .iter()
+            .filter(|a| a.artwork_type == SERIES_BACKGROUND_TYPE)
+            .max_by(|a, b| a.score.unwrap_or(0.0).total_cmp(&b.score.unwrap_or(0.0)))
+            .map(|a| a.image.as_str())
+    }
+
+    pub fn genre_names(&self) -> Vec<String> {
+        self.genres.iter().map(|g| g.name.clone()).collect()
+    }
+}
@@ -2,25 +2,41 @@
 //!
 //! Manages Usenet downloads using native NNTP client.
 //! Parallel to TorrentService but for NZB-based downloads.
+//!
+//! Each server gets a connection pool sized by its `connections` setting,
+//! shared by all downloads. A file's segments are fetched concurrently
+//! through the pools and written in order.
+//!
+//! Each article is tried on the user's primary servers in priority order,
+//! then on their backup (block account) servers. Articles fetched, missing
+//! and failed are counted per server and written to the daily usage table
+//! with the progress updates.
 
 use std::collections::HashMap;
 use std::io::{Seek, SeekFrom, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::{anyhow, Result};
-use parking_lot::RwLock;
+use anyhow::{anyhow, Context, Result};
+use futures::{StreamExt, stream};
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use crate::db::{CreateUsenetDownload, Database, UsenetDownloadRecord, UsenetServerRecord};
+use crate::db::{
+    CreateUsenetDownload, Database, UsenetDownloadRecord, UsenetServerRecord, UsenetUsageCounts,
+};
 use crate::indexer::encryption::CredentialEncryption;
-use crate::usenet::{NntpClient, NntpConfig, NzbFile, NzbFileEntry, decode_yenc};
+use crate::services::bandwidth::BandwidthManager;
+use crate::services::par2::{self, Par2Phase, Par2Status};
+use crate::services::release_precheck;
+use crate::usenet::nntp::ArticleNotFound;
+use crate::usenet::{NntpClient, NntpConfig, NntpPool, NzbFile, NzbFileEntry, decode_yenc};
 
 /// Usenet download event for subscriptions
 #[derive(Debug, Clone, Serialize)]
@@ -29,6 +45,14 @@ pub enum UsenetEvent {
     Added(UsenetDownloadInfo),
     /// Download progress updated
     Progress(UsenetProgressUpdate),
+    /// PAR2 verification or repair progress
+    RepairProgress {
+        id: Uuid,
+        /// 'verifying' or 'repairing'
+        phase: String,
+        /// 0.0 to 1.0
+        progress: f64,
+    },
     /// Download state changed
     StateChanged {
         id: Uuid,
@@ -110,6 +134,17 @@ struct ActiveDownload {
     pub is_running: Arc<AtomicBool>,
 }
 
+/// A server's usage since it was last written to the database
+#[derive(Debug, Default)]
+struct ServerTally {
+    counts: UsenetUsageCounts,
+    /// Last failure other than a missing article
+    last_error: Option<String>,
+}
+
+/// Usage tallies by server ID
+type ServerTallies = Mutex<HashMap<Uuid, ServerTally>>;
+
 /// Usenet download service configuration
 #[derive(Debug, Clone)]
 pub struct UsenetServiceConfig {
@@ -139,6 +174,11 @@ pub struct UsenetService {
     active_downloads: Arc<RwLock<HashMap<Uuid, ActiveDownload>>>,
     /// Event broadcaster
     event_tx: broadcast::Sender<UsenetEvent>,
+    /// Connection pools by server ID
+    ///
+    /// Only one pool per server keeps a server's connection limit, so the
+    /// app shares a single service rather than creating its own.
+    pools: RwLock<HashMap<Uuid, Arc<NntpPool>>>,
 }
 
 impl UsenetService {
@@ -151,9 +191,15 @@ impl UsenetService {
             config,
             active_downloads: Arc::new(RwLock::new(HashMap::new())),
             event_tx,
+            pools: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Folder new downloads are written under
+    pub fn downloads_path(&self) -> &Path {
+        &self.config.downloads_path
+    }
+
     /// Get the encryption service for decrypting server passwords
     async fn get_encryption(&self) -> Result<CredentialEncryption> {
         let key = self
@@ -207,12 +253,54 @@ impl UsenetService {
         }
     }
 
+    /// The connection pool for a server
+    ///
+    /// The pool is replaced when the server's settings changed since it was
+    /// created; downloads still using the old one finish with it.
+    fn pool_for(&self, server: &UsenetServerRecord, password: Option<String>) -> Arc<NntpPool> {
+        let config = Self::create_nntp_config(server, password);
+        let max_connections = server.connections.max(1) as usize;
+
+        if let Some(pool) = self.pools.read().get(&server.id)
+            && pool.config() == &config
+            && pool.max_connections() == max_connections
+        {
+            return pool.clone();
+        }
+
+        let pool = Arc::new(NntpPool::new(config, max_connections));
+        self.pools.write().insert(server.id, pool.clone());
+        pool
+    }
+
+    /// Connect and log in to a server, to check it's reachable
+    pub async fn test_server(&self, server: &UsenetServerRecord, timeout: Duration) -> Result<()> {
+        let password = match (&server.encrypted_password, &server.password_nonce) {
+            (Some(enc_pass), Some(nonce)) => {
+                Some(self.get_encryption().await?.decrypt(enc_pass, nonce)?)
+            }
+            _ => None,
+        };
+        let mut config = Self::create_nntp_config(server, password);
+        config.timeout = timeout;
+
+        tokio::task::spawn_blocking(move || {
+            let mut client = NntpClient::new(config);
+            client.connect()?;
+            client.quit()
+        })
+        .await?
+    }
+
     /// Subscribe to usenet events
     pub fn subscribe(&self) -> broadcast::Receiver<UsenetEvent> {
         self.event_tx.subscribe()
     }
 
     /// Add an NZB download from raw bytes
+    ///
+    /// Fails without adding anything if the NZB is rejected by the
+    /// release pre-check.
     pub async fn add_nzb(
         &self,
         nzb_data: &[u8],
@@ -238,6 +326,18 @@ impl UsenetService {
             return Ok(UsenetDownloadInfo::from(&existing));
         }
 
+        // Screen the NZB's files for fakes; one that can't be parsed fails
+        // when the download starts
+        if let Ok(nzb) = NzbFile::parse(nzb_data) {
+            release_precheck::check_files(
+                &self.db,
+                &name,
+                &release_precheck::nzb_files(&nzb),
+                None,
+            )
+            .await?;
+        }
+
         // Create download directory
         let download_dir = self.config.downloads_path.join(&nzb_hash);
 
@@ -279,9 +379,12 @@ impl UsenetService {
     }
 
     /// Add an NZB download from a URL
+    ///
+    /// The download is named after the URL's file name unless `name` is set.
     pub async fn add_nzb_url(
         &self,
         url: &str,
+        name: Option<&str>,
         user_id: Uuid,
         library_id: Option<Uuid>,
         episode_id: Option<Uuid>,
@@ -304,10 +407,8 @@ impl UsenetService {
         let nzb_data = response.bytes().await?;
 
         // Extract filename from URL or Content-Disposition
-        let name = url
-            .split('/')
-            .last()
-            .and_then(|s| s.split('?').next())
+        let name = name
+            .or_else(|| url.split('/').last().and_then(|s| s.split('?').next()))
             .unwrap_or("download")
             .trim_end_matches(".nzb")
             .to_string();
@@ -363,26 +464,34 @@ impl UsenetService {
     }
 
     /// Remove a download
+    ///
+    /// A running download is cancelled before its record and files go, so
+    /// its task doesn't keep writing. With `delete_files` the files of
+    /// finished downloads are deleted too.
     pub async fn remove(&self, id: Uuid, delete_files: bool) -> Result<()> {
+        let record = self.db.usenet_downloads().get(id).await?;
+
         // Cancel the active download task if running
-        if let Some(download) = self.active_downloads.write().remove(&id) {
+        let active_path = self.active_downloads.write().remove(&id).map(|download| {
             download.cancel_token.cancel();
-
-            // Delete files if requested
-            if delete_files {
-                if let Err(e) = std::fs::remove_dir_all(&download.download_path) {
-                    warn!(
-                        id = %id,
-                        path = %download.download_path.display(),
-                        error = %e,
-                        "Failed to delete download files"
-                    );
-                }
-            }
-        }
+            download.download_path
+        });
 
         self.db.usenet_downloads().remove(id).await?;
 
+        let path = active_path.or_else(|| {
+            record
+                .and_then(|record| record.download_path)
+                .map(PathBuf::from)
+        });
+        if delete_files
+            && let Some(path) = path
+            && path.exists()
+        {
+            std::fs::remove_dir_all(&path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+        }
+
         let _ = self.event_tx.send(UsenetEvent::Removed(id));
 
         info!(id = %id, delete_files = delete_files, "Removed usenet download");
@@ -481,8 +590,16 @@ impl UsenetService {
             .update_progress(download_id, 0.0, 0, 0, None)
             .await?;
 
-        // Get servers for this user
-        let servers = self.get_servers_for_user(user_id).await?;
+        // Get servers for this user, with their connection pools
+        let servers: Vec<(UsenetServerRecord, Arc<NntpPool>)> = self
+            .get_servers_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(|(server, password)| {
+                let pool = self.pool_for(&server, password);
+                (server, pool)
+            })
+            .collect();
 
         // Create download directory
         std::fs::create_dir_all(&download_path)?;
@@ -524,6 +641,13 @@ impl UsenetService {
             )
             .await;
 
+            // Verify (and if needed repair) the assembled files before the
+            // download counts as completed
+            let result = match result {
+                Ok(()) => Self::verify_download(&db, &event_tx, download_id, &download_path).await,
+                Err(e) => Err(e),
+            };
+
             // Mark as not running
             is_running.store(false, Ordering::SeqCst);
 
@@ -534,6 +658,10 @@ impl UsenetService {
             }
 
             match result {
+                // Removed or paused meanwhile, the record isn't this task's anymore
+                Ok(()) if cancel_token.is_cancelled() => {
+                    info!(id = %download_id, "Usenet download cancelled");
+                }
                 Ok(()) => {
                     info!(id = %download_id, "Usenet download completed");
                     
@@ -559,11 +687,16 @@ impl UsenetService {
                         error!(id = %download_id, error = %e, "Usenet download failed");
                         
                         // Mark as failed in database
-                        if let Err(db_err) = db
-                            .usenet_downloads()
-                            .mark_failed(download_id, &e.to_string())
-                            .await
-                        {
+                        let marked = if e.is::<par2::RepairFailed>() {
+                            db.usenet_downloads()
+                                .mark_repair_failed(download_id, &e.to_string())
+                                .await
+                        } else {
+                            db.usenet_downloads()
+                                .mark_failed(download_id, &e.to_string())
+                                .await
+                        };
+                        if let Err(db_err) = marked {
                             error!(id = %download_id, error = %db_err, "Failed to mark download as failed");
                         }
 
@@ -580,11 +713,81 @@ impl UsenetService {
         Ok(())
     }
 
+    /// Run PAR2 verification and repair on a finished download and record
+    /// the result
+    ///
+    /// The download is in the repairing state meanwhile, and par2's progress
+    /// is broadcast as `RepairProgress` events. Fails with
+    /// [`par2::RepairFailed`] only when repair isn't possible. The download
+    /// is then marked repair_failed, which leaves the item wanted so another
+    /// release can be grabbed. Problems running par2 itself are logged and
+    /// don't fail the download.
+    async fn verify_download(
+        db: &Database,
+        event_tx: &broadcast::Sender<UsenetEvent>,
+        download_id: Uuid,
+        download_path: &std::path::Path,
+    ) -> Result<()> {
+        if let Err(e) = db.usenet_downloads().mark_repairing(download_id).await {
+            error!(id = %download_id, error = %e, "Failed to mark download repairing");
+        }
+        let _ = event_tx.send(UsenetEvent::StateChanged {
+            id: download_id,
+            old_state: "downloading".to_string(),
+            new_state: "repairing".to_string(),
+        });
+
+        // par2 prints progress every 0.1%, only pass on whole percents
+        let mut last: Option<(Par2Phase, f64)> = None;
+        let on_progress = |phase: Par2Phase, progress: f64| {
+            let changed = last.is_none_or(|(last_phase, last_progress)| {
+                last_phase != phase || (progress - last_progress).abs() >= 0.01 || progress >= 1.0
+            });
+            if changed {
+                last = Some((phase, progress));
+                let _ = event_tx.send(UsenetEvent::RepairProgress {
+                    id: download_id,
+                    phase: phase.as_str().to_string(),
+                    progress,
+                });
+            }
+        };
+
+        let outcome = match par2::verify_and_repair(download_path, on_progress).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                warn!(id = %download_id, error = %e, "PAR2 verification failed to run");
+                return Ok(());
+            }
+        };
+
+        if let Err(e) = db
+            .usenet_downloads()
+            .record_repair(
+                download_id,
+                outcome.status.as_str(),
+                outcome.blocks_needed,
+                outcome.recovery_blocks,
+                outcome.duration_ms,
+            )
+            .await
+        {
+            error!(id = %download_id, error = %e, "Failed to record PAR2 result");
+        }
+
+        match outcome.status {
+            Par2Status::Failed => {
+                Err(par2::RepairFailed(par2::repair_failed_message(&outcome)).into())
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// The actual download task that runs in background
     async fn download_task(
         download_id: Uuid,
         nzb: NzbFile,
-        servers: Vec<(UsenetServerRecord, Option<String>)>,
+        servers: Vec<(UsenetServerRecord, Arc<NntpPool>)>,
         download_path: PathBuf,
         cancel_token: CancellationToken,
         downloaded_bytes: Arc<AtomicU64>,
@@ -594,15 +797,18 @@ impl UsenetService {
         let total_bytes = nzb.total_size;
         let mut last_progress_update = Instant::now();
         let mut last_downloaded = 0u64;
+        let tallies = ServerTallies::default();
 
         // Process each file in the NZB
+        let mut result = Ok(());
         for file_entry in &nzb.files {
             if cancel_token.is_cancelled() {
-                return Err(anyhow!("Download cancelled"));
+                result = Err(anyhow!("Download cancelled"));
+                break;
             }
 
             // Download single file
-            Self::download_file(
+            result = Self::download_file(
                 download_id,
                 file_entry,
                 &servers,
@@ -612,26 +818,33 @@ impl UsenetService {
                 total_bytes,
                 &mut last_progress_update,
                 &mut last_downloaded,
+                &tallies,
                 &db,
                 &event_tx,
             )
-            .await?;
+            .await;
+            if result.is_err() {
+                break;
+            }
         }
 
-        Ok(())
+        // Usage counts whether or not the download finished
+        Self::flush_usage(&db, &tallies).await;
+        result
     }
 
     /// Download a single file from the NZB
     async fn download_file(
         download_id: Uuid,
         file_entry: &NzbFileEntry,
-        servers: &[(UsenetServerRecord, Option<String>)],
+        servers: &[(UsenetServerRecord, Arc<NntpPool>)],
         download_path: &PathBuf,
         cancel_token: &CancellationToken,
         downloaded_bytes: &Arc<AtomicU64>,
         total_bytes: u64,
         last_progress_update: &mut Instant,
         last_downloaded: &mut u64,
+        tallies: &ServerTallies,
         db: &Database,
         event_tx: &broadcast::Sender<UsenetEvent>,
     ) -> Result<()> {
@@ -651,48 +864,26 @@ impl UsenetService {
         let mut segments = file_entry.segments.clone();
         segments.sort_by_key(|s| s.number);
 
-        // Download each segment
-        for segment in &segments {
+        // Fetch segments concurrently, as many as the largest pool allows,
+        // and write them in order as they arrive
+        let concurrency = servers
+            .iter()
+            .map(|(_, pool)| pool.max_connections())
+            .max()
+            .unwrap_or(1);
+        let mut fetches = stream::iter(segments.iter().map(|segment| async move {
+            let data =
+                Self::fetch_segment(servers, &file_entry.groups, &segment.message_id, tallies)
+                    .await;
+            (segment, data)
+        }))
+        .buffered(concurrency);
+
+        while let Some((segment, article_data)) = fetches.next().await {
             if cancel_token.is_cancelled() {
                 return Err(anyhow!("Download cancelled"));
             }
-
-            // Try each server until one succeeds
-            let mut article_data = None;
-            let mut last_error = None;
-
-            for (server, password) in servers {
-                match Self::fetch_article_from_server(
-                    server,
-                    password.clone(),
-                    &file_entry.groups,
-                    &segment.message_id,
-                )
-                .await
-                {
-                    Ok(data) => {
-                        // Record success
-                        let _ = db.usenet_servers().record_success(server.id).await;
-                        article_data = Some(data);
-                        break;
-                    }
-                    Err(e) => {
-                        debug!(
-                            server = %server.name,
-                            message_id = %segment.message_id,
-                            error = %e,
-                            "Failed to fetch article, trying next server"
-                        );
-                        // Record error
-                        let _ = db.usenet_servers().record_error(server.id, &e.to_string()).await;
-                        last_error = Some(e);
-                    }
-                }
-            }
-
-            let article_data = article_data.ok_or_else(|| {
-                last_error.unwrap_or_else(|| anyhow!("No servers available"))
-            })?;
+            let article_data = article_data?;
 
             // Decode yEnc
             let decoded = decode_yenc(&article_data)?;
@@ -736,6 +927,8 @@ impl UsenetService {
                     .update_progress(download_id, progress, current as i64, speed, eta)
                     .await;
 
+                Self::flush_usage(db, tallies).await;
+
                 // Broadcast progress event
                 let _ = event_tx.send(UsenetEvent::Progress(UsenetProgressUpdate {
                     id: download_id,
@@ -762,40 +955,105 @@ impl UsenetService {
         Ok(())
     }
 
-    /// Fetch an article from a specific server
-    async fn fetch_article_from_server(
-        server: &UsenetServerRecord,
-        password: Option<String>,
+    /// Fetch a segment, trying each server in order until one has it
+    ///
+    /// Servers come primaries first, then backups, each by priority.
+    async fn fetch_segment(
+        servers: &[(UsenetServerRecord, Arc<NntpPool>)],
         groups: &[String],
         message_id: &str,
+        tallies: &ServerTallies,
     ) -> Result<Vec<u8>> {
-        // Run NNTP operations in blocking task since they use std IO
-        let config = Self::create_nntp_config(server, password);
-        let groups = groups.to_vec();
-        let message_id = message_id.to_string();
+        let mut last_error = None;
+
+        for (server, pool) in servers {
+            let fetched = Self::fetch_article(pool, groups, message_id).await;
+
+            let mut tallies = tallies.lock();
+            let tally = tallies.entry(server.id).or_default();
+            match fetched {
+                Ok(data) => {
+                    tally.counts.articles_downloaded += 1;
+                    tally.counts.bytes_downloaded += data.len() as i64;
+                    return Ok(data);
+                }
+                Err(e) if e.is::<ArticleNotFound>() => {
+                    debug!(
+                        server = %server.name,
+                        message_id = %message_id,
+                        "Article not on server, trying next server"
+                    );
+                    tally.counts.articles_missing += 1;
+                    last_error = Some(e);
+                }
+                Err(e) => {
+                    debug!(
+                        server = %server.name,
+                        message_id = %message_id,
+                        error = %e,
+                        "Failed to fetch article, trying next server"
+                    );
+                    tally.counts.articles_failed += 1;
+                    tally.last_error = Some(e.to_string());
+                    last_error = Some(e);
+                }
+            }
+        }
 
-        tokio::task::spawn_blocking(move || {
-            let mut client = NntpClient::new(config);
-            
-            // Connect
-            client.connect()?;
+        Err(last_error.unwrap_or_else(|| anyhow!("No servers available")))
+    }
 
-            // Try to select a group (optional for most servers with article by message-id)
-            if let Some(group) = groups.first() {
-                if let Err(e) = client.group(group) {
-                    debug!(group = %group, error = %e, "Failed to select group, continuing anyway");
-                }
+    /// Write the tallied usage to the database, and record each server's
+    /// health: an error if anything but a missing article failed, a success
+    /// if it served articles
+    async fn flush_usage(db: &Database, tallies: &ServerTallies) {
+        let tallied = std::mem::take(&mut *tallies.lock());
+
+        for (server_id, tally) in tallied {
+            if let Err(e) = db
+                .usenet_servers()
+                .record_usage(server_id, &tally.counts)
+                .await
+            {
+                warn!(server_id = %server_id, error = %e, "Failed to record usenet server usage");
             }
 
-            // Fetch article body
-            let body = client.body(&message_id)?;
+            if let Some(error) = tally.last_error {
+                let _ = db.usenet_servers().record_error(server_id, &error).await;
+            } else if tally.counts.articles_downloaded > 0 {
+                let _ = db.usenet_servers().record_success(server_id).await;
+            }
+        }
+    }
 
-            // Disconnect
-            client.quit()?;
+    /// Fetch an article on one of a server's pooled connections
+    async fn fetch_article(
+        pool: &NntpPool,
+        groups: &[String],
+        message_id: &str,
+    ) -> Result<Vec<u8>> {
+        let group = groups.first().cloned();
+        let message_id = message_id.to_string();
 
-            Ok(body)
-        })
-        .await?
+        let body = pool
+            .run(move |client| {
+                // Try to select a group (optional for most servers with article
+                // by message-id), unless the connection already has it selected
+                if let Some(group) = group
+                    && client.current_group() != Some(group.as_str())
+                    && let Err(e) = client.group(&group)
+                {
+                    debug!(group = %group, error = %e, "Failed to select group, continuing anyway");
+                }
+
+                client.body(&message_id)
+            })
+            .await?;
+
+        // Downloads share one usenet speed limit; waiting here holds back this
+        // segment's worker, not the connection
+        BandwidthManager::global().throttle_usenet(body.len()).await;
+        Ok(body)
     }
 }
 
@@ -0,0 +1,149 @@
+//! Per-user watch state: continue watching and next up
+//!
+//! Continue watching is read straight from `watch_progress`. Next up is
+//! computed per show: the regular episode after the furthest one the user
+//! has watched. Specials (season 0) never count as next up and don't move
+//! the position forward, since they're rarely watched in order.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use uuid::Uuid;
+
+use crate::db::{Database, EpisodeRecord, WatchProgressRecord};
+
+/// What next-up needs to know about one episode of a show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EpisodeState {
+    pub season: i32,
+    pub episode: i32,
+    pub has_file: bool,
+    pub watched: bool,
+}
+
+/// Index of the next episode to watch, given a show's episodes in any order
+///
+/// `None` when nothing has been watched yet, everything after the furthest
+/// watched episode is watched too, or the next episode isn't in the
+/// library.
+pub fn next_up_index(episodes: &[EpisodeState]) -> Option<usize> {
+    let furthest = episodes
+        .iter()
+        .filter(|e| e.watched && e.season > 0)
+        .map(|e| (e.season, e.episode))
+        .max()?;
+
+    let (index, next) = episodes
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| e.season > 0 && (e.season, e.episode) > furthest && !e.watched)
+        .min_by_key(|(_, e)| (e.season, e.episode))?;
+
+    next.has_file.then_some(index)
+}
+
+/// A show's next episode with the user's progress on it, if any
+pub struct NextUp {
+    pub episode: EpisodeRecord,
+    pub progress: Option<WatchProgressRecord>,
+}
+
+/// Next episode of each show the user watched recently, most recent show first
+pub async fn next_up(db: &Database, user_id: Uuid, limit: i64) -> Result<Vec<NextUp>> {
+    let watch_progress = db.watch_progress();
+    let show_ids = watch_progress.get_recent_shows(user_id, limit).await?;
+
+    let mut results = Vec::new();
+    for show_id in show_ids {
+        let episodes = db.episodes().list_by_show(show_id).await?;
+        let episode_ids: Vec<Uuid> = episodes.iter().map(|e| e.id).collect();
+        let mut progress: HashMap<Uuid, WatchProgressRecord> = watch_progress
+            .get_episode_progress_batch(user_id, &episode_ids)
+            .await?
+            .into_iter()
+            .filter_map(|p| p.episode_id.map(|id| (id, p)))
+            .collect();
+
+        let states: Vec<EpisodeState> = episodes
+            .iter()
+            .map(|e| EpisodeState {
+                season: e.season,
+                episode: e.episode,
+                has_file: e.media_file_id.is_some(),
+                watched: progress.get(&e.id).is_some_and(|p| p.is_watched),
+            })
+            .collect();
+
+        if let Some(index) = next_up_index(&states) {
+            let episode = episodes[index].clone();
+            results.push(NextUp {
+                progress: progress.remove(&episode.id),
+                episode,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ep(season: i32, episode: i32, watched: bool) -> EpisodeState {
+        EpisodeState {
+            season,
+            episode,
+            has_file: true,
+            watched,
+        }
+    }
+
+    #[test]
+    fn test_next_after_furthest_watched() {
+        let episodes = [ep(1, 1, true), ep(1, 2, true), ep(1, 3, false)];
+        assert_eq!(next_up_index(&episodes), Some(2));
+    }
+
+    #[test]
+    fn test_skipped_earlier_episode_is_ignored() {
+        let episodes = [ep(1, 1, false), ep(1, 2, true), ep(1, 3, false)];
+        assert_eq!(next_up_index(&episodes), Some(2));
+    }
+
+    #[test]
+    fn test_crosses_into_next_season_in_any_order() {
+        let episodes = [
+            ep(2, 1, false),
+            ep(1, 2, true),
+            ep(2, 2, false),
+            ep(1, 1, true),
+        ];
+        assert_eq!(next_up_index(&episodes), Some(0));
+    }
+
+    #[test]
+    fn test_nothing_watched_or_all_watched() {
+        assert_eq!(next_up_index(&[ep(1, 1, false)]), None);
+        assert_eq!(next_up_index(&[ep(1, 1, true), ep(1, 2, true)]), None);
+    }
+
+    #[test]
+    fn test_specials_are_skipped() {
+        let episodes = [ep(0, 1, true), ep(1, 1, false)];
+        assert_eq!(next_up_index(&episodes), None);
+
+        let episodes = [ep(1, 1, true), ep(0, 1, false), ep(1, 2, false)];
+        assert_eq!(next_up_index(&episodes), Some(2));
+    }
+
+    #[test]
+    fn test_missing_file_has_no_next_up() {
+        let mut next = ep(1, 2, false);
+        next.has_file = false;
+        assert_eq!(
+            next_up_index(&[ep(1, 1, true), next, ep(1, 3, false)]),
+            None
+        );
+    }
+}
@@ -0,0 +1,414 @@
+//! Import pipeline test harness
+//!
+//! Builds a throwaway environment — a temporary SQLite database with the
+//! migrations applied, plus library and download folders under a temp
+//! directory — and drives the scanner, organizer and download completion
+//! processing against it, so import regressions are caught by `cargo test`
+//! rather than in someone's library.
+//!
+//! Compiled for unit tests only. Libraries are created without auto-add or
+//! auto-hunt, so nothing reaches a metadata provider or indexer unless a test
+//! turns that on; tests that need lookups should run with
+//! `LIBRARIAN_OFFLINE=1` and recorded fixtures (see
+//! [`crate::services::http_replay`]).
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tempfile::TempDir;
+use uuid::Uuid;
+
+use crate::db::{
+    CreateLibrary, CreateMovie, CreateTorrent, CreateUser, Database, LibraryRecord, MovieRecord,
+    TorrentRecord,
+};
+use crate::services::file_matcher::{FileInfo, FileMatcher};
+use crate::services::scanner::ScanProgress;
+use crate::services::torrent_completion_handler::process_completed_download;
+use crate::services::{MetadataService, OrganizerService, ScannerService};
+
+/// Matroska (EBML) magic, so generated files look like video to anything that
+/// sniffs the header
+const MKV_MAGIC: [u8; 4] = [0x1A, 0x45, 0xDF, 0xA3];
+
+/// Bytes for a dummy media file of the given size (at least the magic)
+pub fn dummy_media_bytes(size: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; size.max(MKV_MAGIC.len())];
+    bytes[..MKV_MAGIC.len()].copy_from_slice(&MKV_MAGIC);
+    bytes
+}
+
+/// A temporary database and filesystem for one test
+///
+/// Everything is deleted when the environment is dropped.
+pub struct TestEnv {
+    pub db: Database,
+    /// Owner of the libraries and downloads the environment creates
+    pub user_id: Uuid,
+    root: TempDir,
+}
+
+impl TestEnv {
+    /// Create a migrated database and a user to own test data
+    pub async fn new() -> Result<Self> {
+        let root = tempfile::tempdir().context("Failed to create temp directory")?;
+        let url = format!("sqlite://{}", root.path().join("librarian.db").display());
+        let db = Database::connect(&url).await?;
+        db.migrate().await?;
+
+        let user = db
+            .users()
+            .create(CreateUser {
+                username: "harness".to_string(),
+                email: None,
+                // Never logged in with, so no real hash is needed
+                password_hash: "!".to_string(),
+                role: "admin".to_string(),
+                display_name: None,
+            })
+            .await?;
+
+        Ok(Self {
+            db,
+            user_id: Uuid::parse_str(&user.id)?,
+            root,
+        })
+    }
+
+    /// Root of the environment's temp directory
+    pub fn root(&self) -> &Path {
+        self.root.path()
+    }
+
+    /// Folder completed downloads are written to
+    pub fn downloads_dir(&self) -> PathBuf {
+        self.root().join("downloads")
+    }
+
+    /// Create a library (and its folder) with automation turned off
+    ///
+    /// `configure` can adjust the input before it is saved, e.g. to turn on
+    /// organizing or change the missing-file policy.
+    pub async fn create_library(
+        &self,
+        name: &str,
+        library_type: &str,
+        configure: impl FnOnce(&mut CreateLibrary),
+    ) -> Result<LibraryRecord> {
+        let path = self.root().join("libraries").join(name);
+        tokio::fs::create_dir_all(&path).await?;
+
+        let mut input = CreateLibrary {
+            user_id: self.user_id,
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            library_type: library_type.to_string(),
+            icon: None,
+            color: None,
+            auto_scan: false,
+            scan_interval_minutes: 60,
+            watch_for_changes: false,
+            post_download_action: "copy".to_string(),
+            organize_files: false,
+            rename_style: "none".to_string(),
+            naming_pattern: None,
+            auto_add_discovered: false,
+            auto_download: false,
+            auto_hunt: false,
+            allowed_resolutions: Vec::new(),
+            allowed_video_codecs: Vec::new(),
+            allowed_audio_formats: Vec::new(),
+            require_hdr: false,
+            allowed_hdr_types: Vec::new(),
+            allowed_sources: Vec::new(),
+            release_group_blacklist: Vec::new(),
+            release_group_whitelist: Vec::new(),
+            exclude_patterns: Vec::new(),
+            file_uid: None,
+            file_gid: None,
+            file_mode: None,
+            missing_file_policy: "flag".to_string(),
+        };
+        configure(&mut input);
+        self.db.libraries().create(input).await
+    }
+
+    /// Add a monitored movie to a library, as adding it from search would
+    /// (without any metadata beyond the title and year)
+    pub async fn create_movie(
+        &self,
+        library: &LibraryRecord,
+        title: &str,
+        year: i32,
+    ) -> Result<MovieRecord> {
+        self.db
+            .movies()
+            .create(CreateMovie {
+                library_id: library.id,
+                user_id: self.user_id,
+                title: title.to_string(),
+                sort_title: None,
+                original_title: None,
+                year: Some(year),
+                tmdb_id: None,
+                imdb_id: None,
+                overview: None,
+                tagline: None,
+                runtime: None,
+                genres: Vec::new(),
+                production_countries: Vec::new(),
+                spoken_languages: Vec::new(),
+                director: None,
+                cast_names: Vec::new(),
+                tmdb_rating: None,
+                tmdb_vote_count: None,
+                poster_url: None,
+                backdrop_url: None,
+                collection_id: None,
+                collection_name: None,
+                collection_poster_url: None,
+                release_date: None,
+                certification: None,
+                status: None,
+                monitored: true,
+            })
+            .await
+    }
+
+    /// Write a dummy media file under `dir`, creating folders as needed
+    pub async fn write_media_file(&self, dir: &Path, relative_path: &str) -> Result<PathBuf> {
+        let path = dir.join(relative_path);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, dummy_media_bytes(64 * 1024)).await?;
+        Ok(path)
+    }
+
+    /// Metadata service without API keys
+    pub fn metadata_service(&self) -> Arc<MetadataService> {
+        Arc::new(MetadataService::new_default(self.db.clone()))
+    }
+
+    /// Scanner with no queues, notifications or auto-hunt attached
+    pub fn scanner(&self) -> ScannerService {
+        ScannerService::new(self.db.clone(), self.metadata_service())
+    }
+
+    /// Run a full scan of a library
+    pub async fn scan(&self, library_id: Uuid) -> Result<ScanProgress> {
+        self.scanner().scan_library(library_id).await
+    }
+
+    /// Organizer for moving and renaming library files
+    pub fn organizer(&self) -> OrganizerService {
+        OrganizerService::new(self.db.clone())
+    }
+
+    /// Simulate a finished torrent: write its files to the downloads folder,
+    /// record it as completed and match the files to library items, as
+    /// happens when a real torrent is added
+    ///
+    /// `files` are paths relative to the torrent folder.
+    pub async fn add_completed_download(
+        &self,
+        name: &str,
+        files: &[&str],
+    ) -> Result<TorrentRecord> {
+        let save_path = self.downloads_dir();
+        let mut infos = Vec::new();
+        for (index, file) in files.iter().enumerate() {
+            let path = self
+                .write_media_file(&save_path, &format!("{}/{}", name, file))
+                .await?;
+            let size = tokio::fs::metadata(&path).await?.len() as i64;
+            infos.push(FileInfo {
+                path: path.to_string_lossy().to_string(),
+                size,
+                file_index: Some(index as i32),
+                source_name: Some(name.to_string()),
+            });
+        }
+
+        let info_hash = format!("{:040x}", Uuid::new_v4().as_u128());
+        let torrents = self.db.torrents();
+        torrents
+            .create(CreateTorrent {
+                user_id: self.user_id,
+                info_hash: info_hash.clone(),
+                magnet_uri: None,
+                name: name.to_string(),
+                save_path: save_path.to_string_lossy().to_string(),
+                total_bytes: infos.iter().map(|f| f.size).sum(),
+            })
+            .await?;
+        torrents.mark_completed(&info_hash).await?;
+        let torrent = torrents
+            .get_by_info_hash(&info_hash)
+            .await?
+            .context("Torrent missing after insert")?;
+
+        let matcher = FileMatcher::new(self.db.clone());
+        let matches = matcher.match_files(self.user_id, infos, None).await?;
+        matcher
+            .save_matches(self.user_id, "torrent", Some(torrent.id), &matches)
+            .await?;
+
+        Ok(torrent)
+    }
+
+    /// Run completion processing for a download, as the completion handler
+    /// does when the torrent finishes
+    pub async fn complete_download(&self, info_hash: &str) -> Result<TorrentRecord> {
+        process_completed_download(self.db.clone(), info_hash, None, "test harness").await?;
+        self.db
+            .torrents()
+            .get_by_info_hash(info_hash)
+            .await?
+            .context("Torrent missing after processing")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dummy_media_bytes() {
+        let bytes = dummy_media_bytes(16);
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(bytes[..4], MKV_MAGIC);
+        assert_eq!(dummy_media_bytes(0).len(), MKV_MAGIC.len());
+    }
+
+    #[tokio::test]
+    async fn test_scan_adds_and_removes_files() -> Result<()> {
+        let env = TestEnv::new().await?;
+        let library = env
+            .create_library("TV", "tv", |l| l.missing_file_policy = "rewant".to_string())
+            .await?;
+        let dir = PathBuf::from(&library.path);
+        env.write_media_file(&dir, "Show/Season 01/Show - S01E01.mkv")
+            .await?;
+        let second = env
+            .write_media_file(&dir, "Show/Season 01/Show - S01E02.mkv")
+            .await?;
+
+        let progress = env.scan(library.id).await?;
+        assert_eq!(progress.new_files, 2);
+        assert_eq!(env.db.media_files().count_by_library(library.id).await?, 2);
+
+        tokio::fs::remove_file(&second).await?;
+        let progress = env.scan(library.id).await?;
+        assert_eq!(progress.removed_files, 1);
+        assert_eq!(env.db.media_files().count_by_library(library.id).await?, 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flag_policy_keeps_missing_file_records() -> Result<()> {
+        let env = TestEnv::new().await?;
+        let library = env.create_library("Movies", "movies", |_| {}).await?;
+        let dir = PathBuf::from(&library.path);
+        env.write_media_file(&dir, "Film (2020)/Film (2020).mkv")
+            .await?;
+        let gone = env
+            .write_media_file(&dir, "Other (2021)/Other (2021).mkv")
+            .await?;
+        env.scan(library.id).await?;
+
+        tokio::fs::remove_file(&gone).await?;
+        let progress = env.scan(library.id).await?;
+        assert_eq!(progress.removed_files, 0);
+        assert_eq!(env.db.media_files().count_by_library(library.id).await?, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_completed_download_is_recorded() -> Result<()> {
+        let env = TestEnv::new().await?;
+        env.create_library("Movies", "movies", |_| {}).await?;
+
+        let torrent = env
+            .add_completed_download(
+                "Some.Film.2020.1080p.WEB-DL.x264-GRP",
+                &["Some.Film.2020.1080p.WEB-DL.x264-GRP.mkv"],
+            )
+            .await?;
+        let torrent = env.complete_download(&torrent.info_hash).await?;
+
+        // No movie in the library to match, so nothing is imported
+        assert_eq!(torrent.post_process_status.as_deref(), Some("unmatched"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_completed_download_is_imported_and_organized() -> Result<()> {
+        let env = TestEnv::new().await?;
+        let library = env
+            .create_library("Movies", "movies", |l| {
+                // Downloads are only matched against auto-download libraries
+                l.auto_download = true;
+                l.organize_files = true;
+                l.naming_pattern = Some("{title} ({year})/{title}.{ext}".to_string());
+            })
+            .await?;
+        let movie = env.create_movie(&library, "Some Film", 2020).await?;
+
+        let release = "Some.Film.2020.1080p.WEB-DL.x264-GRP";
+        let torrent = env
+            .add_completed_download(release, &[&format!("{}.mkv", release)])
+            .await?;
+        let torrent = env.complete_download(&torrent.info_hash).await?;
+        assert_eq!(torrent.post_process_status.as_deref(), Some("completed"));
+
+        // Copied into the library under the default movie pattern and linked
+        let movie = env
+            .db
+            .movies()
+            .get_by_id(movie.id)
+            .await?
+            .context("Movie missing")?;
+        let file_id = movie.media_file_id.context("Movie not linked to a file")?;
+        let file = env
+            .db
+            .media_files()
+            .get_by_id(file_id)
+            .await?
+            .context("Media file missing")?;
+        assert_eq!(file.movie_id, Some(movie.id));
+        let imported = Path::new(&library.path).join("Some Film (2020)/Some Film (2020).mkv");
+        assert_eq!(Path::new(&file.path), imported);
+        assert!(imported.exists());
+
+        // The organizer then renames it to the library's own pattern
+        let result = env
+            .organizer()
+            .organize_movie_file(
+                &file,
+                &movie,
+                &library.path,
+                library.naming_pattern.as_deref(),
+                &library.post_download_action,
+                false,
+            )
+            .await?;
+        assert!(result.success, "organize failed: {:?}", result.error);
+
+        let organized = Path::new(&library.path).join("Some Film (2020)/Some Film.mkv");
+        assert_eq!(Path::new(&result.new_path), organized);
+        assert!(organized.exists());
+        assert!(!imported.exists());
+
+        let file = env
+            .db
+            .media_files()
+            .get_by_id(file_id)
+            .await?
+            .context("Media file missing")?;
+        assert_eq!(Path::new(&file.path), organized);
+        assert!(file.organized);
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@
 //! - NZB file parsing
 //! - yEnc binary decoding
 //! - NNTP protocol client
+//! - NNTP connection pooling
 //! - Article reassembly
 //!
 //! # Architecture
@@ -20,9 +21,12 @@
 
 pub mod nntp;
 pub mod nzb;
+pub mod pool;
+pub mod retention;
 pub mod yenc;
 
 // Re-export commonly used types
 pub use nntp::{NntpClient, NntpConfig};
 pub use nzb::{NzbFile, NzbFileEntry};
+pub use pool::NntpPool;
 pub use yenc::decode_yenc;
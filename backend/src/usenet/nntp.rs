@@ -10,26 +10,35 @@
 //! - `ARTICLE` - Retrieve full article (headers + body)
 //! - `BODY` - Retrieve article body only
 //! - `STAT` - Check if article exists
+//! - `DATE` - Check the connection is alive
 //! - `QUIT` - Close connection
 //!
 //! # Connection Flow
 //!
-//! 1. Connect to server (plain or TLS)
+//! 1. Connect to server (plain, or TLS with rustls for NNTPS on port 563)
 //! 2. Receive greeting (200/201)
 //! 3. Authenticate if required
 //! 4. Select group and retrieve articles
 //! 5. Close connection
+//!
+//! Connections are reused between articles through
+//! [`NntpPool`](super::pool::NntpPool).
 
-use std::io::{BufRead, BufReader, Write};
-use std::net::TcpStream;
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, OnceLock};
 use std::time::Duration;
 
 use anyhow::{Result, anyhow};
-use native_tls::TlsConnector;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
 use tracing::{debug, info};
 
+/// Read buffer size, large enough for article bodies to arrive in few reads
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
 /// NNTP client configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct NntpConfig {
     /// Server hostname
     pub host: String,
@@ -81,16 +90,61 @@ impl NntpResponse {
     }
 }
 
+/// The server doesn't have an article (430)
+///
+/// Expected for old or taken-down posts, and the reason to try the article
+/// on another server, rather than a problem with this one.
+#[derive(Debug)]
+pub struct ArticleNotFound(pub String);
+
+impl std::fmt::Display for ArticleNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Article not found: {}", self.0)
+    }
+}
+
+impl std::error::Error for ArticleNotFound {}
+
 /// NNTP client for a single connection
 pub struct NntpClient {
     config: NntpConfig,
     // The connection is stored as an enum to handle both TLS and plain
     connection: Option<NntpConnection>,
+    /// Group selected with GROUP, if any
+    current_group: Option<String>,
 }
 
 enum NntpConnection {
     Plain(BufReader<TcpStream>),
-    Tls(BufReader<native_tls::TlsStream<TcpStream>>),
+    Tls(Box<BufReader<StreamOwned<ClientConnection, TcpStream>>>),
+}
+
+impl NntpConnection {
+    /// Read up to and including the next `\n`
+    ///
+    /// The server closing the connection is an `UnexpectedEof` error, so
+    /// callers can tell a dead connection from a bad response.
+    fn read_line(&mut self, buf: &mut Vec<u8>) -> std::io::Result<()> {
+        let read = match self {
+            NntpConnection::Plain(reader) => reader.read_until(b'\n', buf)?,
+            NntpConnection::Tls(reader) => reader.read_until(b'\n', buf)?,
+        };
+        if read == 0 {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> std::io::Result<()> {
+        match self {
+            NntpConnection::Plain(reader) => reader.get_mut().write_all(data),
+            NntpConnection::Tls(reader) => {
+                let stream = reader.get_mut();
+                stream.write_all(data)?;
+                stream.flush()
+            }
+        }
+    }
 }
 
 impl NntpClient {
@@ -99,28 +153,41 @@ impl NntpClient {
         Self {
             config,
             connection: None,
+            current_group: None,
         }
     }
 
     /// Connect to the NNTP server
     pub fn connect(&mut self) -> Result<NntpResponse> {
-        let addr = format!("{}:{}", self.config.host, self.config.port);
-        debug!(addr = %addr, use_tls = self.config.use_tls, "Connecting to NNTP server");
+        debug!(
+            host = %self.config.host,
+            port = self.config.port,
+            use_tls = self.config.use_tls,
+            "Connecting to NNTP server"
+        );
 
-        let stream = TcpStream::connect_timeout(
-            &addr.parse()?,
-            self.config.timeout,
-        )?;
+        let stream = connect_tcp(&self.config.host, self.config.port, self.config.timeout)?;
         stream.set_read_timeout(Some(self.config.timeout))?;
         stream.set_write_timeout(Some(self.config.timeout))?;
+        stream.set_nodelay(true)?;
 
         if self.config.use_tls {
-            let connector = TlsConnector::new()?;
-            let tls_stream = connector.connect(&self.config.host, stream)?;
-            self.connection = Some(NntpConnection::Tls(BufReader::new(tls_stream)));
+            let server_name = ServerName::try_from(self.config.host.clone())
+                .map_err(|_| anyhow!("Invalid server name for TLS: {}", self.config.host))?;
+            let tls = ClientConnection::new(tls_config()?, server_name)?;
+            // The handshake happens on the first read, the greeting below
+            let stream = StreamOwned::new(tls, stream);
+            self.connection = Some(NntpConnection::Tls(Box::new(BufReader::with_capacity(
+                READ_BUFFER_SIZE,
+                stream,
+            ))));
         } else {
-            self.connection = Some(NntpConnection::Plain(BufReader::new(stream)));
+            self.connection = Some(NntpConnection::Plain(BufReader::with_capacity(
+                READ_BUFFER_SIZE,
+                stream,
+            )));
         }
+        self.current_group = None;
 
         // Read greeting
         let response = self.read_response()?;
@@ -172,7 +239,28 @@ impl NntpClient {
     /// Select a newsgroup
     pub fn group(&mut self, group_name: &str) -> Result<NntpResponse> {
         self.send_command(&format!("GROUP {}", group_name))?;
-        self.read_response()
+        let response = self.read_response()?;
+        self.current_group = response.is_success().then(|| group_name.to_string());
+        Ok(response)
+    }
+
+    /// The group selected on this connection, if any
+    pub fn current_group(&self) -> Option<&str> {
+        self.current_group.as_deref()
+    }
+
+    /// Check the server still answers on this connection
+    pub fn ping(&mut self) -> Result<()> {
+        self.send_command("DATE")?;
+        let response = self.read_response()?;
+        if response.code != 111 {
+            return Err(anyhow!(
+                "DATE failed: {} {}",
+                response.code,
+                response.message
+            ));
+        }
+        Ok(())
     }
 
     /// Check if an article exists
@@ -189,6 +277,9 @@ impl NntpClient {
         self.send_command(&format!("BODY {}", mid))?;
         let response = self.read_multiline_response()?;
 
+        if response.code == 430 {
+            return Err(ArticleNotFound(mid).into());
+        }
         if !response.is_success() {
             return Err(anyhow!("BODY failed: {} {}", response.code, response.message));
         }
@@ -219,6 +310,14 @@ impl NntpClient {
         Ok(())
     }
 
+    /// Drop the connection without sending QUIT
+    ///
+    /// For connections that failed or went stale, where waiting for the
+    /// server to answer QUIT could block until the timeout.
+    pub fn close(&mut self) {
+        self.connection = None;
+    }
+
     /// Send a command to the server
     fn send_command(&mut self, command: &str) -> Result<()> {
         let conn = self.connection.as_mut().ok_or_else(|| anyhow!("Not connected"))?;
@@ -233,14 +332,7 @@ impl NntpClient {
         };
         debug!(command = %log_cmd, "Sending NNTP command");
 
-        match conn {
-            NntpConnection::Plain(reader) => {
-                reader.get_mut().write_all(cmd.as_bytes())?;
-            }
-            NntpConnection::Tls(reader) => {
-                reader.get_mut().write_all(cmd.as_bytes())?;
-            }
-        }
+        conn.write_all(cmd.as_bytes())?;
 
         Ok(())
     }
@@ -249,17 +341,10 @@ impl NntpClient {
     fn read_response(&mut self) -> Result<NntpResponse> {
         let conn = self.connection.as_mut().ok_or_else(|| anyhow!("Not connected"))?;
 
-        let mut line = String::new();
-        
-        match conn {
-            NntpConnection::Plain(reader) => {
-                reader.read_line(&mut line)?;
-            }
-            NntpConnection::Tls(reader) => {
-                reader.read_line(&mut line)?;
-            }
-        }
+        let mut line = Vec::new();
+        conn.read_line(&mut line)?;
 
+        let line = String::from_utf8_lossy(&line);
         let line = line.trim_end();
         debug!(response = %line, "Received NNTP response");
 
@@ -271,42 +356,38 @@ impl NntpClient {
         let conn = self.connection.as_mut().ok_or_else(|| anyhow!("Not connected"))?;
 
         // Read first line (status)
-        let mut first_line = String::new();
-        match conn {
-            NntpConnection::Plain(reader) => reader.read_line(&mut first_line)?,
-            NntpConnection::Tls(reader) => reader.read_line(&mut first_line)?,
-        };
+        let mut first_line = Vec::new();
+        conn.read_line(&mut first_line)?;
 
+        let first_line = String::from_utf8_lossy(&first_line);
         let mut response = parse_response_line(first_line.trim_end())?;
 
         if !response.is_success() {
             return Ok(response);
         }
 
-        // Read data lines until "."
+        // Read data lines until ".", as bytes since yEnc bodies aren't UTF-8
         let mut data = Vec::new();
+        let mut line = Vec::new();
         loop {
-            let mut line = String::new();
-            match conn {
-                NntpConnection::Plain(reader) => reader.read_line(&mut line)?,
-                NntpConnection::Tls(reader) => reader.read_line(&mut line)?,
-            };
+            line.clear();
+            conn.read_line(&mut line)?;
 
             // Remove CRLF
-            let line = line.trim_end_matches(|c| c == '\r' || c == '\n');
+            let content = strip_line_ending(&line);
 
-            if line == "." {
+            if content == b"." {
                 break;
             }
 
             // Handle dot-stuffing (lines starting with ".." should be ".")
-            let content = if line.starts_with("..") {
-                &line[1..]
+            let content = if content.starts_with(b"..") {
+                &content[1..]
             } else {
-                line
+                content
             };
 
-            data.extend_from_slice(content.as_bytes());
+            data.extend_from_slice(content);
             data.push(b'\n');
         }
 
@@ -323,6 +404,49 @@ impl Drop for NntpClient {
     }
 }
 
+/// Open a TCP connection, trying each address the host resolves to
+fn connect_tcp(host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+    let mut last_error = None;
+    for addr in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => {
+                debug!(addr = %addr, error = %e, "Failed to connect to NNTP server address");
+                last_error = Some(e);
+            }
+        }
+    }
+    match last_error {
+        Some(e) => Err(e.into()),
+        None => Err(anyhow!("No addresses found for {}", host)),
+    }
+}
+
+/// TLS settings shared by all connections, trusting the Mozilla root
+/// certificates
+fn tls_config() -> Result<Arc<ClientConfig>> {
+    static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
+    if let Some(config) = CONFIG.get() {
+        return Ok(config.clone());
+    }
+
+    let roots = RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let config =
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+    Ok(CONFIG.get_or_init(|| Arc::new(config)).clone())
+}
+
+/// A line without its trailing CRLF or LF
+fn strip_line_ending(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
 /// Normalize a message ID (ensure angle brackets)
 fn normalize_message_id(id: &str) -> String {
     if id.starts_with('<') && id.ends_with('>') {
@@ -381,4 +505,12 @@ mod tests {
         assert_eq!(resp.code, 480);
         assert!(resp.requires_auth());
     }
+
+    #[test]
+    fn test_strip_line_ending() {
+        assert_eq!(strip_line_ending(b"=ybegin part=1\r\n"), b"=ybegin part=1");
+        assert_eq!(strip_line_ending(b"data\n"), b"data");
+        assert_eq!(strip_line_ending(b"\xe9\x00\xff"), b"\xe9\x00\xff");
+        assert_eq!(strip_line_ending(b".\r\n"), b".");
+    }
 }
@@ -0,0 +1,195 @@
+//! NNTP connection pool
+//!
+//! Keeps logged-in connections to a server open between articles, up to the
+//! server's connection limit. Opening a connection costs a TCP and TLS
+//! handshake plus authentication, so reusing them (and fetching on several at
+//! once) is what lets downloads keep a fast line busy.
+//!
+//! A connection is checked out for one blocking exchange at a time. Idle
+//! connections are checked with `DATE` before reuse once they've sat for a
+//! while, and dropped once they've been idle longer than servers usually keep
+//! them open. Connections that fail mid-exchange aren't returned.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use parking_lot::Mutex;
+use tokio::sync::Semaphore;
+use tracing::debug;
+
+use super::nntp::{NntpClient, NntpConfig};
+
+/// Idle connections older than this are checked before reuse
+const HEALTH_CHECK_AFTER: Duration = Duration::from_secs(15);
+
+/// Idle connections older than this are assumed closed by the server
+const MAX_IDLE: Duration = Duration::from_secs(60);
+
+/// What to do with an idle connection before reusing it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdleAction {
+    Reuse,
+    Check,
+    Discard,
+}
+
+impl IdleAction {
+    /// Action for a connection that has been idle this long
+    pub fn for_idle(idle: Duration) -> Self {
+        if idle >= MAX_IDLE {
+            IdleAction::Discard
+        } else if idle >= HEALTH_CHECK_AFTER {
+            IdleAction::Check
+        } else {
+            IdleAction::Reuse
+        }
+    }
+}
+
+struct IdleConnection {
+    client: NntpClient,
+    idle_since: Instant,
+}
+
+/// Pool of connections to one NNTP server
+pub struct NntpPool {
+    config: NntpConfig,
+    max_connections: usize,
+    /// One permit per connection the server allows
+    permits: Arc<Semaphore>,
+    /// Connections waiting to be reused, most recently used last
+    idle: Arc<Mutex<Vec<IdleConnection>>>,
+}
+
+impl NntpPool {
+    pub fn new(config: NntpConfig, max_connections: usize) -> Self {
+        let max_connections = max_connections.max(1);
+        Self {
+            config,
+            max_connections,
+            permits: Arc::new(Semaphore::new(max_connections)),
+            idle: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn config(&self) -> &NntpConfig {
+        &self.config
+    }
+
+    pub fn max_connections(&self) -> usize {
+        self.max_connections
+    }
+
+    /// Run a blocking exchange on a pooled connection
+    ///
+    /// Waits for a free connection if the server's limit is reached. The
+    /// connection goes back to the pool afterwards unless it broke; an error
+    /// from the server, like a missing article, doesn't break it.
+    ///
+    /// The blocking task holds the permit until the connection is back in
+    /// the pool, so the server's limit holds even if the caller stops waiting.
+    pub async fn run<T, F>(&self, exchange: F) -> Result<T>
+    where
+        F: FnOnce(&mut NntpClient) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self.permits.clone().acquire_owned().await?;
+        let idle = self.idle.clone();
+        let config = self.config.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            let last_used = idle.lock().pop();
+            let mut client = checkout(last_used, config)?;
+
+            let result = exchange(&mut client);
+            if result.as_ref().is_err_and(is_connection_error) {
+                client.close();
+            } else {
+                idle.lock().push(IdleConnection {
+                    client,
+                    idle_since: Instant::now(),
+                });
+            }
+            result
+        })
+        .await?
+    }
+}
+
+impl Drop for NntpPool {
+    fn drop(&mut self) {
+        // Saying QUIT blocks, keep it off async threads
+        let idle = std::mem::take(&mut *self.idle.lock());
+        if !idle.is_empty()
+            && let Ok(handle) = tokio::runtime::Handle::try_current()
+        {
+            handle.spawn_blocking(move || drop(idle));
+        }
+    }
+}
+
+/// An idle connection ready for use, or a new one
+fn checkout(idle: Option<IdleConnection>, config: NntpConfig) -> Result<NntpClient> {
+    if let Some(IdleConnection {
+        mut client,
+        idle_since,
+    }) = idle
+    {
+        match IdleAction::for_idle(idle_since.elapsed()) {
+            IdleAction::Reuse => return Ok(client),
+            IdleAction::Check => match client.ping() {
+                Ok(()) => return Ok(client),
+                Err(e) => {
+                    debug!(host = %config.host, error = %e, "Dropping dead NNTP connection");
+                    client.close();
+                }
+            },
+            IdleAction::Discard => client.close(),
+        }
+    }
+
+    let mut client = NntpClient::new(config);
+    client.connect()?;
+    Ok(client)
+}
+
+/// Whether an error means the connection can't be used any more
+///
+/// I/O errors (including TLS errors and the server hanging up) leave the
+/// connection broken or out of step; NNTP error responses don't.
+fn is_connection_error(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| cause.is::<std::io::Error>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_action() {
+        assert_eq!(
+            IdleAction::for_idle(Duration::from_secs(1)),
+            IdleAction::Reuse
+        );
+        assert_eq!(
+            IdleAction::for_idle(Duration::from_secs(20)),
+            IdleAction::Check
+        );
+        assert_eq!(
+            IdleAction::for_idle(Duration::from_secs(300)),
+            IdleAction::Discard
+        );
+    }
+
+    #[test]
+    fn test_is_connection_error() {
+        let eof: anyhow::Error = std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into();
+        assert!(is_connection_error(&eof));
+        assert!(is_connection_error(&eof.context("BODY failed")));
+        assert!(!is_connection_error(&anyhow::anyhow!(
+            "BODY failed: 430 No such article"
+        )));
+    }
+}
@@ -0,0 +1,106 @@
+//! Retention-aware filtering of Newznab results
+//!
+//! A post older than the retention of every configured server can't be
+//! fetched: the grab would start and then fail on missing articles. Results
+//! past the best retention are dropped, and results close to it are moved to
+//! the back, since articles at the edge are often already partly expired.
+
+use chrono::{DateTime, Utc};
+
+use crate::db::UsenetServerRecord;
+use crate::indexer::ReleaseInfo;
+
+/// Releases this many days from the retention edge are tried last
+pub const RETENTION_MARGIN_DAYS: i64 = 14;
+
+/// How a release's age compares to the available retention
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RetentionFit {
+    Within,
+    /// Within [`RETENTION_MARGIN_DAYS`] of the edge
+    NearEdge,
+    Expired,
+}
+
+/// Longest retention among enabled servers, or None if none is known
+///
+/// Servers without a configured retention are ignored rather than treated as
+/// unlimited; with no known retention nothing is filtered.
+pub fn best_retention_days(servers: &[UsenetServerRecord]) -> Option<i32> {
+    servers
+        .iter()
+        .filter(|s| s.enabled)
+        .filter_map(|s| s.retention_days)
+        .filter(|days| *days > 0)
+        .max()
+}
+
+pub fn retention_fit(
+    publish_date: DateTime<Utc>,
+    retention_days: i32,
+    now: DateTime<Utc>,
+) -> RetentionFit {
+    let age_days = (now - publish_date).num_days();
+    let retention_days = i64::from(retention_days);
+    if age_days > retention_days {
+        RetentionFit::Expired
+    } else if age_days > retention_days - RETENTION_MARGIN_DAYS {
+        RetentionFit::NearEdge
+    } else {
+        RetentionFit::Within
+    }
+}
+
+/// Drop releases past retention and move those near the edge to the end
+/// (keeping the indexer's order otherwise), returning how many were dropped
+pub fn apply_retention(
+    releases: &mut Vec<ReleaseInfo>,
+    retention_days: i32,
+    now: DateTime<Utc>,
+) -> usize {
+    let before = releases.len();
+    releases
+        .retain(|r| retention_fit(r.publish_date, retention_days, now) != RetentionFit::Expired);
+    releases.sort_by_key(|r| retention_fit(r.publish_date, retention_days, now));
+    before - releases.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    fn release(title: &str, age_days: i64, now: DateTime<Utc>) -> ReleaseInfo {
+        ReleaseInfo {
+            title: title.to_string(),
+            publish_date: now - Duration::days(age_days),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_retention_fit() {
+        let now = Utc::now();
+        let fit = |age| retention_fit(now - Duration::days(age), 1000, now);
+        assert_eq!(fit(10), RetentionFit::Within);
+        assert_eq!(fit(990), RetentionFit::NearEdge);
+        assert_eq!(fit(1000), RetentionFit::NearEdge);
+        assert_eq!(fit(1001), RetentionFit::Expired);
+    }
+
+    #[test]
+    fn test_apply_retention() {
+        let now = Utc::now();
+        let mut releases = vec![
+            release("edge", 995, now),
+            release("old", 1500, now),
+            release("new", 3, now),
+            release("recent", 30, now),
+        ];
+
+        assert_eq!(apply_retention(&mut releases, 1000, now), 1);
+        let titles: Vec<_> = releases.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, ["new", "recent", "edge"]);
+    }
+}
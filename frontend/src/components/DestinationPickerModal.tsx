@@ -13,7 +13,7 @@ import {
 } from '../lib/graphql'
 import { IconFolder, IconFolderPlus } from '@tabler/icons-react'
 import { InlineError } from './shared'
-import { sanitizeError } from '../lib/format'
+import { joinPath, sanitizeError } from '../lib/format'
 
 interface DestinationPickerModalProps {
   /** Whether the modal is open */
@@ -85,7 +85,7 @@ export function DestinationPickerModal({
     if (!newFolderName.trim()) return
 
     setIsCreatingFolder(true)
-    const newPath = `${currentPath}/${newFolderName.trim()}`
+    const newPath = joinPath(currentPath, newFolderName.trim())
 
     try {
       const result = await createDirectory(newPath)
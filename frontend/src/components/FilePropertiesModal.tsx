@@ -33,6 +33,7 @@ import {
   type ChapterInfo,
   type EmbeddedMetadata,
 } from '../lib/graphql'
+import { fileNameFromPath } from '../lib/format'
 
 interface FilePropertiesModalProps {
   isOpen: boolean
@@ -208,7 +209,7 @@ export function FilePropertiesModal({
   }
 
   const file = details?.file
-  const filename = file?.path.split('/').pop() || file?.originalName || 'Unknown'
+  const filename = (file?.path && fileNameFromPath(file.path)) || file?.originalName || 'Unknown'
 
   return (
     <Modal 
@@ -565,7 +566,7 @@ function SubtitleCard({ subtitle }: { subtitle: SubtitleInfo }) {
       <div className="text-xs">
         {subtitle.title && <PropertyRow label="Title" value={subtitle.title} />}
         {subtitle.filePath && (
-          <PropertyRow label="File" value={subtitle.filePath.split('/').pop()} />
+          <PropertyRow label="File" value={fileNameFromPath(subtitle.filePath)} />
         )}
       </div>
     </StreamCard>
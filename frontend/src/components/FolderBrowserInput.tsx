@@ -13,7 +13,7 @@ import {
 } from '../lib/graphql'
 import { IconFolder, IconFile } from '@tabler/icons-react'
 import { InlineError } from './shared'
-import { sanitizeError } from '../lib/format'
+import { joinPath, sanitizeError } from '../lib/format'
 
 interface FolderBrowserInputProps {
   /** Current folder path value */
@@ -105,7 +105,7 @@ export function FolderBrowserInput({
     if (!newFolderName.trim()) return
     
     setIsCreatingFolder(true)
-    const newPath = `${currentPath}/${newFolderName.trim()}`
+    const newPath = joinPath(currentPath, newFolderName.trim())
     
     try {
       const result = await createDirectory(newPath)
@@ -52,7 +52,7 @@ const navItems = [
 
 export function Navbar() {
   const { user, signOut, loading, error } = useAuth();
-  const { isDark, toggleTheme } = useTheme();
+  const { isDark, toggleTheme } = useTheme(user?.id);
   const [isMenuOpen, setIsMenuOpen] = useState(false);
   const [activeDownloadCount, setActiveDownloadCount] = useState(0);
   const [unreadNotificationCount, setUnreadNotificationCount] = useState(0);
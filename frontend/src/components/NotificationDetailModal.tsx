@@ -100,6 +100,21 @@ const CATEGORY_INFO: Record<
     settingsRoute: "/settings",
     description: "System configuration issues that need attention",
   },
+  SCHEDULE: {
+    label: "Schedule",
+    settingsRoute: "/settings",
+    description: "Reminders for upcoming episode air times",
+  },
+  UPDATE: {
+    label: "Update",
+    settingsRoute: "/settings/system",
+    description: "A new Librarian release is available",
+  },
+  SERVICES: {
+    label: "Services",
+    settingsRoute: "/settings/system",
+    description: "External services going down or recovering",
+  },
 };
 
 // Get specific resolve actions based on notification content
@@ -7,6 +7,7 @@
  */
 
 import { useState, useRef, useEffect, useCallback } from 'react';
+import Hls from 'hls.js';
 import { Button } from '@heroui/button';
 import { Spinner } from '@heroui/spinner';
 import {
@@ -19,7 +20,7 @@ import {
 import { usePlaybackContext } from '../contexts/PlaybackContext';
 import { CastButton } from './cast';
 import { VolumeControl } from './VolumeControl';
-import { getMediaStreamUrl } from './VideoPlayer';
+import { getMediaStreamUrl, getMediaHlsUrl } from './VideoPlayer';
 import { graphqlClient, TV_SHOW_QUERY, EPISODES_QUERY, PLAYBACK_SETTINGS_QUERY, type TvShow, type Episode, type PlaybackSettings } from '../lib/graphql';
 import { useCast } from '../hooks/useCast';
 
@@ -61,6 +62,8 @@ export function PersistentPlayer() {
   const [videoReady, setVideoReady] = useState(false);
   const [syncInterval, setSyncInterval] = useState(DEFAULT_SYNC_INTERVAL);
   const [isPaused, setIsPaused] = useState(true);
+  // Set when the browser can't play the file directly
+  const [useTranscode, setUseTranscode] = useState(false);
   
   const videoRef = useRef<HTMLVideoElement>(null);
   const syncIntervalRef = useRef<NodeJS.Timeout | null>(null);
@@ -111,6 +114,38 @@ export function PersistentPlayer() {
     }
   }, [session?.tvShowId, session?.episodeId, currentShow, setCurrentShow, setCurrentEpisode]);
 
+  // Every file starts with direct play
+  useEffect(() => {
+    setUseTranscode(false);
+  }, [session?.mediaFileId]);
+
+  // Play the server's HLS transcode (natively in Safari, via hls.js elsewhere)
+  useEffect(() => {
+    const video = videoRef.current;
+    if (!useTranscode || !video || !session?.mediaFileId) return;
+
+    const src = getMediaHlsUrl(session.mediaFileId);
+    if (Hls.isSupported()) {
+      const hls = new Hls({ enableWorker: true });
+      hls.loadSource(src);
+      hls.attachMedia(video);
+      hls.on(Hls.Events.ERROR, (_event, data) => {
+        if (data.fatal) setVideoReady(false);
+      });
+      return () => hls.destroy();
+    }
+    video.src = src;
+  }, [useTranscode, session?.mediaFileId]);
+
+  const handleVideoError = useCallback(() => {
+    // Unsupported container or codec: fall back to a transcode
+    if (!useTranscode && videoRef.current?.error?.code === MediaError.MEDIA_ERR_SRC_NOT_SUPPORTED) {
+      setUseTranscode(true);
+      return;
+    }
+    setVideoReady(false);
+  }, [useTranscode]);
+
   // Resume from session position
   useEffect(() => {
     if (!isVideoSession) return;
@@ -414,7 +449,7 @@ export function PersistentPlayer() {
             {/* Video element - SINGLE instance */}
             <video
               ref={videoRef}
-              src={getMediaStreamUrl(session.mediaFileId!)}
+              src={useTranscode ? undefined : getMediaStreamUrl(session.mediaFileId!)}
               className={`w-full ${isExpanded ? 'h-full object-contain' : 'h-40 object-cover cursor-pointer'}`}
               onTimeUpdate={handleTimeUpdate}
               onLoadedMetadata={handleLoadedMetadata}
@@ -422,7 +457,7 @@ export function PersistentPlayer() {
               onPlay={handlePlay}
               onPause={handlePause}
               onEnded={handleStop}
-              onError={() => setVideoReady(false)}
+              onError={handleVideoError}
               onClick={isExpanded ? undefined : () => setIsExpanded(true)}
               playsInline
               muted={isMuted}
@@ -0,0 +1,34 @@
+import { useEffect } from 'react'
+import { addToast } from '@heroui/toast'
+import { Button } from '@heroui/button'
+import { onSchemaVersionChange } from '../lib/graphql'
+
+/**
+ * Component that prompts a refresh when the server's GraphQL schema changes
+ * mid-session (i.e. the server was upgraded and this page is out of date).
+ * Mount this once at the app level.
+ */
+export function SchemaUpdateToaster() {
+  useEffect(() => {
+    const unsubscribe = onSchemaVersionChange(() => {
+      addToast({
+        title: 'Server updated',
+        description: 'A new version of Librarian is running. Refresh to load it.',
+        color: 'primary',
+        timeout: Infinity,
+        endContent: (
+          <Button size="sm" color="primary" variant="flat" onPress={() => window.location.reload()}>
+            Refresh
+          </Button>
+        ),
+      })
+    })
+
+    return () => {
+      unsubscribe()
+    }
+  }, [])
+
+  // This component doesn't render anything
+  return null
+}
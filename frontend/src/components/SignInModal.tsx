@@ -12,7 +12,12 @@ import { Spinner } from "@heroui/spinner";
 import { addToast } from "@heroui/toast";
 import { IconShieldCheck, IconAlertCircle } from "@tabler/icons-react";
 import { useAuth } from "../hooks/useAuth";
-import { graphqlClient, NEEDS_SETUP_QUERY } from "../lib/graphql";
+import {
+  graphqlClient,
+  NEEDS_SETUP_QUERY,
+  REQUEST_PASSWORD_RESET_MUTATION,
+  type AuthMutationResult,
+} from "../lib/graphql";
 
 interface SignInModalProps {
   isOpen: boolean;
@@ -29,6 +34,8 @@ export function SignInModal({
 }: SignInModalProps) {
   const { signIn, signUp } = useAuth();
   const [isSignUp, setIsSignUp] = useState(false);
+  const [isForgot, setIsForgot] = useState(false);
+  const [resetSent, setResetSent] = useState(false);
   const [needsSetup, setNeedsSetup] = useState<boolean | null>(null);
   const [checkingSetup, setCheckingSetup] = useState(true);
 
@@ -75,7 +82,29 @@ export function SignInModal({
     setLoading(true);
 
     try {
-      if (isSignUp) {
+      if (isForgot) {
+        if (!email.trim()) {
+          setError("Email is required");
+          setLoading(false);
+          return;
+        }
+
+        const result = await graphqlClient
+          .mutation<{
+            requestPasswordReset: AuthMutationResult;
+          }>(REQUEST_PASSWORD_RESET_MUTATION, { email: email.trim() })
+          .toPromise();
+        const payload = result.data?.requestPasswordReset;
+        if (payload?.success) {
+          setResetSent(true);
+        } else {
+          setError(
+            payload?.error ??
+              result.error?.message ??
+              "Failed to request a password reset",
+          );
+        }
+      } else if (isSignUp) {
         // Validate email is provided
         if (!email.trim()) {
           setError("Email is required");
@@ -90,9 +119,9 @@ export function SignInModal({
           return;
         }
 
-        // Validate password length
-        if (password.length < 6) {
-          setError("Password must be at least 6 characters");
+        // Validate password length (the server checks the other rules)
+        if (password.length < 8) {
+          setError("Password must be at least 8 characters");
           setLoading(false);
           return;
         }
@@ -145,6 +174,8 @@ export function SignInModal({
     setPassword("");
     setError("");
     setIsSignUp(false);
+    setIsForgot(false);
+    setResetSent(false);
     setNeedsSetup(null);
     setCheckingSetup(true);
     onClose();
@@ -175,9 +206,11 @@ export function SignInModal({
   const showAdminSetup = needsSetup && isSignUp;
   const heading = showAdminSetup
     ? "Create Admin Account"
-    : isSignUp
-      ? "Sign Up"
-      : "Sign In";
+    : isForgot
+      ? "Reset Password"
+      : isSignUp
+        ? "Sign Up"
+        : "Sign In";
 
   return (
     <Modal
@@ -217,15 +250,39 @@ export function SignInModal({
             </div>
           ) : (
             <p className="text-small text-default-500 font-normal">
-              {isSignUp
-                ? "Create your account to get started"
-                : "Welcome back! Sign in to continue"}
+              {isForgot
+                ? "We'll email you a link to choose a new password"
+                : isSignUp
+                  ? "Create your account to get started"
+                  : "Welcome back! Sign in to continue"}
             </p>
           )}
         </ModalHeader>
         <ModalBody>
           <form onSubmit={handleSubmit} className="flex flex-col gap-4">
-            {isSignUp ? (
+            {isForgot ? (
+              resetSent ? (
+                <p className="text-sm text-default-500 text-center">
+                  If an account uses {email.trim()}, a reset link is on its
+                  way. It works for one hour.
+                </p>
+              ) : (
+                <Input
+                  type="email"
+                  label="Email"
+                  placeholder="you@example.com"
+                  value={email}
+                  onChange={(e) => setEmail(e.target.value)}
+                  isRequired
+                  autoComplete="email"
+                  variant="flat"
+                  classNames={{
+                    inputWrapper: "bg-default-100",
+                    input: "text-foreground",
+                  }}
+                />
+              )
+            ) : isSignUp ? (
               <>
                 {/* Sign Up form */}
                 <Input
@@ -266,7 +323,7 @@ export function SignInModal({
                     value={password}
                     onChange={(e) => setPassword(e.target.value)}
                     isRequired
-                    minLength={6}
+                    minLength={8}
                     autoComplete="new-password"
                     variant="flat"
                     classNames={{
@@ -275,7 +332,8 @@ export function SignInModal({
                     }}
                   />
                   <p className="text-tiny text-default-400 pl-1">
-                    Minimum 6 characters
+                    At least 8 characters, mixing upper and lower case, digits
+                    or symbols (or 16+ characters)
                   </p>
                 </div>
               </>
@@ -312,6 +370,17 @@ export function SignInModal({
                     input: "text-foreground",
                   }}
                 />
+
+                <button
+                  type="button"
+                  className="text-small text-primary self-end -mt-2"
+                  onClick={() => {
+                    setIsForgot(true);
+                    setError("");
+                  }}
+                >
+                  Forgot password?
+                </button>
               </>
             )}
 
@@ -325,19 +394,23 @@ export function SignInModal({
               </div>
             )}
 
-            <Button
-              type="submit"
-              color="primary"
-              size="lg"
-              isLoading={loading}
-              className="w-full font-semibold"
-            >
-              {showAdminSetup
-                ? "Create Admin Account"
-                : isSignUp
-                  ? "Create Account"
-                  : "Sign In"}
-            </Button>
+            {!(isForgot && resetSent) && (
+              <Button
+                type="submit"
+                color="primary"
+                size="lg"
+                isLoading={loading}
+                className="w-full font-semibold"
+              >
+                {showAdminSetup
+                  ? "Create Admin Account"
+                  : isForgot
+                    ? "Send Reset Link"
+                    : isSignUp
+                      ? "Create Account"
+                      : "Sign In"}
+              </Button>
+            )}
           </form>
         </ModalBody>
 
@@ -348,13 +421,20 @@ export function SignInModal({
               variant="light"
               color="primary"
               onPress={() => {
-                setIsSignUp(!isSignUp);
+                if (isForgot) {
+                  setIsForgot(false);
+                  setResetSent(false);
+                } else {
+                  setIsSignUp(!isSignUp);
+                }
                 setError("");
               }}
             >
-              {isSignUp
-                ? "Already have an account? Sign in"
-                : "Don't have an account? Sign up"}
+              {isForgot
+                ? "Back to sign in"
+                : isSignUp
+                  ? "Already have an account? Sign in"
+                  : "Don't have an account? Sign up"}
             </Button>
           </ModalFooter>
         )}
@@ -131,3 +131,12 @@ export function getMediaStreamUrl(mediaFileId: string): string {
   const apiUrl = import.meta.env.VITE_API_URL || '';
   return `${apiUrl}/api/media/${mediaFileId}/stream`;
 }
+
+/**
+ * Helper to generate the HLS transcode URL for a media file, for formats the
+ * browser can't play directly. Each request starts a new transcode session.
+ */
+export function getMediaHlsUrl(mediaFileId: string): string {
+  const apiUrl = import.meta.env.VITE_API_URL || '';
+  return `${apiUrl}/api/media/${mediaFileId}/hls.m3u8`;
+}
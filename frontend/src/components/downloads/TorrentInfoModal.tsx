@@ -10,15 +10,18 @@ import { addToast } from '@heroui/toast'
 import {
   graphqlClient,
   TORRENT_DETAILS_QUERY,
+  TORRENT_FILE_PROGRESS_SUBSCRIPTION,
   PENDING_FILE_MATCHES_QUERY,
   REMOVE_MATCH_MUTATION,
   type TorrentDetails,
   type TorrentFileInfo,
+  type TorrentFileProgressEvent,
   type PendingFileMatch,
   type RemoveMatchResult,
 } from '../../lib/graphql'
-import { formatBytes, sanitizeError } from '../../lib/format'
+import { fileNameFromPath, formatBytes, sanitizeError } from '../../lib/format'
 import { TORRENT_STATE_INFO } from './TorrentCard'
+import { TransferSparkline } from './TransferSparkline'
 import { DataTable, type DataTableColumn } from '../data-table'
 import { ErrorState } from '../shared'
 import { IconCheck, IconArrowDown, IconArrowUp, IconFolder, IconLink, IconX, IconTrash, IconCopy } from '@tabler/icons-react'
@@ -111,7 +114,7 @@ function createFileColumns(
       key: 'path',
       label: 'File',
       render: (file) => {
-        const fileName = file.path.split('/').pop() || file.path
+        const fileName = fileNameFromPath(file.path)
         const directory = file.path.includes('/') 
           ? file.path.substring(0, file.path.lastIndexOf('/'))
           : null
@@ -275,6 +278,39 @@ export function TorrentInfoModal({ torrentId, isOpen, onClose }: TorrentInfoModa
     }
   }, [isOpen, torrentId])
 
+  // Live per-file progress while the modal is open
+  useEffect(() => {
+    if (!isOpen || torrentId === null) return
+
+    const sub = graphqlClient
+      .subscription<{ torrentFileProgress: TorrentFileProgressEvent }>(
+        TORRENT_FILE_PROGRESS_SUBSCRIPTION,
+        { torrentId }
+      )
+      .subscribe({
+        next: (result) => {
+          const event = result.data?.torrentFileProgress
+          if (!event) return
+          const changed = new Map(event.files.map((f) => [f.index, f]))
+          setDetails((prev) =>
+            prev
+              ? {
+                  ...prev,
+                  files: prev.files.map((f) => {
+                    const update = changed.get(f.index)
+                    return update
+                      ? { ...f, downloaded: update.downloaded, progress: update.progress }
+                      : f
+                  }),
+                }
+              : prev
+          )
+        },
+      })
+
+    return () => sub.unsubscribe()
+  }, [isOpen, torrentId])
+
   return (
     <Modal 
       isOpen={isOpen} 
@@ -398,6 +434,8 @@ export function TorrentInfoModal({ torrentId, isOpen, onClose }: TorrentInfoModa
                 />
               </div>
 
+              <TransferSparkline torrentId={torrentId} />
+
               {/* Detailed Stats Row */}
               <div className="grid grid-cols-3 lg:grid-cols-6 gap-3 text-sm">
                 <MiniStat label="Downloaded" value={details.downloadedFormatted} />
@@ -0,0 +1,110 @@
+import { useState, useEffect } from 'react'
+import { IconArrowDown, IconArrowUp } from '@tabler/icons-react'
+import {
+  graphqlClient,
+  TRANSFER_RATE_HISTORY_QUERY,
+  type TransferRateHistory,
+} from '../../lib/graphql'
+import { formatBytes } from '../../lib/format'
+
+export interface TransferSparklineProps {
+  /** Torrent to graph; omit for the totals across all torrents */
+  torrentId?: number | null
+  /** How many minutes of history to show */
+  minutes?: number
+  className?: string
+}
+
+const WIDTH = 240
+const HEIGHT = 40
+
+/** SVG polyline points for a series, scaled to the shared maximum */
+function sparklinePoints(values: number[], max: number): string {
+  if (values.length === 0) return ''
+  const step = values.length > 1 ? WIDTH / (values.length - 1) : 0
+  return values
+    .map((value, i) => {
+      const y = HEIGHT - (max > 0 ? (value / max) * (HEIGHT - 2) : 0) - 1
+      return `${(i * step).toFixed(1)},${y.toFixed(1)}`
+    })
+    .join(' ')
+}
+
+/**
+ * Download and upload rate over the last few minutes
+ *
+ * Refetches at the server's sampling interval.
+ */
+export function TransferSparkline({ torrentId = null, minutes = 10, className }: TransferSparklineProps) {
+  const [history, setHistory] = useState<TransferRateHistory | null>(null)
+
+  useEffect(() => {
+    let cancelled = false
+    let timer: ReturnType<typeof setTimeout> | undefined
+
+    const fetchHistory = async () => {
+      const result = await graphqlClient
+        .query<{ transferRateHistory: TransferRateHistory }>(
+          TRANSFER_RATE_HISTORY_QUERY,
+          { torrentId, minutes },
+          { requestPolicy: 'network-only' }
+        )
+        .toPromise()
+      if (cancelled) return
+      if (result.data?.transferRateHistory) {
+        setHistory(result.data.transferRateHistory)
+      }
+      const interval = result.data?.transferRateHistory.intervalSecs ?? 5
+      timer = setTimeout(fetchHistory, interval * 1000)
+    }
+
+    fetchHistory()
+    return () => {
+      cancelled = true
+      if (timer) clearTimeout(timer)
+    }
+  }, [torrentId, minutes])
+
+  if (!history || history.timestamps.length < 2) return null
+
+  const max = Math.max(...history.downloadSpeeds, ...history.uploadSpeeds)
+  const latestDown = history.downloadSpeeds[history.downloadSpeeds.length - 1]
+  const latestUp = history.uploadSpeeds[history.uploadSpeeds.length - 1]
+
+  return (
+    <div className={`flex items-center gap-3 ${className ?? ''}`}>
+      <svg
+        viewBox={`0 0 ${WIDTH} ${HEIGHT}`}
+        preserveAspectRatio="none"
+        className="h-10 w-60 shrink-0"
+        role="img"
+        aria-label={`Transfer rate over the last ${minutes} minutes`}
+      >
+        <polyline
+          points={sparklinePoints(history.uploadSpeeds, max)}
+          fill="none"
+          className="stroke-green-400"
+          strokeWidth={1.5}
+          vectorEffect="non-scaling-stroke"
+        />
+        <polyline
+          points={sparklinePoints(history.downloadSpeeds, max)}
+          fill="none"
+          className="stroke-blue-400"
+          strokeWidth={1.5}
+          vectorEffect="non-scaling-stroke"
+        />
+      </svg>
+      <div className="flex flex-col text-xs tabular-nums text-default-500">
+        <span className="flex items-center gap-1">
+          <IconArrowDown size={12} className="text-blue-400" />
+          {formatBytes(latestDown)}/s
+        </span>
+        <span className="flex items-center gap-1">
+          <IconArrowUp size={12} className="text-green-400" />
+          {formatBytes(latestUp)}/s
+        </span>
+      </div>
+    </div>
+  )
+}
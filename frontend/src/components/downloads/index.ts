@@ -3,3 +3,4 @@ export { TorrentTable, type TorrentTableProps } from './TorrentTable'
 export { AddTorrentModal, type AddTorrentModalProps } from './AddTorrentModal'
 export { TorrentInfoModal } from './TorrentInfoModal'
 export { LinkToLibraryModal, type LinkToLibraryModalProps } from './LinkToLibraryModal'
+export { TransferSparkline, type TransferSparklineProps } from './TransferSparkline'
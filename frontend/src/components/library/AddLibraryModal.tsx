@@ -1,7 +1,7 @@
 import { useState, useCallback } from 'react'
 import { Button } from '@heroui/button'
 import { Modal, ModalContent, ModalHeader, ModalBody, ModalFooter } from '@heroui/modal'
-import { LibrarySettingsForm, DEFAULT_LIBRARY_SETTINGS, type LibrarySettingsValues } from './LibrarySettingsForm'
+import { LibrarySettingsForm, DEFAULT_LIBRARY_SETTINGS, cleanExcludePatterns, parseOwnerId, type LibrarySettingsValues } from './LibrarySettingsForm'
 import type { CreateLibraryInput } from '../../lib/graphql'
 
 export interface AddLibraryModalProps {
@@ -42,6 +42,11 @@ export function AddLibraryModal({ isOpen, onClose, onAdd, isLoading }: AddLibrar
       allowedSources: values.allowedSources,
       releaseGroupBlacklist: values.releaseGroupBlacklist,
       releaseGroupWhitelist: values.releaseGroupWhitelist,
+      excludePatterns: cleanExcludePatterns(values.excludePatterns),
+      fileUid: parseOwnerId(values.fileUid) ?? undefined,
+      fileGid: parseOwnerId(values.fileGid) ?? undefined,
+      fileMode: values.fileMode.trim() || undefined,
+      missingFilePolicy: values.missingFilePolicy,
     })
     
     // Reset form
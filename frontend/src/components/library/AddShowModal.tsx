@@ -79,6 +79,7 @@ export function AddShowModal({
             success: boolean
             tvShow: TvShow | null
             error: string | null
+            missingMetadata: string[]
           }
         }>(ADD_TV_SHOW_MUTATION, {
           libraryId,
@@ -99,11 +100,20 @@ export function AddShowModal({
         return
       }
 
-      addToast({
-        title: 'Success',
-        description: `Added "${selectedShow.name}" to library`,
-        color: 'success',
-      })
+      const { missingMetadata } = data.addTvShow
+      if (missingMetadata.length > 0) {
+        addToast({
+          title: 'Added with incomplete metadata',
+          description: `Added "${selectedShow.name}", but some details (${missingMetadata.join(', ')}) couldn't be fetched. They'll be retried in the background.`,
+          color: 'warning',
+        })
+      } else {
+        addToast({
+          title: 'Success',
+          description: `Added "${selectedShow.name}" to library`,
+          color: 'success',
+        })
+      }
 
       // Reset and close
       setSearchQuery('')
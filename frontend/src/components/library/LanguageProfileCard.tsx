@@ -0,0 +1,291 @@
+import { useState, useEffect, useCallback } from 'react'
+import { Card, CardBody, CardHeader } from '@heroui/card'
+import { Input } from '@heroui/input'
+import { Button } from '@heroui/button'
+import { Select, SelectItem } from '@heroui/select'
+import { Spinner } from '@heroui/spinner'
+import { Switch } from '@heroui/switch'
+import { addToast } from '@heroui/toast'
+import { IconAlertTriangle, IconPlus, IconTrash } from '@tabler/icons-react'
+import {
+  graphqlClient,
+  LANGUAGE_PROFILES_QUERY,
+  LIBRARY_LANGUAGE_PROFILE_QUERY,
+  LANGUAGE_MISMATCHES_QUERY,
+  CREATE_LANGUAGE_PROFILE_MUTATION,
+  DELETE_LANGUAGE_PROFILE_MUTATION,
+  SET_LIBRARY_LANGUAGE_PROFILE_MUTATION,
+  type LanguageProfile,
+  type LanguageMismatch,
+  type LanguageProfileResult,
+} from '../../lib/graphql'
+import { sanitizeError } from '../../lib/format'
+
+export interface LanguageProfileCardProps {
+  libraryId: string
+}
+
+const NONE_KEY = '__none'
+
+/** "en, fr" → ['en', 'fr'] */
+function parseLanguages(value: string): string[] {
+  return value
+    .split(',')
+    .map((s) => s.trim())
+    .filter(Boolean)
+}
+
+function describeProfile(profile: LanguageProfile): string {
+  const parts = []
+  if (profile.preferredAudioLanguages.length > 0) {
+    parts.push(`Audio: ${profile.preferredAudioLanguages.join(', ')}`)
+  }
+  if (profile.requiredAudioLanguages.length > 0) {
+    parts.push(`Requires: ${profile.requiredAudioLanguages.join(', ')}`)
+  }
+  if (profile.requiredSubtitleLanguages.length > 0) {
+    parts.push(`Subtitles: ${profile.requiredSubtitleLanguages.join(', ')}`)
+  }
+  if (profile.regrabMissingAudio) {
+    parts.push('Re-grabs mislabeled releases')
+  }
+  return parts.join(' · ') || 'No rules'
+}
+
+/**
+ * Language profile of a library, plus managing the user's profiles
+ *
+ * Shows can override the library's profile from their own settings.
+ */
+export function LanguageProfileCard({ libraryId }: LanguageProfileCardProps) {
+  const [profiles, setProfiles] = useState<LanguageProfile[]>([])
+  const [assignedId, setAssignedId] = useState<string | null>(null)
+  const [mismatches, setMismatches] = useState<LanguageMismatch[]>([])
+  const [loading, setLoading] = useState(true)
+  const [saving, setSaving] = useState(false)
+
+  const [name, setName] = useState('')
+  const [preferredAudio, setPreferredAudio] = useState('')
+  const [requiredAudio, setRequiredAudio] = useState('')
+  const [requiredSubtitles, setRequiredSubtitles] = useState('')
+  const [regrabMissingAudio, setRegrabMissingAudio] = useState(false)
+
+  const fetchProfiles = useCallback(async () => {
+    const [profilesResult, assignedResult, mismatchesResult] = await Promise.all([
+      graphqlClient
+        .query<{ languageProfiles: LanguageProfile[] }>(
+          LANGUAGE_PROFILES_QUERY,
+          {},
+          { requestPolicy: 'network-only' }
+        )
+        .toPromise(),
+      graphqlClient
+        .query<{ libraryLanguageProfile: Pick<LanguageProfile, 'id' | 'name'> | null }>(
+          LIBRARY_LANGUAGE_PROFILE_QUERY,
+          { libraryId },
+          { requestPolicy: 'network-only' }
+        )
+        .toPromise(),
+      graphqlClient
+        .query<{ languageMismatches: LanguageMismatch[] }>(
+          LANGUAGE_MISMATCHES_QUERY,
+          { libraryId },
+          { requestPolicy: 'network-only' }
+        )
+        .toPromise(),
+    ])
+    setProfiles(profilesResult.data?.languageProfiles ?? [])
+    setAssignedId(assignedResult.data?.libraryLanguageProfile?.id ?? null)
+    setMismatches(mismatchesResult.data?.languageMismatches ?? [])
+    setLoading(false)
+  }, [libraryId])
+
+  useEffect(() => {
+    fetchProfiles()
+  }, [fetchProfiles])
+
+  const handleAssign = async (profileId: string | null) => {
+    const previous = assignedId
+    setAssignedId(profileId)
+    const result = await graphqlClient
+      .mutation<{ setLibraryLanguageProfile: { success: boolean; error: string | null } }>(
+        SET_LIBRARY_LANGUAGE_PROFILE_MUTATION,
+        { libraryId, profileId }
+      )
+      .toPromise()
+    if (!result.data?.setLibraryLanguageProfile.success) {
+      setAssignedId(previous)
+      addToast({
+        title: 'Error',
+        description: result.data?.setLibraryLanguageProfile.error || sanitizeError(result.error),
+        color: 'danger',
+      })
+    }
+  }
+
+  const handleCreate = async () => {
+    setSaving(true)
+    const result = await graphqlClient
+      .mutation<{ createLanguageProfile: LanguageProfileResult }>(CREATE_LANGUAGE_PROFILE_MUTATION, {
+        input: {
+          name,
+          preferredAudioLanguages: parseLanguages(preferredAudio),
+          requiredAudioLanguages: parseLanguages(requiredAudio),
+          requiredSubtitleLanguages: parseLanguages(requiredSubtitles),
+          regrabMissingAudio,
+        },
+      })
+      .toPromise()
+    setSaving(false)
+    const data = result.data?.createLanguageProfile
+    if (data?.success) {
+      setName('')
+      setPreferredAudio('')
+      setRequiredAudio('')
+      setRequiredSubtitles('')
+      setRegrabMissingAudio(false)
+      fetchProfiles()
+    } else {
+      addToast({ title: 'Error', description: data?.error || sanitizeError(result.error), color: 'danger' })
+    }
+  }
+
+  const handleDelete = async (profile: LanguageProfile) => {
+    const result = await graphqlClient
+      .mutation<{ deleteLanguageProfile: { success: boolean; error: string | null } }>(
+        DELETE_LANGUAGE_PROFILE_MUTATION,
+        { id: profile.id }
+      )
+      .toPromise()
+    if (result.data?.deleteLanguageProfile.success) {
+      addToast({ title: 'Deleted', description: `Removed ${profile.name}`, color: 'success' })
+      fetchProfiles()
+    } else {
+      addToast({
+        title: 'Error',
+        description: result.data?.deleteLanguageProfile.error || sanitizeError(result.error),
+        color: 'danger',
+      })
+    }
+  }
+
+  return (
+    <Card>
+      <CardHeader>
+        <h3 className="text-lg font-semibold">Language profile</h3>
+      </CardHeader>
+      <CardBody className="flex flex-col gap-4">
+        {loading ? (
+          <div className="flex justify-center py-4">
+            <Spinner />
+          </div>
+        ) : (
+          <>
+            <Select
+              label="Profile for this library"
+              size="sm"
+              className="max-w-xs"
+              selectedKeys={[assignedId ?? NONE_KEY]}
+              onSelectionChange={(keys) => {
+                const key = Array.from(keys)[0]?.toString()
+                if (!key) return
+                handleAssign(key === NONE_KEY ? null : key)
+              }}
+              description="Used to rank releases and check imported files"
+            >
+              {[
+                <SelectItem key={NONE_KEY}>None</SelectItem>,
+                ...profiles.map((profile) => (
+                  <SelectItem key={profile.id} textValue={profile.name}>
+                    {profile.name}
+                  </SelectItem>
+                )),
+              ]}
+            </Select>
+
+            {mismatches.length > 0 && (
+              <div className="flex flex-col gap-2 rounded-lg bg-warning-50 p-3">
+                <p className="flex items-center gap-2 text-sm font-medium">
+                  <IconAlertTriangle size={16} className="text-warning shrink-0" />
+                  {mismatches.length} {mismatches.length === 1 ? 'file is' : 'files are'} missing required audio
+                </p>
+                <ul className="flex flex-col gap-1">
+                  {mismatches.map((mismatch) => (
+                    <li key={mismatch.mediaFileId} className="text-xs text-default-600 min-w-0">
+                      <span className="font-medium break-all">{mismatch.fileName}</span>
+                      {' · no '}
+                      {mismatch.missingAudioLanguages.join(', ')}
+                      {mismatch.regrabPending && ' · re-grab pending'}
+                    </li>
+                  ))}
+                </ul>
+              </div>
+            )}
+
+            {profiles.length > 0 && (
+              <ul className="flex flex-col gap-2">
+                {profiles.map((profile) => (
+                  <li key={profile.id} className="flex items-center justify-between gap-2">
+                    <div className="min-w-0">
+                      <p className="text-sm font-medium">{profile.name}</p>
+                      <p className="text-xs text-default-500 truncate">{describeProfile(profile)}</p>
+                    </div>
+                    <Button
+                      isIconOnly
+                      size="sm"
+                      variant="light"
+                      color="danger"
+                      aria-label={`Delete ${profile.name}`}
+                      onPress={() => handleDelete(profile)}
+                    >
+                      <IconTrash size={16} />
+                    </Button>
+                  </li>
+                ))}
+              </ul>
+            )}
+
+            <div className="grid grid-cols-1 md:grid-cols-2 gap-2">
+              <Input label="New profile name" size="sm" value={name} onValueChange={setName} />
+              <Input
+                label="Preferred audio"
+                size="sm"
+                value={preferredAudio}
+                onValueChange={setPreferredAudio}
+                description="In order, e.g. fr, en"
+              />
+              <Input
+                label="Required audio"
+                size="sm"
+                value={requiredAudio}
+                onValueChange={setRequiredAudio}
+                description="Reject releases without these"
+              />
+              <Input
+                label="Required subtitles"
+                size="sm"
+                value={requiredSubtitles}
+                onValueChange={setRequiredSubtitles}
+                description="Downloaded after import if missing"
+              />
+            </div>
+            <Switch size="sm" isSelected={regrabMissingAudio} onValueChange={setRegrabMissingAudio}>
+              <span className="text-sm">Re-grab when an imported file lacks a required audio language</span>
+            </Switch>
+            <Button
+              size="sm"
+              color="primary"
+              className="self-start"
+              startContent={<IconPlus size={16} />}
+              onPress={handleCreate}
+              isLoading={saving}
+              isDisabled={!name.trim()}
+            >
+              Create profile
+            </Button>
+          </>
+        )}
+      </CardBody>
+    </Card>
+  )
+}
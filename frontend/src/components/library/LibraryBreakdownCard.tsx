@@ -0,0 +1,96 @@
+import { useState, useEffect } from 'react'
+import { Card, CardBody, CardHeader } from '@heroui/card'
+import { Progress } from '@heroui/progress'
+import { Spinner } from '@heroui/spinner'
+import {
+  graphqlClient,
+  LIBRARY_BREAKDOWN_QUERY,
+  type LibraryBreakdown,
+  type LibraryBreakdownEntry,
+} from '../../lib/graphql'
+import { formatBytes } from '../../lib/format'
+
+export interface LibraryBreakdownCardProps {
+  libraryId: string
+}
+
+const SECTIONS: { key: keyof Omit<LibraryBreakdown, 'libraryId'>; title: string }[] = [
+  { key: 'resolutions', title: 'Resolution' },
+  { key: 'videoCodecs', title: 'Video codec' },
+  { key: 'hdrTypes', title: 'HDR' },
+  { key: 'audioLanguages', title: 'Audio language' },
+  { key: 'containers', title: 'Container' },
+]
+
+function BreakdownSection({ title, entries }: { title: string; entries: LibraryBreakdownEntry[] }) {
+  const totalFiles = entries.reduce((sum, e) => sum + e.fileCount, 0)
+  if (totalFiles === 0) return null
+
+  return (
+    <div className="flex flex-col gap-2">
+      <h4 className="text-sm font-semibold">{title}</h4>
+      {entries.map((entry) => (
+        <div key={entry.value ?? '__unknown'} className="flex flex-col gap-1">
+          <div className="flex justify-between text-xs">
+            <span className={entry.value ? undefined : 'text-default-400 italic'}>
+              {entry.value ?? 'Unknown'}
+            </span>
+            <span className="tabular-nums text-default-500">
+              {entry.fileCount} · {formatBytes(entry.totalSize)}
+            </span>
+          </div>
+          <Progress
+            size="sm"
+            value={(entry.fileCount / totalFiles) * 100}
+            aria-label={`${entry.value ?? 'Unknown'} share of files`}
+          />
+        </div>
+      ))}
+    </div>
+  )
+}
+
+/** File counts and sizes of a library by resolution, codec, HDR, language and container */
+export function LibraryBreakdownCard({ libraryId }: LibraryBreakdownCardProps) {
+  const [breakdown, setBreakdown] = useState<LibraryBreakdown | null>(null)
+  const [loading, setLoading] = useState(true)
+
+  useEffect(() => {
+    let cancelled = false
+    setLoading(true)
+    graphqlClient
+      .query<{ libraryBreakdown: LibraryBreakdown }>(LIBRARY_BREAKDOWN_QUERY, { libraryId })
+      .toPromise()
+      .then((result) => {
+        if (cancelled) return
+        setBreakdown(result.data?.libraryBreakdown ?? null)
+        setLoading(false)
+      })
+    return () => {
+      cancelled = true
+    }
+  }, [libraryId])
+
+  return (
+    <Card>
+      <CardHeader>
+        <h3 className="text-lg font-semibold">Breakdown</h3>
+      </CardHeader>
+      <CardBody>
+        {loading ? (
+          <div className="flex justify-center py-6">
+            <Spinner />
+          </div>
+        ) : !breakdown || breakdown.resolutions.length === 0 ? (
+          <p className="text-sm text-default-500">No files in this library yet.</p>
+        ) : (
+          <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-6">
+            {SECTIONS.map(({ key, title }) => (
+              <BreakdownSection key={key} title={title} entries={breakdown[key]} />
+            ))}
+          </div>
+        )}
+      </CardBody>
+    </Card>
+  )
+}
@@ -90,6 +90,8 @@ export function LibraryFileBrowserTab({ libraryPath, loading: parentLoading }: L
   // Confirm delete modal state
   const { isOpen: isConfirmOpen, onOpen: onConfirmOpen, onClose: onConfirmClose } = useDisclosure()
   const [pathsToDelete, setPathsToDelete] = useState<string[]>([])
+  // Torrents still seeding files about to be deleted
+  const [seedingTorrents, setSeedingTorrents] = useState<string[]>([])
   const [isDeleting, setIsDeleting] = useState(false)
 
   // File properties modal state
@@ -178,9 +180,25 @@ export function LibraryFileBrowserTab({ libraryPath, loading: parentLoading }: L
     onDestinationOpen()
   }
 
-  const handleDeleteClick = (paths: string[]) => {
+  const handleDeleteClick = async (paths: string[]) => {
     setPathsToDelete(paths)
+    setSeedingTorrents([])
     onConfirmOpen()
+
+    // Warn about files whose torrent is still seeding
+    const files = paths.filter((path) => !entries.find((e) => e.path === path)?.isDir)
+    const results = await Promise.all(
+      files.map((path) =>
+        graphqlClient
+          .query<{ mediaFileByPath: MediaFile | null }>(MEDIA_FILE_BY_PATH_QUERY, { path })
+          .toPromise()
+      )
+    )
+    const seeding = results
+      .map((result) => result.data?.mediaFileByPath?.downloadSource)
+      .filter((source) => source?.isSeeding)
+      .map((source) => source!.name)
+    setSeedingTorrents([...new Set(seeding)])
   }
 
   const handleDelete = async () => {
@@ -212,6 +230,7 @@ export function LibraryFileBrowserTab({ libraryPath, loading: parentLoading }: L
       setIsDeleting(false)
       onConfirmClose()
       setPathsToDelete([])
+      setSeedingTorrents([])
     }
   }
 
@@ -585,7 +604,11 @@ export function LibraryFileBrowserTab({ libraryPath, loading: parentLoading }: L
         onConfirm={handleDelete}
         title="Delete Files"
         message={`Are you sure you want to delete ${pathsToDelete.length} item(s)?`}
-        description="This action cannot be undone."
+        description={
+          seedingTorrents.length > 0
+            ? `Still seeding: ${seedingTorrents.join(', ')}. Deleting may break seeding. This action cannot be undone.`
+            : 'This action cannot be undone.'
+        }
         confirmLabel="Delete"
         confirmColor="danger"
         isLoading={isDeleting}
@@ -1,5 +1,5 @@
 import { useState, useEffect, useCallback, useMemo } from 'react'
-import { Input } from '@heroui/input'
+import { Input, Textarea } from '@heroui/input'
 import { Select, SelectItem } from '@heroui/select'
 import { Switch } from '@heroui/switch'
 import { Divider } from '@heroui/divider'
@@ -7,7 +7,7 @@ import { Accordion, AccordionItem } from '@heroui/accordion'
 import { FolderBrowserInput } from '../FolderBrowserInput'
 import { NamingPatternSelector } from './NamingPatternSelector'
 import { QualitySettingsCard, QUALITY_PRESETS, type QualitySettings } from '../settings'
-import { LIBRARY_TYPES, type LibraryType } from '../../lib/graphql'
+import { LIBRARY_TYPES, type LibraryType, type MissingFilePolicy } from '../../lib/graphql'
 import { IconFolder, IconRefresh, IconDownload, IconSettings, IconFilter } from '@tabler/icons-react'
 
 // ============================================================================
@@ -35,6 +35,14 @@ export interface LibrarySettingsValues {
   allowedSources: string[]
   releaseGroupBlacklist: string[]
   releaseGroupWhitelist: string[]
+  // Scanner exclusions (one glob per line; blank lines dropped on save)
+  excludePatterns: string[]
+  // Imported file ownership (blank = inherit the global import settings)
+  fileUid: string
+  fileGid: string
+  fileMode: string
+  // What scans do with files deleted from disk
+  missingFilePolicy: MissingFilePolicy
 }
 
 export const DEFAULT_LIBRARY_SETTINGS: LibrarySettingsValues = {
@@ -57,6 +65,29 @@ export const DEFAULT_LIBRARY_SETTINGS: LibrarySettingsValues = {
   allowedSources: [],
   releaseGroupBlacklist: [],
   releaseGroupWhitelist: [],
+  excludePatterns: [],
+  fileUid: '',
+  fileGid: '',
+  fileMode: '',
+  missingFilePolicy: 'FLAG',
+}
+
+const MISSING_FILE_POLICIES: { value: MissingFilePolicy; label: string; description: string }[] = [
+  { value: 'FLAG', label: 'Flag only', description: 'Keep the files listed and notify; nothing is re-downloaded' },
+  { value: 'REWANT', label: 'Re-add to wanted', description: 'Remove the files so monitored items are searched for again' },
+  { value: 'UNMONITOR', label: 'Unmonitor', description: 'Remove the files and stop monitoring the movie or show' },
+]
+
+/** Drop blank lines from the exclude patterns textarea before saving */
+export function cleanExcludePatterns(patterns: string[]): string[] {
+  return patterns.map((p) => p.trim()).filter((p) => p.length > 0)
+}
+
+/** Parse a uid/gid field (blank or invalid = inherit) */
+export function parseOwnerId(value: string): number | null {
+  const trimmed = value.trim()
+  if (!/^\d+$/.test(trimmed)) return null
+  return parseInt(trimmed, 10)
 }
 
 export interface LibrarySettingsFormProps {
@@ -267,6 +298,35 @@ export function LibrarySettingsForm({
           onValueChange={(v) => updateValue('watchForChanges', v)}
         />
       </SettingRow>
+
+      <Divider />
+
+      <Textarea
+        label="Exclude Patterns"
+        labelPlacement="inside"
+        variant="flat"
+        placeholder={'**/Extras/**\n*.sample.*'}
+        description="One glob per line, relative to the library folder. NAS system folders (@eaDir, #recycle) and hidden folders are always skipped."
+        value={values.excludePatterns.join('\n')}
+        onChange={(e) => updateValue('excludePatterns', e.target.value.split('\n'))}
+        minRows={2}
+        classNames={{
+          label: 'text-sm font-medium text-primary!',
+        }}
+      />
+
+      <Divider />
+
+      <Select
+        label="When files are deleted from disk"
+        selectedKeys={[values.missingFilePolicy]}
+        onChange={(e) => e.target.value && updateValue('missingFilePolicy', e.target.value as MissingFilePolicy)}
+        description={MISSING_FILE_POLICIES.find((p) => p.value === values.missingFilePolicy)?.description}
+      >
+        {MISSING_FILE_POLICIES.map((policy) => (
+          <SelectItem key={policy.value}>{policy.label}</SelectItem>
+        ))}
+      </Select>
     </>
   )
 
@@ -364,6 +424,41 @@ export function LibrarySettingsForm({
             libraryType={values.libraryType.toLowerCase()}
           />
         )}
+
+        <Divider />
+
+        <div>
+          <p className="text-sm font-medium">Imported file ownership</p>
+          <p className="text-xs text-default-500">
+            Owner and permissions for files copied into this library, so media servers sharing the folder can read them. Leave blank to use the global import settings.
+          </p>
+        </div>
+        <div className="grid grid-cols-3 gap-3">
+          <Input
+            label="Owner UID"
+            labelPlacement="inside"
+            variant="flat"
+            placeholder="Inherit"
+            value={values.fileUid}
+            onChange={(e) => updateValue('fileUid', e.target.value)}
+          />
+          <Input
+            label="Group GID"
+            labelPlacement="inside"
+            variant="flat"
+            placeholder="Inherit"
+            value={values.fileGid}
+            onChange={(e) => updateValue('fileGid', e.target.value)}
+          />
+          <Input
+            label="File Mode"
+            labelPlacement="inside"
+            variant="flat"
+            placeholder="e.g. 664"
+            value={values.fileMode}
+            onChange={(e) => updateValue('fileMode', e.target.value)}
+          />
+        </div>
       </>
     )
   }
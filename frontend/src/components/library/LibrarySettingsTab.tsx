@@ -1,6 +1,6 @@
 import { useState, useEffect, useCallback, useMemo } from 'react'
 import { addToast } from '@heroui/toast'
-import { LibrarySettingsForm, type LibrarySettingsValues } from './LibrarySettingsForm'
+import { LibrarySettingsForm, cleanExcludePatterns, parseOwnerId, type LibrarySettingsValues } from './LibrarySettingsForm'
 import { SettingsHeader } from '../shared'
 import type { Library, UpdateLibraryInput } from '../../lib/graphql'
 
@@ -32,6 +32,11 @@ export function LibrarySettingsTab({ library, onSave, isLoading }: LibrarySettin
     allowedSources: lib.allowedSources || [],
     releaseGroupBlacklist: lib.releaseGroupBlacklist || [],
     releaseGroupWhitelist: lib.releaseGroupWhitelist || [],
+    excludePatterns: lib.excludePatterns || [],
+    fileUid: lib.fileUid?.toString() ?? '',
+    fileGid: lib.fileGid?.toString() ?? '',
+    fileMode: lib.fileMode ?? '',
+    missingFilePolicy: lib.missingFilePolicy ?? 'FLAG',
   }), [])
 
   const [values, setValues] = useState<LibrarySettingsValues>(() => libraryToValues(library))
@@ -68,7 +73,12 @@ export function LibrarySettingsTab({ library, onSave, isLoading }: LibrarySettin
       !arraysEqual(values.allowedHdrTypes, originalValues.allowedHdrTypes) ||
       !arraysEqual(values.allowedSources, originalValues.allowedSources) ||
       !arraysEqual(values.releaseGroupBlacklist, originalValues.releaseGroupBlacklist) ||
-      !arraysEqual(values.releaseGroupWhitelist, originalValues.releaseGroupWhitelist)
+      !arraysEqual(values.releaseGroupWhitelist, originalValues.releaseGroupWhitelist) ||
+      !arraysEqual(values.excludePatterns, originalValues.excludePatterns) ||
+      values.fileUid !== originalValues.fileUid ||
+      values.fileGid !== originalValues.fileGid ||
+      values.fileMode !== originalValues.fileMode ||
+      values.missingFilePolicy !== originalValues.missingFilePolicy
     
     setHasChanges(changed)
   }, [values, originalValues])
@@ -107,6 +117,12 @@ export function LibrarySettingsTab({ library, onSave, isLoading }: LibrarySettin
       allowedSources: values.allowedSources,
       releaseGroupBlacklist: values.releaseGroupBlacklist,
       releaseGroupWhitelist: values.releaseGroupWhitelist,
+      excludePatterns: cleanExcludePatterns(values.excludePatterns),
+      // null clears an override
+      fileUid: parseOwnerId(values.fileUid),
+      fileGid: parseOwnerId(values.fileGid),
+      fileMode: values.fileMode.trim() || null,
+      missingFilePolicy: values.missingFilePolicy,
     })
   }
 
@@ -12,7 +12,7 @@ import {
   IconLink,
 } from '@tabler/icons-react'
 import { graphqlClient, UNMATCHED_FILES_QUERY, type MediaFile } from '../../lib/graphql'
-import { sanitizeError } from '../../lib/format'
+import { fileNameFromPath, sanitizeError } from '../../lib/format'
 import { ErrorState } from '../shared'
 import { ManualMatchModal } from './ManualMatchModal'
 
@@ -59,10 +59,7 @@ export function LibraryUnmatchedFilesTab({ libraryId, libraryPath, libraryType,
     fetchUnmatchedFiles()
   }, [libraryId])
 
-  const getFileName = (path: string) => {
-    const parts = path.split('/')
-    return parts[parts.length - 1]
-  }
+  const getFileName = fileNameFromPath
 
   const getRelativePath = (file: MediaFile) => {
     if (file.relativePath) return file.relativePath
@@ -23,6 +23,7 @@ import {
   type MediaFile,
   type ManualMatchResult,
 } from '../../lib/graphql'
+import { fileNameFromPath } from '../../lib/format'
 
 // Queries for fetching library items
 const TV_SHOWS_QUERY = `
@@ -317,10 +318,7 @@ export function ManualMatchModal({
     }
   }
 
-  const getFileName = (path: string) => {
-    const parts = path.split('/')
-    return parts[parts.length - 1]
-  }
+  const getFileName = fileNameFromPath
 
   return (
     <Modal isOpen={isOpen} onClose={onClose} size="2xl" scrollBehavior="inside">
@@ -0,0 +1,132 @@
+import { useState, useEffect } from 'react'
+import { Card, CardBody, CardHeader } from '@heroui/card'
+import { Input } from '@heroui/input'
+import { Button } from '@heroui/button'
+import { Spinner } from '@heroui/spinner'
+import { addToast } from '@heroui/toast'
+import { IconSearch } from '@tabler/icons-react'
+import {
+  graphqlClient,
+  MISSING_SUBTITLES_QUERY,
+  SEARCH_SUBTITLES_BULK_MUTATION,
+  type MediaFile,
+  type SearchSubtitlesBulkResult,
+} from '../../lib/graphql'
+import { sanitizeError } from '../../lib/format'
+
+export interface MissingSubtitlesCardProps {
+  libraryId: string
+}
+
+type MissingFile = Pick<MediaFile, 'id' | 'path' | 'relativePath'>
+
+/** How many missing files to list before summarizing the rest */
+const PREVIEW_COUNT = 8
+
+/** Files without subtitles in a language, with a button to search for all of them */
+export function MissingSubtitlesCard({ libraryId }: MissingSubtitlesCardProps) {
+  const [language, setLanguage] = useState('en')
+  const [files, setFiles] = useState<MissingFile[] | null>(null)
+  const [loading, setLoading] = useState(false)
+  const [searching, setSearching] = useState(false)
+
+  const fetchMissing = async (lang: string) => {
+    if (!lang.trim()) return
+    setLoading(true)
+    const result = await graphqlClient
+      .query<{ missingSubtitles: MissingFile[] }>(
+        MISSING_SUBTITLES_QUERY,
+        { libraryId, language: lang.trim() },
+        { requestPolicy: 'network-only' }
+      )
+      .toPromise()
+    setLoading(false)
+    if (result.error) {
+      addToast({ title: 'Error', description: sanitizeError(result.error), color: 'danger' })
+      return
+    }
+    setFiles(result.data?.missingSubtitles ?? [])
+  }
+
+  // Check the default language when the library changes; later checks use the button
+  useEffect(() => {
+    setFiles(null)
+    fetchMissing('en')
+  }, [libraryId])
+
+  const handleSearch = async () => {
+    setSearching(true)
+    const result = await graphqlClient
+      .mutation<{ searchSubtitlesBulk: SearchSubtitlesBulkResult }>(SEARCH_SUBTITLES_BULK_MUTATION, {
+        where: { libraryId, language: language.trim() },
+      })
+      .toPromise()
+    setSearching(false)
+    const data = result.data?.searchSubtitlesBulk
+    if (data?.success) {
+      addToast({
+        title: 'Subtitle search queued',
+        description: `Searching subtitles for ${data.queuedCount} files`,
+        color: 'success',
+      })
+    } else {
+      addToast({
+        title: 'Error',
+        description: data?.error || sanitizeError(result.error),
+        color: 'danger',
+      })
+    }
+  }
+
+  return (
+    <Card>
+      <CardHeader>
+        <h3 className="text-lg font-semibold">Missing subtitles</h3>
+      </CardHeader>
+      <CardBody className="flex flex-col gap-4">
+        <div className="flex items-end gap-2">
+          <Input
+            label="Language"
+            size="sm"
+            value={language}
+            onValueChange={setLanguage}
+            description="ISO code or name, e.g. en, eng or English"
+            className="max-w-xs"
+          />
+          <Button size="sm" variant="flat" onPress={() => fetchMissing(language)} isLoading={loading}>
+            Check
+          </Button>
+        </div>
+
+        {loading && files === null ? (
+          <div className="flex justify-center py-4">
+            <Spinner />
+          </div>
+        ) : files && files.length === 0 ? (
+          <p className="text-sm text-default-500">Every matched file has subtitles in this language.</p>
+        ) : files ? (
+          <>
+            <ul className="text-xs text-default-500 flex flex-col gap-1">
+              {files.slice(0, PREVIEW_COUNT).map((file) => (
+                <li key={file.id} className="truncate">
+                  {file.relativePath ?? file.path}
+                </li>
+              ))}
+              {files.length > PREVIEW_COUNT && <li>and {files.length - PREVIEW_COUNT} more</li>}
+            </ul>
+            <Button
+              color="primary"
+              size="sm"
+              className="self-start"
+              startContent={<IconSearch size={16} />}
+              onPress={handleSearch}
+              isLoading={searching}
+            >
+              Search subtitles for {files.length} files
+            </Button>
+          </>
+        ) : null}
+      </CardBody>
+    </Card>
+  )
+}
@@ -0,0 +1,202 @@
+import { useState, useEffect } from 'react'
+import { Modal, ModalContent, ModalHeader, ModalBody, ModalFooter } from '@heroui/modal'
+import { Button } from '@heroui/button'
+import { Chip } from '@heroui/chip'
+import { Spinner } from '@heroui/spinner'
+import {
+  graphqlClient,
+  PREVIEW_SCAN_QUERY,
+  type ScanPreview,
+  type ScanPreviewAction,
+} from '../../lib/graphql'
+import { sanitizeError } from '../../lib/format'
+
+export interface ScanPreviewModalProps {
+  isOpen: boolean
+  onClose: () => void
+  libraryId: string
+  /** Start a real scan from the preview */
+  onScan?: () => void
+}
+
+const ACTION_LABELS: Record<ScanPreviewAction, { label: string; color: 'success' | 'primary' | 'warning' | 'default' }> = {
+  create: { label: 'Will add', color: 'success' },
+  link_existing: { label: 'Already in library', color: 'primary' },
+  no_match: { label: 'No match', color: 'warning' },
+  add_unlinked: { label: 'Added unlinked', color: 'default' },
+  not_checked: { label: 'Not checked', color: 'default' },
+}
+
+/** Files shown per section before collapsing */
+const FILE_PREVIEW_COUNT = 20
+
+/**
+ * Dry run of a library scan: shows new and missing files and what new files
+ * would be matched to, without changing anything.
+ */
+export function ScanPreviewModal({ isOpen, onClose, libraryId, onScan }: ScanPreviewModalProps) {
+  const [preview, setPreview] = useState<ScanPreview | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [loading, setLoading] = useState(false)
+
+  useEffect(() => {
+    if (!isOpen) return
+    let cancelled = false
+    setLoading(true)
+    setError(null)
+    setPreview(null)
+
+    graphqlClient
+      .query<{ previewScan: ScanPreview }>(PREVIEW_SCAN_QUERY, { libraryId }, { requestPolicy: 'network-only' })
+      .toPromise()
+      .then((result) => {
+        if (cancelled) return
+        if (result.error) {
+          setError(sanitizeError(result.error))
+        } else {
+          setPreview(result.data?.previewScan ?? null)
+        }
+      })
+      .finally(() => {
+        if (!cancelled) setLoading(false)
+      })
+
+    return () => {
+      cancelled = true
+    }
+  }, [isOpen, libraryId])
+
+  return (
+    <Modal isOpen={isOpen} onClose={onClose} size="3xl" scrollBehavior="inside">
+      <ModalContent>
+        <ModalHeader>Scan Preview</ModalHeader>
+        <ModalBody>
+          {loading && (
+            <div className="flex flex-col items-center gap-2 py-8">
+              <Spinner />
+              <p className="text-sm text-default-500">Walking library and checking matches...</p>
+            </div>
+          )}
+          {error && <p className="text-danger text-sm">{error}</p>}
+          {preview && (
+            <div className="space-y-5">
+              <div className="grid grid-cols-2 sm:grid-cols-4 gap-3 text-center">
+                <Stat label="Files on disk" value={preview.totalFiles} />
+                <Stat label="New files" value={preview.newFileCount} />
+                <Stat label="Missing files" value={preview.missingFileCount} />
+                <Stat label="Items to add" value={preview.itemsToCreate} />
+              </div>
+
+              {(preview.excludedFiles > 0 || preview.skippedFolders > 0) && (
+                <p className="text-xs text-default-500">
+                  Skipping {preview.excludedFiles} excluded file(s) and {preview.skippedFolders} system/excluded folder(s).
+                </p>
+              )}
+
+              {preview.notes.map((note) => (
+                <p key={note} className="text-xs text-warning">
+                  {note}
+                </p>
+              ))}
+
+              {preview.items.length > 0 && (
+                <section className="space-y-2">
+                  <h3 className="text-sm font-semibold">Matches</h3>
+                  <div className="divide-y divide-default-100">
+                    {preview.items.map((item) => {
+                      const action = ACTION_LABELS[item.action] ?? ACTION_LABELS.not_checked
+                      return (
+                        <div
+                          key={`${item.parsedName}-${item.parsedYear ?? ''}`}
+                          className="flex items-center justify-between gap-3 py-2"
+                        >
+                          <div className="min-w-0">
+                            <p className="text-sm truncate">
+                              {item.parsedName}
+                              {item.parsedYear ? ` (${item.parsedYear})` : ''}
+                              <span className="text-default-400"> · {item.fileCount} file(s)</span>
+                            </p>
+                            {item.matchName && (
+                              <p className="text-xs text-default-500 truncate">
+                                → {item.matchName}
+                                {item.matchYear ? ` (${item.matchYear})` : ''}
+                                {item.provider ? ` · ${item.provider}` : ''}
+                                {item.confidence != null ? ` · ${Math.round(item.confidence * 100)}% confidence` : ''}
+                              </p>
+                            )}
+                            {item.error && <p className="text-xs text-danger truncate">{item.error}</p>}
+                          </div>
+                          <Chip size="sm" variant="flat" color={action.color}>
+                            {action.label}
+                          </Chip>
+                        </div>
+                      )
+                    })}
+                  </div>
+                </section>
+              )}
+
+              <FileList title="New files" files={preview.newFiles} total={preview.newFileCount} />
+              <FileList title="Missing from disk" files={preview.missingFiles} total={preview.missingFileCount} />
+            </div>
+          )}
+        </ModalBody>
+        <ModalFooter>
+          <Button variant="flat" onPress={onClose}>
+            Close
+          </Button>
+          {onScan && (
+            <Button
+              color="primary"
+              isDisabled={!preview}
+              onPress={() => {
+                onClose()
+                onScan()
+              }}
+            >
+              Scan Now
+            </Button>
+          )}
+        </ModalFooter>
+      </ModalContent>
+    </Modal>
+  )
+}
+
+function Stat({ label, value }: { label: string; value: number }) {
+  return (
+    <div className="rounded-lg bg-content2 p-3">
+      <p className="text-xl font-semibold">{value}</p>
+      <p className="text-xs text-default-500">{label}</p>
+    </div>
+  )
+}
+
+function FileList({ title, files, total }: { title: string; files: string[]; total: number }) {
+  const [expanded, setExpanded] = useState(false)
+  if (total === 0) return null
+
+  const shown = expanded ? files : files.slice(0, FILE_PREVIEW_COUNT)
+  return (
+    <section className="space-y-1">
+      <h3 className="text-sm font-semibold">
+        {title} ({total})
+      </h3>
+      <ul className="font-mono text-xs text-default-500 space-y-0.5">
+        {shown.map((path) => (
+          <li key={path} className="truncate" title={path}>
+            {path}
+          </li>
+        ))}
+      </ul>
+      {files.length > FILE_PREVIEW_COUNT && (
+        <Button size="sm" variant="light" onPress={() => setExpanded(!expanded)}>
+          {expanded ? 'Show less' : `Show ${files.length - FILE_PREVIEW_COUNT} more`}
+        </Button>
+      )}
+      {total > files.length && (
+        <p className="text-xs text-default-400">…and {total - files.length} more not listed</p>
+      )}
+    </section>
+  )
+}
@@ -11,6 +11,8 @@ export { AddMovieModal, type AddMovieModalProps } from './AddMovieModal'
 export { 
   LibrarySettingsForm, 
   DEFAULT_LIBRARY_SETTINGS,
+  cleanExcludePatterns,
+  parseOwnerId,
   type LibrarySettingsFormProps,
   type LibrarySettingsValues,
 } from './LibrarySettingsForm'
@@ -27,6 +29,10 @@ export { LibraryMoviesTab } from './LibraryMoviesTab'
 export { LibraryUnmatchedFilesTab } from './LibraryUnmatchedFilesTab'
 export { LibraryFileBrowserTab } from './LibraryFileBrowserTab'
 export { LibrarySettingsTab } from './LibrarySettingsTab'
+export { ScanPreviewModal, type ScanPreviewModalProps } from './ScanPreviewModal'
+export { LibraryBreakdownCard, type LibraryBreakdownCardProps } from './LibraryBreakdownCard'
+export { MissingSubtitlesCard, type MissingSubtitlesCardProps } from './MissingSubtitlesCard'
+export { LanguageProfileCard, type LanguageProfileCardProps } from './LanguageProfileCard'
 
 // Music library components
 export { LibraryAlbumsTab } from './LibraryAlbumsTab'
@@ -356,6 +356,7 @@ export function AddToLibraryModal({
             input: {
               magnet: isMagnet ? magnetUri : undefined,
               url: !isMagnet ? (magnetUri || torrentUrl) : undefined,
+              name: release.title,
               libraryId: selectedLibraryId || undefined,
               // Pass indexer ID for authenticated .torrent downloads
               indexerId: !isMagnet && release.indexerId ? release.indexerId : undefined,
@@ -0,0 +1,257 @@
+import { useState, useEffect, useCallback } from 'react'
+import { Input } from '@heroui/input'
+import { Button } from '@heroui/button'
+import { Chip } from '@heroui/chip'
+import { Spinner } from '@heroui/spinner'
+import { addToast } from '@heroui/toast'
+import { IconPlus, IconTrash } from '@tabler/icons-react'
+import {
+  graphqlClient,
+  DOWNLOAD_SCHEDULE_QUERY,
+  SET_DOWNLOAD_SCHEDULE_MUTATION,
+  type DayOfWeek,
+  type DownloadSchedule,
+  type DownloadScheduleWindow,
+  type SettingsResult,
+} from '../../lib/graphql'
+import { sanitizeError } from '../../lib/format'
+
+const DAYS: { value: DayOfWeek; label: string }[] = [
+  { value: 'MONDAY', label: 'Mon' },
+  { value: 'TUESDAY', label: 'Tue' },
+  { value: 'WEDNESDAY', label: 'Wed' },
+  { value: 'THURSDAY', label: 'Thu' },
+  { value: 'FRIDAY', label: 'Fri' },
+  { value: 'SATURDAY', label: 'Sat' },
+  { value: 'SUNDAY', label: 'Sun' },
+]
+
+const MB = 1024 * 1024
+
+type LimitField = 'torrentDownloadLimit' | 'torrentUploadLimit' | 'usenetDownloadLimit'
+
+const LIMIT_FIELDS: { field: LimitField; label: string }[] = [
+  { field: 'torrentDownloadLimit', label: 'Torrent download' },
+  { field: 'torrentUploadLimit', label: 'Torrent upload' },
+  { field: 'usenetDownloadLimit', label: 'Usenet download' },
+]
+
+/** MB/s text for a bytes/sec limit ('' = null) */
+function toMbText(bytesPerSec: number | null): string {
+  if (bytesPerSec === null) return ''
+  return String(Math.round((bytesPerSec / MB) * 100) / 100)
+}
+
+/** bytes/sec for MB/s text (blank = null) */
+function fromMbText(text: string): number | null {
+  const value = parseFloat(text)
+  if (text.trim() === '' || Number.isNaN(value)) return null
+  return Math.max(0, Math.round(value * MB))
+}
+
+function formatLimit(bytesPerSec: number): string {
+  return bytesPerSec === 0 ? 'Unlimited' : `${toMbText(bytesPerSec)} MB/s`
+}
+
+/**
+ * Usenet speed limit and the time windows that override the torrent and
+ * usenet limits, e.g. throttling downloads during the day.
+ */
+export function DownloadScheduleSettings() {
+  const [schedule, setSchedule] = useState<DownloadSchedule | null>(null)
+  const [usenetLimit, setUsenetLimit] = useState('')
+  const [windows, setWindows] = useState<DownloadScheduleWindow[]>([])
+  const [isSaving, setIsSaving] = useState(false)
+
+  const fetchSchedule = useCallback(async () => {
+    const result = await graphqlClient
+      .query<{ downloadSchedule: DownloadSchedule }>(DOWNLOAD_SCHEDULE_QUERY, {})
+      .toPromise()
+    const data = result.data?.downloadSchedule
+    if (data) {
+      setSchedule(data)
+      setUsenetLimit(toMbText(data.defaults.usenetDownloadLimit))
+      setWindows(data.windows)
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchSchedule()
+  }, [fetchSchedule])
+
+  const updateWindow = (index: number, changes: Partial<DownloadScheduleWindow>) => {
+    setWindows((current) => current.map((w, i) => (i === index ? { ...w, ...changes } : w)))
+  }
+
+  const toggleDay = (index: number, day: DayOfWeek) => {
+    const days = windows[index].days
+    updateWindow(index, {
+      days: days.includes(day) ? days.filter((d) => d !== day) : [...days, day],
+    })
+  }
+
+  const addWindow = () => {
+    setWindows((current) => [
+      ...current,
+      {
+        days: [],
+        start: '08:00',
+        end: '00:00',
+        torrentDownloadLimit: null,
+        torrentUploadLimit: null,
+        usenetDownloadLimit: 5 * MB,
+      },
+    ])
+  }
+
+  const handleSave = async () => {
+    setIsSaving(true)
+    try {
+      const result = await graphqlClient
+        .mutation<{ setDownloadSchedule: SettingsResult }>(SET_DOWNLOAD_SCHEDULE_MUTATION, {
+          input: {
+            usenetDownloadLimit: fromMbText(usenetLimit) ?? 0,
+            // Only the input fields, not anything the client added to query results
+            windows: windows.map(({ days, start, end, torrentDownloadLimit, torrentUploadLimit, usenetDownloadLimit }) => ({
+              days,
+              start,
+              end,
+              torrentDownloadLimit,
+              torrentUploadLimit,
+              usenetDownloadLimit,
+            })),
+          },
+        })
+        .toPromise()
+
+      if (result.data?.setDownloadSchedule.success) {
+        addToast({ title: 'Saved', description: 'Download schedule updated', color: 'success' })
+        await fetchSchedule()
+      } else {
+        addToast({
+          title: 'Error',
+          description: sanitizeError(result.data?.setDownloadSchedule.error || result.error?.message || 'Failed to save'),
+          color: 'danger',
+        })
+      }
+    } finally {
+      setIsSaving(false)
+    }
+  }
+
+  if (!schedule) {
+    return (
+      <div className="flex justify-center py-4">
+        <Spinner size="sm" />
+      </div>
+    )
+  }
+
+  return (
+    <div className="space-y-4 pb-2">
+      <p className="text-sm text-default-500">
+        Windows override the default limits while they're active, in the server's local time. An end at or before the
+        start runs past midnight, and the first matching window wins. Leave a limit blank to keep its default; 0 means
+        unlimited.
+      </p>
+
+      <Input
+        type="number"
+        label="Usenet Download Limit"
+        labelPlacement="inside"
+        variant="flat"
+        description="Default for all usenet downloads, blank or 0 = unlimited"
+        value={usenetLimit}
+        onChange={(e) => setUsenetLimit(e.target.value)}
+        endContent={<span className="text-default-400 text-sm">MB/s</span>}
+        className="max-w-xs"
+        classNames={{
+          label: 'text-sm font-medium text-primary!',
+        }}
+      />
+
+      {windows.map((entry, index) => (
+        <div key={index} className="space-y-3 p-4 bg-content2 rounded-lg">
+          <div className="flex items-center justify-between gap-2">
+            <div className="flex flex-wrap gap-1">
+              {DAYS.map((day) => (
+                <Chip
+                  key={day.value}
+                  size="sm"
+                  variant={entry.days.includes(day.value) ? 'solid' : 'flat'}
+                  color={entry.days.includes(day.value) ? 'primary' : 'default'}
+                  className="cursor-pointer"
+                  onClick={() => toggleDay(index, day.value)}
+                >
+                  {day.label}
+                </Chip>
+              ))}
+              {entry.days.length === 0 && <span className="text-xs text-default-400 self-center ml-1">Every day</span>}
+            </div>
+            <div className="flex items-center gap-2">
+              {schedule.activeWindow === index && (
+                <Chip size="sm" variant="flat" color="success">
+                  Active
+                </Chip>
+              )}
+              <Button
+                isIconOnly
+                size="sm"
+                variant="light"
+                color="danger"
+                aria-label="Remove window"
+                onPress={() => setWindows((current) => current.filter((_, i) => i !== index))}
+              >
+                <IconTrash size={16} />
+              </Button>
+            </div>
+          </div>
+          <div className="grid grid-cols-2 md:grid-cols-5 gap-3">
+            <Input
+              type="time"
+              label="Start"
+              size="sm"
+              value={entry.start}
+              onChange={(e) => updateWindow(index, { start: e.target.value })}
+            />
+            <Input
+              type="time"
+              label="End"
+              size="sm"
+              value={entry.end}
+              onChange={(e) => updateWindow(index, { end: e.target.value })}
+            />
+            {LIMIT_FIELDS.map(({ field, label }) => (
+              <Input
+                key={field}
+                type="number"
+                label={label}
+                size="sm"
+                placeholder="Default"
+                value={toMbText(entry[field])}
+                onChange={(e) => updateWindow(index, { [field]: fromMbText(e.target.value) })}
+                endContent={<span className="text-default-400 text-xs">MB/s</span>}
+              />
+            ))}
+          </div>
+        </div>
+      ))}
+
+      <div className="flex items-center justify-between gap-2">
+        <p className="text-xs text-default-400">
+          Now: torrents {formatLimit(schedule.active.torrentDownloadLimit)} down,{' '}
+          {formatLimit(schedule.active.torrentUploadLimit)} up; usenet{' '}
+          {formatLimit(schedule.active.usenetDownloadLimit)}
+        </p>
+        <div className="flex gap-2">
+          <Button size="sm" variant="flat" startContent={<IconPlus size={14} />} onPress={addWindow}>
+            Add Window
+          </Button>
+          <Button color="primary" size="sm" isLoading={isSaving} onPress={handleSave}>
+            Save
+          </Button>
+        </div>
+      </div>
+    </div>
+  )
+}
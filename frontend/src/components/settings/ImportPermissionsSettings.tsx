@@ -0,0 +1,114 @@
+import { useState, useEffect, useCallback } from 'react'
+import { Input } from '@heroui/input'
+import { Button } from '@heroui/button'
+import { Spinner } from '@heroui/spinner'
+import { addToast } from '@heroui/toast'
+import {
+  graphqlClient,
+  IMPORT_PERMISSION_SETTINGS_QUERY,
+  UPDATE_IMPORT_PERMISSION_SETTINGS_MUTATION,
+  type ImportPermissionSettings,
+  type SettingsResult,
+} from '../../lib/graphql'
+import { parseOwnerId } from '../library/LibrarySettingsForm'
+import { sanitizeError } from '../../lib/format'
+
+/**
+ * Global owner/group/mode applied to files copied into libraries.
+ * Libraries can override each value in their own settings.
+ */
+export function ImportPermissionsSettings() {
+  const [settings, setSettings] = useState<ImportPermissionSettings | null>(null)
+  const [fileUid, setFileUid] = useState('')
+  const [fileGid, setFileGid] = useState('')
+  const [fileMode, setFileMode] = useState('')
+  const [isSaving, setIsSaving] = useState(false)
+
+  const fetchSettings = useCallback(async () => {
+    const result = await graphqlClient
+      .query<{ importPermissionSettings: ImportPermissionSettings }>(IMPORT_PERMISSION_SETTINGS_QUERY, {})
+      .toPromise()
+    const data = result.data?.importPermissionSettings
+    if (data) {
+      setSettings(data)
+      setFileUid(data.fileUid?.toString() ?? '')
+      setFileGid(data.fileGid?.toString() ?? '')
+      setFileMode(data.fileMode ?? '')
+    }
+  }, [])
+
+  useEffect(() => {
+    fetchSettings()
+  }, [fetchSettings])
+
+  const handleSave = async () => {
+    setIsSaving(true)
+    try {
+      const result = await graphqlClient
+        .mutation<{ updateImportPermissionSettings: SettingsResult }>(UPDATE_IMPORT_PERMISSION_SETTINGS_MUTATION, {
+          input: {
+            fileUid: parseOwnerId(fileUid),
+            fileGid: parseOwnerId(fileGid),
+            fileMode: fileMode.trim() || null,
+          },
+        })
+        .toPromise()
+
+      if (result.data?.updateImportPermissionSettings.success) {
+        addToast({ title: 'Saved', description: 'Import permissions updated', color: 'success' })
+        await fetchSettings()
+      } else {
+        addToast({
+          title: 'Error',
+          description: sanitizeError(result.data?.updateImportPermissionSettings.error || result.error?.message || 'Failed to save'),
+          color: 'danger',
+        })
+      }
+    } finally {
+      setIsSaving(false)
+    }
+  }
+
+  if (!settings) {
+    return (
+      <div className="flex justify-center py-4">
+        <Spinner size="sm" />
+      </div>
+    )
+  }
+
+  return (
+    <div className="space-y-4 pb-2">
+      <p className="text-sm text-default-500">
+        Owner and permissions given to files copied into libraries, so media servers sharing the same folders can read them.
+        Leave blank to use the PUID/PGID/UMASK environment variables, or to leave files as they are.
+      </p>
+      <div className="grid grid-cols-1 sm:grid-cols-3 gap-3">
+        <Input
+          label="Owner UID"
+          placeholder={settings.envUid?.toString() ?? 'Unchanged'}
+          value={fileUid}
+          onChange={(e) => setFileUid(e.target.value)}
+        />
+        <Input
+          label="Group GID"
+          placeholder={settings.envGid?.toString() ?? 'Unchanged'}
+          value={fileGid}
+          onChange={(e) => setFileGid(e.target.value)}
+        />
+        <Input
+          label="File Mode"
+          placeholder={settings.envFileMode ?? 'e.g. 664'}
+          description="Folders get execute added where read is allowed"
+          value={fileMode}
+          onChange={(e) => setFileMode(e.target.value)}
+        />
+      </div>
+      <div className="flex justify-end">
+        <Button color="primary" size="sm" isLoading={isSaving} onPress={handleSave}>
+          Save
+        </Button>
+      </div>
+    </div>
+  )
+}
@@ -10,6 +10,72 @@ export interface RssFeedFormData {
   url: string
   enabled: boolean
   pollIntervalMinutes: number
+  minPollIntervalMinutes: number
+  maxPollIntervalMinutes: number
+}
+
+interface PollIntervalInputsProps {
+  pollInterval: number
+  minInterval: number
+  maxInterval: number
+  onPollIntervalChange: (value: number) => void
+  onMinIntervalChange: (value: number) => void
+  onMaxIntervalChange: (value: number) => void
+}
+
+/** Starting poll interval plus the bounds it adapts within */
+function PollIntervalInputs({
+  pollInterval,
+  minInterval,
+  maxInterval,
+  onPollIntervalChange,
+  onMinIntervalChange,
+  onMaxIntervalChange,
+}: PollIntervalInputsProps) {
+  const classNames = { label: 'text-sm font-medium text-primary!' }
+  return (
+    <div className="flex flex-col gap-1">
+      <div className="grid grid-cols-3 gap-2">
+        <Input
+          type="number"
+          label="Start (min)"
+          labelPlacement="inside"
+          variant="flat"
+          value={pollInterval.toString()}
+          onChange={(e) => onPollIntervalChange(parseInt(e.target.value) || 15)}
+          min={1}
+          max={1440}
+          classNames={classNames}
+        />
+        <Input
+          type="number"
+          label="Fastest (min)"
+          labelPlacement="inside"
+          variant="flat"
+          value={minInterval.toString()}
+          onChange={(e) => onMinIntervalChange(parseInt(e.target.value) || 5)}
+          min={1}
+          max={1440}
+          classNames={classNames}
+        />
+        <Input
+          type="number"
+          label="Slowest (min)"
+          labelPlacement="inside"
+          variant="flat"
+          value={maxInterval.toString()}
+          onChange={(e) => onMaxIntervalChange(parseInt(e.target.value) || 120)}
+          min={1}
+          max={1440}
+          classNames={classNames}
+        />
+      </div>
+      <p className="text-xs text-default-400">
+        Busy feeds are polled faster and quiet ones back off, within these bounds. Set both bounds
+        to the same value for a fixed interval.
+      </p>
+    </div>
+  )
 }
 
 export interface AddRssFeedModalProps {
@@ -29,6 +95,8 @@ export function AddRssFeedModal({
   const [url, setUrl] = useState('')
   const [enabled, setEnabled] = useState(true)
   const [pollInterval, setPollInterval] = useState(15)
+  const [minInterval, setMinInterval] = useState(5)
+  const [maxInterval, setMaxInterval] = useState(120)
 
   // Reset form when modal opens
   useEffect(() => {
@@ -37,6 +105,8 @@ export function AddRssFeedModal({
       setUrl('')
       setEnabled(true)
       setPollInterval(15)
+      setMinInterval(5)
+      setMaxInterval(120)
     }
   }, [isOpen])
 
@@ -46,6 +116,8 @@ export function AddRssFeedModal({
       url,
       enabled,
       pollIntervalMinutes: pollInterval,
+      minPollIntervalMinutes: minInterval,
+      maxPollIntervalMinutes: maxInterval,
     })
   }
 
@@ -85,20 +157,13 @@ export function AddRssFeedModal({
             </div>
             <Switch isSelected={enabled} onValueChange={setEnabled} />
           </div>
-          <Input
-            type="number"
-            label="Poll Interval (minutes)"
-            labelPlacement="inside"
-            variant="flat"
-            placeholder="15"
-            description="How often to check for new items (5-1440 minutes)"
-            value={pollInterval.toString()}
-            onChange={(e) => setPollInterval(parseInt(e.target.value) || 15)}
-            min={5}
-            max={1440}
-            classNames={{
-              label: 'text-sm font-medium text-primary!',
-            }}
+          <PollIntervalInputs
+            pollInterval={pollInterval}
+            minInterval={minInterval}
+            maxInterval={maxInterval}
+            onPollIntervalChange={setPollInterval}
+            onMinIntervalChange={setMinInterval}
+            onMaxIntervalChange={setMaxInterval}
           />
         </ModalBody>
         <ModalFooter>
@@ -138,6 +203,8 @@ export function EditRssFeedModal({
   const [url, setUrl] = useState('')
   const [enabled, setEnabled] = useState(true)
   const [pollInterval, setPollInterval] = useState(15)
+  const [minInterval, setMinInterval] = useState(5)
+  const [maxInterval, setMaxInterval] = useState(120)
 
   // Initialize form when feed changes
   useEffect(() => {
@@ -146,6 +213,8 @@ export function EditRssFeedModal({
       setUrl(feed.url)
       setEnabled(feed.enabled)
       setPollInterval(feed.pollIntervalMinutes)
+      setMinInterval(feed.minPollIntervalMinutes)
+      setMaxInterval(feed.maxPollIntervalMinutes)
     }
   }, [feed])
 
@@ -155,6 +224,8 @@ export function EditRssFeedModal({
       url,
       enabled,
       pollIntervalMinutes: pollInterval,
+      minPollIntervalMinutes: minInterval,
+      maxPollIntervalMinutes: maxInterval,
     })
   }
 
@@ -192,20 +263,13 @@ export function EditRssFeedModal({
             </div>
             <Switch isSelected={enabled} onValueChange={setEnabled} />
           </div>
-          <Input
-            type="number"
-            label="Poll Interval (minutes)"
-            labelPlacement="inside"
-            variant="flat"
-            placeholder="15"
-            description="How often to check for new items (5-1440 minutes)"
-            value={pollInterval.toString()}
-            onChange={(e) => setPollInterval(parseInt(e.target.value) || 15)}
-            min={5}
-            max={1440}
-            classNames={{
-              label: 'text-sm font-medium text-primary!',
-            }}
+          <PollIntervalInputs
+            pollInterval={pollInterval}
+            minInterval={minInterval}
+            maxInterval={maxInterval}
+            onPollIntervalChange={setPollInterval}
+            onMinIntervalChange={setMinInterval}
+            onMaxIntervalChange={setMaxInterval}
           />
         </ModalBody>
         <ModalFooter>
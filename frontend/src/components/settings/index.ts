@@ -20,3 +20,6 @@ export {
   SOURCE_OPTIONS,
   QUALITY_PRESETS,
 } from './QualitySettingsCard'
+
+export { ImportPermissionsSettings } from './ImportPermissionsSettings'
+export { DownloadScheduleSettings } from './DownloadScheduleSettings'
@@ -14,10 +14,12 @@ import {
   START_PLAYBACK_MUTATION,
   UPDATE_PLAYBACK_MUTATION,
   STOP_PLAYBACK_MUTATION,
+  PLAYBACK_HEARTBEAT_MUTATION,
   ALBUM_WITH_TRACKS_QUERY,
   AUDIOBOOK_WITH_CHAPTERS_QUERY,
   type PlaybackSession,
   type PlaybackResult,
+  type PlaybackHeartbeatResult,
   type StartPlaybackInput,
   type UpdatePlaybackInput,
   type PlaybackContentType,
@@ -125,6 +127,9 @@ interface PlaybackContextValue {
   clearExpandFlag: () => void;
 }
 
+/** How often the stream lease is renewed (well under the server's minimum timeout) */
+const HEARTBEAT_INTERVAL_MS = 20_000;
+
 const PlaybackContext = createContext<PlaybackContextValue | null>(null);
 
 export function PlaybackProvider({ children }: { children: ReactNode }) {
@@ -150,6 +155,8 @@ export function PlaybackProvider({ children }: { children: ReactNode }) {
   const [repeatMode, setRepeatModeState] = useState<RepeatMode>('off');
   
   const lastSyncedPosition = useRef<number>(0);
+  // Stream lease held by this client; kept alive with heartbeats while playing
+  const streamIdRef = useRef<string | null>(null);
 
   const clearExpandFlag = useCallback(() => {
     setShouldExpand(false);
@@ -287,16 +294,50 @@ export function PlaybackProvider({ children }: { children: ReactNode }) {
     }
   }, [auth.isAuthenticated, refreshSession]);
 
+  // Keep the stream lease alive while a session is open. If the server ended
+  // it (stopped by an admin, or expired after missed heartbeats) stop playing.
+  const sessionId = session?.id;
+  useEffect(() => {
+    if (!sessionId) return;
+
+    const interval = setInterval(async () => {
+      const streamId = streamIdRef.current;
+      if (!streamId) return;
+      try {
+        const result = await graphqlClient
+          .mutation<{ playbackHeartbeat: PlaybackHeartbeatResult }>(PLAYBACK_HEARTBEAT_MUTATION, { streamId })
+          .toPromise();
+        const heartbeat = result.data?.playbackHeartbeat;
+        if (heartbeat?.success && !heartbeat.active && streamIdRef.current === streamId) {
+          console.info('Playback stream ended:', heartbeat.endReason);
+          streamIdRef.current = null;
+          clearAllState();
+        }
+      } catch (err) {
+        console.error('Failed to send playback heartbeat:', err);
+      }
+    }, HEARTBEAT_INTERVAL_MS);
+
+    return () => clearInterval(interval);
+  }, [sessionId, clearAllState]);
+
   const startPlayback = useCallback(async (
     input: StartPlaybackInput,
     metadata?: CurrentContentMetadata
   ): Promise<boolean> => {
     try {
       const result = await graphqlClient
-        .mutation<{ startPlayback: PlaybackResult }>(START_PLAYBACK_MUTATION, { input })
+        .mutation<{ startPlayback: PlaybackResult }>(START_PLAYBACK_MUTATION, {
+          input: {
+            ...input,
+            streamId: streamIdRef.current ?? undefined,
+            clientName: navigator.userAgent,
+          },
+        })
         .toPromise();
       
       if (result.data?.startPlayback.success && result.data.startPlayback.session) {
+        streamIdRef.current = result.data.startPlayback.streamId;
         setSession(result.data.startPlayback.session);
         if (metadata) setCurrentContent(metadata);
         lastSyncedPosition.current = input.startPosition || 0;
@@ -410,10 +451,13 @@ export function PlaybackProvider({ children }: { children: ReactNode }) {
   const stopPlayback = useCallback(async (): Promise<boolean> => {
     try {
       const result = await graphqlClient
-        .mutation<{ stopPlayback: PlaybackResult }>(STOP_PLAYBACK_MUTATION, {})
+        .mutation<{ stopPlayback: PlaybackResult }>(STOP_PLAYBACK_MUTATION, {
+          streamId: streamIdRef.current,
+        })
         .toPromise();
       
       if (result.data?.stopPlayback.success) {
+        streamIdRef.current = null;
         clearAllState();
         lastSyncedPosition.current = 0;
         return true;
@@ -122,7 +122,7 @@ export function useDashboardCache(userId: string | null): UseDashboardCacheResul
         graphqlClient.query<{ libraries: Library[] }>(LIBRARIES_QUERY).toPromise(),
         graphqlClient.query<{ libraryUpcomingEpisodes: LibraryUpcomingEpisode[] }>(
           LIBRARY_UPCOMING_EPISODES_QUERY,
-          { days: 7 }
+          { days: 7, utcOffsetMinutes: -new Date().getTimezoneOffset() }
         ).toPromise(),
         graphqlClient.query<{ upcomingEpisodes: UpcomingEpisode[] }>(
           UPCOMING_EPISODES_QUERY,
@@ -1,4 +1,11 @@
 import { useState, useEffect, useCallback } from 'react'
+import {
+  graphqlClient,
+  MY_PREFERENCES_QUERY,
+  UPDATE_PREFERENCES_MUTATION,
+  MY_PREFERENCES_CHANGED_SUBSCRIPTION,
+  type UserPreferences,
+} from '../lib/graphql'
 
 export type Theme = 'light' | 'dark'
 
@@ -12,9 +19,7 @@ function getInitialTheme(): Theme {
       return stored
     }
     // Check system preference
-    if (window.matchMedia('(prefers-color-scheme: light)').matches) {
-      return 'light'
-    }
+    return systemTheme()
   }
   return 'dark'
 }
@@ -35,9 +40,54 @@ function applyTheme(theme: Theme) {
   }
 }
 
-export function useTheme() {
+function systemTheme(): Theme {
+  return window.matchMedia('(prefers-color-scheme: light)').matches ? 'light' : 'dark'
+}
+
+/**
+ * Theme state, kept in localStorage so it applies before anything loads.
+ * With a userId the theme is also synced with the user's server-side
+ * preferences, so it follows them across devices.
+ */
+export function useTheme(userId?: string | null) {
   const [theme, setThemeState] = useState<Theme>(getInitialTheme)
 
+  // Adopt a theme from the server ("system" follows the OS again)
+  const applyServerTheme = useCallback((serverTheme: UserPreferences['theme']) => {
+    if (serverTheme === 'system') {
+      localStorage.removeItem(THEME_STORAGE_KEY)
+      setThemeState(systemTheme())
+    } else {
+      localStorage.setItem(THEME_STORAGE_KEY, serverTheme)
+      setThemeState(serverTheme)
+    }
+  }, [])
+
+  // Load the user's preferences and follow changes made on other devices
+  useEffect(() => {
+    if (!userId) return
+
+    graphqlClient
+      .query<{ myPreferences: UserPreferences }>(MY_PREFERENCES_QUERY)
+      .toPromise()
+      .then(({ data }) => {
+        if (data?.myPreferences) applyServerTheme(data.myPreferences.theme)
+      })
+
+    const sub = graphqlClient
+      .subscription<{ myPreferencesChanged: UserPreferences }>(
+        MY_PREFERENCES_CHANGED_SUBSCRIPTION,
+        {}
+      )
+      .subscribe({
+        next: ({ data }) => {
+          if (data?.myPreferencesChanged) applyServerTheme(data.myPreferencesChanged.theme)
+        },
+      })
+
+    return () => sub.unsubscribe()
+  }, [userId, applyServerTheme])
+
   // Apply theme on mount and changes
   useEffect(() => {
     applyTheme(theme)
@@ -57,10 +107,19 @@ export function useTheme() {
     return () => mediaQuery.removeEventListener('change', handleChange)
   }, [])
 
+  // Applied straight away; the server copy is updated in the background
   const setTheme = useCallback((newTheme: Theme) => {
     localStorage.setItem(THEME_STORAGE_KEY, newTheme)
     setThemeState(newTheme)
-  }, [])
+    if (userId) {
+      graphqlClient
+        .mutation(UPDATE_PREFERENCES_MUTATION, { input: { theme: newTheme } })
+        .toPromise()
+        .then(({ error }) => {
+          if (error) console.warn('Failed to save theme preference:', error.message)
+        })
+    }
+  }, [userId])
 
   const toggleTheme = useCallback(() => {
     setTheme(theme === 'dark' ? 'light' : 'dark')
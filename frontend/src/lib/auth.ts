@@ -15,6 +15,7 @@ export interface AuthUser {
   username: string;
   role: string;
   displayName?: string;
+  avatarUrl?: string;
 }
 
 /** Auth session containing tokens and user info */
@@ -110,6 +110,22 @@ export function formatRelativeTime(dateStr: string | null | undefined): string {
   return 'just now'
 }
 
+/**
+ * Last component of a file path, for Unix ("/a/b.mkv") or Windows ("D:\a\b.mkv") paths
+ */
+export function fileNameFromPath(path: string): string {
+  const parts = path.split(/[\\/]/).filter(Boolean)
+  return parts[parts.length - 1] || path
+}
+
+/**
+ * Join a folder path and a child name with the separator the folder already uses
+ */
+export function joinPath(base: string, name: string): string {
+  const separator = base.includes('\\') && !base.includes('/') ? '\\' : '/'
+  return `${base.replace(/[\\/]+$/, '')}${separator}${name}`
+}
+
 /**
  * Sanitize error messages for display (strips HTML, truncates long messages)
  */
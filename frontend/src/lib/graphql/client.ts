@@ -47,9 +47,37 @@ function getAuthTokenSync(): string {
   return getAuthHeaderSync();
 }
 
+// Schema version reported by the server (x-schema-version header). A change
+// mid-session means the server was upgraded and this bundle may be stale.
+type SchemaVersionHandler = (version: string) => void;
+const schemaVersionHandlers: Set<SchemaVersionHandler> = new Set();
+let knownSchemaVersion: string | null = null;
+
+/** Subscribe to server schema changes (e.g. to prompt a page refresh) */
+export function onSchemaVersionChange(handler: SchemaVersionHandler): () => void {
+  schemaVersionHandlers.add(handler);
+  return () => schemaVersionHandlers.delete(handler);
+}
+
+async function versionCheckingFetch(
+  input: RequestInfo | URL,
+  init?: RequestInit,
+): Promise<Response> {
+  const response = await fetch(input, init);
+  const version = response.headers.get("x-schema-version");
+  if (version) {
+    if (knownSchemaVersion && knownSchemaVersion !== version) {
+      schemaVersionHandlers.forEach((handler) => handler(version));
+    }
+    knownSchemaVersion = version;
+  }
+  return response;
+}
+
 // HTTP link for queries and mutations
 const httpLink = new HttpLink({
   uri: `${API_URL}/graphql`,
+  fetch: versionCheckingFetch,
 });
 
 // Auth context link - adds Authorization header to every request
@@ -1,16 +1,27 @@
 // Re-export everything from sub-modules for easy importing
 
 // Client
-export { apolloClient, graphqlClient, onGraphQLError } from "./client";
+export { apolloClient, graphqlClient, onGraphQLError, onSchemaVersionChange } from "./client";
 
 // Types
 export type {
   // Auth Types
   AuthResult,
   LogoutResult,
+  AuthMutationResult,
+  AvatarUploadResult,
   AuthUserInfo,
   LoginInput,
   RegisterInput,
+  // User Preference Types
+  UserPreferences,
+  UpdatePreferencesInput,
+  // Role Types
+  RolePermission,
+  Role,
+  CreateRoleInput,
+  UpdateRoleInput,
+  RoleResult,
   // Library Type Helpers
   LibraryTypeInfo,
   // Media Item (legacy)
@@ -25,6 +36,8 @@ export type {
   OrganizeTorrentResult,
   TorrentDetails,
   TorrentFileInfo,
+  TorrentFileProgressEvent,
+  TransferRateHistory,
   PendingFileMatch,
   RematchSourceResult,
   ProcessSourceResult,
@@ -36,6 +49,13 @@ export type {
   // Settings
   TorrentSettings,
   SettingsResult,
+  ImportPermissionSettings,
+  UpdateImportPermissionSettingsInput,
+  DayOfWeek,
+  DownloadSpeedLimits,
+  DownloadScheduleWindow,
+  DownloadSchedule,
+  SetDownloadScheduleInput,
   // UPnP and Port Testing
   UpnpResult,
   PortTestResult,
@@ -59,10 +79,17 @@ export type {
   // Library
   LibraryType,
   PostDownloadAction,
+  MissingFilePolicy,
   Library,
   LibraryResult,
   LibraryChangeType,
   LibraryChangedEvent,
+  ScanPreviewAction,
+  ScanPreviewItem,
+  ScanPreview,
+  AutomationPause,
+  LibraryBreakdown,
+  LibraryBreakdownEntry,
   CreateLibraryInput,
   UpdateLibraryInput,
   // TV Show
@@ -79,6 +106,9 @@ export type {
   MovieStatus,
   Movie,
   MovieSearchResult,
+  Credit,
+  FilmographyEntry,
+  Person,
   MovieResult,
   AddMovieInput,
   UpdateMovieInput,
@@ -133,7 +163,14 @@ export type {
   // Media Files
   MediaFile,
   MediaFileDetails,
+  MediaFileDownloadSource,
   ManualMatchResult,
+  SearchSubtitlesBulkResult,
+  // Language Profiles
+  LanguageProfile,
+  CreateLanguageProfileInput,
+  LanguageProfileResult,
+  LanguageMismatch,
   EmbeddedMetadata,
   VideoStreamInfo,
   AudioStreamInfo,
@@ -160,12 +197,33 @@ export type {
   UpdatePlaybackInput,
   UpdatePlaybackSettingsInput,
   PlaybackResult,
+  PlaybackHeartbeatResult,
+  // Watch State Types
+  WatchState,
+  ContinueWatchingItem,
+  NextUpItem,
+  GlobalSearchHit,
+  ActivePlaybackStream,
+  TranscodeCacheStats,
+  DiagnosticStatus,
+  DiagnosticCheck,
+  ExternalServiceHealth,
+  ExternalServiceStatus,
+  ExternalServicesStatus,
+  ClearTranscodeCacheResult,
+  UpdateInfo,
+  UpdateCheckSettings,
+  CheckForUpdatesResult,
+  ApplyUpdateResult,
   // Indexer Search Types
   IndexerSearchInput,
   IndexerSearchResultSet,
   IndexerSearchResultItem,
   TorrentRelease,
   IndexerConfig,
+  TrackerStats,
+  TrackerRatioStatus,
+  GrabHistoryEntry,
   // LLM Parser Types
   LlmParserSettings,
   OllamaConnectionResult,
@@ -188,6 +246,17 @@ export type {
   // Content Download Progress Types
   ContentDownloadType,
   ContentDownloadProgressEvent,
+  // Background Job Types
+  BackgroundJobStatus,
+  BackgroundJob,
+  JobQueuedResult,
+  ConsolidateLibraryResult,
+  ArtworkEntityType,
+  RefreshArtworkResult,
+  ArtworkAuditResult,
+  // Audit Log Types
+  AuditFieldChange,
+  AuditLogEntry,
 } from "./types";
 
 // Constants
@@ -198,17 +267,27 @@ export {
   // Auth Queries
   CHECK_SETUP_STATUS_QUERY,
   ME_QUERY,
+  MY_PREFERENCES_QUERY,
+  // Role Queries
+  ROLES_QUERY,
+  MY_ROLE_QUERY,
   // Torrent Queries
   TORRENTS_QUERY,
   TORRENT_QUERY,
   TORRENT_DETAILS_QUERY,
+  TRANSFER_RATE_HISTORY_QUERY,
   PENDING_FILE_MATCHES_QUERY,
   ACTIVE_DOWNLOAD_COUNT_QUERY,
   TORRENT_SETTINGS_QUERY,
+  IMPORT_PERMISSION_SETTINGS_QUERY,
+  DOWNLOAD_SCHEDULE_QUERY,
   UPnP_STATUS_QUERY,
   TEST_PORT_ACCESSIBILITY_QUERY,
   LIBRARIES_QUERY,
   LIBRARY_QUERY,
+  PREVIEW_SCAN_QUERY,
+  AUTOMATION_PAUSES_QUERY,
+  LIBRARY_BREAKDOWN_QUERY,
   ALL_TV_SHOWS_QUERY,
   TV_SHOWS_QUERY,
   TV_SHOWS_CONNECTION_QUERY,
@@ -251,6 +330,10 @@ export {
   LIBRARY_UPCOMING_EPISODES_QUERY,
   UNMATCHED_FILES_QUERY,
   UNMATCHED_FILES_COUNT_QUERY,
+  MISSING_SUBTITLES_QUERY,
+  LANGUAGE_PROFILES_QUERY,
+  LIBRARY_LANGUAGE_PROFILE_QUERY,
+  LANGUAGE_MISMATCHES_QUERY,
   MEDIA_FILE_BY_PATH_QUERY,
   MOVIE_MEDIA_FILE_QUERY,
   MEDIA_FILE_DETAILS_QUERY,
@@ -267,6 +350,21 @@ export {
   // Playback Queries
   PLAYBACK_SESSION_QUERY,
   PLAYBACK_SETTINGS_QUERY,
+  ACTIVE_SESSIONS_QUERY,
+  TRANSCODE_CACHE_STATS_QUERY,
+  DIAGNOSTICS_QUERY,
+  EXTERNAL_SERVICES_STATUS_QUERY,
+  UPDATE_AVAILABLE_QUERY,
+  UPDATE_CHECK_SETTINGS_QUERY,
+  // Watch State Queries
+  CONTINUE_WATCHING_QUERY,
+  NEXT_UP_QUERY,
+  // Global Search Queries
+  GLOBAL_SEARCH_QUERY,
+  // People Queries
+  MOVIE_CREDITS_QUERY,
+  PERSON_QUERY,
+  DIRECTOR_SUGGESTIONS_QUERY,
   // Indexer Search Queries
   SEARCH_INDEXERS_QUERY,
   INDEXER_CONFIGS_QUERY,
@@ -277,6 +375,11 @@ export {
   RECENT_NOTIFICATIONS_QUERY,
   NOTIFICATION_COUNTS_QUERY,
   UNREAD_NOTIFICATION_COUNT_QUERY,
+  // Background Job Queries
+  BACKGROUND_JOB_QUERY,
+  BACKGROUND_JOBS_QUERY,
+  // Audit Log Queries
+  AUDIT_LOG_QUERY,
 } from "./queries";
 
 // Mutations
@@ -287,6 +390,23 @@ export {
   REFRESH_TOKEN_MUTATION,
   LOGOUT_MUTATION,
   NEEDS_SETUP_QUERY,
+  // Password Reset Mutations
+  REQUEST_PASSWORD_RESET_MUTATION,
+  RESET_PASSWORD_MUTATION,
+  // Account Self-Service Mutations
+  CHANGE_MY_PASSWORD_MUTATION,
+  CHANGE_MY_EMAIL_MUTATION,
+  CONFIRM_EMAIL_CHANGE_MUTATION,
+  REMOVE_MY_AVATAR_MUTATION,
+  // User Preference Mutations
+  UPDATE_PREFERENCES_MUTATION,
+  // Role Mutations
+  CREATE_ROLE_MUTATION,
+  UPDATE_ROLE_MUTATION,
+  DELETE_ROLE_MUTATION,
+  ASSIGN_ROLE_MUTATION,
+  // Usenet Mutations
+  ADD_NZB_MUTATION,
   // Torrent Mutations
   ADD_TORRENT_MUTATION,
   PAUSE_TORRENT_MUTATION,
@@ -299,12 +419,18 @@ export {
   SET_MATCH_MUTATION,
   REMOVE_MATCH_MUTATION,
   UPDATE_TORRENT_SETTINGS_MUTATION,
+  UPDATE_IMPORT_PERMISSION_SETTINGS_MUTATION,
+  SET_DOWNLOAD_SCHEDULE_MUTATION,
   ATTEMPT_UPNP_PORT_FORWARDING_MUTATION,
   CREATE_LIBRARY_MUTATION,
   UPDATE_LIBRARY_MUTATION,
   DELETE_LIBRARY_MUTATION,
+  PAUSE_AUTOMATION_MUTATION,
+  RESUME_AUTOMATION_MUTATION,
   SCAN_LIBRARY_MUTATION,
   CONSOLIDATE_LIBRARY_MUTATION,
+  AUDIT_ARTWORK_MUTATION,
+  REFRESH_ARTWORK_MUTATION,
   ADD_TV_SHOW_MUTATION,
   DELETE_TV_SHOW_MUTATION,
   REFRESH_TV_SHOW_MUTATION,
@@ -357,6 +483,16 @@ export {
   UPDATE_PLAYBACK_MUTATION,
   STOP_PLAYBACK_MUTATION,
   UPDATE_PLAYBACK_SETTINGS_MUTATION,
+  PLAYBACK_HEARTBEAT_MUTATION,
+  STOP_SESSION_MUTATION,
+  CLEAR_TRANSCODE_CACHE_MUTATION,
+  UPDATE_TRANSCODE_CACHE_SETTINGS_MUTATION,
+  UPDATE_UPDATE_CHECK_SETTINGS_MUTATION,
+  CHECK_FOR_UPDATES_MUTATION,
+  APPLY_UPDATE_MUTATION,
+  // Watch State Mutations
+  MARK_WATCHED_MUTATION,
+  MARK_UNWATCHED_MUTATION,
   // Auto-Hunt Mutations
   TRIGGER_AUTO_HUNT_MUTATION,
   // Notification Mutations
@@ -365,6 +501,14 @@ export {
   RESOLVE_NOTIFICATION_MUTATION,
   RESOLVE_NOTIFICATION_WITH_ACTION_MUTATION,
   DELETE_NOTIFICATION_MUTATION,
+  // Subtitle Mutations
+  SEARCH_SUBTITLES_BULK_MUTATION,
+  // Language Profile Mutations
+  CREATE_LANGUAGE_PROFILE_MUTATION,
+  UPDATE_LANGUAGE_PROFILE_MUTATION,
+  DELETE_LANGUAGE_PROFILE_MUTATION,
+  SET_LIBRARY_LANGUAGE_PROFILE_MUTATION,
+  SET_SHOW_LANGUAGE_PROFILE_MUTATION,
   // Manual Match Mutations
   MANUAL_MATCH_MUTATION,
   UNMATCH_MEDIA_FILE_MUTATION,
@@ -376,6 +520,7 @@ export {
   TORRENT_ADDED_SUBSCRIPTION,
   TORRENT_COMPLETED_SUBSCRIPTION,
   TORRENT_REMOVED_SUBSCRIPTION,
+  TORRENT_FILE_PROGRESS_SUBSCRIPTION,
   ACTIVE_DOWNLOAD_COUNT_SUBSCRIPTION,
   LOG_EVENTS_SUBSCRIPTION,
   ERROR_LOGS_SUBSCRIPTION,
@@ -390,6 +535,12 @@ export {
   NOTIFICATION_COUNTS_SUBSCRIPTION,
   // Content Download Progress Subscriptions
   CONTENT_DOWNLOAD_PROGRESS_SUBSCRIPTION,
+  // Background Job Subscriptions
+  BACKGROUND_JOB_UPDATED_SUBSCRIPTION,
+  // User Preference Subscriptions
+  MY_PREFERENCES_CHANGED_SUBSCRIPTION,
+  // Watch State Subscriptions
+  WATCH_STATE_CHANGED_SUBSCRIPTION,
 } from "./subscriptions";
 
 // GraphQL-based filesystem functions (replaces REST API)
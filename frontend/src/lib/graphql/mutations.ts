@@ -77,6 +77,170 @@ export const LOGOUT_MUTATION = `
   }
 `;
 
+// ============================================================================
+// Password Reset Mutations
+// ============================================================================
+
+/** Email a reset link; succeeds whether or not the address has an account */
+export const REQUEST_PASSWORD_RESET_MUTATION = `
+  mutation RequestPasswordReset($email: String!) {
+    requestPasswordReset(email: $email) {
+      success
+      error
+    }
+  }
+`;
+
+/** Set a new password with the token from a reset link */
+export const RESET_PASSWORD_MUTATION = `
+  mutation ResetPassword($token: String!, $newPassword: String!) {
+    resetPassword(token: $token, newPassword: $newPassword) {
+      success
+      error
+    }
+  }
+`;
+
+// ============================================================================
+// Account Self-Service Mutations
+// ============================================================================
+
+/** Change your own password; signs out every other session */
+export const CHANGE_MY_PASSWORD_MUTATION = `
+  mutation ChangeMyPassword($currentPassword: String!, $newPassword: String!) {
+    changeMyPassword(currentPassword: $currentPassword, newPassword: $newPassword) {
+      success
+      error
+    }
+  }
+`;
+
+/** Mail a confirmation link to a new email address */
+export const CHANGE_MY_EMAIL_MUTATION = `
+  mutation ChangeMyEmail($newEmail: String!, $currentPassword: String!) {
+    changeMyEmail(newEmail: $newEmail, currentPassword: $currentPassword) {
+      success
+      error
+    }
+  }
+`;
+
+/** Confirm an email change with the token from the confirmation link */
+export const CONFIRM_EMAIL_CHANGE_MUTATION = `
+  mutation ConfirmEmailChange($token: String!) {
+    confirmEmailChange(token: $token) {
+      success
+      error
+    }
+  }
+`;
+
+/** Remove your avatar (uploads go to POST /api/account/avatar) */
+export const REMOVE_MY_AVATAR_MUTATION = `
+  mutation RemoveMyAvatar {
+    removeMyAvatar {
+      success
+      error
+    }
+  }
+`;
+
+// ============================================================================
+// User Preference Mutations
+// ============================================================================
+
+export const UPDATE_PREFERENCES_MUTATION = `
+  mutation UpdatePreferences($input: UpdatePreferencesInput!) {
+    updatePreferences(input: $input) {
+      userId
+      theme
+      defaultLibraryId
+      gridDensity
+      language
+      notificationsEnabled
+      mutedNotificationCategories
+      updatedAt
+    }
+  }
+`;
+
+// ============================================================================
+// Role Mutations
+// ============================================================================
+
+export const CREATE_ROLE_MUTATION = `
+  mutation CreateRole($input: CreateRoleInput!) {
+    createRole(input: $input) {
+      success
+      error
+      role {
+        name
+        description
+        permissions
+        isBuiltin
+        createdAt
+        updatedAt
+      }
+    }
+  }
+`;
+
+export const UPDATE_ROLE_MUTATION = `
+  mutation UpdateRole($name: String!, $input: UpdateRoleInput!) {
+    updateRole(name: $name, input: $input) {
+      success
+      error
+      role {
+        name
+        description
+        permissions
+        isBuiltin
+        createdAt
+        updatedAt
+      }
+    }
+  }
+`;
+
+export const DELETE_ROLE_MUTATION = `
+  mutation DeleteRole($name: String!) {
+    deleteRole(name: $name) {
+      success
+      error
+    }
+  }
+`;
+
+export const ASSIGN_ROLE_MUTATION = `
+  mutation AssignRole($userId: String!, $role: String!) {
+    assignRole(userId: $userId, role: $role) {
+      success
+      error
+    }
+  }
+`;
+
+// ============================================================================
+// Usenet Mutations
+// ============================================================================
+
+/** Add a usenet download from NZB XML (`nzb`) or a URL (`url`) */
+export const ADD_NZB_MUTATION = `
+  mutation AddNzb($input: AddNzbInput!) {
+    addNzb(input: $input) {
+      success
+      download {
+        id
+        name
+        state
+        progress
+        size
+      }
+      error
+    }
+  }
+`;
+
 // ============================================================================
 // Torrent Mutations
 // ============================================================================
@@ -199,6 +363,24 @@ export const UPDATE_TORRENT_SETTINGS_MUTATION = `
   }
 `;
 
+export const UPDATE_IMPORT_PERMISSION_SETTINGS_MUTATION = `
+  mutation UpdateImportPermissionSettings($input: UpdateImportPermissionSettingsInput!) {
+    updateImportPermissionSettings(input: $input) {
+      success
+      error
+    }
+  }
+`;
+
+export const SET_DOWNLOAD_SCHEDULE_MUTATION = `
+  mutation SetDownloadSchedule($input: SetDownloadScheduleInput!) {
+    setDownloadSchedule(input: $input) {
+      success
+      error
+    }
+  }
+`;
+
 export const ATTEMPT_UPNP_PORT_FORWARDING_MUTATION = `
   mutation AttemptUpnpPortForwarding {
     attemptUpnpPortForwarding {
@@ -256,6 +438,24 @@ export const DELETE_LIBRARY_MUTATION = `
   }
 `;
 
+export const PAUSE_AUTOMATION_MUTATION = `
+  mutation PauseAutomation($libraryId: String, $resumeAt: String) {
+    pauseAutomation(libraryId: $libraryId, resumeAt: $resumeAt) {
+      success
+      error
+    }
+  }
+`;
+
+export const RESUME_AUTOMATION_MUTATION = `
+  mutation ResumeAutomation($libraryId: String) {
+    resumeAutomation(libraryId: $libraryId) {
+      success
+      error
+    }
+  }
+`;
+
 export const SCAN_LIBRARY_MUTATION = `
   mutation ScanLibrary($id: String!) {
     scanLibrary(id: $id) {
@@ -270,9 +470,31 @@ export const CONSOLIDATE_LIBRARY_MUTATION = `
   mutation ConsolidateLibrary($id: String!) {
     consolidateLibrary(id: $id) {
       success
-      foldersRemoved
-      filesMoved
-      messages
+      error
+      jobId
+    }
+  }
+`;
+
+export const AUDIT_ARTWORK_MUTATION = `
+  mutation AuditArtwork($libraryId: String) {
+    auditArtwork(libraryId: $libraryId) {
+      success
+      error
+      jobId
+    }
+  }
+`;
+
+export const REFRESH_ARTWORK_MUTATION = `
+  mutation RefreshArtwork($entityType: ArtworkEntityType!, $id: String!) {
+    refreshArtwork(entityType: $entityType, id: $id) {
+      success
+      error
+      posterUrl
+      backdropUrl
+      imagesUpdated
+      imagesFailed
     }
   }
 `;
@@ -291,6 +513,7 @@ export const ADD_TV_SHOW_MUTATION = `
         posterUrl
       }
       error
+      missingMetadata
     }
   }
 `;
@@ -346,6 +569,10 @@ export const CREATE_RSS_FEED_MUTATION = `
         url
         enabled
         pollIntervalMinutes
+        minPollIntervalMinutes
+        maxPollIntervalMinutes
+        currentPollIntervalMinutes
+        ttlMinutes
         lastPolledAt
         lastSuccessfulAt
         lastError
@@ -367,6 +594,10 @@ export const UPDATE_RSS_FEED_MUTATION = `
         url
         enabled
         pollIntervalMinutes
+        minPollIntervalMinutes
+        maxPollIntervalMinutes
+        currentPollIntervalMinutes
+        ttlMinutes
         lastPolledAt
         lastSuccessfulAt
         lastError
@@ -418,6 +649,10 @@ export const POLL_RSS_FEED_MUTATION = `
         url
         enabled
         pollIntervalMinutes
+        minPollIntervalMinutes
+        maxPollIntervalMinutes
+        currentPollIntervalMinutes
+        ttlMinutes
         lastPolledAt
         lastSuccessfulAt
         lastError
@@ -654,6 +889,9 @@ export const DISCOVER_CAST_DEVICES_MUTATION = `
       port
       model
       deviceType
+      isGroup
+      isAudioOnly
+      isAirplay
       isFavorite
       isManual
       isConnected
@@ -673,6 +911,9 @@ export const ADD_CAST_DEVICE_MUTATION = `
         port
         model
         deviceType
+        isGroup
+        isAudioOnly
+        isAirplay
         isFavorite
         isManual
         isConnected
@@ -694,6 +935,9 @@ export const UPDATE_CAST_DEVICE_MUTATION = `
         port
         model
         deviceType
+        isGroup
+        isAudioOnly
+        isAirplay
         isFavorite
         isManual
         isConnected
@@ -859,6 +1103,7 @@ export const START_PLAYBACK_MUTATION = `
         startedAt
         lastUpdatedAt
       }
+      streamId
       error
     }
   }
@@ -894,8 +1139,8 @@ export const UPDATE_PLAYBACK_MUTATION = `
 `;
 
 export const STOP_PLAYBACK_MUTATION = `
-  mutation StopPlayback {
-    stopPlayback {
+  mutation StopPlayback($streamId: String) {
+    stopPlayback(streamId: $streamId) {
       success
       session {
         id
@@ -909,6 +1154,121 @@ export const UPDATE_PLAYBACK_SETTINGS_MUTATION = `
   mutation UpdatePlaybackSettings($input: UpdatePlaybackSettingsInput!) {
     updatePlaybackSettings(input: $input) {
       syncIntervalSeconds
+      maxConcurrentStreams
+      heartbeatTimeoutSeconds
+    }
+  }
+`;
+
+export const PLAYBACK_HEARTBEAT_MUTATION = `
+  mutation PlaybackHeartbeat($streamId: String!) {
+    playbackHeartbeat(streamId: $streamId) {
+      success
+      active
+      endReason
+      error
+    }
+  }
+`;
+
+export const STOP_SESSION_MUTATION = `
+  mutation StopSession($id: String!) {
+    stopSession(id: $id) {
+      success
+      error
+    }
+  }
+`;
+
+export const CLEAR_TRANSCODE_CACHE_MUTATION = `
+  mutation ClearTranscodeCache {
+    clearTranscodeCache {
+      success
+      sessionsRemoved
+      bytesFreed
+      sessionsSkipped
+      error
+    }
+  }
+`;
+
+export const UPDATE_TRANSCODE_CACHE_SETTINGS_MUTATION = `
+  mutation UpdateTranscodeCacheSettings($maxSizeGb: Float!) {
+    updateTranscodeCacheSettings(maxSizeGb: $maxSizeGb) {
+      success
+      error
+    }
+  }
+`;
+
+export const UPDATE_UPDATE_CHECK_SETTINGS_MUTATION = `
+  mutation UpdateUpdateCheckSettings($enabled: Boolean!) {
+    updateUpdateCheckSettings(enabled: $enabled) {
+      success
+      error
+    }
+  }
+`;
+
+export const CHECK_FOR_UPDATES_MUTATION = `
+  mutation CheckForUpdates {
+    checkForUpdates {
+      success
+      update {
+        currentVersion
+        version
+        name
+        url
+        publishedAt
+        changelog
+      }
+      error
+    }
+  }
+`;
+
+export const APPLY_UPDATE_MUTATION = `
+  mutation ApplyUpdate {
+    applyUpdate {
+      success
+      version
+      error
+    }
+  }
+`;
+
+// ============================================================================
+// Watch State Mutations
+// ============================================================================
+
+export const MARK_WATCHED_MUTATION = `
+  mutation MarkWatched($contentType: PlaybackContentType!, $contentId: String!) {
+    markWatched(contentType: $contentType, contentId: $contentId) {
+      userId
+      contentType
+      contentId
+      mediaFileId
+      position
+      duration
+      progress
+      isWatched
+      lastWatchedAt
+    }
+  }
+`;
+
+export const MARK_UNWATCHED_MUTATION = `
+  mutation MarkUnwatched($contentType: PlaybackContentType!, $contentId: String!) {
+    markUnwatched(contentType: $contentType, contentId: $contentId) {
+      userId
+      contentType
+      contentId
+      mediaFileId
+      position
+      duration
+      progress
+      isWatched
+      lastWatchedAt
     }
   }
 `;
@@ -1116,6 +1476,88 @@ export const DELETE_NOTIFICATION_MUTATION = `
   }
 `;
 
+// ============================================================================
+// Subtitle Mutations
+// ============================================================================
+
+/** Queue subtitle downloads for files missing a language */
+export const SEARCH_SUBTITLES_BULK_MUTATION = `
+  mutation SearchSubtitlesBulk($where: SubtitleSearchWhereInput!) {
+    searchSubtitlesBulk(where: $where) {
+      success
+      queuedCount
+      error
+    }
+  }
+`;
+
+// ============================================================================
+// Language Profile Mutations
+// ============================================================================
+
+export const CREATE_LANGUAGE_PROFILE_MUTATION = `
+  mutation CreateLanguageProfile($input: CreateLanguageProfileInput!) {
+    createLanguageProfile(input: $input) {
+      success
+      error
+      profile {
+        id
+        name
+        preferredAudioLanguages
+        requiredAudioLanguages
+        requiredSubtitleLanguages
+        regrabMissingAudio
+      }
+    }
+  }
+`;
+
+export const UPDATE_LANGUAGE_PROFILE_MUTATION = `
+  mutation UpdateLanguageProfile($id: String!, $input: UpdateLanguageProfileInput!) {
+    updateLanguageProfile(id: $id, input: $input) {
+      success
+      error
+      profile {
+        id
+        name
+        preferredAudioLanguages
+        requiredAudioLanguages
+        requiredSubtitleLanguages
+        regrabMissingAudio
+      }
+    }
+  }
+`;
+
+export const DELETE_LANGUAGE_PROFILE_MUTATION = `
+  mutation DeleteLanguageProfile($id: String!) {
+    deleteLanguageProfile(id: $id) {
+      success
+      error
+    }
+  }
+`;
+
+/** Assign a language profile to a library; a null profileId clears it */
+export const SET_LIBRARY_LANGUAGE_PROFILE_MUTATION = `
+  mutation SetLibraryLanguageProfile($libraryId: String!, $profileId: String) {
+    setLibraryLanguageProfile(libraryId: $libraryId, profileId: $profileId) {
+      success
+      error
+    }
+  }
+`;
+
+/** Assign a language profile to a show; a null profileId uses the library's */
+export const SET_SHOW_LANGUAGE_PROFILE_MUTATION = `
+  mutation SetShowLanguageProfile($showId: String!, $profileId: String) {
+    setShowLanguageProfile(showId: $showId, profileId: $profileId) {
+      success
+      error
+    }
+  }
+`;
+
 // ============================================================================
 // Manual Match Mutations
 // ============================================================================
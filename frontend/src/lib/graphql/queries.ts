@@ -16,6 +16,56 @@ export const ME_QUERY = `
       id
       email
       role
+      displayName
+      avatarUrl
+    }
+  }
+`;
+
+/** Get the current user's UI preferences */
+export const MY_PREFERENCES_QUERY = `
+  query MyPreferences {
+    myPreferences {
+      userId
+      theme
+      defaultLibraryId
+      gridDensity
+      language
+      notificationsEnabled
+      mutedNotificationCategories
+      updatedAt
+    }
+  }
+`;
+
+// ============================================================================
+// Role Queries
+// ============================================================================
+
+/** All roles (requires the manage-users permission) */
+export const ROLES_QUERY = `
+  query Roles {
+    roles {
+      name
+      description
+      permissions
+      isBuiltin
+      createdAt
+      updatedAt
+    }
+  }
+`;
+
+/** The current user's role and its permissions */
+export const MY_ROLE_QUERY = `
+  query MyRole {
+    myRole {
+      name
+      description
+      permissions
+      isBuiltin
+      createdAt
+      updatedAt
     }
   }
 `;
@@ -86,6 +136,7 @@ export const TORRENT_DETAILS_QUERY = `
         index
         path
         size
+        downloaded
         progress
       }
       pieceCount
@@ -108,6 +159,22 @@ export const TORRENT_DETAILS_QUERY = `
   }
 `;
 
+/**
+ * Recent transfer rates for sparkline graphs
+ *
+ * Omit torrentId for the totals across all torrents.
+ */
+export const TRANSFER_RATE_HISTORY_QUERY = `
+  query TransferRateHistory($torrentId: Int, $minutes: Int) {
+    transferRateHistory(torrentId: $torrentId, minutes: $minutes) {
+      intervalSecs
+      timestamps
+      downloadSpeeds
+      uploadSpeeds
+    }
+  }
+`;
+
 /** Query pending file matches for any source (torrent, usenet, etc.) */
 export const PENDING_FILE_MATCHES_QUERY = `
   query PendingFileMatches($sourceType: String!, $sourceId: String!) {
@@ -212,6 +279,45 @@ export const LLM_PARSER_SETTINGS_QUERY = `
   }
 `;
 
+export const IMPORT_PERMISSION_SETTINGS_QUERY = `
+  query ImportPermissionSettings {
+    importPermissionSettings {
+      fileUid
+      fileGid
+      fileMode
+      envUid
+      envGid
+      envFileMode
+    }
+  }
+`;
+
+export const DOWNLOAD_SCHEDULE_QUERY = `
+  query DownloadSchedule {
+    downloadSchedule {
+      defaults {
+        torrentDownloadLimit
+        torrentUploadLimit
+        usenetDownloadLimit
+      }
+      windows {
+        days
+        start
+        end
+        torrentDownloadLimit
+        torrentUploadLimit
+        usenetDownloadLimit
+      }
+      active {
+        torrentDownloadLimit
+        torrentUploadLimit
+        usenetDownloadLimit
+      }
+      activeWindow
+    }
+  }
+`;
+
 // ============================================================================
 // Library Queries
 // ============================================================================
@@ -272,6 +378,85 @@ export const LIBRARY_QUERY = `
       allowedSources
       releaseGroupBlacklist
       releaseGroupWhitelist
+      excludePatterns
+      fileUid
+      fileGid
+      fileMode
+      missingFilePolicy
+    }
+  }
+`;
+
+export const PREVIEW_SCAN_QUERY = `
+  query PreviewScan($libraryId: String!) {
+    previewScan(libraryId: $libraryId) {
+      libraryId
+      libraryName
+      totalFiles
+      newFileCount
+      newFiles
+      missingFileCount
+      missingFiles
+      excludedFiles
+      skippedFolders
+      itemsToCreate
+      items {
+        parsedName
+        parsedYear
+        fileCount
+        action
+        matchName
+        matchYear
+        provider
+        providerId
+        confidence
+        error
+      }
+      notes
+    }
+  }
+`;
+
+export const LIBRARY_BREAKDOWN_QUERY = `
+  query LibraryBreakdown($libraryId: String!) {
+    libraryBreakdown(libraryId: $libraryId) {
+      libraryId
+      resolutions {
+        value
+        fileCount
+        totalSize
+      }
+      videoCodecs {
+        value
+        fileCount
+        totalSize
+      }
+      hdrTypes {
+        value
+        fileCount
+        totalSize
+      }
+      audioLanguages {
+        value
+        fileCount
+        totalSize
+      }
+      containers {
+        value
+        fileCount
+        totalSize
+      }
+    }
+  }
+`;
+
+export const AUTOMATION_PAUSES_QUERY = `
+  query AutomationPauses {
+    automationPauses {
+      id
+      libraryId
+      resumeAt
+      createdAt
     }
   }
 `;
@@ -1136,6 +1321,10 @@ export const RSS_FEEDS_QUERY = `
       url
       enabled
       pollIntervalMinutes
+      minPollIntervalMinutes
+      maxPollIntervalMinutes
+      currentPollIntervalMinutes
+      ttlMinutes
       lastPolledAt
       lastSuccessfulAt
       lastError
@@ -1255,8 +1444,8 @@ export const UPCOMING_EPISODES_QUERY = `
 `;
 
 export const LIBRARY_UPCOMING_EPISODES_QUERY = `
-  query LibraryUpcomingEpisodes($days: Int) {
-    libraryUpcomingEpisodes(days: $days) {
+  query LibraryUpcomingEpisodes($days: Int, $utcOffsetMinutes: Int) {
+    libraryUpcomingEpisodes(days: $days, utcOffsetMinutes: $utcOffsetMinutes) {
       id
       tvmazeId
       name
@@ -1307,6 +1496,20 @@ export const UNMATCHED_FILES_QUERY = `
   }
 `;
 
+/** Matched video files of a library without a subtitle in a language */
+export const MISSING_SUBTITLES_QUERY = `
+  query MissingSubtitles($libraryId: String!, $language: String!) {
+    missingSubtitles(libraryId: $libraryId, language: $language) {
+      id
+      path
+      relativePath
+      sizeBytes
+      episodeId
+      movieId
+    }
+  }
+`;
+
 export const UNMATCHED_FILES_COUNT_QUERY = `
   query UnmatchedFilesCount($libraryId: String!) {
     unmatchedFilesCount(libraryId: $libraryId)
@@ -1331,6 +1534,12 @@ export const MEDIA_FILE_BY_PATH_QUERY = `
       bitrate
       organized
       addedAt
+      downloadSource {
+        sourceType
+        name
+        state
+        isSeeding
+      }
     }
   }
 `;
@@ -1414,6 +1623,10 @@ export const MEDIA_FILE_DETAILS_QUERY = `
         title
         isDefault
         isCommentary
+        integratedLoudness
+        loudnessRange
+        truePeak
+        replayGain
       }
       subtitles {
         id
@@ -1469,6 +1682,9 @@ export const CAST_DEVICES_QUERY = `
       port
       model
       deviceType
+      isGroup
+      isAudioOnly
+      isAirplay
       isFavorite
       isManual
       isConnected
@@ -1486,6 +1702,9 @@ export const CAST_DEVICE_QUERY = `
       port
       model
       deviceType
+      isGroup
+      isAudioOnly
+      isAirplay
       isFavorite
       isManual
       isConnected
@@ -1577,6 +1796,308 @@ export const PLAYBACK_SETTINGS_QUERY = `
   query PlaybackSettings {
     playbackSettings {
       syncIntervalSeconds
+      maxConcurrentStreams
+      heartbeatTimeoutSeconds
+    }
+  }
+`;
+
+export const ACTIVE_SESSIONS_QUERY = `
+  query ActiveSessions {
+    activeSessions {
+      id
+      userId
+      username
+      contentType
+      mediaFileId
+      clientName
+      currentPosition
+      duration
+      isPlaying
+      startedAt
+      lastHeartbeatAt
+    }
+  }
+`;
+
+export const TRANSCODE_CACHE_STATS_QUERY = `
+  query TranscodeCacheStats {
+    transcodeCacheStats {
+      totalBytes
+      maxSizeGb
+      sessionCount
+      pinnedCount
+      pinnedBytes
+      oldestEntryAt
+    }
+  }
+`;
+
+export const DIAGNOSTICS_QUERY = `
+  query Diagnostics {
+    diagnostics {
+      id
+      title
+      status
+      message
+      hint
+    }
+  }
+`;
+
+export const EXTERNAL_SERVICES_STATUS_QUERY = `
+  query ExternalServicesStatus {
+    externalServicesStatus {
+      downCount
+      degradedCount
+      services {
+        id
+        kind
+        name
+        status
+        latencyMs
+        lastError
+        lastCheckedAt
+        lastChangedAt
+      }
+    }
+  }
+`;
+
+export const UPDATE_AVAILABLE_QUERY = `
+  query UpdateAvailable {
+    updateAvailable {
+      currentVersion
+      version
+      name
+      url
+      publishedAt
+      changelog
+    }
+  }
+`;
+
+export const UPDATE_CHECK_SETTINGS_QUERY = `
+  query UpdateCheckSettings {
+    updateCheckSettings {
+      enabled
+      latestVersion
+      lastCheckedAt
+      selfUpdateUnavailableReason
+    }
+  }
+`;
+
+// ============================================================================
+// Watch State Queries
+// ============================================================================
+
+export const CONTINUE_WATCHING_QUERY = `
+  query ContinueWatching($limit: Int) {
+    continueWatching(limit: $limit) {
+      state {
+        userId
+        contentType
+        contentId
+        mediaFileId
+        position
+        duration
+        progress
+        isWatched
+        lastWatchedAt
+      }
+      episode {
+        id
+        tvShowId
+        season
+        episode
+        title
+        overview
+        airDate
+        runtime
+        mediaFileId
+        watchProgress
+        watchPosition
+        isWatched
+      }
+      show {
+        id
+        name
+        year
+        posterUrl
+        backdropUrl
+      }
+      movie {
+        id
+        title
+        year
+        runtime
+        posterUrl
+        backdropUrl
+      }
+    }
+  }
+`;
+
+export const NEXT_UP_QUERY = `
+  query NextUp($limit: Int) {
+    nextUp(limit: $limit) {
+      show {
+        id
+        name
+        year
+        posterUrl
+        backdropUrl
+      }
+      episode {
+        id
+        tvShowId
+        season
+        episode
+        title
+        overview
+        airDate
+        runtime
+        mediaFileId
+        watchProgress
+        watchPosition
+        isWatched
+      }
+    }
+  }
+`;
+
+// ============================================================================
+// Global Search Queries
+// ============================================================================
+
+export const GLOBAL_SEARCH_QUERY = `
+  query GlobalSearch($query: String!, $limit: Int) {
+    globalSearch(query: $query, limit: $limit) {
+      __typename
+      ... on MovieSearchHit {
+        score
+        movie {
+          id
+          title
+          year
+          posterUrl
+        }
+      }
+      ... on ShowSearchHit {
+        score
+        show {
+          id
+          name
+          year
+          posterUrl
+        }
+      }
+      ... on EpisodeSearchHit {
+        score
+        episode {
+          id
+          tvShowId
+          season
+          episode
+          title
+        }
+        show {
+          id
+          name
+          posterUrl
+        }
+      }
+      ... on AlbumSearchHit {
+        score
+        album {
+          id
+          artistId
+          name
+          year
+          coverUrl
+        }
+      }
+      ... on ArtistSearchHit {
+        score
+        artist {
+          id
+          name
+        }
+      }
+      ... on AudiobookSearchHit {
+        score
+        audiobook {
+          id
+          title
+          coverUrl
+        }
+      }
+      ... on PersonSearchHit {
+        score
+        name
+        roles
+      }
+    }
+  }
+`;
+
+// ============================================================================
+// People Queries
+// ============================================================================
+
+export const MOVIE_CREDITS_QUERY = `
+  query MovieCredits($id: String!) {
+    movie(id: $id) {
+      id
+      credits {
+        personId
+        name
+        profileUrl
+        role
+        character
+        billingOrder
+      }
+    }
+  }
+`;
+
+export const PERSON_QUERY = `
+  query Person($id: String!) {
+    person(id: $id) {
+      id
+      tmdbId
+      name
+      profileUrl
+      filmography {
+        role
+        character
+        movie {
+          id
+          title
+          year
+          posterUrl
+        }
+        tvShow {
+          id
+          name
+          year
+          posterUrl
+        }
+      }
+    }
+  }
+`;
+
+export const DIRECTOR_SUGGESTIONS_QUERY = `
+  query DirectorSuggestions($personId: String!, $limit: Int) {
+    directorSuggestions(personId: $personId, limit: $limit) {
+      providerId
+      title
+      year
+      overview
+      posterUrl
+      voteAverage
     }
   }
 `;
@@ -1757,3 +2278,104 @@ export const UNREAD_NOTIFICATION_COUNT_QUERY = `
     unreadNotificationCount
   }
 `;
+
+// ============================================================================
+// Background Job Queries
+// ============================================================================
+
+export const BACKGROUND_JOB_QUERY = `
+  query BackgroundJob($id: String!) {
+    backgroundJob(id: $id) {
+      id
+      kind
+      status
+      progress
+      message
+      payload
+      result
+      error
+      userId
+      isFinished
+      createdAt
+      startedAt
+      finishedAt
+    }
+  }
+`;
+
+export const BACKGROUND_JOBS_QUERY = `
+  query BackgroundJobs($limit: Int) {
+    backgroundJobs(limit: $limit) {
+      id
+      kind
+      status
+      progress
+      message
+      error
+      userId
+      isFinished
+      createdAt
+      startedAt
+      finishedAt
+    }
+  }
+`;
+
+// ============================================================================
+// Audit Log Queries
+// ============================================================================
+
+export const AUDIT_LOG_QUERY = `
+  query AuditLog($entityType: String, $entityId: String, $limit: Int) {
+    auditLog(entityType: $entityType, entityId: $entityId, limit: $limit) {
+      id
+      userId
+      entityType
+      entityId
+      action
+      changes
+      createdAt
+    }
+  }
+`;
+
+// ============================================================================
+// Language Profile Queries
+// ============================================================================
+
+export const LANGUAGE_PROFILES_QUERY = `
+  query LanguageProfiles {
+    languageProfiles {
+      id
+      name
+      preferredAudioLanguages
+      requiredAudioLanguages
+      requiredSubtitleLanguages
+      regrabMissingAudio
+    }
+  }
+`;
+
+/** The profile assigned to a library, or null */
+export const LIBRARY_LANGUAGE_PROFILE_QUERY = `
+  query LibraryLanguageProfile($libraryId: String!) {
+    libraryLanguageProfile(libraryId: $libraryId) {
+      id
+      name
+    }
+  }
+`;
+
+/** Files in a library missing audio languages their profile requires */
+export const LANGUAGE_MISMATCHES_QUERY = `
+  query LanguageMismatches($libraryId: String!) {
+    languageMismatches(libraryId: $libraryId) {
+      mediaFileId
+      fileName
+      missingAudioLanguages
+      releaseTitle
+      regrabPending
+      createdAt
+    }
+  }
+`;
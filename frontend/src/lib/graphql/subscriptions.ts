@@ -45,6 +45,22 @@ export const TORRENT_REMOVED_SUBSCRIPTION = `
   }
 `;
 
+export const TORRENT_FILE_PROGRESS_SUBSCRIPTION = `
+  subscription TorrentFileProgress($torrentId: Int!) {
+    torrentFileProgress(torrentId: $torrentId) {
+      torrentId
+      infoHash
+      files {
+        index
+        path
+        size
+        downloaded
+        progress
+      }
+    }
+  }
+`;
+
 /**
  * Lightweight subscription for navbar badge
  *
@@ -219,3 +235,68 @@ export const CONTENT_DOWNLOAD_PROGRESS_SUBSCRIPTION = `
     }
   }
 `;
+
+// ============================================================================
+// Background Job Subscriptions
+// ============================================================================
+
+/**
+ * Subscribe to background job updates (queued, progress, finished)
+ * Pass a jobId to follow a single job started by a mutation
+ */
+export const BACKGROUND_JOB_UPDATED_SUBSCRIPTION = `
+  subscription BackgroundJobUpdated($jobId: String) {
+    backgroundJobUpdated(jobId: $jobId) {
+      id
+      kind
+      status
+      progress
+      message
+      result
+      error
+      isFinished
+      finishedAt
+    }
+  }
+`;
+
+// ============================================================================
+// User Preference Subscriptions
+// ============================================================================
+
+/** Subscribe to changes to your own preferences (made on any device) */
+export const MY_PREFERENCES_CHANGED_SUBSCRIPTION = `
+  subscription MyPreferencesChanged {
+    myPreferencesChanged {
+      userId
+      theme
+      defaultLibraryId
+      gridDensity
+      language
+      notificationsEnabled
+      mutedNotificationCategories
+      updatedAt
+    }
+  }
+`;
+
+// ============================================================================
+// Watch State Subscriptions
+// ============================================================================
+
+/** Subscribe to your own watch progress and watched marks (from any device) */
+export const WATCH_STATE_CHANGED_SUBSCRIPTION = `
+  subscription WatchStateChanged {
+    watchStateChanged {
+      userId
+      contentType
+      contentId
+      mediaFileId
+      position
+      duration
+      progress
+      isWatched
+      lastWatchedAt
+    }
+  }
+`;
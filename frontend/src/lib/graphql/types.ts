@@ -27,6 +27,19 @@ export interface LogoutResult {
   error: string | null;
 }
 
+/** Success/error result of account mutations (password, email, avatar) */
+export interface AuthMutationResult {
+  success: boolean;
+  error: string | null;
+}
+
+/** Response from `POST /api/account/avatar` */
+export interface AvatarUploadResult {
+  success: boolean;
+  avatarUrl?: string;
+  error?: string;
+}
+
 /** User info returned from auth endpoints */
 export interface AuthUserInfo {
   id: string;
@@ -48,6 +61,76 @@ export interface RegisterInput {
   email?: string | null;
 }
 
+// ============================================================================
+// User Preference Types
+// ============================================================================
+
+/** UI preferences, stored per user so they follow them across devices */
+export interface UserPreferences {
+  userId: string;
+  theme: "light" | "dark" | "system";
+  /** Library opened by default */
+  defaultLibraryId: string | null;
+  gridDensity: "compact" | "comfortable" | "spacious";
+  /** UI language as a BCP 47 tag, e.g. "en" */
+  language: string;
+  notificationsEnabled: boolean;
+  /** Notification categories not shown as toasts */
+  mutedNotificationCategories: string[];
+  updatedAt: string;
+}
+
+/** Omitted fields are unchanged; an empty defaultLibraryId clears it */
+export interface UpdatePreferencesInput {
+  theme?: UserPreferences["theme"];
+  defaultLibraryId?: string;
+  gridDensity?: UserPreferences["gridDensity"];
+  language?: string;
+  notificationsEnabled?: boolean;
+  mutedNotificationCategories?: string[];
+}
+
+// ============================================================================
+// Role Types
+// ============================================================================
+
+/** Something a role allows; a role with none is view-only */
+export type RolePermission =
+  | "MANAGE_LIBRARIES"
+  | "MANAGE_DOWNLOADS"
+  | "MANAGE_USERS"
+  | "MANAGE_SERVER";
+
+/** A named set of permissions */
+export interface Role {
+  name: string;
+  description: string | null;
+  permissions: RolePermission[];
+  /** admin, member and guest can't be edited or deleted */
+  isBuiltin: boolean;
+  createdAt: string;
+  updatedAt: string;
+}
+
+export interface CreateRoleInput {
+  /** Lowercase letters, digits, '-' and '_' */
+  name: string;
+  description?: string | null;
+  permissions: RolePermission[];
+}
+
+/** Omitted fields are unchanged */
+export interface UpdateRoleInput {
+  description?: string;
+  permissions?: RolePermission[];
+}
+
+export interface RoleResult {
+  success: boolean;
+  role: Role | null;
+  error: string | null;
+}
+
 // ============================================================================
 // Library Type Helpers
 // ============================================================================
@@ -202,9 +285,26 @@ export interface TorrentFileInfo {
   index: number;
   path: string;
   size: number;
+  /** Bytes of the file downloaded so far */
+  downloaded: number;
   progress: number;
 }
 
+/** Files of a torrent whose progress changed */
+export interface TorrentFileProgressEvent {
+  torrentId: number;
+  infoHash: string;
+  files: TorrentFileInfo[];
+}
+
+/** Transfer rates sampled at a fixed interval (parallel arrays, oldest first) */
+export interface TransferRateHistory {
+  intervalSecs: number;
+  timestamps: string[];
+  downloadSpeeds: number[];
+  uploadSpeeds: number[];
+}
+
 /** A pending file match (source-agnostic - works for torrents, usenet, etc.) */
 export interface PendingFileMatch {
   id: string;
@@ -289,6 +389,74 @@ export interface SettingsResult {
   error: string | null;
 }
 
+/** Ownership and mode applied to imported files (libraries can override each) */
+export interface ImportPermissionSettings {
+  /** Owner uid (null = PUID environment variable, or leave unchanged) */
+  fileUid: number | null;
+  /** Group gid (null = PGID environment variable, or leave unchanged) */
+  fileGid: number | null;
+  /** Octal file mode, e.g. "664" (null = derived from UMASK, or leave unchanged) */
+  fileMode: string | null;
+  envUid: number | null;
+  envGid: number | null;
+  envFileMode: string | null;
+}
+
+export interface UpdateImportPermissionSettingsInput {
+  /** null clears the setting */
+  fileUid?: number | null;
+  fileGid?: number | null;
+  fileMode?: string | null;
+}
+
+export type DayOfWeek =
+  | "MONDAY"
+  | "TUESDAY"
+  | "WEDNESDAY"
+  | "THURSDAY"
+  | "FRIDAY"
+  | "SATURDAY"
+  | "SUNDAY";
+
+/** Download speed limits in bytes/sec (0 = unlimited) */
+export interface DownloadSpeedLimits {
+  torrentDownloadLimit: number;
+  torrentUploadLimit: number;
+  usenetDownloadLimit: number;
+}
+
+/** A time window that overrides the default speed limits */
+export interface DownloadScheduleWindow {
+  /** Days the window starts on (empty = every day) */
+  days: DayOfWeek[];
+  /** Server local time, HH:MM */
+  start: string;
+  /** HH:MM; at or before the start means the window runs past midnight */
+  end: string;
+  /** bytes/sec while active (null = default, 0 = unlimited) */
+  torrentDownloadLimit: number | null;
+  torrentUploadLimit: number | null;
+  usenetDownloadLimit: number | null;
+}
+
+export interface DownloadSchedule {
+  defaults: DownloadSpeedLimits;
+  /** In priority order; the first active window applies */
+  windows: DownloadScheduleWindow[];
+  active: DownloadSpeedLimits;
+  /** Index of the window in effect now (null = defaults) */
+  activeWindow: number | null;
+}
+
+export interface SetDownloadScheduleInput {
+  /** Default limits in bytes/sec (null = unchanged) */
+  torrentDownloadLimit?: number | null;
+  torrentUploadLimit?: number | null;
+  usenetDownloadLimit?: number | null;
+  /** Replaces the current windows */
+  windows: DownloadScheduleWindow[];
+}
+
 // ============================================================================
 // UPnP and Port Testing Types
 // ============================================================================
@@ -498,6 +666,8 @@ export interface RawBrowseResponse {
 
 export type LibraryType = "MOVIES" | "TV" | "MUSIC" | "AUDIOBOOKS" | "OTHER";
 export type PostDownloadAction = "COPY" | "MOVE" | "HARDLINK";
+/** What a scan does when library files are deleted from disk */
+export type MissingFilePolicy = "FLAG" | "UNMONITOR" | "REWANT";
 
 export interface Library {
   id: string;
@@ -540,6 +710,16 @@ export interface Library {
   releaseGroupBlacklist: string[];
   /** Whitelisted release groups (if set, only allow these). */
   releaseGroupWhitelist: string[];
+  /** Scanner exclude globs, relative to the library root (e.g. "**/Extras/**"). */
+  excludePatterns: string[];
+  /** Owner uid for imported files (null = global setting) */
+  fileUid: number | null;
+  /** Group gid for imported files (null = global setting) */
+  fileGid: number | null;
+  /** Octal mode for imported files, e.g. "664" (null = global setting) */
+  fileMode: string | null;
+  /** What scans do with files deleted from disk */
+  missingFilePolicy: MissingFilePolicy;
 }
 
 export interface LibraryResult {
@@ -557,6 +737,72 @@ export interface LibraryChangedEvent {
   library: Library | null;
 }
 
+/** What a scan would do with a group of new files */
+export type ScanPreviewAction = "create" | "link_existing" | "no_match" | "add_unlinked" | "not_checked";
+
+/** A group of new files in a scan preview */
+export interface ScanPreviewItem {
+  /** Show or movie name parsed from the filenames */
+  parsedName: string;
+  parsedYear: number | null;
+  fileCount: number;
+  action: ScanPreviewAction;
+  matchName: string | null;
+  matchYear: number | null;
+  provider: string | null;
+  providerId: number | null;
+  /** How closely the match fits the parsed name/year (0.0 to 1.0) */
+  confidence: number | null;
+  error: string | null;
+}
+
+/** What a library scan would do (dry run; nothing is written) */
+export interface ScanPreview {
+  libraryId: string;
+  libraryName: string;
+  totalFiles: number;
+  newFileCount: number;
+  /** New file paths (capped at 1000) */
+  newFiles: string[];
+  missingFileCount: number;
+  /** Missing file paths (capped at 1000) */
+  missingFiles: string[];
+  excludedFiles: number;
+  skippedFolders: number;
+  itemsToCreate: number;
+  items: ScanPreviewItem[];
+  notes: string[];
+}
+
+/** Files of a library sharing one value of a property */
+export interface LibraryBreakdownEntry {
+  /** The property's value, or null where it's unknown */
+  value: string | null;
+  fileCount: number;
+  totalSize: number;
+}
+
+/** A library's files grouped by quality and language, largest groups first */
+export interface LibraryBreakdown {
+  libraryId: string;
+  resolutions: LibraryBreakdownEntry[];
+  videoCodecs: LibraryBreakdownEntry[];
+  /** 'SDR' for video without HDR; null for audio-only files */
+  hdrTypes: LibraryBreakdownEntry[];
+  audioLanguages: LibraryBreakdownEntry[];
+  containers: LibraryBreakdownEntry[];
+}
+
+/** A pause of auto-hunt and RSS polling ("vacation mode") */
+export interface AutomationPause {
+  id: string;
+  /** Paused library, or null for a pause covering every library */
+  libraryId: string | null;
+  /** When automation resumes on its own (null = until resumed by hand) */
+  resumeAt: string | null;
+  createdAt: string;
+}
+
 export interface CreateLibraryInput {
   name: string;
   path: string;
@@ -580,6 +826,13 @@ export interface CreateLibraryInput {
   allowedSources?: string[];
   releaseGroupBlacklist?: string[];
   releaseGroupWhitelist?: string[];
+  /** Scanner exclude globs (e.g. "**/Extras/**", "*.sample.*", "@eaDir") */
+  excludePatterns?: string[];
+  fileUid?: number;
+  fileGid?: number;
+  /** Octal mode for imported files, e.g. "664" */
+  fileMode?: string;
+  missingFilePolicy?: MissingFilePolicy;
 }
 
 export interface UpdateLibraryInput {
@@ -604,6 +857,13 @@ export interface UpdateLibraryInput {
   allowedSources?: string[];
   releaseGroupBlacklist?: string[];
   releaseGroupWhitelist?: string[];
+  /** Scanner exclude globs (e.g. "**/Extras/**", "*.sample.*", "@eaDir") */
+  excludePatterns?: string[];
+  /** null clears the override and inherits the global setting */
+  fileUid?: number | null;
+  fileGid?: number | null;
+  fileMode?: string | null;
+  missingFilePolicy?: MissingFilePolicy;
 }
 
 // ============================================================================
@@ -635,6 +895,8 @@ export interface TvShow {
   genres: string[];
   posterUrl: string | null;
   backdropUrl: string | null;
+  posterBlurhash?: string | null;
+  backdropBlurhash?: string | null;
   monitored: boolean;
   monitorType: MonitorType;
   path: string | null;
@@ -735,6 +997,8 @@ export interface TvShowResult {
   success: boolean;
   tvShow: TvShow | null;
   error: string | null;
+  /** Metadata that couldn't be fetched yet ("tmdb", "tvdb", "episodes", "artwork"); retried in the background */
+  missingMetadata: string[];
 }
 
 export interface DownloadEpisodeResult {
@@ -812,6 +1076,8 @@ export interface Movie {
   castNames: string[];
   posterUrl: string | null;
   backdropUrl: string | null;
+  posterBlurhash?: string | null;
+  backdropBlurhash?: string | null;
   monitored: boolean;
   /** Media file ID if movie has been downloaded */
   mediaFileId: string | null;
@@ -840,6 +1106,34 @@ export interface MovieSearchResult {
   popularity: number | null;
 }
 
+/** A person's credit on a movie or show */
+export interface Credit {
+  personId: string;
+  name: string;
+  profileUrl: string | null;
+  role: "cast" | "director";
+  /** Character played, for cast */
+  character: string | null;
+  billingOrder: number | null;
+}
+
+/** One of a person's credits; exactly one of movie and tvShow is set */
+export interface FilmographyEntry {
+  role: "cast" | "director";
+  character: string | null;
+  movie: Movie | null;
+  tvShow: TvShow | null;
+}
+
+/** An actor or director with their credits in the user's libraries */
+export interface Person {
+  id: string;
+  tmdbId: number;
+  name: string;
+  profileUrl: string | null;
+  filmography: FilmographyEntry[];
+}
+
 export interface MovieResult {
   success: boolean;
   movie: Movie | null;
@@ -882,6 +1176,7 @@ export interface Album {
   country: string | null;
   releaseDate: string | null;
   coverUrl: string | null;
+  coverBlurhash?: string | null;
   trackCount: number | null;
   discCount: number | null;
   totalDurationSecs: number | null;
@@ -993,6 +1288,7 @@ export interface Audiobook {
   seriesName: string | null;
   durationSecs: number | null;
   coverUrl: string | null;
+  coverBlurhash?: string | null;
   hasFiles: boolean;
   sizeBytes: number | null;
   path: string | null;
@@ -1092,6 +1388,17 @@ export interface MediaFile {
   isManualMatch: boolean;
   addedAt: string;
   matchedAt: string | null;
+  downloadSource?: MediaFileDownloadSource | null;
+}
+
+/** The torrent or usenet download a media file was imported from */
+export interface MediaFileDownloadSource {
+  sourceType: 'torrent' | 'usenet';
+  downloadId: string;
+  name: string;
+  infoHash: string | null;
+  state: string;
+  isSeeding: boolean;
 }
 
 /** Result of a manual match operation */
@@ -1101,6 +1408,57 @@ export interface ManualMatchResult {
   mediaFile: MediaFile | null;
 }
 
+/** Result of queueing subtitle searches */
+export interface SearchSubtitlesBulkResult {
+  success: boolean;
+  queuedCount: number;
+  error: string | null;
+}
+
+// ============================================================================
+// Language Profile Types
+// ============================================================================
+
+/** Audio and subtitle language rules; languages are ISO 639-1 codes */
+export interface LanguageProfile {
+  id: string;
+  name: string;
+  /** In order of preference */
+  preferredAudioLanguages: string[];
+  /** Releases missing any of these are rejected */
+  requiredAudioLanguages: string[];
+  /** Missing ones are downloaded after import */
+  requiredSubtitleLanguages: string[];
+  /** Search again when an imported file lacks a required audio language */
+  regrabMissingAudio: boolean;
+}
+
+export interface CreateLanguageProfileInput {
+  name: string;
+  preferredAudioLanguages?: string[];
+  requiredAudioLanguages?: string[];
+  requiredSubtitleLanguages?: string[];
+  regrabMissingAudio?: boolean;
+}
+
+/** An imported file missing audio languages its profile requires */
+export interface LanguageMismatch {
+  mediaFileId: string;
+  fileName: string;
+  missingAudioLanguages: string[];
+  /** Torrent or NZB name the file was imported from */
+  releaseTitle: string | null;
+  /** Auto-hunt will search for another release */
+  regrabPending: boolean;
+  createdAt: string;
+}
+
+export interface LanguageProfileResult {
+  success: boolean;
+  error: string | null;
+  profile: LanguageProfile | null;
+}
+
 // ============================================================================
 // Detailed Media File Types (for file properties dialog)
 // ============================================================================
@@ -1139,6 +1497,14 @@ export interface AudioStreamInfo {
   title: string | null;
   isDefault: boolean;
   isCommentary: boolean;
+  /** EBU R128 integrated loudness in LUFS (null unless loudness analysis is on) */
+  integratedLoudness: number | null;
+  /** Loudness range in LU */
+  loudnessRange: number | null;
+  /** True peak in dBTP */
+  truePeak: number | null;
+  /** ReplayGain-style gain in dB that brings the stream to -18 LUFS */
+  replayGain: number | null;
 }
 
 /** Subtitle track information */
@@ -1252,6 +1618,11 @@ export interface RssFeed {
   url: string;
   enabled: boolean;
   pollIntervalMinutes: number;
+  minPollIntervalMinutes: number;
+  maxPollIntervalMinutes: number;
+  /** Interval adapted to how often the feed gets new items */
+  currentPollIntervalMinutes: number;
+  ttlMinutes: number | null;
   lastPolledAt: string | null;
   lastSuccessfulAt: string | null;
   lastError: string | null;
@@ -1289,6 +1660,8 @@ export interface CreateRssFeedInput {
   url: string;
   enabled?: boolean;
   pollIntervalMinutes?: number;
+  minPollIntervalMinutes?: number;
+  maxPollIntervalMinutes?: number;
 }
 
 export interface UpdateRssFeedInput {
@@ -1297,6 +1670,8 @@ export interface UpdateRssFeedInput {
   url?: string;
   enabled?: boolean;
   pollIntervalMinutes?: number;
+  minPollIntervalMinutes?: number;
+  maxPollIntervalMinutes?: number;
 }
 
 // ============================================================================
@@ -1527,6 +1902,37 @@ export interface IndexerResult {
   indexer: IndexerConfig | null;
 }
 
+/** Where a tracker's ratio stands relative to its minimum */
+export type TrackerRatioStatus = "OK" | "APPROACHING" | "BELOW";
+
+/** Upload/download totals for torrents grabbed from an indexer */
+export interface TrackerStats {
+  indexerId: string;
+  indexerName: string;
+  indexerType: string;
+  isPrivate: boolean;
+  uploadedBytes: number;
+  downloadedBytes: number;
+  ratio: number | null;
+  minRatio: number | null;
+  ratioStatus: TrackerRatioStatus;
+  passkeyError: string | null;
+  updatedAt: string | null;
+}
+
+/** A release handed to a download client, from the grab ledger */
+export interface GrabHistoryEntry {
+  id: string;
+  source: "auto_hunt" | "manual" | "watch_folder";
+  downloadType: "torrent" | "usenet";
+  releaseTitle: string | null;
+  infoHash: string | null;
+  releaseGuid: string | null;
+  indexerId: string | null;
+  forced: boolean;
+  createdAt: string;
+}
+
 /** Result of testing an indexer */
 export interface IndexerTestResult {
   success: boolean;
@@ -1598,6 +2004,10 @@ export type CastDeviceType =
   | "GOOGLE_HOME"
   | "GOOGLE_NEST_HUB"
   | "ANDROID_TV"
+  | "SPEAKER_GROUP"
+  | "APPLE_TV"
+  | "HOME_POD"
+  | "AIR_PLAY"
   | "UNKNOWN";
 
 /** Cast player states */
@@ -1611,6 +2021,9 @@ export interface CastDevice {
   port: number;
   model: string | null;
   deviceType: CastDeviceType;
+  isGroup: boolean;
+  isAudioOnly: boolean;
+  isAirplay: boolean;
   isFavorite: boolean;
   isManual: boolean;
   isConnected: boolean;
@@ -1694,6 +2107,10 @@ export interface StartPlaybackInput {
   startPosition?: number;
   /** Duration in seconds */
   duration?: number;
+  /** Stream lease from an earlier startPlayback on this client */
+  streamId?: string;
+  /** Client description shown to admins (e.g. "Firefox on Linux") */
+  clientName?: string;
 }
 
 /** Input for updating playback */
@@ -1709,19 +2126,199 @@ export interface UpdatePlaybackInput {
 export interface PlaybackResult {
   success: boolean;
   session: PlaybackSession | null;
+  /** Stream lease the client must heartbeat while playing */
+  streamId: string | null;
   error: string | null;
 }
 
+/** Result of a playback stream heartbeat */
+export interface PlaybackHeartbeatResult {
+  success: boolean;
+  /** False once the stream has ended; the client should stop playing */
+  active: boolean;
+  /** Why the stream ended ("stopped", "expired" or "terminated") */
+  endReason: string | null;
+  error: string | null;
+}
+
+// ============================================================================
+// Watch State Types
+// ============================================================================
+
+/** A user's watch state for one item */
+export interface WatchState {
+  userId: string;
+  contentType: PlaybackContentType;
+  /** The episode/movie/track/audiobook ID */
+  contentId: string;
+  mediaFileId: string | null;
+  /** Resume position in seconds */
+  position: number;
+  duration: number | null;
+  /** 0.0 to 1.0 */
+  progress: number;
+  isWatched: boolean;
+  lastWatchedAt: string;
+}
+
+/** An item to resume, with the episode or movie it belongs to */
+export interface ContinueWatchingItem {
+  state: WatchState;
+  episode: Episode | null;
+  show: TvShow | null;
+  movie: Movie | null;
+}
+
+/** The next episode to watch of a show the user is following along */
+export interface NextUpItem {
+  show: TvShow;
+  episode: Episode;
+}
+
+/** A global search hit; `score` is how well it matched, 0.0 to 1.0 */
+export type GlobalSearchHit =
+  | { __typename: "MovieSearchHit"; score: number; movie: Movie }
+  | { __typename: "ShowSearchHit"; score: number; show: TvShow }
+  | { __typename: "EpisodeSearchHit"; score: number; episode: Episode; show: TvShow }
+  | { __typename: "AlbumSearchHit"; score: number; album: Album }
+  | { __typename: "ArtistSearchHit"; score: number; artist: Artist }
+  | { __typename: "AudiobookSearchHit"; score: number; audiobook: Audiobook }
+  | { __typename: "PersonSearchHit"; score: number; name: string; roles: string[] };
+
+/** A stream currently being played by some client (admin view) */
+export interface ActivePlaybackStream {
+  id: string;
+  userId: string;
+  username: string | null;
+  contentType: string | null;
+  mediaFileId: string | null;
+  clientName: string | null;
+  currentPosition: number | null;
+  duration: number | null;
+  isPlaying: boolean;
+  startedAt: string;
+  lastHeartbeatAt: string;
+}
+
 /** Playback settings (configurable by user) */
 export interface PlaybackSettings {
   /** How often to sync watch progress to database (in seconds) */
   syncIntervalSeconds: number;
+  /** Most streams a user may play at once (0 = unlimited) */
+  maxConcurrentStreams: number;
+  /** Seconds without a heartbeat before a stream is considered abandoned */
+  heartbeatTimeoutSeconds: number;
 }
 
 /** Input for updating playback settings */
 export interface UpdatePlaybackSettingsInput {
   /** How often to sync watch progress to database (in seconds, 5-60) */
   syncIntervalSeconds?: number;
+  /** Most streams a user may play at once (0 = unlimited, max 20) */
+  maxConcurrentStreams?: number;
+  /** Seconds without a heartbeat before a stream is abandoned (30-600) */
+  heartbeatTimeoutSeconds?: number;
+}
+
+/** Transcode cache usage */
+export interface TranscodeCacheStats {
+  totalBytes: number;
+  /** Size cap in GB (0 = unlimited) */
+  maxSizeGb: number;
+  /** Number of transcode sessions in the cache */
+  sessionCount: number;
+  /** Sessions currently being played (never evicted) */
+  pinnedCount: number;
+  pinnedBytes: number;
+  /** Last use of the least recently used session */
+  oldestEntryAt: string | null;
+}
+
+export type DiagnosticStatus = "PASS" | "WARN" | "FAIL";
+
+/** One environment diagnostics check */
+export interface DiagnosticCheck {
+  /** Stable identifier, e.g. "path.downloads" or "ffmpeg" */
+  id: string;
+  title: string;
+  status: DiagnosticStatus;
+  message: string;
+  /** How to fix a warning or failure */
+  hint: string | null;
+}
+
+export type ExternalServiceHealth = "UP" | "DEGRADED" | "DOWN";
+
+/** An external service's last probe result */
+export interface ExternalServiceStatus {
+  /** Stable identifier, e.g. "tmdb" or "indexer:<id>" */
+  id: string;
+  /** "tmdb", "tvdb", "opensubtitles", "indexer", "usenet", "smtp" or "cast" */
+  kind: string;
+  name: string;
+  status: ExternalServiceHealth;
+  latencyMs: number | null;
+  /** Why the last probe failed */
+  lastError: string | null;
+  lastCheckedAt: string;
+  /** When the status last changed */
+  lastChangedAt: string;
+}
+
+/** Health of every configured external service */
+export interface ExternalServicesStatus {
+  services: ExternalServiceStatus[];
+  downCount: number;
+  degradedCount: number;
+}
+
+/** Result of clearing the transcode cache */
+export interface ClearTranscodeCacheResult {
+  success: boolean;
+  sessionsRemoved: number;
+  bytesFreed: number;
+  /** Sessions left in place because they are being played */
+  sessionsSkipped: number;
+  error: string | null;
+}
+
+/** A Librarian release newer than the running server */
+export interface UpdateInfo {
+  /** Version of the running server */
+  currentVersion: string;
+  /** Version of the new release */
+  version: string;
+  name: string | null;
+  /** Release page on GitHub */
+  url: string;
+  publishedAt: string | null;
+  /** Start of the release notes */
+  changelog: string;
+}
+
+/** Update check settings and the result of the last check */
+export interface UpdateCheckSettings {
+  /** Whether the server checks GitHub for new releases every six hours */
+  enabled: boolean;
+  /** Latest release found by the last check */
+  latestVersion: string | null;
+  lastCheckedAt: string | null;
+  /** Why applyUpdate can't be used on this install (e.g. Docker), or null */
+  selfUpdateUnavailableReason: string | null;
+}
+
+export interface CheckForUpdatesResult {
+  success: boolean;
+  /** The newer release, or null when up to date */
+  update: UpdateInfo | null;
+  error: string | null;
+}
+
+export interface ApplyUpdateResult {
+  success: boolean;
+  /** Version being installed; the server restarts into it shortly after */
+  version: string | null;
+  error: string | null;
 }
 
 /** Input for adding a cast device manually */
@@ -1794,7 +2391,7 @@ export interface AutoHuntResult {
 // Filter Types (for GraphQL where clauses)
 // ============================================================================
 
-/** Filter for string fields */
+/** Filter for string fields (case-insensitive unless mode is CASE_SENSITIVE) */
 export interface StringFilter {
   eq?: string;
   ne?: string;
@@ -1803,8 +2400,12 @@ export interface StringFilter {
   endsWith?: string;
   in?: string[];
   notIn?: string[];
+  mode?: StringFilterMode;
 }
 
+/** How a string filter compares values */
+export type StringFilterMode = "CASE_INSENSITIVE" | "CASE_SENSITIVE";
+
 /** Filter for integer fields */
 export interface IntFilter {
   eq?: number;
@@ -1817,6 +2418,31 @@ export interface IntFilter {
   notIn?: number[];
 }
 
+/** Filter for list fields such as genres or cast (case-insensitive) */
+export interface ListFilter {
+  hasAny?: string[];
+  hasAll?: string[];
+  hasNone?: string[];
+}
+
+/** Range for float between queries */
+export interface FloatRange {
+  start?: number;
+  end?: number;
+}
+
+/** Filter for floating point fields; bounds match within epsilon */
+export interface FloatFilter {
+  eq?: number;
+  lt?: number;
+  lte?: number;
+  gt?: number;
+  gte?: number;
+  between?: FloatRange;
+  /** Tolerance for comparisons (default 0.000001) */
+  epsilon?: number;
+}
+
 /** Filter for boolean fields */
 export interface BoolFilter {
   eq?: boolean;
@@ -1838,7 +2464,24 @@ export interface DateFilter {
   gt?: string;
   gte?: string;
   between?: DateRange;
-}
+  /** Calendar period relative to today */
+  relative?: RelativeDate;
+  /** Older than an ISO 8601 duration ago, e.g. "P30D" */
+  olderThan?: string;
+  /** Newer than an ISO 8601 duration ago */
+  newerThan?: string;
+  /** Offset from UTC in minutes (e.g. -300 for UTC-5); defaults to UTC */
+  utcOffsetMinutes?: number;
+}
+
+/** Calendar periods for relative date filters */
+export type RelativeDate =
+  | "TODAY"
+  | "YESTERDAY"
+  | "TOMORROW"
+  | "THIS_WEEK"
+  | "LAST_WEEK"
+  | "THIS_MONTH";
 
 // ============================================================================
 // Pagination Types (cursor-based)
@@ -1880,6 +2523,9 @@ export interface MovieWhereInput {
   hasFile?: BoolFilter;
   status?: StringFilter;
   createdAt?: DateFilter;
+  tmdbRating?: FloatFilter;
+  genres?: ListFilter;
+  castNames?: ListFilter;
 }
 
 /** Order by input for movies */
@@ -1964,7 +2610,10 @@ export type NotificationCategory =
   | "QUALITY"
   | "STORAGE"
   | "EXTRACTION"
-  | "CONFIGURATION";
+  | "CONFIGURATION"
+  | "SCHEDULE"
+  | "UPDATE"
+  | "SERVICES";
 export type NotificationActionType =
   | "CONFIRM_UPGRADE"
   | "MANUAL_MATCH"
@@ -2073,3 +2722,99 @@ export interface ContentDownloadProgressEvent {
   /** Parent ID (show_id for episodes, album_id for tracks, audiobook_id for chapters) */
   parentId: string | null;
 }
+
+// ============================================================================
+// Background Job Types
+// ============================================================================
+
+/** Status of a background job */
+export type BackgroundJobStatus = "queued" | "running" | "completed" | "failed" | "interrupted";
+
+/** A long-running operation started by a mutation */
+export interface BackgroundJob {
+  id: string;
+  /** What the job does, e.g. "consolidate_library" */
+  kind: string;
+  status: BackgroundJobStatus;
+  /** Progress (0.0 to 1.0), when the job reports it */
+  progress: number | null;
+  message: string | null;
+  /** Input the job was started with */
+  payload: unknown;
+  /** Output of a completed job (shape depends on the kind) */
+  result: unknown;
+  error: string | null;
+  userId: string | null;
+  isFinished: boolean;
+  createdAt: string;
+  startedAt: string | null;
+  finishedAt: string | null;
+}
+
+/** Result of a mutation that runs as a background job */
+export interface JobQueuedResult {
+  success: boolean;
+  error: string | null;
+  /** ID of the background job doing the work */
+  jobId: string | null;
+}
+
+// ============================================================================
+// Audit Log Types
+// ============================================================================
+
+/** A field change: `from` is absent on create, `to` on delete */
+export interface AuditFieldChange {
+  from?: unknown;
+  to?: unknown;
+}
+
+/** A create, update or delete made through a mutation */
+export interface AuditLogEntry {
+  id: string;
+  /** Who made the change (null if the user was deleted) */
+  userId: string | null;
+  /** e.g. "library", "rss_feed" */
+  entityType: string;
+  entityId: string;
+  action: "create" | "update" | "delete";
+  /** Changed fields; secrets show as "[redacted]" */
+  changes: Record<string, AuditFieldChange>;
+  createdAt: string;
+}
+
+/** Kind of item whose artwork can be refreshed */
+export type ArtworkEntityType = "SHOW" | "MOVIE" | "ALBUM";
+
+/** Result of refreshing one item's artwork */
+export interface RefreshArtworkResult {
+  success: boolean;
+  error: string | null;
+  /** Poster (or album cover) URL after the refresh */
+  posterUrl: string | null;
+  backdropUrl: string | null;
+  /** Images downloaded and cached again */
+  imagesUpdated: number;
+  imagesFailed: number;
+}
+
+/** Result stored by a finished artwork_audit job */
+export interface ArtworkAuditResult {
+  itemsChecked: number;
+  /** Images that were cached and still are */
+  imagesOk: number;
+  /** Broken or uncached images downloaded again from their source */
+  imagesRecached: number;
+  /** Images fetched from the metadata provider (missing or dead) */
+  imagesFetched: number;
+  /** Images that couldn't be fixed */
+  imagesFailed: number;
+}
+
+/** Result stored by a finished consolidate_library job */
+export interface ConsolidateLibraryResult {
+  success: boolean;
+  foldersRemoved: number;
+  filesMoved: number;
+  messages: string[];
+}
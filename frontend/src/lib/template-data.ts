@@ -287,6 +287,10 @@ export const rssFeedsTemplate: RssFeed[] = Array.from({ length: 3 }, (_, i) => (
   url: 'https://example.com/rss',
   enabled: true,
   pollIntervalMinutes: 15,
+  minPollIntervalMinutes: 5,
+  maxPollIntervalMinutes: 120,
+  currentPollIntervalMinutes: 15,
+  ttlMinutes: null,
   lastPolledAt: null,
   lastSuccessfulAt: null,
   lastError: null,
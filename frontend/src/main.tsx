@@ -44,6 +44,8 @@ interface MeUser {
   id: string;
   email: string | null;
   role: string | null;
+  displayName: string | null;
+  avatarUrl: string | null;
 }
 
 interface MeResponse {
@@ -177,7 +179,9 @@ function InnerApp() {
                 email: meUser.email || undefined,
                 username: existingSession.user.username,
                 role: meUser.role || "member",
-                displayName: existingSession.user.displayName,
+                displayName:
+                  meUser.displayName ?? existingSession.user.displayName,
+                avatarUrl: meUser.avatarUrl ?? undefined,
               };
               setAuth({
                 isAuthenticated: true,
@@ -20,6 +20,7 @@ import { Route as DownloadsIndexRouteImport } from './routes/downloads/index'
 import { Route as ShowsShowIdRouteImport } from './routes/shows/$showId'
 import { Route as SettingsUsenetRouteImport } from './routes/settings/usenet'
 import { Route as SettingsTorrentRouteImport } from './routes/settings/torrent'
+import { Route as SettingsSystemRouteImport } from './routes/settings/system'
 import { Route as SettingsSourcePrioritiesRouteImport } from './routes/settings/source-priorities'
 import { Route as SettingsRssRouteImport } from './routes/settings/rss'
 import { Route as SettingsOrganizationRouteImport } from './routes/settings/organization'
@@ -29,7 +30,9 @@ import { Route as SettingsIndexersRouteImport } from './routes/settings/indexers
 import { Route as SettingsCastingRouteImport } from './routes/settings/casting'
 import { Route as MoviesMovieIdRouteImport } from './routes/movies/$movieId'
 import { Route as LibrariesLibraryIdRouteImport } from './routes/libraries/$libraryId'
+import { Route as AuthConfirmEmailRouteImport } from './routes/auth/confirm-email'
 import { Route as AuthLoginRouteImport } from './routes/auth/login'
+import { Route as AuthResetPasswordRouteImport } from './routes/auth/reset-password'
 import { Route as AudiobooksAudiobookIdRouteImport } from './routes/audiobooks/$audiobookId'
 import { Route as AlbumsAlbumIdRouteImport } from './routes/albums/$albumId'
 import { Route as LibrariesLibraryIdIndexRouteImport } from './routes/libraries/$libraryId/index'
@@ -100,6 +103,11 @@ const SettingsTorrentRoute = SettingsTorrentRouteImport.update({
   path: '/torrent',
   getParentRoute: () => SettingsRoute,
 } as any)
+const SettingsSystemRoute = SettingsSystemRouteImport.update({
+  id: '/system',
+  path: '/system',
+  getParentRoute: () => SettingsRoute,
+} as any)
 const SettingsSourcePrioritiesRoute =
   SettingsSourcePrioritiesRouteImport.update({
     id: '/source-priorities',
@@ -146,11 +154,21 @@ const LibrariesLibraryIdRoute = LibrariesLibraryIdRouteImport.update({
   path: '/libraries/$libraryId',
   getParentRoute: () => rootRouteImport,
 } as any)
+const AuthResetPasswordRoute = AuthResetPasswordRouteImport.update({
+  id: '/auth/reset-password',
+  path: '/auth/reset-password',
+  getParentRoute: () => rootRouteImport,
+} as any)
 const AuthLoginRoute = AuthLoginRouteImport.update({
   id: '/auth/login',
   path: '/auth/login',
   getParentRoute: () => rootRouteImport,
 } as any)
+const AuthConfirmEmailRoute = AuthConfirmEmailRouteImport.update({
+  id: '/auth/confirm-email',
+  path: '/auth/confirm-email',
+  getParentRoute: () => rootRouteImport,
+} as any)
 const AudiobooksAudiobookIdRoute = AudiobooksAudiobookIdRouteImport.update({
   id: '/audiobooks/$audiobookId',
   path: '/audiobooks/$audiobookId',
@@ -239,7 +257,9 @@ export interface FileRoutesByFullPath {
   '/settings': typeof SettingsRouteWithChildren
   '/albums/$albumId': typeof AlbumsAlbumIdRoute
   '/audiobooks/$audiobookId': typeof AudiobooksAudiobookIdRoute
+  '/auth/confirm-email': typeof AuthConfirmEmailRoute
   '/auth/login': typeof AuthLoginRoute
+  '/auth/reset-password': typeof AuthResetPasswordRoute
   '/libraries/$libraryId': typeof LibrariesLibraryIdRouteWithChildren
   '/movies/$movieId': typeof MoviesMovieIdRoute
   '/settings/casting': typeof SettingsCastingRoute
@@ -249,6 +269,7 @@ export interface FileRoutesByFullPath {
   '/settings/organization': typeof SettingsOrganizationRoute
   '/settings/rss': typeof SettingsRssRoute
   '/settings/source-priorities': typeof SettingsSourcePrioritiesRoute
+  '/settings/system': typeof SettingsSystemRoute
   '/settings/torrent': typeof SettingsTorrentRoute
   '/settings/usenet': typeof SettingsUsenetRoute
   '/shows/$showId': typeof ShowsShowIdRoute
@@ -275,7 +296,9 @@ export interface FileRoutesByTo {
   '/search': typeof SearchRoute
   '/albums/$albumId': typeof AlbumsAlbumIdRoute
   '/audiobooks/$audiobookId': typeof AudiobooksAudiobookIdRoute
+  '/auth/confirm-email': typeof AuthConfirmEmailRoute
   '/auth/login': typeof AuthLoginRoute
+  '/auth/reset-password': typeof AuthResetPasswordRoute
   '/movies/$movieId': typeof MoviesMovieIdRoute
   '/settings/casting': typeof SettingsCastingRoute
   '/settings/indexers': typeof SettingsIndexersRoute
@@ -284,6 +307,7 @@ export interface FileRoutesByTo {
   '/settings/organization': typeof SettingsOrganizationRoute
   '/settings/rss': typeof SettingsRssRoute
   '/settings/source-priorities': typeof SettingsSourcePrioritiesRoute
+  '/settings/system': typeof SettingsSystemRoute
   '/settings/torrent': typeof SettingsTorrentRoute
   '/settings/usenet': typeof SettingsUsenetRoute
   '/shows/$showId': typeof ShowsShowIdRoute
@@ -312,7 +336,9 @@ export interface FileRoutesById {
   '/settings': typeof SettingsRouteWithChildren
   '/albums/$albumId': typeof AlbumsAlbumIdRoute
   '/audiobooks/$audiobookId': typeof AudiobooksAudiobookIdRoute
+  '/auth/confirm-email': typeof AuthConfirmEmailRoute
   '/auth/login': typeof AuthLoginRoute
+  '/auth/reset-password': typeof AuthResetPasswordRoute
   '/libraries/$libraryId': typeof LibrariesLibraryIdRouteWithChildren
   '/movies/$movieId': typeof MoviesMovieIdRoute
   '/settings/casting': typeof SettingsCastingRoute
@@ -322,6 +348,7 @@ export interface FileRoutesById {
   '/settings/organization': typeof SettingsOrganizationRoute
   '/settings/rss': typeof SettingsRssRoute
   '/settings/source-priorities': typeof SettingsSourcePrioritiesRoute
+  '/settings/system': typeof SettingsSystemRoute
   '/settings/torrent': typeof SettingsTorrentRoute
   '/settings/usenet': typeof SettingsUsenetRoute
   '/shows/$showId': typeof ShowsShowIdRoute
@@ -351,7 +378,9 @@ export interface FileRouteTypes {
     | '/settings'
     | '/albums/$albumId'
     | '/audiobooks/$audiobookId'
+    | '/auth/confirm-email'
     | '/auth/login'
+    | '/auth/reset-password'
     | '/libraries/$libraryId'
     | '/movies/$movieId'
     | '/settings/casting'
@@ -361,6 +390,7 @@ export interface FileRouteTypes {
     | '/settings/organization'
     | '/settings/rss'
     | '/settings/source-priorities'
+    | '/settings/system'
     | '/settings/torrent'
     | '/settings/usenet'
     | '/shows/$showId'
@@ -387,7 +417,9 @@ export interface FileRouteTypes {
     | '/search'
     | '/albums/$albumId'
     | '/audiobooks/$audiobookId'
+    | '/auth/confirm-email'
     | '/auth/login'
+    | '/auth/reset-password'
     | '/movies/$movieId'
     | '/settings/casting'
     | '/settings/indexers'
@@ -396,6 +428,7 @@ export interface FileRouteTypes {
     | '/settings/organization'
     | '/settings/rss'
     | '/settings/source-priorities'
+    | '/settings/system'
     | '/settings/torrent'
     | '/settings/usenet'
     | '/shows/$showId'
@@ -423,7 +456,9 @@ export interface FileRouteTypes {
     | '/settings'
     | '/albums/$albumId'
     | '/audiobooks/$audiobookId'
+    | '/auth/confirm-email'
     | '/auth/login'
+    | '/auth/reset-password'
     | '/libraries/$libraryId'
     | '/movies/$movieId'
     | '/settings/casting'
@@ -433,6 +468,7 @@ export interface FileRouteTypes {
     | '/settings/organization'
     | '/settings/rss'
     | '/settings/source-priorities'
+    | '/settings/system'
     | '/settings/torrent'
     | '/settings/usenet'
     | '/shows/$showId'
@@ -461,7 +497,9 @@ export interface RootRouteChildren {
   SettingsRoute: typeof SettingsRouteWithChildren
   AlbumsAlbumIdRoute: typeof AlbumsAlbumIdRoute
   AudiobooksAudiobookIdRoute: typeof AudiobooksAudiobookIdRoute
+  AuthConfirmEmailRoute: typeof AuthConfirmEmailRoute
   AuthLoginRoute: typeof AuthLoginRoute
+  AuthResetPasswordRoute: typeof AuthResetPasswordRoute
   LibrariesLibraryIdRoute: typeof LibrariesLibraryIdRouteWithChildren
   MoviesMovieIdRoute: typeof MoviesMovieIdRoute
   ShowsShowIdRoute: typeof ShowsShowIdRoute
@@ -548,6 +586,13 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof SettingsTorrentRouteImport
       parentRoute: typeof SettingsRoute
     }
+    '/settings/system': {
+      id: '/settings/system'
+      path: '/system'
+      fullPath: '/settings/system'
+      preLoaderRoute: typeof SettingsSystemRouteImport
+      parentRoute: typeof SettingsRoute
+    }
     '/settings/source-priorities': {
       id: '/settings/source-priorities'
       path: '/source-priorities'
@@ -611,6 +656,13 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof LibrariesLibraryIdRouteImport
       parentRoute: typeof rootRouteImport
     }
+    '/auth/reset-password': {
+      id: '/auth/reset-password'
+      path: '/auth/reset-password'
+      fullPath: '/auth/reset-password'
+      preLoaderRoute: typeof AuthResetPasswordRouteImport
+      parentRoute: typeof rootRouteImport
+    }
     '/auth/login': {
       id: '/auth/login'
       path: '/auth/login'
@@ -618,6 +670,13 @@ declare module '@tanstack/react-router' {
       preLoaderRoute: typeof AuthLoginRouteImport
       parentRoute: typeof rootRouteImport
     }
+    '/auth/confirm-email': {
+      id: '/auth/confirm-email'
+      path: '/auth/confirm-email'
+      fullPath: '/auth/confirm-email'
+      preLoaderRoute: typeof AuthConfirmEmailRouteImport
+      parentRoute: typeof rootRouteImport
+    }
     '/audiobooks/$audiobookId': {
       id: '/audiobooks/$audiobookId'
       path: '/audiobooks/$audiobookId'
@@ -727,6 +786,7 @@ interface SettingsRouteChildren {
   SettingsOrganizationRoute: typeof SettingsOrganizationRoute
   SettingsRssRoute: typeof SettingsRssRoute
   SettingsSourcePrioritiesRoute: typeof SettingsSourcePrioritiesRoute
+  SettingsSystemRoute: typeof SettingsSystemRoute
   SettingsTorrentRoute: typeof SettingsTorrentRoute
   SettingsUsenetRoute: typeof SettingsUsenetRoute
   SettingsIndexRoute: typeof SettingsIndexRoute
@@ -740,6 +800,7 @@ const SettingsRouteChildren: SettingsRouteChildren = {
   SettingsOrganizationRoute: SettingsOrganizationRoute,
   SettingsRssRoute: SettingsRssRoute,
   SettingsSourcePrioritiesRoute: SettingsSourcePrioritiesRoute,
+  SettingsSystemRoute: SettingsSystemRoute,
   SettingsTorrentRoute: SettingsTorrentRoute,
   SettingsUsenetRoute: SettingsUsenetRoute,
   SettingsIndexRoute: SettingsIndexRoute,
@@ -790,7 +851,9 @@ const rootRouteChildren: RootRouteChildren = {
   SettingsRoute: SettingsRouteWithChildren,
   AlbumsAlbumIdRoute: AlbumsAlbumIdRoute,
   AudiobooksAudiobookIdRoute: AudiobooksAudiobookIdRoute,
+  AuthConfirmEmailRoute: AuthConfirmEmailRoute,
   AuthLoginRoute: AuthLoginRoute,
+  AuthResetPasswordRoute: AuthResetPasswordRoute,
   LibrariesLibraryIdRoute: LibrariesLibraryIdRouteWithChildren,
   MoviesMovieIdRoute: MoviesMovieIdRoute,
   ShowsShowIdRoute: ShowsShowIdRoute,
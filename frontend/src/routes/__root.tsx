@@ -8,6 +8,7 @@ import { Navbar } from '../components/Navbar'
 import { NotFound } from '../components/NotFound'
 import { ErrorLogToaster } from '../components/ErrorLogToaster'
 import { GraphQLErrorToaster } from '../components/GraphQLErrorToaster'
+import { SchemaUpdateToaster } from '../components/SchemaUpdateToaster'
 import { PersistentPlayer } from '../components/PersistentPlayer'
 import { PersistentAudioPlayer } from '../components/PersistentAudioPlayer'
 import { CastControlBar } from '../components/cast'
@@ -97,6 +98,9 @@ function RootLayoutContent() {
       {/* GraphQL error toaster - shows toast for GraphQL/network errors */}
       <GraphQLErrorToaster />
 
+      {/* Prompts a refresh when the server is upgraded mid-session */}
+      <SchemaUpdateToaster />
+
       {/* Persistent video player - shows when video content is playing */}
       <PersistentPlayer />
 
@@ -0,0 +1,82 @@
+import { createFileRoute, Link } from '@tanstack/react-router'
+import { useEffect, useRef, useState } from 'react'
+import { Card, CardBody } from '@heroui/card'
+import { Spinner } from '@heroui/spinner'
+import {
+  graphqlClient,
+  CONFIRM_EMAIL_CHANGE_MUTATION,
+  type AuthMutationResult,
+} from '../../lib/graphql'
+
+interface ConfirmEmailSearchParams {
+  token?: string
+}
+
+// Landing page for the link in an email change confirmation mail
+export const Route = createFileRoute('/auth/confirm-email')({
+  validateSearch: (search: Record<string, unknown>): ConfirmEmailSearchParams => {
+    return {
+      token: typeof search.token === 'string' ? search.token : undefined,
+    }
+  },
+  component: ConfirmEmailPage,
+})
+
+function ConfirmEmailPage() {
+  const { token } = Route.useSearch()
+  const [status, setStatus] = useState<'pending' | 'done' | 'failed'>('pending')
+  const [error, setError] = useState<string | null>(null)
+  // Tokens are single-use, so only ever send it once
+  const sent = useRef(false)
+
+  useEffect(() => {
+    if (sent.current) return
+    sent.current = true
+
+    if (!token) {
+      setStatus('failed')
+      setError('This link is missing its confirmation token.')
+      return
+    }
+
+    graphqlClient
+      .mutation<{ confirmEmailChange: AuthMutationResult }>(CONFIRM_EMAIL_CHANGE_MUTATION, { token })
+      .toPromise()
+      .then((result) => {
+        const payload = result.data?.confirmEmailChange
+        if (payload?.success) {
+          setStatus('done')
+        } else {
+          setStatus('failed')
+          setError(payload?.error ?? result.error?.message ?? 'Confirmation failed')
+        }
+      })
+  }, [token])
+
+  return (
+    <div className="flex justify-center p-8">
+      <Card className="w-full max-w-md">
+        <CardBody className="gap-3 text-center">
+          {status === 'pending' && <Spinner size="lg" />}
+          {status === 'done' && (
+            <>
+              <p className="text-lg font-semibold">Email address changed</p>
+              <p className="text-default-500">Sign in with your new address from now on.</p>
+            </>
+          )}
+          {status === 'failed' && (
+            <>
+              <p className="text-lg font-semibold">Couldn't confirm your email</p>
+              <p className="text-default-500">{error}</p>
+            </>
+          )}
+          {status !== 'pending' && (
+            <Link to="/" className="text-primary">
+              Back to Librarian
+            </Link>
+          )}
+        </CardBody>
+      </Card>
+    </div>
+  )
+}
@@ -0,0 +1,108 @@
+import { createFileRoute, Link } from '@tanstack/react-router'
+import { useState } from 'react'
+import { Button } from '@heroui/button'
+import { Card, CardBody } from '@heroui/card'
+import { Input } from '@heroui/input'
+import {
+  graphqlClient,
+  RESET_PASSWORD_MUTATION,
+  type AuthMutationResult,
+} from '../../lib/graphql'
+
+interface ResetPasswordSearchParams {
+  token?: string
+}
+
+// Landing page for the link in a password reset mail
+export const Route = createFileRoute('/auth/reset-password')({
+  validateSearch: (search: Record<string, unknown>): ResetPasswordSearchParams => {
+    return {
+      token: typeof search.token === 'string' ? search.token : undefined,
+    }
+  },
+  component: ResetPasswordPage,
+})
+
+function ResetPasswordPage() {
+  const { token } = Route.useSearch()
+  const [password, setPassword] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const [loading, setLoading] = useState(false)
+  const [done, setDone] = useState(false)
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault()
+    setError(null)
+
+    if (password !== confirm) {
+      setError("The passwords don't match")
+      return
+    }
+
+    setLoading(true)
+    const result = await graphqlClient
+      .mutation<{ resetPassword: AuthMutationResult }>(RESET_PASSWORD_MUTATION, {
+        token,
+        newPassword: password,
+      })
+      .toPromise()
+    setLoading(false)
+
+    const payload = result.data?.resetPassword
+    if (payload?.success) {
+      setDone(true)
+    } else {
+      setError(payload?.error ?? result.error?.message ?? 'Password reset failed')
+    }
+  }
+
+  return (
+    <div className="flex justify-center p-8">
+      <Card className="w-full max-w-md">
+        <CardBody className="gap-4">
+          <p className="text-lg font-semibold text-center">Choose a new password</p>
+          {!token ? (
+            <p className="text-default-500 text-center">
+              This link is missing its reset token. Request a new one from the sign-in screen.
+            </p>
+          ) : done ? (
+            <>
+              <p className="text-default-500 text-center">
+                Your password has been changed and your other sessions signed out.
+              </p>
+              <Link to="/" search={{ signin: true }} className="text-primary text-center">
+                Sign in
+              </Link>
+            </>
+          ) : (
+            <form onSubmit={handleSubmit} className="flex flex-col gap-4">
+              <Input
+                type="password"
+                label="New password"
+                value={password}
+                onChange={(e) => setPassword(e.target.value)}
+                isRequired
+                minLength={8}
+                autoComplete="new-password"
+                description="At least 8 characters, mixing upper and lower case, digits or symbols (or 16+ characters)"
+              />
+              <Input
+                type="password"
+                label="Confirm new password"
+                value={confirm}
+                onChange={(e) => setConfirm(e.target.value)}
+                isRequired
+                autoComplete="new-password"
+              />
+              {error && <p className="text-sm text-danger">{error}</p>}
+              <Button type="submit" color="primary" isLoading={loading}>
+                Set Password
+              </Button>
+            </form>
+          )}
+        </CardBody>
+      </Card>
+    </div>
+  )
+}
@@ -31,7 +31,7 @@ import {
   type ProcessSourceResult,
   type RematchSourceResult,
 } from '../../lib/graphql'
-import { TorrentTable, AddTorrentModal, TorrentInfoModal, LinkToLibraryModal } from '../../components/downloads'
+import { TorrentTable, AddTorrentModal, TorrentInfoModal, LinkToLibraryModal, TransferSparkline } from '../../components/downloads'
 import { sanitizeError, formatBytes } from '../../lib/format'
 import { RouteError } from '../../components/RouteError'
 
@@ -46,6 +46,8 @@ interface UsenetDownload {
   downloadSpeed: number
   etaSeconds: number | null
   errorMessage: string | null
+  repairStatus: string | null
+  repairBlocksNeeded: number | null
 }
 
 // Usenet GraphQL queries
@@ -61,6 +63,8 @@ const USENET_DOWNLOADS_QUERY = `
       downloadSpeed
       etaSeconds
       errorMessage
+      repairStatus
+      repairBlocksNeeded
     }
   }
 `
@@ -634,6 +638,9 @@ function DownloadsPage() {
       {/* Tab Content */}
       {activeTab === 'torrents' ? (
         <>
+          {/* Combined transfer rate over the last few minutes */}
+          <TransferSparkline className="mb-4" />
+
           {/* Torrents table - skeleton loading handled by DataTable */}
           <TorrentTable
             torrents={torrents}
@@ -698,11 +705,12 @@ function DownloadsPage() {
                           color={
                             download.state === 'completed' ? 'success' :
                             download.state === 'downloading' ? 'primary' :
+                            download.state === 'repairing' ? 'secondary' :
                             download.state === 'paused' ? 'warning' :
-                            download.state === 'failed' ? 'danger' : 'default'
+                            download.state === 'failed' || download.state === 'repair_failed' ? 'danger' : 'default'
                           }
                         >
-                          {download.state}
+                          {download.state.replace('_', ' ')}
                         </Chip>
                       </div>
                       <div className="flex items-center gap-4 text-sm text-default-500">
@@ -713,6 +721,9 @@ function DownloadsPage() {
                         {download.etaSeconds && download.etaSeconds > 0 && (
                           <span>ETA: {Math.floor(download.etaSeconds / 60)}m</span>
                         )}
+                        {download.repairStatus === 'repaired' && (
+                          <span>Repaired {download.repairBlocksNeeded ?? 0} blocks</span>
+                        )}
                       </div>
                       {download.state === 'downloading' && (
                         <Progress
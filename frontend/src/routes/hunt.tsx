@@ -252,7 +252,7 @@ function SearchPage() {
   };
 
   const handleDownload = useCallback(
-    async (release: TorrentRelease) => {
+    async (release: TorrentRelease, force = false): Promise<void> => {
       // Prefer magnet link, fall back to torrent file URL
       const magnetUri = release.magnetUri;
       const torrentUrl = release.link;
@@ -295,6 +295,9 @@ function SearchPage() {
             input: {
               magnet: isMagnet ? magnetUri : undefined,
               url: !isMagnet ? magnetUri || torrentUrl : undefined,
+              // Title lets the server skip releases that were already grabbed
+              name: release.title,
+              force,
               // Pass indexer ID for authenticated .torrent downloads
               indexerId:
                 !isMagnet && release.indexerId ? release.indexerId : undefined,
@@ -316,6 +319,12 @@ function SearchPage() {
             description: `Added: ${release.title}`,
             color: "success",
           });
+        } else if (
+          !force &&
+          data?.addTorrent?.error?.startsWith("Release was already grabbed") &&
+          confirm(`${data.addTorrent.error}\n\nGrab it again?`)
+        ) {
+          return handleDownload(release, true);
         } else {
           throw new Error(data?.addTorrent?.error || "Failed to add torrent");
         }
@@ -454,7 +463,7 @@ function SearchPage() {
         key: "download",
         label: "Download",
         icon: <IconDownload size={16} />,
-        onAction: handleDownload,
+        onAction: (release: TorrentRelease) => handleDownload(release),
       },
       {
         key: "add-to-library",
@@ -35,6 +35,7 @@ import { RouteError } from "../../components/RouteError";
 import {
   AddShowModal,
   LibraryLayout,
+  ScanPreviewModal,
   type LibraryTab,
 } from "../../components/library";
 import { sanitizeError } from "../../lib/format";
@@ -113,6 +114,11 @@ function LibraryDetailLayout() {
     onOpen: onConfirmOpen,
     onClose: onConfirmClose,
   } = useDisclosure();
+  const {
+    isOpen: isPreviewOpen,
+    onOpen: onPreviewOpen,
+    onClose: onPreviewClose,
+  } = useDisclosure();
   const [library, setLibrary] = useState<Library | null>(null);
   const [tvShows, setTvShows] = useState<TvShow[]>([]);
   const [loading, setLoading] = useState(true);
@@ -491,6 +497,14 @@ function LibraryDetailLayout() {
                   codecs={displayLibrary.allowedVideoCodecs || []}
                   requireHdr={displayLibrary.requireHdr || false}
                 />
+                <Button
+                  variant="flat"
+                  size="sm"
+                  onPress={onPreviewOpen}
+                  isDisabled={loading || isScanning || displayLibrary.scanning}
+                >
+                  Preview Scan
+                </Button>
                 <Button
                   color="primary"
                   variant="flat"
@@ -517,6 +531,14 @@ function LibraryDetailLayout() {
           <Outlet />
         </LibraryLayout>
 
+        {/* Scan Preview Modal */}
+        <ScanPreviewModal
+          isOpen={isPreviewOpen}
+          onClose={onPreviewClose}
+          libraryId={libraryId}
+          onScan={handleScanLibrary}
+        />
+
         {/* Add Show Modal */}
         <AddShowModal
           isOpen={isOpen}
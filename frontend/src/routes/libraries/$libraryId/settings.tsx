@@ -1,5 +1,10 @@
 import { createFileRoute } from '@tanstack/react-router'
-import { LibrarySettingsTab } from '../../../components/library'
+import {
+  LanguageProfileCard,
+  LibraryBreakdownCard,
+  LibrarySettingsTab,
+  MissingSubtitlesCard,
+} from '../../../components/library'
 import { useLibraryContext } from '../$libraryId'
 import { ShimmerLoader } from '../../../components/shared/ShimmerLoader'
 import { libraryTemplate } from '../../../lib/template-data'
@@ -12,12 +17,17 @@ function SettingsPage() {
   const { library, loading, handleUpdateLibrary, actionLoading } = useLibraryContext()
 
   return (
-    <ShimmerLoader loading={loading} delay={500} templateProps={{ library: libraryTemplate }}>
-      <LibrarySettingsTab
-        library={library}
-        onSave={handleUpdateLibrary}
-        isLoading={actionLoading}
-      />
-    </ShimmerLoader>
+    <div className="flex flex-col gap-6">
+      <ShimmerLoader loading={loading} delay={500} templateProps={{ library: libraryTemplate }}>
+        <LibrarySettingsTab
+          library={library}
+          onSave={handleUpdateLibrary}
+          isLoading={actionLoading}
+        />
+      </ShimmerLoader>
+      {!loading && <LibraryBreakdownCard libraryId={library.id} />}
+      {!loading && <LanguageProfileCard libraryId={library.id} />}
+      {!loading && <MissingSubtitlesCard libraryId={library.id} />}
+    </div>
   )
 }
@@ -66,6 +66,9 @@ const CATEGORY_LABELS: Record<NotificationCategory, string> = {
   STORAGE: "Storage",
   EXTRACTION: "Extraction",
   CONFIGURATION: "Configuration",
+  SCHEDULE: "Schedule",
+  UPDATE: "Update",
+  SERVICES: "Services",
 };
 
 const getNotificationIcon = (type: NotificationType) => {
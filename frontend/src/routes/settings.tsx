@@ -3,7 +3,7 @@ import { Link, useLocation } from '@tanstack/react-router'
 import { Card, CardBody } from '@heroui/card'
 import { RouteError } from '../components/RouteError'
 import type { TablerIcon } from '@tabler/icons-react'
-import { IconSettings, IconDownload, IconRss, IconMovie, IconClipboard, IconSearch, IconCast, IconFolderCog, IconCloud, IconSortDescending } from '@tabler/icons-react'
+import { IconSettings, IconDownload, IconRss, IconMovie, IconClipboard, IconSearch, IconCast, IconFolderCog, IconCloud, IconSortDescending, IconStethoscope } from '@tabler/icons-react'
 
 // This is the parent route for /settings/* that provides the shared layout
 export const Route = createFileRoute('/settings')({
@@ -104,6 +104,14 @@ const settingsTabs: SettingsTab[] = [
     iconColor: 'text-teal-400',
     description: 'Chromecast devices',
   },
+  {
+    key: 'system',
+    path: '/settings/system',
+    label: 'System',
+    Icon: IconStethoscope,
+    iconColor: 'text-rose-400',
+    description: 'Updates & diagnostics',
+  },
   {
     key: 'logs',
     path: '/settings/logs',
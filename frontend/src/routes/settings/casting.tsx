@@ -200,6 +200,14 @@ function CastingSettingsPage() {
         return 'Nest Hub';
       case 'ANDROID_TV':
         return 'Android TV';
+      case 'SPEAKER_GROUP':
+        return 'Speaker Group';
+      case 'APPLE_TV':
+        return 'Apple TV';
+      case 'HOME_POD':
+        return 'HomePod';
+      case 'AIR_PLAY':
+        return 'AirPlay';
       default:
         return 'Unknown';
     }
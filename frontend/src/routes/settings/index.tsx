@@ -1,17 +1,25 @@
 import { createFileRoute } from '@tanstack/react-router'
-import { useState, useEffect } from 'react'
+import { useState, useEffect, useCallback } from 'react'
 import { Card, CardBody, CardHeader } from '@heroui/card'
 import { Slider } from '@heroui/slider'
 import { Button } from '@heroui/button'
 import { Spinner } from '@heroui/spinner'
 import { Divider } from '@heroui/divider'
+import { Switch } from '@heroui/switch'
+import { Input } from '@heroui/input'
 import { addToast } from '@heroui/toast'
-import { IconPlayerPlay, IconDeviceFloppy } from '@tabler/icons-react'
+import { IconPlayerPlay, IconDeviceFloppy, IconPlayerPause } from '@tabler/icons-react'
 import {
   graphqlClient,
   PLAYBACK_SETTINGS_QUERY,
   UPDATE_PLAYBACK_SETTINGS_MUTATION,
+  LIBRARIES_QUERY,
+  AUTOMATION_PAUSES_QUERY,
+  PAUSE_AUTOMATION_MUTATION,
+  RESUME_AUTOMATION_MUTATION,
   type PlaybackSettings,
+  type Library,
+  type AutomationPause,
 } from '../../lib/graphql'
 
 export const Route = createFileRoute('/settings/')({
@@ -176,6 +184,152 @@ function GeneralSettingsPage() {
           </div>
         </CardBody>
       </Card>
+
+      <AutomationCard />
     </div>
   )
 }
+
+/** Pause auto-hunt and RSS polling globally or per library ("vacation mode") */
+function AutomationCard() {
+  const [libraries, setLibraries] = useState<Library[]>([])
+  const [pauses, setPauses] = useState<AutomationPause[]>([])
+  const [resumeAt, setResumeAt] = useState('')
+  const [updating, setUpdating] = useState<string | null>(null)
+
+  const loadPauses = useCallback(async () => {
+    const [librariesResult, pausesResult] = await Promise.all([
+      graphqlClient.query<{ libraries: Library[] }>(LIBRARIES_QUERY, {}).toPromise(),
+      graphqlClient
+        .query<{ automationPauses: AutomationPause[] }>(
+          AUTOMATION_PAUSES_QUERY,
+          {},
+          { requestPolicy: 'network-only' }
+        )
+        .toPromise(),
+    ])
+    if (librariesResult.data?.libraries) {
+      setLibraries(librariesResult.data.libraries)
+    }
+    if (pausesResult.data?.automationPauses) {
+      setPauses(pausesResult.data.automationPauses)
+    }
+  }, [])
+
+  useEffect(() => {
+    loadPauses().catch((err) => console.error('Failed to load automation pauses:', err))
+  }, [loadPauses])
+
+  const pauseFor = (libraryId: string | null) =>
+    pauses.find((p) => p.libraryId === libraryId)
+  const globalPause = pauseFor(null)
+
+  const setPaused = async (libraryId: string | null, paused: boolean) => {
+    setUpdating(libraryId ?? 'global')
+    try {
+      let payload: { success: boolean; error: string | null } | undefined
+      if (paused) {
+        const result = await graphqlClient
+          .mutation<{ pauseAutomation: { success: boolean; error: string | null } }>(
+            PAUSE_AUTOMATION_MUTATION,
+            {
+              libraryId,
+              resumeAt: resumeAt ? new Date(resumeAt).toISOString() : null,
+            }
+          )
+          .toPromise()
+        payload = result.data?.pauseAutomation
+      } else {
+        const result = await graphqlClient
+          .mutation<{ resumeAutomation: { success: boolean; error: string | null } }>(
+            RESUME_AUTOMATION_MUTATION,
+            { libraryId }
+          )
+          .toPromise()
+        payload = result.data?.resumeAutomation
+      }
+
+      if (!payload?.success) {
+        addToast({
+          title: 'Error',
+          description: payload?.error || 'Failed to update automation',
+          color: 'danger',
+        })
+      }
+      await loadPauses()
+    } catch (err) {
+      console.error('Failed to update automation pause:', err)
+    } finally {
+      setUpdating(null)
+    }
+  }
+
+  const describe = (pause: AutomationPause | undefined) =>
+    pause?.resumeAt
+      ? `Paused until ${new Date(pause.resumeAt).toLocaleString()}`
+      : pause
+        ? 'Paused until resumed'
+        : 'Active'
+
+  return (
+    <Card>
+      <CardHeader className="flex gap-3">
+        <IconPlayerPause size={24} className="text-warning" />
+        <div className="flex flex-col">
+          <p className="text-lg font-semibold">Automation</p>
+          <p className="text-small text-default-500">
+            Pause auto-hunt and RSS polling while you're away. Scans and metadata
+            refresh keep running.
+          </p>
+        </div>
+      </CardHeader>
+      <Divider />
+      <CardBody className="gap-4">
+        <Input
+          type="datetime-local"
+          label="Resume automatically at"
+          description="Optional. Applies to pauses you turn on below."
+          value={resumeAt}
+          onValueChange={setResumeAt}
+          className="max-w-xs"
+        />
+
+        <div className="flex items-center justify-between">
+          <div>
+            <p className="font-medium">Pause all libraries</p>
+            <p className="text-small text-default-500">{describe(globalPause)}</p>
+          </div>
+          <Switch
+            aria-label="Pause all libraries"
+            isSelected={!!globalPause}
+            isDisabled={updating !== null}
+            onValueChange={(paused) => setPaused(null, paused)}
+          />
+        </div>
+
+        {libraries.length > 0 && <Divider />}
+
+        {libraries.map((library) => {
+          const pause = pauseFor(library.id)
+          return (
+            <div key={library.id} className="flex items-center justify-between">
+              <div>
+                <p className="font-medium">{library.name}</p>
+                <p className="text-small text-default-500">
+                  {globalPause ? 'Paused with all libraries' : describe(pause)}
+                </p>
+              </div>
+              <Switch
+                aria-label={`Pause ${library.name}`}
+                size="sm"
+                isSelected={!!pause}
+                isDisabled={updating !== null}
+                onValueChange={(paused) => setPaused(library.id, paused)}
+              />
+            </div>
+          )
+        })}
+      </CardBody>
+    </Card>
+  )
+}
@@ -22,6 +22,7 @@ import {
   IconTestPipe,
   IconDownload,
   IconUsers,
+  IconKey,
 } from '@tabler/icons-react'
 import { graphqlClient } from '../../lib/graphql'
 import { formatBytes, sanitizeError } from '../../lib/format'
@@ -34,6 +35,7 @@ import type {
   IndexerTestResult,
   IndexerSearchResultSet,
   TorrentRelease,
+  TrackerStats,
 } from '../../lib/graphql/types'
 
 // GraphQL response types
@@ -69,6 +71,18 @@ interface SearchIndexersResponse {
   searchIndexers: IndexerSearchResultSet
 }
 
+interface TrackerStatsQueryResponse {
+  trackerStats: TrackerStats[]
+}
+
+interface SetTrackerMinRatioResponse {
+  setTrackerMinRatio: { success: boolean; error: string | null }
+}
+
+interface UpdateTrackerPasskeyResponse {
+  updateTrackerPasskey: { success: boolean; error: string | null; torrentsUpdated: number }
+}
+
 export const Route = createFileRoute('/settings/indexers')({
   component: IndexersSettingsPage,
 })
@@ -203,6 +217,43 @@ const SEARCH_INDEXERS_QUERY = `
   }
 `
 
+const TRACKER_STATS_QUERY = `
+  query TrackerStats {
+    trackerStats {
+      indexerId
+      indexerName
+      indexerType
+      isPrivate
+      uploadedBytes
+      downloadedBytes
+      ratio
+      minRatio
+      ratioStatus
+      passkeyError
+      updatedAt
+    }
+  }
+`
+
+const SET_TRACKER_MIN_RATIO_MUTATION = `
+  mutation SetTrackerMinRatio($indexerId: String!, $minRatio: Float) {
+    setTrackerMinRatio(indexerId: $indexerId, minRatio: $minRatio) {
+      success
+      error
+    }
+  }
+`
+
+const UPDATE_TRACKER_PASSKEY_MUTATION = `
+  mutation UpdateTrackerPasskey($indexerId: String!, $newPasskey: String!) {
+    updateTrackerPasskey(indexerId: $indexerId, newPasskey: $newPasskey) {
+      success
+      error
+      torrentsUpdated
+    }
+  }
+`
+
 function IndexersSettingsPage() {
   const [indexers, setIndexers] = useState<IndexerConfig[]>([])
   const [availableTypes, setAvailableTypes] = useState<IndexerTypeInfo[]>([])
@@ -210,6 +261,7 @@ function IndexersSettingsPage() {
   const [testResults, setTestResults] = useState<Record<string, IndexerTestResult>>({})
   const [testingIds, setTestingIds] = useState<Set<string>>(new Set())
   const [editingIndexer, setEditingIndexer] = useState<IndexerConfig | null>(null)
+  const [trackerStats, setTrackerStats] = useState<TrackerStats[]>([])
   
   // Search state
   const [searchQuery, setSearchQuery] = useState('')
@@ -255,9 +307,22 @@ function IndexersSettingsPage() {
     }
   }
 
+  // Fetch tracker ratio stats
+  const fetchTrackerStats = async () => {
+    try {
+      const result = await graphqlClient.query<TrackerStatsQueryResponse>(TRACKER_STATS_QUERY, {}).toPromise()
+      if (result.data?.trackerStats) {
+        setTrackerStats(result.data.trackerStats)
+      }
+    } catch (e) {
+      console.error('Failed to fetch tracker stats:', e)
+    }
+  }
+
   useEffect(() => {
     fetchIndexers()
     fetchAvailableTypes()
+    fetchTrackerStats()
   }, [])
 
   // Toggle indexer enabled
@@ -583,6 +648,13 @@ function IndexersSettingsPage() {
         }
       />
 
+      {/* Tracker Ratios */}
+      {trackerStats.some((t) => t.isPrivate) && (
+        <TrackerStatsCard
+          stats={trackerStats.filter((t) => t.isPrivate)}
+          onChanged={fetchTrackerStats}
+        />
+      )}
 
       {/* Add Indexer Modal */}
       <AddIndexerModal
@@ -640,6 +712,188 @@ function IndexersSettingsPage() {
   )
 }
 
+// Tracker Ratio Card Component
+interface TrackerStatsCardProps {
+  stats: TrackerStats[]
+  onChanged: () => void
+}
+
+const RATIO_STATUS_COLORS = {
+  OK: 'success',
+  APPROACHING: 'warning',
+  BELOW: 'danger',
+} as const
+
+function TrackerStatsCard({ stats, onChanged }: TrackerStatsCardProps) {
+  const [drafts, setDrafts] = useState<Record<string, string>>({})
+  const [passkeyDrafts, setPasskeyDrafts] = useState<Record<string, string>>({})
+  const [savingPasskey, setSavingPasskey] = useState<string | null>(null)
+
+  const updatePasskey = async (tracker: TrackerStats) => {
+    const newPasskey = passkeyDrafts[tracker.indexerId]?.trim()
+    if (!newPasskey) return
+
+    setSavingPasskey(tracker.indexerId)
+    const result = await graphqlClient
+      .mutation<UpdateTrackerPasskeyResponse>(UPDATE_TRACKER_PASSKEY_MUTATION, {
+        indexerId: tracker.indexerId,
+        newPasskey,
+      })
+      .toPromise()
+    setSavingPasskey(null)
+
+    const data = result.data?.updateTrackerPasskey
+    if (data?.success) {
+      setPasskeyDrafts((prev) => {
+        const next = { ...prev }
+        delete next[tracker.indexerId]
+        return next
+      })
+      addToast({
+        title: 'Passkey Updated',
+        description: `${data.torrentsUpdated} torrent${data.torrentsUpdated !== 1 ? 's' : ''} updated`,
+        color: 'success',
+      })
+    } else {
+      addToast({
+        title: 'Update Failed',
+        description: sanitizeError(data?.error || result.error || 'Failed to update passkey'),
+        color: 'danger',
+      })
+    }
+    onChanged()
+  }
+
+  const saveMinRatio = async (tracker: TrackerStats) => {
+    const draft = drafts[tracker.indexerId]
+    if (draft === undefined) return
+
+    const minRatio = draft.trim() === '' ? null : Number(draft)
+    if (minRatio !== null && (Number.isNaN(minRatio) || minRatio < 0)) {
+      addToast({
+        title: 'Invalid ratio',
+        description: 'Enter a number of zero or more, or leave empty',
+        color: 'warning',
+      })
+      return
+    }
+
+    const result = await graphqlClient
+      .mutation<SetTrackerMinRatioResponse>(SET_TRACKER_MIN_RATIO_MUTATION, {
+        indexerId: tracker.indexerId,
+        minRatio,
+      })
+      .toPromise()
+
+    if (result.data?.setTrackerMinRatio?.success) {
+      setDrafts((prev) => {
+        const next = { ...prev }
+        delete next[tracker.indexerId]
+        return next
+      })
+      onChanged()
+    } else {
+      addToast({
+        title: 'Save Failed',
+        description: sanitizeError(result.data?.setTrackerMinRatio?.error || 'Failed to save minimum ratio'),
+        color: 'danger',
+      })
+    }
+  }
+
+  return (
+    <Card>
+      <CardHeader className="flex flex-col items-start gap-1">
+        <h3 className="text-lg font-semibold">Tracker Ratios</h3>
+        <p className="text-default-500 text-sm">
+          Traffic for torrents grabbed from each private tracker. Only transfers seen by
+          Librarian are counted, so the tracker's own figure may differ.
+        </p>
+      </CardHeader>
+      <Divider />
+      <CardBody className="gap-3">
+        {stats.map((tracker) => (
+          <div key={tracker.indexerId} className="flex flex-row items-center gap-4">
+            <div className="flex-1 min-w-0">
+              <div className="flex items-center gap-2">
+                <span className="font-medium">{tracker.indexerName}</span>
+                <Chip size="sm" variant="flat" color={RATIO_STATUS_COLORS[tracker.ratioStatus]}>
+                  {tracker.ratio !== null ? tracker.ratio.toFixed(2) : '—'}
+                </Chip>
+                {tracker.passkeyError && (
+                  <Tooltip content={tracker.passkeyError}>
+                    <Chip size="sm" variant="flat" color="danger">
+                      <IconAlertTriangle size={12} className="mr-1" />
+                      Passkey rejected
+                    </Chip>
+                  </Tooltip>
+                )}
+              </div>
+              <div className="text-sm text-default-400 flex items-center gap-4 mt-1">
+                <span>Uploaded: {formatBytes(tracker.uploadedBytes)}</span>
+                <span>Downloaded: {formatBytes(tracker.downloadedBytes)}</span>
+              </div>
+            </div>
+            <Input
+              size="sm"
+              type="number"
+              min={0}
+              step={0.1}
+              label="Minimum ratio"
+              labelPlacement="inside"
+              variant="flat"
+              className="w-36"
+              value={drafts[tracker.indexerId] ?? (tracker.minRatio?.toString() ?? '')}
+              onValueChange={(value) =>
+                setDrafts((prev) => ({ ...prev, [tracker.indexerId]: value }))
+              }
+              onBlur={() => saveMinRatio(tracker)}
+              onKeyDown={(e) => e.key === 'Enter' && saveMinRatio(tracker)}
+            />
+            {passkeyDrafts[tracker.indexerId] === undefined && !tracker.passkeyError ? (
+              <Tooltip content="Update passkey">
+                <Button
+                  isIconOnly
+                  size="sm"
+                  variant="light"
+                  onPress={() => setPasskeyDrafts((prev) => ({ ...prev, [tracker.indexerId]: '' }))}
+                >
+                  <IconKey size={16} />
+                </Button>
+              </Tooltip>
+            ) : (
+              <div className="flex items-center gap-1">
+                <Input
+                  size="sm"
+                  label="New passkey"
+                  labelPlacement="inside"
+                  variant="flat"
+                  className="w-56"
+                  value={passkeyDrafts[tracker.indexerId] ?? ''}
+                  onValueChange={(value) =>
+                    setPasskeyDrafts((prev) => ({ ...prev, [tracker.indexerId]: value }))
+                  }
+                  onKeyDown={(e) => e.key === 'Enter' && updatePasskey(tracker)}
+                />
+                <Button
+                  size="sm"
+                  color="primary"
+                  variant="flat"
+                  isLoading={savingPasskey === tracker.indexerId}
+                  isDisabled={!passkeyDrafts[tracker.indexerId]?.trim()}
+                  onPress={() => updatePasskey(tracker)}
+                >
+                  Update
+                </Button>
+              </div>
+            )}
+          </div>
+        ))}
+      </CardBody>
+    </Card>
+  )
+}
+
 // Indexer Card Component
 interface IndexerCardProps {
   indexer: IndexerConfig
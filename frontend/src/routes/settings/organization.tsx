@@ -29,6 +29,7 @@ import {
   IconDeviceTv,
   IconMusic,
   IconBook,
+  IconLock,
 } from '@tabler/icons-react'
 import {
   graphqlClient,
@@ -48,6 +49,7 @@ import {
 import { DataTable, type DataTableColumn, type CardRendererProps, type RowAction } from '../../components/data-table'
 import { previewNamingPattern, sanitizeError } from '../../lib/format'
 import { SettingsHeader } from '../../components/shared'
+import { ImportPermissionsSettings } from '../../components/settings'
 
 // GraphQL mutations for LLM parser
 const UPDATE_LLM_PARSER_SETTINGS_MUTATION = `
@@ -1183,6 +1185,21 @@ function OrganizationSettingsPage() {
           </div>
         </AccordionItem>
 
+        {/* Imported File Permissions Section */}
+        <AccordionItem
+          key="permissions"
+          aria-label="Imported File Permissions"
+          title={
+            <div className="flex items-center gap-2">
+              <IconLock size={18} className="text-green-400" />
+              <span className="font-semibold">Imported File Permissions</span>
+            </div>
+          }
+          subtitle="Owner and mode for files copied into libraries"
+        >
+          <ImportPermissionsSettings />
+        </AccordionItem>
+
         {/* Test Parser Section */}
         <AccordionItem
           key="test"
@@ -128,6 +128,8 @@ function RssSettingsPage() {
             url: data.url,
             enabled: data.enabled,
             pollIntervalMinutes: data.pollIntervalMinutes,
+            minPollIntervalMinutes: data.minPollIntervalMinutes,
+            maxPollIntervalMinutes: data.maxPollIntervalMinutes,
           },
         })
         .toPromise()
@@ -171,6 +173,8 @@ function RssSettingsPage() {
             url: data.url,
             enabled: data.enabled,
             pollIntervalMinutes: data.pollIntervalMinutes,
+            minPollIntervalMinutes: data.minPollIntervalMinutes,
+            maxPollIntervalMinutes: data.maxPollIntervalMinutes,
           },
         })
         .toPromise()
@@ -295,7 +299,6 @@ function RssSettingsPage() {
             name: feed.name,
             url: feed.url,
             enabled: !feed.enabled,
-            pollIntervalMinutes: feed.pollIntervalMinutes,
           },
         })
         .toPromise()
@@ -400,7 +403,13 @@ function RssSettingsPage() {
                     </span>
                   </TableCell>
                   <TableCell>
-                    <span className="text-sm">{feed.pollIntervalMinutes} min</span>
+                    <Tooltip
+                      content={`Adapts between ${feed.minPollIntervalMinutes} and ${feed.maxPollIntervalMinutes} min${
+                        feed.ttlMinutes ? ` (feed TTL ${feed.ttlMinutes} min)` : ''
+                      }`}
+                    >
+                      <span className="text-sm">{feed.currentPollIntervalMinutes} min</span>
+                    </Tooltip>
                   </TableCell>
                   <TableCell>
                     <div className="flex justify-end">
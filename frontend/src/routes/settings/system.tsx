@@ -0,0 +1,437 @@
+import { createFileRoute } from '@tanstack/react-router'
+import { useState, useEffect, useCallback } from 'react'
+import { Button } from '@heroui/button'
+import { Card, CardBody } from '@heroui/card'
+import { Chip } from '@heroui/chip'
+import { Spinner } from '@heroui/spinner'
+import { Switch } from '@heroui/switch'
+import { addToast } from '@heroui/toast'
+import { useDisclosure } from '@heroui/modal'
+import { IconCircleCheck, IconAlertTriangle, IconCircleX, IconRefresh, IconDownload } from '@tabler/icons-react'
+import {
+  graphqlClient,
+  DIAGNOSTICS_QUERY,
+  EXTERNAL_SERVICES_STATUS_QUERY,
+  UPDATE_AVAILABLE_QUERY,
+  UPDATE_CHECK_SETTINGS_QUERY,
+  UPDATE_UPDATE_CHECK_SETTINGS_MUTATION,
+  CHECK_FOR_UPDATES_MUTATION,
+  APPLY_UPDATE_MUTATION,
+  type DiagnosticCheck,
+  type DiagnosticStatus,
+  type ExternalServiceHealth,
+  type ExternalServicesStatus,
+  type UpdateInfo,
+  type UpdateCheckSettings,
+  type CheckForUpdatesResult,
+  type ApplyUpdateResult,
+  type SettingsResult,
+} from '../../lib/graphql'
+import { SettingsHeader } from '../../components/shared'
+import { ConfirmModal } from '../../components/ConfirmModal'
+import { formatRelativeTime, sanitizeError } from '../../lib/format'
+
+export const Route = createFileRoute('/settings/system')({
+  component: SystemSettingsPage,
+})
+
+/** How long to wait for the server to come back after installing an update */
+const RESTART_TIMEOUT_MS = 3 * 60 * 1000
+
+const statusDisplay: Record<DiagnosticStatus, { label: string; color: 'success' | 'warning' | 'danger' }> = {
+  PASS: { label: 'Pass', color: 'success' },
+  WARN: { label: 'Warning', color: 'warning' },
+  FAIL: { label: 'Fail', color: 'danger' },
+}
+
+function StatusIcon({ status }: { status: DiagnosticStatus }) {
+  switch (status) {
+    case 'PASS':
+      return <IconCircleCheck size={20} className="text-success shrink-0" />
+    case 'WARN':
+      return <IconAlertTriangle size={20} className="text-warning shrink-0" />
+    case 'FAIL':
+      return <IconCircleX size={20} className="text-danger shrink-0" />
+  }
+}
+
+const healthDisplay: Record<ExternalServiceHealth, { label: string; color: 'success' | 'warning' | 'danger' }> = {
+  UP: { label: 'Up', color: 'success' },
+  DEGRADED: { label: 'Slow', color: 'warning' },
+  DOWN: { label: 'Down', color: 'danger' },
+}
+
+const kindLabels: Record<string, string> = {
+  indexer: 'Indexer',
+  usenet: 'Usenet server',
+  cast: 'Cast device',
+}
+
+/** External services as of the last background probe (every 5 minutes) */
+function ExternalServicesCard() {
+  const [status, setStatus] = useState<ExternalServicesStatus | null>(null)
+
+  useEffect(() => {
+    graphqlClient
+      .query<{ externalServicesStatus: ExternalServicesStatus }>(
+        EXTERNAL_SERVICES_STATUS_QUERY,
+        {},
+        { requestPolicy: 'network-only' },
+      )
+      .toPromise()
+      .then((result) => {
+        if (result.data?.externalServicesStatus) {
+          setStatus(result.data.externalServicesStatus)
+        }
+      })
+      .catch((e) => console.error('Failed to load external service status:', e))
+  }, [])
+
+  if (!status || status.services.length === 0) return null
+
+  return (
+    <Card>
+      <CardBody className="gap-4">
+        <div className="flex items-center justify-between gap-4">
+          <div>
+            <p className="font-semibold">External Services</p>
+            <p className="text-sm text-default-500">
+              Checked every five minutes; outages and recoveries are sent as notifications
+            </p>
+          </div>
+          {status.downCount > 0 && (
+            <Chip color="danger" variant="flat">
+              {status.downCount} down
+            </Chip>
+          )}
+        </div>
+        <div className="divide-y divide-divider">
+          {status.services.map((service) => (
+            <div key={service.id} className="flex items-center justify-between gap-3 py-2">
+              <div className="flex flex-col min-w-0">
+                <span className="font-medium truncate">
+                  {service.name}
+                  {kindLabels[service.kind] && (
+                    <span className="text-default-400 font-normal"> · {kindLabels[service.kind]}</span>
+                  )}
+                </span>
+                <span className="text-xs text-default-500 break-words">
+                  {service.status === 'DOWN' && service.lastError
+                    ? service.lastError
+                    : `${healthDisplay[service.status].label} since ${formatRelativeTime(service.lastChangedAt)}`}
+                </span>
+              </div>
+              <div className="flex items-center gap-2 shrink-0">
+                {service.status !== 'DOWN' && service.latencyMs !== null && (
+                  <span className="text-xs text-default-400">{service.latencyMs} ms</span>
+                )}
+                <Chip size="sm" variant="flat" color={healthDisplay[service.status].color}>
+                  {healthDisplay[service.status].label}
+                </Chip>
+              </div>
+            </div>
+          ))}
+        </div>
+      </CardBody>
+    </Card>
+  )
+}
+
+function UpdatesCard() {
+  const [settings, setSettings] = useState<UpdateCheckSettings | null>(null)
+  const [update, setUpdate] = useState<UpdateInfo | null>(null)
+  const [isChecking, setIsChecking] = useState(false)
+  const [isInstalling, setIsInstalling] = useState(false)
+  const { isOpen: isConfirmOpen, onOpen: onConfirmOpen, onClose: onConfirmClose } = useDisclosure()
+
+  useEffect(() => {
+    Promise.all([
+      graphqlClient.query<{ updateCheckSettings: UpdateCheckSettings }>(UPDATE_CHECK_SETTINGS_QUERY, {}).toPromise(),
+      graphqlClient.query<{ updateAvailable: UpdateInfo | null }>(UPDATE_AVAILABLE_QUERY, {}).toPromise(),
+    ]).then(([settingsRes, updateRes]) => {
+      if (settingsRes.data?.updateCheckSettings) {
+        setSettings(settingsRes.data.updateCheckSettings)
+      }
+      setUpdate(updateRes.data?.updateAvailable ?? null)
+    })
+  }, [])
+
+  const handleToggle = async (enabled: boolean) => {
+    const result = await graphqlClient
+      .mutation<{ updateUpdateCheckSettings: SettingsResult }>(UPDATE_UPDATE_CHECK_SETTINGS_MUTATION, { enabled })
+      .toPromise()
+    if (result.data?.updateUpdateCheckSettings.success) {
+      setSettings((prev) => (prev ? { ...prev, enabled } : prev))
+    } else {
+      addToast({
+        title: 'Failed to save',
+        description: result.data?.updateUpdateCheckSettings.error || sanitizeError(result.error),
+        color: 'danger',
+      })
+    }
+  }
+
+  const handleCheckNow = async () => {
+    setIsChecking(true)
+    try {
+      const result = await graphqlClient
+        .mutation<{ checkForUpdates: CheckForUpdatesResult }>(CHECK_FOR_UPDATES_MUTATION, {})
+        .toPromise()
+      const payload = result.data?.checkForUpdates
+      if (payload?.success) {
+        setUpdate(payload.update)
+        setSettings((prev) =>
+          prev
+            ? {
+                ...prev,
+                latestVersion: payload.update?.version ?? prev.latestVersion,
+                lastCheckedAt: new Date().toISOString(),
+              }
+            : prev
+        )
+        if (!payload.update) {
+          addToast({ title: 'Up to date', description: 'You are running the latest release', color: 'success' })
+        }
+      } else {
+        addToast({
+          title: 'Update check failed',
+          description: payload?.error || sanitizeError(result.error),
+          color: 'danger',
+        })
+      }
+    } finally {
+      setIsChecking(false)
+    }
+  }
+
+  // The server restarts after installing; once it no longer reports an update
+  // it's running the new version
+  const waitForRestart = async () => {
+    const deadline = Date.now() + RESTART_TIMEOUT_MS
+    while (Date.now() < deadline) {
+      await new Promise((resolve) => setTimeout(resolve, 3000))
+      const result = await graphqlClient
+        .query<{ updateAvailable: UpdateInfo | null }>(UPDATE_AVAILABLE_QUERY, {}, { requestPolicy: 'network-only' })
+        .toPromise()
+      if (result.data && result.data.updateAvailable === null) {
+        window.location.reload()
+        return
+      }
+    }
+    setIsInstalling(false)
+    addToast({
+      title: 'Update not confirmed',
+      description: 'The server has not come back on the new version. Check the logs; a failed update is rolled back.',
+      color: 'warning',
+    })
+  }
+
+  const handleInstall = async () => {
+    onConfirmClose()
+    setIsInstalling(true)
+    const result = await graphqlClient.mutation<{ applyUpdate: ApplyUpdateResult }>(APPLY_UPDATE_MUTATION, {}).toPromise()
+    const payload = result.data?.applyUpdate
+    if (payload?.success) {
+      addToast({
+        title: `Installing ${payload.version}`,
+        description: 'Librarian is restarting. This page reloads when it is back.',
+        color: 'primary',
+      })
+      await waitForRestart()
+    } else {
+      setIsInstalling(false)
+      addToast({
+        title: 'Update failed',
+        description: payload?.error || sanitizeError(result.error),
+        color: 'danger',
+      })
+    }
+  }
+
+  const unavailableReason = settings?.selfUpdateUnavailableReason
+
+  return (
+    <Card>
+      <CardBody className="gap-4">
+        <div className="flex items-center justify-between gap-4">
+          <div>
+            <p className="font-semibold">Updates</p>
+            <p className="text-sm text-default-500">
+              Check GitHub for new releases every six hours and notify admins
+              {settings?.lastCheckedAt && ` · last checked ${formatRelativeTime(settings.lastCheckedAt)}`}
+            </p>
+          </div>
+          <div className="flex items-center gap-3 shrink-0">
+            <Button
+              variant="flat"
+              size="sm"
+              startContent={isChecking ? <Spinner size="sm" /> : <IconRefresh size={16} />}
+              onPress={handleCheckNow}
+              isDisabled={isChecking}
+            >
+              Check Now
+            </Button>
+            <Switch
+              isSelected={settings?.enabled ?? true}
+              isDisabled={!settings}
+              onValueChange={handleToggle}
+              aria-label="Check for updates automatically"
+            />
+          </div>
+        </div>
+
+        {update && (
+          <div className="flex gap-3 rounded-lg bg-primary-50 p-4">
+            <IconDownload size={20} className="text-primary shrink-0" />
+            <div className="flex flex-col gap-2 min-w-0">
+              <p className="font-medium">
+                {update.name || `Librarian ${update.version}`} is available (you have {update.currentVersion})
+              </p>
+              {update.changelog && (
+                <p className="text-sm text-default-600 whitespace-pre-line break-words">{update.changelog}</p>
+              )}
+              <a href={update.url} target="_blank" rel="noopener noreferrer" className="text-sm text-primary">
+                Release notes
+              </a>
+              {settings && !unavailableReason && (
+                <Button
+                  color="primary"
+                  size="sm"
+                  className="self-start"
+                  startContent={!isInstalling && <IconDownload size={16} />}
+                  isLoading={isInstalling}
+                  onPress={onConfirmOpen}
+                >
+                  {isInstalling ? 'Installing...' : 'Install & Restart'}
+                </Button>
+              )}
+              {unavailableReason && <p className="text-sm text-default-500">{unavailableReason}</p>}
+            </div>
+          </div>
+        )}
+      </CardBody>
+
+      <ConfirmModal
+        isOpen={isConfirmOpen}
+        onClose={onConfirmClose}
+        onConfirm={handleInstall}
+        title={`Install ${update?.version ?? 'update'}`}
+        message="Librarian will download the release, restart, and be unavailable for a minute."
+        description="Active downloads and playback are interrupted. The database is backed up first and the update is rolled back if the new version fails to start."
+        confirmLabel="Install & Restart"
+        confirmColor="primary"
+      />
+    </Card>
+  )
+}
+
+function SystemSettingsPage() {
+  const [checks, setChecks] = useState<DiagnosticCheck[]>([])
+  const [isRunning, setIsRunning] = useState(true)
+  const [error, setError] = useState<string | null>(null)
+
+  const runDiagnostics = useCallback(async () => {
+    setIsRunning(true)
+    setError(null)
+    try {
+      // Bypass the cache so re-running actually re-checks
+      const result = await graphqlClient
+        .query<{ diagnostics: DiagnosticCheck[] }>(DIAGNOSTICS_QUERY, {}, { requestPolicy: 'network-only' })
+        .toPromise()
+      if (result.data?.diagnostics) {
+        setChecks(result.data.diagnostics)
+      } else if (result.error) {
+        setError(sanitizeError(result.error))
+      }
+    } catch (e) {
+      setError(sanitizeError(e))
+    } finally {
+      setIsRunning(false)
+    }
+  }, [])
+
+  useEffect(() => {
+    runDiagnostics()
+  }, [runDiagnostics])
+
+  // Problems first, failures before warnings
+  const order: Record<DiagnosticStatus, number> = { FAIL: 0, WARN: 1, PASS: 2 }
+  const sorted = [...checks].sort((a, b) => order[a.status] - order[b.status])
+  const failures = checks.filter((c) => c.status === 'FAIL').length
+  const warnings = checks.filter((c) => c.status === 'WARN').length
+
+  return (
+    <div className="flex flex-col gap-6">
+      <SettingsHeader
+        title="System"
+        subtitle="Updates and environment checks"
+        actions={
+          <Button
+            variant="flat"
+            size="sm"
+            startContent={isRunning ? <Spinner size="sm" /> : <IconRefresh size={16} />}
+            onPress={runDiagnostics}
+            isDisabled={isRunning}
+          >
+            {isRunning ? 'Checking...' : 'Run Checks Again'}
+          </Button>
+        }
+      />
+
+      <UpdatesCard />
+
+      <ExternalServicesCard />
+
+      {error && (
+        <Card>
+          <CardBody>
+            <p className="text-danger text-sm">{error}</p>
+          </CardBody>
+        </Card>
+      )}
+
+      {isRunning && checks.length === 0 ? (
+        <div className="flex flex-col items-center justify-center h-64 gap-3">
+          <Spinner size="lg" />
+          <p className="text-default-500 text-sm">Running checks, this takes a few seconds</p>
+        </div>
+      ) : (
+        checks.length > 0 && (
+          <>
+            <div className="flex gap-2">
+              <Chip color={failures > 0 ? 'danger' : 'default'} variant="flat">
+                {failures} failed
+              </Chip>
+              <Chip color={warnings > 0 ? 'warning' : 'default'} variant="flat">
+                {warnings} {warnings === 1 ? 'warning' : 'warnings'}
+              </Chip>
+              <Chip color="success" variant="flat">
+                {checks.length - failures - warnings} passed
+              </Chip>
+            </div>
+
+            <Card>
+              <CardBody className="p-0 divide-y divide-divider">
+                {sorted.map((check) => (
+                  <div key={check.id} className="flex gap-3 p-4">
+                    <StatusIcon status={check.status} />
+                    <div className="flex flex-col gap-1 min-w-0 flex-1">
+                      <div className="flex items-center justify-between gap-2">
+                        <span className="font-medium">{check.title}</span>
+                        <Chip size="sm" variant="flat" color={statusDisplay[check.status].color}>
+                          {statusDisplay[check.status].label}
+                        </Chip>
+                      </div>
+                      <p className="text-sm text-default-500 break-words">{check.message}</p>
+                      {check.hint && <p className="text-sm text-default-400">{check.hint}</p>}
+                    </div>
+                  </div>
+                ))}
+              </CardBody>
+            </Card>
+          </>
+        )
+      )}
+    </div>
+  )
+}
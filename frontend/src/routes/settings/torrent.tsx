@@ -19,8 +19,9 @@ import {
 } from '../../lib/graphql'
 import { FolderBrowserInput } from '../../components/FolderBrowserInput'
 import { SettingsHeader } from '../../components/shared'
+import { DownloadScheduleSettings } from '../../components/settings'
 import { sanitizeError } from '../../lib/format'
-import { IconFolder, IconNetwork, IconGauge, IconTestPipe, IconAlertTriangle, IconCheck, IconX } from '@tabler/icons-react'
+import { IconFolder, IconNetwork, IconGauge, IconTestPipe, IconAlertTriangle, IconCheck, IconX, IconClock } from '@tabler/icons-react'
 
 export const Route = createFileRoute('/settings/torrent')({
   component: TorrentSettingsPage,
@@ -200,7 +201,7 @@ function TorrentSettingsPage() {
       if (result.data?.updateTorrentSettings.success) {
         addToast({
           title: 'Settings Saved',
-          description: 'Restart the server for changes to take effect.',
+          description: 'Speed limits apply now; restart the server for other changes to take effect.',
           color: 'success',
         })
       } else {
@@ -467,7 +468,7 @@ function TorrentSettingsPage() {
               label={`Download Limit: ${formatSpeed(downloadLimit)}`}
               labelPlacement="inside"
               variant="flat"
-              description="0 = unlimited"
+              description="0 = unlimited; schedule windows can override it"
               value={downloadLimit.toString()}
               onChange={(e) => setDownloadLimit(parseInt(e.target.value) || 0)}
               placeholder="0"
@@ -494,6 +495,21 @@ function TorrentSettingsPage() {
             />
           </div>
         </AccordionItem>
+
+        {/* Bandwidth Schedule Section */}
+        <AccordionItem
+          key="schedule"
+          aria-label="Bandwidth Schedule"
+          title={
+            <div className="flex items-center gap-2">
+              <IconClock size={18} className="text-purple-400" />
+              <span className="font-semibold">Bandwidth Schedule</span>
+            </div>
+          }
+          subtitle="Time windows that change torrent and usenet speed limits"
+        >
+          <DownloadScheduleSettings />
+        </AccordionItem>
       </Accordion>
     </div>
   )
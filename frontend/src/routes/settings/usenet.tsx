@@ -21,7 +21,7 @@ import {
   IconArrowDown,
 } from '@tabler/icons-react'
 import { graphqlClient } from '../../lib/graphql'
-import { sanitizeError } from '../../lib/format'
+import { formatBytes, sanitizeError } from '../../lib/format'
 import { InlineError } from '../../components/shared'
 
 export const Route = createFileRoute('/settings/usenet')({
@@ -50,18 +50,33 @@ interface UsenetServer {
   connections: number
   priority: number
   enabled: boolean
+  isBackup: boolean
   retentionDays: number | null
   lastSuccessAt: string | null
   lastError: string | null
   errorCount: number
 }
 
+interface UsenetServerUsage {
+  serverId: string
+  bytesDownloaded: number
+  articlesDownloaded: number
+  articlesMissing: number
+  articlesFailed: number
+}
+
 interface UsenetServerResult {
   success: boolean
   error: string | null
   server: UsenetServer | null
 }
 
+interface SabnzbdApiKey {
+  key: string | null
+  createdAt: string
+  lastUsedAt: string | null
+}
+
 // GraphQL Queries
 const USENET_SERVERS_QUERY = `
   query UsenetServers {
@@ -75,6 +90,7 @@ const USENET_SERVERS_QUERY = `
       connections
       priority
       enabled
+      isBackup
       retentionDays
       lastSuccessAt
       lastError
@@ -83,6 +99,21 @@ const USENET_SERVERS_QUERY = `
   }
 `
 
+const USENET_SERVER_USAGE_QUERY = `
+  query UsenetServerUsage($days: Int) {
+    usenetServerUsage(days: $days) {
+      serverId
+      bytesDownloaded
+      articlesDownloaded
+      articlesMissing
+      articlesFailed
+    }
+  }
+`
+
+// Period shown for server usage
+const USAGE_DAYS = 30
+
 const CREATE_USENET_SERVER_MUTATION = `
   mutation CreateUsenetServer($input: CreateUsenetServerInput!) {
     createUsenetServer(input: $input) {
@@ -98,6 +129,7 @@ const CREATE_USENET_SERVER_MUTATION = `
         connections
         priority
         enabled
+        isBackup
         retentionDays
         lastSuccessAt
         lastError
@@ -122,6 +154,7 @@ const UPDATE_USENET_SERVER_MUTATION = `
         connections
         priority
         enabled
+        isBackup
         retentionDays
         lastSuccessAt
         lastError
@@ -149,8 +182,52 @@ const REORDER_USENET_SERVERS_MUTATION = `
   }
 `
 
+const NZB_WATCH_FOLDER_QUERY = `
+  query NzbWatchFolder {
+    nzbWatchFolder
+  }
+`
+
+const SET_NZB_WATCH_FOLDER_MUTATION = `
+  mutation SetNzbWatchFolder($path: String) {
+    setNzbWatchFolder(path: $path) {
+      success
+      error
+    }
+  }
+`
+
+const SABNZBD_API_KEY_QUERY = `
+  query SabnzbdApiKey {
+    sabnzbdApiKey {
+      createdAt
+      lastUsedAt
+    }
+  }
+`
+
+const GENERATE_SABNZBD_API_KEY_MUTATION = `
+  mutation GenerateSabnzbdApiKey {
+    generateSabnzbdApiKey {
+      key
+      createdAt
+      lastUsedAt
+    }
+  }
+`
+
+const REVOKE_SABNZBD_API_KEY_MUTATION = `
+  mutation RevokeSabnzbdApiKey {
+    revokeSabnzbdApiKey {
+      success
+      error
+    }
+  }
+`
+
 function UsenetSettingsPage() {
   const [servers, setServers] = useState<UsenetServer[]>([])
+  const [usage, setUsage] = useState<Record<string, UsenetServerUsage>>({})
   const [loading, setLoading] = useState(true)
   const [error, setError] = useState<string | null>(null)
   
@@ -165,6 +242,7 @@ function UsenetSettingsPage() {
     username: '',
     password: '',
     connections: 10,
+    isBackup: false,
     retentionDays: null as number | null,
   })
   const [saving, setSaving] = useState(false)
@@ -183,6 +261,16 @@ function UsenetSettingsPage() {
       
       setServers(result.data?.usenetServers || [])
       setError(null)
+
+      // Usage is extra detail, the list works without it
+      const usageResult = await graphqlClient
+        .query<{ usenetServerUsage: UsenetServerUsage[] }>(USENET_SERVER_USAGE_QUERY, {
+          days: USAGE_DAYS,
+        })
+        .toPromise()
+      setUsage(
+        Object.fromEntries((usageResult.data?.usenetServerUsage || []).map((u) => [u.serverId, u]))
+      )
     } catch (err) {
       // Silently ignore auth errors - they can happen during login race conditions
       const errorMsg = err instanceof Error ? err.message : String(err);
@@ -212,6 +300,7 @@ function UsenetSettingsPage() {
       username: '',
       password: '',
       connections: 10,
+      isBackup: false,
       retentionDays: null,
     })
     onOpen()
@@ -228,6 +317,7 @@ function UsenetSettingsPage() {
       username: server.username || '',
       password: '',
       connections: server.connections,
+      isBackup: server.isBackup,
       retentionDays: server.retentionDays,
     })
     onOpen()
@@ -253,6 +343,7 @@ function UsenetSettingsPage() {
                 username: formData.username || undefined,
                 password: formData.password || undefined,
                 connections: formData.connections,
+                isBackup: formData.isBackup,
                 retentionDays: formData.retentionDays,
               },
             }
@@ -278,6 +369,7 @@ function UsenetSettingsPage() {
                 username: formData.username || undefined,
                 password: formData.password || undefined,
                 connections: formData.connections,
+                isBackup: formData.isBackup,
                 retentionDays: formData.retentionDays,
               },
             }
@@ -451,6 +543,13 @@ function UsenetSettingsPage() {
                         <IconLockOpen size={14} className="text-amber-500" />
                       </Tooltip>
                     )}
+                    {server.isBackup && (
+                      <Tooltip content="Only used for articles your other servers don't have">
+                        <Chip size="sm" variant="flat">
+                          Backup
+                        </Chip>
+                      </Tooltip>
+                    )}
                     {server.errorCount > 0 && (
                       <Chip color="danger" size="sm" variant="flat">
                         {server.errorCount} errors
@@ -461,6 +560,13 @@ function UsenetSettingsPage() {
                     {server.host}:{server.port} • {server.connections} connections
                     {server.retentionDays && ` • ${server.retentionDays} days retention`}
                   </div>
+                  {(usage[server.id]?.articlesDownloaded ?? 0) > 0 && (
+                    <div className="text-xs text-default-400">
+                      {formatBytes(usage[server.id].bytesDownloaded)} in the last {USAGE_DAYS} days
+                      {usage[server.id].articlesMissing > 0 &&
+                        ` • ${usage[server.id].articlesMissing.toLocaleString()} articles missing`}
+                    </div>
+                  )}
                   {server.lastError && (
                     <div className="text-xs text-danger mt-1 truncate">{server.lastError}</div>
                   )}
@@ -492,6 +598,10 @@ function UsenetSettingsPage() {
         </div>
       )}
 
+      <NzbWatchFolderCard />
+
+      <SabnzbdApiCard />
+
       {/* Add/Edit Modal */}
       <Modal isOpen={isOpen} onClose={onClose} size="lg">
         <ModalContent>
@@ -527,6 +637,17 @@ function UsenetSettingsPage() {
             >
               Use SSL/TLS
             </Switch>
+            <div>
+              <Switch
+                isSelected={formData.isBackup}
+                onValueChange={(v) => setFormData({ ...formData, isBackup: v })}
+              >
+                Backup server
+              </Switch>
+              <p className="text-xs text-default-400 mt-1">
+                Only used for articles your other servers don't have, e.g. a block account
+              </p>
+            </div>
             <Divider />
             <div className="flex gap-4">
               <Input
@@ -552,7 +673,7 @@ function UsenetSettingsPage() {
                 type="number"
                 value={formData.connections.toString()}
                 onChange={(e) => setFormData({ ...formData, connections: parseInt(e.target.value) || 10 })}
-                description="Number of simultaneous connections"
+                description="Maximum simultaneous connections, shared by all downloads"
                 className="flex-1"
               />
               <Input
@@ -566,7 +687,7 @@ function UsenetSettingsPage() {
                     retentionDays: e.target.value ? parseInt(e.target.value) : null,
                   })
                 }
-                description="How many days of articles the server keeps"
+                description="Search results older than your longest retention are skipped"
                 className="flex-1"
               />
             </div>
@@ -589,3 +710,173 @@ function UsenetSettingsPage() {
     </div>
   )
 }
+
+// Folder polled for .nzb files, e.g. one a browser saves NZBs into
+function NzbWatchFolderCard() {
+  const [path, setPath] = useState('')
+  const [saved, setSaved] = useState<string | null>(null)
+  const [saving, setSaving] = useState(false)
+
+  useEffect(() => {
+    graphqlClient
+      .query<{ nzbWatchFolder: string | null }>(NZB_WATCH_FOLDER_QUERY, {})
+      .toPromise()
+      .then((result) => {
+        const folder = result.data?.nzbWatchFolder ?? null
+        setSaved(folder)
+        setPath(folder ?? '')
+      })
+  }, [])
+
+  const handleSave = async () => {
+    try {
+      setSaving(true)
+      const folder = path.trim() || null
+      const result = await graphqlClient
+        .mutation<{ setNzbWatchFolder: { success: boolean; error: string | null } }>(
+          SET_NZB_WATCH_FOLDER_MUTATION,
+          { path: folder }
+        )
+        .toPromise()
+
+      if (result.error || !result.data?.setNzbWatchFolder.success) {
+        throw new Error(result.data?.setNzbWatchFolder.error || result.error?.message)
+      }
+
+      setSaved(folder)
+      addToast({ title: folder ? 'Watch folder saved' : 'Watch folder turned off', color: 'success' })
+    } catch (err) {
+      addToast({ title: 'Error saving watch folder', description: sanitizeError(err), color: 'danger' })
+    } finally {
+      setSaving(false)
+    }
+  }
+
+  return (
+    <Card>
+      <CardBody className="gap-3">
+        <div>
+          <h2 className="text-lg font-semibold">NZB Watch Folder</h2>
+          <p className="text-sm text-default-500">
+            .nzb files saved here are downloaded within a minute and then deleted. Files that can't be
+            added are renamed to <code>.nzb.failed</code>.
+          </p>
+        </div>
+        <Input
+          label="Folder"
+          placeholder="/downloads/nzb"
+          value={path}
+          onChange={(e) => setPath(e.target.value)}
+          description="Leave empty to turn the watch folder off"
+        />
+        <div className="flex gap-2">
+          <Button
+            color="primary"
+            variant="flat"
+            onPress={handleSave}
+            isLoading={saving}
+            isDisabled={(path.trim() || null) === saved}
+          >
+            Save
+          </Button>
+        </div>
+      </CardBody>
+    </Card>
+  )
+}
+
+// Key for Sonarr, Radarr and other apps that use Librarian as a SABnzbd client
+function SabnzbdApiCard() {
+  const [apiKey, setApiKey] = useState<SabnzbdApiKey | null>(null)
+  const [working, setWorking] = useState(false)
+
+  useEffect(() => {
+    graphqlClient
+      .query<{ sabnzbdApiKey: SabnzbdApiKey | null }>(SABNZBD_API_KEY_QUERY, {})
+      .toPromise()
+      .then((result) => setApiKey(result.data?.sabnzbdApiKey ?? null))
+  }, [])
+
+  const handleGenerate = async () => {
+    try {
+      setWorking(true)
+      const result = await graphqlClient
+        .mutation<{ generateSabnzbdApiKey: SabnzbdApiKey }>(GENERATE_SABNZBD_API_KEY_MUTATION, {})
+        .toPromise()
+
+      if (result.error || !result.data) {
+        throw new Error(result.error?.message)
+      }
+
+      setApiKey(result.data.generateSabnzbdApiKey)
+    } catch (err) {
+      addToast({ title: 'Error generating API key', description: sanitizeError(err), color: 'danger' })
+    } finally {
+      setWorking(false)
+    }
+  }
+
+  const handleRevoke = async () => {
+    try {
+      setWorking(true)
+      const result = await graphqlClient
+        .mutation<{ revokeSabnzbdApiKey: { success: boolean; error: string | null } }>(
+          REVOKE_SABNZBD_API_KEY_MUTATION,
+          {}
+        )
+        .toPromise()
+
+      if (result.error || !result.data?.revokeSabnzbdApiKey.success) {
+        throw new Error(result.data?.revokeSabnzbdApiKey.error || result.error?.message)
+      }
+
+      setApiKey(null)
+    } catch (err) {
+      addToast({ title: 'Error revoking API key', description: sanitizeError(err), color: 'danger' })
+    } finally {
+      setWorking(false)
+    }
+  }
+
+  return (
+    <Card>
+      <CardBody className="gap-3">
+        <div>
+          <h2 className="text-lg font-semibold">SABnzbd API</h2>
+          <p className="text-sm text-default-500">
+            Add Librarian to Sonarr or Radarr as a SABnzbd download client with URL base{' '}
+            <code>/api/sabnzbd</code>. Downloads they add are left for them to import.
+          </p>
+          <p className="text-sm text-default-500">
+            The key is also the password for the qBittorrent download client, URL base{' '}
+            <code>/api/qbittorrent</code>, which uses the built-in torrent client.
+          </p>
+        </div>
+        {apiKey?.key && (
+          <Input
+            label="API key"
+            value={apiKey.key}
+            isReadOnly
+            description="Copy it now, it won't be shown again"
+          />
+        )}
+        {apiKey && !apiKey.key && (
+          <p className="text-sm text-default-500">
+            Key created {new Date(apiKey.createdAt).toLocaleString()}
+            {apiKey.lastUsedAt && ` • last used ${new Date(apiKey.lastUsedAt).toLocaleString()}`}
+          </p>
+        )}
+        <div className="flex gap-2">
+          <Button color="primary" variant="flat" onPress={handleGenerate} isLoading={working}>
+            {apiKey ? 'Regenerate Key' : 'Generate Key'}
+          </Button>
+          {apiKey && (
+            <Button color="danger" variant="flat" onPress={handleRevoke} isDisabled={working}>
+              Revoke
+            </Button>
+          )}
+        </div>
+      </CardBody>
+    </Card>
+  )
+}
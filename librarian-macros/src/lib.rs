@@ -3,6 +3,7 @@
 //! This crate provides macros to reduce boilerplate in the Librarian backend:
 //!
 //! - `mutation_result!` - Generate GraphQL mutation result types
+//! - `job_result!` - Generate result types for mutations that run as background jobs
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -106,6 +107,71 @@ pub fn mutation_result(input: TokenStream) -> TokenStream {
     }
 }
 
+/// Generate a GraphQL result type for a mutation that enqueues a background job.
+///
+/// Heavy mutations return as soon as the job is recorded; clients follow it
+/// through the `backgroundJob` query or `backgroundJobUpdated` subscription
+/// using the returned job ID.
+///
+/// # Usage
+///
+/// ```ignore
+/// job_result!(ConsolidateLibraryJobResult);
+/// ```
+///
+/// # Generated Code
+///
+/// ```ignore
+/// #[derive(Debug, Clone, async_graphql::SimpleObject)]
+/// pub struct ConsolidateLibraryJobResult {
+///     pub success: bool,
+///     pub error: Option<String>,
+///     pub job_id: Option<String>,
+/// }
+///
+/// impl ConsolidateLibraryJobResult {
+///     pub fn queued(job_id: impl ToString) -> Self {
+///         Self { success: true, error: None, job_id: Some(job_id.to_string()) }
+///     }
+///     pub fn error(msg: impl Into<String>) -> Self {
+///         Self { success: false, error: Some(msg.into()), job_id: None }
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn job_result(input: TokenStream) -> TokenStream {
+    let struct_name = parse_macro_input!(input as Ident);
+
+    let output = quote! {
+        #[derive(Debug, Clone, async_graphql::SimpleObject)]
+        pub struct #struct_name {
+            pub success: bool,
+            pub error: Option<String>,
+            /// ID of the background job doing the work
+            pub job_id: Option<String>,
+        }
+
+        impl #struct_name {
+            pub fn queued(job_id: impl ToString) -> Self {
+                Self {
+                    success: true,
+                    error: None,
+                    job_id: Some(job_id.to_string()),
+                }
+            }
+
+            pub fn error(msg: impl Into<String>) -> Self {
+                Self {
+                    success: false,
+                    error: Some(msg.into()),
+                    job_id: None,
+                }
+            }
+        }
+    };
+    output.into()
+}
+
 /// Input for mutation_result! macro
 struct MutationResultInput {
     name: Ident,
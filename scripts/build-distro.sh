@@ -117,4 +117,8 @@ else
   fi
 fi
 
+# The self-updater refuses releases without checksums, so upload this with the archives
+log "Writing SHA256SUMS..."
+(cd "${DIST_DIR}" && find . -maxdepth 1 -type f -name "librarian-${VERSION}-*" -printf '%f\n' | sort | xargs -r sha256sum > SHA256SUMS)
+
 log "Distro build complete. Artifacts are in ${DIST_DIR}"